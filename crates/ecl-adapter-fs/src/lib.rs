@@ -2,7 +2,14 @@
 //!
 //! Provides `FilesystemAdapter`, which implements `SourceAdapter` by walking
 //! a local directory tree, applying extension and glob filters, and reading
-//! file contents with blake3 hashing.
+//! file contents with blake3 hashing. It also implements `PushSourceAdapter`
+//! for watching a drop folder (see the `watch` module).
+//!
+//! Relative directories — the watch `processed_dir` and `error_dir`, and
+//! lifecycle destinations such as `historical/` — are resolved against the
+//! source root. Files under the watch directories, and under the lifecycle
+//! directories given to `FilesystemAdapter::with_lifecycle`, are never
+//! enumerated or watched, so moved files are not picked up again.
 
 #![forbid(unsafe_code)]
 #![warn(missing_docs)]
@@ -11,6 +18,7 @@
 #![deny(clippy::panic)]
 
 mod error;
+mod watch;
 
 pub use error::FsAdapterError;
 
@@ -21,10 +29,12 @@ use futures::StreamExt;
 use glob::Pattern;
 use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::sync::{Mutex, Notify};
 use tracing::debug;
 
-use ecl_pipeline_spec::SourceSpec;
-use ecl_pipeline_spec::source::{FilesystemSourceSpec, FilterAction, FilterRule};
+use ecl_pipeline_spec::source::{FilesystemSourceSpec, FilterAction, FilterRule, FsWatchSpec};
+use ecl_pipeline_spec::{LifecycleSpec, SourceSpec};
 use ecl_pipeline_state::{Blake3Hash, ItemProvenance};
 use ecl_pipeline_topo::error::{ResolveError, SourceError};
 use ecl_pipeline_topo::{ExtractedDocument, SourceAdapter, SourceItem};
@@ -33,6 +43,7 @@ use ecl_pipeline_topo::{ExtractedDocument, SourceAdapter, SourceItem};
 ///
 /// Recursively walks a root directory, applying extension and glob filters
 /// during enumeration. Fetches file content and computes blake3 hashes.
+/// As a push source, watches the root and emits files once they settle.
 #[derive(Debug)]
 pub struct FilesystemAdapter {
     /// Root directory to scan.
@@ -43,10 +54,18 @@ pub struct FilesystemAdapter {
     filters: Vec<CompiledFilter>,
     /// Source name (for error reporting and provenance).
     source_name: String,
+    /// Watch-mode settings, with processed/error dirs resolved against the root.
+    watch: FsWatchSpec,
+    /// Lifecycle destinations, resolved against the root.
+    lifecycle_dirs: Vec<PathBuf>,
+    /// Shutdown signal for the watch task.
+    shutdown: Arc<Notify>,
+    /// Running watcher — set when `start()` is called.
+    watcher: Mutex<Option<watch::WatchHandle>>,
 }
 
 /// A compiled filter rule with a pre-parsed glob pattern.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 struct CompiledFilter {
     pattern: Pattern,
     action: FilterAction,
@@ -84,14 +103,76 @@ impl FilesystemAdapter {
     ) -> Result<Self, ResolveError> {
         let filters = compile_filters(&spec.filters)?;
 
+        let mut watch = spec.watch.clone().unwrap_or_default();
+        watch.processed_dir = watch.processed_dir.map(|dir| resolve_dir(&spec.root, &dir));
+        watch.error_dir = watch.error_dir.map(|dir| resolve_dir(&spec.root, &dir));
+
         Ok(Self {
             root: spec.root.clone(),
             extensions: spec.extensions.clone(),
             filters,
             source_name: source_name.to_string(),
+            watch,
+            lifecycle_dirs: Vec::new(),
+            shutdown: Arc::new(Notify::new()),
+            watcher: Mutex::new(None),
         })
     }
 
+    /// Skip the lifecycle's historical and error directories when scanning,
+    /// since relative lifecycle destinations sit inside the root.
+    pub fn with_lifecycle(mut self, lifecycle: &LifecycleSpec) -> Self {
+        self.lifecycle_dirs = [&lifecycle.historical_prefix, &lifecycle.error_prefix]
+            .into_iter()
+            .filter(|prefix| !prefix.trim_matches('/').is_empty())
+            .map(|prefix| resolve_dir(&self.root, Path::new(prefix)))
+            .collect();
+        self
+    }
+
+    /// A copy of this adapter's scan settings, without any watch state.
+    /// Used by the watch task, which outlives the `&self` borrow.
+    fn detached(&self) -> Self {
+        Self {
+            root: self.root.clone(),
+            extensions: self.extensions.clone(),
+            filters: self.filters.clone(),
+            source_name: self.source_name.clone(),
+            watch: self.watch.clone(),
+            lifecycle_dirs: self.lifecycle_dirs.clone(),
+            shutdown: self.shutdown.clone(),
+            watcher: Mutex::new(None),
+        }
+    }
+
+    /// Move a file (by its root-relative ID) to the same relative path under
+    /// `dest_dir`, creating directories as needed. Returns the new path.
+    async fn move_file(&self, item_id: &str, dest_dir: &Path) -> Result<PathBuf, SourceError> {
+        let from = self.root.join(item_id);
+        let to = dest_dir.join(item_id);
+        let moved = async {
+            if let Some(parent) = to.parent() {
+                tokio::fs::create_dir_all(parent).await?;
+            }
+            tokio::fs::rename(&from, &to).await
+        };
+        moved.await.map_err(|e| SourceError::Transient {
+            source_name: self.source_name.clone(),
+            message: format!("failed to move {} to {}: {e}", from.display(), to.display()),
+        })?;
+        Ok(to)
+    }
+
+    /// Whether `path` lies in a directory files are moved into: the watch
+    /// processed or error directory, or a lifecycle destination.
+    fn is_archived(&self, path: &Path) -> bool {
+        [&self.watch.processed_dir, &self.watch.error_dir]
+            .into_iter()
+            .flatten()
+            .chain(&self.lifecycle_dirs)
+            .any(|dir| path.starts_with(dir))
+    }
+
     /// Check whether a path passes the extension filter.
     fn matches_extension(&self, path: &Path) -> bool {
         if self.extensions.is_empty() {
@@ -149,6 +230,12 @@ impl FilesystemAdapter {
     }
 }
 
+/// Resolve a configured directory: relative paths are taken against the
+/// source root, absolute paths are used as given.
+fn resolve_dir(root: &Path, dir: &Path) -> PathBuf {
+    root.join(dir)
+}
+
 /// Compile filter rules into glob patterns.
 fn compile_filters(rules: &[FilterRule]) -> Result<Vec<CompiledFilter>, ResolveError> {
     rules
@@ -199,6 +286,11 @@ impl SourceAdapter for FilesystemAdapter {
                 continue;
             }
 
+            if self.is_archived(&path) {
+                debug!(path = %path.display(), "skipped: archived");
+                continue;
+            }
+
             // Apply extension filter
             if !self.matches_extension(&path) {
                 debug!(path = %path.display(), "skipped: extension filter");
@@ -283,6 +375,33 @@ impl SourceAdapter for FilesystemAdapter {
             content_hash,
         })
     }
+
+    async fn move_item(&self, item_id: &str, dest_dir: &str) -> Result<String, SourceError> {
+        let to = self
+            .move_file(item_id, &resolve_dir(&self.root, Path::new(dest_dir)))
+            .await?;
+        debug!(source = %self.source_name, item = %item_id, to = %to.display(), "lifecycle: moved file");
+        Ok(to.to_string_lossy().into_owned())
+    }
+
+    async fn delete_item(&self, item_id: &str) -> Result<(), SourceError> {
+        let path = self.root.join(item_id);
+        tokio::fs::remove_file(&path).await.map_err(|e| {
+            if e.kind() == std::io::ErrorKind::NotFound {
+                SourceError::NotFound {
+                    source_name: self.source_name.clone(),
+                    item_id: item_id.to_string(),
+                }
+            } else {
+                SourceError::Transient {
+                    source_name: self.source_name.clone(),
+                    message: format!("failed to delete {}: {e}", path.display()),
+                }
+            }
+        })?;
+        debug!(source = %self.source_name, item = %item_id, "lifecycle: deleted file");
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -298,6 +417,7 @@ mod tests {
             root: root.to_path_buf(),
             filters: vec![],
             extensions: vec![],
+            watch: None,
             stream: None,
         }
     }
@@ -323,10 +443,11 @@ mod tests {
             root: PathBuf::from("/tmp"),
             filters: vec![],
             extensions: vec![],
+            watch: None,
             stream: None,
         });
         let adapter = FilesystemAdapter::from_spec("local", &spec).unwrap();
-        assert_eq!(adapter.source_kind(), "filesystem");
+        assert_eq!(SourceAdapter::source_kind(&adapter), "filesystem");
         assert_eq!(adapter.root, PathBuf::from("/tmp"));
     }
 
@@ -349,6 +470,7 @@ mod tests {
             root: PathBuf::from("/tmp"),
             filters: vec![],
             extensions: vec!["md".to_string(), "txt".to_string()],
+            watch: None,
             stream: None,
         };
         let adapter = FilesystemAdapter::from_fs_spec("local", &spec).unwrap();
@@ -370,6 +492,7 @@ mod tests {
                 },
             ],
             extensions: vec![],
+            watch: None,
             stream: None,
         };
         let adapter = FilesystemAdapter::from_fs_spec("local", &spec).unwrap();
@@ -385,6 +508,7 @@ mod tests {
                 action: FilterAction::Include,
             }],
             extensions: vec![],
+            watch: None,
             stream: None,
         };
         let result = FilesystemAdapter::from_fs_spec("local", &spec);
@@ -400,6 +524,10 @@ mod tests {
             extensions: vec![],
             filters: vec![],
             source_name: "test".to_string(),
+            watch: FsWatchSpec::default(),
+            lifecycle_dirs: vec![],
+            shutdown: Arc::new(Notify::new()),
+            watcher: Mutex::new(None),
         };
         assert!(adapter.matches_extension(Path::new("file.md")));
         assert!(adapter.matches_extension(Path::new("file.rs")));
@@ -413,6 +541,10 @@ mod tests {
             extensions: vec!["md".to_string(), "txt".to_string()],
             filters: vec![],
             source_name: "test".to_string(),
+            watch: FsWatchSpec::default(),
+            lifecycle_dirs: vec![],
+            shutdown: Arc::new(Notify::new()),
+            watcher: Mutex::new(None),
         };
         assert!(adapter.matches_extension(Path::new("readme.md")));
         assert!(adapter.matches_extension(Path::new("notes.txt")));
@@ -427,6 +559,10 @@ mod tests {
             extensions: vec!["md".to_string()],
             filters: vec![],
             source_name: "test".to_string(),
+            watch: FsWatchSpec::default(),
+            lifecycle_dirs: vec![],
+            shutdown: Arc::new(Notify::new()),
+            watcher: Mutex::new(None),
         };
         assert!(adapter.matches_extension(Path::new("readme.MD")));
         assert!(adapter.matches_extension(Path::new("readme.Md")));
@@ -439,6 +575,10 @@ mod tests {
             extensions: vec![],
             filters: vec![],
             source_name: "test".to_string(),
+            watch: FsWatchSpec::default(),
+            lifecycle_dirs: vec![],
+            shutdown: Arc::new(Notify::new()),
+            watcher: Mutex::new(None),
         };
         assert!(adapter.passes_filters("any/path.md"));
     }
@@ -452,6 +592,7 @@ mod tests {
                 action: FilterAction::Exclude,
             }],
             extensions: vec![],
+            watch: None,
             stream: None,
         };
         let adapter = FilesystemAdapter::from_fs_spec("test", &spec).unwrap();
@@ -474,6 +615,7 @@ mod tests {
                 },
             ],
             extensions: vec![],
+            watch: None,
             stream: None,
         };
         let adapter = FilesystemAdapter::from_fs_spec("test", &spec).unwrap();
@@ -539,6 +681,7 @@ mod tests {
             root: tmp.path().to_path_buf(),
             filters: vec![],
             extensions: vec!["md".to_string()],
+            watch: None,
             stream: None,
         };
         let adapter = FilesystemAdapter::from_fs_spec("test", &spec).unwrap();
@@ -558,6 +701,7 @@ mod tests {
                 action: FilterAction::Exclude,
             }],
             extensions: vec![],
+            watch: None,
             stream: None,
         };
         let adapter = FilesystemAdapter::from_fs_spec("test", &spec).unwrap();
@@ -652,6 +796,64 @@ mod tests {
         assert!(matches!(result.unwrap_err(), SourceError::NotFound { .. }));
     }
 
+    // ── Lifecycle tests ────────────────────────────────────────────
+
+    #[tokio::test]
+    async fn test_move_item_resolves_dest_against_root() {
+        let tmp = TempDir::new().unwrap();
+        let root = tmp.path().join("staging");
+        fs::create_dir_all(root.join("sub")).unwrap();
+        fs::write(root.join("sub/a.csv"), "a").unwrap();
+        fs::write(root.join("b.csv"), "b").unwrap();
+        let lifecycle: LifecycleSpec = serde_json::from_value(serde_json::json!({})).unwrap();
+        let adapter = make_adapter(&root).with_lifecycle(&lifecycle);
+
+        let to = adapter
+            .move_item("sub/a.csv", "historical/run-1")
+            .await
+            .unwrap();
+        let expected = root.join("historical/run-1/sub/a.csv");
+        assert_eq!(Path::new(&to), expected);
+        assert!(expected.exists());
+        assert!(!root.join("sub/a.csv").exists());
+
+        // Moved files are not enumerated again.
+        let items = adapter.enumerate().await.unwrap();
+        let ids: Vec<&str> = items.iter().map(|i| i.id.as_str()).collect();
+        assert_eq!(ids, ["b.csv"]);
+    }
+
+    #[tokio::test]
+    async fn test_move_item_absolute_dest_and_missing_file() {
+        let tmp = TempDir::new().unwrap();
+        let root = tmp.path().join("in");
+        fs::create_dir_all(&root).unwrap();
+        fs::write(root.join("b.csv"), "b").unwrap();
+        let adapter = make_adapter(&root);
+
+        let dest = tmp.path().join("elsewhere");
+        let to = adapter
+            .move_item("b.csv", dest.to_str().unwrap())
+            .await
+            .unwrap();
+        assert_eq!(Path::new(&to), dest.join("b.csv"));
+
+        let err = adapter.move_item("b.csv", "error").await.unwrap_err();
+        assert!(matches!(err, SourceError::Transient { .. }));
+    }
+
+    #[tokio::test]
+    async fn test_delete_item() {
+        let tmp = TempDir::new().unwrap();
+        fs::write(tmp.path().join("c.csv"), "c").unwrap();
+        let adapter = make_adapter(tmp.path());
+
+        adapter.delete_item("c.csv").await.unwrap();
+        assert!(!tmp.path().join("c.csv").exists());
+        let err = adapter.delete_item("c.csv").await.unwrap_err();
+        assert!(matches!(err, SourceError::NotFound { .. }));
+    }
+
     // ── Relative path tests ────────────────────────────────────────
 
     #[test]
@@ -661,6 +863,10 @@ mod tests {
             extensions: vec![],
             filters: vec![],
             source_name: "test".to_string(),
+            watch: FsWatchSpec::default(),
+            lifecycle_dirs: vec![],
+            shutdown: Arc::new(Notify::new()),
+            watcher: Mutex::new(None),
         };
         assert_eq!(
             adapter.relative_path(Path::new("/data/root/sub/file.md")),
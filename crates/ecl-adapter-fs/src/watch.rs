@@ -0,0 +1,498 @@
+//! Directory watch mode: `FilesystemAdapter` as a push source.
+//!
+//! Change notifications are debounced per path by `notify-debouncer-mini`.
+//! Each candidate file is then polled until its size and modification time
+//! have stayed the same for the settle time, so files still being written
+//! are never read. Settled files are read with the same filters and
+//! provenance as a pull scan and sent through a bounded channel.
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures::StreamExt;
+use notify::{RecommendedWatcher, RecursiveMode};
+use notify_debouncer_mini::{DebounceEventResult, Debouncer, new_debouncer};
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tracing::{debug, warn};
+
+use ecl_pipeline_topo::error::SourceError;
+use ecl_pipeline_topo::{ExtractedDocument, PushSourceAdapter, SourceAdapter, SourceItem};
+
+use crate::FilesystemAdapter;
+
+/// A running directory watch. Dropping it stops the notifications.
+pub(crate) struct WatchHandle {
+    /// Keeps the OS watch alive.
+    _debouncer: Debouncer<RecommendedWatcher>,
+    /// The task settling and emitting files.
+    task: JoinHandle<()>,
+}
+
+impl std::fmt::Debug for WatchHandle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WatchHandle").finish_non_exhaustive()
+    }
+}
+
+/// What a file looked like when last checked: its length and mtime.
+type Signature = (u64, Option<SystemTime>);
+
+/// A file waiting to settle.
+struct Pending {
+    signature: Signature,
+    /// When the signature last changed.
+    since: Instant,
+}
+
+/// State of the watch task.
+struct WatchTask {
+    /// Scan settings for filtering and reading files.
+    source: FilesystemAdapter,
+    /// Settled documents go here.
+    sender: mpsc::Sender<ExtractedDocument>,
+    /// Files waiting to settle.
+    pending: HashMap<PathBuf, Pending>,
+    /// The signature of each file at the time it was emitted, so that
+    /// notifications that don't change a file (e.g. our own read) don't
+    /// emit it again.
+    emitted: HashMap<PathBuf, Signature>,
+    /// Directories already scanned. Each directory is walked once, when
+    /// first seen; later files in it raise their own events.
+    dirs: HashSet<PathBuf>,
+}
+
+impl FilesystemAdapter {
+    /// Whether a file at `path` is selected by the extension and glob filters.
+    fn selects(&self, path: &Path) -> bool {
+        !self.is_archived(path)
+            && self.matches_extension(path)
+            && self.passes_filters(&self.relative_path(path))
+    }
+}
+
+impl WatchTask {
+    /// Queue existing files (if configured), then settle and emit files
+    /// until shutdown or until the pipeline drops the receiver.
+    async fn run(mut self, mut events: mpsc::UnboundedReceiver<PathBuf>) {
+        let shutdown = self.source.shutdown.clone();
+        let settle = Duration::from_millis(self.source.watch.settle_ms);
+        let mut tick = tokio::time::interval((settle / 4).max(Duration::from_millis(10)));
+        tick.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+        if self.source.watch.process_existing {
+            match self.source.enumerate().await {
+                Ok(items) => {
+                    for item in items {
+                        let path = self.source.root.join(&item.path);
+                        self.queue(path).await;
+                    }
+                }
+                Err(e) => {
+                    warn!(source = %self.source.source_name, error = %e, "initial scan failed");
+                }
+            }
+        }
+
+        loop {
+            tokio::select! {
+                _ = shutdown.notified() => break,
+                event = events.recv() => match event {
+                    Some(path) => self.queue(path).await,
+                    None => break,
+                },
+                _ = tick.tick(), if !self.pending.is_empty() => {
+                    if !self.flush(settle).await {
+                        break;
+                    }
+                }
+            }
+        }
+        debug!(source = %self.source.source_name, "watch task stopped");
+    }
+
+    /// Start (or restart) the settle clock for a changed path.
+    async fn queue(&mut self, path: PathBuf) {
+        let Ok(metadata) = tokio::fs::metadata(&path).await else {
+            // Deleted or moved away; a file dropped again is new.
+            self.pending.remove(&path);
+            self.emitted.remove(&path);
+            self.dirs.remove(&path);
+            return;
+        };
+        if metadata.is_dir() {
+            // A directory moved in brings files that raise no events of
+            // their own.
+            if !self.source.is_archived(&path) && self.dirs.insert(path.clone()) {
+                self.queue_tree(&path).await;
+            }
+            return;
+        }
+        if !self.source.selects(&path) {
+            return;
+        }
+        let signature = (metadata.len(), metadata.modified().ok());
+        match self.pending.get(&path) {
+            Some(pending) if pending.signature == signature => {}
+            _ => {
+                self.pending.insert(
+                    path,
+                    Pending {
+                        signature,
+                        since: Instant::now(),
+                    },
+                );
+            }
+        }
+    }
+
+    /// Queue every selected file under `dir`.
+    async fn queue_tree(&mut self, dir: &Path) {
+        let mut walker = async_walkdir::WalkDir::new(dir);
+        while let Some(entry) = walker.next().await {
+            match entry {
+                Ok(entry) => {
+                    let path = entry.path();
+                    match entry.file_type().await {
+                        Ok(t) if t.is_dir() => {
+                            self.dirs.insert(path);
+                        }
+                        Ok(t) if t.is_file() => Box::pin(self.queue(path)).await,
+                        _ => {}
+                    }
+                }
+                Err(e) => {
+                    warn!(source = %self.source.source_name, error = %e, "directory walk error");
+                }
+            }
+        }
+    }
+
+    /// Emit every pending file whose signature has held for `settle`.
+    /// Returns false once the task should stop.
+    async fn flush(&mut self, settle: Duration) -> bool {
+        let mut ready = Vec::new();
+        let paths: Vec<PathBuf> = self.pending.keys().cloned().collect();
+        for path in paths {
+            let Ok(metadata) = tokio::fs::metadata(&path).await else {
+                self.pending.remove(&path);
+                continue;
+            };
+            let signature = (metadata.len(), metadata.modified().ok());
+            let Some(pending) = self.pending.get_mut(&path) else {
+                continue;
+            };
+            if pending.signature != signature {
+                pending.signature = signature;
+                pending.since = Instant::now();
+            } else if pending.since.elapsed() >= settle {
+                self.pending.remove(&path);
+                ready.push((path, signature));
+            }
+        }
+        ready.sort();
+
+        for (path, signature) in ready {
+            if self.emitted.get(&path) == Some(&signature) {
+                continue;
+            }
+            let Some(doc) = self.read(&path, signature).await else {
+                continue;
+            };
+            debug!(source = %self.source.source_name, item = %doc.id, "file settled");
+            tokio::select! {
+                sent = self.sender.send(doc) => {
+                    if sent.is_err() {
+                        return false;
+                    }
+                }
+                _ = self.source.shutdown.notified() => return false,
+            }
+            self.emitted.insert(path, signature);
+        }
+        true
+    }
+
+    /// Read a settled file into a document.
+    async fn read(&self, path: &Path, signature: Signature) -> Option<ExtractedDocument> {
+        let rel_path = self.source.relative_path(path);
+        let item = SourceItem {
+            id: rel_path.clone(),
+            display_name: path
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_else(|| rel_path.clone()),
+            mime_type: FilesystemAdapter::mime_from_extension(path),
+            path: rel_path,
+            modified_at: signature.1.map(DateTime::<Utc>::from),
+            source_hash: None,
+        };
+        match self.source.fetch(&item).await {
+            Ok(doc) => Some(doc),
+            Err(e) => {
+                warn!(source = %self.source.source_name, item = %item.id, error = %e, "failed to read settled file");
+                None
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl PushSourceAdapter for FilesystemAdapter {
+    fn source_kind(&self) -> &str {
+        "filesystem"
+    }
+
+    async fn start(&self) -> Result<mpsc::Receiver<ExtractedDocument>, SourceError> {
+        let mut watcher = self.watcher.lock().await;
+        if watcher.is_some() {
+            return Err(SourceError::Permanent {
+                source_name: self.source_name.clone(),
+                message: "adapter already started".to_string(),
+            });
+        }
+
+        // The debouncer calls back on its own thread; hand paths to the
+        // async task through an unbounded channel.
+        let (event_tx, event_rx) = mpsc::unbounded_channel();
+        let source_name = self.source_name.clone();
+        let mut debouncer = new_debouncer(
+            Duration::from_millis(self.watch.debounce_ms),
+            move |result: DebounceEventResult| match result {
+                Ok(events) => {
+                    for event in events {
+                        let _ = event_tx.send(event.path);
+                    }
+                }
+                Err(e) => warn!(source = %source_name, error = %e, "watch error"),
+            },
+        )
+        .map_err(|e| SourceError::Permanent {
+            source_name: self.source_name.clone(),
+            message: format!("failed to create watcher: {e}"),
+        })?;
+        debouncer
+            .watcher()
+            .watch(&self.root, RecursiveMode::Recursive)
+            .map_err(|e| SourceError::Permanent {
+                source_name: self.source_name.clone(),
+                message: format!("failed to watch {}: {e}", self.root.display()),
+            })?;
+
+        let (sender, receiver) = mpsc::channel(self.watch.channel_capacity.max(1));
+        let task = WatchTask {
+            source: self.detached(),
+            sender,
+            pending: HashMap::new(),
+            emitted: HashMap::new(),
+            dirs: HashSet::from([self.root.clone()]),
+        };
+        let task = tokio::spawn(task.run(event_rx));
+
+        *watcher = Some(WatchHandle {
+            _debouncer: debouncer,
+            task,
+        });
+        Ok(receiver)
+    }
+
+    async fn shutdown(&self) -> Result<(), SourceError> {
+        self.shutdown.notify_one();
+        if let Some(handle) = self.watcher.lock().await.take()
+            && let Err(e) = handle.task.await
+        {
+            warn!(source = %self.source_name, "watch task join error: {e}");
+        }
+        Ok(())
+    }
+
+    /// Move the file into the processed or error directory, if configured.
+    /// Its path relative to the root is kept.
+    async fn complete(&self, item_id: &str, succeeded: bool) -> Result<(), SourceError> {
+        let dir = if succeeded {
+            &self.watch.processed_dir
+        } else {
+            &self.watch.error_dir
+        };
+        let Some(dir) = dir else {
+            return Ok(());
+        };
+
+        let to = self.move_file(item_id, dir).await?;
+        debug!(source = %self.source_name, item = %item_id, to = %to.display(), "moved completed file");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use ecl_pipeline_spec::source::{FilesystemSourceSpec, FsWatchSpec};
+    use std::fs;
+    use tempfile::TempDir;
+
+    const WAIT: Duration = Duration::from_secs(10);
+
+    fn make_watch_adapter(
+        root: &Path,
+        extensions: &[&str],
+        watch: FsWatchSpec,
+    ) -> FilesystemAdapter {
+        let spec = FilesystemSourceSpec {
+            root: root.to_path_buf(),
+            filters: vec![],
+            extensions: extensions.iter().map(|e| e.to_string()).collect(),
+            watch: Some(watch),
+            stream: None,
+        };
+        FilesystemAdapter::from_fs_spec("drop", &spec).unwrap()
+    }
+
+    fn fast_watch() -> FsWatchSpec {
+        FsWatchSpec {
+            debounce_ms: 50,
+            settle_ms: 100,
+            ..FsWatchSpec::default()
+        }
+    }
+
+    async fn next_doc(rx: &mut mpsc::Receiver<ExtractedDocument>) -> ExtractedDocument {
+        tokio::time::timeout(WAIT, rx.recv())
+            .await
+            .unwrap()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_watch_emits_new_files() {
+        let dir = TempDir::new().unwrap();
+        let adapter = make_watch_adapter(
+            dir.path(),
+            &[],
+            FsWatchSpec {
+                process_existing: false,
+                ..fast_watch()
+            },
+        );
+        fs::write(dir.path().join("old.txt"), "already here").unwrap();
+
+        let mut rx = adapter.start().await.unwrap();
+        fs::create_dir(dir.path().join("in")).unwrap();
+        fs::write(dir.path().join("in/new.txt"), "fresh").unwrap();
+
+        let doc = next_doc(&mut rx).await;
+        assert_eq!(doc.id, "in/new.txt");
+        assert_eq!(doc.display_name, "new.txt");
+        assert_eq!(doc.content, b"fresh");
+        assert_eq!(doc.mime_type, "text/plain");
+        assert_eq!(doc.provenance.source_kind, "filesystem");
+
+        // Reading the file raises notifications but must not re-emit it.
+        let again = tokio::time::timeout(Duration::from_millis(500), rx.recv()).await;
+        assert!(again.is_err());
+
+        adapter.shutdown().await.unwrap();
+        assert!(rx.recv().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_watch_process_existing_applies_filters() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("a.csv"), "id\n1\n").unwrap();
+        fs::write(dir.path().join("b.txt"), "skip me").unwrap();
+        let adapter = make_watch_adapter(dir.path(), &["csv"], fast_watch());
+
+        let mut rx = adapter.start().await.unwrap();
+        assert_eq!(next_doc(&mut rx).await.id, "a.csv");
+
+        fs::write(dir.path().join("c.txt"), "also skipped").unwrap();
+        fs::write(dir.path().join("d.csv"), "id\n2\n").unwrap();
+        assert_eq!(next_doc(&mut rx).await.id, "d.csv");
+
+        adapter.shutdown().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_watch_waits_for_file_to_settle() {
+        let dir = TempDir::new().unwrap();
+        let adapter = make_watch_adapter(
+            dir.path(),
+            &[],
+            FsWatchSpec {
+                settle_ms: 600,
+                ..fast_watch()
+            },
+        );
+        let mut rx = adapter.start().await.unwrap();
+
+        let path = dir.path().join("upload.bin");
+        fs::write(&path, b"part one,").unwrap();
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        let mut content = fs::read(&path).unwrap();
+        content.extend_from_slice(b" part two");
+        fs::write(&path, &content).unwrap();
+
+        let doc = next_doc(&mut rx).await;
+        assert_eq!(doc.content, b"part one, part two");
+
+        adapter.shutdown().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_complete_moves_to_processed_and_error_dirs() {
+        let dir = TempDir::new().unwrap();
+        fs::create_dir(dir.path().join("sub")).unwrap();
+        fs::write(dir.path().join("sub/good.csv"), "ok").unwrap();
+        fs::write(dir.path().join("bad.csv"), "not ok").unwrap();
+        let adapter = make_watch_adapter(
+            dir.path(),
+            &[],
+            FsWatchSpec {
+                processed_dir: Some(PathBuf::from("processed")),
+                error_dir: Some(PathBuf::from("error")),
+                ..fast_watch()
+            },
+        );
+
+        adapter.complete("sub/good.csv", true).await.unwrap();
+        adapter.complete("bad.csv", false).await.unwrap();
+
+        assert!(!dir.path().join("sub/good.csv").exists());
+        assert!(dir.path().join("processed/sub/good.csv").exists());
+        assert!(dir.path().join("error/bad.csv").exists());
+
+        // Archived files are not picked up by the watch.
+        let mut rx = adapter.start().await.unwrap();
+        let nothing = tokio::time::timeout(Duration::from_millis(500), rx.recv()).await;
+        assert!(nothing.is_err());
+        adapter.shutdown().await.unwrap();
+
+        let missing = adapter.complete("gone.csv", true).await.unwrap_err();
+        assert!(matches!(missing, SourceError::Transient { .. }));
+    }
+
+    #[tokio::test]
+    async fn test_complete_without_dirs_leaves_file() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("a.txt"), "stay").unwrap();
+        let adapter = make_watch_adapter(dir.path(), &[], fast_watch());
+
+        adapter.complete("a.txt", true).await.unwrap();
+        adapter.complete("a.txt", false).await.unwrap();
+        assert!(dir.path().join("a.txt").exists());
+    }
+
+    #[tokio::test]
+    async fn test_start_twice_fails() {
+        let dir = TempDir::new().unwrap();
+        let adapter = make_watch_adapter(dir.path(), &[], fast_watch());
+        let _rx = adapter.start().await.unwrap();
+        let err = adapter.start().await.unwrap_err();
+        assert!(err.to_string().contains("already started"));
+        adapter.shutdown().await.unwrap();
+    }
+}
@@ -4,10 +4,12 @@
 //! - **Service account** (`CredentialRef::File`): JWT assertion → token endpoint
 //! - **Environment variable** (`CredentialRef::EnvVar`): raw bearer token from env
 //! - **Application Default Credentials** (`CredentialRef::ApplicationDefault`):
-//!   checks `GOOGLE_APPLICATION_CREDENTIALS` env var, then well-known gcloud path
+//!   checks `GOOGLE_APPLICATION_CREDENTIALS` env var, then well-known gcloud path,
+//!   then the metadata server
 
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Duration;
 
 use chrono::{DateTime, Utc};
 use tokio::sync::RwLock;
@@ -17,10 +19,13 @@ use ecl_pipeline_spec::CredentialRef;
 
 use crate::error::GcsAdapterError;
 use crate::types::{
-    AuthorizedUserCredentials, GCS_READONLY_SCOPE, GOOGLE_TOKEN_URL, ServiceAccountKey,
-    TokenResponse,
+    AuthorizedUserCredentials, GCS_READONLY_SCOPE, GOOGLE_TOKEN_URL, METADATA_TOKEN_URL,
+    ServiceAccountKey, TokenResponse,
 };
 
+/// How long to wait for the metadata server, which is absent off GCP.
+const METADATA_TIMEOUT: Duration = Duration::from_secs(2);
+
 /// Manages OAuth2 tokens for GCS API access.
 ///
 /// Caches tokens and refreshes them before expiry. Thread-safe via `RwLock`.
@@ -31,6 +36,8 @@ pub struct TokenProvider {
     cached: Arc<RwLock<Option<CachedToken>>>,
     /// Override token endpoint URL (for testing).
     token_url_override: Option<String>,
+    /// Override metadata server token URL (for testing).
+    metadata_url_override: Option<String>,
     /// OAuth2 scope to request. Defaults to GCS read-only.
     scope: String,
 }
@@ -60,6 +67,7 @@ impl TokenProvider {
             http_client,
             cached: Arc::new(RwLock::new(None)),
             token_url_override: None,
+            metadata_url_override: None,
             scope: GCS_READONLY_SCOPE.to_string(),
         }
     }
@@ -76,6 +84,7 @@ impl TokenProvider {
             http_client: reqwest::Client::new(),
             cached: Arc::new(RwLock::new(Some(cached))),
             token_url_override: None,
+            metadata_url_override: None,
             scope: GCS_READONLY_SCOPE.to_string(),
         }
     }
@@ -86,6 +95,12 @@ impl TokenProvider {
         self
     }
 
+    /// Override the metadata server token URL (for testing with wiremock).
+    pub fn with_metadata_url(mut self, url: String) -> Self {
+        self.metadata_url_override = Some(url);
+        self
+    }
+
     /// Override the OAuth2 scope (e.g., for read-write access in sinks).
     pub fn with_scope(mut self, scope: String) -> Self {
         self.scope = scope;
@@ -229,6 +244,7 @@ impl TokenProvider {
     /// Checks in order:
     /// 1. `GOOGLE_APPLICATION_CREDENTIALS` env var (path to key file)
     /// 2. Well-known gcloud path (`~/.config/gcloud/application_default_credentials.json`)
+    /// 3. The metadata server (GCE, GKE, Cloud Run)
     async fn adc_flow(&self) -> Result<CachedToken, GcsAdapterError> {
         if let Ok(cred_path) = std::env::var("GOOGLE_APPLICATION_CREDENTIALS") {
             let path = PathBuf::from(&cred_path);
@@ -243,6 +259,14 @@ impl TokenProvider {
             return self.resolve_credential_file(&path).await;
         }
 
+        match self.metadata_server_flow().await {
+            Ok(token) => {
+                debug!("using the metadata server for GCS");
+                return Ok(token);
+            }
+            Err(e) => debug!(error = %e, "metadata server unavailable"),
+        }
+
         Err(GcsAdapterError::Auth {
             message:
                 "no Application Default Credentials found: set GOOGLE_APPLICATION_CREDENTIALS \
@@ -251,6 +275,39 @@ impl TokenProvider {
         })
     }
 
+    /// Metadata server flow: the token of the attached service account.
+    async fn metadata_server_flow(&self) -> Result<CachedToken, GcsAdapterError> {
+        let url = self
+            .metadata_url_override
+            .as_deref()
+            .unwrap_or(METADATA_TOKEN_URL);
+
+        let response = self
+            .http_client
+            .get(url)
+            .header("Metadata-Flavor", "Google")
+            .timeout(METADATA_TIMEOUT)
+            .send()
+            .await?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(GcsAdapterError::ApiError {
+                status: status.as_u16(),
+                message: body,
+            });
+        }
+
+        let token_resp: TokenResponse = response.json().await?;
+        let expires_in = token_resp.expires_in.unwrap_or(3600);
+
+        Ok(CachedToken {
+            access_token: token_resp.access_token,
+            expires_at: Utc::now() + chrono::Duration::seconds(expires_in as i64),
+        })
+    }
+
     /// Detect credential type from file content and obtain a token.
     async fn resolve_credential_file(
         &self,
@@ -461,6 +518,31 @@ mod tests {
         assert!(msg.contains("400"));
     }
 
+    #[tokio::test]
+    async fn test_metadata_server_flow_wiremock() {
+        let mock_server = wiremock::MockServer::start().await;
+
+        wiremock::Mock::given(wiremock::matchers::method("GET"))
+            .and(wiremock::matchers::path("/token"))
+            .and(wiremock::matchers::header("Metadata-Flavor", "Google"))
+            .respond_with(
+                wiremock::ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                    "access_token": "ya29.gcs-metadata",
+                    "expires_in": 1800,
+                    "token_type": "Bearer"
+                })),
+            )
+            .mount(&mock_server)
+            .await;
+
+        let provider =
+            TokenProvider::new(CredentialRef::ApplicationDefault, reqwest::Client::new())
+                .with_metadata_url(format!("{}/token", mock_server.uri()));
+
+        let result = provider.metadata_server_flow().await.unwrap();
+        assert_eq!(result.access_token, "ya29.gcs-metadata");
+    }
+
     #[tokio::test]
     async fn test_refresh_token_flow_wiremock() {
         let mock_server = wiremock::MockServer::start().await;
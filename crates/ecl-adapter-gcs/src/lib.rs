@@ -409,6 +409,7 @@ mod tests {
             root: std::path::PathBuf::from("/tmp"),
             filters: vec![],
             extensions: vec![],
+            watch: None,
             stream: None,
         });
         let result = GcsAdapter::from_spec("test", &fs_spec);
@@ -20,6 +20,11 @@ pub const GCS_UPLOAD_BASE_URL: &str = "https://storage.googleapis.com/upload/sto
 /// Google OAuth2 token endpoint.
 pub const GOOGLE_TOKEN_URL: &str = "https://oauth2.googleapis.com/token";
 
+/// Metadata server endpoint for the attached service account's token
+/// (GCE, GKE, Cloud Run).
+pub const METADATA_TOKEN_URL: &str =
+    "http://metadata.google.internal/computeMetadata/v1/instance/service-accounts/default/token";
+
 /// Response from the GCS Objects.list API endpoint.
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct ObjectListResponse {
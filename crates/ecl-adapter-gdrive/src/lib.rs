@@ -562,6 +562,7 @@ mod tests {
             root: std::path::PathBuf::from("/tmp"),
             filters: vec![],
             extensions: vec![],
+            watch: None,
             stream: None,
         });
         let result = GoogleDriveAdapter::from_spec("drive", &spec);
@@ -0,0 +1,310 @@
+//! Request authentication for HTTP sources.
+//!
+//! Supports bearer tokens, HTTP Basic, API-key headers, and the OAuth2
+//! client credentials grant. Secrets are resolved once through the
+//! pipeline's `SecretResolver` (or from a file / environment variable) and
+//! cached; OAuth2 access tokens are cached until shortly before expiry.
+
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use tokio::sync::{OnceCell, RwLock};
+use tracing::debug;
+
+use ecl_pipeline_spec::{CredentialRef, HttpAuth};
+use ecl_secrets::SecretResolver;
+
+/// Applies the configured authentication to outgoing requests.
+#[derive(Debug)]
+pub struct Authenticator {
+    auth: HttpAuth,
+    resolver: Arc<dyn SecretResolver>,
+    http_client: reqwest::Client,
+    /// The resolved secret of the configured auth scheme.
+    secret: OnceCell<String>,
+    /// Cached OAuth2 access token.
+    token: RwLock<Option<CachedToken>>,
+}
+
+/// An OAuth2 access token with expiry tracking.
+#[derive(Debug, Clone)]
+struct CachedToken {
+    access_token: String,
+    expires_at: DateTime<Utc>,
+}
+
+/// OAuth2 token endpoint response.
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    #[serde(default)]
+    expires_in: Option<i64>,
+}
+
+impl Authenticator {
+    /// Create an authenticator for the given auth configuration.
+    pub fn new(
+        auth: HttpAuth,
+        resolver: Arc<dyn SecretResolver>,
+        http_client: reqwest::Client,
+    ) -> Self {
+        Self {
+            auth,
+            resolver,
+            http_client,
+            secret: OnceCell::new(),
+            token: RwLock::new(None),
+        }
+    }
+
+    /// Add authentication to a request.
+    ///
+    /// # Errors
+    ///
+    /// Returns a message if a credential cannot be resolved or the OAuth2
+    /// token request fails.
+    pub async fn apply(
+        &self,
+        request: reqwest::RequestBuilder,
+    ) -> Result<reqwest::RequestBuilder, String> {
+        Ok(match &self.auth {
+            HttpAuth::None => request,
+            HttpAuth::Bearer { token } => request.bearer_auth(self.secret(token).await?),
+            HttpAuth::Basic { username, password } => {
+                request.basic_auth(username, Some(self.secret(password).await?))
+            }
+            HttpAuth::Header { name, value } => {
+                request.header(name.as_str(), self.secret(value).await?)
+            }
+            HttpAuth::OAuth2ClientCredentials { .. } => {
+                request.bearer_auth(self.access_token().await?)
+            }
+        })
+    }
+
+    /// Resolve (once) the secret behind a credential reference.
+    async fn secret(&self, cred: &CredentialRef) -> Result<&str, String> {
+        self.secret
+            .get_or_try_init(|| resolve_credential(cred, self.resolver.as_ref()))
+            .await
+            .map(String::as_str)
+    }
+
+    /// Get a valid OAuth2 access token, requesting a new one if necessary.
+    async fn access_token(&self) -> Result<String, String> {
+        {
+            let cached = self.token.read().await;
+            if let Some(token) = cached.as_ref()
+                && token.expires_at > Utc::now() + chrono::Duration::seconds(30)
+            {
+                return Ok(token.access_token.clone());
+            }
+        }
+
+        let HttpAuth::OAuth2ClientCredentials {
+            token_url,
+            client_id,
+            client_secret,
+            scopes,
+        } = &self.auth
+        else {
+            return Err("auth is not oauth2_client_credentials".to_string());
+        };
+        let client_secret = self.secret(client_secret).await?;
+
+        debug!(token_url = %token_url, "requesting OAuth2 access token");
+        let mut form = vec![
+            ("grant_type", "client_credentials".to_string()),
+            ("client_id", client_id.clone()),
+            ("client_secret", client_secret.to_string()),
+        ];
+        if !scopes.is_empty() {
+            form.push(("scope", scopes.join(" ")));
+        }
+        let response = self
+            .http_client
+            .post(token_url)
+            .form(&form)
+            .send()
+            .await
+            .map_err(|e| format!("token request failed: {e}"))?;
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(format!("token endpoint returned {status}: {body}"));
+        }
+        let token: TokenResponse = response
+            .json()
+            .await
+            .map_err(|e| format!("failed to parse token response: {e}"))?;
+
+        let cached = CachedToken {
+            access_token: token.access_token,
+            expires_at: Utc::now() + chrono::Duration::seconds(token.expires_in.unwrap_or(3600)),
+        };
+        let access_token = cached.access_token.clone();
+        *self.token.write().await = Some(cached);
+        Ok(access_token)
+    }
+}
+
+/// Resolve a `CredentialRef` to a string secret.
+async fn resolve_credential(
+    cred: &CredentialRef,
+    resolver: &dyn SecretResolver,
+) -> Result<String, String> {
+    let value = match cred {
+        CredentialRef::Secret { name } => resolver
+            .resolve_string(name)
+            .await
+            .map_err(|e| e.to_string())?,
+        CredentialRef::File { path } => tokio::fs::read_to_string(path)
+            .await
+            .map_err(|e| format!("failed to read credential file '{}': {e}", path.display()))?,
+        CredentialRef::EnvVar { env } => {
+            std::env::var(env).map_err(|_| format!("environment variable '{env}' not set"))?
+        }
+        CredentialRef::ApplicationDefault => {
+            return Err(
+                "application default credentials are not supported for HTTP sources".to_string(),
+            );
+        }
+    };
+    Ok(value.trim().to_string())
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use ecl_secrets::SecretError;
+    use wiremock::matchers::{body_string_contains, header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[derive(Debug)]
+    struct MapResolver;
+
+    #[async_trait::async_trait]
+    impl SecretResolver for MapResolver {
+        async fn resolve(&self, name: &str) -> Result<Vec<u8>, SecretError> {
+            match name {
+                "api-key" => Ok(b"k-123\n".to_vec()),
+                "client-secret" => Ok(b"shh".to_vec()),
+                _ => Err(SecretError::NotFound {
+                    name: name.to_string(),
+                }),
+            }
+        }
+    }
+
+    fn authenticator(auth: HttpAuth) -> Authenticator {
+        Authenticator::new(auth, Arc::new(MapResolver), reqwest::Client::new())
+    }
+
+    fn secret(name: &str) -> CredentialRef {
+        CredentialRef::Secret {
+            name: name.to_string(),
+        }
+    }
+
+    async fn applied_headers(auth: &Authenticator) -> reqwest::header::HeaderMap {
+        let request = reqwest::Client::new().get("http://localhost/");
+        auth.apply(request)
+            .await
+            .unwrap()
+            .build()
+            .unwrap()
+            .headers()
+            .clone()
+    }
+
+    #[tokio::test]
+    async fn test_static_schemes_set_headers() {
+        let bearer = authenticator(HttpAuth::Bearer {
+            token: secret("api-key"),
+        });
+        assert_eq!(
+            applied_headers(&bearer).await["authorization"],
+            "Bearer k-123"
+        );
+
+        let basic = authenticator(HttpAuth::Basic {
+            username: "ecl".to_string(),
+            password: secret("api-key"),
+        });
+        // base64("ecl:k-123")
+        assert_eq!(
+            applied_headers(&basic).await["authorization"],
+            "Basic ZWNsOmstMTIz"
+        );
+
+        let header = authenticator(HttpAuth::Header {
+            name: "X-Api-Key".to_string(),
+            value: secret("api-key"),
+        });
+        assert_eq!(applied_headers(&header).await["x-api-key"], "k-123");
+    }
+
+    #[tokio::test]
+    async fn test_missing_secret_is_error() {
+        let auth = authenticator(HttpAuth::Bearer {
+            token: secret("nope"),
+        });
+        let request = reqwest::Client::new().get("http://localhost/");
+        let err = auth.apply(request).await.unwrap_err();
+        assert!(err.contains("nope"), "unexpected error: {err}");
+    }
+
+    #[tokio::test]
+    async fn test_oauth2_client_credentials_token_is_cached() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/token"))
+            .and(body_string_contains("grant_type=client_credentials"))
+            .and(body_string_contains("client_secret=shh"))
+            .and(body_string_contains("scope=read+write"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "access_token": "tok-1",
+                "token_type": "Bearer",
+                "expires_in": 3600
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let auth = authenticator(HttpAuth::OAuth2ClientCredentials {
+            token_url: format!("{}/token", server.uri()),
+            client_id: "ecl".to_string(),
+            client_secret: secret("client-secret"),
+            scopes: vec!["read".to_string(), "write".to_string()],
+        });
+        for _ in 0..2 {
+            assert_eq!(
+                applied_headers(&auth).await["authorization"],
+                "Bearer tok-1"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_oauth2_token_endpoint_error() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/token"))
+            .and(header("content-type", "application/x-www-form-urlencoded"))
+            .respond_with(ResponseTemplate::new(401).set_body_string("bad client"))
+            .mount(&server)
+            .await;
+
+        let auth = authenticator(HttpAuth::OAuth2ClientCredentials {
+            token_url: format!("{}/token", server.uri()),
+            client_id: "ecl".to_string(),
+            client_secret: secret("client-secret"),
+            scopes: vec![],
+        });
+        let request = reqwest::Client::new().get("http://localhost/");
+        let err = auth.apply(request).await.unwrap_err();
+        assert!(err.contains("401"), "unexpected error: {err}");
+    }
+}
@@ -0,0 +1,934 @@
+//! Generic HTTP/REST source adapter for the ECL pipeline runner.
+//!
+//! Provides `HttpAdapter`, which implements `SourceAdapter` for any JSON
+//! listing API described declaratively by a `kind = "http"` source: it
+//! authenticates, pages through the listing endpoint, selects item objects
+//! with JSONPath, and either emits each object as-is or fetches its content
+//! from a per-item URL template.
+
+#![forbid(unsafe_code)]
+#![warn(missing_docs)]
+#![deny(clippy::unwrap_used)]
+#![warn(clippy::expect_used)]
+#![deny(clippy::panic)]
+
+pub mod auth;
+pub mod pagination;
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde_json_path::JsonPath;
+use tokio::sync::{Mutex, RwLock};
+use tokio::time::Instant;
+use tracing::{debug, warn};
+
+use ecl_pipeline_spec::{HttpSourceSpec, SourceSpec};
+use ecl_pipeline_state::{Blake3Hash, ItemProvenance};
+use ecl_pipeline_topo::error::{ResolveError, SourceError};
+use ecl_pipeline_topo::{ExtractedDocument, SourceAdapter, SourceItem};
+use ecl_secrets::SecretResolver;
+
+use crate::auth::Authenticator;
+use crate::pagination::Pager;
+
+/// MIME type of items emitted straight from the listing.
+const MIME_JSON: &str = "application/json";
+
+/// Generic HTTP/REST source adapter.
+///
+/// Enumerates items from a paginated JSON listing endpoint. Items are
+/// sorted by ID for deterministic ordering.
+#[derive(Debug)]
+pub struct HttpAdapter {
+    /// Source name (for error reporting and provenance).
+    source_name: String,
+    /// Source configuration.
+    spec: HttpSourceSpec,
+    /// HTTP client for API calls.
+    http_client: reqwest::Client,
+    /// Applies the configured authentication.
+    authenticator: Authenticator,
+    /// Compiled `items` JSONPath.
+    items_path: JsonPath,
+    /// Parsed `fetch_url` template.
+    fetch_template: Option<Vec<TemplatePart>>,
+    /// Request pacing, if `requests_per_second` is set.
+    rate_limiter: Option<RateLimiter>,
+    /// Item objects from the last listing, by ID, when there is no
+    /// `fetch_url` and the object itself is the content.
+    listed: RwLock<BTreeMap<String, Vec<u8>>>,
+}
+
+/// A piece of a parsed URL template.
+#[derive(Debug, Clone, PartialEq)]
+enum TemplatePart {
+    Literal(String),
+    /// `{id}` or a dotted path into the item object.
+    Field(String),
+}
+
+/// Spaces requests at least `interval` apart.
+#[derive(Debug)]
+struct RateLimiter {
+    interval: Duration,
+    next: Mutex<Instant>,
+}
+
+impl RateLimiter {
+    fn new(requests_per_second: f64) -> Self {
+        Self {
+            interval: Duration::from_secs_f64(1.0 / requests_per_second),
+            next: Mutex::new(Instant::now()),
+        }
+    }
+
+    /// Wait until the next request may be sent.
+    async fn wait(&self) {
+        let mut next = self.next.lock().await;
+        let now = Instant::now();
+        if *next > now {
+            tokio::time::sleep_until(*next).await;
+        }
+        *next = (*next).max(now) + self.interval;
+    }
+}
+
+impl HttpAdapter {
+    /// Create a new adapter from a `SourceSpec`.
+    ///
+    /// `resolver` resolves `CredentialRef::Secret` credentials.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ResolveError::UnknownAdapter` if the spec is not an HTTP source.
+    /// Returns `ResolveError::Io` if the spec is invalid.
+    pub fn from_spec(
+        source_name: &str,
+        spec: &SourceSpec,
+        resolver: Arc<dyn SecretResolver>,
+    ) -> Result<Self, ResolveError> {
+        let http_spec = match spec {
+            SourceSpec::Http(hs) => hs,
+            _ => {
+                return Err(ResolveError::UnknownAdapter {
+                    stage: source_name.to_string(),
+                    adapter: "http".to_string(),
+                });
+            }
+        };
+
+        Self::from_http_spec(source_name, http_spec, resolver)
+    }
+
+    /// Create a new adapter directly from an `HttpSourceSpec`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ResolveError::Io` if a JSONPath or URL template does not
+    /// parse, or `requests_per_second` is not positive.
+    pub fn from_http_spec(
+        source_name: &str,
+        spec: &HttpSourceSpec,
+        resolver: Arc<dyn SecretResolver>,
+    ) -> Result<Self, ResolveError> {
+        let invalid = |message: String| {
+            ResolveError::Io(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!("http source '{source_name}': {message}"),
+            ))
+        };
+
+        let items = spec.items.as_deref().unwrap_or("$[*]");
+        let items_path = JsonPath::parse(items)
+            .map_err(|e| invalid(format!("bad items path '{items}': {e}")))?;
+        Pager::new(&spec.pagination, &spec.url, Vec::new()).map_err(invalid)?;
+        let fetch_template = spec
+            .fetch_url
+            .as_deref()
+            .map(parse_template)
+            .transpose()
+            .map_err(|e| invalid(format!("bad fetch_url: {e}")))?;
+        let rate_limiter = match spec.requests_per_second {
+            Some(rps) if rps.is_finite() && rps > 0.0 => Some(RateLimiter::new(rps)),
+            Some(rps) => {
+                return Err(invalid(format!(
+                    "requests_per_second must be positive, got {rps}"
+                )));
+            }
+            None => None,
+        };
+
+        let http_client = reqwest::Client::new();
+        let authenticator = Authenticator::new(spec.auth.clone(), resolver, http_client.clone());
+
+        Ok(Self {
+            source_name: source_name.to_string(),
+            spec: spec.clone(),
+            http_client,
+            authenticator,
+            items_path,
+            fetch_template,
+            rate_limiter,
+            listed: RwLock::new(BTreeMap::new()),
+        })
+    }
+
+    /// Send a request with pacing, headers and authentication applied,
+    /// mapping error statuses to `SourceError`.
+    ///
+    /// `item_id` is set for item fetches, so a 404 becomes `NotFound`.
+    async fn send(
+        &self,
+        mut request: reqwest::RequestBuilder,
+        item_id: Option<&str>,
+    ) -> Result<reqwest::Response, SourceError> {
+        for (name, value) in &self.spec.headers {
+            request = request.header(name.as_str(), value.as_str());
+        }
+        let request =
+            self.authenticator
+                .apply(request)
+                .await
+                .map_err(|message| SourceError::AuthError {
+                    source_name: self.source_name.clone(),
+                    message,
+                })?;
+        if let Some(limiter) = &self.rate_limiter {
+            limiter.wait().await;
+        }
+
+        let response = request.send().await.map_err(|e| SourceError::Transient {
+            source_name: self.source_name.clone(),
+            message: format!("HTTP request failed: {e}"),
+        })?;
+
+        let status = response.status();
+
+        if status.as_u16() == 401 || status.as_u16() == 403 {
+            return Err(SourceError::AuthError {
+                source_name: self.source_name.clone(),
+                message: format!("HTTP API returned {status}"),
+            });
+        }
+
+        if status.as_u16() == 429 {
+            let retry_after = response
+                .headers()
+                .get("retry-after")
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(60);
+            return Err(SourceError::RateLimited {
+                source_name: self.source_name.clone(),
+                retry_after_secs: retry_after,
+            });
+        }
+
+        if status.as_u16() == 404
+            && let Some(item_id) = item_id
+        {
+            return Err(SourceError::NotFound {
+                source_name: self.source_name.clone(),
+                item_id: item_id.to_string(),
+            });
+        }
+
+        if status.is_server_error() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(SourceError::Transient {
+                source_name: self.source_name.clone(),
+                message: format!("HTTP API error ({status}): {body}"),
+            });
+        }
+
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(SourceError::Permanent {
+                source_name: self.source_name.clone(),
+                message: format!("HTTP API error ({status}): {body}"),
+            });
+        }
+
+        Ok(response)
+    }
+
+    /// Page through the listing endpoint, collecting the selected items.
+    async fn list(&self) -> Result<Vec<serde_json::Value>, SourceError> {
+        let base_query: Vec<(String, String)> = self
+            .spec
+            .query
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect();
+        let mut pager =
+            Pager::new(&self.spec.pagination, &self.spec.url, base_query).map_err(|message| {
+                SourceError::Permanent {
+                    source_name: self.source_name.clone(),
+                    message,
+                }
+            })?;
+        let mut items = Vec::new();
+
+        for page in 0..self.spec.max_pages {
+            let (url, query) = pager.request();
+            let response = self
+                .send(self.http_client.get(&url).query(&query), None)
+                .await?;
+            let response_url = response.url().clone();
+            let link = response
+                .headers()
+                .get("link")
+                .and_then(|v| v.to_str().ok())
+                .map(str::to_string);
+            let body: serde_json::Value =
+                response.json().await.map_err(|e| SourceError::Permanent {
+                    source_name: self.source_name.clone(),
+                    message: format!("failed to parse listing response: {e}"),
+                })?;
+
+            let selected = self.items_path.query(&body).all();
+            let count = selected.len();
+            items.extend(selected.into_iter().cloned());
+            debug!(page, count, url = %response_url, "listed page");
+
+            if !pager.advance(&response_url, &body, count, link.as_deref()) {
+                return Ok(items);
+            }
+        }
+
+        warn!(
+            source = %self.source_name,
+            max_pages = self.spec.max_pages,
+            "stopped listing at max_pages"
+        );
+        Ok(items)
+    }
+
+    /// List the items. Without a `fetch_url`, also returns each item's
+    /// object, by ID, as its content.
+    async fn list_items(
+        &self,
+    ) -> Result<(Vec<SourceItem>, BTreeMap<String, Vec<u8>>), SourceError> {
+        let values = self.list().await?;
+
+        let mut items = Vec::with_capacity(values.len());
+        let mut listed = BTreeMap::new();
+        for value in &values {
+            let item = self.source_item(value)?;
+            if self.fetch_template.is_none() {
+                listed.insert(
+                    item.id.clone(),
+                    serde_json::to_vec(value).unwrap_or_default(),
+                );
+            }
+            items.push(item);
+        }
+        Ok((items, listed))
+    }
+
+    /// The listed object for `id`. A resumed run skips `enumerate`, so the
+    /// endpoint is listed again when no objects are held.
+    async fn listed_record(&self, id: &str) -> Result<Option<Vec<u8>>, SourceError> {
+        if let Some(record) = self.listed.read().await.get(id) {
+            return Ok(Some(record.clone()));
+        }
+        let mut listed = self.listed.write().await;
+        if listed.is_empty() {
+            *listed = self.list_items().await?.1;
+        }
+        Ok(listed.get(id).cloned())
+    }
+
+    /// Build the `SourceItem` for one listed object.
+    fn source_item(&self, value: &serde_json::Value) -> Result<SourceItem, SourceError> {
+        let id =
+            field_string(value, &self.spec.id_field).ok_or_else(|| SourceError::Permanent {
+                source_name: self.source_name.clone(),
+                message: format!("listed item has no '{}' field: {value}", self.spec.id_field),
+            })?;
+        let display_name = self
+            .spec
+            .name_field
+            .as_deref()
+            .and_then(|field| field_string(value, field))
+            .unwrap_or_else(|| id.clone());
+        let modified_at = self
+            .spec
+            .modified_field
+            .as_deref()
+            .and_then(|field| field_string(value, field))
+            .and_then(|t| t.parse::<DateTime<Utc>>().ok());
+        let (path, mime_type) = match &self.fetch_template {
+            Some(template) => {
+                let url = render_template(template, &id, value).map_err(|message| {
+                    SourceError::Permanent {
+                        source_name: self.source_name.clone(),
+                        message: format!("item '{id}': {message}"),
+                    }
+                })?;
+                (url, "application/octet-stream".to_string())
+            }
+            None => (id.clone(), MIME_JSON.to_string()),
+        };
+        let content = serde_json::to_vec(value).unwrap_or_default();
+
+        Ok(SourceItem {
+            id,
+            display_name,
+            mime_type,
+            path,
+            modified_at,
+            source_hash: Some(blake3::hash(&content).to_hex().to_string()),
+        })
+    }
+}
+
+/// Parse a URL template with `{field}` placeholders.
+fn parse_template(template: &str) -> Result<Vec<TemplatePart>, String> {
+    let mut parts = Vec::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        if start > 0 {
+            parts.push(TemplatePart::Literal(rest[..start].to_string()));
+        }
+        let end = rest[start..]
+            .find('}')
+            .ok_or_else(|| format!("unclosed placeholder in '{template}'"))?;
+        let field = &rest[start + 1..start + end];
+        if field.is_empty() {
+            return Err(format!("empty placeholder in '{template}'"));
+        }
+        parts.push(TemplatePart::Field(field.to_string()));
+        rest = &rest[start + end + 1..];
+    }
+    if !rest.is_empty() {
+        parts.push(TemplatePart::Literal(rest.to_string()));
+    }
+    Ok(parts)
+}
+
+/// Render a URL template for one item. Substituted values are
+/// percent-encoded, so they cannot change the URL's structure.
+fn render_template(
+    parts: &[TemplatePart],
+    id: &str,
+    item: &serde_json::Value,
+) -> Result<String, String> {
+    let mut url = String::new();
+    for part in parts {
+        match part {
+            TemplatePart::Literal(text) => url.push_str(text),
+            TemplatePart::Field(field) => {
+                let value = if field == "id" {
+                    id.to_string()
+                } else {
+                    field_string(item, field)
+                        .ok_or_else(|| format!("no value for '{{{field}}}' in fetch_url"))?
+                };
+                url.push_str(&percent_encode(&value));
+            }
+        }
+    }
+    Ok(url)
+}
+
+/// Look up a dotted path in an object and render scalars as strings.
+fn field_string(value: &serde_json::Value, path: &str) -> Option<String> {
+    let found = path
+        .split('.')
+        .try_fold(value, |value, key| value.get(key))?;
+    match found {
+        serde_json::Value::String(s) => Some(s.clone()),
+        serde_json::Value::Number(n) => Some(n.to_string()),
+        serde_json::Value::Bool(b) => Some(b.to_string()),
+        _ => None,
+    }
+}
+
+/// Percent-encode everything except RFC 3986 unreserved characters.
+fn percent_encode(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~') {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    encoded
+}
+
+#[async_trait]
+impl SourceAdapter for HttpAdapter {
+    fn source_kind(&self) -> &str {
+        "http"
+    }
+
+    async fn enumerate(&self) -> Result<Vec<SourceItem>, SourceError> {
+        let (mut items, listed) = self.list_items().await?;
+        *self.listed.write().await = listed;
+
+        // Sort by ID for deterministic ordering.
+        items.sort_by(|a, b| a.id.cmp(&b.id));
+        Ok(items)
+    }
+
+    async fn fetch(&self, item: &SourceItem) -> Result<ExtractedDocument, SourceError> {
+        let (content, mime_type, url) =
+            if self.fetch_template.is_some() {
+                let response = self
+                    .send(self.http_client.get(&item.path), Some(&item.id))
+                    .await?;
+                let mime_type = response
+                    .headers()
+                    .get("content-type")
+                    .and_then(|v| v.to_str().ok())
+                    .and_then(|v| v.split(';').next())
+                    .map(|v| v.trim().to_string())
+                    .unwrap_or_else(|| item.mime_type.clone());
+                let content = response.bytes().await.map(|b| b.to_vec()).map_err(|e| {
+                    SourceError::Transient {
+                        source_name: self.source_name.clone(),
+                        message: format!("failed to read response body: {e}"),
+                    }
+                })?;
+                (content, mime_type, item.path.clone())
+            } else {
+                let content =
+                    self.listed_record(&item.id)
+                        .await?
+                        .ok_or_else(|| SourceError::NotFound {
+                            source_name: self.source_name.clone(),
+                            item_id: item.id.clone(),
+                        })?;
+                (content, MIME_JSON.to_string(), self.spec.url.clone())
+            };
+
+        let content_hash = Blake3Hash::new(blake3::hash(&content).to_hex().as_str());
+
+        let mut prov_metadata = BTreeMap::new();
+        prov_metadata.insert(
+            "item_id".to_string(),
+            serde_json::Value::String(item.id.clone()),
+        );
+        prov_metadata.insert("url".to_string(), serde_json::Value::String(url));
+
+        let provenance = ItemProvenance {
+            source_kind: "http".to_string(),
+            metadata: prov_metadata,
+            source_modified: item.modified_at,
+            extracted_at: Utc::now(),
+        };
+
+        Ok(ExtractedDocument {
+            id: item.id.clone(),
+            display_name: item.display_name.clone(),
+            content,
+            mime_type,
+            provenance,
+            content_hash,
+        })
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use ecl_pipeline_spec::{CredentialRef, HttpAuth, HttpPagination};
+    use ecl_secrets::SecretError;
+    use serde_json::json;
+    use wiremock::matchers::{header, method, path, query_param, query_param_is_missing};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[derive(Debug)]
+    struct TestResolver;
+
+    #[async_trait]
+    impl SecretResolver for TestResolver {
+        async fn resolve(&self, name: &str) -> Result<Vec<u8>, SecretError> {
+            match name {
+                "token" => Ok(b"test-token".to_vec()),
+                _ => Err(SecretError::NotFound {
+                    name: name.to_string(),
+                }),
+            }
+        }
+    }
+
+    fn make_spec(url: &str) -> HttpSourceSpec {
+        serde_json::from_value(json!({ "url": url })).unwrap()
+    }
+
+    fn make_adapter(spec: &HttpSourceSpec) -> HttpAdapter {
+        HttpAdapter::from_http_spec("api", spec, Arc::new(TestResolver)).unwrap()
+    }
+
+    // ── Construction tests ─────────────────────────────────────────
+
+    #[test]
+    fn test_from_spec_wrong_kind_returns_error() {
+        let spec = SourceSpec::Filesystem(ecl_pipeline_spec::FilesystemSourceSpec {
+            root: std::path::PathBuf::from("/tmp"),
+            filters: vec![],
+            extensions: vec![],
+            watch: None,
+            stream: None,
+        });
+        let result = HttpAdapter::from_spec("api", &spec, Arc::new(TestResolver));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_from_spec_rejects_invalid_config() {
+        let resolver: Arc<dyn SecretResolver> = Arc::new(TestResolver);
+        let mut spec = make_spec("http://unused");
+        spec.items = Some("$[[".to_string());
+        assert!(HttpAdapter::from_http_spec("api", &spec, resolver.clone()).is_err());
+
+        let mut spec = make_spec("http://unused");
+        spec.fetch_url = Some("http://x/{id".to_string());
+        assert!(HttpAdapter::from_http_spec("api", &spec, resolver.clone()).is_err());
+
+        let mut spec = make_spec("http://unused");
+        spec.requests_per_second = Some(0.0);
+        assert!(HttpAdapter::from_http_spec("api", &spec, resolver).is_err());
+    }
+
+    #[test]
+    fn test_render_template_encodes_values() {
+        let parts = parse_template("https://x/{id}/files/{meta.name}?v=1").unwrap();
+        let url = render_template(&parts, "a/b", &json!({"meta": {"name": "q 1"}})).unwrap();
+        assert_eq!(url, "https://x/a%2Fb/files/q%201?v=1");
+        assert!(render_template(&parts, "a", &json!({})).is_err());
+    }
+
+    // ── Enumerate tests (wiremock) ─────────────────────────────────
+
+    #[tokio::test]
+    async fn test_enumerate_items_path_with_auth_and_fetch_listed() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/tickets"))
+            .and(header("authorization", "Bearer test-token"))
+            .and(header("accept", "application/json"))
+            .and(query_param("status", "open"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "data": [
+                    {"id": 2, "title": "Second", "updated": "2026-03-02T00:00:00Z"},
+                    {"id": 1, "title": "First", "updated": "2026-03-01T00:00:00Z"}
+                ]
+            })))
+            .mount(&server)
+            .await;
+
+        let mut spec = make_spec(&format!("{}/tickets", server.uri()));
+        spec.items = Some("$.data[*]".to_string());
+        spec.name_field = Some("title".to_string());
+        spec.modified_field = Some("updated".to_string());
+        spec.query.insert("status".to_string(), "open".to_string());
+        spec.headers
+            .insert("Accept".to_string(), "application/json".to_string());
+        spec.auth = HttpAuth::Bearer {
+            token: CredentialRef::Secret {
+                name: "token".to_string(),
+            },
+        };
+        let adapter = make_adapter(&spec);
+
+        let items = adapter.enumerate().await.unwrap();
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].id, "1");
+        assert_eq!(items[0].display_name, "First");
+        assert!(items[0].modified_at.is_some());
+        assert!(items[0].source_hash.is_some());
+
+        let doc = adapter.fetch(&items[1]).await.unwrap();
+        assert_eq!(doc.mime_type, "application/json");
+        let body: serde_json::Value = serde_json::from_slice(&doc.content).unwrap();
+        assert_eq!(body["title"], json!("Second"));
+        assert_eq!(doc.provenance.source_kind, "http");
+    }
+
+    #[tokio::test]
+    async fn test_enumerate_cursor_pagination() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/items"))
+            .and(query_param_is_missing("after"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "results": [{"id": "a"}, {"id": "b"}],
+                "next": "cur-2"
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/items"))
+            .and(query_param("after", "cur-2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "results": [{"id": "c"}],
+                "next": null
+            })))
+            .mount(&server)
+            .await;
+
+        let mut spec = make_spec(&format!("{}/items", server.uri()));
+        spec.items = Some("$.results[*]".to_string());
+        spec.pagination = HttpPagination::Cursor {
+            cursor_path: "$.next".to_string(),
+            cursor_param: "after".to_string(),
+        };
+        let items = make_adapter(&spec).enumerate().await.unwrap();
+        let ids: Vec<_> = items.iter().map(|i| i.id.as_str()).collect();
+        assert_eq!(ids, vec!["a", "b", "c"]);
+    }
+
+    #[tokio::test]
+    async fn test_enumerate_page_number_pagination() {
+        let server = MockServer::start().await;
+        for (page, body) in [
+            ("1", json!([{"id": "a"}, {"id": "b"}])),
+            ("2", json!([{"id": "c"}])),
+            ("3", json!([])),
+        ] {
+            Mock::given(method("GET"))
+                .and(path("/items"))
+                .and(query_param("page", page))
+                .respond_with(ResponseTemplate::new(200).set_body_json(body))
+                .expect(1)
+                .mount(&server)
+                .await;
+        }
+
+        let mut spec = make_spec(&format!("{}/items", server.uri()));
+        spec.pagination = HttpPagination::PageNumber {
+            page_param: "page".to_string(),
+            first_page: 1,
+            size_param: None,
+            page_size: None,
+        };
+        let items = make_adapter(&spec).enumerate().await.unwrap();
+        assert_eq!(items.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_enumerate_link_header_pagination() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/items"))
+            .and(query_param_is_missing("page"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("link", r#"</items?page=2>; rel="next""#)
+                    .set_body_json(json!([{"id": "a"}])),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/items"))
+            .and(query_param("page", "2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([{"id": "b"}])))
+            .mount(&server)
+            .await;
+
+        let mut spec = make_spec(&format!("{}/items", server.uri()));
+        spec.pagination = HttpPagination::LinkHeader;
+        let items = make_adapter(&spec).enumerate().await.unwrap();
+        assert_eq!(items.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_enumerate_offset_pagination_and_max_pages() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/items"))
+            .and(query_param("limit", "2"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(json!([{"id": "x"}, {"id": "y"}])),
+            )
+            .expect(3)
+            .mount(&server)
+            .await;
+
+        let mut spec = make_spec(&format!("{}/items", server.uri()));
+        spec.pagination = HttpPagination::Offset {
+            offset_param: "offset".to_string(),
+            limit_param: "limit".to_string(),
+            limit: 2,
+        };
+        spec.max_pages = 3;
+        let items = make_adapter(&spec).enumerate().await.unwrap();
+        assert_eq!(items.len(), 6);
+    }
+
+    #[tokio::test]
+    async fn test_enumerate_missing_id_is_error() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([{"name": "x"}])))
+            .mount(&server)
+            .await;
+
+        let spec = make_spec(&server.uri());
+        let result = make_adapter(&spec).enumerate().await;
+        assert!(matches!(result, Err(SourceError::Permanent { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_enumerate_error_statuses() {
+        for (status, check) in [
+            (401, "auth"),
+            (429, "rate"),
+            (503, "transient"),
+            (400, "permanent"),
+        ] {
+            let server = MockServer::start().await;
+            Mock::given(method("GET"))
+                .respond_with(ResponseTemplate::new(status).insert_header("retry-after", "7"))
+                .mount(&server)
+                .await;
+            let result = make_adapter(&make_spec(&server.uri())).enumerate().await;
+            let matched = match (check, result) {
+                ("auth", Err(SourceError::AuthError { .. })) => true,
+                (
+                    "rate",
+                    Err(SourceError::RateLimited {
+                        retry_after_secs, ..
+                    }),
+                ) => retry_after_secs == 7,
+                ("transient", Err(SourceError::Transient { .. })) => true,
+                ("permanent", Err(SourceError::Permanent { .. })) => true,
+                _ => false,
+            };
+            assert!(matched, "status {status} not mapped to {check}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_enumerate_respects_rate_limit() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([{"id": "a"}])))
+            .mount(&server)
+            .await;
+
+        let mut spec = make_spec(&server.uri());
+        spec.pagination = HttpPagination::PageNumber {
+            page_param: "page".to_string(),
+            first_page: 1,
+            size_param: None,
+            page_size: None,
+        };
+        spec.max_pages = 3;
+        spec.requests_per_second = Some(20.0);
+        let started = std::time::Instant::now();
+        make_adapter(&spec).enumerate().await.unwrap();
+        // Three requests at 20/s are at least two 50ms intervals apart.
+        assert!(started.elapsed() >= Duration::from_millis(100));
+    }
+
+    // ── Fetch tests (wiremock) ─────────────────────────────────────
+
+    #[tokio::test]
+    async fn test_fetch_url_template() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/files"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+                {"id": "doc 1", "name": "report.csv"}
+            ])))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/files/doc%201/content"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_raw("a,b\n1,2\n", "text/csv; charset=utf-8"),
+            )
+            .mount(&server)
+            .await;
+
+        let mut spec = make_spec(&format!("{}/files", server.uri()));
+        spec.name_field = Some("name".to_string());
+        spec.fetch_url = Some(format!("{}/files/{{id}}/content", server.uri()));
+        let adapter = make_adapter(&spec);
+
+        let items = adapter.enumerate().await.unwrap();
+        assert_eq!(
+            items[0].path,
+            format!("{}/files/doc%201/content", server.uri())
+        );
+        let doc = adapter.fetch(&items[0]).await.unwrap();
+        assert_eq!(doc.display_name, "report.csv");
+        assert_eq!(doc.mime_type, "text/csv");
+        assert_eq!(doc.content, b"a,b\n1,2\n");
+        assert_eq!(doc.provenance.metadata["url"], json!(items[0].path));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_not_found() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&server)
+            .await;
+
+        let mut spec = make_spec(&server.uri());
+        spec.fetch_url = Some(format!("{}/files/{{id}}", server.uri()));
+        let adapter = make_adapter(&spec);
+        let item = SourceItem {
+            id: "gone".to_string(),
+            display_name: "gone".to_string(),
+            mime_type: "application/octet-stream".to_string(),
+            path: format!("{}/files/gone", server.uri()),
+            modified_at: None,
+            source_hash: None,
+        };
+        let result = adapter.fetch(&item).await;
+        assert!(matches!(result, Err(SourceError::NotFound { .. })));
+
+        // Without fetch_url, items not in the listing are not found.
+        let listing = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([{"id": 1}])))
+            .mount(&listing)
+            .await;
+        let adapter = make_adapter(&make_spec(&listing.uri()));
+        let result = adapter.fetch(&item).await;
+        assert!(matches!(result, Err(SourceError::NotFound { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_listed_after_resume_relists() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/tickets"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+                {"id": 1, "title": "First"},
+                {"id": 2, "title": "Second"}
+            ])))
+            .expect(2)
+            .mount(&server)
+            .await;
+        let spec = make_spec(&format!("{}/tickets", server.uri()));
+        let items = make_adapter(&spec).enumerate().await.unwrap();
+
+        // A resumed run uses a fresh adapter and skips enumeration; the
+        // endpoint is listed once more, not once per item.
+        let resumed = make_adapter(&spec);
+        for (item, title) in items.iter().zip(["First", "Second"]) {
+            let doc = resumed.fetch(item).await.unwrap();
+            let body: serde_json::Value = serde_json::from_slice(&doc.content).unwrap();
+            assert_eq!(body["title"], json!(title));
+        }
+    }
+
+    #[test]
+    fn test_source_kind() {
+        let adapter = make_adapter(&make_spec("http://unused"));
+        assert_eq!(adapter.source_kind(), "http");
+    }
+}
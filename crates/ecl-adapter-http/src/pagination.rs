@@ -0,0 +1,269 @@
+//! Pagination strategies for HTTP listing endpoints.
+//!
+//! A [`Pager`] knows which URL and query parameters to request next and,
+//! given a response, whether another page follows.
+
+use serde_json_path::JsonPath;
+
+use ecl_pipeline_spec::HttpPagination;
+
+/// Walks the pages of a listing endpoint.
+#[derive(Debug)]
+pub struct Pager {
+    strategy: HttpPagination,
+    /// Compiled `cursor_path` for cursor pagination.
+    cursor_path: Option<JsonPath>,
+    base_url: String,
+    base_query: Vec<(String, String)>,
+    /// Cursor for the next cursor-paginated request.
+    cursor: Option<String>,
+    /// Page number or offset of the next request.
+    position: u64,
+    /// URL of the next page from a `Link` header.
+    next_url: Option<String>,
+}
+
+impl Pager {
+    /// Create a pager starting at the first page.
+    ///
+    /// # Errors
+    ///
+    /// Returns a message if the cursor JSONPath does not parse.
+    pub fn new(
+        strategy: &HttpPagination,
+        base_url: &str,
+        base_query: Vec<(String, String)>,
+    ) -> Result<Self, String> {
+        let cursor_path = match strategy {
+            HttpPagination::Cursor { cursor_path, .. } => Some(
+                JsonPath::parse(cursor_path)
+                    .map_err(|e| format!("bad cursor_path '{cursor_path}': {e}"))?,
+            ),
+            _ => None,
+        };
+        let position = match strategy {
+            HttpPagination::PageNumber { first_page, .. } => *first_page,
+            _ => 0,
+        };
+        Ok(Self {
+            strategy: strategy.clone(),
+            cursor_path,
+            base_url: base_url.to_string(),
+            base_query,
+            cursor: None,
+            position,
+            next_url: None,
+        })
+    }
+
+    /// The URL and query parameters of the current page.
+    pub fn request(&self) -> (String, Vec<(String, String)>) {
+        if let Some(next) = &self.next_url {
+            // A `Link` URL already carries every parameter it needs.
+            return (next.clone(), Vec::new());
+        }
+        let mut query = self.base_query.clone();
+        match &self.strategy {
+            HttpPagination::None | HttpPagination::LinkHeader => {}
+            HttpPagination::Cursor { cursor_param, .. } => {
+                if let Some(cursor) = &self.cursor {
+                    query.push((cursor_param.clone(), cursor.clone()));
+                }
+            }
+            HttpPagination::PageNumber {
+                page_param,
+                size_param,
+                page_size,
+                ..
+            } => {
+                query.push((page_param.clone(), self.position.to_string()));
+                if let (Some(param), Some(size)) = (size_param, page_size) {
+                    query.push((param.clone(), size.to_string()));
+                }
+            }
+            HttpPagination::Offset {
+                offset_param,
+                limit_param,
+                limit,
+            } => {
+                query.push((offset_param.clone(), self.position.to_string()));
+                query.push((limit_param.clone(), limit.to_string()));
+            }
+        }
+        (self.base_url.clone(), query)
+    }
+
+    /// Move to the next page. Returns `false` when the listing is done.
+    ///
+    /// `url` is the URL the response came from, `body` its parsed JSON,
+    /// `item_count` the number of items it held, and `link` its `Link`
+    /// header, if any.
+    pub fn advance(
+        &mut self,
+        url: &reqwest::Url,
+        body: &serde_json::Value,
+        item_count: usize,
+        link: Option<&str>,
+    ) -> bool {
+        match &self.strategy {
+            HttpPagination::None => false,
+            HttpPagination::Cursor { .. } => {
+                let next = self
+                    .cursor_path
+                    .as_ref()
+                    .and_then(|path| path.query(body).first())
+                    .and_then(|value| match value {
+                        serde_json::Value::String(s) if !s.is_empty() => Some(s.clone()),
+                        serde_json::Value::Number(n) => Some(n.to_string()),
+                        _ => None,
+                    });
+                // A repeated cursor would loop forever.
+                if next.is_none() || next == self.cursor {
+                    return false;
+                }
+                self.cursor = next;
+                true
+            }
+            HttpPagination::PageNumber { page_size, .. } => {
+                if item_count == 0 || page_size.is_some_and(|size| (item_count as u64) < size) {
+                    return false;
+                }
+                self.position += 1;
+                true
+            }
+            HttpPagination::LinkHeader => {
+                let next = link
+                    .and_then(next_link)
+                    .and_then(|next| url.join(&next).ok())
+                    .map(String::from);
+                if next.is_none() || next.as_deref() == Some(url.as_str()) {
+                    return false;
+                }
+                self.next_url = next;
+                true
+            }
+            HttpPagination::Offset { limit, .. } => {
+                if (item_count as u64) < *limit {
+                    return false;
+                }
+                self.position += limit;
+                true
+            }
+        }
+    }
+}
+
+/// Extract the `rel="next"` target from a `Link` header value.
+fn next_link(header: &str) -> Option<String> {
+    header.split(',').find_map(|link| {
+        let mut parts = link.split(';');
+        let target = parts.next()?.trim();
+        let is_next = parts.any(|param| {
+            let param = param.trim();
+            param
+                .strip_prefix("rel=")
+                .map(|rel| rel.trim_matches('"'))
+                .is_some_and(|rel| rel.split_whitespace().any(|r| r == "next"))
+        });
+        let target = target.strip_prefix('<')?.strip_suffix('>')?;
+        is_next.then(|| target.to_string())
+    })
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn url() -> reqwest::Url {
+        reqwest::Url::parse("https://api.example.com/v1/items?page=1").unwrap()
+    }
+
+    #[test]
+    fn test_next_link_parsing() {
+        let header = r#"<https://api.example.com/items?page=3>; rel="last", <https://api.example.com/items?page=2>; rel="next""#;
+        assert_eq!(
+            next_link(header).as_deref(),
+            Some("https://api.example.com/items?page=2")
+        );
+        assert_eq!(next_link(r#"</a>; rel="prev""#), None);
+        assert_eq!(next_link("</b>; rel=next").as_deref(), Some("/b"));
+    }
+
+    #[test]
+    fn test_cursor_pager_stops_on_missing_or_repeated_cursor() {
+        let strategy = HttpPagination::Cursor {
+            cursor_path: "$.meta.next".to_string(),
+            cursor_param: "after".to_string(),
+        };
+        let mut pager = Pager::new(&strategy, "https://x", vec![]).unwrap();
+        assert!(pager.request().1.is_empty());
+
+        assert!(pager.advance(&url(), &json!({"meta": {"next": "c1"}}), 10, None));
+        assert_eq!(pager.request().1, vec![("after".into(), "c1".into())]);
+        assert!(!pager.advance(&url(), &json!({"meta": {"next": "c1"}}), 10, None));
+        assert!(!pager.advance(&url(), &json!({"meta": {"next": null}}), 10, None));
+    }
+
+    #[test]
+    fn test_page_number_and_offset_pagers() {
+        let strategy = HttpPagination::PageNumber {
+            page_param: "page".to_string(),
+            first_page: 0,
+            size_param: Some("per_page".to_string()),
+            page_size: Some(2),
+        };
+        let mut pager = Pager::new(&strategy, "https://x", vec![]).unwrap();
+        assert_eq!(
+            pager.request().1,
+            vec![("page".into(), "0".into()), ("per_page".into(), "2".into())]
+        );
+        assert!(pager.advance(&url(), &json!([]), 2, None));
+        assert_eq!(pager.request().1[0], ("page".into(), "1".into()));
+        assert!(!pager.advance(&url(), &json!([]), 1, None));
+
+        let strategy = HttpPagination::Offset {
+            offset_param: "offset".to_string(),
+            limit_param: "limit".to_string(),
+            limit: 50,
+        };
+        let mut pager = Pager::new(&strategy, "https://x", vec![]).unwrap();
+        assert!(pager.advance(&url(), &json!([]), 50, None));
+        assert_eq!(
+            pager.request().1,
+            vec![
+                ("offset".into(), "50".into()),
+                ("limit".into(), "50".into())
+            ]
+        );
+        assert!(!pager.advance(&url(), &json!([]), 49, None));
+    }
+
+    #[test]
+    fn test_link_header_pager_resolves_relative_urls() {
+        let base_query = vec![("state".to_string(), "open".to_string())];
+        let mut pager = Pager::new(&HttpPagination::LinkHeader, "https://x", base_query).unwrap();
+        assert_eq!(pager.request().1.len(), 1);
+
+        assert!(pager.advance(
+            &url(),
+            &json!([]),
+            3,
+            Some(r#"</v1/items?page=2>; rel="next""#)
+        ));
+        let (next, query) = pager.request();
+        assert_eq!(next, "https://api.example.com/v1/items?page=2");
+        assert!(query.is_empty());
+        assert!(!pager.advance(&url(), &json!([]), 3, None));
+    }
+
+    #[test]
+    fn test_bad_cursor_path_is_error() {
+        let strategy = HttpPagination::Cursor {
+            cursor_path: "$[[".to_string(),
+            cursor_param: "after".to_string(),
+        };
+        assert!(Pager::new(&strategy, "https://x", vec![]).is_err());
+    }
+}
@@ -217,6 +217,59 @@ impl SftpAdapter {
         Ok(sftp)
     }
 
+    /// Resolve a lifecycle destination directory. Relative paths are taken
+    /// against the parent of `remote_path`, so `historical/` sits next to it.
+    fn lifecycle_dir(&self, dest_dir: &str) -> String {
+        let dest = dest_dir.trim_end_matches('/');
+        if dest_dir.starts_with('/') {
+            return format!("/{}", dest.trim_start_matches('/'));
+        }
+        let root = self.remote_path.trim_end_matches('/');
+        match root.rsplit_once('/') {
+            Some((parent, _)) => format!("{parent}/{dest}"),
+            None if self.remote_path.starts_with('/') => format!("/{dest}"),
+            None => dest.to_string(),
+        }
+    }
+
+    /// Create `dir` and any missing parents on the server.
+    async fn create_dir_all(
+        &self,
+        sftp: &russh_sftp::client::SftpSession,
+        dir: &str,
+    ) -> Result<(), SourceError> {
+        let mut current = String::new();
+        for segment in dir.split('/') {
+            if segment.is_empty() {
+                if current.is_empty() && dir.starts_with('/') {
+                    current.push('/');
+                }
+                continue;
+            }
+            if !current.is_empty() && !current.ends_with('/') {
+                current.push('/');
+            }
+            current.push_str(segment);
+
+            let exists =
+                sftp.try_exists(current.clone())
+                    .await
+                    .map_err(|e| SourceError::Transient {
+                        source_name: self.source_name.clone(),
+                        message: format!("failed to stat '{current}': {e}"),
+                    })?;
+            if !exists {
+                sftp.create_dir(current.clone())
+                    .await
+                    .map_err(|e| SourceError::Transient {
+                        source_name: self.source_name.clone(),
+                        message: format!("failed to create directory '{current}': {e}"),
+                    })?;
+            }
+        }
+        Ok(())
+    }
+
     /// Check if a filename matches the configured glob pattern.
     fn matches_pattern(&self, name: &str) -> bool {
         match &self.pattern {
@@ -323,6 +376,44 @@ impl SourceAdapter for SftpAdapter {
             content_hash,
         })
     }
+
+    async fn move_item(&self, item_id: &str, dest_dir: &str) -> Result<String, SourceError> {
+        let from = remote_item_path(item_id);
+        let name = from.rsplit('/').next().unwrap_or(from);
+        let dir = self.lifecycle_dir(dest_dir);
+        let to = format!("{dir}/{name}");
+
+        let sftp = self.connect().await?;
+        self.create_dir_all(&sftp, &dir).await?;
+        sftp.rename(from, to.clone())
+            .await
+            .map_err(|e| SourceError::Transient {
+                source_name: self.source_name.clone(),
+                message: format!("failed to rename '{from}' to '{to}': {e}"),
+            })?;
+
+        debug!(source = %self.source_name, from = %from, to = %to, "lifecycle: moved SFTP file");
+        Ok(to)
+    }
+
+    async fn delete_item(&self, item_id: &str) -> Result<(), SourceError> {
+        let path = remote_item_path(item_id);
+        let sftp = self.connect().await?;
+        sftp.remove_file(path)
+            .await
+            .map_err(|e| SourceError::Transient {
+                source_name: self.source_name.clone(),
+                message: format!("failed to delete '{path}': {e}"),
+            })?;
+
+        debug!(source = %self.source_name, path = %path, "lifecycle: deleted SFTP file");
+        Ok(())
+    }
+}
+
+/// The remote path of an item, given its `sftp:`-prefixed ID.
+fn remote_item_path(item_id: &str) -> &str {
+    item_id.strip_prefix("sftp:").unwrap_or(item_id)
 }
 
 /// Detect if the material looks like an SSH private key.
@@ -658,6 +749,37 @@ mod tests {
         assert!(adapter.matches_pattern("transactions.csv"));
     }
 
+    #[test]
+    fn test_lifecycle_dir_resolution() {
+        let mut adapter = SftpAdapter {
+            source_name: "test".to_string(),
+            host: "localhost".to_string(),
+            port: 22,
+            username: "user".to_string(),
+            auth_material: b"password".to_vec(),
+            auth_type: AuthType::Password,
+            remote_path: "/data/staging/".to_string(),
+            pattern: None,
+            stream: None,
+        };
+        assert_eq!(
+            adapter.lifecycle_dir("historical/run-1/"),
+            "/data/historical/run-1"
+        );
+        assert_eq!(adapter.lifecycle_dir("/archive/"), "/archive");
+
+        adapter.remote_path = "/staging".to_string();
+        assert_eq!(adapter.lifecycle_dir("error"), "/error");
+
+        adapter.remote_path = "outbox".to_string();
+        assert_eq!(adapter.lifecycle_dir("error/"), "error");
+
+        assert_eq!(
+            remote_item_path("sftp:/data/staging/a.csv"),
+            "/data/staging/a.csv"
+        );
+    }
+
     #[test]
     fn test_matches_pattern_without_pattern() {
         let adapter = SftpAdapter {
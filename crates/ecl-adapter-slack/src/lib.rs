@@ -331,6 +331,7 @@ mod tests {
             root: "/tmp".into(),
             extensions: vec![],
             filters: vec![],
+            watch: None,
             stream: None,
         });
         let result = SlackAdapter::from_spec("test", &spec);
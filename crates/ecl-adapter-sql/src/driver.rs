@@ -0,0 +1,321 @@
+//! Database connections for the supported dialects.
+//!
+//! Each driver returns result rows as JSON [`Record`]s. Postgres rows
+//! arrive already JSON-encoded (see `query::chunk_sql`); MySQL and SQLite
+//! values are converted column by column.
+
+use std::sync::{Arc, Mutex};
+
+use base64::Engine as _;
+use mysql_async::consts::ColumnType;
+use mysql_async::prelude::Queryable;
+use serde_json::Value;
+use tracing::warn;
+
+use ecl_pipeline_topo::Record;
+
+use crate::query::Dialect;
+
+/// An open database connection.
+pub enum Connection {
+    /// PostgreSQL client; its connection task runs in the background.
+    Postgres(tokio_postgres::Client),
+    /// MySQL connection.
+    MySql(mysql_async::Conn),
+    /// SQLite connection, used from blocking tasks.
+    Sqlite(Arc<Mutex<rusqlite::Connection>>),
+}
+
+impl std::fmt::Debug for Connection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let dialect = match self {
+            Self::Postgres(_) => Dialect::Postgres,
+            Self::MySql(_) => Dialect::MySql,
+            Self::Sqlite(_) => Dialect::Sqlite,
+        };
+        f.debug_tuple("Connection").field(&dialect).finish()
+    }
+}
+
+impl Connection {
+    /// Connect to the database at `url`.
+    ///
+    /// `password`, if given, overrides any password in the URL. SQLite
+    /// databases are opened read-only.
+    ///
+    /// # Errors
+    ///
+    /// Returns a message if the URL is invalid or the connection fails.
+    pub async fn open(dialect: Dialect, url: &str, password: Option<&str>) -> Result<Self, String> {
+        match dialect {
+            Dialect::Postgres => {
+                let mut config: tokio_postgres::Config = url
+                    .parse()
+                    .map_err(|e| format!("invalid postgres URL: {e}"))?;
+                if let Some(password) = password {
+                    config.password(password);
+                }
+                let (client, connection) = config
+                    .connect(postgres_tls())
+                    .await
+                    .map_err(|e| format!("failed to connect to postgres: {e}"))?;
+                tokio::spawn(async move {
+                    if let Err(e) = connection.await {
+                        warn!(error = %e, "postgres connection closed with error");
+                    }
+                });
+                Ok(Self::Postgres(client))
+            }
+            Dialect::MySql => {
+                install_crypto_provider();
+                let opts = mysql_async::Opts::from_url(url)
+                    .map_err(|e| format!("invalid mysql URL: {e}"))?;
+                let mut builder = mysql_async::OptsBuilder::from_opts(opts);
+                if let Some(password) = password {
+                    builder = builder.pass(Some(password));
+                }
+                let conn = mysql_async::Conn::new(builder)
+                    .await
+                    .map_err(|e| format!("failed to connect to mysql: {e}"))?;
+                Ok(Self::MySql(conn))
+            }
+            Dialect::Sqlite => {
+                let path = sqlite_path(url).to_string();
+                let conn = tokio::task::spawn_blocking(move || {
+                    rusqlite::Connection::open_with_flags(
+                        &path,
+                        rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY
+                            | rusqlite::OpenFlags::SQLITE_OPEN_NO_MUTEX,
+                    )
+                    .map_err(|e| format!("failed to open sqlite database '{path}': {e}"))
+                })
+                .await
+                .map_err(|e| format!("sqlite open task failed: {e}"))??;
+                Ok(Self::Sqlite(Arc::new(Mutex::new(conn))))
+            }
+        }
+    }
+
+    /// Run `sql` and return its rows as records.
+    ///
+    /// # Errors
+    ///
+    /// Returns a message if the query fails or a row cannot be decoded.
+    pub async fn query(&mut self, sql: &str) -> Result<Vec<Record>, String> {
+        match self {
+            Self::Postgres(client) => {
+                let rows = client.query(sql, &[]).await.map_err(|e| e.to_string())?;
+                rows.iter()
+                    .map(|row| {
+                        let json: String = row.try_get(0).map_err(|e| e.to_string())?;
+                        serde_json::from_str(&json).map_err(|e| format!("bad row JSON: {e}"))
+                    })
+                    .collect()
+            }
+            Self::MySql(conn) => {
+                // The binary protocol (a prepared statement) returns typed
+                // values; the text protocol would return every value as bytes.
+                let rows: Vec<mysql_async::Row> =
+                    conn.exec(sql, ()).await.map_err(|e| e.to_string())?;
+                Ok(rows.iter().map(mysql_record).collect())
+            }
+            Self::Sqlite(conn) => {
+                let conn = conn.clone();
+                let sql = sql.to_string();
+                tokio::task::spawn_blocking(move || {
+                    let conn = conn
+                        .lock()
+                        .map_err(|_| "sqlite connection lock poisoned".to_string())?;
+                    sqlite_records(&conn, &sql).map_err(|e| e.to_string())
+                })
+                .await
+                .map_err(|e| format!("sqlite query task failed: {e}"))?
+            }
+        }
+    }
+
+    /// Close the connection.
+    pub async fn close(self) {
+        if let Self::MySql(conn) = self
+            && let Err(e) = conn.disconnect().await
+        {
+            warn!(error = %e, "failed to close mysql connection");
+        }
+    }
+}
+
+/// The file path of a `sqlite://path` or `sqlite:path` URL.
+fn sqlite_path(url: &str) -> &str {
+    url.strip_prefix("sqlite://")
+        .or_else(|| url.strip_prefix("sqlite:"))
+        .unwrap_or(url)
+}
+
+/// Install ring as the process-wide rustls provider, unless one already is.
+fn install_crypto_provider() {
+    let _ = rustls::crypto::ring::default_provider().install_default();
+}
+
+/// TLS connector for Postgres, trusting the platform and webpki roots.
+///
+/// With the default `sslmode=prefer`, TLS is used when the server offers it.
+fn postgres_tls() -> tokio_postgres_rustls::MakeRustlsConnect {
+    install_crypto_provider();
+    let mut roots = rustls::RootCertStore::empty();
+    roots.extend(webpki_roots::TLS_SERVER_ROOTS.iter().cloned());
+    for cert in rustls_native_certs::load_native_certs().certs {
+        let _ = roots.add(cert);
+    }
+    let config = rustls::ClientConfig::builder()
+        .with_root_certificates(roots)
+        .with_no_client_auth();
+    tokio_postgres_rustls::MakeRustlsConnect::new(config)
+}
+
+/// Read every row of a SQLite query as a record.
+fn sqlite_records(conn: &rusqlite::Connection, sql: &str) -> rusqlite::Result<Vec<Record>> {
+    use rusqlite::types::ValueRef;
+
+    let mut stmt = conn.prepare(sql)?;
+    let names: Vec<String> = stmt.column_names().into_iter().map(String::from).collect();
+    let mut rows = stmt.query([])?;
+    let mut records = Vec::new();
+    while let Some(row) = rows.next()? {
+        let mut record = Record::new();
+        for (i, name) in names.iter().enumerate() {
+            let value = match row.get_ref(i)? {
+                ValueRef::Null => Value::Null,
+                ValueRef::Integer(n) => Value::from(n),
+                ValueRef::Real(f) => float(f),
+                ValueRef::Text(text) => Value::String(String::from_utf8_lossy(text).into_owned()),
+                ValueRef::Blob(bytes) => blob(bytes),
+            };
+            record.insert(name.clone(), value);
+        }
+        records.push(record);
+    }
+    Ok(records)
+}
+
+/// Convert a MySQL row to a record.
+fn mysql_record(row: &mysql_async::Row) -> Record {
+    let mut record = Record::new();
+    for (i, column) in row.columns_ref().iter().enumerate() {
+        let value = row
+            .as_ref(i)
+            .map(|value| mysql_value(value, column.column_type()))
+            .unwrap_or(Value::Null);
+        record.insert(column.name_str().into_owned(), value);
+    }
+    record
+}
+
+/// Convert one MySQL value to JSON. Dates and times become strings in
+/// MySQL's own literal format, so they round-trip as watermarks.
+fn mysql_value(value: &mysql_async::Value, column_type: ColumnType) -> Value {
+    use mysql_async::Value as My;
+
+    match value {
+        My::NULL => Value::Null,
+        My::Int(n) => Value::from(*n),
+        My::UInt(n) => Value::from(*n),
+        My::Float(f) => float(f64::from(*f)),
+        My::Double(f) => float(*f),
+        My::Bytes(bytes) => match std::str::from_utf8(bytes) {
+            Ok(text) if column_type == ColumnType::MYSQL_TYPE_JSON => {
+                serde_json::from_str(text).unwrap_or_else(|_| Value::String(text.to_string()))
+            }
+            Ok(text) => Value::String(text.to_string()),
+            Err(_) => blob(bytes),
+        },
+        My::Date(year, month, day, hour, minute, second, micros) => {
+            let mut text = format!("{year:04}-{month:02}-{day:02}");
+            if column_type != ColumnType::MYSQL_TYPE_DATE {
+                text.push_str(&format!(" {hour:02}:{minute:02}:{second:02}"));
+                if *micros > 0 {
+                    text.push_str(&format!(".{micros:06}"));
+                }
+            }
+            Value::String(text)
+        }
+        My::Time(negative, days, hours, minutes, seconds, micros) => {
+            let sign = if *negative { "-" } else { "" };
+            let hours = u64::from(*days) * 24 + u64::from(*hours);
+            let mut text = format!("{sign}{hours:02}:{minutes:02}:{seconds:02}");
+            if *micros > 0 {
+                text.push_str(&format!(".{micros:06}"));
+            }
+            Value::String(text)
+        }
+    }
+}
+
+/// A float as JSON; non-finite values have no JSON form and become null.
+fn float(f: f64) -> Value {
+    serde_json::Number::from_f64(f)
+        .map(Value::Number)
+        .unwrap_or(Value::Null)
+}
+
+/// Binary data as a base64 string.
+fn blob(bytes: &[u8]) -> Value {
+    Value::String(base64::engine::general_purpose::STANDARD.encode(bytes))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use mysql_async::Value as My;
+
+    #[test]
+    fn test_sqlite_path() {
+        assert_eq!(sqlite_path("sqlite:///var/db/ref.db"), "/var/db/ref.db");
+        assert_eq!(sqlite_path("sqlite://ref.db"), "ref.db");
+        assert_eq!(sqlite_path("sqlite:ref.db"), "ref.db");
+    }
+
+    #[test]
+    fn test_mysql_value_conversion() {
+        assert_eq!(mysql_value(&My::Int(-3), ColumnType::MYSQL_TYPE_LONG), -3);
+        assert_eq!(
+            mysql_value(
+                &My::Bytes(b"12.50".to_vec()),
+                ColumnType::MYSQL_TYPE_NEWDECIMAL
+            ),
+            "12.50"
+        );
+        assert_eq!(
+            mysql_value(
+                &My::Bytes(br#"{"a": 1}"#.to_vec()),
+                ColumnType::MYSQL_TYPE_JSON
+            ),
+            serde_json::json!({"a": 1})
+        );
+        assert_eq!(
+            mysql_value(
+                &My::Date(2026, 3, 4, 5, 6, 7, 0),
+                ColumnType::MYSQL_TYPE_DATETIME
+            ),
+            "2026-03-04 05:06:07"
+        );
+        assert_eq!(
+            mysql_value(
+                &My::Date(2026, 3, 4, 0, 0, 0, 0),
+                ColumnType::MYSQL_TYPE_DATE
+            ),
+            "2026-03-04"
+        );
+        assert_eq!(
+            mysql_value(
+                &My::Time(true, 1, 2, 3, 4, 500),
+                ColumnType::MYSQL_TYPE_TIME
+            ),
+            "-26:03:04.000500"
+        );
+        assert_eq!(
+            mysql_value(&My::Bytes(vec![0xff, 0x00]), ColumnType::MYSQL_TYPE_BLOB),
+            "/wA="
+        );
+    }
+}
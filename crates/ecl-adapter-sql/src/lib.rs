@@ -0,0 +1,612 @@
+//! SQL database source adapter for the ECL pipeline runner.
+//!
+//! Provides `SqlAdapter`, which implements `SourceAdapter` for a
+//! `kind = "sql"` source: it runs the configured query against Postgres,
+//! MySQL, or SQLite in `LIMIT`/`OFFSET` chunks and emits each row as a
+//! record-bearing item. With a `watermark_column`, only rows newer than the
+//! watermark recorded by the previous completed run are read.
+
+#![forbid(unsafe_code)]
+#![warn(missing_docs)]
+#![deny(clippy::unwrap_used)]
+#![warn(clippy::expect_used)]
+#![deny(clippy::panic)]
+
+pub mod driver;
+pub mod query;
+
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDateTime, Utc};
+use tokio::sync::RwLock;
+use tracing::debug;
+
+use ecl_pipeline_spec::{CredentialRef, SourceSpec, SqlSourceSpec};
+use ecl_pipeline_state::{Blake3Hash, ItemProvenance};
+use ecl_pipeline_topo::error::{ResolveError, SourceError};
+use ecl_pipeline_topo::{ExtractedDocument, RECORD_MIME_TYPE, Record, SourceAdapter, SourceItem};
+use ecl_secrets::SecretResolver;
+
+use crate::driver::Connection;
+use crate::query::{Chunk, Dialect};
+
+/// Items, JSON-encoded rows by ID, and the highest watermark read.
+type LoadedRows = (
+    Vec<SourceItem>,
+    BTreeMap<String, Vec<u8>>,
+    Option<serde_json::Value>,
+);
+
+/// SQL database source adapter.
+///
+/// Rows are read during enumeration and held until fetched, so each item's
+/// content is exactly the row that was enumerated. A resumed run, which
+/// does not enumerate, reads the rows again on its first fetch. Items are
+/// sorted by ID for deterministic ordering.
+#[derive(Debug)]
+pub struct SqlAdapter {
+    /// Source name (for error reporting and provenance).
+    source_name: String,
+    /// Source configuration.
+    spec: SqlSourceSpec,
+    /// Dialect selected by the URL scheme.
+    dialect: Dialect,
+    /// Resolves the password credential.
+    resolver: Arc<dyn SecretResolver>,
+    /// Watermark recorded by the previous run.
+    resume: Mutex<Option<serde_json::Value>>,
+    /// Watermark reached by the last enumeration, JSON-encoded.
+    reached: Mutex<Option<String>>,
+    /// JSON-encoded rows from the last enumeration, by ID.
+    rows: RwLock<BTreeMap<String, Vec<u8>>>,
+}
+
+impl SqlAdapter {
+    /// Create a new adapter from a `SourceSpec`.
+    ///
+    /// `resolver` resolves a `CredentialRef::Secret` password.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ResolveError::UnknownAdapter` if the spec is not a SQL source.
+    /// Returns `ResolveError::Io` if the spec is invalid.
+    pub fn from_spec(
+        source_name: &str,
+        spec: &SourceSpec,
+        resolver: Arc<dyn SecretResolver>,
+    ) -> Result<Self, ResolveError> {
+        let sql_spec = match spec {
+            SourceSpec::Sql(ss) => ss,
+            _ => {
+                return Err(ResolveError::UnknownAdapter {
+                    stage: source_name.to_string(),
+                    adapter: "sql".to_string(),
+                });
+            }
+        };
+
+        Self::from_sql_spec(source_name, sql_spec, resolver)
+    }
+
+    /// Create a new adapter directly from a `SqlSourceSpec`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ResolveError::Io` if the URL scheme is not a supported
+    /// database, the query is empty, or `chunk_size` is zero.
+    pub fn from_sql_spec(
+        source_name: &str,
+        spec: &SqlSourceSpec,
+        resolver: Arc<dyn SecretResolver>,
+    ) -> Result<Self, ResolveError> {
+        let invalid = |message: String| {
+            ResolveError::Io(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!("sql source '{source_name}': {message}"),
+            ))
+        };
+
+        let dialect = Dialect::from_url(&spec.url).ok_or_else(|| {
+            invalid("url must start with postgres://, mysql:// or sqlite://".to_string())
+        })?;
+        if spec.query.trim().trim_end_matches(';').trim().is_empty() {
+            return Err(invalid("query is empty".to_string()));
+        }
+        if spec.chunk_size == 0 {
+            return Err(invalid("chunk_size must be positive".to_string()));
+        }
+
+        Ok(Self {
+            source_name: source_name.to_string(),
+            spec: spec.clone(),
+            dialect,
+            resolver,
+            resume: Mutex::new(None),
+            reached: Mutex::new(None),
+            rows: RwLock::new(BTreeMap::new()),
+        })
+    }
+
+    fn permanent(&self, message: String) -> SourceError {
+        SourceError::Permanent {
+            source_name: self.source_name.clone(),
+            message,
+        }
+    }
+
+    /// Connect to the configured database.
+    async fn connect(&self) -> Result<Connection, SourceError> {
+        let password = match &self.spec.password {
+            Some(cred) => Some(
+                resolve_credential(cred, self.resolver.as_ref())
+                    .await
+                    .map_err(|message| SourceError::AuthError {
+                        source_name: self.source_name.clone(),
+                        message,
+                    })?,
+            ),
+            None => None,
+        };
+        Connection::open(self.dialect, &self.spec.url, password.as_deref())
+            .await
+            .map_err(|message| match self.dialect {
+                // A local file that will not open will not open on retry.
+                Dialect::Sqlite => self.permanent(message),
+                Dialect::Postgres | Dialect::MySql => SourceError::Transient {
+                    source_name: self.source_name.clone(),
+                    message,
+                },
+            })
+    }
+
+    /// Read all rows past the previous watermark, chunk by chunk.
+    async fn read_rows(
+        &self,
+        conn: &mut Connection,
+        after: Option<&serde_json::Value>,
+    ) -> Result<Vec<Record>, SourceError> {
+        let mut rows = Vec::new();
+        let mut offset = 0;
+        loop {
+            let sql = query::chunk_sql(
+                self.dialect,
+                &self.spec.query,
+                &self.spec.id_column,
+                self.spec.watermark_column.as_deref(),
+                Chunk {
+                    after,
+                    limit: self.spec.chunk_size,
+                    offset,
+                },
+            )
+            .map_err(|message| self.permanent(message))?;
+            let chunk = conn
+                .query(&sql)
+                .await
+                .map_err(|e| self.permanent(format!("query failed: {e}")))?;
+            let count = chunk.len() as u64;
+            debug!(source = %self.source_name, offset, count, "read chunk");
+            rows.extend(chunk);
+            if count < self.spec.chunk_size {
+                return Ok(rows);
+            }
+            offset += count;
+        }
+    }
+
+    /// Run the query, returning the items, their JSON-encoded rows by ID,
+    /// and the highest watermark value seen (starting from `watermark`).
+    async fn load(
+        &self,
+        after: Option<&serde_json::Value>,
+        mut watermark: Option<serde_json::Value>,
+    ) -> Result<LoadedRows, SourceError> {
+        let mut conn = self.connect().await?;
+        let result = self.read_rows(&mut conn, after).await;
+        conn.close().await;
+        let records = result?;
+
+        let mut items = Vec::with_capacity(records.len());
+        let mut rows = BTreeMap::new();
+        for record in &records {
+            let content = serde_json::to_vec(record).unwrap_or_default();
+            let item = self.source_item(record, &content)?;
+            if rows.insert(item.id.clone(), content).is_some() {
+                return Err(self.permanent(format!(
+                    "duplicate value '{}' in id column '{}'",
+                    item.id, self.spec.id_column
+                )));
+            }
+            // Rows arrive in watermark order, so the last non-null value
+            // is the highest.
+            if let Some(value) = self
+                .spec
+                .watermark_column
+                .as_ref()
+                .and_then(|column| record.get(column))
+                .filter(|value| !value.is_null())
+            {
+                watermark = Some(value.clone());
+            }
+            items.push(item);
+        }
+        Ok((items, rows, watermark))
+    }
+
+    /// The JSON-encoded row for `id`. A resumed run skips `enumerate`, so
+    /// the rows are read again when none are held.
+    async fn row(&self, id: &str) -> Result<Option<Vec<u8>>, SourceError> {
+        if let Some(row) = self.rows.read().await.get(id) {
+            return Ok(Some(row.clone()));
+        }
+        let mut rows = self.rows.write().await;
+        if rows.is_empty() {
+            *rows = self.load(None, None).await?.1;
+        }
+        Ok(rows.get(id).cloned())
+    }
+
+    /// Build the `SourceItem` for one row.
+    fn source_item(&self, row: &Record, content: &[u8]) -> Result<SourceItem, SourceError> {
+        let id = row
+            .get(&self.spec.id_column)
+            .and_then(scalar_string)
+            .ok_or_else(|| {
+                self.permanent(format!(
+                    "row has no value in id column '{}'",
+                    self.spec.id_column
+                ))
+            })?;
+        let modified_at = self
+            .spec
+            .watermark_column
+            .as_ref()
+            .and_then(|column| row.get(column))
+            .and_then(scalar_string)
+            .and_then(|value| parse_timestamp(&value));
+
+        Ok(SourceItem {
+            display_name: id.clone(),
+            mime_type: RECORD_MIME_TYPE.to_string(),
+            path: id.clone(),
+            id,
+            modified_at,
+            source_hash: Some(blake3::hash(content).to_hex().to_string()),
+        })
+    }
+}
+
+/// Render a scalar JSON value as a string.
+fn scalar_string(value: &serde_json::Value) -> Option<String> {
+    match value {
+        serde_json::Value::String(s) => Some(s.clone()),
+        serde_json::Value::Number(n) => Some(n.to_string()),
+        serde_json::Value::Bool(b) => Some(b.to_string()),
+        _ => None,
+    }
+}
+
+/// Parse an RFC 3339 or `YYYY-MM-DD HH:MM:SS[.f]` (UTC) timestamp.
+fn parse_timestamp(value: &str) -> Option<DateTime<Utc>> {
+    value.parse::<DateTime<Utc>>().ok().or_else(|| {
+        NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S%.f")
+            .ok()
+            .map(|t| t.and_utc())
+    })
+}
+
+/// Resolve a `CredentialRef` to a string secret.
+async fn resolve_credential(
+    cred: &CredentialRef,
+    resolver: &dyn SecretResolver,
+) -> Result<String, String> {
+    let value = match cred {
+        CredentialRef::Secret { name } => resolver
+            .resolve_string(name)
+            .await
+            .map_err(|e| e.to_string())?,
+        CredentialRef::File { path } => tokio::fs::read_to_string(path)
+            .await
+            .map_err(|e| format!("failed to read credential file '{}': {e}", path.display()))?,
+        CredentialRef::EnvVar { env } => {
+            std::env::var(env).map_err(|_| format!("environment variable '{env}' not set"))?
+        }
+        CredentialRef::ApplicationDefault => {
+            return Err(
+                "application default credentials are not supported for SQL sources".to_string(),
+            );
+        }
+    };
+    Ok(value.trim().to_string())
+}
+
+#[async_trait]
+impl SourceAdapter for SqlAdapter {
+    fn source_kind(&self) -> &str {
+        "sql"
+    }
+
+    async fn enumerate(&self) -> Result<Vec<SourceItem>, SourceError> {
+        let previous = self.resume.lock().map(|g| g.clone()).unwrap_or_default();
+        let after = previous
+            .as_ref()
+            .filter(|_| self.spec.watermark_column.is_some());
+
+        let (mut items, rows, watermark) = self.load(after, previous.clone()).await?;
+        *self.rows.write().await = rows;
+        if self.spec.watermark_column.is_some()
+            && let Ok(mut reached) = self.reached.lock()
+        {
+            *reached = watermark.map(|value| value.to_string());
+        }
+
+        // Sort by ID for deterministic ordering.
+        items.sort_by(|a, b| a.id.cmp(&b.id));
+        Ok(items)
+    }
+
+    async fn fetch(&self, item: &SourceItem) -> Result<ExtractedDocument, SourceError> {
+        let content = self
+            .row(&item.id)
+            .await?
+            .ok_or_else(|| SourceError::NotFound {
+                source_name: self.source_name.clone(),
+                item_id: item.id.clone(),
+            })?;
+
+        let content_hash = Blake3Hash::new(blake3::hash(&content).to_hex().as_str());
+
+        let mut prov_metadata = BTreeMap::new();
+        prov_metadata.insert(
+            "item_id".to_string(),
+            serde_json::Value::String(item.id.clone()),
+        );
+        prov_metadata.insert(
+            "driver".to_string(),
+            serde_json::Value::String(self.dialect.name().to_string()),
+        );
+
+        let provenance = ItemProvenance {
+            source_kind: "sql".to_string(),
+            metadata: prov_metadata,
+            source_modified: item.modified_at,
+            extracted_at: Utc::now(),
+        };
+
+        Ok(ExtractedDocument {
+            id: item.id.clone(),
+            display_name: item.display_name.clone(),
+            content,
+            mime_type: RECORD_MIME_TYPE.to_string(),
+            provenance,
+            content_hash,
+        })
+    }
+
+    fn resume_from(&self, watermark: Option<&str>) {
+        // Watermarks are stored JSON-encoded; accept a bare string too.
+        let value = watermark.map(|raw| {
+            serde_json::from_str(raw).unwrap_or_else(|_| serde_json::Value::String(raw.to_string()))
+        });
+        if let Ok(mut resume) = self.resume.lock() {
+            *resume = value;
+        }
+    }
+
+    fn watermark(&self) -> Option<String> {
+        self.reached.lock().ok().and_then(|reached| reached.clone())
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use ecl_secrets::SecretError;
+    use tempfile::TempDir;
+
+    #[derive(Debug)]
+    struct NoSecrets;
+
+    #[async_trait]
+    impl SecretResolver for NoSecrets {
+        async fn resolve(&self, name: &str) -> Result<Vec<u8>, SecretError> {
+            Err(SecretError::NotFound {
+                name: name.to_string(),
+            })
+        }
+    }
+
+    /// Create a SQLite database with a `products` table.
+    fn make_db(dir: &TempDir) -> String {
+        let path = dir.path().join("ref.db");
+        let conn = rusqlite::Connection::open(&path).unwrap();
+        conn.execute_batch(
+            "CREATE TABLE products (sku TEXT PRIMARY KEY, name TEXT, price REAL, \
+                 qty INTEGER, image BLOB, updated_at TEXT);
+             INSERT INTO products VALUES
+                 ('A1', 'Apple', 0.5, 10, NULL, '2026-01-01 00:00:00'),
+                 ('B2', 'Banana', 0.25, NULL, x'00ff', '2026-01-02 00:00:00'),
+                 ('C3', 'Cherry', 3.0, 7, NULL, '2026-01-03 00:00:00'),
+                 ('D4', 'Date', 5.5, 1, NULL, '2026-01-04 00:00:00'),
+                 ('E5', 'Elder''berry', 1.0, 2, NULL, '2026-01-05 00:00:00');",
+        )
+        .unwrap();
+        format!("sqlite://{}", path.display())
+    }
+
+    fn spec(url: &str) -> SqlSourceSpec {
+        SqlSourceSpec {
+            url: url.to_string(),
+            password: None,
+            query: "SELECT * FROM products;".to_string(),
+            id_column: "sku".to_string(),
+            watermark_column: None,
+            chunk_size: 2,
+            stream: None,
+        }
+    }
+
+    fn adapter(spec: &SqlSourceSpec) -> SqlAdapter {
+        SqlAdapter::from_sql_spec("products", spec, Arc::new(NoSecrets)).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_enumerate_reads_all_chunks_and_fetches_records() {
+        let dir = TempDir::new().unwrap();
+        let adapter = adapter(&spec(&make_db(&dir)));
+
+        let items = adapter.enumerate().await.unwrap();
+        let ids: Vec<_> = items.iter().map(|i| i.id.as_str()).collect();
+        assert_eq!(ids, ["A1", "B2", "C3", "D4", "E5"]);
+        assert_eq!(items[0].mime_type, RECORD_MIME_TYPE);
+        assert!(items[0].source_hash.is_some());
+        assert_eq!(adapter.watermark(), None);
+
+        let doc = adapter.fetch(&items[1]).await.unwrap();
+        assert_eq!(doc.mime_type, RECORD_MIME_TYPE);
+        assert_eq!(doc.provenance.source_kind, "sql");
+        assert_eq!(doc.provenance.metadata["driver"], "sqlite");
+        let record: Record = serde_json::from_slice(&doc.content).unwrap();
+        assert_eq!(record["name"], "Banana");
+        assert_eq!(record["price"], 0.25);
+        assert!(record["qty"].is_null());
+        assert_eq!(record["image"], "AP8=");
+        assert_eq!(record.len(), 6);
+    }
+
+    #[tokio::test]
+    async fn test_watermark_limits_rows_and_advances() {
+        let dir = TempDir::new().unwrap();
+        let mut spec = spec(&make_db(&dir));
+        spec.watermark_column = Some("updated_at".to_string());
+        let adapter = adapter(&spec);
+
+        adapter.enumerate().await.unwrap();
+        let first = adapter.watermark().unwrap();
+        assert_eq!(first, r#""2026-01-05 00:00:00""#);
+
+        adapter.resume_from(Some(r#""2026-01-03 00:00:00""#));
+        let items = adapter.enumerate().await.unwrap();
+        let ids: Vec<_> = items.iter().map(|i| i.id.as_str()).collect();
+        assert_eq!(ids, ["D4", "E5"]);
+        assert_eq!(
+            items[0].modified_at.unwrap().to_rfc3339(),
+            "2026-01-04T00:00:00+00:00"
+        );
+
+        // Nothing newer: the watermark stays where it was.
+        adapter.resume_from(Some(&first));
+        assert!(adapter.enumerate().await.unwrap().is_empty());
+        assert_eq!(adapter.watermark().as_deref(), Some(first.as_str()));
+    }
+
+    #[tokio::test]
+    async fn test_numeric_watermark() {
+        let dir = TempDir::new().unwrap();
+        let mut spec = spec(&make_db(&dir));
+        spec.query = "SELECT rowid AS seq, sku FROM products".to_string();
+        spec.watermark_column = Some("seq".to_string());
+        let adapter = adapter(&spec);
+
+        adapter.resume_from(Some("3"));
+        let items = adapter.enumerate().await.unwrap();
+        assert_eq!(items.len(), 2);
+        assert_eq!(adapter.watermark().as_deref(), Some("5"));
+    }
+
+    #[tokio::test]
+    async fn test_bad_rows_are_permanent_errors() {
+        let dir = TempDir::new().unwrap();
+        let url = make_db(&dir);
+
+        let mut missing_id = spec(&url);
+        missing_id.id_column = "nope".to_string();
+        let err = adapter(&missing_id).enumerate().await.unwrap_err();
+        assert!(matches!(err, SourceError::Permanent { .. }), "{err}");
+
+        let mut duplicate = spec(&url);
+        duplicate.query = "SELECT 'x' AS sku FROM products".to_string();
+        let err = adapter(&duplicate).enumerate().await.unwrap_err();
+        assert!(err.to_string().contains("duplicate value 'x'"), "{err}");
+
+        let mut bad_sql = spec(&url);
+        bad_sql.query = "SELEC nonsense".to_string();
+        let err = adapter(&bad_sql).enumerate().await.unwrap_err();
+        assert!(err.to_string().contains("query failed"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn test_missing_database_and_unknown_item() {
+        let dir = TempDir::new().unwrap();
+        let url = format!("sqlite://{}", dir.path().join("missing.db").display());
+        let err = adapter(&spec(&url)).enumerate().await.unwrap_err();
+        assert!(matches!(err, SourceError::Permanent { .. }), "{err}");
+
+        let adapter = adapter(&spec(&make_db(&dir)));
+        let item = SourceItem {
+            id: "Z9".to_string(),
+            display_name: "Z9".to_string(),
+            mime_type: RECORD_MIME_TYPE.to_string(),
+            path: "Z9".to_string(),
+            modified_at: None,
+            source_hash: None,
+        };
+        let err = adapter.fetch(&item).await.unwrap_err();
+        assert!(matches!(err, SourceError::NotFound { .. }));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_after_resume_rereads_rows() {
+        let dir = TempDir::new().unwrap();
+        let mut spec = spec(&make_db(&dir));
+        spec.watermark_column = Some("updated_at".to_string());
+        let items = adapter(&spec).enumerate().await.unwrap();
+
+        // A resumed run uses a fresh adapter and skips enumeration.
+        let resumed = adapter(&spec);
+        let doc = resumed.fetch(&items[2]).await.unwrap();
+        let record: Record = serde_json::from_slice(&doc.content).unwrap();
+        assert_eq!(record["name"], "Cherry");
+        assert!(resumed.fetch(&items[4]).await.is_ok());
+        assert_eq!(resumed.watermark(), None);
+    }
+
+    #[tokio::test]
+    async fn test_password_secret_must_resolve() {
+        let mut spec = spec("postgres://ecl@127.0.0.1:1/db");
+        spec.password = Some(CredentialRef::Secret {
+            name: "db-password".to_string(),
+        });
+        let err = adapter(&spec).enumerate().await.unwrap_err();
+        assert!(matches!(err, SourceError::AuthError { .. }), "{err}");
+    }
+
+    #[test]
+    fn test_invalid_specs() {
+        let bad = |spec: SqlSourceSpec| {
+            SqlAdapter::from_sql_spec("s", &spec, Arc::new(NoSecrets)).unwrap_err()
+        };
+        assert!(bad(spec("oracle://db")).to_string().contains("url must"));
+        let mut empty = spec("sqlite://x.db");
+        empty.query = " ; ".to_string();
+        assert!(bad(empty).to_string().contains("query is empty"));
+        let mut zero = spec("sqlite://x.db");
+        zero.chunk_size = 0;
+        assert!(bad(zero).to_string().contains("chunk_size"));
+
+        let wrong_kind = SourceSpec::Filesystem(ecl_pipeline_spec::FilesystemSourceSpec {
+            root: "/tmp".into(),
+            filters: vec![],
+            extensions: vec![],
+            watch: None,
+            stream: None,
+        });
+        assert!(matches!(
+            SqlAdapter::from_spec("s", &wrong_kind, Arc::new(NoSecrets)),
+            Err(ResolveError::UnknownAdapter { .. })
+        ));
+    }
+}
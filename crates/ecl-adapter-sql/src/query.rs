@@ -0,0 +1,196 @@
+//! SQL generation for chunked, incremental reads.
+//!
+//! The configured query is wrapped as a subquery so the adapter can add a
+//! watermark filter, a deterministic order, and `LIMIT`/`OFFSET` paging
+//! without parsing the user's SQL.
+
+use std::fmt::Write as _;
+
+/// The SQL dialect of the connected database.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Dialect {
+    /// PostgreSQL.
+    Postgres,
+    /// MySQL / MariaDB.
+    MySql,
+    /// SQLite.
+    Sqlite,
+}
+
+impl Dialect {
+    /// Determine the dialect from a connection URL scheme.
+    pub fn from_url(url: &str) -> Option<Self> {
+        let scheme = url.split_once(':')?.0.to_ascii_lowercase();
+        match scheme.as_str() {
+            "postgres" | "postgresql" => Some(Self::Postgres),
+            "mysql" => Some(Self::MySql),
+            "sqlite" => Some(Self::Sqlite),
+            _ => None,
+        }
+    }
+
+    /// Driver name, as recorded in provenance.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Postgres => "postgres",
+            Self::MySql => "mysql",
+            Self::Sqlite => "sqlite",
+        }
+    }
+
+    /// Quote an identifier, doubling any embedded quote characters.
+    pub fn quote_ident(self, ident: &str) -> String {
+        match self {
+            Self::MySql => format!("`{}`", ident.replace('`', "``")),
+            Self::Postgres | Self::Sqlite => format!("\"{}\"", ident.replace('"', "\"\"")),
+        }
+    }
+
+    /// Render a watermark value as a SQL literal.
+    ///
+    /// Watermarks are inlined rather than bound because their column type
+    /// is unknown; an untyped literal compares correctly against numeric,
+    /// text, and timestamp columns in all three dialects.
+    ///
+    /// # Errors
+    ///
+    /// Returns a message for values that have no literal form (null,
+    /// arrays, objects).
+    pub fn literal(self, value: &serde_json::Value) -> Result<String, String> {
+        match value {
+            serde_json::Value::Number(n) => Ok(n.to_string()),
+            serde_json::Value::Bool(b) => Ok(if *b { "TRUE" } else { "FALSE" }.to_string()),
+            serde_json::Value::String(s) => {
+                let mut escaped = s.replace('\'', "''");
+                if self == Self::MySql {
+                    // MySQL treats backslash as an escape character by default.
+                    escaped = escaped.replace('\\', "\\\\");
+                }
+                Ok(format!("'{escaped}'"))
+            }
+            other => Err(format!("unsupported watermark value: {other}")),
+        }
+    }
+}
+
+/// Parameters for one chunk of an incremental read.
+#[derive(Debug, Clone, Copy)]
+pub struct Chunk<'a> {
+    /// Only rows with a watermark column value above this are read.
+    pub after: Option<&'a serde_json::Value>,
+    /// Maximum rows to return.
+    pub limit: u64,
+    /// Rows to skip.
+    pub offset: u64,
+}
+
+/// Build the SQL for one chunk of `query`.
+///
+/// Postgres rows are selected as `row_to_json` text so that column types
+/// map to JSON natively; the other dialects select `*`.
+///
+/// # Errors
+///
+/// Returns a message if the watermark value has no literal form.
+pub fn chunk_sql(
+    dialect: Dialect,
+    query: &str,
+    id_column: &str,
+    watermark_column: Option<&str>,
+    chunk: Chunk<'_>,
+) -> Result<String, String> {
+    let columns = match dialect {
+        Dialect::Postgres => "row_to_json(ecl_src)::text",
+        Dialect::MySql | Dialect::Sqlite => "*",
+    };
+    let query = query.trim().trim_end_matches(';').trim_end();
+    let id = format!("ecl_src.{}", dialect.quote_ident(id_column));
+
+    let mut sql = format!("SELECT {columns} FROM ({query}) AS ecl_src");
+    match watermark_column {
+        Some(column) => {
+            let column = format!("ecl_src.{}", dialect.quote_ident(column));
+            if let Some(after) = chunk.after {
+                let _ = write!(sql, " WHERE {column} > {}", dialect.literal(after)?);
+            }
+            let _ = write!(sql, " ORDER BY {column}, {id}");
+        }
+        None => {
+            let _ = write!(sql, " ORDER BY {id}");
+        }
+    }
+    let _ = write!(sql, " LIMIT {} OFFSET {}", chunk.limit, chunk.offset);
+    Ok(sql)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_dialect_from_url() {
+        assert_eq!(
+            Dialect::from_url("postgresql://u@h/db"),
+            Some(Dialect::Postgres)
+        );
+        assert_eq!(Dialect::from_url("MySQL://h/db"), Some(Dialect::MySql));
+        assert_eq!(Dialect::from_url("sqlite://a.db"), Some(Dialect::Sqlite));
+        assert_eq!(Dialect::from_url("oracle://h"), None);
+        assert_eq!(Dialect::from_url("no-scheme"), None);
+    }
+
+    #[test]
+    fn test_quoting_and_literals() {
+        assert_eq!(Dialect::Postgres.quote_ident(r#"we"ird"#), r#""we""ird""#);
+        assert_eq!(Dialect::MySql.quote_ident("a`b"), "`a``b`");
+        assert_eq!(Dialect::Sqlite.literal(&json!(42)).unwrap(), "42");
+        assert_eq!(
+            Dialect::Postgres.literal(&json!("O'Hara")).unwrap(),
+            "'O''Hara'"
+        );
+        assert_eq!(Dialect::MySql.literal(&json!(r"a\'")).unwrap(), r"'a\\'''");
+        assert!(Dialect::Sqlite.literal(&json!(null)).is_err());
+    }
+
+    #[test]
+    fn test_chunk_sql() {
+        let full = chunk_sql(
+            Dialect::Sqlite,
+            "SELECT * FROM t;\n",
+            "id",
+            None,
+            Chunk {
+                after: None,
+                limit: 10,
+                offset: 20,
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            full,
+            r#"SELECT * FROM (SELECT * FROM t) AS ecl_src ORDER BY ecl_src."id" LIMIT 10 OFFSET 20"#
+        );
+
+        let after = json!("2026-01-01 00:00:00");
+        let incremental = chunk_sql(
+            Dialect::Postgres,
+            "SELECT * FROM t",
+            "id",
+            Some("updated_at"),
+            Chunk {
+                after: Some(&after),
+                limit: 5,
+                offset: 0,
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            incremental,
+            "SELECT row_to_json(ecl_src)::text FROM (SELECT * FROM t) AS ecl_src \
+             WHERE ecl_src.\"updated_at\" > '2026-01-01 00:00:00' \
+             ORDER BY ecl_src.\"updated_at\", ecl_src.\"id\" LIMIT 5 OFFSET 0"
+        );
+    }
+}
@@ -221,6 +221,7 @@ mod tests {
             root: std::path::PathBuf::from("/tmp"),
             filters: vec![],
             extensions: vec![],
+            watch: None,
             stream: None,
         });
         let result = ZapierAdapter::from_spec("test", &fs_spec);
@@ -1,8 +1,8 @@
 //! Default adapter and stage registries for the CLI.
 //!
-//! Registers all built-in adapters (filesystem, Google Drive) and stages
-//! (extract, normalize, filter, emit) so that TOML configs can reference
-//! them by name.
+//! Registers all built-in adapters (filesystem, Google Drive, HTTP, SQL) and
+//! stages (extract, normalize, filter, emit) so that TOML configs can
+//! reference them by name.
 
 use std::collections::BTreeMap;
 use std::sync::Arc;
@@ -10,16 +10,20 @@ use std::sync::Arc;
 use ecl_adapter_fs::FilesystemAdapter;
 use ecl_adapter_gcs::GcsAdapter;
 use ecl_adapter_gdrive::GoogleDriveAdapter;
+use ecl_adapter_http::HttpAdapter;
 use ecl_adapter_slack::SlackAdapter;
+use ecl_adapter_sql::SqlAdapter;
 use ecl_adapter_zapier::ZapierAdapter;
-use ecl_pipeline_spec::{PipelineSpec, SourceSpec, StageSpec};
+use ecl_pipeline_spec::{PipelineSpec, SecretsConfig, SourceSpec, StageSpec, StreamSchemas};
 use ecl_pipeline_topo::error::ResolveError;
 use ecl_pipeline_topo::{PushSourceAdapter, SourceAdapter, Stage};
+use ecl_secrets::SecretResolver;
 use ecl_sink_gcs::GcsSinkStage;
 use ecl_sink_kafka::KafkaSinkStage;
 use ecl_stages::{
-    CsvParseStage, EmitStage, ExtractStage, FieldMapStage, FilterStage, NormalizeStage,
-    ValidateStage,
+    CsvParseStage, DecryptStage, EmitStage, ExtractStage, FieldMapStage, FilterStage,
+    FixedWidthParseStage, JsonParseStage, NormalizeStage, SqlStage, ValidateStage, XlsxParseStage,
+    XmlParseStage,
 };
 
 /// Pre-resolve all source adapters from the spec.
@@ -33,17 +37,33 @@ pub fn resolve_adapters(
     spec: &PipelineSpec,
 ) -> Result<BTreeMap<String, Arc<dyn SourceAdapter>>, ResolveError> {
     let mut adapters = BTreeMap::new();
+    // Built on first use so pipelines without HTTP or SQL sources never
+    // need a working secrets provider here.
+    let mut resolver: Option<Arc<dyn SecretResolver>> = None;
+    let mut secrets = || -> Result<Arc<dyn SecretResolver>, ResolveError> {
+        if let Some(secrets) = &resolver {
+            return Ok(secrets.clone());
+        }
+        let secrets = secret_resolver(&spec.secrets)?;
+        resolver = Some(secrets.clone());
+        Ok(secrets)
+    };
 
     for (name, source_spec) in &spec.sources {
+        if source_spec.is_push() {
+            continue; // Push sources resolved separately
+        }
         let adapter: Arc<dyn SourceAdapter> = match source_spec {
-            SourceSpec::Filesystem(_) => Arc::new(FilesystemAdapter::from_spec(name, source_spec)?),
+            SourceSpec::Filesystem(_) => Arc::new(filesystem_adapter(spec, name, source_spec)?),
             SourceSpec::GoogleDrive(_) => {
                 Arc::new(GoogleDriveAdapter::from_spec(name, source_spec)?)
             }
             SourceSpec::Slack(_) => Arc::new(SlackAdapter::from_spec(name, source_spec)?),
-            SourceSpec::Zapier(_) => continue, // Push sources resolved separately
+            SourceSpec::Zapier(_) => continue, // Push source, skipped above
             SourceSpec::Gcs(_) => Arc::new(GcsAdapter::from_spec(name, source_spec)?),
             SourceSpec::Sftp(_) => continue, // SFTP requires async + SecretResolver; resolved separately
+            SourceSpec::Http(_) => Arc::new(HttpAdapter::from_spec(name, source_spec, secrets()?)?),
+            SourceSpec::Sql(_) => Arc::new(SqlAdapter::from_spec(name, source_spec, secrets()?)?),
         };
         adapters.insert(name.clone(), adapter);
     }
@@ -51,6 +71,20 @@ pub fn resolve_adapters(
     Ok(adapters)
 }
 
+/// Build a filesystem adapter that skips the pipeline's lifecycle
+/// directories when scanning.
+fn filesystem_adapter(
+    spec: &PipelineSpec,
+    name: &str,
+    source_spec: &SourceSpec,
+) -> Result<FilesystemAdapter, ResolveError> {
+    let adapter = FilesystemAdapter::from_spec(name, source_spec)?;
+    Ok(match &spec.lifecycle {
+        Some(lifecycle) => adapter.with_lifecycle(lifecycle),
+        None => adapter,
+    })
+}
+
 /// Create an adapter lookup closure from pre-resolved adapters.
 ///
 /// The closure returns clones of the pre-resolved adapters.
@@ -68,10 +102,36 @@ pub fn adapter_lookup_fn(
     }
 }
 
+/// Build the secret resolver for the spec's `[secrets]` provider.
+///
+/// # Errors
+///
+/// Returns `ResolveError::Io` if the provider is not supported.
+pub fn secret_resolver(config: &SecretsConfig) -> Result<Arc<dyn SecretResolver>, ResolveError> {
+    let provider = match config {
+        SecretsConfig::None => "none",
+        SecretsConfig::GcpSecretManager { .. } => "gcp_secret_manager",
+    };
+    ecl_secrets::build_resolver(provider)
+        .map(Arc::from)
+        .map_err(|e| {
+            ResolveError::Io(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                e.to_string(),
+            ))
+        })
+}
+
 /// Create a stage lookup closure that uses pre-resolved adapters for extract stages.
-pub fn stage_lookup_fn(
-    adapters: &BTreeMap<String, Arc<dyn SourceAdapter>>,
-) -> impl Fn(&str, &StageSpec) -> Result<Arc<dyn Stage>, ResolveError> + '_ {
+///
+/// `schemas` are the pipeline's declared stream schemas, used by sinks that
+/// generate their wire schema from a stream. `secrets` selects the provider
+/// that stages needing key material (e.g. `decrypt`) resolve secrets from.
+pub fn stage_lookup_fn<'a>(
+    adapters: &'a BTreeMap<String, Arc<dyn SourceAdapter>>,
+    schemas: &'a StreamSchemas,
+    secrets: &'a SecretsConfig,
+) -> impl Fn(&str, &StageSpec) -> Result<Arc<dyn Stage>, ResolveError> + 'a {
     move |name: &str, spec: &StageSpec| -> Result<Arc<dyn Stage>, ResolveError> {
         match spec.adapter.as_str() {
             "extract" => {
@@ -99,6 +159,16 @@ pub fn stage_lookup_fn(
                 })?;
                 Ok(Arc::new(stage))
             }
+            "decrypt" => {
+                let stage = DecryptStage::from_params(&spec.params, secret_resolver(secrets)?)
+                    .map_err(|e| {
+                        ResolveError::Io(std::io::Error::new(
+                            std::io::ErrorKind::InvalidInput,
+                            format!("decrypt stage '{name}': {e}"),
+                        ))
+                    })?;
+                Ok(Arc::new(stage))
+            }
             "csv_parse" => {
                 let stage = CsvParseStage::from_params(&spec.params).map_err(|e| {
                     ResolveError::Io(std::io::Error::new(
@@ -108,6 +178,42 @@ pub fn stage_lookup_fn(
                 })?;
                 Ok(Arc::new(stage))
             }
+            "xlsx_parse" => {
+                let stage = XlsxParseStage::from_params(&spec.params).map_err(|e| {
+                    ResolveError::Io(std::io::Error::new(
+                        std::io::ErrorKind::InvalidInput,
+                        format!("xlsx_parse stage '{name}': {e}"),
+                    ))
+                })?;
+                Ok(Arc::new(stage))
+            }
+            "json_parse" => {
+                let stage = JsonParseStage::from_params(&spec.params).map_err(|e| {
+                    ResolveError::Io(std::io::Error::new(
+                        std::io::ErrorKind::InvalidInput,
+                        format!("json_parse stage '{name}': {e}"),
+                    ))
+                })?;
+                Ok(Arc::new(stage))
+            }
+            "xml_parse" => {
+                let stage = XmlParseStage::from_params(&spec.params).map_err(|e| {
+                    ResolveError::Io(std::io::Error::new(
+                        std::io::ErrorKind::InvalidInput,
+                        format!("xml_parse stage '{name}': {e}"),
+                    ))
+                })?;
+                Ok(Arc::new(stage))
+            }
+            "fixed_width_parse" => {
+                let stage = FixedWidthParseStage::from_params(&spec.params).map_err(|e| {
+                    ResolveError::Io(std::io::Error::new(
+                        std::io::ErrorKind::InvalidInput,
+                        format!("fixed_width_parse stage '{name}': {e}"),
+                    ))
+                })?;
+                Ok(Arc::new(stage))
+            }
             "field_map" => {
                 let stage = FieldMapStage::from_params(&spec.params).map_err(|e| {
                     ResolveError::Io(std::io::Error::new(
@@ -126,15 +232,25 @@ pub fn stage_lookup_fn(
                 })?;
                 Ok(Arc::new(stage))
             }
-            "kafka_sink" => {
-                let stage = KafkaSinkStage::from_params(&spec.params).map_err(|e| {
+            "sql" => {
+                let stage = SqlStage::from_params(&spec.params).map_err(|e| {
                     ResolveError::Io(std::io::Error::new(
                         std::io::ErrorKind::InvalidInput,
-                        format!("kafka_sink stage '{name}': {e}"),
+                        format!("sql stage '{name}': {e}"),
                     ))
                 })?;
                 Ok(Arc::new(stage))
             }
+            "kafka_sink" => {
+                let stage = KafkaSinkStage::from_params_with_schemas(&spec.params, schemas)
+                    .map_err(|e| {
+                        ResolveError::Io(std::io::Error::new(
+                            std::io::ErrorKind::InvalidInput,
+                            format!("kafka_sink stage '{name}': {e}"),
+                        ))
+                    })?;
+                Ok(Arc::new(stage))
+            }
             "gcs_sink" => {
                 let stage = GcsSinkStage::from_params(&spec.params).map_err(|e| {
                     ResolveError::Io(std::io::Error::new(
@@ -144,7 +260,15 @@ pub fn stage_lookup_fn(
                 })?;
                 Ok(Arc::new(stage))
             }
-            "emit" => Ok(Arc::new(EmitStage::new())),
+            "emit" => {
+                let stage = EmitStage::from_params(&spec.params).map_err(|e| {
+                    ResolveError::Io(std::io::Error::new(
+                        std::io::ErrorKind::InvalidInput,
+                        format!("emit stage '{name}': {e}"),
+                    ))
+                })?;
+                Ok(Arc::new(stage))
+            }
             other => Err(ResolveError::UnknownAdapter {
                 stage: name.to_string(),
                 adapter: other.to_string(),
@@ -155,8 +279,8 @@ pub fn stage_lookup_fn(
 
 /// Pre-resolve all push-based source adapters from the spec.
 ///
-/// Returns a map of source_name -> concrete push adapter.
-/// Currently only Zapier sources are push-based.
+/// Returns a map of source_name -> concrete push adapter: Zapier webhooks
+/// and filesystem sources with `watch` set.
 ///
 /// # Errors
 ///
@@ -167,13 +291,14 @@ pub fn resolve_push_adapters(
     let mut adapters = BTreeMap::new();
 
     for (name, source_spec) in &spec.sources {
-        if let SourceSpec::Zapier(_) = source_spec {
-            let adapter = ZapierAdapter::from_spec(name, source_spec)?;
-            adapters.insert(
-                name.clone(),
-                Arc::new(adapter) as Arc<dyn PushSourceAdapter>,
-            );
-        }
+        let adapter: Arc<dyn PushSourceAdapter> = match source_spec {
+            SourceSpec::Zapier(_) => Arc::new(ZapierAdapter::from_spec(name, source_spec)?),
+            SourceSpec::Filesystem(_) if source_spec.is_push() => {
+                Arc::new(filesystem_adapter(spec, name, source_spec)?)
+            }
+            _ => continue,
+        };
+        adapters.insert(name.clone(), adapter);
     }
 
     Ok(adapters)
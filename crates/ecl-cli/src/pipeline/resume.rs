@@ -58,7 +58,9 @@ pub async fn execute(output_dir: PathBuf, force: bool) -> Result<()> {
     // Re-resolve the topology from the checkpointed spec.
     let adapters = registry::resolve_adapters(&spec)?;
     let adapter_fn = registry::adapter_lookup_fn(&adapters);
-    let stage_fn = registry::stage_lookup_fn(&adapters);
+    let schemas = spec.schemas.clone();
+    let secrets = spec.secrets.clone();
+    let stage_fn = registry::stage_lookup_fn(&adapters, &schemas, &secrets);
 
     let push_adapters = registry::resolve_push_adapters(&spec)?;
     let mut topology = resolve(spec, adapter_fn, stage_fn).await?;
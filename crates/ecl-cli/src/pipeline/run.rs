@@ -32,7 +32,9 @@ pub async fn execute(config_path: PathBuf) -> Result<()> {
     // Pre-resolve adapters, then use them for both lookups.
     let adapters = registry::resolve_adapters(&spec)?;
     let adapter_fn = registry::adapter_lookup_fn(&adapters);
-    let stage_fn = registry::stage_lookup_fn(&adapters);
+    let schemas = spec.schemas.clone();
+    let secrets = spec.secrets.clone();
+    let stage_fn = registry::stage_lookup_fn(&adapters, &schemas, &secrets);
 
     let push_adapters = registry::resolve_push_adapters(&spec)?;
     let mut topology = resolve(spec, adapter_fn, stage_fn).await?;
@@ -2,6 +2,8 @@
 
 use thiserror::Error;
 
+use crate::schema::FieldType;
+
 /// Errors that can occur when parsing or validating a pipeline specification.
 #[derive(Debug, Error)]
 #[non_exhaustive]
@@ -45,6 +47,72 @@ pub enum SpecError {
     },
 }
 
+/// Errors found while checking records against declared stream schemas.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[non_exhaustive]
+pub enum SchemaError {
+    /// A stage references a field that the stream does not have.
+    #[error("stream '{stream}' has no field '{field}'{}", did_you_mean(suggestion))]
+    UnknownField {
+        /// The stream whose schema was consulted.
+        stream: String,
+        /// The referenced field.
+        field: String,
+        /// The closest existing field name, if any.
+        suggestion: Option<String>,
+    },
+
+    /// A field has a type incompatible with the declared one.
+    #[error("field '{field}' on stream '{stream}' is {actual}, expected {expected}")]
+    TypeMismatch {
+        /// The stream whose schema was consulted.
+        stream: String,
+        /// The mismatched field.
+        field: String,
+        /// The declared type.
+        expected: FieldType,
+        /// The type actually produced.
+        actual: FieldType,
+    },
+
+    /// A non-nullable declared field is never produced.
+    #[error("stream '{stream}' is missing declared field '{field}'")]
+    MissingField {
+        /// The stream whose schema was declared.
+        stream: String,
+        /// The declared field that is not produced.
+        field: String,
+    },
+
+    /// A stage produces a field that the stream's declared schema lacks.
+    #[error(
+        "field '{field}' is not declared in the schema for stream '{stream}'{}",
+        did_you_mean(suggestion)
+    )]
+    UndeclaredField {
+        /// The stream whose schema was declared.
+        stream: String,
+        /// The produced field.
+        field: String,
+        /// The closest declared field name, if any.
+        suggestion: Option<String>,
+    },
+
+    /// Any other schema problem.
+    #[error("{message}")]
+    Invalid {
+        /// Description of the problem.
+        message: String,
+    },
+}
+
+fn did_you_mean(suggestion: &Option<String>) -> String {
+    suggestion
+        .as_ref()
+        .map(|s| format!(" (did you mean '{s}'?)"))
+        .unwrap_or_default()
+}
+
 /// Result type for specification operations.
 pub type Result<T> = std::result::Result<T, SpecError>;
 
@@ -83,6 +151,31 @@ mod tests {
     fn test_error_implements_send_sync() {
         fn assert_send_sync<T: Send + Sync>() {}
         assert_send_sync::<SpecError>();
+        assert_send_sync::<SchemaError>();
+    }
+
+    #[test]
+    fn test_schema_error_display() {
+        let err = SchemaError::UndeclaredField {
+            stream: "orders".to_string(),
+            field: "totl".to_string(),
+            suggestion: Some("total".to_string()),
+        };
+        assert_eq!(
+            err.to_string(),
+            "field 'totl' is not declared in the schema for stream 'orders' (did you mean 'total'?)"
+        );
+
+        let err = SchemaError::TypeMismatch {
+            stream: "orders".to_string(),
+            field: "total".to_string(),
+            expected: FieldType::Float,
+            actual: FieldType::Boolean,
+        };
+        assert_eq!(
+            err.to_string(),
+            "field 'total' on stream 'orders' is boolean, expected float"
+        );
     }
 
     #[test]
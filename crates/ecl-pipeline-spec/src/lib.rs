@@ -7,16 +7,19 @@
 pub mod defaults;
 pub mod error;
 pub mod lifecycle;
+pub mod schema;
 pub mod source;
 pub mod stage;
 pub mod validation;
 
 pub use defaults::{CheckpointStrategy, DefaultsSpec, RetrySpec};
-pub use error::{Result, SpecError};
+pub use error::{Result, SchemaError, SpecError};
 pub use lifecycle::LifecycleSpec;
+pub use schema::{FieldSchema, FieldType, StreamSchema, StreamSchemas};
 pub use source::{
-    CredentialRef, FileTypeFilter, FilesystemSourceSpec, FilterAction, FilterRule, GcsSourceSpec,
-    GoogleDriveSourceSpec, SftpSourceSpec, SlackSourceSpec, SourceSpec,
+    CredentialRef, FileTypeFilter, FilesystemSourceSpec, FilterAction, FilterRule, FsWatchSpec,
+    GcsSourceSpec, GoogleDriveSourceSpec, HttpAuth, HttpPagination, HttpSourceSpec, SftpSourceSpec,
+    SlackSourceSpec, SourceSpec, SqlSourceSpec,
 };
 pub use stage::{ResourceSpec, StageSpec};
 
@@ -101,6 +104,12 @@ pub struct PipelineSpec {
     /// Optional schedule configuration for cron-based execution.
     #[serde(default)]
     pub schedule: Option<ScheduleSpec>,
+
+    /// Optional record schemas, keyed by stream name.
+    /// Topology resolution propagates these through the stages and checks
+    /// every field reference against them.
+    #[serde(default)]
+    pub schemas: StreamSchemas,
 }
 
 impl PipelineSpec {
@@ -341,4 +350,33 @@ resources = { reads = ["raw"] }
         let schedule = spec.schedule.unwrap();
         assert_eq!(schedule.cron, "30 21 * * *");
     }
+
+    #[test]
+    fn test_pipeline_spec_with_schemas() {
+        let toml_str = r#"
+name = "typed"
+version = 1
+output_dir = "./output/typed"
+
+[schemas.transactions]
+fields = [
+    { name = "txn_id", type = "string" },
+    { name = "amount", type = "float", nullable = true },
+]
+
+[sources.local]
+kind = "filesystem"
+root = "/tmp/test"
+
+[stages.extract]
+adapter = "extract"
+source = "local"
+resources = { creates = ["raw"] }
+"#;
+        let spec = PipelineSpec::from_toml(toml_str).unwrap();
+        let schema = &spec.schemas["transactions"];
+        assert_eq!(schema.fields.len(), 2);
+        assert_eq!(schema.fields[1].field_type, FieldType::Float);
+        assert!(schema.fields[1].nullable);
+    }
 }
@@ -1,7 +1,8 @@
 //! File lifecycle management configuration.
 //!
-//! Defines how processed files move through GCS prefixes:
+//! Defines how processed files move between locations:
 //! `staging/` → `historical/{run_id}/` on success, or back to `input/` on failure.
+//! Applies to GCS, filesystem, and SFTP sources.
 
 use serde::{Deserialize, Serialize};
 
@@ -12,9 +13,18 @@ use crate::source::CredentialRef;
 /// Controls automatic movement of source files after pipeline execution:
 /// - **On success**: move from staging to historical (or delete)
 /// - **On failure**: move back to input (or to error prefix)
+///
+/// For GCS sources the prefixes are object name prefixes. For filesystem
+/// sources they are directories resolved against the source's root, like
+/// the watch `processed_dir` and `error_dir`; files under them are not
+/// scanned again. For SFTP sources relative directories are resolved
+/// against the parent of the remote path, so that `staging/`,
+/// `historical/` and `error/` sit side by side.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LifecycleSpec {
-    /// GCS bucket for lifecycle operations.
+    /// GCS bucket for lifecycle operations. Defaults to each GCS source's
+    /// own bucket.
+    #[serde(default)]
     pub bucket: String,
 
     /// Prefix where the pipeline reads input files (e.g., `"staging/"`).
@@ -37,6 +47,12 @@ pub struct LifecycleSpec {
     #[serde(default = "default_on_failure")]
     pub on_failure: LifecycleAction,
 
+    /// Decide per item instead of per run: each file gets `on_success` or
+    /// `on_failure` by its own outcome, so only failed files are moved to
+    /// the error location.
+    #[serde(default)]
+    pub per_item: bool,
+
     /// Credential reference for GCS auth.
     #[serde(default = "default_adc")]
     pub credentials: CredentialRef,
@@ -96,6 +112,7 @@ mod tests {
             error_prefix: "error/".to_string(),
             on_success: LifecycleAction::MoveToHistorical,
             on_failure: LifecycleAction::MoveToError,
+            per_item: false,
             credentials: CredentialRef::ApplicationDefault,
         };
 
@@ -115,6 +132,7 @@ mod tests {
         assert_eq!(spec.error_prefix, "error/");
         assert_eq!(spec.on_success, LifecycleAction::MoveToHistorical);
         assert_eq!(spec.on_failure, LifecycleAction::MoveToInput);
+        assert!(!spec.per_item);
         assert!(matches!(
             spec.credentials,
             CredentialRef::ApplicationDefault
@@ -176,6 +194,19 @@ mod tests {
         assert_eq!(lc.historical_prefix, "archive/");
     }
 
+    #[test]
+    fn test_lifecycle_spec_for_filesystem_source() {
+        let toml_str = r#"
+            error_prefix = "rejected/"
+            on_failure = "move_to_error"
+            per_item = true
+        "#;
+        let spec: LifecycleSpec = toml::from_str(toml_str).unwrap();
+        assert!(spec.bucket.is_empty());
+        assert_eq!(spec.error_prefix, "rejected/");
+        assert!(spec.per_item);
+    }
+
     #[test]
     fn test_pipeline_spec_without_lifecycle_backward_compat() {
         use crate::PipelineSpec;
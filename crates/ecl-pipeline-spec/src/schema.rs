@@ -0,0 +1,490 @@
+//! Record schema types for named streams.
+//!
+//! Schemas are optional. A schema declared under `[schemas.<stream>]`
+//! describes the records on that stream once they have been parsed (or,
+//! for streams created by a stage's `output_stream`, as that stage emits
+//! them). Topology resolution propagates schemas through the stages and
+//! checks every field reference against them.
+//!
+//! ```toml
+//! [schemas.transactions]
+//! fields = [
+//!     { name = "txn_id", type = "string" },
+//!     { name = "amount", type = "float" },
+//!     { name = "coupon", type = "string", nullable = true },
+//! ]
+//! ```
+
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+use crate::error::SchemaError;
+
+/// Schemas keyed by stream name.
+pub type StreamSchemas = BTreeMap<String, StreamSchema>;
+
+/// The type of a record field.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FieldType {
+    /// UTF-8 string.
+    String,
+    /// 64-bit signed integer.
+    Integer,
+    /// 64-bit float.
+    Float,
+    /// Boolean.
+    Boolean,
+    /// Calendar date, carried as a string.
+    Date,
+    /// Date and time, carried as an RFC 3339 string.
+    Timestamp,
+    /// Nested JSON object.
+    Object,
+    /// JSON array.
+    Array,
+    /// Any JSON value; disables type checks for the field.
+    #[default]
+    Any,
+}
+
+impl FieldType {
+    /// The type name as written in TOML.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::String => "string",
+            Self::Integer => "integer",
+            Self::Float => "float",
+            Self::Boolean => "boolean",
+            Self::Date => "date",
+            Self::Timestamp => "timestamp",
+            Self::Object => "object",
+            Self::Array => "array",
+            Self::Any => "any",
+        }
+    }
+
+    /// Infer the type of a JSON value (`null` infers `Any`).
+    pub fn of_value(value: &Value) -> Self {
+        match value {
+            Value::Null => Self::Any,
+            Value::Bool(_) => Self::Boolean,
+            Value::Number(n) if n.is_f64() => Self::Float,
+            Value::Number(_) => Self::Integer,
+            Value::String(_) => Self::String,
+            Value::Array(_) => Self::Array,
+            Value::Object(_) => Self::Object,
+        }
+    }
+
+    /// Whether values of this type may be used where `expected` is declared.
+    ///
+    /// `any` is compatible with everything, integers widen to floats, and
+    /// dates and timestamps are interchangeable with strings because records
+    /// carry them as strings.
+    pub fn is_compatible_with(self, expected: FieldType) -> bool {
+        use FieldType::*;
+        self == expected
+            || self == Any
+            || expected == Any
+            || matches!((self, expected), (Integer, Float))
+            || (self.is_textual() && expected.is_textual())
+    }
+
+    fn is_textual(self) -> bool {
+        matches!(self, Self::String | Self::Date | Self::Timestamp)
+    }
+
+    /// The Avro type for this field type.
+    ///
+    /// Dates and timestamps map to strings (matching their record
+    /// representation); objects, arrays and `any` map to JSON-encoded strings.
+    pub fn avro_type(self) -> &'static str {
+        match self {
+            Self::Integer => "long",
+            Self::Float => "double",
+            Self::Boolean => "boolean",
+            _ => "string",
+        }
+    }
+}
+
+impl fmt::Display for FieldType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// A single field in a stream schema.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FieldSchema {
+    /// Field name (the record key).
+    pub name: String,
+    /// Field type. Default: `any`.
+    #[serde(rename = "type", default)]
+    pub field_type: FieldType,
+    /// Whether the field may be null or absent. Default: false.
+    #[serde(default)]
+    pub nullable: bool,
+    /// Optional description, carried into generated Avro schemas.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub doc: Option<String>,
+}
+
+impl FieldSchema {
+    /// Create a non-nullable field.
+    pub fn new(name: impl Into<String>, field_type: FieldType) -> Self {
+        Self {
+            name: name.into(),
+            field_type,
+            nullable: false,
+            doc: None,
+        }
+    }
+
+    /// Mark the field as nullable.
+    pub fn nullable(mut self) -> Self {
+        self.nullable = true;
+        self
+    }
+}
+
+/// The record schema of one stream: an ordered list of fields.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StreamSchema {
+    /// Fields in declaration order.
+    #[serde(default)]
+    pub fields: Vec<FieldSchema>,
+}
+
+impl StreamSchema {
+    /// Create a schema from a list of fields.
+    pub fn new(fields: Vec<FieldSchema>) -> Self {
+        Self { fields }
+    }
+
+    /// Look up a field by name.
+    pub fn field(&self, name: &str) -> Option<&FieldSchema> {
+        self.fields.iter().find(|f| f.name == name)
+    }
+
+    /// Whether the schema has a field with this name.
+    pub fn contains(&self, name: &str) -> bool {
+        self.field(name).is_some()
+    }
+
+    /// Add a field, replacing any existing field with the same name in place.
+    pub fn with_field(mut self, field: FieldSchema) -> Self {
+        self.set_field(field);
+        self
+    }
+
+    /// Add a field, replacing any existing field with the same name in place.
+    pub fn set_field(&mut self, field: FieldSchema) {
+        match self.fields.iter_mut().find(|f| f.name == field.name) {
+            Some(existing) => *existing = field,
+            None => self.fields.push(field),
+        }
+    }
+
+    /// Remove a field by name, returning it if present.
+    pub fn remove_field(&mut self, name: &str) -> Option<FieldSchema> {
+        let index = self.fields.iter().position(|f| f.name == name)?;
+        Some(self.fields.remove(index))
+    }
+
+    /// Look up a field that a stage references on `stream`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `SchemaError::UnknownField`, with a spelling suggestion when
+    /// one is close, if the field does not exist.
+    pub fn require(&self, stream: &str, field: &str) -> Result<&FieldSchema, SchemaError> {
+        self.field(field).ok_or_else(|| SchemaError::UnknownField {
+            stream: stream.to_string(),
+            field: field.to_string(),
+            suggestion: self.suggest(field).map(String::from),
+        })
+    }
+
+    /// Check that a schema produced by a stage conforms to this declared
+    /// schema for `stream`.
+    ///
+    /// Every produced field must be declared with a compatible type, and
+    /// every non-nullable declared field must be produced.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first mismatch found.
+    pub fn check_produced(&self, stream: &str, produced: &StreamSchema) -> Result<(), SchemaError> {
+        for field in &produced.fields {
+            let Some(declared) = self.field(&field.name) else {
+                return Err(SchemaError::UndeclaredField {
+                    stream: stream.to_string(),
+                    field: field.name.clone(),
+                    suggestion: self.suggest(&field.name).map(String::from),
+                });
+            };
+            if !field.field_type.is_compatible_with(declared.field_type) {
+                return Err(SchemaError::TypeMismatch {
+                    stream: stream.to_string(),
+                    field: field.name.clone(),
+                    expected: declared.field_type,
+                    actual: field.field_type,
+                });
+            }
+        }
+        for declared in &self.fields {
+            if !declared.nullable && !produced.contains(&declared.name) {
+                return Err(SchemaError::MissingField {
+                    stream: stream.to_string(),
+                    field: declared.name.clone(),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Check the schema for duplicate field names.
+    ///
+    /// # Errors
+    ///
+    /// Returns `SchemaError::Invalid` naming the first duplicate.
+    pub fn check_unique(&self, stream: &str) -> Result<(), SchemaError> {
+        let mut seen = HashSet::new();
+        for field in &self.fields {
+            if !seen.insert(field.name.as_str()) {
+                return Err(SchemaError::Invalid {
+                    message: format!(
+                        "schema for stream '{stream}' declares field '{}' more than once",
+                        field.name
+                    ),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// The closest field name to `name`, if one is within a small edit
+    /// distance (used for "did you mean" hints).
+    pub fn suggest(&self, name: &str) -> Option<&str> {
+        let max_distance = (name.chars().count() / 3).max(1);
+        self.fields
+            .iter()
+            .map(|f| (edit_distance(name, &f.name), f.name.as_str()))
+            .filter(|(d, _)| *d <= max_distance)
+            .min_by_key(|(d, _)| *d)
+            .map(|(_, n)| n)
+    }
+
+    /// Generate an Avro record schema for this stream.
+    ///
+    /// Nullable fields become `["null", T]` unions defaulting to null.
+    /// Invalid characters in `record_name` are replaced with `_`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `SchemaError::Invalid` if a field name is not a valid Avro
+    /// name (Avro field names must match the record keys exactly).
+    pub fn to_avro(&self, record_name: &str) -> Result<Value, SchemaError> {
+        let mut fields = Vec::with_capacity(self.fields.len());
+        for field in &self.fields {
+            if !is_avro_name(&field.name) {
+                return Err(SchemaError::Invalid {
+                    message: format!("field '{}' is not a valid Avro name", field.name),
+                });
+            }
+            let avro_type = field.field_type.avro_type();
+            let mut entry = if field.nullable {
+                json!({ "name": field.name, "type": ["null", avro_type], "default": null })
+            } else {
+                json!({ "name": field.name, "type": avro_type })
+            };
+            if let (Some(doc), Some(obj)) = (&field.doc, entry.as_object_mut()) {
+                obj.insert("doc".to_string(), Value::String(doc.clone()));
+            }
+            fields.push(entry);
+        }
+        Ok(json!({
+            "type": "record",
+            "name": sanitize_avro_name(record_name),
+            "fields": fields,
+        }))
+    }
+}
+
+fn is_avro_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn sanitize_avro_name(name: &str) -> String {
+    let mut out: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    if !out.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_') {
+        out.insert(0, '_');
+    }
+    out
+}
+
+/// Levenshtein distance between two strings.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let cost = usize::from(ca != *cb);
+            current[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(current[j] + 1);
+        }
+        prev = current;
+    }
+    prev[b.len()]
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    fn txn_schema() -> StreamSchema {
+        StreamSchema::new(vec![
+            FieldSchema::new("txn_id", FieldType::String),
+            FieldSchema::new("amount", FieldType::Float),
+            FieldSchema::new("coupon", FieldType::String).nullable(),
+        ])
+    }
+
+    #[test]
+    fn test_schema_toml_roundtrip() {
+        let schema: StreamSchema = toml::from_str(
+            r#"
+fields = [
+    { name = "txn_id", type = "string" },
+    { name = "amount", type = "float" },
+    { name = "coupon", type = "string", nullable = true },
+]
+"#,
+        )
+        .unwrap();
+        assert_eq!(schema, txn_schema());
+    }
+
+    #[test]
+    fn test_field_type_defaults_to_any() {
+        let field: FieldSchema = serde_json::from_str(r#"{"name": "x"}"#).unwrap();
+        assert_eq!(field.field_type, FieldType::Any);
+    }
+
+    #[test]
+    fn test_require_suggests_close_name() {
+        let err = txn_schema().require("transactions", "amout").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "stream 'transactions' has no field 'amout' (did you mean 'amount'?)"
+        );
+
+        let err = txn_schema().require("transactions", "zzz").unwrap_err();
+        assert_eq!(err.to_string(), "stream 'transactions' has no field 'zzz'");
+    }
+
+    #[test]
+    fn test_type_compatibility() {
+        assert!(FieldType::Integer.is_compatible_with(FieldType::Float));
+        assert!(!FieldType::Float.is_compatible_with(FieldType::Integer));
+        assert!(FieldType::String.is_compatible_with(FieldType::Timestamp));
+        assert!(FieldType::Any.is_compatible_with(FieldType::Boolean));
+        assert!(!FieldType::Boolean.is_compatible_with(FieldType::String));
+    }
+
+    #[test]
+    fn test_check_produced() {
+        let declared = txn_schema();
+        let ok = StreamSchema::new(vec![
+            FieldSchema::new("txn_id", FieldType::String),
+            FieldSchema::new("amount", FieldType::Integer),
+        ]);
+        assert!(declared.check_produced("t", &ok).is_ok());
+
+        let typo = ok
+            .clone()
+            .with_field(FieldSchema::new("copon", FieldType::String));
+        assert!(matches!(
+            declared.check_produced("t", &typo),
+            Err(SchemaError::UndeclaredField { suggestion: Some(s), .. }) if s == "coupon"
+        ));
+
+        let missing = StreamSchema::new(vec![FieldSchema::new("txn_id", FieldType::String)]);
+        assert!(matches!(
+            declared.check_produced("t", &missing),
+            Err(SchemaError::MissingField { field, .. }) if field == "amount"
+        ));
+
+        let wrong_type = StreamSchema::new(vec![
+            FieldSchema::new("txn_id", FieldType::Boolean),
+            FieldSchema::new("amount", FieldType::Float),
+        ]);
+        assert!(matches!(
+            declared.check_produced("t", &wrong_type),
+            Err(SchemaError::TypeMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_set_and_remove_field() {
+        let mut schema = txn_schema();
+        schema.set_field(FieldSchema::new("amount", FieldType::String));
+        assert_eq!(schema.fields[1].field_type, FieldType::String);
+        assert!(schema.remove_field("txn_id").is_some());
+        assert!(!schema.contains("txn_id"));
+        assert!(schema.remove_field("txn_id").is_none());
+    }
+
+    #[test]
+    fn test_check_unique() {
+        let schema = txn_schema().with_field(FieldSchema::new("extra", FieldType::Any));
+        assert!(schema.check_unique("t").is_ok());
+        let mut dup = schema.clone();
+        dup.fields
+            .push(FieldSchema::new("amount", FieldType::Float));
+        assert!(dup.check_unique("t").is_err());
+    }
+
+    #[test]
+    fn test_to_avro() {
+        let avro = txn_schema().to_avro("my-transactions").unwrap();
+        assert_eq!(
+            avro,
+            json!({
+                "type": "record",
+                "name": "my_transactions",
+                "fields": [
+                    { "name": "txn_id", "type": "string" },
+                    { "name": "amount", "type": "double" },
+                    { "name": "coupon", "type": ["null", "string"], "default": null },
+                ]
+            })
+        );
+    }
+
+    #[test]
+    fn test_to_avro_rejects_invalid_field_name() {
+        let schema = StreamSchema::new(vec![FieldSchema::new("bad-name", FieldType::String)]);
+        assert!(schema.to_avro("r").is_err());
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("amount", "amount"), 0);
+        assert_eq!(edit_distance("amout", "amount"), 1);
+        assert_eq!(edit_distance("", "abc"), 3);
+    }
+}
@@ -1,6 +1,7 @@
 //! Source specification types for external data services.
 
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 
 /// A source is "where does the data come from?"
@@ -31,6 +32,14 @@ pub enum SourceSpec {
     /// SFTP file server source.
     #[serde(rename = "sftp")]
     Sftp(SftpSourceSpec),
+
+    /// Generic HTTP/REST API source.
+    #[serde(rename = "http")]
+    Http(Box<HttpSourceSpec>),
+
+    /// SQL database query source (Postgres, MySQL, SQLite).
+    #[serde(rename = "sql")]
+    Sql(SqlSourceSpec),
 }
 
 /// Google Drive source configuration.
@@ -94,11 +103,63 @@ pub struct FilesystemSourceSpec {
     #[serde(default)]
     pub extensions: Vec<String>,
 
+    /// Watch the root for new files instead of scanning it once.
+    /// When set, the source runs as a push source.
+    #[serde(default)]
+    pub watch: Option<FsWatchSpec>,
+
     /// Named data stream for items from this source.
     #[serde(default)]
     pub stream: Option<String>,
 }
 
+/// Directory watch configuration for a filesystem push source.
+///
+/// Files are picked up from change notifications, then held until they
+/// stop changing so partially written files are never read.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FsWatchSpec {
+    /// Milliseconds to coalesce change notifications for a path.
+    #[serde(default = "default_debounce_ms")]
+    pub debounce_ms: u64,
+
+    /// Milliseconds a file's size and modification time must stay
+    /// unchanged before it is read.
+    #[serde(default = "default_settle_ms")]
+    pub settle_ms: u64,
+
+    /// Emit files already present in the root when watching starts.
+    #[serde(default = "default_true")]
+    pub process_existing: bool,
+
+    /// Directory to move successfully processed files into
+    /// (relative paths are resolved against the root).
+    #[serde(default)]
+    pub processed_dir: Option<PathBuf>,
+
+    /// Directory to move files that failed processing into
+    /// (relative paths are resolved against the root).
+    #[serde(default)]
+    pub error_dir: Option<PathBuf>,
+
+    /// Bounded channel capacity for backpressure between watcher and pipeline.
+    #[serde(default = "default_channel_capacity")]
+    pub channel_capacity: usize,
+}
+
+impl Default for FsWatchSpec {
+    fn default() -> Self {
+        Self {
+            debounce_ms: default_debounce_ms(),
+            settle_ms: default_settle_ms(),
+            process_existing: default_true(),
+            processed_dir: None,
+            error_dir: None,
+            channel_capacity: default_channel_capacity(),
+        }
+    }
+}
+
 /// Zapier webhook push source configuration.
 ///
 /// Configures an HTTP webhook receiver that accepts push events from Zapier.
@@ -188,6 +249,237 @@ pub struct SftpSourceSpec {
     pub stream: Option<String>,
 }
 
+/// Generic HTTP/REST API source configuration.
+///
+/// A listing endpoint is paged through and each JSON object selected by
+/// `items` becomes one source item. Its content is either the object itself
+/// or, when `fetch_url` is set, the body fetched from that URL.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HttpSourceSpec {
+    /// URL of the listing endpoint.
+    pub url: String,
+
+    /// Extra query parameters sent with every listing request.
+    #[serde(default)]
+    pub query: BTreeMap<String, String>,
+
+    /// Extra headers sent with every request.
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+
+    /// How requests authenticate. Default: no authentication.
+    #[serde(default)]
+    pub auth: HttpAuth,
+
+    /// How the listing endpoint is paged. Default: a single request.
+    #[serde(default)]
+    pub pagination: HttpPagination,
+
+    /// JSONPath selecting the item objects in a listing response.
+    /// Default: `$[*]` (the elements of a top-level array).
+    #[serde(default)]
+    pub items: Option<String>,
+
+    /// Dotted path of the item ID within each item object.
+    #[serde(default = "default_id_field")]
+    pub id_field: String,
+
+    /// Dotted path of the display name. Default: the item ID.
+    #[serde(default)]
+    pub name_field: Option<String>,
+
+    /// Dotted path of an RFC 3339 last-modified timestamp.
+    #[serde(default)]
+    pub modified_field: Option<String>,
+
+    /// URL template for fetching each item's content. `{id}` is the item
+    /// ID and `{a.b}` any dotted path into the item object. Default: the
+    /// listed item object is the content.
+    #[serde(default)]
+    pub fetch_url: Option<String>,
+
+    /// Maximum requests per second across listing and fetching.
+    #[serde(default)]
+    pub requests_per_second: Option<f64>,
+
+    /// Upper bound on listing pages, guarding against pagination loops.
+    #[serde(default = "default_max_pages")]
+    pub max_pages: usize,
+
+    /// Named data stream for items from this source.
+    #[serde(default)]
+    pub stream: Option<String>,
+}
+
+/// SQL database source configuration.
+///
+/// Runs `query` and emits each result row as a record-bearing item. With a
+/// `watermark_column`, only rows whose value exceeds the one recorded by the
+/// previous completed run are read.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SqlSourceSpec {
+    /// Connection URL; the scheme selects the driver: `postgres://`,
+    /// `mysql://`, or `sqlite://` (followed by a file path).
+    pub url: String,
+
+    /// Database password, if not embedded in the URL.
+    #[serde(default)]
+    pub password: Option<CredentialRef>,
+
+    /// The query whose rows become items. It is run as a subquery; a
+    /// trailing semicolon is ignored.
+    pub query: String,
+
+    /// Column holding each row's unique ID.
+    #[serde(default = "default_id_column")]
+    pub id_column: String,
+
+    /// Monotonically increasing column (e.g. `updated_at`) used for
+    /// incremental reads. Default: every run reads all rows.
+    #[serde(default)]
+    pub watermark_column: Option<String>,
+
+    /// Rows fetched per round trip during enumeration.
+    #[serde(default = "default_chunk_size")]
+    pub chunk_size: u64,
+
+    /// Named data stream for items from this source.
+    #[serde(default)]
+    pub stream: Option<String>,
+}
+
+/// How an HTTP source authenticates its requests.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum HttpAuth {
+    /// No authentication.
+    #[serde(rename = "none")]
+    #[default]
+    None,
+    /// `Authorization: Bearer <token>`.
+    #[serde(rename = "bearer")]
+    Bearer {
+        /// The bearer token.
+        token: CredentialRef,
+    },
+    /// HTTP Basic authentication.
+    #[serde(rename = "basic")]
+    Basic {
+        /// Username (plain string — not sensitive).
+        username: String,
+        /// The password.
+        password: CredentialRef,
+    },
+    /// A custom header carrying an API key.
+    #[serde(rename = "header")]
+    Header {
+        /// Header name (e.g. `X-Api-Key`).
+        name: String,
+        /// The header value.
+        value: CredentialRef,
+    },
+    /// OAuth2 client credentials grant; the token is cached until expiry.
+    #[serde(rename = "oauth2_client_credentials")]
+    OAuth2ClientCredentials {
+        /// Token endpoint URL.
+        token_url: String,
+        /// OAuth2 client ID.
+        client_id: String,
+        /// OAuth2 client secret.
+        client_secret: CredentialRef,
+        /// Scopes to request.
+        #[serde(default)]
+        scopes: Vec<String>,
+    },
+}
+
+/// How an HTTP source pages through its listing endpoint.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum HttpPagination {
+    /// A single listing request.
+    #[serde(rename = "none")]
+    #[default]
+    None,
+    /// The response carries an opaque cursor for the next page.
+    #[serde(rename = "cursor")]
+    Cursor {
+        /// JSONPath of the next-page cursor in the response; paging stops
+        /// when it is missing, null or empty.
+        cursor_path: String,
+        /// Query parameter the cursor is sent in.
+        cursor_param: String,
+    },
+    /// Pages are numbered; paging stops at the first empty page.
+    #[serde(rename = "page_number")]
+    PageNumber {
+        /// Query parameter carrying the page number.
+        #[serde(default = "default_page_param")]
+        page_param: String,
+        /// Number of the first page.
+        #[serde(default = "default_first_page")]
+        first_page: u64,
+        /// Query parameter carrying the page size, if the API takes one.
+        #[serde(default)]
+        size_param: Option<String>,
+        /// Page size to request when `size_param` is set.
+        #[serde(default)]
+        page_size: Option<u64>,
+    },
+    /// Follow `Link: <...>; rel="next"` response headers.
+    #[serde(rename = "link_header")]
+    LinkHeader,
+    /// Offset/limit paging; stops at the first short page.
+    #[serde(rename = "offset")]
+    Offset {
+        /// Query parameter carrying the offset.
+        #[serde(default = "default_offset_param")]
+        offset_param: String,
+        /// Query parameter carrying the limit.
+        #[serde(default = "default_limit_param")]
+        limit_param: String,
+        /// Items requested per page.
+        #[serde(default = "default_limit")]
+        limit: u64,
+    },
+}
+
+fn default_id_field() -> String {
+    "id".to_string()
+}
+
+fn default_max_pages() -> usize {
+    1000
+}
+
+fn default_page_param() -> String {
+    "page".to_string()
+}
+
+fn default_first_page() -> u64 {
+    1
+}
+
+fn default_offset_param() -> String {
+    "offset".to_string()
+}
+
+fn default_limit_param() -> String {
+    "limit".to_string()
+}
+
+fn default_limit() -> u64 {
+    100
+}
+
+fn default_id_column() -> String {
+    "id".to_string()
+}
+
+fn default_chunk_size() -> u64 {
+    1000
+}
+
 fn default_ssh_port() -> u16 {
     22
 }
@@ -206,8 +498,26 @@ impl SourceSpec {
             SourceSpec::Zapier(s) => s.stream.as_deref(),
             SourceSpec::Gcs(s) => s.stream.as_deref(),
             SourceSpec::Sftp(s) => s.stream.as_deref(),
+            SourceSpec::Http(s) => s.stream.as_deref(),
+            SourceSpec::Sql(s) => s.stream.as_deref(),
         }
     }
+
+    /// Whether this source pushes documents as they arrive rather than
+    /// being enumerated once per run.
+    pub fn is_push(&self) -> bool {
+        match self {
+            SourceSpec::Zapier(_) => true,
+            SourceSpec::Filesystem(s) => s.watch.is_some(),
+            _ => false,
+        }
+    }
+
+    /// Whether this source's items carry records (e.g. SQL rows) rather
+    /// than raw documents that a parse stage turns into records.
+    pub fn emits_records(&self) -> bool {
+        matches!(self, SourceSpec::Sql(_))
+    }
 }
 
 fn default_adc() -> CredentialRef {
@@ -226,6 +536,18 @@ fn default_channel_capacity() -> usize {
     1000
 }
 
+fn default_debounce_ms() -> u64 {
+    500
+}
+
+fn default_settle_ms() -> u64 {
+    2000
+}
+
+fn default_true() -> bool {
+    true
+}
+
 /// How to resolve credentials for a source.
 ///
 /// Uses internally-tagged representation (`"type": "file"`) rather than
@@ -337,6 +659,7 @@ mod tests {
             root: PathBuf::from("/tmp/data"),
             filters: vec![],
             extensions: vec!["md".to_string()],
+            watch: None,
             stream: None,
         });
         let json = serde_json::to_string(&source).unwrap();
@@ -345,6 +668,34 @@ mod tests {
         assert_eq!(json, json2);
     }
 
+    #[test]
+    fn test_filesystem_watch_spec_from_toml() {
+        let toml_str = r#"
+            kind = "filesystem"
+            root = "/srv/drop"
+            extensions = ["csv"]
+
+            [watch]
+            settle_ms = 5000
+            processed_dir = "processed"
+        "#;
+        let spec: SourceSpec = toml::from_str(toml_str).unwrap();
+        assert!(spec.is_push());
+        let SourceSpec::Filesystem(fs) = spec else {
+            unreachable!("expected Filesystem variant");
+        };
+        let watch = fs.watch.unwrap();
+        assert_eq!(watch.debounce_ms, 500);
+        assert_eq!(watch.settle_ms, 5000);
+        assert!(watch.process_existing);
+        assert_eq!(watch.processed_dir, Some(PathBuf::from("processed")));
+        assert_eq!(watch.error_dir, None);
+        assert_eq!(watch.channel_capacity, 1000);
+
+        let scan: SourceSpec = toml::from_str("kind = \"filesystem\"\nroot = \"/srv\"").unwrap();
+        assert!(!scan.is_push());
+    }
+
     #[test]
     fn test_source_spec_zapier_serde_roundtrip() {
         let source = SourceSpec::Zapier(ZapierSourceSpec {
@@ -521,4 +872,89 @@ mod tests {
             panic!("expected Sftp variant");
         }
     }
+
+    #[test]
+    fn test_http_source_spec_from_toml() {
+        let toml_str = r#"
+kind = "http"
+url = "https://api.example.com/v1/tickets"
+items = "$.data[*]"
+fetch_url = "https://api.example.com/v1/tickets/{id}/body"
+requests_per_second = 5.0
+
+[auth]
+type = "oauth2_client_credentials"
+token_url = "https://auth.example.com/token"
+client_id = "ecl"
+client_secret = { type = "secret", name = "api-secret" }
+
+[pagination]
+type = "cursor"
+cursor_path = "$.meta.next"
+cursor_param = "after"
+"#;
+        let spec: SourceSpec = toml::from_str(toml_str).unwrap();
+        let SourceSpec::Http(http) = spec else {
+            unreachable!("expected Http variant");
+        };
+        assert_eq!(http.id_field, "id");
+        assert_eq!(http.max_pages, 1000);
+        assert!(matches!(
+            http.auth,
+            HttpAuth::OAuth2ClientCredentials { ref scopes, .. } if scopes.is_empty()
+        ));
+        assert!(matches!(
+            http.pagination,
+            HttpPagination::Cursor { ref cursor_param, .. } if cursor_param == "after"
+        ));
+    }
+
+    #[test]
+    fn test_http_pagination_defaults() {
+        let json = r#"{"kind": "http", "url": "http://x", "pagination": {"type": "offset"}}"#;
+        let spec: SourceSpec = serde_json::from_str(json).unwrap();
+        let SourceSpec::Http(http) = spec else {
+            unreachable!("expected Http variant");
+        };
+        assert!(matches!(http.auth, HttpAuth::None));
+        let HttpPagination::Offset {
+            offset_param,
+            limit_param,
+            limit,
+        } = http.pagination
+        else {
+            unreachable!("expected offset pagination");
+        };
+        assert_eq!(
+            (offset_param.as_str(), limit_param.as_str(), limit),
+            ("offset", "limit", 100)
+        );
+    }
+
+    #[test]
+    fn test_sql_source_spec_from_toml() {
+        let toml_str = r#"
+kind = "sql"
+url = "postgres://ecl@db.internal/reference"
+password = { type = "secret", name = "db-password" }
+query = "SELECT sku, name, updated_at FROM products"
+id_column = "sku"
+watermark_column = "updated_at"
+"#;
+        let spec: SourceSpec = toml::from_str(toml_str).unwrap();
+        let SourceSpec::Sql(sql) = spec else {
+            unreachable!("expected Sql variant");
+        };
+        assert_eq!(sql.id_column, "sku");
+        assert_eq!(sql.watermark_column.as_deref(), Some("updated_at"));
+        assert_eq!(sql.chunk_size, 1000);
+        assert!(matches!(sql.password, Some(CredentialRef::Secret { .. })));
+
+        let json = r#"{"kind": "sql", "url": "sqlite://ref.db", "query": "SELECT 1 AS id"}"#;
+        let SourceSpec::Sql(sql) = serde_json::from_str(json).unwrap() else {
+            unreachable!("expected Sql variant");
+        };
+        assert_eq!(sql.id_column, "id");
+        assert!(sql.watermark_column.is_none() && sql.password.is_none());
+    }
 }
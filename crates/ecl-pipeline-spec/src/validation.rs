@@ -9,6 +9,7 @@ use crate::error::{Result, SpecError};
 /// - Pipeline has at least one source
 /// - Pipeline has at least one stage
 /// - Every stage with a `source` field references an existing source
+/// - No stream schema declares the same field twice
 pub fn validate(spec: &PipelineSpec) -> Result<()> {
     if spec.sources.is_empty() {
         return Err(SpecError::EmptySources);
@@ -30,6 +31,14 @@ pub fn validate(spec: &PipelineSpec) -> Result<()> {
         }
     }
 
+    for (stream, schema) in &spec.schemas {
+        schema
+            .check_unique(stream)
+            .map_err(|e| SpecError::ValidationError {
+                message: e.to_string(),
+            })?;
+    }
+
     Ok(())
 }
 
@@ -51,6 +60,7 @@ mod tests {
                 root: PathBuf::from("/tmp/data"),
                 filters: vec![],
                 extensions: vec![],
+                watch: None,
                 stream: None,
             }),
         );
@@ -83,6 +93,7 @@ mod tests {
             secrets: Default::default(),
             triggers: None,
             schedule: None,
+            schemas: Default::default(),
         }
     }
 
@@ -129,4 +140,20 @@ mod tests {
         let spec = minimal_spec();
         assert!(validate(&spec).is_ok());
     }
+
+    #[test]
+    fn test_validate_duplicate_schema_field_fails() {
+        use crate::schema::{FieldSchema, FieldType, StreamSchema};
+
+        let mut spec = minimal_spec();
+        spec.schemas.insert(
+            "rows".to_string(),
+            StreamSchema::new(vec![
+                FieldSchema::new("id", FieldType::String),
+                FieldSchema::new("id", FieldType::Integer),
+            ]),
+        );
+        let err = validate(&spec).unwrap_err();
+        assert!(err.to_string().contains("field 'id' more than once"));
+    }
 }
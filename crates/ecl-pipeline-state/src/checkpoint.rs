@@ -122,6 +122,7 @@ resources = { reads = ["raw-docs"] }
                 source_modified: None,
                 extracted_at: test_time(),
             },
+            lifecycle: None,
         }
     }
 
@@ -151,6 +152,7 @@ resources = { reads = ["raw-docs"] }
                 items_accepted: 2,
                 items_skipped_unchanged: 1,
                 items: source_items,
+                watermark: None,
             },
         );
 
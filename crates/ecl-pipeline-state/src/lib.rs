@@ -25,8 +25,8 @@ pub use memory_store::InMemoryStateStore;
 pub use redb_store::RedbStateStore;
 pub use store::StateStore;
 pub use types::{
-    CompletedStageRecord, ItemProvenance, ItemState, ItemStatus, PipelineStats, PipelineStatus,
-    SourceState, StageState, StageStatus,
+    CompletedStageRecord, ItemProvenance, ItemState, ItemStatus, LifecycleRecord, PipelineStats,
+    PipelineStatus, SourceState, StageState, StageStatus,
 };
 
 use chrono::{DateTime, Utc};
@@ -124,6 +124,7 @@ mod tests {
                 source_modified: None,
                 extracted_at: test_time(),
             },
+            lifecycle: None,
         }
     }
 
@@ -150,6 +151,7 @@ mod tests {
                 items_accepted: 2,
                 items_skipped_unchanged: 1,
                 items,
+                watermark: None,
             },
         );
 
@@ -267,6 +269,7 @@ mod tests {
                 items_accepted: 5,
                 items_skipped_unchanged: 0,
                 items: BTreeMap::new(),
+                watermark: None,
             },
         );
         state.sources.insert(
@@ -276,6 +279,7 @@ mod tests {
                 items_accepted: 3,
                 items_skipped_unchanged: 0,
                 items: BTreeMap::new(),
+                watermark: None,
             },
         );
         state.update_stats();
@@ -311,6 +315,7 @@ mod tests {
                 items_accepted: 3,
                 items_skipped_unchanged: 3, // source-level count
                 items,
+                watermark: None,
             },
         );
         state.update_stats();
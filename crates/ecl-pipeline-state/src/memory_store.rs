@@ -19,6 +19,8 @@ pub struct InMemoryStateStore {
     checkpoint: RwLock<Option<Checkpoint>>,
     /// Content hashes from the most recent completed run.
     hashes: RwLock<BTreeMap<String, Blake3Hash>>,
+    /// Per-source watermarks from completed runs.
+    watermarks: RwLock<BTreeMap<String, String>>,
 }
 
 impl InMemoryStateStore {
@@ -27,6 +29,7 @@ impl InMemoryStateStore {
         Self {
             checkpoint: RwLock::new(None),
             hashes: RwLock::new(BTreeMap::new()),
+            watermarks: RwLock::new(BTreeMap::new()),
         }
     }
 }
@@ -63,6 +66,20 @@ impl StateStore for InMemoryStateStore {
         *guard = hashes.clone();
         Ok(())
     }
+
+    async fn load_watermarks(&self) -> std::result::Result<BTreeMap<String, String>, StateError> {
+        let guard = self.watermarks.read().await;
+        Ok(guard.clone())
+    }
+
+    async fn save_watermarks(
+        &self,
+        watermarks: &BTreeMap<String, String>,
+    ) -> std::result::Result<(), StateError> {
+        let mut guard = self.watermarks.write().await;
+        guard.extend(watermarks.iter().map(|(k, v)| (k.clone(), v.clone())));
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -120,6 +137,7 @@ resources = { reads = ["raw-docs"] }
                     source_modified: None,
                     extracted_at: test_time(),
                 },
+                lifecycle: None,
             },
         );
 
@@ -131,6 +149,7 @@ resources = { reads = ["raw-docs"] }
                 items_accepted: 1,
                 items_skipped_unchanged: 0,
                 items: source_items,
+                watermark: None,
             },
         );
 
@@ -228,6 +247,24 @@ resources = { reads = ["raw-docs"] }
         assert!(loaded.is_empty());
     }
 
+    #[tokio::test]
+    async fn test_memory_store_save_watermarks_merges() {
+        let store = InMemoryStateStore::new();
+        assert!(store.load_watermarks().await.unwrap().is_empty());
+
+        let first = BTreeMap::from([
+            ("a".to_string(), "1".to_string()),
+            ("b".to_string(), "2".to_string()),
+        ]);
+        store.save_watermarks(&first).await.unwrap();
+        let second = BTreeMap::from([("a".to_string(), "5".to_string())]);
+        store.save_watermarks(&second).await.unwrap();
+
+        let loaded = store.load_watermarks().await.unwrap();
+        assert_eq!(loaded["a"], "5");
+        assert_eq!(loaded["b"], "2");
+    }
+
     #[tokio::test]
     async fn test_memory_store_object_safety() {
         let store: Box<dyn StateStore> = Box::new(InMemoryStateStore::new());
@@ -22,6 +22,9 @@ const CHECKPOINTS: TableDefinition<&str, &[u8]> = TableDefinition::new("checkpoi
 /// redb table: item_id (str) -> blake3 hex hash (str).
 const HASHES: TableDefinition<&str, &str> = TableDefinition::new("hashes");
 
+/// redb table: source name (str) -> incremental watermark (str).
+const WATERMARKS: TableDefinition<&str, &str> = TableDefinition::new("watermarks");
+
 /// redb table: metadata key (str) -> metadata value (str).
 /// Keys used:
 /// - "latest_run_id" — the run_id of the most recent checkpoint
@@ -36,9 +39,10 @@ const KEY_LATEST_COMPLETED_RUN_ID: &str = "latest_completed_run_id";
 
 /// Redb-backed state store providing crash-safe, ACID persistence.
 ///
-/// Uses three tables:
+/// Uses four tables:
 /// - `checkpoints`: maps run_id -> serialized JSON checkpoint
 /// - `hashes`: maps item_id -> blake3 hex hash (for the latest completed run)
+/// - `watermarks`: maps source name -> incremental watermark
 /// - `metadata`: maps string keys -> string values (for tracking latest run IDs)
 ///
 /// All operations run inside `tokio::task::spawn_blocking` because redb
@@ -331,6 +335,79 @@ impl StateStore for RedbStateStore {
             message: format!("spawn_blocking join error: {e}"),
         })?
     }
+
+    /// Load per-source watermarks.
+    ///
+    /// Returns an empty map if no watermark has been saved yet.
+    async fn load_watermarks(&self) -> std::result::Result<BTreeMap<String, String>, StateError> {
+        let db = self.db.clone();
+
+        tokio::task::spawn_blocking(move || {
+            let read_txn = db.begin_read().map_err(|e| StateError::StoreError {
+                message: format!("failed to begin read transaction: {e}"),
+            })?;
+            let table: redb::ReadOnlyTable<&str, &str> = match read_txn.open_table(WATERMARKS) {
+                Ok(table) => table,
+                Err(_) => return Ok(BTreeMap::new()),
+            };
+
+            let mut watermarks = BTreeMap::new();
+            let iter = table.iter().map_err(|e| StateError::StoreError {
+                message: format!("failed to iterate watermarks table: {e}"),
+            })?;
+            for entry in iter {
+                let entry = entry.map_err(|e| StateError::StoreError {
+                    message: format!("failed to read watermark entry: {e}"),
+                })?;
+                watermarks.insert(entry.0.value().to_owned(), entry.1.value().to_owned());
+            }
+            Ok(watermarks)
+        })
+        .await
+        .map_err(|e| StateError::StoreError {
+            message: format!("spawn_blocking join error: {e}"),
+        })?
+    }
+
+    /// Save per-source watermarks in a single ACID transaction.
+    ///
+    /// Upserts each entry; watermarks of other sources are left untouched.
+    async fn save_watermarks(
+        &self,
+        watermarks: &BTreeMap<String, String>,
+    ) -> std::result::Result<(), StateError> {
+        let db = self.db.clone();
+        let watermarks = watermarks.clone();
+
+        tokio::task::spawn_blocking(move || {
+            let write_txn = db.begin_write().map_err(|e| StateError::StoreError {
+                message: format!("failed to begin write transaction: {e}"),
+            })?;
+            {
+                let mut table =
+                    write_txn
+                        .open_table(WATERMARKS)
+                        .map_err(|e| StateError::StoreError {
+                            message: format!("failed to open watermarks table: {e}"),
+                        })?;
+                for (source, watermark) in &watermarks {
+                    table
+                        .insert(source.as_str(), watermark.as_str())
+                        .map_err(|e| StateError::StoreError {
+                            message: format!("failed to insert watermark: {e}"),
+                        })?;
+                }
+            }
+            write_txn.commit().map_err(|e| StateError::StoreError {
+                message: format!("failed to commit transaction: {e}"),
+            })?;
+            Ok(())
+        })
+        .await
+        .map_err(|e| StateError::StoreError {
+            message: format!("spawn_blocking join error: {e}"),
+        })?
+    }
 }
 
 #[cfg(test)]
@@ -516,6 +593,7 @@ resources = { reads = ["raw-docs"] }
                     source_modified: None,
                     extracted_at: Utc::now(),
                 },
+                lifecycle: None,
             },
         );
         source_items.insert(
@@ -537,6 +615,7 @@ resources = { reads = ["raw-docs"] }
                     source_modified: None,
                     extracted_at: Utc::now(),
                 },
+                lifecycle: None,
             },
         );
         checkpoint.state.sources.insert(
@@ -546,6 +625,7 @@ resources = { reads = ["raw-docs"] }
                 items_accepted: 3,
                 items_skipped_unchanged: 2,
                 items: source_items,
+                watermark: None,
             },
         );
 
@@ -639,6 +719,33 @@ resources = { reads = ["raw-docs"] }
         assert!(loaded.is_empty());
     }
 
+    // --- watermark tests ---
+
+    #[tokio::test]
+    async fn test_redb_store_watermarks_merge_and_persist() {
+        let dir = TempDir::new().unwrap();
+        let db_path = dir.path().join("test.redb");
+
+        {
+            let store = RedbStateStore::open(&db_path).unwrap();
+            assert!(store.load_watermarks().await.unwrap().is_empty());
+
+            let first = BTreeMap::from([
+                ("orders".to_string(), "100".to_string()),
+                ("users".to_string(), "\"2026-01-01\"".to_string()),
+            ]);
+            store.save_watermarks(&first).await.unwrap();
+            let second = BTreeMap::from([("orders".to_string(), "250".to_string())]);
+            store.save_watermarks(&second).await.unwrap();
+        }
+
+        let store = RedbStateStore::open(&db_path).unwrap();
+        let loaded = store.load_watermarks().await.unwrap();
+        assert_eq!(loaded.len(), 2);
+        assert_eq!(loaded["orders"], "250");
+        assert_eq!(loaded["users"], "\"2026-01-01\"");
+    }
+
     // --- crash safety tests ---
 
     #[tokio::test]
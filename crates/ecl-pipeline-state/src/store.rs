@@ -11,7 +11,8 @@ use crate::checkpoint::Checkpoint;
 use crate::error::StateError;
 use crate::ids::{Blake3Hash, RunId};
 
-/// Persistent state storage for pipeline checkpoints and content hashes.
+/// Persistent state storage for pipeline checkpoints, content hashes, and
+/// source watermarks.
 ///
 /// Implementations must be crash-safe: either the full checkpoint
 /// is persisted or none of it is. redb provides this via ACID
@@ -39,4 +40,15 @@ pub trait StateStore: Send + Sync {
         run_id: &RunId,
         hashes: &BTreeMap<String, Blake3Hash>,
     ) -> std::result::Result<(), StateError>;
+
+    /// Load per-source incremental watermarks recorded by earlier runs.
+    /// Key: source name.
+    async fn load_watermarks(&self) -> std::result::Result<BTreeMap<String, String>, StateError>;
+
+    /// Record per-source watermarks at the end of a successful run.
+    /// Sources absent from `watermarks` keep their previous value.
+    async fn save_watermarks(
+        &self,
+        watermarks: &BTreeMap<String, String>,
+    ) -> std::result::Result<(), StateError>;
 }
@@ -7,6 +7,8 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 
+use ecl_pipeline_spec::lifecycle::LifecycleAction;
+
 use crate::ids::{Blake3Hash, StageId};
 
 /// Overall pipeline execution status.
@@ -65,6 +67,12 @@ pub struct SourceState {
     /// Per-item state for items that entered the pipeline.
     /// Key: source-specific item ID.
     pub items: BTreeMap<String, ItemState>,
+
+    /// Incremental watermark reached by this run's enumeration, for sources
+    /// that track one. Persisted via `StateStore::save_watermarks` once the
+    /// run completes without failures from this source.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub watermark: Option<String>,
 }
 
 /// The state of a single item flowing through the pipeline.
@@ -91,6 +99,11 @@ pub struct ItemState {
 
     /// Provenance: where did this item come from and when?
     pub provenance: ItemProvenance,
+
+    /// File lifecycle action applied to the item's source file after the
+    /// run, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lifecycle: Option<LifecycleRecord>,
 }
 
 /// Processing status of a single pipeline item.
@@ -153,6 +166,23 @@ pub struct CompletedStageRecord {
     pub duration_ms: u64,
 }
 
+/// Record of a file lifecycle action (move or delete) applied to an
+/// item's source file, kept in the checkpoint for auditing.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LifecycleRecord {
+    /// The action taken.
+    pub action: LifecycleAction,
+    /// The file's location before the action.
+    pub from: String,
+    /// The file's new location; `None` for deletions.
+    pub to: Option<String>,
+    /// When the action was applied.
+    pub applied_at: DateTime<Utc>,
+    /// Why the action failed, if it did.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
 /// Provenance information for a pipeline item.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ItemProvenance {
@@ -296,6 +326,7 @@ mod tests {
                 source_modified: Some(test_time()),
                 extracted_at: test_time(),
             },
+            lifecycle: None,
         };
         let json = serde_json::to_string(&item).unwrap();
         let deserialized: ItemState = serde_json::from_str(&json).unwrap();
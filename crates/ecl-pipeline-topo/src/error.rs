@@ -1,5 +1,6 @@
 //! Error types for the topology layer.
 
+use ecl_pipeline_spec::SchemaError;
 use ecl_pipeline_state::StageId;
 use thiserror::Error;
 
@@ -61,6 +62,16 @@ pub enum ResolveError {
         /// The adapter name that was not recognized.
         adapter: String,
     },
+
+    /// A stage's records do not match the schemas of the streams it reads
+    /// or writes.
+    #[error("schema error in stage '{stage}': {source}")]
+    Schema {
+        /// The stage whose schema check failed.
+        stage: String,
+        /// The underlying schema mismatch.
+        source: SchemaError,
+    },
 }
 
 /// Errors that occur in source adapters.
@@ -3,6 +3,7 @@
 //! This crate defines:
 //! - The resolved pipeline topology (`PipelineTopology`, `ResolvedStage`)
 //! - Resource graph computation and parallel schedule derivation
+//! - Record schema propagation and checking along the schedule
 //! - Core traits (`SourceAdapter`, `Stage`) and their supporting types
 //!   (`PipelineItem`, `SourceItem`, `ExtractedDocument`, `StageContext`)
 //!
@@ -19,12 +20,13 @@ pub mod error;
 pub mod resolve;
 pub mod resource_graph;
 pub mod schedule;
+pub mod schema;
 pub mod traits;
 
 pub use error::{ResolveError, ResolveResult, SourceError, StageError};
 pub use traits::{
-    ExtractedDocument, PipelineItem, PushSourceAdapter, Record, SourceAdapter, SourceItem, Stage,
-    StageContext,
+    ExtractedDocument, PipelineItem, PushSourceAdapter, RECORD_MIME_TYPE, Record, SourceAdapter,
+    SourceItem, Stage, StageContext,
 };
 
 use std::collections::BTreeMap;
@@ -34,7 +36,7 @@ use std::time::Duration;
 
 use serde::{Deserialize, Serialize};
 
-use ecl_pipeline_spec::PipelineSpec;
+use ecl_pipeline_spec::{PipelineSpec, SchemaError, StreamSchemas};
 use ecl_pipeline_state::{Blake3Hash, StageId};
 
 /// The resolved pipeline, ready to execute.
@@ -66,6 +68,22 @@ pub struct PipelineTopology {
 
     /// Resolved output directory (created if needed at init).
     pub output_dir: PathBuf,
+
+    /// Record schemas of the streams, declared in the spec or inferred
+    /// from the stages that produce them.
+    pub schemas: StreamSchemas,
+}
+
+impl PipelineTopology {
+    /// Generate the Avro record schema for a stream's records, e.g. for a
+    /// Kafka sink reading that stream.
+    ///
+    /// Returns `None` if the stream's schema is not known.
+    pub fn avro_schema(&self, stream: &str) -> Option<Result<serde_json::Value, SchemaError>> {
+        self.schemas
+            .get(stream)
+            .map(|schema| schema.to_avro(stream))
+    }
 }
 
 /// A resolved stage: the concrete implementation with merged configuration.
@@ -305,6 +323,7 @@ resources = { creates = ["docs"] }
             stages: stages_map,
             schedule: vec![vec![StageId::new("extract")]],
             output_dir: PathBuf::from("./out"),
+            schemas: StreamSchemas::new(),
         };
 
         assert_eq!(topo.spec.name, "test");
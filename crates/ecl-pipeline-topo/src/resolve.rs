@@ -7,7 +7,8 @@
 //! 4. Merge stage-level retry overrides with global defaults.
 //! 5. Build the resource graph and validate.
 //! 6. Compute the parallel execution schedule.
-//! 7. Create the output directory (async).
+//! 7. Propagate and check record schemas along the schedule.
+//! 8. Create the output directory (async).
 
 use std::collections::BTreeMap;
 use std::sync::Arc;
@@ -18,6 +19,7 @@ use ecl_pipeline_state::{Blake3Hash, StageId};
 
 use crate::error::ResolveError;
 use crate::resource_graph::ResourceGraph;
+use crate::schema::propagate_schemas;
 use crate::{ConditionExpr, PipelineTopology, ResolvedStage, RetryPolicy, SourceAdapter, Stage};
 
 /// Resolve a `PipelineSpec` into a `PipelineTopology`.
@@ -31,7 +33,9 @@ use crate::{ConditionExpr, PipelineTopology, ResolvedStage, RetryPolicy, SourceA
 /// 4. Merge retry policies (stage override > global default).
 /// 5. Build the resource graph and validate (no missing inputs, no cycles).
 /// 6. Compute the parallel schedule.
-/// 7. Create the output directory.
+/// 7. Propagate record schemas through the stages and check every field
+///    reference against them.
+/// 8. Create the output directory.
 ///
 /// # Arguments
 ///
@@ -44,7 +48,7 @@ use crate::{ConditionExpr, PipelineTopology, ResolvedStage, RetryPolicy, SourceA
 /// # Errors
 ///
 /// Returns `ResolveError` if any step fails (unknown adapter, cycle,
-/// missing resource, I/O error, etc.).
+/// missing resource, schema mismatch, I/O error, etc.).
 pub async fn resolve<F, G>(
     spec: PipelineSpec,
     adapter_lookup: F,
@@ -64,11 +68,12 @@ where
     let spec = Arc::new(spec);
 
     // 2. Resolve each pull-based source into a concrete adapter.
-    //    Push sources (e.g., Zapier webhooks) are resolved separately —
-    //    they implement PushSourceAdapter, not SourceAdapter.
+    //    Push sources (e.g., Zapier webhooks, watched directories) are
+    //    resolved separately — they implement PushSourceAdapter, not
+    //    SourceAdapter.
     let mut sources: BTreeMap<String, Arc<dyn SourceAdapter>> = BTreeMap::new();
     for (name, source_spec) in &spec.sources {
-        if matches!(source_spec, SourceSpec::Zapier(_)) {
+        if source_spec.is_push() {
             continue; // Push sources resolved separately via push_sources field
         }
         let kind = source_kind(source_spec);
@@ -98,7 +103,10 @@ where
     // 5. Compute the parallel schedule.
     let schedule = resource_graph.compute_schedule()?;
 
-    // 6. Create output directory (async to avoid blocking the runtime).
+    // 6. Propagate and check record schemas.
+    let schemas = propagate_schemas(&spec, &stages, &schedule)?;
+
+    // 7. Create output directory (async to avoid blocking the runtime).
     let output_dir = spec.output_dir.clone();
     tokio::fs::create_dir_all(&output_dir)
         .await
@@ -112,6 +120,7 @@ where
         stages,
         schedule,
         output_dir,
+        schemas,
     })
 }
 
@@ -129,6 +138,8 @@ fn source_kind(spec: &SourceSpec) -> &'static str {
         SourceSpec::Zapier(_) => "zapier",
         SourceSpec::Gcs(_) => "gcs",
         SourceSpec::Sftp(_) => "sftp",
+        SourceSpec::Http(_) => "http",
+        SourceSpec::Sql(_) => "sql",
     }
 }
 
@@ -260,6 +271,8 @@ mod tests {
             SourceSpec::Zapier(_) => "zapier",
             SourceSpec::Gcs(_) => "gcs",
             SourceSpec::Sftp(_) => "sftp",
+            SourceSpec::Http(_) => "http",
+            SourceSpec::Sql(_) => "sql",
         };
         Ok(Arc::new(MockSourceAdapter::new(kind)))
     }
@@ -459,6 +472,7 @@ subdir = "normalized"
             root: PathBuf::from("/tmp"),
             filters: vec![],
             extensions: vec![],
+            watch: None,
             stream: None,
         });
         assert_eq!(source_kind(&spec), "filesystem");
@@ -0,0 +1,521 @@
+//! Record schema propagation through the resolved stages.
+//!
+//! Walks the execution schedule in order, tracking the schema of every
+//! stream that is live at each point. Document sources start each of their
+//! streams with an empty schema (raw items carry no record); record sources
+//! such as SQL start with the stream's declared schema, or an unknown one
+//! if none is declared. Each stage then maps the schemas of the streams it
+//! reads through `Stage::transform_schema`.
+//! Whenever a stage emits a known schema onto a stream with a declared
+//! schema, the two are checked against each other.
+
+use std::collections::BTreeMap;
+
+use ecl_pipeline_spec::{FieldType, PipelineSpec, SchemaError, StreamSchema, StreamSchemas};
+use ecl_pipeline_state::StageId;
+
+use crate::ResolvedStage;
+use crate::error::ResolveError;
+
+/// Stream name used for untagged items (sources without a `stream`).
+pub const DEFAULT_STREAM: &str = "_default";
+
+/// Live streams and their schemas; `None` means the schema is unknown.
+type StreamEnv = BTreeMap<String, Option<StreamSchema>>;
+
+/// Propagate record schemas through the stages in schedule order.
+///
+/// Returns every non-empty schema a stream was given along the way, keyed
+/// by stream name (a later stage emitting onto the same stream wins).
+/// Declared schemas take precedence over inferred ones.
+///
+/// # Errors
+///
+/// Returns `ResolveError::Schema` naming the first stage whose
+/// configuration references an unknown field, or whose output does not
+/// conform to the declared schema of the stream it emits onto.
+pub fn propagate_schemas(
+    spec: &PipelineSpec,
+    stages: &BTreeMap<String, ResolvedStage>,
+    schedule: &[Vec<StageId>],
+) -> Result<StreamSchemas, ResolveError> {
+    let mut env = StreamEnv::new();
+    for source in spec.sources.values() {
+        let stream = source.stream().unwrap_or(DEFAULT_STREAM);
+        let seed = if source.emits_records() {
+            spec.schemas.get(stream).cloned()
+        } else {
+            Some(StreamSchema::default())
+        };
+        // Sources sharing a stream but disagreeing on its schema leave it
+        // unknown.
+        match env.get(stream) {
+            Some(existing) if *existing != seed => {
+                env.insert(stream.to_string(), None);
+            }
+            _ => {
+                env.insert(stream.to_string(), seed);
+            }
+        }
+    }
+
+    let mut resolved = StreamSchemas::new();
+    for batch in schedule {
+        // Stages in a batch run concurrently against the same item pool,
+        // so they all see the streams as they were before the batch.
+        let snapshot = env.clone();
+        let mut placements = Vec::new();
+        for stage_id in batch {
+            let (Some(stage), Some(stage_spec)) = (
+                stages.get(stage_id.as_str()),
+                spec.stages.get(stage_id.as_str()),
+            ) else {
+                continue;
+            };
+
+            let selected: Vec<&String> = snapshot
+                .keys()
+                .filter(|s| {
+                    stage_spec.input_streams.is_empty()
+                        || s.as_str() == DEFAULT_STREAM
+                        || stage_spec.input_streams.contains(s)
+                })
+                .collect();
+            let inputs: StreamSchemas = selected
+                .iter()
+                .filter_map(|s| Some(((*s).clone(), snapshot.get(*s)?.clone()?)))
+                .collect();
+
+            let schema_err = |source| ResolveError::Schema {
+                stage: stage_id.as_str().to_string(),
+                source,
+            };
+            let described = stage
+                .handler
+                .transform_schema(&inputs)
+                .map_err(schema_err)?;
+            let opaque = described.is_none();
+            let mut outputs: StreamEnv = described
+                .unwrap_or_default()
+                .into_iter()
+                .map(|(s, p)| (s, Some(p)))
+                .collect();
+            // Streams whose schema the stage could not see stay unknown, as
+            // does everything when the stage cannot describe its output.
+            for stream in &selected {
+                if opaque || !inputs.contains_key(*stream) {
+                    outputs.entry((*stream).clone()).or_insert(None);
+                }
+            }
+            for stream in &selected {
+                env.remove(*stream);
+            }
+
+            match &stage_spec.output_stream {
+                Some(target) if !outputs.is_empty() => {
+                    let merged = outputs
+                        .into_values()
+                        .collect::<Option<Vec<_>>>()
+                        .map(|schemas| merge_schemas(&schemas));
+                    placements.push((stage_id, target.clone(), merged));
+                }
+                Some(_) => {}
+                None => placements.extend(outputs.into_iter().map(|(s, p)| (stage_id, s, p))),
+            }
+        }
+
+        for (stage_id, stream, produced) in placements {
+            let schema = match (produced, spec.schemas.get(&stream)) {
+                (Some(produced), Some(declared)) if !produced.fields.is_empty() => {
+                    declared
+                        .check_produced(&stream, &produced)
+                        .map_err(|source| ResolveError::Schema {
+                            stage: stage_id.as_str().to_string(),
+                            source,
+                        })?;
+                    Some(declared.clone())
+                }
+                // An unknown output falls back to the stream's declared schema.
+                (None, Some(declared)) => Some(declared.clone()),
+                (produced, _) => produced,
+            };
+            if let Some(schema) = schema.as_ref().filter(|s| !s.fields.is_empty()) {
+                resolved.insert(stream.clone(), schema.clone());
+            }
+            env.insert(stream, schema);
+        }
+    }
+
+    Ok(resolved)
+}
+
+/// Merge the schemas of several streams retagged onto one output stream.
+///
+/// Fields missing from some of the schemas become nullable, and fields
+/// whose types disagree become `any`.
+pub fn merge_schemas(schemas: &[StreamSchema]) -> StreamSchema {
+    let mut merged = StreamSchema::default();
+    for schema in schemas {
+        for field in &schema.fields {
+            let in_all = schemas.iter().all(|s| s.contains(&field.name));
+            match merged.fields.iter_mut().find(|f| f.name == field.name) {
+                Some(existing) => {
+                    if existing.field_type != field.field_type {
+                        existing.field_type = FieldType::Any;
+                    }
+                    existing.nullable |= field.nullable;
+                }
+                None => {
+                    let mut field = field.clone();
+                    field.nullable |= !in_all;
+                    merged.fields.push(field);
+                }
+            }
+        }
+    }
+    merged
+}
+
+/// Apply `f` to the schema of every input stream.
+///
+/// Convenience for `Stage::transform_schema` implementations that map each
+/// record independently and leave it on its stream.
+///
+/// # Errors
+///
+/// Returns the first error `f` returns.
+pub fn map_streams<F>(
+    inputs: &StreamSchemas,
+    mut f: F,
+) -> Result<Option<StreamSchemas>, SchemaError>
+where
+    F: FnMut(&str, &StreamSchema) -> Result<StreamSchema, SchemaError>,
+{
+    inputs
+        .iter()
+        .map(|(stream, schema)| Ok((stream.clone(), f(stream, schema)?)))
+        .collect::<Result<StreamSchemas, _>>()
+        .map(Some)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use ecl_pipeline_spec::FieldSchema;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use async_trait::async_trait;
+
+    use crate::{PipelineItem, RetryPolicy, Stage, StageContext, StageError};
+
+    /// Passes schemas through, optionally requiring and adding a field.
+    #[derive(Debug, Default)]
+    struct SchemaStage {
+        requires: Option<&'static str>,
+        adds: Option<FieldSchema>,
+        opaque: bool,
+    }
+
+    #[async_trait]
+    impl Stage for SchemaStage {
+        fn name(&self) -> &str {
+            "schema"
+        }
+
+        async fn process(
+            &self,
+            item: PipelineItem,
+            _ctx: &StageContext,
+        ) -> Result<Vec<PipelineItem>, StageError> {
+            Ok(vec![item])
+        }
+
+        fn transform_schema(
+            &self,
+            inputs: &StreamSchemas,
+        ) -> Result<Option<StreamSchemas>, SchemaError> {
+            if self.opaque {
+                return Ok(None);
+            }
+            map_streams(inputs, |stream, schema| {
+                if let Some(field) = self.requires {
+                    schema.require(stream, field)?;
+                }
+                let mut schema = schema.clone();
+                if let Some(field) = &self.adds {
+                    schema.set_field(field.clone());
+                }
+                Ok(schema)
+            })
+        }
+    }
+
+    fn resolved(name: &str, stage: SchemaStage) -> (String, ResolvedStage) {
+        (
+            name.to_string(),
+            ResolvedStage {
+                id: StageId::new(name),
+                handler: Arc::new(stage),
+                retry: RetryPolicy::default(),
+                skip_on_error: false,
+                timeout: Some(Duration::from_secs(60)),
+                source: None,
+                condition: None,
+            },
+        )
+    }
+
+    fn spec(extra: &str) -> PipelineSpec {
+        PipelineSpec::from_toml(&format!(
+            r#"
+name = "typed"
+version = 1
+output_dir = "./out"
+
+[schemas.txn]
+fields = [
+    {{ name = "id", type = "string" }},
+    {{ name = "amount", type = "float" }},
+]
+
+[sources.local]
+kind = "filesystem"
+root = "/tmp"
+stream = "txn"
+
+[stages.parse]
+adapter = "parse"
+resources = {{ creates = ["rows"] }}
+
+[stages.check]
+adapter = "check"
+resources = {{ reads = ["rows"] }}
+{extra}
+"#
+        ))
+        .unwrap()
+    }
+
+    fn schedule() -> Vec<Vec<StageId>> {
+        vec![vec![StageId::new("parse")], vec![StageId::new("check")]]
+    }
+
+    #[test]
+    fn test_declared_schema_flows_to_downstream_checks() {
+        let spec = spec("");
+        let stages = BTreeMap::from([
+            resolved(
+                "parse",
+                SchemaStage {
+                    adds: Some(FieldSchema::new("id", FieldType::String)),
+                    ..Default::default()
+                },
+            ),
+            resolved(
+                "check",
+                SchemaStage {
+                    requires: Some("amount"),
+                    ..Default::default()
+                },
+            ),
+        ]);
+        // `parse` only produces `id`, so the declared `amount` is missing.
+        let err = propagate_schemas(&spec, &stages, &schedule()).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "schema error in stage 'parse': stream 'txn' is missing declared field 'amount'"
+        );
+    }
+
+    #[test]
+    fn test_unknown_field_reference_is_reported_with_stage() {
+        let spec = spec("");
+        let stages = BTreeMap::from([
+            resolved(
+                "parse",
+                SchemaStage {
+                    opaque: true,
+                    ..Default::default()
+                },
+            ),
+            resolved(
+                "check",
+                SchemaStage {
+                    requires: Some("amout"),
+                    ..Default::default()
+                },
+            ),
+        ]);
+        // The opaque stage falls back to the declared schema for `txn`.
+        let err = propagate_schemas(&spec, &stages, &schedule()).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "schema error in stage 'check': stream 'txn' has no field 'amout' (did you mean 'amount'?)"
+        );
+    }
+
+    #[test]
+    fn test_output_stream_schema_is_inferred() {
+        let spec = spec("output_stream = \"enriched\"");
+        let stages = BTreeMap::from([
+            resolved(
+                "parse",
+                SchemaStage {
+                    opaque: true,
+                    ..Default::default()
+                },
+            ),
+            resolved(
+                "check",
+                SchemaStage {
+                    adds: Some(FieldSchema::new("score", FieldType::Integer)),
+                    ..Default::default()
+                },
+            ),
+        ]);
+        let schemas = propagate_schemas(&spec, &stages, &schedule()).unwrap();
+        let enriched = &schemas["enriched"];
+        assert!(enriched.contains("amount"));
+        assert_eq!(
+            enriched.field("score").unwrap().field_type,
+            FieldType::Integer
+        );
+    }
+
+    #[test]
+    fn test_undeclared_stream_without_schema_is_unchecked() {
+        let spec = PipelineSpec::from_toml(
+            r#"
+name = "untyped"
+version = 1
+output_dir = "./out"
+
+[sources.local]
+kind = "filesystem"
+root = "/tmp"
+
+[stages.parse]
+adapter = "parse"
+resources = { creates = ["rows"] }
+
+[stages.check]
+adapter = "check"
+resources = { reads = ["rows"] }
+"#,
+        )
+        .unwrap();
+        let stages = BTreeMap::from([
+            resolved(
+                "parse",
+                SchemaStage {
+                    opaque: true,
+                    ..Default::default()
+                },
+            ),
+            resolved(
+                "check",
+                SchemaStage {
+                    requires: Some("anything"),
+                    ..Default::default()
+                },
+            ),
+        ]);
+        assert!(
+            propagate_schemas(&spec, &stages, &schedule())
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    fn sql_spec(schemas: &str) -> PipelineSpec {
+        PipelineSpec::from_toml(&format!(
+            r#"
+name = "orders"
+version = 1
+output_dir = "./out"
+{schemas}
+[sources.db]
+kind = "sql"
+url = "sqlite://orders.db"
+query = "SELECT id, total FROM orders"
+stream = "orders"
+
+[stages.extract]
+adapter = "extract"
+resources = {{ creates = ["rows"] }}
+
+[stages.field_map]
+adapter = "field_map"
+resources = {{ reads = ["rows"] }}
+"#
+        ))
+        .unwrap()
+    }
+
+    fn sql_stages(requires: &'static str) -> BTreeMap<String, ResolvedStage> {
+        BTreeMap::from([
+            resolved("extract", SchemaStage::default()),
+            resolved(
+                "field_map",
+                SchemaStage {
+                    requires: Some(requires),
+                    ..Default::default()
+                },
+            ),
+        ])
+    }
+
+    fn sql_schedule() -> Vec<Vec<StageId>> {
+        vec![
+            vec![StageId::new("extract")],
+            vec![StageId::new("field_map")],
+        ]
+    }
+
+    #[test]
+    fn test_sql_source_starts_with_declared_schema() {
+        let spec = sql_spec(
+            r#"
+[schemas.orders]
+fields = [
+    { name = "id", type = "integer" },
+    { name = "total", type = "float" },
+]
+"#,
+        );
+        let schemas = propagate_schemas(&spec, &sql_stages("total"), &sql_schedule()).unwrap();
+        assert!(schemas["orders"].contains("total"));
+
+        let err = propagate_schemas(&spec, &sql_stages("totl"), &sql_schedule()).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "schema error in stage 'field_map': stream 'orders' has no field 'totl' (did you mean 'total'?)"
+        );
+    }
+
+    #[test]
+    fn test_sql_source_without_declared_schema_is_unchecked() {
+        let spec = sql_spec("");
+        let schemas = propagate_schemas(&spec, &sql_stages("total"), &sql_schedule()).unwrap();
+        assert!(schemas.is_empty());
+    }
+
+    #[test]
+    fn test_merge_schemas() {
+        let a = StreamSchema::new(vec![
+            FieldSchema::new("id", FieldType::String),
+            FieldSchema::new("n", FieldType::Integer),
+        ]);
+        let b = StreamSchema::new(vec![
+            FieldSchema::new("id", FieldType::String),
+            FieldSchema::new("n", FieldType::String),
+            FieldSchema::new("extra", FieldType::Boolean),
+        ]);
+        let merged = merge_schemas(&[a, b]);
+        assert!(!merged.field("id").unwrap().nullable);
+        assert_eq!(merged.field("n").unwrap().field_type, FieldType::Any);
+        assert!(merged.field("extra").unwrap().nullable);
+    }
+}
@@ -8,7 +8,7 @@ use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
-use ecl_pipeline_spec::PipelineSpec;
+use ecl_pipeline_spec::{PipelineSpec, SchemaError, StreamSchemas};
 use ecl_pipeline_state::{Blake3Hash, ItemProvenance};
 
 use crate::error::{SourceError, StageError};
@@ -20,6 +20,11 @@ use crate::error::{SourceError, StageError};
 /// (important for CSV column ordering).
 pub type Record = serde_json::Map<String, serde_json::Value>;
 
+/// MIME type of documents whose content is a single JSON-encoded [`Record`].
+/// The extract stage decodes such content into `PipelineItem::record`, so
+/// row-oriented sources (e.g. SQL) need no parse stage.
+pub const RECORD_MIME_TYPE: &str = "application/x-ecl-record";
+
 /// Custom serde module for `Arc<[u8]>` using serde_bytes for efficient
 /// binary serialization. `serde_bytes` doesn't natively support `Arc<[u8]>`,
 /// so we serialize via `&[u8]` and deserialize via `Vec<u8>` then convert.
@@ -74,6 +79,36 @@ pub trait SourceAdapter: Send + Sync + std::fmt::Debug {
     /// Separate from `enumerate()` because fetching is expensive and we
     /// want to skip unchanged items before paying this cost.
     async fn fetch(&self, item: &SourceItem) -> Result<ExtractedDocument, SourceError>;
+
+    /// Resume incremental enumeration from the watermark the previous
+    /// completed run recorded for this source. Called by the runner before
+    /// `enumerate()`. Default: ignored (the source is not incremental).
+    fn resume_from(&self, _watermark: Option<&str>) {}
+
+    /// The watermark reached by the most recent `enumerate()`, recorded for
+    /// the next run once this run completes. Default: `None`.
+    fn watermark(&self) -> Option<String> {
+        None
+    }
+
+    /// Move an item's original into `dest_dir`, for file lifecycle
+    /// management, and return its new location. How a relative `dest_dir`
+    /// is resolved is up to the adapter. Default: unsupported.
+    async fn move_item(&self, item_id: &str, _dest_dir: &str) -> Result<String, SourceError> {
+        Err(SourceError::Permanent {
+            source_name: self.source_kind().to_string(),
+            message: format!("cannot move '{item_id}': moving items is not supported"),
+        })
+    }
+
+    /// Delete an item's original, for file lifecycle management.
+    /// Default: unsupported.
+    async fn delete_item(&self, item_id: &str) -> Result<(), SourceError> {
+        Err(SourceError::Permanent {
+            source_name: self.source_kind().to_string(),
+            message: format!("cannot delete '{item_id}': deleting items is not supported"),
+        })
+    }
 }
 
 /// A push-based source adapter that receives data via external events
@@ -111,6 +146,13 @@ pub trait PushSourceAdapter: Send + Sync + std::fmt::Debug {
     /// After this call, the receiver returned by `start()` will eventually
     /// close once all buffered items have been consumed.
     async fn shutdown(&self) -> Result<(), SourceError>;
+
+    /// Called by the runner once a pushed document has been through every
+    /// stage. `succeeded` is false if any stage failed it. Adapters may use
+    /// this to acknowledge or archive the original. Default: no-op.
+    async fn complete(&self, _item_id: &str, _succeeded: bool) -> Result<(), SourceError> {
+        Ok(())
+    }
 }
 
 /// Lightweight item descriptor returned by `SourceAdapter::enumerate()`.
@@ -256,6 +298,27 @@ pub trait Stage: Send + Sync + std::fmt::Debug {
         }
         Ok(results)
     }
+
+    /// Describe how this stage transforms record schemas.
+    ///
+    /// `inputs` holds the known schemas of the streams this stage reads,
+    /// keyed by stream name. Return the schemas of the records this stage
+    /// emits, keyed by the stream they leave on; untagged records use
+    /// [`DEFAULT_STREAM`](crate::schema::DEFAULT_STREAM). Streams in
+    /// `inputs` that are absent from the result are consumed. Return an
+    /// error when the stage's configuration references a field the inputs
+    /// do not have.
+    ///
+    /// Default: `Ok(None)` — the output schema is unknown. Downstream
+    /// stages are then only checked against a declared schema, if the
+    /// stream has one.
+    fn transform_schema(
+        &self,
+        inputs: &StreamSchemas,
+    ) -> Result<Option<StreamSchemas>, SchemaError> {
+        let _ = inputs;
+        Ok(None)
+    }
 }
 
 /// Read-only context provided to stages during execution.
@@ -489,6 +552,14 @@ mod tests {
         assert_eq!(adapter.source_kind(), "mock");
     }
 
+    #[tokio::test]
+    async fn test_source_adapter_lifecycle_defaults_unsupported() {
+        let adapter: Arc<dyn SourceAdapter> = Arc::new(MockSourceAdapter);
+        let err = adapter.move_item("a.csv", "error/").await.unwrap_err();
+        assert!(err.to_string().contains("not supported"));
+        assert!(adapter.delete_item("a.csv").await.is_err());
+    }
+
     #[derive(Debug)]
     struct MockPushSourceAdapter;
 
@@ -516,6 +587,12 @@ mod tests {
         assert_eq!(adapter.source_kind(), "mock-push");
     }
 
+    #[tokio::test]
+    async fn test_push_source_adapter_default_complete() {
+        let adapter: Arc<dyn PushSourceAdapter> = Arc::new(MockPushSourceAdapter);
+        assert!(adapter.complete("item-1", false).await.is_ok());
+    }
+
     #[test]
     fn test_stage_is_object_safe() {
         let stage: Arc<dyn Stage> = Arc::new(MockStage);
@@ -447,6 +447,7 @@ mod tests {
                 secrets: Default::default(),
                 triggers: None,
                 schedule: None,
+                schemas: Default::default(),
             }),
             output_dir: PathBuf::from("/tmp/test"),
             params: serde_json::Value::Null,
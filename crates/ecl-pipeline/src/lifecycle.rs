@@ -1,20 +1,27 @@
-//! File lifecycle management for GCS-based pipelines.
+//! File lifecycle management for processed source files.
 //!
-//! After pipeline execution, moves processed files between GCS prefixes:
+//! After pipeline execution, moves processed files out of staging:
 //! - **On success**: `staging/` → `historical/{run_id}/` (or delete)
 //! - **On failure**: `staging/` → `input/` (or `error/`)
 //!
-//! Uses the GCS JSON API (copy + delete) for moves, reusing the
-//! `TokenProvider` from `ecl-adapter-gcs` for authentication.
+//! The decision is made once for the whole run, or per item when
+//! `LifecycleSpec::per_item` is set. GCS objects are moved with the GCS
+//! JSON API (copy + delete), reusing the `TokenProvider` from
+//! `ecl-adapter-gcs`; filesystem and SFTP files are moved by their source
+//! adapter. Each attempted move or delete is recorded on the item's state.
 
+use chrono::Utc;
 use ecl_adapter_gcs::auth::TokenProvider;
 use ecl_adapter_gcs::types::{GCS_API_BASE_URL, GCS_READWRITE_SCOPE};
+use ecl_pipeline_spec::SourceSpec;
 use ecl_pipeline_spec::lifecycle::{LifecycleAction, LifecycleSpec};
+use ecl_pipeline_state::{ItemStatus, LifecycleRecord, PipelineState};
+use ecl_pipeline_topo::{PipelineTopology, SourceAdapter};
 use tracing::{debug, warn};
 
 use crate::error::{PipelineError, Result};
 
-/// Manages file lifecycle operations on GCS.
+/// Manages file lifecycle operations on GCS, filesystem, and SFTP sources.
 #[derive(Debug)]
 pub struct LifecycleManager {
     spec: LifecycleSpec,
@@ -23,6 +30,18 @@ pub struct LifecycleManager {
     base_url: String,
 }
 
+/// Where an item's original file lives, which decides how it is moved.
+#[derive(Debug, Clone, Copy)]
+pub enum LifecycleTarget<'a> {
+    /// An object in a GCS bucket.
+    Gcs {
+        /// The bucket holding the object.
+        bucket: &'a str,
+    },
+    /// A file moved by its source adapter (filesystem, SFTP).
+    Adapter(&'a dyn SourceAdapter),
+}
+
 impl LifecycleManager {
     /// Create a new lifecycle manager from a `LifecycleSpec`.
     pub fn new(spec: &LifecycleSpec) -> Self {
@@ -52,116 +71,178 @@ impl LifecycleManager {
         self
     }
 
-    /// Execute the on-success lifecycle action.
+    /// Apply the lifecycle actions to every item in `state`, recording each
+    /// move or delete in `ItemState::lifecycle`.
     ///
-    /// Lists objects under the staging prefix and moves them to the
-    /// historical prefix (under a run-ID subdirectory).
-    pub async fn on_success(&self, run_id: &str, source_objects: &[String]) -> Result<()> {
-        match self.spec.on_success {
-            LifecycleAction::MoveToHistorical => {
-                let dest_prefix = format!(
-                    "{}{}/",
-                    self.spec.historical_prefix.trim_end_matches('/'),
-                    if run_id.is_empty() { "" } else { "/" }
-                );
-                let dest_prefix = if run_id.is_empty() {
-                    dest_prefix
-                } else {
-                    format!(
-                        "{}{}/",
-                        self.spec.historical_prefix.trim_end_matches('/'),
-                        format_args!("/{run_id}")
+    /// Failures are logged and recorded, never propagated. Items already
+    /// moved by an earlier attempt are left alone, as are items from
+    /// sources without lifecycle support (push sources, APIs).
+    pub async fn apply(&self, topology: &PipelineTopology, state: &mut PipelineState) {
+        let run_id = state.run_id.as_str().to_string();
+        let run_succeeded = state.stats.total_items_failed == 0;
+
+        for (source_name, source_state) in &mut state.sources {
+            let Some(target) = self.target_for(topology, source_name) else {
+                debug!(source = %source_name, "lifecycle: source does not support moves, skipping");
+                continue;
+            };
+
+            for item in source_state.items.values_mut() {
+                if item.lifecycle.as_ref().is_some_and(|r| r.error.is_none()) {
+                    continue;
+                }
+                let succeeded = if self.spec.per_item {
+                    !matches!(
+                        item.status,
+                        ItemStatus::Failed { .. } | ItemStatus::Skipped { .. }
                     )
+                } else {
+                    run_succeeded
                 };
-                self.move_objects(source_objects, &dest_prefix).await?;
-            }
-            LifecycleAction::Delete => {
-                self.delete_objects(source_objects).await?;
-            }
-            LifecycleAction::None => {
-                debug!("lifecycle on_success: no action configured");
-            }
-            _ => {
-                warn!(action = ?self.spec.on_success, "unexpected on_success action, skipping");
+                if let Some(record) = self
+                    .apply_item(target, &run_id, &item.source_id, succeeded)
+                    .await
+                {
+                    item.lifecycle = Some(record);
+                }
             }
         }
-        Ok(())
     }
 
-    /// Execute the on-failure lifecycle action.
+    /// Execute the on-success or on-failure action for a single object.
     ///
-    /// Depending on configuration, moves staging files back to input
-    /// or to an error prefix.
-    pub async fn on_failure(&self, source_objects: &[String]) -> Result<()> {
-        match self.spec.on_failure {
-            LifecycleAction::MoveToInput => {
-                // "Move to input" means removing the staging prefix.
-                // If staging_prefix is "staging/foo.csv", the input is just "foo.csv"
-                // at the bucket root (or wherever the original was).
-                // For simplicity, we move to the input prefix (which is the bucket root
-                // minus the staging prefix). In practice, the user configures where
-                // "input" lives. For Phase 1, we treat it as moving objects to the
-                // same name without the staging prefix — i.e., the object stays in
-                // place. This is a no-op since the pipeline reads from staging.
-                debug!(
-                    "lifecycle on_failure: move_to_input is a no-op in Phase 1 (files stay in staging for retry)"
-                );
+    /// Returns a record of the move or delete (including any error), or
+    /// `None` when the action leaves the file where it is.
+    pub async fn apply_item(
+        &self,
+        target: LifecycleTarget<'_>,
+        run_id: &str,
+        object: &str,
+        succeeded: bool,
+    ) -> Option<LifecycleRecord> {
+        let action = if succeeded {
+            &self.spec.on_success
+        } else {
+            &self.spec.on_failure
+        };
+
+        let outcome = match action {
+            LifecycleAction::MoveToHistorical => {
+                let dest_dir = join_prefix(&self.spec.historical_prefix, run_id);
+                self.move_object(target, object, &dest_dir).await.map(Some)
             }
             LifecycleAction::MoveToError => {
-                let error_prefix = format!("{}/", self.spec.error_prefix.trim_end_matches('/'));
-                self.move_objects(source_objects, &error_prefix).await?;
+                let dest_dir = join_prefix(&self.spec.error_prefix, "");
+                self.move_object(target, object, &dest_dir).await.map(Some)
+            }
+            LifecycleAction::Delete => self.delete_object(target, object).await.map(|()| None),
+            LifecycleAction::MoveToInput => {
+                // Files stay in staging, so the next run picks them up again.
+                debug!(object = %object, "lifecycle: move_to_input leaves file in staging for retry");
+                return None;
             }
             LifecycleAction::None => {
-                debug!("lifecycle on_failure: no action configured");
+                debug!(object = %object, "lifecycle: no action configured");
+                return None;
             }
-            _ => {
-                warn!(action = ?self.spec.on_failure, "unexpected on_failure action, skipping");
+        };
+
+        let (to, error) = match outcome {
+            Ok(to) => (to, None),
+            Err(e) => {
+                warn!(object = %object, action = ?action, error = %e, "lifecycle action failed (non-fatal)");
+                (None, Some(e.to_string()))
             }
-        }
-        Ok(())
+        };
+        Some(LifecycleRecord {
+            action: action.clone(),
+            from: object.to_string(),
+            to,
+            applied_at: Utc::now(),
+            error,
+        })
     }
 
-    /// Move objects by copying to a new prefix, then deleting the originals.
-    async fn move_objects(&self, objects: &[String], dest_prefix: &str) -> Result<()> {
-        let token = self.get_token().await?;
-
-        for object_name in objects {
-            let filename = object_name.rsplit('/').next().unwrap_or(object_name);
-            let dest_name = format!("{dest_prefix}{filename}");
-
-            self.copy_object(object_name, &dest_name, &token).await?;
-            self.delete_object(object_name, &token).await?;
-
-            debug!(
-                from = %object_name,
-                to = %dest_name,
-                "lifecycle: moved object"
-            );
+    /// Decide how items from `source_name` are moved, if at all.
+    fn target_for<'a>(
+        &'a self,
+        topology: &'a PipelineTopology,
+        source_name: &str,
+    ) -> Option<LifecycleTarget<'a>> {
+        match topology.spec.sources.get(source_name)? {
+            SourceSpec::Gcs(gcs) => Some(LifecycleTarget::Gcs {
+                bucket: if self.spec.bucket.is_empty() {
+                    &gcs.bucket
+                } else {
+                    &self.spec.bucket
+                },
+            }),
+            // Watched (push) sources are not in `topology.sources`.
+            SourceSpec::Filesystem(_) | SourceSpec::Sftp(_) => topology
+                .sources
+                .get(source_name)
+                .map(|adapter| LifecycleTarget::Adapter(adapter.as_ref())),
+            _ => None,
         }
-
-        Ok(())
     }
 
-    /// Delete objects from the bucket.
-    async fn delete_objects(&self, objects: &[String]) -> Result<()> {
-        let token = self.get_token().await?;
+    /// Move an object into `dest_dir` and return its new location.
+    async fn move_object(
+        &self,
+        target: LifecycleTarget<'_>,
+        object: &str,
+        dest_dir: &str,
+    ) -> Result<String> {
+        let dest = match target {
+            LifecycleTarget::Gcs { bucket } => {
+                let filename = object.rsplit('/').next().unwrap_or(object);
+                let dest_name = format!("{dest_dir}/{filename}");
+                let token = self.get_token().await?;
+                self.copy_object(bucket, object, &dest_name, &token).await?;
+                self.delete_object_gcs(bucket, object, &token).await?;
+                dest_name
+            }
+            LifecycleTarget::Adapter(adapter) => adapter
+                .move_item(object, dest_dir)
+                .await
+                .map_err(|e| PipelineError::Lifecycle {
+                    message: e.to_string(),
+                })?,
+        };
+
+        debug!(from = %object, to = %dest, "lifecycle: moved object");
+        Ok(dest)
+    }
 
-        for object_name in objects {
-            self.delete_object(object_name, &token).await?;
-            debug!(object = %object_name, "lifecycle: deleted object");
+    /// Delete an object.
+    async fn delete_object(&self, target: LifecycleTarget<'_>, object: &str) -> Result<()> {
+        match target {
+            LifecycleTarget::Gcs { bucket } => {
+                let token = self.get_token().await?;
+                self.delete_object_gcs(bucket, object, &token).await?;
+            }
+            LifecycleTarget::Adapter(adapter) => {
+                adapter
+                    .delete_item(object)
+                    .await
+                    .map_err(|e| PipelineError::Lifecycle {
+                        message: e.to_string(),
+                    })?;
+            }
         }
 
+        debug!(object = %object, "lifecycle: deleted object");
         Ok(())
     }
 
     /// Copy a GCS object to a new name within the same bucket.
-    async fn copy_object(&self, source: &str, dest: &str, token: &str) -> Result<()> {
+    async fn copy_object(&self, bucket: &str, source: &str, dest: &str, token: &str) -> Result<()> {
         let url = format!(
             "{}/b/{}/o/{}/copyTo/b/{}/o/{}",
             self.base_url,
-            self.spec.bucket,
+            bucket,
             urlencoded(source),
-            self.spec.bucket,
+            bucket,
             urlencoded(dest),
         );
 
@@ -187,11 +268,11 @@ impl LifecycleManager {
     }
 
     /// Delete a GCS object.
-    async fn delete_object(&self, object_name: &str, token: &str) -> Result<()> {
+    async fn delete_object_gcs(&self, bucket: &str, object_name: &str, token: &str) -> Result<()> {
         let url = format!(
             "{}/b/{}/o/{}",
             self.base_url,
-            self.spec.bucket,
+            bucket,
             urlencoded(object_name),
         );
 
@@ -228,6 +309,17 @@ impl LifecycleManager {
     }
 }
 
+/// Join a destination prefix and an optional subdirectory, without a
+/// trailing slash.
+fn join_prefix(prefix: &str, sub: &str) -> String {
+    let prefix = prefix.trim_end_matches('/');
+    if sub.is_empty() {
+        prefix.to_string()
+    } else {
+        format!("{prefix}/{sub}")
+    }
+}
+
 /// Minimal percent-encoding for GCS object names in URL paths.
 fn urlencoded(input: &str) -> String {
     let mut out = String::with_capacity(input.len());
@@ -250,8 +342,16 @@ fn urlencoded(input: &str) -> String {
 #[allow(clippy::unwrap_used)]
 mod tests {
     use super::*;
+    use ecl_adapter_fs::FilesystemAdapter;
     use ecl_pipeline_spec::CredentialRef;
     use ecl_pipeline_spec::lifecycle::LifecycleAction;
+    use ecl_pipeline_spec::source::FilesystemSourceSpec;
+    use std::fs;
+    use tempfile::TempDir;
+
+    const GCS: LifecycleTarget<'static> = LifecycleTarget::Gcs {
+        bucket: "test-bucket",
+    };
 
     fn test_spec() -> LifecycleSpec {
         LifecycleSpec {
@@ -261,10 +361,22 @@ mod tests {
             error_prefix: "error/".to_string(),
             on_success: LifecycleAction::MoveToHistorical,
             on_failure: LifecycleAction::MoveToError,
+            per_item: false,
             credentials: CredentialRef::ApplicationDefault,
         }
     }
 
+    fn fs_adapter(root: &std::path::Path) -> FilesystemAdapter {
+        let spec = FilesystemSourceSpec {
+            root: root.to_path_buf(),
+            filters: vec![],
+            extensions: vec![],
+            watch: None,
+            stream: None,
+        };
+        FilesystemAdapter::from_fs_spec("local", &spec).unwrap()
+    }
+
     #[test]
     fn test_urlencoded() {
         assert_eq!(urlencoded("simple.csv"), "simple.csv");
@@ -272,13 +384,22 @@ mod tests {
         assert_eq!(urlencoded("has space"), "has%20space");
     }
 
+    #[test]
+    fn test_join_prefix() {
+        assert_eq!(join_prefix("historical/", "run-1"), "historical/run-1");
+        assert_eq!(join_prefix("error/", ""), "error");
+        assert_eq!(join_prefix("error", ""), "error");
+    }
+
     #[tokio::test]
     async fn test_lifecycle_on_success_move_to_historical() {
         let mock_server = wiremock::MockServer::start().await;
 
         // Mock the copy endpoint.
         wiremock::Mock::given(wiremock::matchers::method("POST"))
-            .and(wiremock::matchers::path_regex(".*/copyTo/.*"))
+            .and(wiremock::matchers::path_regex(
+                ".*/b/test-bucket/o/.*/copyTo/.*",
+            ))
             .respond_with(
                 wiremock::ResponseTemplate::new(200)
                     .set_body_json(serde_json::json!({"name": "copied"})),
@@ -296,11 +417,14 @@ mod tests {
             .with_base_url(mock_server.uri())
             .with_token_provider(TokenProvider::static_token("test-token".to_string()));
 
-        let objects = vec![
-            "staging/file1.csv".to_string(),
-            "staging/file2.csv".to_string(),
-        ];
-        manager.on_success("run-001", &objects).await.unwrap();
+        let record = manager
+            .apply_item(GCS, "run-001", "staging/file1.csv", true)
+            .await
+            .unwrap();
+        assert_eq!(record.action, LifecycleAction::MoveToHistorical);
+        assert_eq!(record.from, "staging/file1.csv");
+        assert_eq!(record.to.as_deref(), Some("historical/run-001/file1.csv"));
+        assert!(record.error.is_none());
     }
 
     #[tokio::test]
@@ -319,8 +443,13 @@ mod tests {
             .with_base_url(mock_server.uri())
             .with_token_provider(TokenProvider::static_token("test-token".to_string()));
 
-        let objects = vec!["staging/file1.csv".to_string()];
-        manager.on_success("run-001", &objects).await.unwrap();
+        let record = manager
+            .apply_item(GCS, "run-001", "staging/file1.csv", true)
+            .await
+            .unwrap();
+        assert_eq!(record.action, LifecycleAction::Delete);
+        assert!(record.to.is_none());
+        assert!(record.error.is_none());
     }
 
     #[tokio::test]
@@ -330,7 +459,10 @@ mod tests {
 
         let manager = LifecycleManager::new(&spec);
         // Should succeed without any HTTP calls.
-        manager.on_success("run-001", &[]).await.unwrap();
+        let record = manager
+            .apply_item(GCS, "run-001", "staging/a.csv", true)
+            .await;
+        assert!(record.is_none());
     }
 
     #[tokio::test]
@@ -355,8 +487,12 @@ mod tests {
             .with_base_url(mock_server.uri())
             .with_token_provider(TokenProvider::static_token("test-token".to_string()));
 
-        let objects = vec!["staging/bad-file.csv".to_string()];
-        manager.on_failure(&objects).await.unwrap();
+        let record = manager
+            .apply_item(GCS, "run-001", "staging/bad-file.csv", false)
+            .await
+            .unwrap();
+        assert_eq!(record.action, LifecycleAction::MoveToError);
+        assert_eq!(record.to.as_deref(), Some("error/bad-file.csv"));
     }
 
     #[tokio::test]
@@ -365,11 +501,14 @@ mod tests {
         spec.on_failure = LifecycleAction::None;
 
         let manager = LifecycleManager::new(&spec);
-        manager.on_failure(&[]).await.unwrap();
+        let record = manager
+            .apply_item(GCS, "run-001", "staging/a.csv", false)
+            .await;
+        assert!(record.is_none());
     }
 
     #[tokio::test]
-    async fn test_lifecycle_copy_error_propagates() {
+    async fn test_lifecycle_copy_error_is_recorded() {
         let mock_server = wiremock::MockServer::start().await;
 
         wiremock::Mock::given(wiremock::matchers::method("POST"))
@@ -381,18 +520,17 @@ mod tests {
             .with_base_url(mock_server.uri())
             .with_token_provider(TokenProvider::static_token("test-token".to_string()));
 
-        let objects = vec!["staging/file.csv".to_string()];
-        let result = manager.on_success("run-001", &objects).await;
-        assert!(result.is_err());
-        let err = result.unwrap_err();
-        assert!(
-            err.to_string().contains("403"),
-            "error should contain status: {err}"
-        );
+        let record = manager
+            .apply_item(GCS, "run-001", "staging/file.csv", true)
+            .await
+            .unwrap();
+        assert!(record.to.is_none());
+        let err = record.error.unwrap();
+        assert!(err.contains("403"), "error should contain status: {err}");
     }
 
     #[tokio::test]
-    async fn test_lifecycle_delete_error_propagates() {
+    async fn test_lifecycle_delete_error_is_recorded() {
         let mock_server = wiremock::MockServer::start().await;
 
         // Copy succeeds but delete fails.
@@ -416,9 +554,11 @@ mod tests {
             .with_base_url(mock_server.uri())
             .with_token_provider(TokenProvider::static_token("test-token".to_string()));
 
-        let objects = vec!["staging/file.csv".to_string()];
-        let result = manager.on_success("run-001", &objects).await;
-        assert!(result.is_err());
+        let record = manager
+            .apply_item(GCS, "run-001", "staging/file.csv", true)
+            .await
+            .unwrap();
+        assert!(record.error.unwrap().contains("500"));
     }
 
     #[tokio::test]
@@ -427,8 +567,66 @@ mod tests {
         spec.on_failure = LifecycleAction::MoveToInput;
 
         let manager = LifecycleManager::new(&spec);
-        // Should succeed without HTTP calls — it's a no-op in Phase 1.
-        let objects = vec!["staging/file.csv".to_string()];
-        manager.on_failure(&objects).await.unwrap();
+        // Should succeed without HTTP calls — files stay in staging.
+        let record = manager
+            .apply_item(GCS, "run-001", "staging/file.csv", false)
+            .await;
+        assert!(record.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_lifecycle_filesystem_moves_and_deletes() {
+        let tmp = TempDir::new().unwrap();
+        let staging = tmp.path().join("staging");
+        fs::create_dir_all(&staging).unwrap();
+        fs::write(staging.join("good.csv"), "ok").unwrap();
+        fs::write(staging.join("bad.csv"), "bad").unwrap();
+        let adapter = fs_adapter(&staging);
+        let target = LifecycleTarget::Adapter(&adapter);
+
+        let manager = LifecycleManager::new(&test_spec());
+        let record = manager
+            .apply_item(target, "run-001", "good.csv", true)
+            .await
+            .unwrap();
+        let moved = staging.join("historical/run-001/good.csv");
+        assert_eq!(record.to.as_deref(), Some(moved.to_str().unwrap()));
+        assert!(moved.exists());
+
+        let record = manager
+            .apply_item(target, "run-001", "bad.csv", false)
+            .await
+            .unwrap();
+        assert!(record.error.is_none());
+        assert!(staging.join("error/bad.csv").exists());
+        assert!(!staging.join("bad.csv").exists());
+
+        let mut spec = test_spec();
+        spec.on_success = LifecycleAction::Delete;
+        fs::write(staging.join("done.csv"), "done").unwrap();
+        let record = LifecycleManager::new(&spec)
+            .apply_item(target, "run-002", "done.csv", true)
+            .await
+            .unwrap();
+        assert_eq!(record.action, LifecycleAction::Delete);
+        assert!(!staging.join("done.csv").exists());
+    }
+
+    #[tokio::test]
+    async fn test_lifecycle_filesystem_missing_file_is_recorded() {
+        let tmp = TempDir::new().unwrap();
+        let adapter = fs_adapter(tmp.path());
+
+        let record = LifecycleManager::new(&test_spec())
+            .apply_item(
+                LifecycleTarget::Adapter(&adapter),
+                "run-001",
+                "gone.csv",
+                true,
+            )
+            .await
+            .unwrap();
+        assert!(record.to.is_none());
+        assert!(record.error.unwrap().contains("gone.csv"));
     }
 }
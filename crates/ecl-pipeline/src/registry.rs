@@ -280,6 +280,7 @@ mod tests {
             root: std::path::PathBuf::from("/tmp"),
             filters: vec![],
             extensions: vec![],
+            watch: None,
             stream: None,
         });
         let result = registry.resolve("nonexistent", "my-source", &spec);
@@ -303,6 +304,7 @@ mod tests {
             root: std::path::PathBuf::from("/tmp"),
             filters: vec![],
             extensions: vec![],
+            watch: None,
             stream: None,
         });
         let adapter = registry.resolve("filesystem", "local", &spec).unwrap();
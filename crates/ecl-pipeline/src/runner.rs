@@ -142,6 +142,7 @@ impl PipelineRunner {
     /// 2. Apply incrementality (skip unchanged items).
     /// 3. Execute batches in order, checkpointing after each.
     /// 4. Save completed hashes and finalize state.
+    /// 5. Apply file lifecycle actions, if configured.
     ///
     /// Returns a reference to the final pipeline state.
     pub async fn run(&mut self) -> Result<&PipelineState> {
@@ -176,24 +177,18 @@ impl PipelineRunner {
 
         // Phase 4: Finalize.
         self.save_completed_hashes().await?;
+        self.save_watermarks().await?;
         self.state.status = PipelineStatus::Completed {
             finished_at: Utc::now(),
         };
         self.checkpoint().await?;
 
-        // Phase 5: File lifecycle management (if configured).
+        // Phase 5: File lifecycle management (if configured). Moves are
+        // recorded on each item, so checkpoint again afterwards.
         if let Some(ref lifecycle_spec) = self.topology.spec.lifecycle {
-            let source_objects = self.collect_source_object_ids();
             let manager = crate::lifecycle::LifecycleManager::new(lifecycle_spec);
-            let run_id = self.state.run_id.as_str();
-
-            if self.state.stats.total_items_failed == 0 {
-                if let Err(e) = manager.on_success(run_id, &source_objects).await {
-                    tracing::warn!(error = %e, "lifecycle on_success failed (non-fatal)");
-                }
-            } else if let Err(e) = manager.on_failure(&source_objects).await {
-                tracing::warn!(error = %e, "lifecycle on_failure failed (non-fatal)");
-            }
+            manager.apply(&self.topology, &mut self.state).await;
+            self.checkpoint().await?;
         }
 
         let duration_ms = run_start.elapsed().as_millis() as u64;
@@ -258,23 +253,37 @@ impl PipelineRunner {
             }
         }
 
-        // Collect results and merge into state.
+        // Merge every stage's result before reporting the first failure, so
+        // one failing stage does not discard its siblings' work.
+        let mut first_error: Option<PipelineError> = None;
         while let Some(result) = join_set.join_next().await {
-            let stage_result = result??;
-            self.merge_stage_result(stage_result)?;
+            let merged = match result {
+                Ok(Ok(stage_result)) => self.merge_stage_result(stage_result),
+                Ok(Err(e)) => Err(e),
+                Err(e) => Err(e.into()),
+            };
+            if let Err(e) = merged {
+                first_error.get_or_insert(e);
+            }
         }
 
-        Ok(())
+        match first_error {
+            Some(e) => Err(e),
+            None => Ok(()),
+        }
     }
 
     /// Enumerate all sources and populate the item list.
     ///
-    /// Calls `SourceAdapter::enumerate()` for each source in the topology.
-    /// Creates `ItemState` entries in `PipelineState::sources` for each
+    /// Calls `SourceAdapter::enumerate()` for each source in the topology,
+    /// first handing each adapter the watermark recorded by the previous
+    /// run. Creates `ItemState` entries in `PipelineState::sources` for each
     /// discovered item.
     async fn enumerate_sources(&mut self) -> Result<()> {
+        let previous_watermarks = self.store.load_watermarks().await?;
         for (name, adapter) in &self.topology.sources {
             tracing::info!(source = %name, "enumerating source");
+            adapter.resume_from(previous_watermarks.get(name).map(String::as_str));
             let items =
                 adapter
                     .enumerate()
@@ -288,6 +297,7 @@ impl PipelineRunner {
             let source_state = self.state.sources.entry(name.clone()).or_default();
 
             source_state.items_discovered = items.len();
+            source_state.watermark = adapter.watermark();
 
             // Look up the stream tag from the source spec.
             let stream_tag = self
@@ -315,6 +325,7 @@ impl PipelineRunner {
                         status: ItemStatus::Pending,
                         completed_stages: vec![],
                         provenance: provenance.clone(),
+                        lifecycle: None,
                     },
                 );
 
@@ -395,6 +406,29 @@ impl PipelineRunner {
         Ok(())
     }
 
+    /// Record source watermarks for the next run's incremental enumeration.
+    ///
+    /// A source's watermark only advances when none of its items failed,
+    /// so failed items are enumerated again on the next run.
+    async fn save_watermarks(&self) -> Result<()> {
+        let watermarks: BTreeMap<String, String> = self
+            .state
+            .sources
+            .iter()
+            .filter(|(_, source)| {
+                !source
+                    .items
+                    .values()
+                    .any(|item| matches!(item.status, ItemStatus::Failed { .. }))
+            })
+            .filter_map(|(name, source)| Some((name.clone(), source.watermark.clone()?)))
+            .collect();
+        if !watermarks.is_empty() {
+            self.store.save_watermarks(&watermarks).await?;
+        }
+        Ok(())
+    }
+
     /// Determine whether a stage should execute.
     ///
     /// Currently always returns true. Condition expression evaluation
@@ -475,7 +509,14 @@ impl PipelineRunner {
 
             for source_state in self.state.sources.values_mut() {
                 if let Some(item_state) = source_state.items.get_mut(&success.item_id) {
-                    item_state.status = ItemStatus::Completed;
+                    // A concurrent stage in the same batch may already have
+                    // failed or skipped the item; that outcome stands.
+                    if !matches!(
+                        item_state.status,
+                        ItemStatus::Failed { .. } | ItemStatus::Skipped { .. }
+                    ) {
+                        item_state.status = ItemStatus::Completed;
+                    }
                     item_state
                         .completed_stages
                         .push(ecl_pipeline_state::CompletedStageRecord {
@@ -549,6 +590,8 @@ impl PipelineRunner {
             }
         }
 
+        self.state.update_stats();
+
         // If any items hard-failed, propagate as error.
         if let Some(first_failure) = result.failures.first() {
             return Err(PipelineError::ItemFailed {
@@ -558,22 +601,9 @@ impl PipelineRunner {
             });
         }
 
-        self.state.update_stats();
         Ok(())
     }
 
-    /// Collect source object IDs (e.g., GCS object names) from all sources.
-    ///
-    /// Returns the original source IDs for all discovered items. Used by
-    /// the lifecycle manager to move processed files.
-    fn collect_source_object_ids(&self) -> Vec<String> {
-        self.state
-            .sources
-            .values()
-            .flat_map(|source| source.items.values().map(|item| item.source_id.clone()))
-            .collect()
-    }
-
     /// Get a reference to the current pipeline state.
     pub fn state(&self) -> &PipelineState {
         &self.state
@@ -665,8 +695,16 @@ impl PipelineRunner {
                 continue;
             }
 
-            // Convert documents to item states and pipeline items.
+            // Convert documents to item states and pipeline items. Items
+            // left in the pool by the previous batch are final outputs.
+            self.active_items.clear();
             for (source_name, doc) in &batch {
+                let stream_tag = self
+                    .topology
+                    .spec
+                    .sources
+                    .get(source_name)
+                    .and_then(|spec| spec.stream().map(|s| s.to_string()));
                 let source_state = self.state.sources.entry(source_name.clone()).or_default();
                 source_state.items_discovered += 1;
                 source_state.items_accepted += 1;
@@ -680,16 +718,42 @@ impl PipelineRunner {
                         status: ItemStatus::Pending,
                         completed_stages: vec![],
                         provenance: doc.provenance.clone(),
+                        lifecycle: None,
                     },
                 );
+                self.active_items.push(PipelineItem {
+                    id: doc.id.clone(),
+                    display_name: doc.display_name.clone(),
+                    content: Arc::from(doc.content.as_slice()),
+                    mime_type: doc.mime_type.clone(),
+                    source_name: source_name.clone(),
+                    source_content_hash: doc.content_hash.clone(),
+                    provenance: doc.provenance.clone(),
+                    metadata: BTreeMap::new(),
+                    record: None,
+                    stream: stream_tag,
+                });
             }
             self.state.update_stats();
 
-            // Execute the batch through all stage batches.
+            // Execute the batch through all stage batches. A failed item is
+            // recorded in its state; the listener keeps serving the rest.
             let schedule = self.topology.schedule.clone();
             for (batch_idx, stage_batch) in schedule.iter().enumerate() {
-                self.execute_batch(batch_idx, stage_batch).await?;
+                match self.execute_batch(batch_idx, stage_batch).await {
+                    Ok(()) => {}
+                    Err(PipelineError::ItemFailed {
+                        stage,
+                        item_id,
+                        error,
+                    }) => {
+                        tracing::warn!(stage = %stage, item = %item_id, "push item failed: {error}");
+                        self.state.update_stats();
+                    }
+                    Err(e) => return Err(e),
+                }
             }
+            self.complete_push_items(&batch).await;
             self.checkpoint().await?;
 
             tracing::info!(items = batch.len(), "push source batch processed");
@@ -708,6 +772,29 @@ impl PipelineRunner {
 
         Ok(())
     }
+
+    /// Report each pushed document's outcome back to its adapter.
+    ///
+    /// A document succeeded only if a stage's success for it was merged
+    /// (its status is `Completed`); failed, skipped and never-merged
+    /// documents are reported as failed. Adapter errors are logged, not
+    /// propagated: the document has already been processed.
+    async fn complete_push_items(&self, batch: &[(String, ExtractedDocument)]) {
+        for (source_name, doc) in batch {
+            let Some(adapter) = self.topology.push_sources.get(source_name) else {
+                continue;
+            };
+            let succeeded = self
+                .state
+                .sources
+                .get(source_name)
+                .and_then(|source| source.items.get(&doc.id))
+                .is_some_and(|item| matches!(item.status, ItemStatus::Completed));
+            if let Err(e) = adapter.complete(&doc.id, succeeded).await {
+                tracing::warn!(source = %source_name, item = %doc.id, "push item completion failed: {e}");
+            }
+        }
+    }
 }
 
 /// Check whether an item's stream matches a stage's input_streams filter.
@@ -815,6 +902,40 @@ mod tests {
         }
     }
 
+    /// A source adapter that tracks an incremental watermark.
+    #[derive(Debug, Default)]
+    struct WatermarkSourceAdapter {
+        resumed_from: std::sync::Mutex<Option<String>>,
+    }
+
+    #[async_trait::async_trait]
+    impl SourceAdapter for WatermarkSourceAdapter {
+        fn source_kind(&self) -> &str {
+            "watermark"
+        }
+
+        async fn enumerate(&self) -> std::result::Result<Vec<SourceItem>, SourceError> {
+            Ok(vec![make_source_item("a")])
+        }
+
+        async fn fetch(
+            &self,
+            item: &SourceItem,
+        ) -> std::result::Result<ExtractedDocument, SourceError> {
+            MockSourceAdapter::new("watermark", vec![])
+                .fetch(item)
+                .await
+        }
+
+        fn resume_from(&self, watermark: Option<&str>) {
+            *self.resumed_from.lock().unwrap() = watermark.map(str::to_string);
+        }
+
+        fn watermark(&self) -> Option<String> {
+            Some("w2".to_string())
+        }
+    }
+
     #[derive(Debug)]
     struct MockStage {
         name: String,
@@ -867,6 +988,101 @@ mod tests {
         }
     }
 
+    /// A stage that fails one item and passes the rest through.
+    #[derive(Debug)]
+    struct FailOneStage {
+        fail_id: String,
+    }
+
+    #[async_trait::async_trait]
+    impl Stage for FailOneStage {
+        fn name(&self) -> &str {
+            "fail-one"
+        }
+
+        async fn process(
+            &self,
+            item: PipelineItem,
+            _ctx: &StageContext,
+        ) -> std::result::Result<Vec<PipelineItem>, StageError> {
+            if item.id == self.fail_id {
+                return Err(StageError::Permanent {
+                    stage: "fail-one".to_string(),
+                    item_id: item.id.clone(),
+                    message: "bad document".to_string(),
+                });
+            }
+            Ok(vec![item])
+        }
+    }
+
+    /// A push source that sends a fixed set of documents and then closes,
+    /// recording the outcome reported for each.
+    #[derive(Debug)]
+    struct MockPushSourceAdapter {
+        ids: Vec<String>,
+        completed: std::sync::Mutex<Vec<(String, bool)>>,
+    }
+
+    #[async_trait::async_trait]
+    impl PushSourceAdapter for MockPushSourceAdapter {
+        fn source_kind(&self) -> &str {
+            "mock-push"
+        }
+
+        async fn start(
+            &self,
+        ) -> std::result::Result<tokio::sync::mpsc::Receiver<ExtractedDocument>, SourceError>
+        {
+            let (tx, rx) = tokio::sync::mpsc::channel(self.ids.len());
+            for id in &self.ids {
+                let item = make_source_item(id);
+                let doc = MockSourceAdapter::new("mock-push", vec![])
+                    .fetch(&item)
+                    .await?;
+                tx.try_send(doc).unwrap();
+            }
+            Ok(rx)
+        }
+
+        async fn shutdown(&self) -> std::result::Result<(), SourceError> {
+            Ok(())
+        }
+
+        async fn complete(
+            &self,
+            item_id: &str,
+            succeeded: bool,
+        ) -> std::result::Result<(), SourceError> {
+            self.completed
+                .lock()
+                .unwrap()
+                .push((item_id.to_string(), succeeded));
+            Ok(())
+        }
+    }
+
+    /// Passes items through after a delay, so it finishes after its
+    /// siblings in the same batch.
+    #[derive(Debug)]
+    struct SlowStage;
+
+    #[async_trait::async_trait]
+    impl Stage for SlowStage {
+        fn name(&self) -> &str {
+            "slow"
+        }
+
+        async fn process(
+            &self,
+            item: PipelineItem,
+            _ctx: &StageContext,
+        ) -> std::result::Result<Vec<PipelineItem>, StageError> {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            Ok(vec![item])
+        }
+    }
+
     // ── Test helpers ────────────────────────────────────────────────────
 
     fn make_source_item(id: &str) -> SourceItem {
@@ -893,6 +1109,7 @@ mod tests {
                     root: PathBuf::from("/tmp/test"),
                     filters: vec![],
                     extensions: vec![],
+                    watch: None,
                     stream: None,
                 }),
             );
@@ -928,6 +1145,7 @@ mod tests {
             secrets: Default::default(),
             triggers: None,
             schedule: None,
+            schemas: Default::default(),
         });
 
         let topo_sources: BTreeMap<String, Arc<dyn SourceAdapter>> = sources.into_iter().collect();
@@ -964,6 +1182,7 @@ mod tests {
             stages: resolved_stages,
             schedule,
             output_dir: PathBuf::from("/tmp/test-output"),
+            schemas: Default::default(),
         }
     }
 
@@ -1112,6 +1331,7 @@ mod tests {
                     source_modified: None,
                     extracted_at: Utc::now(),
                 },
+                lifecycle: None,
             },
         );
         sources.insert(
@@ -1121,6 +1341,7 @@ mod tests {
                 items_accepted: 1,
                 items_skipped_unchanged: 0,
                 items: items.clone(),
+                watermark: None,
             },
         );
 
@@ -1437,6 +1658,66 @@ mod tests {
         assert_eq!(hashes["a"].as_str(), "hash-a");
     }
 
+    #[tokio::test]
+    async fn test_watermarks_resume_and_advance() {
+        let adapter = Arc::new(WatermarkSourceAdapter::default());
+        let topo = build_test_topology(
+            vec![("src".to_string(), adapter.clone())],
+            vec![(
+                "stage-a".to_string(),
+                Arc::new(MockStage::new("stage-a")),
+                None,
+                false,
+            )],
+        );
+        let store = InMemoryStateStore::new();
+        store
+            .save_watermarks(&BTreeMap::from([("src".to_string(), "w1".to_string())]))
+            .await
+            .unwrap();
+
+        let mut runner = PipelineRunner::new(topo, Box::new(store)).await.unwrap();
+        runner.run().await.unwrap();
+
+        assert_eq!(adapter.resumed_from.lock().unwrap().as_deref(), Some("w1"));
+        assert_eq!(
+            runner.state().sources["src"].watermark.as_deref(),
+            Some("w2")
+        );
+        let saved = runner.store.load_watermarks().await.unwrap();
+        assert_eq!(saved["src"], "w2");
+    }
+
+    #[tokio::test]
+    async fn test_watermark_not_advanced_when_items_failed() {
+        let topo = build_test_topology(
+            vec![(
+                "src".to_string(),
+                Arc::new(WatermarkSourceAdapter::default()),
+            )],
+            vec![],
+        );
+        let mut runner = PipelineRunner::new(topo, Box::new(InMemoryStateStore::new()))
+            .await
+            .unwrap();
+        runner.enumerate_sources().await.unwrap();
+        if let Some(item) = runner
+            .state
+            .sources
+            .get_mut("src")
+            .and_then(|source| source.items.get_mut("a"))
+        {
+            item.status = ItemStatus::Failed {
+                stage: "stage-a".to_string(),
+                error: "boom".to_string(),
+                attempts: 1,
+            };
+        }
+
+        runner.save_watermarks().await.unwrap();
+        assert!(runner.store.load_watermarks().await.unwrap().is_empty());
+    }
+
     // ── Full run() lifecycle tests ──────────────────────────────────────
 
     #[tokio::test]
@@ -1624,6 +1905,7 @@ mod tests {
                     source_modified: None,
                     extracted_at: Utc::now(),
                 },
+                lifecycle: None,
             },
         );
         let mut sources = BTreeMap::new();
@@ -1634,6 +1916,7 @@ mod tests {
                 items_accepted: 1,
                 items_skipped_unchanged: 0,
                 items,
+                watermark: None,
             },
         );
         let checkpoint = Checkpoint {
@@ -2105,4 +2388,82 @@ mod tests {
         assert_eq!(runner.active_items.len(), 1);
         assert_eq!(runner.active_items[0].id, "a-out");
     }
+
+    #[tokio::test]
+    async fn test_push_items_run_through_stages_and_complete() {
+        let mut topo = build_test_topology(
+            vec![],
+            vec![(
+                "stage-a".to_string(),
+                Arc::new(FailOneStage {
+                    fail_id: "bad".to_string(),
+                }),
+                None,
+                false,
+            )],
+        );
+        let push = Arc::new(MockPushSourceAdapter {
+            ids: vec!["good".to_string(), "bad".to_string()],
+            completed: std::sync::Mutex::new(vec![]),
+        });
+        topo.push_sources.insert(
+            "drop".to_string(),
+            push.clone() as Arc<dyn PushSourceAdapter>,
+        );
+        let store = Box::new(InMemoryStateStore::new());
+        let mut runner = PipelineRunner::new(topo, store).await.unwrap();
+
+        // A failed pushed item does not stop the run.
+        let state = runner.run().await.unwrap();
+        let items = &state.sources["drop"].items;
+        assert!(matches!(items["good"].status, ItemStatus::Completed));
+        assert!(matches!(items["bad"].status, ItemStatus::Failed { .. }));
+        assert_eq!(state.stages[&StageId::new("stage-a")].items_processed, 1);
+        assert_eq!(
+            *push.completed.lock().unwrap(),
+            vec![("good".to_string(), true), ("bad".to_string(), false)]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_failed_stage_does_not_drop_concurrent_results() {
+        let mut topo = build_test_topology(
+            vec![],
+            vec![
+                (
+                    "stage-a".to_string(),
+                    Arc::new(FailOneStage {
+                        fail_id: "bad".to_string(),
+                    }),
+                    None,
+                    false,
+                ),
+                ("stage-b".to_string(), Arc::new(SlowStage), None, false),
+            ],
+        );
+        // Both stages run in the same batch; stage-a fails first.
+        topo.schedule = vec![vec![StageId::new("stage-a"), StageId::new("stage-b")]];
+        let push = Arc::new(MockPushSourceAdapter {
+            ids: vec!["good".to_string(), "bad".to_string()],
+            completed: std::sync::Mutex::new(vec![]),
+        });
+        topo.push_sources.insert(
+            "drop".to_string(),
+            push.clone() as Arc<dyn PushSourceAdapter>,
+        );
+        let store = Box::new(InMemoryStateStore::new());
+        let mut runner = PipelineRunner::new(topo, store).await.unwrap();
+
+        let state = runner.run().await.unwrap();
+        let slow = &state.stages[&StageId::new("stage-b")];
+        assert_eq!(slow.items_processed, 2);
+        assert!(matches!(slow.status, StageStatus::Completed));
+        let items = &state.sources["drop"].items;
+        assert!(matches!(items["good"].status, ItemStatus::Completed));
+        assert!(matches!(items["bad"].status, ItemStatus::Failed { .. }));
+        assert_eq!(
+            *push.completed.lock().unwrap(),
+            vec![("good".to_string(), true), ("bad".to_string(), false)]
+        );
+    }
 }
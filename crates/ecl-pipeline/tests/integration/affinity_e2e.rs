@@ -185,6 +185,7 @@ fn make_context(output_dir: PathBuf, params: serde_json::Value) -> StageContext
             secrets: Default::default(),
             triggers: None,
             schedule: None,
+            schemas: Default::default(),
         }),
         output_dir,
         params,
@@ -548,6 +549,7 @@ fn build_affinity_topo(
         root: input_dir.to_path_buf(),
         filters: vec![],
         extensions: vec!["csv".to_string()],
+        watch: None,
         stream: None,
     };
     let adapter: Arc<dyn SourceAdapter> =
@@ -591,6 +593,7 @@ fn build_affinity_topo(
         secrets: Default::default(),
         triggers: None,
         schedule: None,
+        schemas: Default::default(),
     });
 
     let spec_hash_bytes = serde_json::to_string(&*spec).unwrap();
@@ -615,6 +618,7 @@ fn build_affinity_topo(
         push_sources: BTreeMap::new(),
         schedule: vec![vec![StageId::new("affinity-pipeline")]],
         output_dir: output_dir.to_path_buf(),
+        schemas: Default::default(),
     }
 }
 
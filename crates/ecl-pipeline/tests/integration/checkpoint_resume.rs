@@ -34,6 +34,7 @@ fn build_simple_topo(
         root: input_dir.to_path_buf(),
         filters: vec![],
         extensions: vec![],
+        watch: None,
         stream: None,
     };
     let adapter: Arc<dyn SourceAdapter> =
@@ -112,6 +113,7 @@ fn build_simple_topo(
         secrets: Default::default(),
         triggers: None,
         schedule: None,
+        schemas: Default::default(),
     });
 
     let spec_hash_bytes = serde_json::to_string(&*spec).unwrap();
@@ -166,6 +168,7 @@ fn build_simple_topo(
             vec![StageId::new("emit")],
         ],
         output_dir: output_dir.to_path_buf(),
+        schemas: Default::default(),
     }
 }
 
@@ -214,6 +217,7 @@ async fn test_resume_from_checkpoint_completes() {
                 source_modified: None,
                 extracted_at: chrono::Utc::now(),
             },
+            lifecycle: None,
         },
     );
     items.insert(
@@ -231,6 +235,7 @@ async fn test_resume_from_checkpoint_completes() {
                 source_modified: None,
                 extracted_at: chrono::Utc::now(),
             },
+            lifecycle: None,
         },
     );
 
@@ -242,6 +247,7 @@ async fn test_resume_from_checkpoint_completes() {
             items_accepted: 2,
             items_skipped_unchanged: 0,
             items,
+            watermark: None,
         },
     );
 
@@ -345,6 +351,7 @@ async fn test_processing_items_reset_to_pending_on_resume() {
                 source_modified: None,
                 extracted_at: chrono::Utc::now(),
             },
+            lifecycle: None,
         },
     );
 
@@ -356,6 +363,7 @@ async fn test_processing_items_reset_to_pending_on_resume() {
             items_accepted: 1,
             items_skipped_unchanged: 0,
             items,
+            watermark: None,
         },
     );
 
@@ -96,6 +96,7 @@ fn build_single_stage_topo(
         root: input_dir.to_path_buf(),
         filters: vec![],
         extensions: vec![],
+        watch: None,
         stream: None,
     };
     let adapter: Arc<dyn SourceAdapter> =
@@ -130,6 +131,7 @@ fn build_single_stage_topo(
         secrets: Default::default(),
         triggers: None,
         schedule: None,
+        schemas: Default::default(),
     });
 
     let spec_hash_bytes = serde_json::to_string(&*spec).unwrap();
@@ -155,6 +157,7 @@ fn build_single_stage_topo(
         push_sources: BTreeMap::new(),
         schedule: vec![vec![stage_id]],
         output_dir: output_dir.to_path_buf(),
+        schemas: Default::default(),
     }
 }
 
@@ -111,6 +111,7 @@ fn build_fs_pipeline(
         root: input_dir.to_path_buf(),
         filters: vec![],
         extensions: vec![],
+        watch: None,
         stream: None,
     };
     let adapter: Arc<dyn SourceAdapter> =
@@ -150,6 +151,7 @@ fn build_fs_pipeline(
         secrets: Default::default(),
         triggers: None,
         schedule: None,
+        schemas: Default::default(),
     });
 
     let spec_hash_bytes = serde_json::to_string(&*spec).unwrap();
@@ -174,6 +176,7 @@ fn build_fs_pipeline(
         push_sources: BTreeMap::new(),
         schedule: vec![vec![StageId::new("process")]],
         output_dir: output_dir.to_path_buf(),
+        schemas: Default::default(),
     }
 }
 
@@ -300,6 +303,7 @@ async fn test_full_pipeline_with_extension_filter() {
         root: input.path().to_path_buf(),
         filters: vec![],
         extensions: vec!["md".to_string()],
+        watch: None,
         stream: None,
     };
     let adapter: Arc<dyn SourceAdapter> =
@@ -339,6 +343,7 @@ async fn test_full_pipeline_with_extension_filter() {
         secrets: Default::default(),
         triggers: None,
         schedule: None,
+        schemas: Default::default(),
     });
 
     let spec_hash = Blake3Hash::new("test-hash");
@@ -361,6 +366,7 @@ async fn test_full_pipeline_with_extension_filter() {
         push_sources: BTreeMap::new(),
         schedule: vec![vec![StageId::new("process")]],
         output_dir: output.path().to_path_buf(),
+        schemas: Default::default(),
     };
 
     let store = Box::new(InMemoryStateStore::new());
@@ -386,6 +392,7 @@ async fn test_multi_stage_extract_then_passthrough() {
         root: input.path().to_path_buf(),
         filters: vec![],
         extensions: vec![],
+        watch: None,
         stream: None,
     };
     let adapter: Arc<dyn SourceAdapter> =
@@ -423,6 +430,7 @@ async fn test_multi_stage_extract_then_passthrough() {
         secrets: Default::default(),
         triggers: None,
         schedule: None,
+        schemas: Default::default(),
     });
 
     let spec_hash = Blake3Hash::new("test-hash");
@@ -445,6 +453,7 @@ async fn test_multi_stage_extract_then_passthrough() {
         push_sources: BTreeMap::new(),
         schedule: vec![vec![StageId::new("extract")]],
         output_dir: output.path().to_path_buf(),
+        schemas: Default::default(),
     };
 
     let store = Box::new(InMemoryStateStore::new());
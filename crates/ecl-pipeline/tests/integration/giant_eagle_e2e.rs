@@ -63,6 +63,7 @@ fn make_context(params: serde_json::Value) -> StageContext {
             secrets: Default::default(),
             triggers: None,
             schedule: None,
+            schemas: Default::default(),
         }),
         output_dir,
         params,
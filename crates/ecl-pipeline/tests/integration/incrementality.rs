@@ -106,6 +106,7 @@ fn build_extract_emit_topo(
         root: input_dir.to_path_buf(),
         filters: vec![],
         extensions: vec![],
+        watch: None,
         stream: None,
     };
     let adapter: Arc<dyn SourceAdapter> =
@@ -145,6 +146,7 @@ fn build_extract_emit_topo(
         secrets: Default::default(),
         triggers: None,
         schedule: None,
+        schemas: Default::default(),
     });
 
     let spec_hash_bytes = serde_json::to_string(&*spec).unwrap();
@@ -169,6 +171,7 @@ fn build_extract_emit_topo(
         push_sources: BTreeMap::new(),
         schedule: vec![vec![StageId::new("process")]],
         output_dir: output_dir.to_path_buf(),
+        schemas: Default::default(),
     }
 }
 
@@ -0,0 +1,194 @@
+//! File lifecycle integration tests: per-item moves on a filesystem source.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use ecl_adapter_fs::FilesystemAdapter;
+use ecl_pipeline::PipelineRunner;
+use ecl_pipeline_spec::lifecycle::{LifecycleAction, LifecycleSpec};
+use ecl_pipeline_spec::source::FilesystemSourceSpec;
+use ecl_pipeline_spec::{
+    CredentialRef, DefaultsSpec, PipelineSpec, ResourceSpec, SourceSpec, StageSpec,
+};
+use ecl_pipeline_state::{Blake3Hash, RedbStateStore, StageId, StateStore};
+use ecl_pipeline_topo::error::StageError;
+use ecl_pipeline_topo::{
+    PipelineItem, PipelineTopology, ResolvedStage, RetryPolicy, SourceAdapter, Stage, StageContext,
+};
+use tempfile::TempDir;
+
+/// A stage that fails items whose ID starts with `bad`.
+#[derive(Debug)]
+struct RejectBadStage;
+
+#[async_trait]
+impl Stage for RejectBadStage {
+    fn name(&self) -> &str {
+        "reject-bad"
+    }
+
+    async fn process(
+        &self,
+        item: PipelineItem,
+        _ctx: &StageContext,
+    ) -> Result<Vec<PipelineItem>, StageError> {
+        if item.id.starts_with("bad") {
+            Err(StageError::Permanent {
+                stage: "reject-bad".to_string(),
+                item_id: item.id.clone(),
+                message: "rejected".to_string(),
+            })
+        } else {
+            Ok(vec![item])
+        }
+    }
+}
+
+fn build_topo(
+    staging: &std::path::Path,
+    output: &std::path::Path,
+    per_item: bool,
+) -> PipelineTopology {
+    let fs_spec = FilesystemSourceSpec {
+        root: staging.to_path_buf(),
+        filters: vec![],
+        extensions: vec![],
+        watch: None,
+        stream: None,
+    };
+    let lifecycle = LifecycleSpec {
+        bucket: String::new(),
+        staging_prefix: "staging/".to_string(),
+        historical_prefix: "historical/".to_string(),
+        error_prefix: "error/".to_string(),
+        on_success: LifecycleAction::MoveToHistorical,
+        on_failure: LifecycleAction::MoveToError,
+        per_item,
+        credentials: CredentialRef::ApplicationDefault,
+    };
+    let adapter: Arc<dyn SourceAdapter> = Arc::new(
+        FilesystemAdapter::from_fs_spec("local", &fs_spec)
+            .unwrap()
+            .with_lifecycle(&lifecycle),
+    );
+
+    let spec = Arc::new(PipelineSpec {
+        name: "lifecycle-test".to_string(),
+        version: 1,
+        output_dir: output.to_path_buf(),
+        sources: BTreeMap::from([("local".to_string(), SourceSpec::Filesystem(fs_spec))]),
+        stages: BTreeMap::from([(
+            "reject-bad".to_string(),
+            StageSpec {
+                adapter: "reject-bad".to_string(),
+                source: Some("local".to_string()),
+                resources: ResourceSpec::default(),
+                params: serde_json::Value::Null,
+                retry: None,
+                timeout_secs: None,
+                skip_on_error: true,
+                condition: None,
+                input_streams: vec![],
+                output_stream: None,
+            },
+        )]),
+        defaults: DefaultsSpec::default(),
+        lifecycle: Some(lifecycle),
+        secrets: Default::default(),
+        triggers: None,
+        schedule: None,
+        schemas: Default::default(),
+    });
+
+    let stage_id = StageId::new("reject-bad");
+    PipelineTopology {
+        spec,
+        spec_hash: Blake3Hash::new("lifecycle-test"),
+        sources: BTreeMap::from([("local".to_string(), adapter)]),
+        push_sources: BTreeMap::new(),
+        stages: BTreeMap::from([(
+            "reject-bad".to_string(),
+            ResolvedStage {
+                id: stage_id.clone(),
+                handler: Arc::new(RejectBadStage),
+                retry: RetryPolicy {
+                    max_attempts: 1,
+                    initial_backoff: Duration::from_millis(1),
+                    backoff_multiplier: 1.0,
+                    max_backoff: Duration::from_millis(10),
+                },
+                skip_on_error: true,
+                timeout: None,
+                source: Some("local".to_string()),
+                condition: None,
+            },
+        )]),
+        schedule: vec![vec![stage_id]],
+        output_dir: output.to_path_buf(),
+        schemas: Default::default(),
+    }
+}
+
+#[tokio::test]
+async fn test_per_item_lifecycle_moves_and_records() {
+    let tmp = TempDir::new().unwrap();
+    let staging = tmp.path().join("staging");
+    fs::create_dir_all(&staging).unwrap();
+    fs::write(staging.join("good.txt"), "good").unwrap();
+    fs::write(staging.join("bad.txt"), "bad").unwrap();
+    let output = TempDir::new().unwrap();
+    let db = tmp.path().join("state.redb");
+
+    let topo = build_topo(&staging, output.path(), true);
+    let store = Box::new(RedbStateStore::open(&db).unwrap());
+    let mut runner = PipelineRunner::new(topo, store).await.unwrap();
+    let run_id = runner.run().await.unwrap().run_id.as_str().to_string();
+    drop(runner);
+
+    // Only the failed file goes to error/, both under the source root.
+    assert!(
+        staging
+            .join(format!("historical/{run_id}/good.txt"))
+            .exists()
+    );
+    assert!(staging.join("error/bad.txt").exists());
+    assert!(!staging.join("good.txt").exists());
+    assert!(!staging.join("bad.txt").exists());
+
+    // Each move is recorded in the final checkpoint.
+    let store = RedbStateStore::open(&db).unwrap();
+    let checkpoint = store.load_checkpoint().await.unwrap().unwrap();
+    let items = &checkpoint.state.sources["local"].items;
+    let good = items["good.txt"].lifecycle.as_ref().unwrap();
+    assert_eq!(good.action, LifecycleAction::MoveToHistorical);
+    assert!(good.to.as_ref().unwrap().ends_with("good.txt"));
+    let bad = items["bad.txt"].lifecycle.as_ref().unwrap();
+    assert_eq!(bad.action, LifecycleAction::MoveToError);
+    assert!(bad.error.is_none());
+}
+
+#[tokio::test]
+async fn test_whole_run_lifecycle_ignores_item_outcomes() {
+    let tmp = TempDir::new().unwrap();
+    let staging = tmp.path().join("staging");
+    fs::create_dir_all(&staging).unwrap();
+    fs::write(staging.join("good.txt"), "good").unwrap();
+    fs::write(staging.join("bad.txt"), "bad").unwrap();
+    let output = TempDir::new().unwrap();
+
+    let topo = build_topo(&staging, output.path(), false);
+    let store = Box::new(ecl_pipeline_state::InMemoryStateStore::new());
+    let mut runner = PipelineRunner::new(topo, store).await.unwrap();
+    let state = runner.run().await.unwrap();
+
+    // Skipped items do not fail the run, so without `per_item` every
+    // file is treated as a success.
+    assert_eq!(state.stats.total_items_failed, 0);
+    let historical = staging.join("historical").join(state.run_id.as_str());
+    assert!(historical.join("good.txt").exists());
+    assert!(historical.join("bad.txt").exists());
+    assert!(!staging.join("error").exists());
+}
@@ -4,3 +4,4 @@ mod error_handling;
 mod full_pipeline;
 mod giant_eagle_e2e;
 mod incrementality;
+mod lifecycle;
@@ -23,6 +23,7 @@ use serde::Deserialize;
 use tokio::sync::OnceCell;
 use tracing::debug;
 
+use ecl_pipeline_spec::{SchemaError, StreamSchemas};
 use ecl_pipeline_topo::error::StageError;
 use ecl_pipeline_topo::{PipelineItem, Stage, StageContext};
 
@@ -44,6 +45,10 @@ pub struct KafkaSinkConfig {
     /// Path to a `.avsc` file on disk.
     #[serde(default)]
     pub avro_schema_file: Option<String>,
+    /// Stream whose declared record schema the Avro schema is generated
+    /// from, when neither `avro_schema` nor `avro_schema_file` is given.
+    #[serde(default)]
+    pub schema_stream: Option<String>,
     /// Kafka security protocol (default: `"SASL_SSL"`).
     #[serde(default = "default_security_protocol")]
     pub security_protocol: String,
@@ -121,6 +126,20 @@ impl KafkaSinkStage {
     /// Returns `StageError::Permanent` if config parsing, schema loading,
     /// or Kafka producer creation fails.
     pub fn from_params(params: &serde_json::Value) -> Result<Self, StageError> {
+        Self::from_params_with_schemas(params, &StreamSchemas::new())
+    }
+
+    /// Build a `KafkaSinkStage`, resolving `schema_stream` against the
+    /// pipeline's declared stream schemas.
+    ///
+    /// # Errors
+    ///
+    /// Returns `StageError::Permanent` if config parsing, schema loading or
+    /// generation, or Kafka producer creation fails.
+    pub fn from_params_with_schemas(
+        params: &serde_json::Value,
+        schemas: &StreamSchemas,
+    ) -> Result<Self, StageError> {
         let mut config: KafkaSinkConfig =
             serde_json::from_value(params.clone()).map_err(|e| StageError::Permanent {
                 stage: "kafka_sink".to_string(),
@@ -150,12 +169,28 @@ impl KafkaSinkStage {
                 })?
             }
             (None, None) => {
-                return Err(StageError::Permanent {
+                let Some(stream) = &config.schema_stream else {
+                    return Err(StageError::Permanent {
+                        stage: "kafka_sink".to_string(),
+                        item_id: String::new(),
+                        message: "one of 'avro_schema', 'avro_schema_file' or 'schema_stream' \
+                                  must be specified"
+                            .to_string(),
+                    });
+                };
+                let declared = schemas.get(stream).ok_or_else(|| StageError::Permanent {
                     stage: "kafka_sink".to_string(),
                     item_id: String::new(),
-                    message: "either 'avro_schema' or 'avro_schema_file' must be specified"
-                        .to_string(),
-                });
+                    message: format!("no schema is declared for stream '{stream}'"),
+                })?;
+                declared
+                    .to_avro(stream)
+                    .map_err(|e| StageError::Permanent {
+                        stage: "kafka_sink".to_string(),
+                        item_id: String::new(),
+                        message: format!("cannot generate Avro schema: {e}"),
+                    })?
+                    .to_string()
             }
         };
 
@@ -306,6 +341,25 @@ impl Stage for KafkaSinkStage {
         // Terminal stage — no output items.
         Ok(vec![])
     }
+
+    fn transform_schema(
+        &self,
+        inputs: &StreamSchemas,
+    ) -> Result<Option<StreamSchemas>, SchemaError> {
+        // Every non-nullable Avro field must exist on the records produced.
+        if let apache_avro::Schema::Record(record) = &self.schema {
+            for field in &record.fields {
+                if matches!(field.schema, apache_avro::Schema::Union(_)) {
+                    continue;
+                }
+                for (stream, schema) in inputs {
+                    schema.require(stream, &field.name)?;
+                }
+            }
+        }
+        // Terminal stage: every input stream is consumed.
+        Ok(Some(StreamSchemas::new()))
+    }
 }
 
 #[cfg(test)]
@@ -521,4 +575,66 @@ mod tests {
         fn assert_send_sync<T: Send + Sync>() {}
         assert_send_sync::<KafkaSinkStage>();
     }
+
+    #[test]
+    fn test_kafka_sink_from_params_with_schema_stream() {
+        use ecl_pipeline_spec::{FieldSchema, FieldType, StreamSchema};
+
+        let schemas = StreamSchemas::from([(
+            "txn".to_string(),
+            StreamSchema::new(vec![
+                FieldSchema::new("id", FieldType::String),
+                FieldSchema::new("amount", FieldType::Float).nullable(),
+            ]),
+        )]);
+        let params = json!({
+            "topic": "t",
+            "bootstrap_servers": "localhost:9092",
+            "schema_registry_url": "http://localhost:8081",
+            "schema_stream": "txn",
+            "security_protocol": "PLAINTEXT"
+        });
+        let stage = KafkaSinkStage::from_params_with_schemas(&params, &schemas).unwrap();
+        let generated: serde_json::Value = serde_json::from_str(&stage.schema_json).unwrap();
+        assert_eq!(generated["name"], "txn");
+        assert_eq!(generated["fields"][1]["type"], json!(["null", "double"]));
+
+        let missing = KafkaSinkStage::from_params_with_schemas(&params, &StreamSchemas::new());
+        assert!(missing.unwrap_err().to_string().contains("stream 'txn'"));
+    }
+
+    #[test]
+    fn test_kafka_sink_transform_schema_checks_avro_fields() {
+        use ecl_pipeline_spec::{FieldSchema, FieldType, StreamSchema};
+
+        let params = json!({
+            "topic": "t",
+            "bootstrap_servers": "localhost:9092",
+            "schema_registry_url": "http://localhost:8081",
+            "avro_schema": r#"{"type":"record","name":"Test","fields":[
+                {"name":"id","type":"string"},
+                {"name":"note","type":["null","string"],"default":null}
+            ]}"#,
+            "security_protocol": "PLAINTEXT"
+        });
+        let stage = KafkaSinkStage::from_params(&params).unwrap();
+
+        let ok = StreamSchemas::from([(
+            "txn".to_string(),
+            StreamSchema::new(vec![FieldSchema::new("id", FieldType::String)]),
+        )]);
+        assert_eq!(
+            stage.transform_schema(&ok).unwrap(),
+            Some(StreamSchemas::new())
+        );
+
+        let typo = StreamSchemas::from([(
+            "txn".to_string(),
+            StreamSchema::new(vec![FieldSchema::new("ids", FieldType::String)]),
+        )]);
+        assert_eq!(
+            stage.transform_schema(&typo).unwrap_err().to_string(),
+            "stream 'txn' has no field 'id' (did you mean 'ids'?)"
+        );
+    }
 }
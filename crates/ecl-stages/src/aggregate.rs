@@ -10,7 +10,9 @@ use serde::Deserialize;
 use serde_json::Value;
 use tracing::debug;
 
+use ecl_pipeline_spec::{FieldSchema, FieldType, SchemaError, StreamSchema, StreamSchemas};
 use ecl_pipeline_topo::error::StageError;
+use ecl_pipeline_topo::schema::map_streams;
 use ecl_pipeline_topo::{PipelineItem, Stage, StageContext};
 
 type Record = serde_json::Map<String, serde_json::Value>;
@@ -72,6 +74,36 @@ impl AggregateStage {
         Ok(Self { config })
     }
 
+    /// The schema of the group records produced from `input`.
+    fn aggregate_schema(
+        &self,
+        stream: &str,
+        input: &StreamSchema,
+    ) -> Result<StreamSchema, SchemaError> {
+        let mut schema = StreamSchema::default();
+        for field in &self.config.group_by {
+            schema.set_field(input.require(stream, field)?.clone());
+        }
+        for agg in &self.config.aggregates {
+            let source = input.require(stream, &agg.field)?;
+            let output = match agg.function.as_str() {
+                "sum" => FieldSchema::new(&agg.output, FieldType::Float),
+                "count" => FieldSchema::new(&agg.output, FieldType::Integer),
+                "max" | "min" | "avg" => FieldSchema::new(&agg.output, FieldType::Float).nullable(),
+                "first" | "last" => FieldSchema::new(&agg.output, source.field_type).nullable(),
+                _ => FieldSchema::new(&agg.output, FieldType::Any).nullable(),
+            };
+            schema.set_field(output);
+        }
+        for collect in &self.config.collect_arrays {
+            for field in &collect.fields {
+                input.require(stream, field)?;
+            }
+            schema.set_field(FieldSchema::new(&collect.output, FieldType::Array));
+        }
+        Ok(schema)
+    }
+
     fn compute_group_key(&self, record: &Record) -> String {
         self.config
             .group_by
@@ -221,6 +253,15 @@ impl Stage for AggregateStage {
         );
         Ok(results)
     }
+
+    fn transform_schema(
+        &self,
+        inputs: &StreamSchemas,
+    ) -> Result<Option<StreamSchemas>, SchemaError> {
+        map_streams(inputs, |stream, schema| {
+            self.aggregate_schema(stream, schema)
+        })
+    }
 }
 
 #[cfg(test)]
@@ -273,6 +314,7 @@ mod tests {
                 secrets: Default::default(),
                 triggers: None,
                 schedule: None,
+                schemas: Default::default(),
             }),
             output_dir: std::path::PathBuf::from("./out"),
             params: serde_json::Value::Null,
@@ -662,4 +704,34 @@ mod tests {
             .unwrap();
         assert_eq!(t2.record.as_ref().unwrap()["total_amount"], json!(15.0));
     }
+
+    #[test]
+    fn test_aggregate_transform_schema() {
+        let stage = AggregateStage::from_params(&json!({
+            "group_by": ["txn_id"],
+            "aggregates": [
+                { "field": "amount", "function": "sum", "output": "total" },
+                { "field": "sku", "function": "count", "output": "items" },
+            ],
+            "collect_arrays": [{ "output": "lines", "fields": ["sku", "amount"] }]
+        }))
+        .unwrap();
+        let inputs = StreamSchemas::from([(
+            "lines".to_string(),
+            StreamSchema::new(vec![
+                FieldSchema::new("txn_id", FieldType::String),
+                FieldSchema::new("sku", FieldType::String),
+                FieldSchema::new("amount", FieldType::Float),
+            ]),
+        )]);
+        let outputs = stage.transform_schema(&inputs).unwrap().unwrap();
+        let schema = &outputs["lines"];
+        let names: Vec<&str> = schema.fields.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, ["txn_id", "total", "items", "lines"]);
+        assert_eq!(
+            schema.field("items").unwrap().field_type,
+            FieldType::Integer
+        );
+        assert_eq!(schema.field("lines").unwrap().field_type, FieldType::Array);
+    }
 }
@@ -11,6 +11,7 @@ use serde::Deserialize;
 use serde_json::Value;
 use tracing::debug;
 
+use ecl_pipeline_spec::{FieldSchema, FieldType, SchemaError, StreamSchemas};
 use ecl_pipeline_topo::error::StageError;
 use ecl_pipeline_topo::{PipelineItem, Stage, StageContext};
 
@@ -193,6 +194,37 @@ impl Stage for AssembleStage {
 
         Ok(results)
     }
+
+    fn transform_schema(
+        &self,
+        inputs: &StreamSchemas,
+    ) -> Result<Option<StreamSchemas>, SchemaError> {
+        let primary_stream = &self.config.primary_stream;
+        let Some(primary) = inputs.get(primary_stream) else {
+            return Ok(None);
+        };
+        primary.require(primary_stream, &self.config.primary_key)?;
+
+        let mut assembled = primary.clone();
+        for join_def in &self.config.joins {
+            primary.require(primary_stream, &join_def.key)?;
+            if let Some(joined) = inputs.get(&join_def.stream) {
+                joined.require(&join_def.stream, &join_def.foreign_key)?;
+            }
+            let nested = if join_def.collect {
+                FieldSchema::new(&join_def.nest_as, FieldType::Array)
+            } else {
+                FieldSchema::new(&join_def.nest_as, FieldType::Object).nullable()
+            };
+            assembled.set_field(nested);
+        }
+
+        // Only primary records produce output; joined streams are consumed.
+        Ok(Some(StreamSchemas::from([(
+            primary_stream.clone(),
+            assembled,
+        )])))
+    }
 }
 
 #[cfg(test)]
@@ -247,6 +279,7 @@ mod tests {
                 secrets: Default::default(),
                 triggers: None,
                 schedule: None,
+                schemas: Default::default(),
             }),
             output_dir: std::path::PathBuf::from("./out"),
             params: serde_json::Value::Null,
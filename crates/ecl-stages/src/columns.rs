@@ -0,0 +1,174 @@
+//! Column typing and row fan-out shared by the structured-input parse stages.
+//!
+//! `csv_parse`, `xlsx_parse`, `json_parse`, `xml_parse` and
+//! `fixed_width_parse` all turn one file item into one record item per row,
+//! using the same column definitions, value conversion and `on_row_error`
+//! handling.
+
+use std::sync::Arc;
+
+use serde::Deserialize;
+
+use ecl_pipeline_spec::{FieldSchema, FieldType, StreamSchema, StreamSchemas};
+use ecl_pipeline_topo::error::StageError;
+use ecl_pipeline_topo::{PipelineItem, Record};
+
+/// A single column definition.
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct ColumnDef {
+    /// Column name (used as Record field key).
+    pub(crate) name: String,
+    /// Column type for conversion: "string", "integer", "float", "boolean".
+    /// Default: "string"
+    #[serde(default = "default_column_type")]
+    pub(crate) r#type: String,
+}
+
+pub(crate) fn default_on_error() -> String {
+    "skip".to_string()
+}
+
+fn default_column_type() -> String {
+    "string".to_string()
+}
+
+/// Convert a raw string value to the appropriate JSON type.
+pub(crate) fn convert_value(raw: &str, col_type: &str) -> serde_json::Value {
+    match col_type {
+        "integer" => raw
+            .parse::<i64>()
+            .map(serde_json::Value::from)
+            .unwrap_or_else(|_| serde_json::Value::String(raw.to_string())),
+        "float" => raw
+            .parse::<f64>()
+            .map(serde_json::Value::from)
+            .unwrap_or_else(|_| serde_json::Value::String(raw.to_string())),
+        "boolean" => match raw.to_lowercase().as_str() {
+            "true" | "1" | "yes" | "y" => serde_json::Value::Bool(true),
+            "false" | "0" | "no" | "n" => serde_json::Value::Bool(false),
+            _ => serde_json::Value::String(raw.to_string()),
+        },
+        _ => serde_json::Value::String(raw.to_string()), // "string" or unknown
+    }
+}
+
+/// Convert an already-typed JSON value to a column type.
+///
+/// Values that already have the column's type are kept, as is `null`;
+/// anything else is converted from its string form, as for text formats.
+pub(crate) fn convert_json_value(value: &serde_json::Value, col_type: &str) -> serde_json::Value {
+    match (value, col_type) {
+        (serde_json::Value::Null, _) => serde_json::Value::Null,
+        (serde_json::Value::String(s), _) => convert_value(s, col_type),
+        (serde_json::Value::Number(n), "integer") if n.is_i64() => value.clone(),
+        (serde_json::Value::Number(_), "float") => value.clone(),
+        (serde_json::Value::Bool(_), "boolean") => value.clone(),
+        (other, _) => convert_value(&other.to_string(), col_type),
+    }
+}
+
+/// The schema field type for a column type name.
+fn column_field_type(col_type: &str) -> FieldType {
+    match col_type {
+        "integer" => FieldType::Integer,
+        "float" => FieldType::Float,
+        "boolean" => FieldType::Boolean,
+        _ => FieldType::String,
+    }
+}
+
+/// The schemas of the rows a parse stage emits: every input stream carries
+/// exactly the configured columns.
+pub(crate) fn columns_schema<'a>(
+    columns: impl Iterator<Item = &'a ColumnDef>,
+    inputs: &StreamSchemas,
+) -> StreamSchemas {
+    let schema = StreamSchema::new(
+        columns
+            .map(|col| FieldSchema::new(&col.name, column_field_type(&col.r#type)))
+            .collect(),
+    );
+    inputs
+        .keys()
+        .map(|stream| (stream.clone(), schema.clone()))
+        .collect()
+}
+
+/// Apply the `on_row_error` policy to a bad row.
+///
+/// Returns the error when the policy is `"fail"`; otherwise logs a warning
+/// so the caller can skip the row.
+pub(crate) fn row_error(
+    stage: &str,
+    on_row_error: &str,
+    item: &PipelineItem,
+    row: usize,
+    message: impl std::fmt::Display,
+) -> Result<(), StageError> {
+    if on_row_error == "fail" {
+        return Err(StageError::Permanent {
+            stage: stage.to_string(),
+            item_id: item.id.clone(),
+            message: format!("row {row} parse error: {message}"),
+        });
+    }
+    tracing::warn!(item_id = %item.id, row, "skipping bad {stage} row: {message}");
+    Ok(())
+}
+
+/// Build the record item for one row of a parsed file.
+///
+/// The row inherits the parent's metadata plus `_source_file` and
+/// `_line_number`, and keeps the raw row bytes as content for debugging.
+pub(crate) fn row_item(
+    parent: &PipelineItem,
+    row: usize,
+    record: Record,
+    raw: Vec<u8>,
+    mime_type: &str,
+) -> PipelineItem {
+    let mut metadata = parent.metadata.clone();
+    metadata.insert(
+        "_source_file".to_string(),
+        serde_json::Value::String(parent.display_name.clone()),
+    );
+    metadata.insert(
+        "_line_number".to_string(),
+        serde_json::Value::Number(serde_json::Number::from(row)),
+    );
+
+    PipelineItem {
+        id: format!("{}:row:{row}", parent.id),
+        display_name: format!("{}:{row}", parent.display_name),
+        content: Arc::from(raw),
+        mime_type: mime_type.to_string(),
+        source_name: parent.source_name.clone(),
+        source_content_hash: parent.source_content_hash.clone(),
+        provenance: parent.provenance.clone(),
+        metadata,
+        record: Some(record),
+        stream: parent.stream.clone(),
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_convert_json_value_keeps_matching_types() {
+        assert_eq!(convert_json_value(&json!(42), "integer"), json!(42));
+        assert_eq!(convert_json_value(&json!(42), "float"), json!(42));
+        assert_eq!(convert_json_value(&json!(true), "boolean"), json!(true));
+    }
+
+    #[test]
+    fn test_convert_json_value_converts_from_string_form() {
+        assert_eq!(convert_json_value(&json!("7"), "integer"), json!(7));
+        assert_eq!(convert_json_value(&json!(2.5), "string"), json!("2.5"));
+        assert_eq!(convert_json_value(&json!(null), "integer"), json!(null));
+        assert_eq!(convert_json_value(&json!("yes"), "boolean"), json!(true));
+    }
+}
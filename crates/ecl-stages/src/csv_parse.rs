@@ -5,14 +5,17 @@
 //! configurable column definitions with type conversion, custom delimiters,
 //! and header handling.
 
-use std::sync::Arc;
-
 use async_trait::async_trait;
 use serde::Deserialize;
 
+use ecl_pipeline_spec::{SchemaError, StreamSchemas};
 use ecl_pipeline_topo::error::StageError;
 use ecl_pipeline_topo::{PipelineItem, Record, Stage, StageContext};
 
+use crate::columns::{
+    ColumnDef, columns_schema, convert_value, default_on_error, row_error, row_item,
+};
+
 /// Parsed from stage params TOML/JSON.
 #[derive(Debug, Clone, Deserialize)]
 struct CsvParseConfig {
@@ -34,17 +37,6 @@ struct CsvParseConfig {
     on_row_error: String,
 }
 
-/// A single column definition.
-#[derive(Debug, Clone, Deserialize)]
-struct ColumnDef {
-    /// Column name (used as Record field key).
-    name: String,
-    /// Column type for conversion: "string", "integer", "float", "boolean".
-    /// Default: "string"
-    #[serde(default = "default_column_type")]
-    r#type: String,
-}
-
 fn default_delimiter() -> char {
     ','
 }
@@ -54,32 +46,6 @@ fn default_quote() -> char {
 fn default_has_headers() -> bool {
     true
 }
-fn default_on_error() -> String {
-    "skip".to_string()
-}
-fn default_column_type() -> String {
-    "string".to_string()
-}
-
-/// Convert a raw string value to the appropriate JSON type.
-fn convert_value(raw: &str, col_type: &str) -> serde_json::Value {
-    match col_type {
-        "integer" => raw
-            .parse::<i64>()
-            .map(serde_json::Value::from)
-            .unwrap_or_else(|_| serde_json::Value::String(raw.to_string())),
-        "float" => raw
-            .parse::<f64>()
-            .map(serde_json::Value::from)
-            .unwrap_or_else(|_| serde_json::Value::String(raw.to_string())),
-        "boolean" => match raw.to_lowercase().as_str() {
-            "true" | "1" | "yes" | "y" => serde_json::Value::Bool(true),
-            "false" | "0" | "no" | "n" => serde_json::Value::Bool(false),
-            _ => serde_json::Value::String(raw.to_string()),
-        },
-        _ => serde_json::Value::String(raw.to_string()), // "string" or unknown
-    }
-}
 
 /// CSV parsing stage: one file in, N record items out.
 #[derive(Debug)]
@@ -134,18 +100,13 @@ impl Stage for CsvParseStage {
             let csv_record = match result {
                 Ok(r) => r,
                 Err(e) => {
-                    if self.config.on_row_error == "fail" {
-                        return Err(StageError::Permanent {
-                            stage: "csv_parse".to_string(),
-                            item_id: item.id.clone(),
-                            message: format!("row {line_number} parse error: {e}"),
-                        });
-                    }
-                    tracing::warn!(
-                        item_id = %item.id,
-                        line = line_number,
-                        "skipping bad CSV row: {e}"
-                    );
+                    row_error(
+                        "csv_parse",
+                        &self.config.on_row_error,
+                        &item,
+                        line_number,
+                        e,
+                    )?;
                     continue;
                 }
             };
@@ -157,17 +118,6 @@ impl Stage for CsvParseStage {
                 record.insert(col_def.name.clone(), convert_value(raw, &col_def.r#type));
             }
 
-            // Build metadata inherited from parent + row-specific fields.
-            let mut metadata = item.metadata.clone();
-            metadata.insert(
-                "_source_file".to_string(),
-                serde_json::Value::String(item.display_name.clone()),
-            );
-            metadata.insert(
-                "_line_number".to_string(),
-                serde_json::Value::Number(serde_json::Number::from(line_number)),
-            );
-
             // Build the raw CSV row bytes for debugging.
             let row_bytes: Vec<u8> = csv_record
                 .iter()
@@ -175,31 +125,36 @@ impl Stage for CsvParseStage {
                 .join(&(self.config.delimiter.to_string()))
                 .into_bytes();
 
-            output.push(PipelineItem {
-                id: format!("{}:row:{line_number}", item.id),
-                display_name: format!("{}:{line_number}", item.display_name),
-                content: Arc::from(row_bytes),
-                mime_type: "application/x-csv-row".to_string(),
-                source_name: item.source_name.clone(),
-                source_content_hash: item.source_content_hash.clone(),
-                provenance: item.provenance.clone(),
-                metadata,
-                record: Some(record),
-                stream: item.stream.clone(),
-            });
+            output.push(row_item(
+                &item,
+                line_number,
+                record,
+                row_bytes,
+                "application/x-csv-row",
+            ));
         }
 
         Ok(output)
     }
+
+    fn transform_schema(
+        &self,
+        inputs: &StreamSchemas,
+    ) -> Result<Option<StreamSchemas>, SchemaError> {
+        // Every row carries exactly the configured columns.
+        Ok(Some(columns_schema(self.config.columns.iter(), inputs)))
+    }
 }
 
 #[cfg(test)]
 #[allow(clippy::unwrap_used)]
 mod tests {
     use super::*;
+    use ecl_pipeline_spec::{FieldType, StreamSchema};
     use ecl_pipeline_state::{Blake3Hash, ItemProvenance};
     use serde_json::json;
     use std::collections::BTreeMap;
+    use std::sync::Arc;
 
     fn make_test_item(csv_content: &str) -> PipelineItem {
         PipelineItem {
@@ -579,4 +534,22 @@ mod tests {
         let result = CsvParseStage::from_params(&params);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_csv_parse_transform_schema_uses_columns() {
+        let params = json!({
+            "columns": [
+                { "name": "id", "type": "string" },
+                { "name": "qty", "type": "integer" },
+                { "name": "price", "type": "float" },
+            ]
+        });
+        let stage = CsvParseStage::from_params(&params).unwrap();
+        let inputs = StreamSchemas::from([("orders".to_string(), StreamSchema::default())]);
+        let outputs = stage.transform_schema(&inputs).unwrap().unwrap();
+        let schema = &outputs["orders"];
+        assert_eq!(schema.fields.len(), 3);
+        assert_eq!(schema.field("qty").unwrap().field_type, FieldType::Integer);
+        assert_eq!(schema.field("price").unwrap().field_type, FieldType::Float);
+    }
 }
@@ -11,7 +11,9 @@ use serde::Deserialize;
 use serde_json::Value;
 use tracing::debug;
 
+use ecl_pipeline_spec::{FieldSchema, FieldType, SchemaError, StreamSchemas};
 use ecl_pipeline_topo::error::StageError;
+use ecl_pipeline_topo::schema::map_streams;
 use ecl_pipeline_topo::{PipelineItem, Stage, StageContext};
 
 type Record = serde_json::Map<String, serde_json::Value>;
@@ -160,6 +162,21 @@ impl Stage for DateParseStage {
 
         Ok(vec![item])
     }
+
+    fn transform_schema(
+        &self,
+        inputs: &StreamSchemas,
+    ) -> Result<Option<StreamSchemas>, SchemaError> {
+        // Unparseable dates become null.
+        map_streams(inputs, |stream, schema| {
+            let mut schema = schema.clone();
+            for conv in &self.config.conversions {
+                schema.require(stream, &conv.field)?;
+                schema.set_field(FieldSchema::new(&conv.output, FieldType::Timestamp).nullable());
+            }
+            Ok(schema)
+        })
+    }
 }
 
 #[cfg(test)]
@@ -205,6 +222,7 @@ mod tests {
                 secrets: Default::default(),
                 triggers: None,
                 schedule: None,
+                schemas: Default::default(),
             }),
             output_dir: std::path::PathBuf::from("./out"),
             params: serde_json::Value::Null,
@@ -1,37 +1,260 @@
-//! Decompress stage: extracts files from ZIP and GZIP archives (fan-out).
+//! Decompress stage: extracts files from archives and compressed streams
+//! (fan-out).
 //!
-//! Produces one `PipelineItem` per extracted file. Supports optional extension
-//! filtering and preserves stream tags from the parent item.
+//! Supports ZIP and TAR containers and GZIP, BZIP2, XZ and ZSTD streams,
+//! alone or combined (`tar.gz`, `tar.zst`, ...). Produces one `PipelineItem`
+//! per extracted file, optionally expanding archives nested inside archives
+//! up to a configured depth. Supports optional extension filtering and
+//! preserves stream tags from the parent item.
+//!
+//! Archives are untrusted input: expansion is bounded by total expanded
+//! bytes, entry count and compression ratio, entry names are sanitized so
+//! they cannot escape the output directory, and links and other special
+//! entries are skipped.
 
-use std::io::{Cursor, Read as _};
+use std::io::{Cursor, Read};
 use std::sync::Arc;
 
 use async_trait::async_trait;
 use serde::Deserialize;
-use tracing::debug;
+use tracing::{debug, warn};
 
+use ecl_pipeline_spec::{SchemaError, StreamSchemas};
 use ecl_pipeline_topo::error::StageError;
 use ecl_pipeline_topo::{PipelineItem, Stage, StageContext};
 
+/// Expanded bytes allowed before `max_ratio` is enforced, so small files
+/// that happen to compress well are not rejected.
+const RATIO_GRACE_BYTES: u64 = 1024 * 1024;
+
 /// Configuration for the decompress stage, parsed from stage params.
 #[derive(Debug, Clone, Deserialize)]
 pub struct DecompressConfig {
-    /// Supported formats: "zip", "gzip". Default: "zip".
+    /// Supported formats: "zip", "tar", "gzip", "bzip2", "xz", "zstd",
+    /// "tar.gz", "tar.bz2", "tar.xz", "tar.zst", or "auto" to detect the
+    /// format from the content. Default: "zip".
     #[serde(default = "default_zip")]
     pub format: String,
     /// File extension filter for extracted files (empty = all).
     #[serde(default)]
     pub extensions: Vec<String>,
+    /// Number of archive levels to expand. 1 expands only the input item;
+    /// higher values also expand archives found inside it. Default: 1.
+    #[serde(default = "default_max_depth")]
+    pub max_depth: usize,
+    /// Maximum total bytes expanded from one input item. Default: 1 GiB.
+    #[serde(default = "default_max_total_bytes")]
+    pub max_total_bytes: u64,
+    /// Maximum number of files extracted from one input item. Default: 10000.
+    #[serde(default = "default_max_entries")]
+    pub max_entries: usize,
+    /// Maximum ratio of expanded bytes to input bytes. Default: 200.
+    #[serde(default = "default_max_ratio")]
+    pub max_ratio: f64,
 }
 
 fn default_zip() -> String {
     "zip".to_string()
 }
 
+fn default_max_depth() -> usize {
+    1
+}
+
+fn default_max_total_bytes() -> u64 {
+    1024 * 1024 * 1024
+}
+
+fn default_max_entries() -> usize {
+    10_000
+}
+
+fn default_max_ratio() -> f64 {
+    200.0
+}
+
+/// A compression layer wrapping the payload.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Codec {
+    None,
+    Gzip,
+    Bzip2,
+    Xz,
+    Zstd,
+}
+
+/// How the (decompressed) payload is laid out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Container {
+    /// A single file.
+    Single,
+    Zip,
+    Tar,
+}
+
+/// A concrete archive format: an optional codec around a container.
+type Format = (Codec, Container);
+
+/// Parse a configured format name; `None` for unknown names and "auto".
+fn parse_format(name: &str) -> Option<Format> {
+    Some(match name {
+        "zip" => (Codec::None, Container::Zip),
+        "tar" => (Codec::None, Container::Tar),
+        "gzip" | "gz" => (Codec::Gzip, Container::Single),
+        "bzip2" | "bz2" => (Codec::Bzip2, Container::Single),
+        "xz" => (Codec::Xz, Container::Single),
+        "zstd" | "zst" => (Codec::Zstd, Container::Single),
+        "tar.gz" | "tgz" => (Codec::Gzip, Container::Tar),
+        "tar.bz2" | "tbz2" => (Codec::Bzip2, Container::Tar),
+        "tar.xz" | "txz" => (Codec::Xz, Container::Tar),
+        "tar.zst" | "tzst" => (Codec::Zstd, Container::Tar),
+        _ => return None,
+    })
+}
+
+/// Detect a compression layer from magic bytes.
+fn detect_codec(data: &[u8]) -> Codec {
+    if data.starts_with(&[0x1f, 0x8b]) {
+        Codec::Gzip
+    } else if data.starts_with(b"BZh") {
+        Codec::Bzip2
+    } else if data.starts_with(&[0xfd, b'7', b'z', b'X', b'Z', 0x00]) {
+        Codec::Xz
+    } else if data.starts_with(&[0x28, 0xb5, 0x2f, 0xfd]) {
+        Codec::Zstd
+    } else {
+        Codec::None
+    }
+}
+
+/// Detect an uncompressed container from magic bytes.
+fn detect_container(data: &[u8]) -> Option<Container> {
+    if data.starts_with(b"PK\x03\x04") || data.starts_with(b"PK\x05\x06") {
+        Some(Container::Zip)
+    } else if data.get(257..262) == Some(b"ustar".as_slice()) {
+        Some(Container::Tar)
+    } else {
+        None
+    }
+}
+
+/// Detect the format of `data`, or `None` if it is not an archive.
+///
+/// Compressed streams are peeked into so that tarballs are recognized
+/// whatever their name.
+fn detect_format(data: &[u8]) -> Option<Format> {
+    match detect_codec(data) {
+        Codec::None => detect_container(data).map(|c| (Codec::None, c)),
+        codec => {
+            let mut header = Vec::new();
+            // A truncated or corrupt stream is reported when it is expanded.
+            let _ = decoder(codec, data).take(512).read_to_end(&mut header);
+            match detect_container(&header) {
+                Some(Container::Tar) => Some((codec, Container::Tar)),
+                _ => Some((codec, Container::Single)),
+            }
+        }
+    }
+}
+
+/// Strip the codec's file extension from a name.
+fn strip_codec_suffix(name: &str, codec: Codec) -> String {
+    let suffixes: &[(&str, &str)] = match codec {
+        Codec::None => &[],
+        Codec::Gzip => &[(".tgz", ".tar"), (".gz", "")],
+        Codec::Bzip2 => &[(".tbz2", ".tar"), (".bz2", "")],
+        Codec::Xz => &[(".txz", ".tar"), (".xz", "")],
+        Codec::Zstd => &[(".tzst", ".tar"), (".zst", "")],
+    };
+    suffixes
+        .iter()
+        .find_map(|(suffix, replacement)| {
+            name.strip_suffix(suffix)
+                .map(|stem| format!("{stem}{replacement}"))
+        })
+        .unwrap_or_else(|| name.to_string())
+}
+
+/// Sanitize an archive entry name into a safe relative path.
+///
+/// Backslashes are treated as separators; empty, `.` and `..` components and
+/// drive prefixes are dropped, so the result can never escape the directory
+/// it is joined to. Returns `None` if nothing is left.
+fn sanitize_entry_name(name: &str) -> Option<String> {
+    let parts: Vec<&str> = name
+        .split(['/', '\\'])
+        .filter(|part| !matches!(*part, "" | "." | ".."))
+        .filter(|part| !(part.len() == 2 && part.ends_with(':')))
+        .collect();
+    (!parts.is_empty()).then(|| parts.join("/"))
+}
+
+/// Expansion limits shared by everything extracted from one input item.
+#[derive(Debug)]
+struct Budget {
+    input_bytes: u64,
+    expanded_bytes: u64,
+    entries: usize,
+    max_total_bytes: u64,
+    max_entries: usize,
+    max_ratio: f64,
+}
+
+impl Budget {
+    fn new(config: &DecompressConfig, input_bytes: usize) -> Self {
+        Self {
+            input_bytes: input_bytes as u64,
+            expanded_bytes: 0,
+            entries: 0,
+            max_total_bytes: config.max_total_bytes,
+            max_entries: config.max_entries,
+            max_ratio: config.max_ratio,
+        }
+    }
+
+    /// Count one more extracted entry.
+    fn add_entry(&mut self) -> Result<(), String> {
+        self.entries += 1;
+        if self.entries > self.max_entries {
+            return Err(format!(
+                "archive has more than max_entries ({}) files",
+                self.max_entries
+            ));
+        }
+        Ok(())
+    }
+
+    /// Read `reader` to the end without exceeding the byte or ratio limits.
+    fn read(&mut self, reader: impl Read, name: &str) -> Result<Vec<u8>, String> {
+        let remaining = self.max_total_bytes.saturating_sub(self.expanded_bytes);
+        let mut content = Vec::new();
+        reader
+            .take(remaining.saturating_add(1))
+            .read_to_end(&mut content)
+            .map_err(|e| format!("failed to read '{name}': {e}"))?;
+        self.expanded_bytes += content.len() as u64;
+        if self.expanded_bytes > self.max_total_bytes {
+            return Err(format!(
+                "archive expands beyond max_total_bytes ({})",
+                self.max_total_bytes
+            ));
+        }
+        let ratio = self.expanded_bytes as f64 / self.input_bytes.max(1) as f64;
+        if self.expanded_bytes > RATIO_GRACE_BYTES && ratio > self.max_ratio {
+            return Err(format!(
+                "archive compression ratio {ratio:.0} exceeds max_ratio ({})",
+                self.max_ratio
+            ));
+        }
+        Ok(content)
+    }
+}
+
 /// Decompress stage that extracts files from archives.
 #[derive(Debug)]
 pub struct DecompressStage {
     config: DecompressConfig,
+    format: Option<Format>,
 }
 
 impl DecompressStage {
@@ -39,128 +262,221 @@ impl DecompressStage {
     ///
     /// # Errors
     ///
-    /// Returns `StageError::Permanent` if params cannot be deserialized.
+    /// Returns `StageError::Permanent` if params cannot be deserialized or
+    /// name an unsupported format.
     pub fn from_params(params: &serde_json::Value) -> Result<Self, StageError> {
+        let invalid = |message: String| StageError::Permanent {
+            stage: "decompress".into(),
+            item_id: String::new(),
+            message: format!("invalid decompress config: {message}"),
+        };
         let config: DecompressConfig =
-            serde_json::from_value(params.clone()).map_err(|e| StageError::Permanent {
-                stage: "decompress".into(),
-                item_id: String::new(),
-                message: format!("invalid decompress config: {e}"),
-            })?;
+            serde_json::from_value(params.clone()).map_err(|e| invalid(e.to_string()))?;
+        let format = match config.format.as_str() {
+            "auto" => None,
+            name => Some(
+                parse_format(name).ok_or_else(|| invalid(format!("unsupported format: {name}")))?,
+            ),
+        };
+        if config.max_depth == 0 {
+            return Err(invalid("max_depth must be at least 1".to_string()));
+        }
+
+        Ok(Self { config, format })
+    }
 
-        Ok(Self { config })
+    /// Expand one archive, recursing into nested archives while `depth` is
+    /// below `max_depth`.
+    fn expand(
+        &self,
+        item: &PipelineItem,
+        format: Format,
+        depth: usize,
+        budget: &mut Budget,
+        results: &mut Vec<PipelineItem>,
+    ) -> Result<(), String> {
+        let data = item.content.as_ref();
+        match format {
+            (Codec::None, Container::Zip) => self.expand_zip(item, depth, budget, results),
+            (Codec::None, Container::Tar) => {
+                self.expand_tar(item, Cursor::new(data), depth, budget, results)
+            }
+            (Codec::None, Container::Single) => Err("not a recognized archive".to_string()),
+            (codec, Container::Tar) => {
+                self.expand_tar(item, decoder(codec, data), depth, budget, results)
+            }
+            (codec, _) => {
+                budget.add_entry()?;
+                let content = budget.read(decoder(codec, data), &item.display_name)?;
+                let name = strip_codec_suffix(&item.display_name, codec);
+                let suffix = if codec == Codec::Gzip {
+                    "gunzipped"
+                } else {
+                    "decompressed"
+                };
+                let entry = derive_item(item, format!("{}:{suffix}", item.id), name, content);
+                self.emit(entry, depth, budget, results)
+            }
+        }
     }
 
     /// Extract files from a ZIP archive.
-    fn decompress_zip(&self, item: PipelineItem) -> Result<Vec<PipelineItem>, StageError> {
+    fn expand_zip(
+        &self,
+        item: &PipelineItem,
+        depth: usize,
+        budget: &mut Budget,
+        results: &mut Vec<PipelineItem>,
+    ) -> Result<(), String> {
         let cursor = Cursor::new(item.content.as_ref());
-        let mut archive = zip::ZipArchive::new(cursor).map_err(|e| StageError::Permanent {
-            stage: "decompress".into(),
-            item_id: item.id.clone(),
-            message: format!("invalid ZIP archive: {e}"),
-        })?;
+        let mut archive =
+            zip::ZipArchive::new(cursor).map_err(|e| format!("invalid ZIP archive: {e}"))?;
 
-        let mut results = Vec::new();
         for i in 0..archive.len() {
-            let mut file = archive.by_index(i).map_err(|e| StageError::Permanent {
-                stage: "decompress".into(),
-                item_id: item.id.clone(),
-                message: format!("failed to read ZIP entry {i}: {e}"),
-            })?;
-
+            let file = archive
+                .by_index(i)
+                .map_err(|e| format!("failed to read ZIP entry {i}: {e}"))?;
             if file.is_dir() {
                 continue;
             }
+            let raw_name = file.name().to_string();
+            if file.is_symlink() {
+                warn!(item_id = %item.id, entry = %raw_name, "skipping ZIP symlink entry");
+                continue;
+            }
+            let Some(name) = self.accept_entry(item, &raw_name) else {
+                continue;
+            };
+            budget.add_entry()?;
+            let content = budget.read(file, &name)?;
+            let entry = derive_item(item, format!("{}:{name}", item.id), name, content);
+            self.emit(entry, depth, budget, results)?;
+        }
+        Ok(())
+    }
 
-            let name = file.name().to_string();
-
-            // Extension filter.
-            if !self.config.extensions.is_empty() {
-                let ext = std::path::Path::new(&name)
-                    .extension()
-                    .and_then(|e| e.to_str())
-                    .unwrap_or("");
-                if !self.config.extensions.iter().any(|e| e == ext) {
-                    continue;
+    /// Extract regular files from a TAR archive.
+    fn expand_tar(
+        &self,
+        item: &PipelineItem,
+        reader: impl Read,
+        depth: usize,
+        budget: &mut Budget,
+        results: &mut Vec<PipelineItem>,
+    ) -> Result<(), String> {
+        let mut archive = tar::Archive::new(reader);
+        let entries = archive
+            .entries()
+            .map_err(|e| format!("invalid TAR archive: {e}"))?;
+        for entry in entries {
+            let entry = entry.map_err(|e| format!("failed to read TAR entry: {e}"))?;
+            let raw_name = String::from_utf8_lossy(&entry.path_bytes()).into_owned();
+            if !entry.header().entry_type().is_file() {
+                if !entry.header().entry_type().is_dir() {
+                    warn!(item_id = %item.id, entry = %raw_name, "skipping non-file TAR entry");
                 }
+                continue;
             }
+            let Some(name) = self.accept_entry(item, &raw_name) else {
+                continue;
+            };
+            budget.add_entry()?;
+            let content = budget.read(entry, &name)?;
+            let entry = derive_item(item, format!("{}:{name}", item.id), name, content);
+            self.emit(entry, depth, budget, results)?;
+        }
+        Ok(())
+    }
 
-            let mut content = Vec::new();
-            file.read_to_end(&mut content)
-                .map_err(|e| StageError::Permanent {
-                    stage: "decompress".into(),
-                    item_id: item.id.clone(),
-                    message: format!("failed to read ZIP entry '{name}': {e}"),
-                })?;
-
-            results.push(PipelineItem {
-                id: format!("{}:{}", item.id, name),
-                display_name: name.clone(),
-                content: Arc::from(content.as_slice()),
-                mime_type: mime_from_extension(&name),
-                source_name: item.source_name.clone(),
-                source_content_hash: item.source_content_hash.clone(),
-                provenance: item.provenance.clone(),
-                metadata: item.metadata.clone(),
-                record: None,
-                stream: item.stream.clone(),
-            });
+    /// Sanitize an entry name; `None` if the entry should be skipped.
+    fn accept_entry(&self, item: &PipelineItem, raw_name: &str) -> Option<String> {
+        let Some(name) = sanitize_entry_name(raw_name) else {
+            warn!(item_id = %item.id, entry = %raw_name, "skipping entry with unsafe name");
+            return None;
+        };
+        if name != raw_name {
+            warn!(item_id = %item.id, entry = %raw_name, sanitized = %name, "sanitized entry name");
         }
+        Some(name)
+    }
 
-        debug!(
-            item_id = %item.id,
-            extracted = results.len(),
-            "decompressed ZIP archive"
-        );
+    /// Emit an extracted entry, expanding it instead if it is itself an
+    /// archive and the depth limit allows.
+    fn emit(
+        &self,
+        entry: PipelineItem,
+        depth: usize,
+        budget: &mut Budget,
+        results: &mut Vec<PipelineItem>,
+    ) -> Result<(), String> {
+        if depth + 1 < self.config.max_depth
+            && let Some(format) = detect_format(&entry.content)
+        {
+            return self.expand(&entry, format, depth + 1, budget, results);
+        }
+        if self.matches_extension(&entry.display_name) {
+            results.push(entry);
+        }
+        Ok(())
+    }
 
-        Ok(results)
+    /// Whether a file name passes the extension filter.
+    fn matches_extension(&self, name: &str) -> bool {
+        if self.config.extensions.is_empty() {
+            return true;
+        }
+        let ext = std::path::Path::new(name)
+            .extension()
+            .and_then(|e| e.to_str())
+            .unwrap_or("");
+        self.config.extensions.iter().any(|e| e == ext)
     }
+}
 
-    /// Decompress a GZIP file (single file output).
-    fn decompress_gzip(&self, item: PipelineItem) -> Result<Vec<PipelineItem>, StageError> {
-        let cursor = Cursor::new(item.content.as_ref());
-        let mut decoder = flate2::read::GzDecoder::new(cursor);
-        let mut content = Vec::new();
-        decoder
-            .read_to_end(&mut content)
-            .map_err(|e| StageError::Permanent {
-                stage: "decompress".into(),
-                item_id: item.id.clone(),
-                message: format!("failed to decompress GZIP: {e}"),
-            })?;
-
-        // Strip .gz extension from the display name for the output.
-        let output_name = item
-            .display_name
-            .strip_suffix(".gz")
-            .unwrap_or(&item.display_name)
-            .to_string();
-
-        let output_item = PipelineItem {
-            id: format!("{}:gunzipped", item.id),
-            display_name: output_name.clone(),
-            content: Arc::from(content.as_slice()),
-            mime_type: mime_from_extension(&output_name),
-            source_name: item.source_name.clone(),
-            source_content_hash: item.source_content_hash.clone(),
-            provenance: item.provenance.clone(),
-            metadata: item.metadata.clone(),
-            record: None,
-            stream: item.stream.clone(),
-        };
+/// A streaming decoder for a compression layer.
+fn decoder<'a>(codec: Codec, data: &'a [u8]) -> Box<dyn Read + 'a> {
+    match codec {
+        Codec::None => Box::new(data),
+        Codec::Gzip => Box::new(flate2::read::GzDecoder::new(data)),
+        Codec::Bzip2 => Box::new(bzip2::read::BzDecoder::new(data)),
+        Codec::Xz => Box::new(xz2::read::XzDecoder::new(data)),
+        Codec::Zstd => match zstd::stream::read::Decoder::new(data) {
+            Ok(decoder) => Box::new(decoder),
+            Err(e) => Box::new(FailingReader(Some(e))),
+        },
+    }
+}
 
-        debug!(
-            item_id = %item.id,
-            output_size = content.len(),
-            "decompressed GZIP file"
-        );
+/// A reader that fails with a stored error on first read.
+struct FailingReader(Option<std::io::Error>);
+
+impl Read for FailingReader {
+    fn read(&mut self, _buf: &mut [u8]) -> std::io::Result<usize> {
+        Err(self
+            .0
+            .take()
+            .unwrap_or_else(|| std::io::Error::other("decoder failed")))
+    }
+}
 
-        Ok(vec![output_item])
+/// Build an extracted item that inherits the parent's provenance.
+fn derive_item(parent: &PipelineItem, id: String, name: String, content: Vec<u8>) -> PipelineItem {
+    PipelineItem {
+        id,
+        mime_type: mime_from_extension(&name),
+        display_name: name,
+        content: Arc::from(content),
+        source_name: parent.source_name.clone(),
+        source_content_hash: parent.source_content_hash.clone(),
+        provenance: parent.provenance.clone(),
+        metadata: parent.metadata.clone(),
+        record: None,
+        stream: parent.stream.clone(),
     }
 }
 
 /// Guess MIME type from file extension.
-fn mime_from_extension(name: &str) -> String {
+pub(crate) fn mime_from_extension(name: &str) -> String {
     match std::path::Path::new(name)
         .extension()
         .and_then(|e| e.to_str())
@@ -186,15 +502,38 @@ impl Stage for DecompressStage {
         item: PipelineItem,
         _ctx: &StageContext,
     ) -> Result<Vec<PipelineItem>, StageError> {
-        match self.config.format.as_str() {
-            "zip" => self.decompress_zip(item),
-            "gzip" => self.decompress_gzip(item),
-            other => Err(StageError::Permanent {
-                stage: "decompress".into(),
-                item_id: String::new(),
-                message: format!("unsupported format: {other}"),
-            }),
-        }
+        let permanent = |message: String| StageError::Permanent {
+            stage: "decompress".into(),
+            item_id: item.id.clone(),
+            message,
+        };
+        let format = match self.format {
+            Some(format) => format,
+            None => detect_format(&item.content)
+                .ok_or_else(|| permanent("not a recognized archive".to_string()))?,
+        };
+
+        let mut budget = Budget::new(&self.config, item.content.len());
+        let mut results = Vec::new();
+        self.expand(&item, format, 0, &mut budget, &mut results)
+            .map_err(permanent)?;
+
+        debug!(
+            item_id = %item.id,
+            extracted = results.len(),
+            expanded_bytes = budget.expanded_bytes,
+            "decompressed archive"
+        );
+
+        Ok(results)
+    }
+
+    fn transform_schema(
+        &self,
+        inputs: &StreamSchemas,
+    ) -> Result<Option<StreamSchemas>, SchemaError> {
+        // Items pass through with their records untouched.
+        Ok(Some(inputs.clone()))
     }
 }
 
@@ -247,6 +586,7 @@ mod tests {
                 secrets: Default::default(),
                 triggers: None,
                 schedule: None,
+                schemas: Default::default(),
             }),
             output_dir: std::path::PathBuf::from("./out"),
             params: serde_json::Value::Null,
@@ -403,4 +743,207 @@ mod tests {
         assert_eq!(result[0].mime_type, "text/csv");
         assert_eq!(result[0].content.as_ref(), b"hello world compressed");
     }
+
+    /// Create a TAR archive in memory with the given files.
+    fn create_test_tar(files: &[(&str, &[u8])]) -> Vec<u8> {
+        let mut builder = tar::Builder::new(Vec::new());
+        for (name, content) in files {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(content.len() as u64);
+            header.set_mode(0o644);
+            builder.append_data(&mut header, name, *content).unwrap();
+        }
+        builder.into_inner().unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_decompress_tar_gz() {
+        let tar = create_test_tar(&[("dir/a.csv", b"a,b"), ("b.json", b"{}")]);
+        let stage = DecompressStage::from_params(&json!({ "format": "tar.gz" })).unwrap();
+        let item = make_item("bundle.tgz", &create_test_gzip(&tar));
+
+        let result = stage.process(item, &ctx()).await.unwrap();
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0].id, "bundle.tgz:dir/a.csv");
+        assert_eq!(result[0].mime_type, "text/csv");
+        assert_eq!(result[1].content.as_ref(), b"{}");
+    }
+
+    #[tokio::test]
+    async fn test_decompress_tar_zst_skips_symlinks() {
+        let mut builder = tar::Builder::new(Vec::new());
+        let mut header = tar::Header::new_gnu();
+        header.set_size(4);
+        builder
+            .append_data(&mut header, "real.txt", &b"real"[..])
+            .unwrap();
+        let mut link = tar::Header::new_gnu();
+        link.set_entry_type(tar::EntryType::Symlink);
+        link.set_size(0);
+        builder
+            .append_link(&mut link, "link.txt", "/etc/passwd")
+            .unwrap();
+        let tar = builder.into_inner().unwrap();
+        let data = zstd::encode_all(tar.as_slice(), 0).unwrap();
+
+        let stage = DecompressStage::from_params(&json!({ "format": "tar.zst" })).unwrap();
+        let result = stage
+            .process(make_item("t.tar.zst", &data), &ctx())
+            .await
+            .unwrap();
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].display_name, "real.txt");
+    }
+
+    #[tokio::test]
+    async fn test_decompress_single_file_codecs() {
+        let mut xz = xz2::write::XzEncoder::new(Vec::new(), 6);
+        xz.write_all(b"xz data").unwrap();
+        let xz = xz.finish().unwrap();
+        let mut bz = bzip2::write::BzEncoder::new(Vec::new(), bzip2::Compression::default());
+        bz.write_all(b"bz data").unwrap();
+        let bz = bz.finish().unwrap();
+
+        let stage = DecompressStage::from_params(&json!({ "format": "xz" })).unwrap();
+        let result = stage
+            .process(make_item("notes.txt.xz", &xz), &ctx())
+            .await
+            .unwrap();
+        assert_eq!(result[0].id, "notes.txt.xz:decompressed");
+        assert_eq!(result[0].display_name, "notes.txt");
+        assert_eq!(result[0].content.as_ref(), b"xz data");
+
+        let stage = DecompressStage::from_params(&json!({ "format": "bzip2" })).unwrap();
+        let result = stage
+            .process(make_item("notes.txt.bz2", &bz), &ctx())
+            .await
+            .unwrap();
+        assert_eq!(result[0].display_name, "notes.txt");
+        assert_eq!(result[0].content.as_ref(), b"bz data");
+    }
+
+    #[tokio::test]
+    async fn test_decompress_auto_detects_format() {
+        let stage = DecompressStage::from_params(&json!({ "format": "auto" })).unwrap();
+
+        let zip = create_test_zip(&[("a.txt", b"zip")]);
+        let result = stage.process(make_item("x", &zip), &ctx()).await.unwrap();
+        assert_eq!(result[0].id, "x:a.txt");
+
+        let tar = create_test_tar(&[("b.txt", b"tar")]);
+        let data = zstd::encode_all(tar.as_slice(), 0).unwrap();
+        let result = stage.process(make_item("y", &data), &ctx()).await.unwrap();
+        assert_eq!(result[0].id, "y:b.txt");
+
+        let result = stage
+            .process(make_item("z.gz", &create_test_gzip(b"plain")), &ctx())
+            .await
+            .unwrap();
+        assert_eq!(result[0].id, "z.gz:gunzipped");
+
+        let err = stage
+            .process(make_item("plain.txt", b"just text"), &ctx())
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("not a recognized archive"));
+    }
+
+    #[tokio::test]
+    async fn test_decompress_nested_archives_respect_max_depth() {
+        let inner = create_test_tar(&[("deep.csv", b"x,y")]);
+        let outer = create_test_zip(&[("inner.tar.gz", &create_test_gzip(&inner))]);
+
+        let stage = DecompressStage::from_params(&json!({ "format": "zip" })).unwrap();
+        let result = stage
+            .process(make_item("outer.zip", &outer), &ctx())
+            .await
+            .unwrap();
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].display_name, "inner.tar.gz");
+
+        let stage =
+            DecompressStage::from_params(&json!({ "format": "zip", "max_depth": 2 })).unwrap();
+        let result = stage
+            .process(make_item("outer.zip", &outer), &ctx())
+            .await
+            .unwrap();
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].id, "outer.zip:inner.tar.gz:deep.csv");
+        assert_eq!(result[0].content.as_ref(), b"x,y");
+    }
+
+    #[tokio::test]
+    async fn test_decompress_enforces_entry_and_size_limits() {
+        let zip = create_test_zip(&[("a.txt", b"1"), ("b.txt", b"2"), ("c.txt", b"3")]);
+        let stage =
+            DecompressStage::from_params(&json!({ "format": "zip", "max_entries": 2 })).unwrap();
+        let err = stage
+            .process(make_item("a.zip", &zip), &ctx())
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("max_entries"));
+
+        let zip = create_test_zip(&[("big.txt", &[b'x'; 100])]);
+        let stage =
+            DecompressStage::from_params(&json!({ "format": "zip", "max_total_bytes": 64 }))
+                .unwrap();
+        let err = stage
+            .process(make_item("b.zip", &zip), &ctx())
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("max_total_bytes"));
+    }
+
+    #[tokio::test]
+    async fn test_decompress_rejects_high_compression_ratio() {
+        let bomb = create_test_gzip(&vec![0u8; 4 * 1024 * 1024]);
+        let stage = DecompressStage::from_params(&json!({ "format": "gzip" })).unwrap();
+        let err = stage
+            .process(make_item("bomb.gz", &bomb), &ctx())
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("max_ratio"), "unexpected: {err}");
+
+        let stage =
+            DecompressStage::from_params(&json!({ "format": "gzip", "max_ratio": 100000.0 }))
+                .unwrap();
+        let result = stage
+            .process(make_item("bomb.gz", &bomb), &ctx())
+            .await
+            .unwrap();
+        assert_eq!(result[0].content.len(), 4 * 1024 * 1024);
+    }
+
+    #[tokio::test]
+    async fn test_decompress_sanitizes_entry_names() {
+        let zip = create_test_zip(&[
+            ("../../etc/passwd", b"root"),
+            ("/abs/./file.txt", b"abs"),
+            ("..", b"nothing"),
+        ]);
+        let stage = DecompressStage::from_params(&json!({ "format": "zip" })).unwrap();
+        let result = stage
+            .process(make_item("evil.zip", &zip), &ctx())
+            .await
+            .unwrap();
+        let names: Vec<_> = result.iter().map(|r| r.display_name.as_str()).collect();
+        assert_eq!(names, vec!["etc/passwd", "abs/file.txt"]);
+        assert_eq!(result[0].id, "evil.zip:etc/passwd");
+    }
+
+    #[test]
+    fn test_sanitize_entry_name() {
+        assert_eq!(sanitize_entry_name("a/b.txt").as_deref(), Some("a/b.txt"));
+        assert_eq!(
+            sanitize_entry_name("C:\\temp\\..\\x.txt").as_deref(),
+            Some("temp/x.txt")
+        );
+        assert_eq!(sanitize_entry_name("./.."), None);
+    }
+
+    #[test]
+    fn test_decompress_rejects_bad_config() {
+        assert!(DecompressStage::from_params(&json!({ "format": "rar" })).is_err());
+        assert!(DecompressStage::from_params(&json!({ "max_depth": 0 })).is_err());
+    }
 }
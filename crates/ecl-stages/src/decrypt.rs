@@ -0,0 +1,519 @@
+//! Decrypt stage: decrypts OpenPGP-encrypted files (1:1).
+//!
+//! Accepts armored or binary OpenPGP messages, decrypts them with a private
+//! key resolved through `ecl-secrets`, decompresses any inner compression
+//! layer, and optionally verifies the message signature against configured
+//! public keys. The decrypted item keeps its stream tag and metadata, so the
+//! stage chains directly into `decompress` or `csv_parse`.
+
+use std::io::Cursor;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use pgp::composed::{Deserializable, Message, SignedPublicKey, SignedSecretKey};
+use pgp::types::{Password, VerifyingKey};
+use serde::Deserialize;
+use tokio::sync::OnceCell;
+use tracing::debug;
+
+use ecl_pipeline_spec::{CredentialRef, SchemaError, StreamSchemas};
+use ecl_pipeline_topo::error::StageError;
+use ecl_pipeline_topo::{PipelineItem, Stage, StageContext};
+use ecl_secrets::SecretResolver;
+
+use crate::decompress::mime_from_extension;
+
+/// Configuration for the decrypt stage, parsed from stage params.
+#[derive(Debug, Clone, Deserialize)]
+pub struct DecryptConfig {
+    /// Private key (armored or binary) used to decrypt messages.
+    pub private_key: CredentialRef,
+    /// Passphrase protecting the private key. Default: none.
+    #[serde(default)]
+    pub passphrase: Option<CredentialRef>,
+    /// Public keys whose signatures are accepted. When non-empty, every
+    /// message must carry a valid signature from one of these keys.
+    #[serde(default)]
+    pub verify_keys: Vec<CredentialRef>,
+}
+
+/// Keys resolved on first use.
+struct Keyring {
+    secret_key: SignedSecretKey,
+    passphrase: Vec<u8>,
+    verify_keys: Vec<SignedPublicKey>,
+}
+
+/// Decrypt stage for OpenPGP messages.
+pub struct DecryptStage {
+    config: DecryptConfig,
+    resolver: Arc<dyn SecretResolver>,
+    keyring: OnceCell<Keyring>,
+}
+
+impl std::fmt::Debug for DecryptStage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Never print key material.
+        f.debug_struct("DecryptStage")
+            .field("config", &self.config)
+            .field("resolver", &self.resolver)
+            .finish_non_exhaustive()
+    }
+}
+
+impl DecryptStage {
+    /// Create a decrypt stage from JSON params and a secret resolver.
+    ///
+    /// Keys are resolved lazily when the first item is processed.
+    ///
+    /// # Errors
+    ///
+    /// Returns `StageError::Permanent` if params cannot be deserialized.
+    pub fn from_params(
+        params: &serde_json::Value,
+        resolver: Arc<dyn SecretResolver>,
+    ) -> Result<Self, StageError> {
+        let config: DecryptConfig =
+            serde_json::from_value(params.clone()).map_err(|e| StageError::Permanent {
+                stage: "decrypt".into(),
+                item_id: String::new(),
+                message: format!("invalid decrypt config: {e}"),
+            })?;
+
+        Ok(Self {
+            config,
+            resolver,
+            keyring: OnceCell::new(),
+        })
+    }
+
+    /// Resolve and parse the configured keys.
+    async fn load_keyring(&self, item_id: &str) -> Result<Keyring, StageError> {
+        let permanent = |message: String| StageError::Permanent {
+            stage: "decrypt".into(),
+            item_id: item_id.to_string(),
+            message,
+        };
+
+        let material = self.resolve(&self.config.private_key, item_id).await?;
+        let (secret_key, _) = SignedSecretKey::from_reader_single(material.as_slice())
+            .map_err(|e| permanent(format!("invalid PGP private key: {e}")))?;
+
+        let passphrase = match &self.config.passphrase {
+            Some(cred) => self.resolve(cred, item_id).await?,
+            None => Vec::new(),
+        };
+
+        let mut verify_keys = Vec::new();
+        for cred in &self.config.verify_keys {
+            let material = self.resolve(cred, item_id).await?;
+            let (key, _) = SignedPublicKey::from_reader_single(material.as_slice())
+                .map_err(|e| permanent(format!("invalid PGP public key: {e}")))?;
+            key.verify_bindings()
+                .map_err(|e| permanent(format!("invalid PGP public key bindings: {e}")))?;
+            verify_keys.push(key);
+        }
+
+        Ok(Keyring {
+            secret_key,
+            passphrase,
+            verify_keys,
+        })
+    }
+
+    /// Resolve a `CredentialRef` to raw bytes.
+    async fn resolve(&self, cred: &CredentialRef, item_id: &str) -> Result<Vec<u8>, StageError> {
+        let result = match cred {
+            CredentialRef::Secret { name } => {
+                self.resolver.resolve(name).await.map_err(|e| e.to_string())
+            }
+            CredentialRef::File { path } => tokio::fs::read(path)
+                .await
+                .map_err(|e| format!("failed to read key file '{}': {e}", path.display())),
+            CredentialRef::EnvVar { env } => std::env::var(env)
+                .map(String::into_bytes)
+                .map_err(|_| format!("environment variable '{env}' not set")),
+            CredentialRef::ApplicationDefault => {
+                Err("application default credentials cannot hold PGP keys".to_string())
+            }
+        };
+        result.map_err(|message| StageError::Permanent {
+            stage: "decrypt".into(),
+            item_id: item_id.to_string(),
+            message,
+        })
+    }
+
+    /// Decrypt, decompress and verify one message.
+    ///
+    /// Returns the plaintext and, when verification is configured, the
+    /// fingerprint of the key that signed it.
+    fn decrypt(
+        &self,
+        keyring: &Keyring,
+        item: &PipelineItem,
+    ) -> Result<(Vec<u8>, Option<String>), StageError> {
+        let permanent = |message: String| StageError::Permanent {
+            stage: "decrypt".into(),
+            item_id: item.id.clone(),
+            message,
+        };
+
+        let (message, _) = Message::from_reader(Cursor::new(item.content.as_ref()))
+            .map_err(|e| permanent(format!("invalid OpenPGP message: {e}")))?;
+        if !message.is_encrypted() {
+            return Err(permanent("OpenPGP message is not encrypted".to_string()));
+        }
+        let password = Password::from(keyring.passphrase.as_slice());
+        let mut message = message
+            .decrypt(&password, &keyring.secret_key)
+            .map_err(|e| permanent(format!("failed to decrypt: {e}")))?;
+        while message.is_compressed() {
+            message = message
+                .decompress()
+                .map_err(|e| permanent(format!("failed to decompress: {e}")))?;
+        }
+        let plaintext = message
+            .as_data_vec()
+            .map_err(|e| permanent(format!("failed to read decrypted data: {e}")))?;
+
+        if keyring.verify_keys.is_empty() {
+            return Ok((plaintext, None));
+        }
+        if !message.is_signed() {
+            return Err(permanent("message is not signed".to_string()));
+        }
+        // Signatures usually come from a signing subkey, so offer every
+        // component key of each configured certificate.
+        let candidates: Vec<&dyn VerifyingKey> = keyring
+            .verify_keys
+            .iter()
+            .flat_map(|key| {
+                std::iter::once(key as &dyn VerifyingKey)
+                    .chain(key.public_subkeys.iter().map(|s| s as &dyn VerifyingKey))
+            })
+            .collect();
+        let signer = message
+            .verify_nested(&candidates)
+            .map_err(|e| permanent(format!("bad signature: {e}")))?
+            .iter()
+            .position(|r| matches!(r, pgp::composed::VerificationResult::Valid(_)))
+            .map(|idx| format!("{:X}", candidates[idx].fingerprint()))
+            .ok_or_else(|| {
+                permanent("bad signature: no valid signature from a trusted key".to_string())
+            })?;
+
+        Ok((plaintext, Some(signer)))
+    }
+}
+
+#[async_trait]
+impl Stage for DecryptStage {
+    fn name(&self) -> &str {
+        "decrypt"
+    }
+
+    async fn process(
+        &self,
+        item: PipelineItem,
+        _ctx: &StageContext,
+    ) -> Result<Vec<PipelineItem>, StageError> {
+        let keyring = self
+            .keyring
+            .get_or_try_init(|| self.load_keyring(&item.id))
+            .await?;
+        let (plaintext, signer) = self.decrypt(keyring, &item)?;
+
+        // Strip the encryption extension from the display name for the output.
+        let output_name = [".pgp", ".gpg", ".asc"]
+            .iter()
+            .find_map(|ext| item.display_name.strip_suffix(ext))
+            .unwrap_or(&item.display_name)
+            .to_string();
+
+        let mut metadata = item.metadata.clone();
+        if let Some(signer) = signer {
+            metadata.insert("_pgp_signer".to_string(), serde_json::Value::String(signer));
+        }
+
+        debug!(
+            item_id = %item.id,
+            output_size = plaintext.len(),
+            "decrypted OpenPGP message"
+        );
+
+        Ok(vec![PipelineItem {
+            id: format!("{}:decrypted", item.id),
+            display_name: output_name.clone(),
+            content: Arc::from(plaintext),
+            mime_type: mime_from_extension(&output_name),
+            source_name: item.source_name.clone(),
+            source_content_hash: item.source_content_hash.clone(),
+            provenance: item.provenance.clone(),
+            metadata,
+            record: None,
+            stream: item.stream.clone(),
+        }])
+    }
+
+    fn transform_schema(
+        &self,
+        inputs: &StreamSchemas,
+    ) -> Result<Option<StreamSchemas>, SchemaError> {
+        // Files in, files out: records are untouched.
+        Ok(Some(inputs.clone()))
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use ecl_pipeline_state::{Blake3Hash, ItemProvenance};
+    use ecl_secrets::SecretError;
+    use pgp::composed::{
+        ArmorOptions, EncryptionCaps, KeyType, MessageBuilder, SecretKeyParamsBuilder,
+        SubkeyParamsBuilder,
+    };
+    use pgp::crypto::ecc_curve::ECCCurve;
+    use pgp::crypto::hash::HashAlgorithm;
+    use pgp::crypto::sym::SymmetricKeyAlgorithm;
+    use pgp::ser::Serialize as _;
+    use pgp::types::{CompressionAlgorithm, KeyDetails};
+    use rand::thread_rng;
+    use serde_json::json;
+    use std::collections::BTreeMap;
+
+    /// Serves secrets from an in-memory map.
+    #[derive(Debug, Default)]
+    struct MapResolver(BTreeMap<String, Vec<u8>>);
+
+    #[async_trait]
+    impl SecretResolver for MapResolver {
+        async fn resolve(&self, name: &str) -> Result<Vec<u8>, SecretError> {
+            self.0
+                .get(name)
+                .cloned()
+                .ok_or_else(|| SecretError::NotFound {
+                    name: name.to_string(),
+                })
+        }
+    }
+
+    /// Generate a key with a signing subkey and an encryption subkey.
+    fn keygen(passphrase: Option<&str>) -> SignedSecretKey {
+        let mut sign = SubkeyParamsBuilder::default();
+        sign.key_type(KeyType::Ed25519Legacy)
+            .can_sign(true)
+            .can_encrypt(EncryptionCaps::None)
+            .passphrase(passphrase.map(str::to_string));
+        let mut encrypt = SubkeyParamsBuilder::default();
+        encrypt
+            .key_type(KeyType::ECDH(ECCCurve::Curve25519))
+            .can_sign(false)
+            .can_encrypt(EncryptionCaps::All)
+            .passphrase(passphrase.map(str::to_string));
+        let mut params = SecretKeyParamsBuilder::default();
+        params
+            .key_type(KeyType::Ed25519Legacy)
+            .can_certify(true)
+            .can_sign(false)
+            .can_encrypt(EncryptionCaps::None)
+            .primary_user_id("Test <test@example.com>".into())
+            .passphrase(passphrase.map(str::to_string))
+            .subkeys(vec![sign.build().unwrap(), encrypt.build().unwrap()]);
+        params.build().unwrap().generate(thread_rng()).unwrap()
+    }
+
+    /// Encrypt `data` to `recipient`, optionally signing with `signer`.
+    fn encrypt(
+        recipient: &SignedSecretKey,
+        signer: Option<&SignedSecretKey>,
+        data: &[u8],
+        armor: bool,
+    ) -> Vec<u8> {
+        let public = SignedPublicKey::from(recipient.clone());
+        let mut builder = MessageBuilder::from_bytes("", data.to_vec())
+            .seipd_v1(thread_rng(), SymmetricKeyAlgorithm::AES256);
+        builder.compression(CompressionAlgorithm::ZLIB);
+        builder
+            .encrypt_to_key(thread_rng(), &public.public_subkeys[1])
+            .unwrap();
+        if let Some(signer) = signer {
+            builder.sign(
+                &signer.secret_subkeys[0].key,
+                Password::empty(),
+                HashAlgorithm::Sha256,
+            );
+        }
+        if armor {
+            builder
+                .to_armored_string(thread_rng(), ArmorOptions::default())
+                .unwrap()
+                .into_bytes()
+        } else {
+            builder.to_vec(thread_rng()).unwrap()
+        }
+    }
+
+    fn public_bytes(key: &SignedSecretKey) -> Vec<u8> {
+        SignedPublicKey::from(key.clone()).to_bytes().unwrap()
+    }
+
+    fn make_test_item(name: &str, content: Vec<u8>) -> PipelineItem {
+        PipelineItem {
+            id: "file-1".to_string(),
+            display_name: name.to_string(),
+            content: Arc::from(content),
+            mime_type: "application/pgp-encrypted".to_string(),
+            source_name: "test".to_string(),
+            source_content_hash: Blake3Hash::new("abc"),
+            provenance: ItemProvenance {
+                source_kind: "sftp".to_string(),
+                metadata: BTreeMap::new(),
+                source_modified: None,
+                extracted_at: chrono::Utc::now(),
+            },
+            metadata: BTreeMap::new(),
+            record: None,
+            stream: Some("txn".to_string()),
+        }
+    }
+
+    fn make_ctx() -> StageContext {
+        StageContext {
+            spec: Arc::new(
+                ecl_pipeline_spec::PipelineSpec::from_toml(
+                    "name = \"t\"\nversion = 1\noutput_dir = \"./o\"\n[sources.s]\nkind = \"filesystem\"\nroot = \"/tmp\"\n[stages.x]\nadapter = \"x\"\nresources = { creates = [\"y\"] }",
+                ).unwrap(),
+            ),
+            output_dir: std::path::PathBuf::from("/tmp"),
+            params: serde_json::Value::Null,
+            span: tracing::Span::none(),
+        }
+    }
+
+    fn stage(secrets: &[(&str, Vec<u8>)], params: serde_json::Value) -> DecryptStage {
+        let resolver = MapResolver(
+            secrets
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.clone()))
+                .collect(),
+        );
+        DecryptStage::from_params(&params, Arc::new(resolver)).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_decrypt_binary_message_with_passphrase() {
+        let key = keygen(Some("hunter2"));
+        let content = encrypt(&key, None, b"a,b\n1,2\n", false);
+        let stage = stage(
+            &[
+                ("pgp-key", key.to_bytes().unwrap()),
+                ("pgp-pass", b"hunter2".to_vec()),
+            ],
+            json!({
+                "private_key": {"type": "secret", "name": "pgp-key"},
+                "passphrase": {"type": "secret", "name": "pgp-pass"},
+            }),
+        );
+
+        let result = stage
+            .process(make_test_item("drop.csv.pgp", content), &make_ctx())
+            .await
+            .unwrap();
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].content.as_ref(), b"a,b\n1,2\n");
+        assert_eq!(result[0].display_name, "drop.csv");
+        assert_eq!(result[0].mime_type, "text/csv");
+        assert_eq!(result[0].stream.as_deref(), Some("txn"));
+        assert!(!result[0].metadata.contains_key("_pgp_signer"));
+    }
+
+    #[tokio::test]
+    async fn test_decrypt_armored_message_verifies_signature() {
+        let key = keygen(None);
+        let partner = keygen(None);
+        let content = encrypt(&key, Some(&partner), b"payload", true);
+        let stage = stage(
+            &[
+                ("pgp-key", key.to_bytes().unwrap()),
+                ("partner-pub", public_bytes(&partner)),
+            ],
+            json!({
+                "private_key": {"type": "secret", "name": "pgp-key"},
+                "verify_keys": [{"type": "secret", "name": "partner-pub"}],
+            }),
+        );
+
+        let result = stage
+            .process(make_test_item("drop.gpg", content), &make_ctx())
+            .await
+            .unwrap();
+        assert_eq!(result[0].content.as_ref(), b"payload");
+        assert_eq!(
+            result[0].metadata["_pgp_signer"],
+            json!(format!("{:X}", partner.secret_subkeys[0].fingerprint()))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_decrypt_rejects_untrusted_or_missing_signature() {
+        let key = keygen(None);
+        let partner = keygen(None);
+        let impostor = keygen(None);
+        let params = json!({
+            "private_key": {"type": "secret", "name": "pgp-key"},
+            "verify_keys": [{"type": "secret", "name": "partner-pub"}],
+        });
+        let secrets = [
+            ("pgp-key", key.to_bytes().unwrap()),
+            ("partner-pub", public_bytes(&partner)),
+        ];
+
+        let forged = encrypt(&key, Some(&impostor), b"payload", false);
+        let err = stage(&secrets, params.clone())
+            .process(make_test_item("drop.pgp", forged), &make_ctx())
+            .await
+            .unwrap_err();
+        assert!(matches!(err, StageError::Permanent { .. }));
+        assert!(err.to_string().contains("bad signature"), "{err}");
+
+        let unsigned = encrypt(&key, None, b"payload", false);
+        let err = stage(&secrets, params)
+            .process(make_test_item("drop.pgp", unsigned), &make_ctx())
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("not signed"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn test_decrypt_missing_secret_is_permanent() {
+        let stage = stage(
+            &[],
+            json!({"private_key": {"type": "secret", "name": "absent"}}),
+        );
+        let err = stage
+            .process(make_test_item("drop.pgp", b"junk".to_vec()), &make_ctx())
+            .await
+            .unwrap_err();
+        assert!(matches!(err, StageError::Permanent { .. }));
+        assert!(err.to_string().contains("absent"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn test_decrypt_wrong_key_fails() {
+        let key = keygen(None);
+        let other = keygen(None);
+        let content = encrypt(&other, None, b"payload", false);
+        let stage = stage(
+            &[("pgp-key", key.to_bytes().unwrap())],
+            json!({"private_key": {"type": "secret", "name": "pgp-key"}}),
+        );
+        let err = stage
+            .process(make_test_item("drop.pgp", content), &make_ctx())
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("failed to decrypt"), "{err}");
+    }
+}
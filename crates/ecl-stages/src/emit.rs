@@ -1,30 +1,414 @@
 //! Emit stage: writes pipeline item content to the output directory.
+//!
+//! The output location comes from a path template such as
+//! `{source}/{date}/{display_name}`. Rendered paths are sanitized so every
+//! write stays under `output_dir`, and files are written to a temporary
+//! name and renamed into place so readers never see partial content.
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
 
 use async_trait::async_trait;
-use tracing::debug;
+use serde::Deserialize;
+use tokio::io::AsyncWriteExt;
+use tokio::sync::Mutex;
+use tracing::{debug, warn};
 
+use ecl_pipeline_spec::{SchemaError, StreamSchemas};
 use ecl_pipeline_topo::error::StageError;
 use ecl_pipeline_topo::{PipelineItem, Stage, StageContext};
 
+/// Suffix appended to an output path to name its sidecar metadata file.
+const SIDECAR_SUFFIX: &str = ".meta.json";
+
+/// Upper bound on `name-N.ext` candidates tried by the "suffix" policy.
+const MAX_SUFFIX_ATTEMPTS: usize = 10_000;
+
+/// Distinguishes temporary files written concurrently by this process.
+static TEMP_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// Parsed from stage params TOML/JSON.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct EmitConfig {
+    /// Output path template, relative to `output_dir`.
+    /// Default: `{id}`
+    #[serde(default = "default_path")]
+    path: String,
+    /// Directory (relative to `output_dir`) prepended to `path`, kept for
+    /// specs written before path templates. Default: none
+    #[serde(default)]
+    subdir: Option<String>,
+    /// What to do when the output file already exists:
+    /// "overwrite", "skip", or "suffix" (write `name-1.ext`, `name-2.ext`, ...).
+    /// Default: "overwrite"
+    #[serde(default = "default_on_collision")]
+    on_collision: String,
+    /// Whether to write a `<file>.meta.json` sidecar with provenance.
+    /// Default: false
+    #[serde(default)]
+    sidecar: bool,
+    /// File name (relative to `output_dir`) of a JSON Lines manifest listing
+    /// every item emitted in the run. Default: no manifest
+    #[serde(default)]
+    manifest: Option<String>,
+}
+
+impl Default for EmitConfig {
+    fn default() -> Self {
+        Self {
+            path: default_path(),
+            subdir: None,
+            on_collision: default_on_collision(),
+            sidecar: false,
+            manifest: None,
+        }
+    }
+}
+
+fn default_path() -> String {
+    "{id}".to_string()
+}
+
+fn default_on_collision() -> String {
+    "overwrite".to_string()
+}
+
+/// How to treat an output file that already exists.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Collision {
+    Overwrite,
+    Skip,
+    Suffix,
+}
+
+/// A piece of a parsed path template.
+#[derive(Debug, Clone, PartialEq)]
+enum Segment {
+    Literal(String),
+    Id,
+    DisplayName,
+    Stem,
+    Ext,
+    Source,
+    Stream,
+    Hash,
+    /// Extraction date, with an optional strftime format (default `%Y-%m-%d`).
+    Date(Option<String>),
+    Meta(String),
+}
+
+/// Parse a path template into segments.
+///
+/// Placeholders are written `{name}`; `{{` and `}}` are literal braces.
+fn parse_template(template: &str) -> Result<Vec<Segment>, String> {
+    let mut segments = Vec::new();
+    let mut literal = String::new();
+    let mut chars = template.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                literal.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                literal.push('}');
+            }
+            '{' => {
+                let mut name = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => name.push(c),
+                        None => return Err(format!("unclosed placeholder '{{{name}'")),
+                    }
+                }
+                if !literal.is_empty() {
+                    segments.push(Segment::Literal(std::mem::take(&mut literal)));
+                }
+                segments.push(parse_placeholder(&name)?);
+            }
+            '}' => return Err("unmatched '}'".to_string()),
+            c => literal.push(c),
+        }
+    }
+    if !literal.is_empty() {
+        segments.push(Segment::Literal(literal));
+    }
+    Ok(segments)
+}
+
+fn parse_placeholder(name: &str) -> Result<Segment, String> {
+    let segment = match name {
+        "id" => Segment::Id,
+        "display_name" => Segment::DisplayName,
+        "stem" => Segment::Stem,
+        "ext" => Segment::Ext,
+        "source" => Segment::Source,
+        "stream" => Segment::Stream,
+        "hash" => Segment::Hash,
+        "date" => Segment::Date(None),
+        _ => {
+            if let Some(format) = name.strip_prefix("date:") {
+                let items = chrono::format::StrftimeItems::new(format);
+                if items
+                    .into_iter()
+                    .any(|item| matches!(item, chrono::format::Item::Error))
+                {
+                    return Err(format!("invalid date format '{format}'"));
+                }
+                Segment::Date(Some(format.to_string()))
+            } else if let Some(key) = name.strip_prefix("meta.")
+                && !key.is_empty()
+            {
+                Segment::Meta(key.to_string())
+            } else {
+                return Err(format!("unknown placeholder '{{{name}}}'"));
+            }
+        }
+    };
+    Ok(segment)
+}
+
+/// Render a parsed template for one item. Missing values render as `unknown`.
+fn render_template(segments: &[Segment], item: &PipelineItem) -> String {
+    let name = Path::new(&item.display_name);
+    let mut out = String::new();
+    for segment in segments {
+        match segment {
+            Segment::Literal(text) => out.push_str(text),
+            Segment::Id => out.push_str(&item.id),
+            Segment::DisplayName => out.push_str(&item.display_name),
+            Segment::Stem => out.push_str(
+                name.file_stem()
+                    .and_then(|s| s.to_str())
+                    .unwrap_or("unknown"),
+            ),
+            Segment::Ext => out.push_str(name.extension().and_then(|e| e.to_str()).unwrap_or("")),
+            Segment::Source => out.push_str(&item.source_name),
+            Segment::Stream => out.push_str(item.stream.as_deref().unwrap_or("unknown")),
+            Segment::Hash => out.push_str(item.source_content_hash.as_str()),
+            Segment::Date(format) => {
+                let format = format.as_deref().unwrap_or("%Y-%m-%d");
+                out.push_str(&item.provenance.extracted_at.format(format).to_string());
+            }
+            Segment::Meta(key) => match item.metadata.get(key) {
+                Some(serde_json::Value::String(s)) => out.push_str(s),
+                Some(serde_json::Value::Null) | None => out.push_str("unknown"),
+                Some(other) => out.push_str(&other.to_string()),
+            },
+        }
+    }
+    out
+}
+
+/// Turn a rendered path into a relative path that cannot escape its root.
+///
+/// `/` and `\\` separate components; empty, `.` and `..` components and
+/// drive prefixes are dropped and control characters become `_`. Returns
+/// `None` if nothing is left.
+fn sanitize_relative_path(rendered: &str) -> Option<PathBuf> {
+    let components: Vec<String> = rendered
+        .split(['/', '\\'])
+        .map(|part| {
+            part.trim()
+                .chars()
+                .map(|c| if c.is_control() { '_' } else { c })
+                .collect::<String>()
+        })
+        .filter(|part| !matches!(part.as_str(), "" | "." | ".."))
+        .filter(|part| !(part.len() == 2 && part.ends_with(':')))
+        .collect();
+    (!components.is_empty()).then(|| components.iter().collect())
+}
+
+/// `dir/name.ext` -> `dir/name-{n}.ext`.
+fn suffixed_path(path: &Path, n: usize) -> PathBuf {
+    let stem = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let name = match path.extension() {
+        Some(ext) => format!("{stem}-{n}.{}", ext.to_string_lossy()),
+        None => format!("{stem}-{n}"),
+    };
+    path.with_file_name(name)
+}
+
+/// A temporary sibling of `path` for write-then-rename.
+fn temp_path(path: &Path) -> PathBuf {
+    let name = path
+        .file_name()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let n = TEMP_COUNTER.fetch_add(1, Ordering::Relaxed);
+    path.with_file_name(format!(".{name}.{}.{n}.tmp", std::process::id()))
+}
+
 /// Emit stage that writes pipeline items to files in the output directory.
 ///
-/// Each item is written to `{output_dir}/{item.id}`. Parent directories
-/// are created automatically. The item passes through unchanged after
-/// writing, so downstream stages (if any) still receive it.
+/// Each item is written to `{output_dir}/{path}`, where `path` is the
+/// configured template rendered for the item (default `{id}`), under
+/// `subdir` if one is set. Parent
+/// directories are created automatically. The item passes through after
+/// writing, with `_emit_path` set to the path it was written to relative
+/// to `output_dir`, so downstream stages (if any) still receive it.
+///
+/// Template placeholders: `{id}`, `{display_name}`, `{stem}`, `{ext}`,
+/// `{source}`, `{stream}`, `{hash}`, `{date}` / `{date:<strftime>}` and
+/// `{meta.<key>}`.
 #[derive(Debug)]
-pub struct EmitStage;
+pub struct EmitStage {
+    config: EmitConfig,
+    template: Vec<Segment>,
+    collision: Collision,
+    /// Whether the manifest has been truncated for this run yet.
+    manifest_started: Mutex<bool>,
+}
 
 impl EmitStage {
-    /// Create a new emit stage.
+    /// Create a new emit stage with the default layout (`{id}`, overwrite).
     pub fn new() -> Self {
-        Self
+        Self {
+            config: EmitConfig::default(),
+            template: vec![Segment::Id],
+            collision: Collision::Overwrite,
+            manifest_started: Mutex::new(false),
+        }
+    }
+
+    /// Create from stage params JSON. Null params give the default layout.
+    ///
+    /// # Errors
+    ///
+    /// Returns `StageError::Permanent` if the config is invalid, the path
+    /// template does not parse, or the collision policy is unknown.
+    pub fn from_params(params: &serde_json::Value) -> Result<Self, StageError> {
+        let invalid = |message: String| StageError::Permanent {
+            stage: "emit".to_string(),
+            item_id: String::new(),
+            message: format!("invalid emit config: {message}"),
+        };
+        let config: EmitConfig = if params.is_null() {
+            EmitConfig::default()
+        } else {
+            serde_json::from_value(params.clone()).map_err(|e| invalid(e.to_string()))?
+        };
+        let mut template =
+            parse_template(&config.path).map_err(|e| invalid(format!("bad path template: {e}")))?;
+        if let Some(subdir) = &config.subdir {
+            template.insert(0, Segment::Literal(format!("{subdir}/")));
+        }
+        let collision = match config.on_collision.as_str() {
+            "overwrite" => Collision::Overwrite,
+            "skip" => Collision::Skip,
+            "suffix" => Collision::Suffix,
+            other => return Err(invalid(format!("unknown on_collision '{other}'"))),
+        };
+        if let Some(manifest) = &config.manifest
+            && sanitize_relative_path(manifest).is_none()
+        {
+            return Err(invalid(format!("bad manifest path '{manifest}'")));
+        }
+        Ok(Self {
+            config,
+            template,
+            collision,
+            manifest_started: Mutex::new(false),
+        })
+    }
+
+    /// Compute the output path for an item, relative to `output_dir`.
+    fn relative_path(&self, item: &PipelineItem) -> Option<PathBuf> {
+        sanitize_relative_path(&render_template(&self.template, item))
+    }
+
+    /// Write `content` to `target` via a temporary file, honouring the
+    /// collision policy. Returns the path written, or `None` if skipped.
+    async fn write_atomic(
+        &self,
+        target: &Path,
+        content: &[u8],
+        collision: Collision,
+    ) -> std::io::Result<Option<PathBuf>> {
+        let temp = temp_path(target);
+        tokio::fs::write(&temp, content).await?;
+
+        if collision == Collision::Overwrite {
+            if let Err(e) = tokio::fs::rename(&temp, target).await {
+                let _ = tokio::fs::remove_file(&temp).await;
+                return Err(e);
+            }
+            return Ok(Some(target.to_path_buf()));
+        }
+
+        // Hard links fail if the target exists, which makes the existence
+        // check and the write a single atomic step.
+        let mut result = Ok(None);
+        for n in 0..MAX_SUFFIX_ATTEMPTS {
+            let candidate = if n == 0 {
+                target.to_path_buf()
+            } else {
+                suffixed_path(target, n)
+            };
+            match tokio::fs::hard_link(&temp, &candidate).await {
+                Ok(()) => {
+                    result = Ok(Some(candidate));
+                    break;
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {
+                    if collision == Collision::Skip {
+                        break;
+                    }
+                }
+                Err(e) => {
+                    result = Err(e);
+                    break;
+                }
+            }
+        }
+        let _ = tokio::fs::remove_file(&temp).await;
+        if collision == Collision::Suffix && matches!(result, Ok(None)) {
+            return Err(std::io::Error::other(format!(
+                "no free name after {MAX_SUFFIX_ATTEMPTS} attempts"
+            )));
+        }
+        result
     }
 
-    /// Compute the output path for an item.
-    fn output_path(output_dir: &std::path::Path, item_id: &str) -> PathBuf {
-        output_dir.join(item_id)
+    /// Append one line to the run manifest, truncating it on first use.
+    async fn record_manifest(
+        &self,
+        output_dir: &Path,
+        entry: &serde_json::Value,
+    ) -> std::io::Result<()> {
+        let Some(relative) = self
+            .config
+            .manifest
+            .as_deref()
+            .and_then(sanitize_relative_path)
+        else {
+            return Ok(());
+        };
+        let path = output_dir.join(relative);
+        let mut started = self.manifest_started.lock().await;
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(!*started)
+            .append(*started)
+            .open(&path)
+            .await?;
+        let mut line = entry.to_string();
+        line.push('\n');
+        file.write_all(line.as_bytes()).await?;
+        file.flush().await?;
+        *started = true;
+        Ok(())
     }
 }
 
@@ -42,40 +426,102 @@ impl Stage for EmitStage {
 
     async fn process(
         &self,
-        item: PipelineItem,
+        mut item: PipelineItem,
         ctx: &StageContext,
     ) -> Result<Vec<PipelineItem>, StageError> {
-        let output_path = Self::output_path(&ctx.output_dir, &item.id);
+        let permanent = |message: String| StageError::Permanent {
+            stage: "emit".to_string(),
+            item_id: item.id.clone(),
+            message,
+        };
+        let relative = self
+            .relative_path(&item)
+            .ok_or_else(|| permanent("output path template rendered an empty path".to_string()))?;
+        let output_path = ctx.output_dir.join(&relative);
 
         // Create parent directories
         if let Some(parent) = output_path.parent() {
-            tokio::fs::create_dir_all(parent)
-                .await
-                .map_err(|e| StageError::Permanent {
-                    stage: "emit".to_string(),
-                    item_id: item.id.clone(),
-                    message: format!("failed to create directory {}: {e}", parent.display()),
-                })?;
+            tokio::fs::create_dir_all(parent).await.map_err(|e| {
+                permanent(format!(
+                    "failed to create directory {}: {e}",
+                    parent.display()
+                ))
+            })?;
         }
 
         // Write content
-        tokio::fs::write(&output_path, item.content.as_ref())
+        let written = self
+            .write_atomic(&output_path, &item.content, self.collision)
             .await
-            .map_err(|e| StageError::Permanent {
-                stage: "emit".to_string(),
-                item_id: item.id.clone(),
-                message: format!("failed to write {}: {e}", output_path.display()),
-            })?;
-
-        debug!(
-            item_id = %item.id,
-            path = %output_path.display(),
-            bytes = item.content.len(),
-            "emit: wrote file"
-        );
+            .map_err(|e| permanent(format!("failed to write {}: {e}", output_path.display())))?;
+
+        let status = match &written {
+            Some(path) => {
+                let relative = path.strip_prefix(&ctx.output_dir).unwrap_or(path);
+                item.metadata.insert(
+                    "_emit_path".to_string(),
+                    serde_json::Value::String(relative.to_string_lossy().into_owned()),
+                );
+                if self.config.sidecar {
+                    let mut sidecar = path.clone().into_os_string();
+                    sidecar.push(SIDECAR_SUFFIX);
+                    let body = serde_json::to_vec_pretty(&serde_json::json!({
+                        "id": item.id,
+                        "display_name": item.display_name,
+                        "mime_type": item.mime_type,
+                        "source_name": item.source_name,
+                        "source_content_hash": item.source_content_hash,
+                        "stream": item.stream,
+                        "provenance": item.provenance,
+                        "metadata": item.metadata,
+                    }))
+                    .map_err(|e| permanent(format!("failed to encode sidecar: {e}")))?;
+                    self.write_atomic(Path::new(&sidecar), &body, Collision::Overwrite)
+                        .await
+                        .map_err(|e| permanent(format!("failed to write sidecar: {e}")))?;
+                }
+                debug!(
+                    item_id = %item.id,
+                    path = %path.display(),
+                    bytes = item.content.len(),
+                    "emit: wrote file"
+                );
+                "written"
+            }
+            None => {
+                warn!(
+                    item_id = %item.id,
+                    path = %output_path.display(),
+                    "emit: output exists, skipping"
+                );
+                "skipped"
+            }
+        };
+
+        let path = written.as_ref().unwrap_or(&output_path);
+        let entry = serde_json::json!({
+            "id": item.id,
+            "path": path.strip_prefix(&ctx.output_dir).unwrap_or(path),
+            "status": status,
+            "bytes": item.content.len(),
+            "mime_type": item.mime_type,
+            "source_name": item.source_name,
+            "stream": item.stream,
+        });
+        self.record_manifest(&ctx.output_dir, &entry)
+            .await
+            .map_err(|e| permanent(format!("failed to update manifest: {e}")))?;
 
         Ok(vec![item])
     }
+
+    fn transform_schema(
+        &self,
+        inputs: &StreamSchemas,
+    ) -> Result<Option<StreamSchemas>, SchemaError> {
+        // Items pass through with their records untouched.
+        Ok(Some(inputs.clone()))
+    }
 }
 
 #[cfg(test)]
@@ -84,6 +530,7 @@ mod tests {
     use super::*;
     use ecl_pipeline_spec::PipelineSpec;
     use ecl_pipeline_state::{Blake3Hash, ItemProvenance};
+    use serde_json::json;
     use std::collections::BTreeMap;
     use std::sync::Arc;
     use tempfile::TempDir;
@@ -143,20 +590,76 @@ resources = { creates = ["docs"] }
 
     #[test]
     fn test_emit_stage_default() {
-        let stage = EmitStage;
+        let stage = EmitStage::default();
         assert_eq!(stage.name(), "emit");
     }
 
     #[test]
     fn test_output_path_simple() {
-        let path = EmitStage::output_path(std::path::Path::new("/out"), "readme.md");
-        assert_eq!(path, PathBuf::from("/out/readme.md"));
+        let path = EmitStage::new().relative_path(&make_item("readme.md", b""));
+        assert_eq!(path, Some(PathBuf::from("readme.md")));
     }
 
     #[test]
     fn test_output_path_nested() {
-        let path = EmitStage::output_path(std::path::Path::new("/out"), "sub/dir/file.txt");
-        assert_eq!(path, PathBuf::from("/out/sub/dir/file.txt"));
+        let path = EmitStage::new().relative_path(&make_item("sub/dir/file.txt", b""));
+        assert_eq!(path, Some(PathBuf::from("sub/dir/file.txt")));
+    }
+
+    #[test]
+    fn test_output_path_stays_under_output_dir() {
+        let stage = EmitStage::new();
+        let path = stage.relative_path(&make_item("../../etc/passwd", b""));
+        assert_eq!(path, Some(PathBuf::from("etc/passwd")));
+        let path = stage.relative_path(&make_item("C:\\abs\\.\\a:b\n.txt", b""));
+        assert_eq!(path, Some(PathBuf::from("abs/a:b_.txt")));
+        assert_eq!(stage.relative_path(&make_item("..", b"")), None);
+    }
+
+    #[test]
+    fn test_output_path_template() {
+        let stage = EmitStage::from_params(&json!({
+            "path": "{source}/{stream}/{date:%Y}/{meta.channel}/{stem}.{{x}}.{ext}"
+        }))
+        .unwrap();
+        let mut item = make_item("id-1", b"");
+        item.display_name = "report.final.csv".to_string();
+        item.stream = Some("orders".to_string());
+        item.metadata
+            .insert("channel".to_string(), json!("#ops/alerts"));
+        let year = item.provenance.extracted_at.format("%Y").to_string();
+
+        let path = stage.relative_path(&item).unwrap();
+        assert_eq!(
+            path,
+            PathBuf::from(format!(
+                "local/orders/{year}/#ops/alerts/report.final.{{x}}.csv"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_output_path_subdir_prefixes_template() {
+        let stage = EmitStage::from_params(&json!({ "subdir": "normalized" })).unwrap();
+        let path = stage.relative_path(&make_item("docs/a.md", b""));
+        assert_eq!(path, Some(PathBuf::from("normalized/docs/a.md")));
+
+        let stage =
+            EmitStage::from_params(&json!({ "subdir": "out/{x}", "path": "{source}/{id}" }))
+                .unwrap();
+        let path = stage.relative_path(&make_item("a.md", b""));
+        assert_eq!(path, Some(PathBuf::from("out/{x}/local/a.md")));
+    }
+
+    #[test]
+    fn test_emit_rejects_bad_config() {
+        assert!(EmitStage::from_params(&json!({ "path": "{nope}" })).is_err());
+        assert!(EmitStage::from_params(&json!({ "path": "{id" })).is_err());
+        assert!(EmitStage::from_params(&json!({ "path": "{date:%Q}" })).is_err());
+        assert!(EmitStage::from_params(&json!({ "on_collision": "merge" })).is_err());
+        assert!(EmitStage::from_params(&json!({ "manifest": "../" })).is_err());
+        assert!(EmitStage::from_params(&json!({ "sub_dir": "x" })).is_err());
+        assert!(EmitStage::from_params(&serde_json::Value::Null).is_ok());
     }
 
     #[tokio::test]
@@ -244,4 +747,90 @@ resources = { creates = ["docs"] }
         let written = std::fs::read(tmp.path().join("empty.txt")).unwrap();
         assert!(written.is_empty());
     }
+
+    #[tokio::test]
+    async fn test_emit_collision_skip_and_suffix() {
+        let tmp = TempDir::new().unwrap();
+        let ctx = make_context(tmp.path().to_path_buf());
+
+        let skip = EmitStage::from_params(&json!({ "on_collision": "skip" })).unwrap();
+        let first = skip
+            .process(make_item("a.txt", b"one"), &ctx)
+            .await
+            .unwrap();
+        assert_eq!(first[0].metadata["_emit_path"], json!("a.txt"));
+        let second = skip
+            .process(make_item("a.txt", b"two"), &ctx)
+            .await
+            .unwrap();
+        assert!(!second[0].metadata.contains_key("_emit_path"));
+        assert_eq!(
+            std::fs::read_to_string(tmp.path().join("a.txt")).unwrap(),
+            "one"
+        );
+
+        let suffix = EmitStage::from_params(&json!({ "on_collision": "suffix" })).unwrap();
+        for content in [b"two", b"tri"] {
+            suffix
+                .process(make_item("a.txt", content), &ctx)
+                .await
+                .unwrap();
+        }
+        assert_eq!(
+            std::fs::read_to_string(tmp.path().join("a-1.txt")).unwrap(),
+            "two"
+        );
+        assert_eq!(
+            std::fs::read_to_string(tmp.path().join("a-2.txt")).unwrap(),
+            "tri"
+        );
+
+        // No temporary files are left behind.
+        let mut names: Vec<_> = std::fs::read_dir(tmp.path())
+            .unwrap()
+            .map(|e| e.unwrap().file_name().into_string().unwrap())
+            .collect();
+        names.sort();
+        assert_eq!(names, vec!["a-1.txt", "a-2.txt", "a.txt"]);
+    }
+
+    #[tokio::test]
+    async fn test_emit_writes_sidecar_and_manifest() {
+        let tmp = TempDir::new().unwrap();
+        let ctx = make_context(tmp.path().to_path_buf());
+        std::fs::write(tmp.path().join("manifest.jsonl"), "stale\n").unwrap();
+
+        let stage = EmitStage::from_params(&json!({
+            "path": "{source}/{display_name}",
+            "sidecar": true,
+            "manifest": "manifest.jsonl",
+        }))
+        .unwrap();
+        stage
+            .process(make_item("x/1.md", b"# one"), &ctx)
+            .await
+            .unwrap();
+        stage
+            .process(make_item("x/2.md", b"# two"), &ctx)
+            .await
+            .unwrap();
+
+        let sidecar: serde_json::Value = serde_json::from_slice(
+            &std::fs::read(tmp.path().join("local/x/1.md.meta.json")).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(sidecar["id"], json!("x/1.md"));
+        assert_eq!(sidecar["source_content_hash"], json!("aabb"));
+        assert_eq!(sidecar["provenance"]["source_kind"], json!("filesystem"));
+
+        let manifest = std::fs::read_to_string(tmp.path().join("manifest.jsonl")).unwrap();
+        let lines: Vec<serde_json::Value> = manifest
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["path"], json!("local/x/1.md"));
+        assert_eq!(lines[1]["status"], json!("written"));
+        assert_eq!(lines[1]["bytes"], json!(5));
+    }
 }
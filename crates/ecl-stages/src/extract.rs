@@ -6,15 +6,19 @@ use std::sync::Arc;
 use async_trait::async_trait;
 use tracing::debug;
 
+use ecl_pipeline_spec::{SchemaError, StreamSchemas};
 use ecl_pipeline_topo::error::StageError;
-use ecl_pipeline_topo::{PipelineItem, SourceAdapter, SourceItem, Stage, StageContext};
+use ecl_pipeline_topo::{
+    PipelineItem, RECORD_MIME_TYPE, Record, SourceAdapter, SourceItem, Stage, StageContext,
+};
 
 /// Extract stage that fetches content from a source adapter.
 ///
 /// For each `PipelineItem` that arrives (typically created by the runner from
 /// `SourceItem` enumeration), this stage calls `adapter.fetch()` to retrieve
 /// the full document content. The result replaces the item's content and
-/// metadata.
+/// metadata. Documents of type [`RECORD_MIME_TYPE`] are decoded into the
+/// item's `record`.
 #[derive(Debug)]
 pub struct ExtractStage {
     /// The source adapter to fetch from.
@@ -66,6 +70,18 @@ impl Stage for ExtractStage {
                 message: format!("source fetch failed: {e}"),
             })?;
 
+        let record = if doc.mime_type == RECORD_MIME_TYPE {
+            let record: Record =
+                serde_json::from_slice(&doc.content).map_err(|e| StageError::Permanent {
+                    stage: "extract".to_string(),
+                    item_id: item.id.clone(),
+                    message: format!("invalid record content: {e}"),
+                })?;
+            Some(record)
+        } else {
+            None
+        };
+
         let extracted = PipelineItem {
             id: doc.id,
             display_name: doc.display_name,
@@ -75,12 +91,20 @@ impl Stage for ExtractStage {
             source_content_hash: doc.content_hash,
             provenance: doc.provenance,
             metadata: BTreeMap::new(),
-            record: None,
+            record,
             stream: item.stream.clone(),
         };
 
         Ok(vec![extracted])
     }
+
+    fn transform_schema(
+        &self,
+        inputs: &StreamSchemas,
+    ) -> Result<Option<StreamSchemas>, SchemaError> {
+        // Items pass through with their records untouched.
+        Ok(Some(inputs.clone()))
+    }
 }
 
 #[cfg(test)]
@@ -142,6 +166,36 @@ mod tests {
         }
     }
 
+    #[derive(Debug)]
+    struct RecordAdapter {
+        content: Vec<u8>,
+    }
+
+    #[async_trait]
+    impl SourceAdapter for RecordAdapter {
+        fn source_kind(&self) -> &str {
+            "record"
+        }
+        async fn enumerate(&self) -> Result<Vec<SourceItem>, SourceError> {
+            Ok(vec![])
+        }
+        async fn fetch(&self, item: &SourceItem) -> Result<ExtractedDocument, SourceError> {
+            Ok(ExtractedDocument {
+                id: item.id.clone(),
+                display_name: item.display_name.clone(),
+                content: self.content.clone(),
+                mime_type: RECORD_MIME_TYPE.to_string(),
+                provenance: ItemProvenance {
+                    source_kind: "record".to_string(),
+                    metadata: BTreeMap::new(),
+                    source_modified: None,
+                    extracted_at: chrono::Utc::now(),
+                },
+                content_hash: Blake3Hash::new("abc123"),
+            })
+        }
+    }
+
     fn make_pipeline_item() -> PipelineItem {
         PipelineItem {
             id: "test-file.md".to_string(),
@@ -235,4 +289,33 @@ resources = { creates = ["docs"] }
         assert!(result.is_err());
         assert!(matches!(result.unwrap_err(), StageError::Permanent { .. }));
     }
+
+    #[tokio::test]
+    async fn test_extract_stage_decodes_record_documents() {
+        let stage = ExtractStage::new(
+            Arc::new(RecordAdapter {
+                content: br#"{"id": 7, "name": "Alice"}"#.to_vec(),
+            }),
+            "db",
+        );
+        let result = stage
+            .process(make_pipeline_item(), &make_context())
+            .await
+            .unwrap();
+        let record = result[0].record.as_ref().unwrap();
+        assert_eq!(record["id"], 7);
+        assert_eq!(record["name"], "Alice");
+
+        let stage = ExtractStage::new(
+            Arc::new(RecordAdapter {
+                content: b"[1, 2]".to_vec(),
+            }),
+            "db",
+        );
+        let err = stage
+            .process(make_pipeline_item(), &make_context())
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("invalid record content"));
+    }
 }
@@ -12,7 +12,9 @@ use regex::Regex;
 use serde::Deserialize;
 use tracing::debug;
 
+use ecl_pipeline_spec::{FieldSchema, FieldType, SchemaError, StreamSchema, StreamSchemas};
 use ecl_pipeline_topo::error::StageError;
+use ecl_pipeline_topo::schema::map_streams;
 use ecl_pipeline_topo::{PipelineItem, Record, Stage, StageContext};
 
 /// Configuration for the field mapping stage, deserialized from stage params.
@@ -196,6 +198,50 @@ impl FieldMapStage {
         })
     }
 
+    /// Apply the operations to a stream schema, in the same order as
+    /// `process` applies them to records.
+    fn map_schema(&self, stream: &str, input: &StreamSchema) -> Result<StreamSchema, SchemaError> {
+        let mut schema = input.clone();
+        for op in &self.config.rename {
+            let mut field = schema.require(stream, &op.from)?.clone();
+            schema.remove_field(&op.from);
+            field.name = op.to.clone();
+            schema.set_field(field);
+        }
+        for op in &self.config.copy {
+            let mut field = schema.require(stream, &op.from)?.clone();
+            field.name = op.to.clone();
+            schema.set_field(field);
+        }
+        for op in &self.config.set {
+            let mut field = FieldSchema::new(&op.field, FieldType::of_value(&op.value));
+            field.nullable = op.value.is_null();
+            schema.set_field(field);
+        }
+        for op in &self.config.parse_dates {
+            schema.require(stream, &op.field)?;
+            schema.set_field(FieldSchema::new(&op.output, FieldType::Timestamp).nullable());
+        }
+        for op in &self.config.pad {
+            schema.require(stream, &op.field)?;
+        }
+        for op in &self.config.regex_extract {
+            schema.require(stream, &op.field)?;
+            schema.set_field(FieldSchema::new(&op.output, FieldType::String).nullable());
+        }
+        for op in &self.config.nest {
+            for src_field in op.fields.values() {
+                schema.require(stream, src_field)?;
+            }
+            schema.set_field(FieldSchema::new(&op.output, FieldType::Object));
+        }
+        for field in &self.config.drop {
+            schema.require(stream, field)?;
+            schema.remove_field(field);
+        }
+        Ok(schema)
+    }
+
     /// Apply all rename operations to the record.
     fn apply_renames(record: &mut Record, ops: &[RenameOp]) {
         for op in ops {
@@ -347,6 +393,13 @@ impl Stage for FieldMapStage {
 
         Ok(vec![item])
     }
+
+    fn transform_schema(
+        &self,
+        inputs: &StreamSchemas,
+    ) -> Result<Option<StreamSchemas>, SchemaError> {
+        map_streams(inputs, |stream, schema| self.map_schema(stream, schema))
+    }
 }
 
 #[cfg(test)]
@@ -817,4 +870,46 @@ resources = { creates = ["mapped"] }
         let result = FieldMapStage::from_params(&params);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_field_map_transform_schema() {
+        let params = json!({
+            "rename": [{ "from": "Amt", "to": "amount" }],
+            "set": [{ "field": "partner_id", "value": 290 }],
+            "parse_dates": [{ "field": "Date", "format": "%m/%d/%Y", "output": "ts" }],
+            "drop": ["Date"]
+        });
+        let stage = FieldMapStage::from_params(&params).unwrap();
+        let inputs = StreamSchemas::from([(
+            "txn".to_string(),
+            StreamSchema::new(vec![
+                FieldSchema::new("Amt", FieldType::Float),
+                FieldSchema::new("Date", FieldType::String),
+            ]),
+        )]);
+        let outputs = stage.transform_schema(&inputs).unwrap().unwrap();
+        let schema = &outputs["txn"];
+        let names: Vec<&str> = schema.fields.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, ["amount", "partner_id", "ts"]);
+        assert_eq!(
+            schema.field("partner_id").unwrap().field_type,
+            FieldType::Integer
+        );
+        assert!(schema.field("ts").unwrap().nullable);
+    }
+
+    #[test]
+    fn test_field_map_transform_schema_unknown_field() {
+        let params = json!({ "rename": [{ "from": "Amout", "to": "amount" }] });
+        let stage = FieldMapStage::from_params(&params).unwrap();
+        let inputs = StreamSchemas::from([(
+            "txn".to_string(),
+            StreamSchema::new(vec![FieldSchema::new("Amount", FieldType::Float)]),
+        )]);
+        let err = stage.transform_schema(&inputs).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "stream 'txn' has no field 'Amout' (did you mean 'Amount'?)"
+        );
+    }
 }
@@ -4,6 +4,7 @@ use async_trait::async_trait;
 use glob::Pattern;
 use tracing::debug;
 
+use ecl_pipeline_spec::{SchemaError, StreamSchemas};
 use ecl_pipeline_topo::error::StageError;
 use ecl_pipeline_topo::{PipelineItem, Stage, StageContext};
 
@@ -109,6 +110,14 @@ impl Stage for FilterStage {
             Ok(vec![])
         }
     }
+
+    fn transform_schema(
+        &self,
+        inputs: &StreamSchemas,
+    ) -> Result<Option<StreamSchemas>, SchemaError> {
+        // Items pass through with their records untouched.
+        Ok(Some(inputs.clone()))
+    }
 }
 
 #[cfg(test)]
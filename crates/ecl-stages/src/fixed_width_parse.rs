@@ -0,0 +1,262 @@
+//! Fixed-width parsing stage: fan-out from file → individual records.
+//!
+//! Reads fixed-width text from a `PipelineItem`, slices each line into
+//! columns by character offset, and emits one `PipelineItem` per line.
+//! Columns use the same typing as `csv_parse`.
+
+use async_trait::async_trait;
+use serde::Deserialize;
+
+use ecl_pipeline_spec::{SchemaError, StreamSchemas};
+use ecl_pipeline_topo::error::StageError;
+use ecl_pipeline_topo::{PipelineItem, Record, Stage, StageContext};
+
+use crate::columns::{
+    ColumnDef, columns_schema, convert_value, default_on_error, row_error, row_item,
+};
+
+/// Parsed from stage params TOML/JSON.
+#[derive(Debug, Clone, Deserialize)]
+struct FixedWidthParseConfig {
+    /// Column definitions with their character offsets.
+    columns: Vec<FixedWidthColumnDef>,
+    /// Number of leading lines (headers, banners) to skip. Default: 0
+    #[serde(default)]
+    skip_lines: usize,
+    /// Whether to trim surrounding whitespace from each value. Default: true
+    #[serde(default = "default_trim")]
+    trim: bool,
+    /// How to handle parse errors for individual rows.
+    /// "skip" = skip bad rows (log warning), "fail" = fail the item.
+    /// Default: "skip"
+    #[serde(default = "default_on_error")]
+    on_row_error: String,
+}
+
+/// A column definition with its position in the line.
+#[derive(Debug, Clone, Deserialize)]
+struct FixedWidthColumnDef {
+    #[serde(flatten)]
+    column: ColumnDef,
+    /// Zero-based character offset of the column.
+    start: usize,
+    /// Column width in characters. A short final column takes what remains.
+    width: usize,
+}
+
+fn default_trim() -> bool {
+    true
+}
+
+/// Fixed-width parsing stage: one file in, N record items out.
+#[derive(Debug)]
+pub struct FixedWidthParseStage {
+    config: FixedWidthParseConfig,
+}
+
+impl FixedWidthParseStage {
+    /// Create from stage params JSON.
+    ///
+    /// # Errors
+    ///
+    /// Returns `StageError::Permanent` if the config is invalid.
+    pub fn from_params(params: &serde_json::Value) -> Result<Self, StageError> {
+        let config: FixedWidthParseConfig =
+            serde_json::from_value(params.clone()).map_err(|e| StageError::Permanent {
+                stage: "fixed_width_parse".to_string(),
+                item_id: String::new(),
+                message: format!("invalid fixed_width_parse config: {e}"),
+            })?;
+        Ok(Self { config })
+    }
+
+    /// Slice one line into a record, or describe why it is bad.
+    fn parse_line(&self, line: &str) -> Result<Record, String> {
+        let chars: Vec<char> = line.chars().collect();
+        let mut record = Record::new();
+        for col in &self.config.columns {
+            if col.start >= chars.len() {
+                return Err(format!(
+                    "line has {} characters, column '{}' starts at {}",
+                    chars.len(),
+                    col.column.name,
+                    col.start
+                ));
+            }
+            let end = col.start.saturating_add(col.width).min(chars.len());
+            let raw: String = chars[col.start..end].iter().collect();
+            let raw = if self.config.trim { raw.trim() } else { &raw };
+            record.insert(
+                col.column.name.clone(),
+                convert_value(raw, &col.column.r#type),
+            );
+        }
+        Ok(record)
+    }
+}
+
+#[async_trait]
+impl Stage for FixedWidthParseStage {
+    fn name(&self) -> &str {
+        "fixed_width_parse"
+    }
+
+    async fn process(
+        &self,
+        item: PipelineItem,
+        _ctx: &StageContext,
+    ) -> Result<Vec<PipelineItem>, StageError> {
+        let text = String::from_utf8_lossy(&item.content);
+        let mut output = Vec::new();
+
+        for (idx, line) in text.lines().enumerate().skip(self.config.skip_lines) {
+            let line_number = idx + 1;
+            if line.trim().is_empty() {
+                continue;
+            }
+            match self.parse_line(line) {
+                Ok(record) => output.push(row_item(
+                    &item,
+                    line_number,
+                    record,
+                    line.as_bytes().to_vec(),
+                    "text/plain",
+                )),
+                Err(e) => row_error(
+                    "fixed_width_parse",
+                    &self.config.on_row_error,
+                    &item,
+                    line_number,
+                    e,
+                )?,
+            }
+        }
+
+        Ok(output)
+    }
+
+    fn transform_schema(
+        &self,
+        inputs: &StreamSchemas,
+    ) -> Result<Option<StreamSchemas>, SchemaError> {
+        let columns = self.config.columns.iter().map(|c| &c.column);
+        Ok(Some(columns_schema(columns, inputs)))
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use ecl_pipeline_state::{Blake3Hash, ItemProvenance};
+    use serde_json::json;
+    use std::collections::BTreeMap;
+    use std::sync::Arc;
+
+    fn make_test_item(content: &str) -> PipelineItem {
+        PipelineItem {
+            id: "file-1".to_string(),
+            display_name: "test.txt".to_string(),
+            content: Arc::from(content.as_bytes()),
+            mime_type: "text/plain".to_string(),
+            source_name: "test".to_string(),
+            source_content_hash: Blake3Hash::new("abc"),
+            provenance: ItemProvenance {
+                source_kind: "filesystem".to_string(),
+                metadata: BTreeMap::new(),
+                source_modified: None,
+                extracted_at: chrono::Utc::now(),
+            },
+            metadata: BTreeMap::new(),
+            record: None,
+            stream: None,
+        }
+    }
+
+    fn make_ctx() -> StageContext {
+        StageContext {
+            spec: Arc::new(
+                ecl_pipeline_spec::PipelineSpec::from_toml(
+                    "name = \"t\"\nversion = 1\noutput_dir = \"./o\"\n[sources.s]\nkind = \"filesystem\"\nroot = \"/tmp\"\n[stages.x]\nadapter = \"x\"\nresources = { creates = [\"y\"] }",
+                ).unwrap(),
+            ),
+            output_dir: std::path::PathBuf::from("/tmp"),
+            params: serde_json::Value::Null,
+            span: tracing::Span::none(),
+        }
+    }
+
+    fn params(extra: serde_json::Value) -> serde_json::Value {
+        let mut params = json!({
+            "columns": [
+                {"name": "sku", "start": 0, "width": 6},
+                {"name": "qty", "type": "integer", "start": 6, "width": 4},
+                {"name": "desc", "start": 10, "width": 20},
+            ]
+        });
+        params
+            .as_object_mut()
+            .unwrap()
+            .extend(extra.as_object().unwrap().clone());
+        params
+    }
+
+    #[tokio::test]
+    async fn test_fixed_width_parse_slices_columns() {
+        let content = "SKU   QTY DESC\nA100     3Widget\nB200    12Gadget deluxe\n";
+        let stage = FixedWidthParseStage::from_params(&params(json!({"skip_lines": 1}))).unwrap();
+
+        let result = stage
+            .process(make_test_item(content), &make_ctx())
+            .await
+            .unwrap();
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0].id, "file-1:row:2");
+        let rec = result[1].record.as_ref().unwrap();
+        assert_eq!(rec["sku"], json!("B200"));
+        assert_eq!(rec["qty"], json!(12));
+        assert_eq!(rec["desc"], json!("Gadget deluxe"));
+    }
+
+    #[tokio::test]
+    async fn test_fixed_width_parse_short_line_is_row_error() {
+        let content = "A100     3Widget\nB200\n";
+        let stage = FixedWidthParseStage::from_params(&params(json!({}))).unwrap();
+        let result = stage
+            .process(make_test_item(content), &make_ctx())
+            .await
+            .unwrap();
+        assert_eq!(result.len(), 1);
+
+        let stage =
+            FixedWidthParseStage::from_params(&params(json!({"on_row_error": "fail"}))).unwrap();
+        let err = stage
+            .process(make_test_item(content), &make_ctx())
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("row 2"));
+    }
+
+    #[tokio::test]
+    async fn test_fixed_width_parse_without_trim() {
+        let stage = FixedWidthParseStage::from_params(&params(json!({"trim": false}))).unwrap();
+        let result = stage
+            .process(make_test_item("A1       7x\n"), &make_ctx())
+            .await
+            .unwrap();
+        assert_eq!(result[0].record.as_ref().unwrap()["sku"], json!("A1    "));
+    }
+
+    #[tokio::test]
+    async fn test_fixed_width_parse_huge_width_takes_rest_of_line() {
+        let stage = FixedWidthParseStage::from_params(&json!({
+            "columns": [{"name": "rest", "start": 2, "width": usize::MAX}]
+        }))
+        .unwrap();
+        let result = stage
+            .process(make_test_item("A1 tail\n"), &make_ctx())
+            .await
+            .unwrap();
+        assert_eq!(result[0].record.as_ref().unwrap()["rest"], json!("tail"));
+    }
+}
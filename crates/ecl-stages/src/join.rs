@@ -9,6 +9,7 @@ use async_trait::async_trait;
 use serde::Deserialize;
 use tracing::debug;
 
+use ecl_pipeline_spec::{SchemaError, StreamSchemas};
 use ecl_pipeline_topo::error::StageError;
 use ecl_pipeline_topo::{PipelineItem, Stage, StageContext};
 
@@ -63,6 +64,14 @@ impl JoinStage {
             })?;
         Ok(Self { config })
     }
+
+    /// Prefix for right-side fields (default: `"{right_stream}_"`).
+    fn right_prefix(&self) -> String {
+        self.config
+            .right_prefix
+            .clone()
+            .unwrap_or_else(|| format!("{}_", self.config.right_stream))
+    }
 }
 
 #[async_trait]
@@ -133,12 +142,7 @@ impl Stage for JoinStage {
             }
         }
 
-        let right_prefix = self
-            .config
-            .right_prefix
-            .as_deref()
-            .map(|s| s.to_string())
-            .unwrap_or_else(|| format!("{}_", right_stream));
+        let right_prefix = self.right_prefix();
 
         // For each left item, lookup and merge.
         let mut results = Vec::new();
@@ -215,6 +219,40 @@ impl Stage for JoinStage {
         debug!(output_items = results.len(), "join stage complete");
         Ok(results)
     }
+
+    fn transform_schema(
+        &self,
+        inputs: &StreamSchemas,
+    ) -> Result<Option<StreamSchemas>, SchemaError> {
+        let left_stream = &self.config.left_stream;
+        let right_stream = &self.config.right_stream;
+        let (Some(left), Some(right)) = (inputs.get(left_stream), inputs.get(right_stream)) else {
+            return Ok(None);
+        };
+        left.require(left_stream, &self.config.left_key)?;
+        right.require(right_stream, &self.config.right_key)?;
+
+        // Unmatched left records keep their own fields only, so the
+        // right-side fields are nullable unless this is an inner join.
+        let right_prefix = self.right_prefix();
+        let mut joined = left.clone();
+        for field in &right.fields {
+            if field.name != self.config.right_key {
+                let mut field = field.clone();
+                field.name = format!("{right_prefix}{}", field.name);
+                field.nullable |= self.config.join_type != "inner";
+                joined.set_field(field);
+            }
+        }
+
+        let mut outputs = inputs.clone();
+        outputs.insert(left_stream.clone(), joined);
+        // Only a full join passes unmatched right records through.
+        if self.config.join_type != "full" {
+            outputs.remove(right_stream);
+        }
+        Ok(Some(outputs))
+    }
 }
 
 #[cfg(test)]
@@ -269,6 +307,7 @@ mod tests {
                 secrets: Default::default(),
                 triggers: None,
                 schedule: None,
+                schemas: Default::default(),
             }),
             output_dir: PathBuf::from("./out"),
             params: serde_json::Value::Null,
@@ -634,4 +673,48 @@ mod tests {
         let record2 = item2.record.as_ref().unwrap();
         assert_eq!(record2["products_brand"], json!("Pepsi"));
     }
+
+    #[test]
+    fn test_join_transform_schema() {
+        use ecl_pipeline_spec::{FieldSchema, FieldType, StreamSchema};
+
+        let stage = JoinStage::from_params(&json!({
+            "left_stream": "txn",
+            "right_stream": "stores",
+            "left_key": "store_id",
+            "right_key": "id",
+        }))
+        .unwrap();
+        let inputs = StreamSchemas::from([
+            (
+                "txn".to_string(),
+                StreamSchema::new(vec![FieldSchema::new("store_id", FieldType::String)]),
+            ),
+            (
+                "stores".to_string(),
+                StreamSchema::new(vec![
+                    FieldSchema::new("id", FieldType::String),
+                    FieldSchema::new("city", FieldType::String),
+                ]),
+            ),
+        ]);
+        let outputs = stage.transform_schema(&inputs).unwrap().unwrap();
+        assert!(!outputs.contains_key("stores"));
+        let joined = &outputs["txn"];
+        assert!(joined.field("stores_city").unwrap().nullable);
+        assert!(!joined.contains("stores_id"));
+
+        let typo = JoinStage::from_params(&json!({
+            "left_stream": "txn",
+            "right_stream": "stores",
+            "left_key": "stor_id",
+            "right_key": "id",
+        }))
+        .unwrap();
+        let err = typo.transform_schema(&inputs).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "stream 'txn' has no field 'stor_id' (did you mean 'store_id'?)"
+        );
+    }
 }
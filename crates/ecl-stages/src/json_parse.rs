@@ -0,0 +1,309 @@
+//! JSON parsing stage: fan-out from file → individual records.
+//!
+//! Reads a JSON document (or newline-delimited JSON) from a `PipelineItem`,
+//! selects the row objects with a JSONPath expression, and emits one
+//! `PipelineItem` per row. Columns use the same typing as `csv_parse` and
+//! may pull values from nested objects with a dotted `path`.
+
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json_path::JsonPath;
+
+use ecl_pipeline_spec::{SchemaError, StreamSchemas};
+use ecl_pipeline_topo::error::StageError;
+use ecl_pipeline_topo::{PipelineItem, Record, Stage, StageContext};
+
+use crate::columns::{
+    ColumnDef, columns_schema, convert_json_value, default_on_error, row_error, row_item,
+};
+
+/// Parsed from stage params TOML/JSON.
+#[derive(Debug, Clone, Deserialize)]
+struct JsonParseConfig {
+    /// Column definitions in order.
+    columns: Vec<JsonColumnDef>,
+    /// Input format: "json" (one document) or "ndjson" (one row per line).
+    /// Default: "json"
+    #[serde(default = "default_format")]
+    format: String,
+    /// JSONPath selecting the row objects within a "json" document.
+    /// Default: `$[*]` (the elements of a top-level array)
+    #[serde(default)]
+    records: Option<String>,
+    /// How to handle parse errors for individual rows.
+    /// "skip" = skip bad rows (log warning), "fail" = fail the item.
+    /// Default: "skip"
+    #[serde(default = "default_on_error")]
+    on_row_error: String,
+}
+
+/// A column definition with an optional location inside the row object.
+#[derive(Debug, Clone, Deserialize)]
+struct JsonColumnDef {
+    #[serde(flatten)]
+    column: ColumnDef,
+    /// Dotted key path within the row object (e.g. `customer.id`).
+    /// Default: the column name
+    #[serde(default)]
+    path: Option<String>,
+}
+
+fn default_format() -> String {
+    "json".to_string()
+}
+
+/// JSON parsing stage: one file in, N record items out.
+#[derive(Debug)]
+pub struct JsonParseStage {
+    config: JsonParseConfig,
+    records: JsonPath,
+}
+
+impl JsonParseStage {
+    /// Create from stage params JSON.
+    ///
+    /// # Errors
+    ///
+    /// Returns `StageError::Permanent` if the config is invalid or the
+    /// `records` JSONPath does not parse.
+    pub fn from_params(params: &serde_json::Value) -> Result<Self, StageError> {
+        let invalid = |message: String| StageError::Permanent {
+            stage: "json_parse".to_string(),
+            item_id: String::new(),
+            message: format!("invalid json_parse config: {message}"),
+        };
+        let config: JsonParseConfig =
+            serde_json::from_value(params.clone()).map_err(|e| invalid(e.to_string()))?;
+        match config.format.as_str() {
+            "json" => {}
+            "ndjson" if config.records.is_some() => {
+                return Err(invalid(
+                    "'records' is only supported with format \"json\"".to_string(),
+                ));
+            }
+            "ndjson" => {}
+            other => return Err(invalid(format!("unknown format '{other}'"))),
+        }
+        let records = JsonPath::parse(config.records.as_deref().unwrap_or("$[*]"))
+            .map_err(|e| invalid(format!("bad records path: {e}")))?;
+        Ok(Self { config, records })
+    }
+
+    /// Build the record for one row object, or describe why it is bad.
+    fn parse_row(&self, row: &serde_json::Value) -> Result<Record, String> {
+        let serde_json::Value::Object(_) = row else {
+            return Err(format!("expected an object, found {row}"));
+        };
+        let mut record = Record::new();
+        for col in &self.config.columns {
+            let path = col.path.as_deref().unwrap_or(&col.column.name);
+            let value = path
+                .split('.')
+                .try_fold(row, |value, key| value.get(key))
+                .unwrap_or(&serde_json::Value::Null);
+            record.insert(
+                col.column.name.clone(),
+                convert_json_value(value, &col.column.r#type),
+            );
+        }
+        Ok(record)
+    }
+
+    /// Emit a row item, or apply `on_row_error` if the row is bad.
+    fn push_row(
+        &self,
+        item: &PipelineItem,
+        row: usize,
+        value: &serde_json::Value,
+        output: &mut Vec<PipelineItem>,
+    ) -> Result<(), StageError> {
+        match self.parse_row(value) {
+            Ok(record) => {
+                let raw = serde_json::to_vec(value).unwrap_or_default();
+                output.push(row_item(item, row, record, raw, "application/json"));
+                Ok(())
+            }
+            Err(e) => row_error("json_parse", &self.config.on_row_error, item, row, e),
+        }
+    }
+}
+
+#[async_trait]
+impl Stage for JsonParseStage {
+    fn name(&self) -> &str {
+        "json_parse"
+    }
+
+    async fn process(
+        &self,
+        item: PipelineItem,
+        _ctx: &StageContext,
+    ) -> Result<Vec<PipelineItem>, StageError> {
+        let mut output = Vec::new();
+
+        if self.config.format == "ndjson" {
+            let text = String::from_utf8_lossy(&item.content);
+            for (idx, line) in text.lines().enumerate() {
+                let line_number = idx + 1;
+                if line.trim().is_empty() {
+                    continue;
+                }
+                match serde_json::from_str::<serde_json::Value>(line) {
+                    Ok(value) => self.push_row(&item, line_number, &value, &mut output)?,
+                    Err(e) => {
+                        row_error(
+                            "json_parse",
+                            &self.config.on_row_error,
+                            &item,
+                            line_number,
+                            e,
+                        )?;
+                    }
+                }
+            }
+            return Ok(output);
+        }
+
+        let document: serde_json::Value =
+            serde_json::from_slice(&item.content).map_err(|e| StageError::Permanent {
+                stage: "json_parse".to_string(),
+                item_id: item.id.clone(),
+                message: format!("invalid JSON document: {e}"),
+            })?;
+        for (idx, value) in self.records.query(&document).into_iter().enumerate() {
+            self.push_row(&item, idx + 1, value, &mut output)?;
+        }
+
+        Ok(output)
+    }
+
+    fn transform_schema(
+        &self,
+        inputs: &StreamSchemas,
+    ) -> Result<Option<StreamSchemas>, SchemaError> {
+        let columns = self.config.columns.iter().map(|c| &c.column);
+        Ok(Some(columns_schema(columns, inputs)))
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use ecl_pipeline_state::{Blake3Hash, ItemProvenance};
+    use serde_json::json;
+    use std::collections::BTreeMap;
+    use std::sync::Arc;
+
+    fn make_test_item(content: &str) -> PipelineItem {
+        PipelineItem {
+            id: "file-1".to_string(),
+            display_name: "test.json".to_string(),
+            content: Arc::from(content.as_bytes()),
+            mime_type: "application/json".to_string(),
+            source_name: "test".to_string(),
+            source_content_hash: Blake3Hash::new("abc"),
+            provenance: ItemProvenance {
+                source_kind: "filesystem".to_string(),
+                metadata: BTreeMap::new(),
+                source_modified: None,
+                extracted_at: chrono::Utc::now(),
+            },
+            metadata: BTreeMap::new(),
+            record: None,
+            stream: None,
+        }
+    }
+
+    fn make_ctx() -> StageContext {
+        StageContext {
+            spec: Arc::new(
+                ecl_pipeline_spec::PipelineSpec::from_toml(
+                    "name = \"t\"\nversion = 1\noutput_dir = \"./o\"\n[sources.s]\nkind = \"filesystem\"\nroot = \"/tmp\"\n[stages.x]\nadapter = \"x\"\nresources = { creates = [\"y\"] }",
+                ).unwrap(),
+            ),
+            output_dir: std::path::PathBuf::from("/tmp"),
+            params: serde_json::Value::Null,
+            span: tracing::Span::none(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_json_parse_records_path_and_nested_columns() {
+        let doc = r#"{"data": {"orders": [
+            {"id": "A1", "qty": "3", "customer": {"name": "Ann"}},
+            {"id": "B2", "qty": 5, "customer": {"name": "Bob"}}
+        ]}}"#;
+        let stage = JsonParseStage::from_params(&json!({
+            "records": "$.data.orders[*]",
+            "columns": [
+                {"name": "id"},
+                {"name": "qty", "type": "integer"},
+                {"name": "customer", "path": "customer.name"},
+                {"name": "missing"},
+            ]
+        }))
+        .unwrap();
+
+        let result = stage
+            .process(make_test_item(doc), &make_ctx())
+            .await
+            .unwrap();
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0].id, "file-1:row:1");
+        let rec = result[1].record.as_ref().unwrap();
+        assert_eq!(rec["id"], json!("B2"));
+        assert_eq!(rec["qty"], json!(5));
+        assert_eq!(rec["customer"], json!("Bob"));
+        assert_eq!(rec["missing"], json!(null));
+        assert_eq!(result[0].record.as_ref().unwrap()["qty"], json!(3));
+    }
+
+    #[tokio::test]
+    async fn test_json_parse_ndjson_skips_bad_lines() {
+        let content = "{\"id\": 1}\nnot json\n\n[1, 2]\n{\"id\": 4}\n";
+        let stage = JsonParseStage::from_params(&json!({
+            "format": "ndjson",
+            "columns": [{"name": "id", "type": "integer"}]
+        }))
+        .unwrap();
+
+        let result = stage
+            .process(make_test_item(content), &make_ctx())
+            .await
+            .unwrap();
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[1].id, "file-1:row:5");
+        assert_eq!(result[1].metadata["_line_number"], json!(5));
+        assert_eq!(result[1].record.as_ref().unwrap()["id"], json!(4));
+    }
+
+    #[tokio::test]
+    async fn test_json_parse_fail_on_row_error() {
+        let stage = JsonParseStage::from_params(&json!({
+            "columns": [{"name": "id"}],
+            "on_row_error": "fail"
+        }))
+        .unwrap();
+
+        let result = stage
+            .process(make_test_item("[{\"id\": \"a\"}, 7]"), &make_ctx())
+            .await;
+        let err = result.unwrap_err().to_string();
+        assert!(err.contains("row 2"), "unexpected error: {err}");
+    }
+
+    #[test]
+    fn test_json_parse_rejects_bad_config() {
+        assert!(JsonParseStage::from_params(&json!({"columns": [], "records": "$[["})).is_err());
+        assert!(
+            JsonParseStage::from_params(&json!({
+                "columns": [],
+                "format": "ndjson",
+                "records": "$[*]"
+            }))
+            .is_err()
+        );
+        assert!(JsonParseStage::from_params(&json!({"columns": [], "format": "yaml"})).is_err());
+    }
+}
@@ -3,6 +3,10 @@
 //! Provides stages for extraction, transformation, and output:
 //! - [`ExtractStage`] — delegates to a `SourceAdapter` to fetch content
 //! - [`CsvParseStage`] — parses CSV content into structured records (fan-out)
+//! - [`XlsxParseStage`] — parses an XLSX worksheet range into records (fan-out)
+//! - [`JsonParseStage`] — parses JSON/NDJSON rows selected by JSONPath (fan-out)
+//! - [`XmlParseStage`] — parses XML rows selected by an XPath subset (fan-out)
+//! - [`FixedWidthParseStage`] — parses fixed-width lines by column offsets (fan-out)
 //! - [`NormalizeStage`] — passthrough (placeholder for future format conversion)
 //! - [`FilterStage`] — glob-based include/exclude filtering
 //! - [`FieldMapStage`] — field renaming, date parsing, padding, regex extraction
@@ -12,8 +16,10 @@
 //! - [`LookupStage`] — static value mapping through lookup tables
 //! - [`DateParseStage`] — date string parsing to RFC3339 format
 //! - [`TimezoneStage`] — local datetime to UTC conversion via ZIP code lookup
+//! - [`DecryptStage`] — OpenPGP decryption with optional signature verification
 //! - [`DecompressStage`] — ZIP/GZIP archive extraction (fan-out)
 //! - [`AssembleStage`] — batch merging of multiple streams into nested structures
+//! - [`SqlStage`] — batch SQL query over streams registered as tables
 //! - [`EmitStage`] — writes pipeline items to the output directory
 
 #![forbid(unsafe_code)]
@@ -24,30 +30,43 @@
 
 pub mod aggregate;
 pub mod assemble;
+mod columns;
 pub mod csv_parse;
 pub mod date_parse;
 pub mod decompress;
+pub mod decrypt;
 pub mod emit;
 pub mod extract;
 pub mod field_map;
 pub mod filter;
+pub mod fixed_width_parse;
 pub mod join;
+pub mod json_parse;
 pub mod lookup;
 pub mod normalize;
+pub mod sql;
 pub mod timezone;
 pub mod validate;
+pub mod xlsx_parse;
+pub mod xml_parse;
 
 pub use aggregate::AggregateStage;
 pub use assemble::AssembleStage;
 pub use csv_parse::CsvParseStage;
 pub use date_parse::DateParseStage;
 pub use decompress::DecompressStage;
+pub use decrypt::DecryptStage;
 pub use emit::EmitStage;
 pub use extract::ExtractStage;
 pub use field_map::FieldMapStage;
 pub use filter::FilterStage;
+pub use fixed_width_parse::FixedWidthParseStage;
 pub use join::JoinStage;
+pub use json_parse::JsonParseStage;
 pub use lookup::LookupStage;
 pub use normalize::NormalizeStage;
+pub use sql::SqlStage;
 pub use timezone::TimezoneStage;
 pub use validate::ValidateStage;
+pub use xlsx_parse::XlsxParseStage;
+pub use xml_parse::XmlParseStage;
@@ -11,7 +11,9 @@ use serde::Deserialize;
 use serde_json::Value;
 use tracing::debug;
 
+use ecl_pipeline_spec::{FieldSchema, FieldType, SchemaError, StreamSchemas};
 use ecl_pipeline_topo::error::StageError;
+use ecl_pipeline_topo::schema::map_streams;
 use ecl_pipeline_topo::{PipelineItem, Stage, StageContext};
 
 type Record = serde_json::Map<String, serde_json::Value>;
@@ -138,6 +140,22 @@ impl Stage for LookupStage {
 
         Ok(vec![item])
     }
+
+    fn transform_schema(
+        &self,
+        inputs: &StreamSchemas,
+    ) -> Result<Option<StreamSchemas>, SchemaError> {
+        map_streams(inputs, |stream, schema| {
+            let mut schema = schema.clone();
+            for op in &self.config.lookups {
+                schema.require(stream, &op.field)?;
+                let mut output = FieldSchema::new(&op.output, FieldType::String);
+                output.nullable = op.default.is_none();
+                schema.set_field(output);
+            }
+            Ok(schema)
+        })
+    }
 }
 
 #[cfg(test)]
@@ -183,6 +201,7 @@ mod tests {
                 secrets: Default::default(),
                 triggers: None,
                 schedule: None,
+                schemas: Default::default(),
             }),
             output_dir: std::path::PathBuf::from("./out"),
             params: serde_json::Value::Null,
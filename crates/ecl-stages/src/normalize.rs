@@ -6,6 +6,7 @@
 use async_trait::async_trait;
 use tracing::debug;
 
+use ecl_pipeline_spec::{SchemaError, StreamSchemas};
 use ecl_pipeline_topo::error::StageError;
 use ecl_pipeline_topo::{PipelineItem, Stage, StageContext};
 
@@ -44,6 +45,14 @@ impl Stage for NormalizeStage {
         debug!(item_id = %item.id, "normalize: passthrough");
         Ok(vec![item])
     }
+
+    fn transform_schema(
+        &self,
+        inputs: &StreamSchemas,
+    ) -> Result<Option<StreamSchemas>, SchemaError> {
+        // Items pass through with their records untouched.
+        Ok(Some(inputs.clone()))
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,631 @@
+//! SQL stage: runs a SQL query over pipeline records.
+//!
+//! Each input stream is registered as a table in an in-memory SQLite
+//! database, one row per item record. The query's result rows become new
+//! items. This is a batch stage that requires all items at once.
+//!
+//! Like SQLite column names, record keys are matched ignoring ASCII case:
+//! `Name` and `name` fill the same column, named by the first spelling seen.
+//!
+//! # Provenance
+//!
+//! Every table has a hidden `_item_id` column holding the source item's ID.
+//! When a result row selects `_item_id` (e.g. `SELECT o._item_id, ...`),
+//! the output item inherits that item's source, provenance and metadata.
+//! Other rows get synthesized provenance with `source_kind = "sql"`.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use rusqlite::types::{Value as SqlValue, ValueRef};
+use rusqlite::{Connection, params_from_iter};
+use serde::Deserialize;
+use serde_json::Value;
+use tracing::debug;
+
+use ecl_pipeline_state::{Blake3Hash, ItemProvenance};
+use ecl_pipeline_topo::error::StageError;
+use ecl_pipeline_topo::{PipelineItem, Stage, StageContext};
+
+type Record = serde_json::Map<String, serde_json::Value>;
+
+/// Hidden column linking each table row to its source item.
+pub const ITEM_ID_COLUMN: &str = "_item_id";
+
+/// Table name for items that belong to no stream.
+const DEFAULT_TABLE: &str = "input";
+
+/// Configuration for the SQL stage, parsed from stage params.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SqlConfig {
+    /// The query to run. Must be a single read-only statement.
+    pub query: String,
+    /// Table name → stream name. When empty, every stream present in the
+    /// batch becomes a table of the same name, and items with no stream
+    /// become the `input` table.
+    #[serde(default)]
+    pub tables: BTreeMap<String, String>,
+    /// Result columns holding JSON text (e.g. from `json_group_array`)
+    /// to parse back into nested values.
+    #[serde(default)]
+    pub json_columns: Vec<String>,
+}
+
+/// SQL stage: registers streams as tables and emits query result rows.
+///
+/// This is a batch stage (`requires_batch() -> true`). Items in streams
+/// not registered as tables are passed through unchanged.
+#[derive(Debug)]
+pub struct SqlStage {
+    config: SqlConfig,
+}
+
+impl SqlStage {
+    /// Create a SqlStage from stage params JSON.
+    ///
+    /// # Errors
+    ///
+    /// Returns `StageError::Permanent` if params cannot be deserialized
+    /// or the query is empty.
+    pub fn from_params(params: &serde_json::Value) -> Result<Self, StageError> {
+        let config: SqlConfig =
+            serde_json::from_value(params.clone()).map_err(|e| StageError::Permanent {
+                stage: "sql".to_string(),
+                item_id: String::new(),
+                message: format!("invalid sql config: {e}"),
+            })?;
+        if config.query.trim().is_empty() {
+            return Err(permanent("", "sql query is empty"));
+        }
+        Ok(Self { config })
+    }
+
+    /// Resolve the table an item belongs to, if any.
+    fn table_for(&self, item: &PipelineItem) -> Option<String> {
+        if self.config.tables.is_empty() {
+            return Some(
+                item.stream
+                    .clone()
+                    .unwrap_or_else(|| DEFAULT_TABLE.to_string()),
+            );
+        }
+        let stream = item.stream.as_deref()?;
+        self.config
+            .tables
+            .iter()
+            .find(|(_, s)| s.as_str() == stream)
+            .map(|(table, _)| table.clone())
+    }
+
+    /// Create and populate one table per stream.
+    fn load_tables(
+        &self,
+        conn: &Connection,
+        tables: &BTreeMap<String, Vec<&PipelineItem>>,
+    ) -> Result<(), StageError> {
+        for (table, items) in tables {
+            // Union of record fields, in first-seen order. SQLite column
+            // names ignore ASCII case, so keys differing only in case share
+            // the first-seen spelling's column.
+            let mut columns = vec![ITEM_ID_COLUMN.to_string()];
+            for item in items {
+                let Some(record) = &item.record else {
+                    return Err(permanent(&item.id, "item has no record"));
+                };
+                for key in record.keys() {
+                    if !columns.iter().any(|c| c.eq_ignore_ascii_case(key)) {
+                        columns.push(key.clone());
+                    }
+                }
+            }
+
+            let column_list = columns
+                .iter()
+                .map(|c| quote_ident(c))
+                .collect::<Vec<_>>()
+                .join(", ");
+            conn.execute_batch(&format!(
+                "CREATE TABLE {} ({column_list});",
+                quote_ident(table)
+            ))
+            .map_err(|e| permanent("", &format!("failed to create table '{table}': {e}")))?;
+
+            let placeholders = vec!["?"; columns.len()].join(", ");
+            let mut insert = conn
+                .prepare(&format!(
+                    "INSERT INTO {} ({column_list}) VALUES ({placeholders})",
+                    quote_ident(table)
+                ))
+                .map_err(|e| permanent("", &e.to_string()))?;
+
+            for item in items {
+                let record = item.record.as_ref();
+                let values =
+                    columns
+                        .iter()
+                        .map(|column| match record.and_then(|r| field(r, column)) {
+                            Some(value) => to_sql(value),
+                            None if column == ITEM_ID_COLUMN => SqlValue::Text(item.id.clone()),
+                            None => SqlValue::Null,
+                        });
+                insert
+                    .execute(params_from_iter(values))
+                    .map_err(|e| permanent(&item.id, &e.to_string()))?;
+            }
+
+            debug!(
+                table,
+                rows = items.len(),
+                columns = columns.len(),
+                "registered table"
+            );
+        }
+        Ok(())
+    }
+
+    /// Run the query and convert result rows to records.
+    fn run_query(&self, conn: &Connection) -> Result<Vec<Record>, StageError> {
+        let mut stmt = conn
+            .prepare(&self.config.query)
+            .map_err(|e| permanent("", &format!("invalid sql query: {e}")))?;
+        if !stmt.readonly() {
+            return Err(permanent("", "sql query must be read-only"));
+        }
+
+        let columns: Vec<String> = stmt.column_names().into_iter().map(String::from).collect();
+        let mut rows = stmt
+            .query([])
+            .map_err(|e| permanent("", &format!("sql query failed: {e}")))?;
+
+        let mut records = Vec::new();
+        while let Some(row) = rows
+            .next()
+            .map_err(|e| permanent("", &format!("sql query failed: {e}")))?
+        {
+            let mut record = Record::new();
+            for (i, column) in columns.iter().enumerate() {
+                let value = row.get_ref(i).map_err(|e| permanent("", &e.to_string()))?;
+                let value = from_sql(value);
+                let value = match value {
+                    Value::String(text) if self.config.json_columns.contains(column) => {
+                        serde_json::from_str(&text).unwrap_or(Value::String(text))
+                    }
+                    other => other,
+                };
+                record.insert(column.clone(), value);
+            }
+            records.push(record);
+        }
+        Ok(records)
+    }
+
+    /// Build an output item for a result row, inheriting provenance from
+    /// the row's `_item_id` when it names an input item.
+    fn make_item(
+        &self,
+        index: usize,
+        mut record: Record,
+        inputs: &HashMap<&str, &PipelineItem>,
+        used: &mut HashMap<String, usize>,
+        source_name: &str,
+    ) -> PipelineItem {
+        let base = match record.remove(ITEM_ID_COLUMN) {
+            Some(Value::String(id)) => inputs.get(id.as_str()).copied(),
+            _ => None,
+        };
+
+        match base {
+            Some(base) => {
+                let count = used.entry(base.id.clone()).or_insert(0);
+                let id = if *count == 0 {
+                    base.id.clone()
+                } else {
+                    format!("{}:sql:{index}", base.id)
+                };
+                *count += 1;
+                PipelineItem {
+                    id,
+                    record: Some(record),
+                    ..base.clone()
+                }
+            }
+            None => {
+                let hash = blake3::hash(Value::Object(record.clone()).to_string().as_bytes());
+                let mut metadata = BTreeMap::new();
+                metadata.insert(
+                    "query".to_string(),
+                    Value::String(self.config.query.clone()),
+                );
+                let id = format!("sql:{index}");
+                PipelineItem {
+                    display_name: id.clone(),
+                    id,
+                    content: Arc::from(b"" as &[u8]),
+                    mime_type: "application/x-sql-row".to_string(),
+                    source_name: source_name.to_string(),
+                    source_content_hash: Blake3Hash::new(hash.to_hex().as_str()),
+                    provenance: ItemProvenance {
+                        source_kind: "sql".to_string(),
+                        metadata,
+                        source_modified: None,
+                        extracted_at: chrono::Utc::now(),
+                    },
+                    metadata: BTreeMap::new(),
+                    record: Some(record),
+                    stream: None,
+                }
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl Stage for SqlStage {
+    fn name(&self) -> &str {
+        "sql"
+    }
+
+    fn requires_batch(&self) -> bool {
+        true
+    }
+
+    async fn process(
+        &self,
+        _item: PipelineItem,
+        _ctx: &StageContext,
+    ) -> Result<Vec<PipelineItem>, StageError> {
+        Err(StageError::Permanent {
+            stage: "sql".to_string(),
+            item_id: String::new(),
+            message: "sql stage requires batch mode; use process_batch()".to_string(),
+        })
+    }
+
+    async fn process_batch(
+        &self,
+        items: Vec<PipelineItem>,
+        _ctx: &StageContext,
+    ) -> Result<Vec<PipelineItem>, StageError> {
+        debug!(items = items.len(), "sql stage starting");
+
+        // Partition items into tables and passthrough.
+        let mut tables: BTreeMap<String, Vec<&PipelineItem>> = BTreeMap::new();
+        let mut passthrough = Vec::new();
+        for item in &items {
+            match self.table_for(item) {
+                Some(table) => tables.entry(table).or_default().push(item),
+                None => passthrough.push(item.clone()),
+            }
+        }
+        // Declared tables exist even when their stream is empty.
+        for table in self.config.tables.keys() {
+            tables.entry(table.clone()).or_default();
+        }
+
+        let conn = Connection::open_in_memory()
+            .map_err(|e| permanent("", &format!("failed to open sql engine: {e}")))?;
+        conn.set_limit(rusqlite::limits::Limit::SQLITE_LIMIT_ATTACHED, 0)
+            .map_err(|e| permanent("", &e.to_string()))?;
+
+        self.load_tables(&conn, &tables)?;
+        let records = self.run_query(&conn)?;
+
+        let inputs: HashMap<&str, &PipelineItem> = tables
+            .values()
+            .flatten()
+            .map(|item| (item.id.as_str(), *item))
+            .collect();
+        let mut sources = inputs.values().map(|item| item.source_name.as_str());
+        let first_source = sources.next().unwrap_or("sql");
+        let source_name = if sources.all(|s| s == first_source) {
+            first_source
+        } else {
+            "sql"
+        };
+
+        let mut used = HashMap::new();
+        let mut results: Vec<PipelineItem> = records
+            .into_iter()
+            .enumerate()
+            .map(|(i, record)| self.make_item(i, record, &inputs, &mut used, source_name))
+            .collect();
+        results.extend(passthrough);
+
+        debug!(
+            tables = tables.len(),
+            output_items = results.len(),
+            "sql stage complete"
+        );
+        Ok(results)
+    }
+}
+
+/// A record's value for `column`: the exact key if present, else a key
+/// differing only in ASCII case.
+fn field<'a>(record: &'a Record, column: &str) -> Option<&'a Value> {
+    record.get(column).or_else(|| {
+        record
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(column))
+            .map(|(_, value)| value)
+    })
+}
+
+fn permanent(item_id: &str, message: &str) -> StageError {
+    StageError::Permanent {
+        stage: "sql".to_string(),
+        item_id: item_id.to_string(),
+        message: message.to_string(),
+    }
+}
+
+fn quote_ident(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+fn to_sql(value: &Value) -> SqlValue {
+    match value {
+        Value::Null => SqlValue::Null,
+        Value::Bool(b) => SqlValue::Integer(i64::from(*b)),
+        Value::Number(n) => match n.as_i64() {
+            Some(i) => SqlValue::Integer(i),
+            None => n.as_f64().map_or(SqlValue::Null, SqlValue::Real),
+        },
+        Value::String(s) => SqlValue::Text(s.clone()),
+        Value::Array(_) | Value::Object(_) => SqlValue::Text(value.to_string()),
+    }
+}
+
+fn from_sql(value: ValueRef<'_>) -> Value {
+    match value {
+        ValueRef::Null => Value::Null,
+        ValueRef::Integer(i) => Value::from(i),
+        ValueRef::Real(f) => serde_json::Number::from_f64(f).map_or(Value::Null, Value::Number),
+        ValueRef::Text(bytes) | ValueRef::Blob(bytes) => {
+            Value::String(String::from_utf8_lossy(bytes).into_owned())
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use ecl_pipeline_spec::PipelineSpec;
+    use serde_json::json;
+    use std::path::PathBuf;
+
+    fn make_item(id: &str, stream: &str, record: Value) -> PipelineItem {
+        PipelineItem {
+            id: id.to_string(),
+            display_name: id.to_string(),
+            content: Arc::from(b"" as &[u8]),
+            mime_type: "application/x-csv-row".to_string(),
+            source_name: "test".to_string(),
+            source_content_hash: Blake3Hash::new("test"),
+            provenance: ItemProvenance {
+                source_kind: "test".to_string(),
+                metadata: BTreeMap::new(),
+                source_modified: None,
+                extracted_at: chrono::Utc::now(),
+            },
+            metadata: BTreeMap::new(),
+            record: record.as_object().cloned(),
+            stream: Some(stream.to_string()),
+        }
+    }
+
+    fn make_context() -> StageContext {
+        StageContext {
+            spec: Arc::new(PipelineSpec {
+                name: "test".to_string(),
+                version: 1,
+                output_dir: PathBuf::from("./out"),
+                sources: BTreeMap::new(),
+                stages: BTreeMap::new(),
+                defaults: ecl_pipeline_spec::DefaultsSpec::default(),
+                lifecycle: None,
+                secrets: Default::default(),
+                triggers: None,
+                schedule: None,
+                schemas: Default::default(),
+            }),
+            output_dir: PathBuf::from("./out"),
+            params: serde_json::Value::Null,
+            span: tracing::Span::none(),
+        }
+    }
+
+    fn make_items() -> Vec<PipelineItem> {
+        vec![
+            make_item("o1", "orders", json!({"order": "1", "upc": "A", "qty": 2})),
+            make_item("o2", "orders", json!({"order": "2", "upc": "B", "qty": 5})),
+            make_item("o3", "orders", json!({"order": "3", "upc": "A", "qty": 1})),
+            make_item(
+                "p1",
+                "products",
+                json!({"upc": "A", "brand": "Acme", "price": 1.5}),
+            ),
+            make_item(
+                "p2",
+                "products",
+                json!({"upc": "B", "brand": "Beta", "price": 2.0}),
+            ),
+        ]
+    }
+
+    fn stage(params: Value) -> SqlStage {
+        SqlStage::from_params(&params).unwrap()
+    }
+
+    #[test]
+    fn test_sql_requires_batch_true() {
+        assert!(stage(json!({"query": "SELECT 1"})).requires_batch());
+    }
+
+    #[test]
+    fn test_sql_from_params_rejects_empty_query() {
+        assert!(SqlStage::from_params(&json!({"query": "  "})).is_err());
+        assert!(SqlStage::from_params(&json!({})).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_sql_process_returns_error() {
+        let stage = stage(json!({"query": "SELECT 1"}));
+        let item = make_item("x", "orders", json!({}));
+        assert!(stage.process(item, &make_context()).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_sql_multi_key_join_and_aggregate() {
+        let stage = stage(json!({
+            "query": "SELECT p.brand, SUM(o.qty) AS units, SUM(o.qty * p.price) AS revenue \
+                      FROM orders o JOIN products p ON o.upc = p.upc \
+                      GROUP BY p.brand ORDER BY p.brand"
+        }));
+        let result = stage
+            .process_batch(make_items(), &make_context())
+            .await
+            .unwrap();
+
+        assert_eq!(result.len(), 2);
+        let acme = result[0].record.as_ref().unwrap();
+        assert_eq!(acme["brand"], json!("Acme"));
+        assert_eq!(acme["units"], json!(3));
+        assert_eq!(acme["revenue"], json!(4.5));
+        assert_eq!(result[0].provenance.source_kind, "sql");
+        assert_eq!(result[0].source_name, "test");
+        assert_eq!(result[0].id, "sql:0");
+    }
+
+    #[tokio::test]
+    async fn test_sql_preserves_provenance_via_item_id() {
+        let stage = stage(json!({
+            "query": "SELECT o._item_id, o.\"order\", p.brand \
+                      FROM orders o JOIN products p ON o.upc = p.upc ORDER BY o.\"order\""
+        }));
+        let result = stage
+            .process_batch(make_items(), &make_context())
+            .await
+            .unwrap();
+
+        let ids: Vec<&str> = result.iter().map(|i| i.id.as_str()).collect();
+        assert_eq!(ids, vec!["o1", "o2", "o3"]);
+        assert_eq!(result[0].provenance.source_kind, "test");
+        let record = result[0].record.as_ref().unwrap();
+        assert!(!record.contains_key(ITEM_ID_COLUMN));
+        assert_eq!(record["brand"], json!("Acme"));
+    }
+
+    #[tokio::test]
+    async fn test_sql_repeated_item_id_gets_unique_ids() {
+        let stage = stage(json!({
+            "query": "SELECT _item_id, 'a' AS tag FROM orders WHERE _item_id = 'o1' \
+                      UNION ALL SELECT _item_id, 'b' FROM orders WHERE _item_id = 'o1'"
+        }));
+        let result = stage
+            .process_batch(make_items(), &make_context())
+            .await
+            .unwrap();
+        assert_eq!(result[0].id, "o1");
+        assert_eq!(result[1].id, "o1:sql:1");
+    }
+
+    #[tokio::test]
+    async fn test_sql_json_columns_and_nested_values() {
+        let items = vec![
+            make_item("a", "events", json!({"k": "x", "tags": ["t1"], "ok": true})),
+            make_item(
+                "b",
+                "events",
+                json!({"k": "x", "tags": ["t2"], "ok": false}),
+            ),
+        ];
+        let stage = stage(json!({
+            "query": "SELECT k, json_group_array(json_extract(tags, '$[0]')) AS tags, \
+                      SUM(ok) AS ok_count FROM events GROUP BY k",
+            "json_columns": ["tags"]
+        }));
+        let result = stage.process_batch(items, &make_context()).await.unwrap();
+        let record = result[0].record.as_ref().unwrap();
+        assert_eq!(record["tags"], json!(["t1", "t2"]));
+        assert_eq!(record["ok_count"], json!(1));
+    }
+
+    #[tokio::test]
+    async fn test_sql_declared_tables_and_passthrough() {
+        let stage = stage(json!({
+            "query": "SELECT COUNT(*) AS n FROM items",
+            "tables": {"items": "orders"}
+        }));
+        let result = stage
+            .process_batch(make_items(), &make_context())
+            .await
+            .unwrap();
+        // One result row plus the two untouched product items.
+        assert_eq!(result.len(), 3);
+        assert_eq!(result[0].record.as_ref().unwrap()["n"], json!(3));
+        assert_eq!(result[1].id, "p1");
+        assert_eq!(result[2].id, "p2");
+    }
+
+    #[tokio::test]
+    async fn test_sql_missing_fields_are_null() {
+        let items = vec![
+            make_item("a", "rows", json!({"x": 1})),
+            make_item("b", "rows", json!({"y": 2})),
+        ];
+        let stage = stage(json!({"query": "SELECT x, y FROM rows ORDER BY _item_id"}));
+        let result = stage.process_batch(items, &make_context()).await.unwrap();
+        assert_eq!(result[0].record.as_ref().unwrap()["y"], Value::Null);
+        assert_eq!(result[1].record.as_ref().unwrap()["x"], Value::Null);
+    }
+
+    #[tokio::test]
+    async fn test_sql_keys_differing_in_case_share_a_column() {
+        let items = vec![
+            make_item("a", "rows", json!({"Name": "x", "n": 1})),
+            make_item("b", "rows", json!({"name": "y", "N": 2})),
+        ];
+        let stage = stage(json!({"query": "SELECT name, n FROM rows ORDER BY _item_id"}));
+        let result = stage.process_batch(items, &make_context()).await.unwrap();
+        let rows: Vec<_> = result.iter().map(|i| i.record.clone().unwrap()).collect();
+        assert_eq!(rows[0]["Name"], "x");
+        assert_eq!(rows[1]["Name"], "y");
+        assert_eq!(rows[1]["n"], 2);
+    }
+
+    #[tokio::test]
+    async fn test_sql_rejects_writes_and_attach() {
+        let ctx = make_context();
+        let write = stage(json!({"query": "DELETE FROM orders"}));
+        assert!(write.process_batch(make_items(), &ctx).await.is_err());
+
+        let attach = stage(json!({"query": "ATTACH DATABASE ':memory:' AS other"}));
+        assert!(attach.process_batch(make_items(), &ctx).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_sql_invalid_query_is_permanent_error() {
+        let stage = stage(json!({"query": "SELECT nope FROM missing"}));
+        let err = stage
+            .process_batch(make_items(), &make_context())
+            .await
+            .unwrap_err();
+        assert!(matches!(err, StageError::Permanent { .. }));
+    }
+
+    #[tokio::test]
+    async fn test_sql_item_without_record_fails() {
+        let mut item = make_item("x", "orders", json!({}));
+        item.record = None;
+        let stage = stage(json!({"query": "SELECT * FROM orders"}));
+        assert!(
+            stage
+                .process_batch(vec![item], &make_context())
+                .await
+                .is_err()
+        );
+    }
+}
@@ -13,7 +13,9 @@ use serde::Deserialize;
 use serde_json::Value;
 use tracing::debug;
 
+use ecl_pipeline_spec::{FieldSchema, FieldType, SchemaError, StreamSchemas};
 use ecl_pipeline_topo::error::StageError;
+use ecl_pipeline_topo::schema::map_streams;
 use ecl_pipeline_topo::{PipelineItem, Stage, StageContext};
 
 type Record = serde_json::Map<String, serde_json::Value>;
@@ -200,6 +202,22 @@ impl Stage for TimezoneStage {
 
         Ok(vec![item])
     }
+
+    fn transform_schema(
+        &self,
+        inputs: &StreamSchemas,
+    ) -> Result<Option<StreamSchemas>, SchemaError> {
+        map_streams(inputs, |stream, schema| {
+            schema.require(stream, &self.config.datetime_field)?;
+            schema.require(stream, &self.config.zipcode_field)?;
+            if let Some(field) = &self.config.override_key_field {
+                schema.require(stream, field)?;
+            }
+            Ok(schema
+                .clone()
+                .with_field(FieldSchema::new(&self.config.output, FieldType::Timestamp).nullable()))
+        })
+    }
 }
 
 /// Build the US 3-digit ZIP prefix → timezone lookup table.
@@ -392,6 +410,7 @@ mod tests {
                 secrets: Default::default(),
                 triggers: None,
                 schedule: None,
+                schemas: Default::default(),
             }),
             output_dir: std::path::PathBuf::from("./out"),
             params: serde_json::Value::Null,
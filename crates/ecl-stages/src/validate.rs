@@ -12,7 +12,9 @@ use serde::Deserialize;
 use serde_json::Value;
 use tracing::debug;
 
+use ecl_pipeline_spec::{SchemaError, StreamSchemas};
 use ecl_pipeline_topo::error::StageError;
+use ecl_pipeline_topo::schema::map_streams;
 use ecl_pipeline_topo::{PipelineItem, Record, Stage, StageContext};
 
 /// Configuration for the validation stage.
@@ -377,6 +379,19 @@ impl Stage for ValidateStage {
 
         Ok(vec![item])
     }
+
+    fn transform_schema(
+        &self,
+        inputs: &StreamSchemas,
+    ) -> Result<Option<StreamSchemas>, SchemaError> {
+        // Results go to metadata, so records pass through unchanged.
+        map_streams(inputs, |stream, schema| {
+            for rule in &self.config.rules {
+                schema.require(stream, &rule.field)?;
+            }
+            Ok(schema.clone())
+        })
+    }
 }
 
 #[cfg(test)]
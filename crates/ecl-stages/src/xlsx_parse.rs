@@ -0,0 +1,409 @@
+//! XLSX parsing stage: fan-out from workbook → individual records.
+//!
+//! Reads an Excel workbook from a `PipelineItem`, selects a worksheet and an
+//! optional cell range, and emits one `PipelineItem` per row. Columns are
+//! positional within the range and use the same typing as `csv_parse`.
+
+use std::io::Cursor;
+
+use async_trait::async_trait;
+use calamine::{Data, Reader, Xlsx};
+use serde::Deserialize;
+
+use ecl_pipeline_spec::{SchemaError, StreamSchemas};
+use ecl_pipeline_topo::error::StageError;
+use ecl_pipeline_topo::{PipelineItem, Record, Stage, StageContext};
+
+use crate::columns::{
+    ColumnDef, columns_schema, convert_json_value, default_on_error, row_error, row_item,
+};
+
+/// Parsed from stage params TOML/JSON.
+#[derive(Debug, Clone, Deserialize)]
+struct XlsxParseConfig {
+    /// Column definitions in order, starting at the range's first column.
+    columns: Vec<ColumnDef>,
+    /// Worksheet name. Default: the first sheet in the workbook
+    #[serde(default)]
+    sheet: Option<String>,
+    /// A1-style cell range, e.g. `B2:E100`, or a single start cell such as
+    /// `B2` to read to the end of the sheet. Default: the whole used range
+    #[serde(default)]
+    range: Option<String>,
+    /// Whether the first row of the range is a header row. Default: true
+    #[serde(default = "default_has_headers")]
+    has_headers: bool,
+    /// How to handle parse errors for individual rows.
+    /// "skip" = skip bad rows (log warning), "fail" = fail the item.
+    /// Default: "skip"
+    #[serde(default = "default_on_error")]
+    on_row_error: String,
+}
+
+fn default_has_headers() -> bool {
+    true
+}
+
+/// A zero-based (row, column) cell position.
+type Cell = (u32, u32);
+
+/// Parse an A1-style cell reference like `C12` into a zero-based position.
+fn parse_cell(reference: &str) -> Option<Cell> {
+    let split = reference.find(|c: char| c.is_ascii_digit())?;
+    let (letters, digits) = reference.split_at(split);
+    if letters.is_empty() || !letters.chars().all(|c| c.is_ascii_alphabetic()) {
+        return None;
+    }
+    let col = letters.chars().try_fold(0u32, |acc, c| {
+        acc.checked_mul(26)?
+            .checked_add(u32::from(c.to_ascii_uppercase() as u8 - b'A') + 1)
+    })?;
+    let row: u32 = digits.parse().ok()?;
+    Some((row.checked_sub(1)?, col - 1))
+}
+
+/// Parse an A1-style range into its start cell and optional end cell.
+fn parse_range(range: &str) -> Option<(Cell, Option<Cell>)> {
+    match range.split_once(':') {
+        Some((start, end)) => Some((parse_cell(start)?, Some(parse_cell(end)?))),
+        None => Some((parse_cell(range)?, None)),
+    }
+}
+
+/// Convert a cell to JSON, or describe why it is bad.
+fn cell_value(cell: &Data) -> Result<serde_json::Value, String> {
+    Ok(match cell {
+        Data::Empty => serde_json::Value::Null,
+        Data::Int(i) => serde_json::Value::from(*i),
+        // Spreadsheets store whole numbers as floats.
+        Data::Float(f) if f.fract() == 0.0 && f.abs() < 9.0e15 => {
+            serde_json::Value::from(*f as i64)
+        }
+        Data::Float(f) => serde_json::Value::from(*f),
+        Data::Bool(b) => serde_json::Value::Bool(*b),
+        Data::String(s) | Data::DateTimeIso(s) | Data::DurationIso(s) => {
+            serde_json::Value::String(s.clone())
+        }
+        Data::DateTime(dt) => match dt.as_datetime() {
+            Some(dt) => serde_json::Value::String(dt.format("%Y-%m-%dT%H:%M:%S").to_string()),
+            None => return Err(format!("unrepresentable date cell {cell}")),
+        },
+        Data::Error(e) => return Err(format!("cell error {e}")),
+    })
+}
+
+/// XLSX parsing stage: one workbook in, N record items out.
+#[derive(Debug)]
+pub struct XlsxParseStage {
+    config: XlsxParseConfig,
+    range: Option<(Cell, Option<Cell>)>,
+}
+
+impl XlsxParseStage {
+    /// Create from stage params JSON.
+    ///
+    /// # Errors
+    ///
+    /// Returns `StageError::Permanent` if the config is invalid or the
+    /// `range` is not an A1-style reference.
+    pub fn from_params(params: &serde_json::Value) -> Result<Self, StageError> {
+        let invalid = |message: String| StageError::Permanent {
+            stage: "xlsx_parse".to_string(),
+            item_id: String::new(),
+            message: format!("invalid xlsx_parse config: {message}"),
+        };
+        let config: XlsxParseConfig =
+            serde_json::from_value(params.clone()).map_err(|e| invalid(e.to_string()))?;
+        let range = match &config.range {
+            Some(r) => {
+                let range = parse_range(r).ok_or_else(|| invalid(format!("bad range '{r}'")))?;
+                if let (start, Some(end)) = range
+                    && (start.0 > end.0 || start.1 > end.1)
+                {
+                    return Err(invalid(format!("range '{r}' ends before it starts")));
+                }
+                Some(range)
+            }
+            None => None,
+        };
+        Ok(Self { config, range })
+    }
+
+    /// Build the record for one row, or describe why it is bad.
+    fn parse_row(&self, cells: &[Data]) -> Result<Record, String> {
+        let mut record = Record::new();
+        for (idx, col) in self.config.columns.iter().enumerate() {
+            let value = cell_value(cells.get(idx).unwrap_or(&Data::Empty))
+                .map_err(|e| format!("column '{}': {e}", col.name))?;
+            record.insert(col.name.clone(), convert_json_value(&value, &col.r#type));
+        }
+        Ok(record)
+    }
+}
+
+#[async_trait]
+impl Stage for XlsxParseStage {
+    fn name(&self) -> &str {
+        "xlsx_parse"
+    }
+
+    async fn process(
+        &self,
+        item: PipelineItem,
+        _ctx: &StageContext,
+    ) -> Result<Vec<PipelineItem>, StageError> {
+        let invalid = |message: String| StageError::Permanent {
+            stage: "xlsx_parse".to_string(),
+            item_id: item.id.clone(),
+            message,
+        };
+        let mut workbook = Xlsx::new(Cursor::new(item.content.to_vec()))
+            .map_err(|e| invalid(format!("invalid XLSX workbook: {e}")))?;
+        let sheet = match &self.config.sheet {
+            Some(sheet) => sheet.clone(),
+            None => workbook
+                .sheet_names()
+                .first()
+                .cloned()
+                .ok_or_else(|| invalid("workbook has no sheets".to_string()))?,
+        };
+        let used = workbook
+            .worksheet_range(&sheet)
+            .map_err(|e| invalid(format!("cannot read sheet '{sheet}': {e}")))?;
+        let Some(used_end) = used.end() else {
+            return Ok(Vec::new());
+        };
+        let selected = match self.range {
+            // An open range starting past the used area selects nothing.
+            Some((start, None)) if start.0 > used_end.0 || start.1 > used_end.1 => {
+                return Ok(Vec::new());
+            }
+            Some((start, end)) => used.range(start, end.unwrap_or(used_end)),
+            None => used,
+        };
+        let Some((first_row, _)) = selected.start() else {
+            return Ok(Vec::new());
+        };
+
+        let mut output = Vec::new();
+        let skip = usize::from(self.config.has_headers);
+        for (idx, cells) in selected.rows().enumerate().skip(skip) {
+            // Rows are numbered as in the spreadsheet (1-based).
+            let row_number = first_row as usize + idx + 1;
+            if cells.iter().all(|c| *c == Data::Empty) {
+                continue;
+            }
+            match self.parse_row(cells) {
+                Ok(record) => {
+                    let raw: Vec<serde_json::Value> = cells
+                        .iter()
+                        .map(|c| cell_value(c).unwrap_or(serde_json::Value::Null))
+                        .collect();
+                    let raw = serde_json::to_vec(&raw).unwrap_or_default();
+                    output.push(row_item(
+                        &item,
+                        row_number,
+                        record,
+                        raw,
+                        "application/x-xlsx-row",
+                    ));
+                }
+                Err(e) => {
+                    row_error(
+                        "xlsx_parse",
+                        &self.config.on_row_error,
+                        &item,
+                        row_number,
+                        e,
+                    )?;
+                }
+            }
+        }
+
+        Ok(output)
+    }
+
+    fn transform_schema(
+        &self,
+        inputs: &StreamSchemas,
+    ) -> Result<Option<StreamSchemas>, SchemaError> {
+        Ok(Some(columns_schema(self.config.columns.iter(), inputs)))
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use ecl_pipeline_state::{Blake3Hash, ItemProvenance};
+    use rust_xlsxwriter::Workbook;
+    use serde_json::json;
+    use std::collections::BTreeMap;
+    use std::sync::Arc;
+
+    /// A workbook with a notes sheet and an orders sheet whose table starts
+    /// at B3 under a title row.
+    fn make_workbook() -> Vec<u8> {
+        let mut workbook = Workbook::new();
+        let notes = workbook.add_worksheet();
+        notes.set_name("Notes").unwrap();
+        notes.write_string(0, 0, "not data").unwrap();
+
+        let orders = workbook.add_worksheet();
+        orders.set_name("Orders").unwrap();
+        orders.write_string(0, 0, "Order export").unwrap();
+        for (col, header) in ["id", "qty", "paid"].iter().enumerate() {
+            orders.write_string(2, col as u16 + 1, *header).unwrap();
+        }
+        orders.write_string(3, 1, "A1").unwrap();
+        orders.write_number(3, 2, 3.0).unwrap();
+        orders.write_boolean(3, 3, true).unwrap();
+        orders.write_string(4, 1, "B2").unwrap();
+        orders.write_string(4, 2, "12").unwrap();
+        orders.write_boolean(4, 3, false).unwrap();
+        orders.write_string(6, 1, "C3").unwrap();
+        orders.write_number(6, 2, 1.5).unwrap();
+        workbook.save_to_buffer().unwrap()
+    }
+
+    fn make_test_item(content: Vec<u8>) -> PipelineItem {
+        PipelineItem {
+            id: "file-1".to_string(),
+            display_name: "test.xlsx".to_string(),
+            content: Arc::from(content),
+            mime_type: "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet"
+                .to_string(),
+            source_name: "test".to_string(),
+            source_content_hash: Blake3Hash::new("abc"),
+            provenance: ItemProvenance {
+                source_kind: "filesystem".to_string(),
+                metadata: BTreeMap::new(),
+                source_modified: None,
+                extracted_at: chrono::Utc::now(),
+            },
+            metadata: BTreeMap::new(),
+            record: None,
+            stream: None,
+        }
+    }
+
+    fn make_ctx() -> StageContext {
+        StageContext {
+            spec: Arc::new(
+                ecl_pipeline_spec::PipelineSpec::from_toml(
+                    "name = \"t\"\nversion = 1\noutput_dir = \"./o\"\n[sources.s]\nkind = \"filesystem\"\nroot = \"/tmp\"\n[stages.x]\nadapter = \"x\"\nresources = { creates = [\"y\"] }",
+                ).unwrap(),
+            ),
+            output_dir: std::path::PathBuf::from("/tmp"),
+            params: serde_json::Value::Null,
+            span: tracing::Span::none(),
+        }
+    }
+
+    fn orders_params(range: &str) -> serde_json::Value {
+        json!({
+            "sheet": "Orders",
+            "range": range,
+            "columns": [
+                {"name": "id"},
+                {"name": "qty", "type": "integer"},
+                {"name": "paid", "type": "boolean"},
+            ]
+        })
+    }
+
+    #[tokio::test]
+    async fn test_xlsx_parse_sheet_and_range() {
+        let stage = XlsxParseStage::from_params(&orders_params("B3")).unwrap();
+        let result = stage
+            .process(make_test_item(make_workbook()), &make_ctx())
+            .await
+            .unwrap();
+
+        // The blank spreadsheet row 6 is skipped.
+        assert_eq!(result.len(), 3);
+        assert_eq!(result[0].id, "file-1:row:4");
+        assert_eq!(result[2].id, "file-1:row:7");
+        let rec = result[0].record.as_ref().unwrap();
+        assert_eq!(rec["id"], json!("A1"));
+        assert_eq!(rec["qty"], json!(3));
+        assert_eq!(rec["paid"], json!(true));
+        assert_eq!(result[1].record.as_ref().unwrap()["qty"], json!(12));
+        let last = result[2].record.as_ref().unwrap();
+        assert_eq!(last["qty"], json!("1.5"));
+        assert_eq!(last["paid"], json!(null));
+    }
+
+    #[tokio::test]
+    async fn test_xlsx_parse_bounded_range() {
+        let stage = XlsxParseStage::from_params(&orders_params("B3:D4")).unwrap();
+        let result = stage
+            .process(make_test_item(make_workbook()), &make_ctx())
+            .await
+            .unwrap();
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].record.as_ref().unwrap()["id"], json!("A1"));
+    }
+
+    #[tokio::test]
+    async fn test_xlsx_parse_range_past_used_area() {
+        for range in ["B20", "Z4", "B20:D30"] {
+            let stage = XlsxParseStage::from_params(&orders_params(range)).unwrap();
+            let result = stage
+                .process(make_test_item(make_workbook()), &make_ctx())
+                .await
+                .unwrap();
+            assert!(result.is_empty(), "{range}");
+        }
+    }
+
+    #[test]
+    fn test_xlsx_parse_rejects_reversed_range() {
+        for range in ["E5:B2", "B5:D2", "D2:B5"] {
+            let err = XlsxParseStage::from_params(&orders_params(range)).unwrap_err();
+            assert!(err.to_string().contains("ends before it starts"), "{range}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_xlsx_parse_defaults_to_first_sheet() {
+        let stage = XlsxParseStage::from_params(&json!({
+            "has_headers": false,
+            "columns": [{"name": "note"}]
+        }))
+        .unwrap();
+        let result = stage
+            .process(make_test_item(make_workbook()), &make_ctx())
+            .await
+            .unwrap();
+        assert_eq!(result.len(), 1);
+        assert_eq!(
+            result[0].record.as_ref().unwrap()["note"],
+            json!("not data")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_xlsx_parse_missing_sheet_fails() {
+        let stage = XlsxParseStage::from_params(&json!({
+            "sheet": "Nope",
+            "columns": []
+        }))
+        .unwrap();
+        let result = stage
+            .process(make_test_item(make_workbook()), &make_ctx())
+            .await;
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_parse_range() {
+        assert_eq!(parse_cell("A1"), Some((0, 0)));
+        assert_eq!(parse_cell("ab12"), Some((11, 27)));
+        assert_eq!(parse_range("B2:D10"), Some(((1, 1), Some((9, 3)))));
+        assert_eq!(parse_range("C5"), Some(((4, 2), None)));
+        assert_eq!(parse_cell("A0"), None);
+        assert_eq!(parse_cell("12"), None);
+        assert!(XlsxParseStage::from_params(&json!({"columns": [], "range": "B:"})).is_err());
+    }
+}
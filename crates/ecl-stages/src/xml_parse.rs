@@ -0,0 +1,366 @@
+//! XML parsing stage: fan-out from file → individual records.
+//!
+//! Reads an XML document from a `PipelineItem`, selects the row elements
+//! with a small XPath subset, and emits one `PipelineItem` per row. Columns
+//! use the same typing as `csv_parse` and read child elements or attributes
+//! of the row element.
+//!
+//! Supported path syntax: `/` (child) and `//` (descendant) steps over
+//! element names or `*`, matched on local names so namespace prefixes can
+//! be omitted. Column paths are relative to the row element, may use `.`
+//! for the row's own text, and may end in `@attr` to read an attribute.
+
+use async_trait::async_trait;
+use roxmltree::{Document, Node};
+use serde::Deserialize;
+
+use ecl_pipeline_spec::{SchemaError, StreamSchemas};
+use ecl_pipeline_topo::error::StageError;
+use ecl_pipeline_topo::{PipelineItem, Record, Stage, StageContext};
+
+use crate::columns::{
+    ColumnDef, columns_schema, convert_value, default_on_error, row_error, row_item,
+};
+
+/// Parsed from stage params TOML/JSON.
+#[derive(Debug, Clone, Deserialize)]
+struct XmlParseConfig {
+    /// Column definitions in order.
+    columns: Vec<XmlColumnDef>,
+    /// Path selecting the row elements (e.g. `/orders/order` or `//order`).
+    rows: String,
+    /// How to handle parse errors for individual rows.
+    /// "skip" = skip bad rows (log warning), "fail" = fail the item.
+    /// Default: "skip"
+    #[serde(default = "default_on_error")]
+    on_row_error: String,
+}
+
+/// A column definition with an optional location inside the row element.
+#[derive(Debug, Clone, Deserialize)]
+struct XmlColumnDef {
+    #[serde(flatten)]
+    column: ColumnDef,
+    /// Path relative to the row element (e.g. `customer/name`, `@id`).
+    /// Default: the child element named after the column
+    #[serde(default)]
+    path: Option<String>,
+}
+
+/// One step of a row path.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Step {
+    /// Whether the step matches any descendant (`//`) or only children (`/`).
+    descendant: bool,
+    /// Element local name, or `*` for any element.
+    name: String,
+}
+
+/// Parse a row path like `/a/b` or `//b/*` into steps.
+fn parse_row_path(path: &str) -> Result<Vec<Step>, String> {
+    let Some(mut rest) = path.strip_prefix('/') else {
+        return Err(format!("row path '{path}' must start with '/'"));
+    };
+    let mut steps = Vec::new();
+    loop {
+        let descendant = match rest.strip_prefix('/') {
+            Some(r) => {
+                rest = r;
+                true
+            }
+            None => false,
+        };
+        let (name, next) = match rest.find('/') {
+            Some(i) => (&rest[..i], Some(&rest[i + 1..])),
+            None => (rest, None),
+        };
+        if name.is_empty() || name.starts_with('@') {
+            return Err(format!("row path '{path}' has an empty or attribute step"));
+        }
+        steps.push(Step {
+            descendant,
+            name: name.to_string(),
+        });
+        match next {
+            Some(r) => rest = r,
+            None => return Ok(steps),
+        }
+    }
+}
+
+fn matches(node: &Node<'_, '_>, name: &str) -> bool {
+    node.is_element() && (name == "*" || node.tag_name().name() == name)
+}
+
+/// Select the elements a row path matches, in document order.
+fn select_rows<'a, 'input>(doc: &'a Document<'input>, steps: &[Step]) -> Vec<Node<'a, 'input>> {
+    let mut nodes = vec![doc.root()];
+    for step in steps {
+        let mut next = Vec::new();
+        for node in &nodes {
+            if step.descendant {
+                next.extend(
+                    node.descendants()
+                        .skip(1)
+                        .filter(|n| matches(n, &step.name)),
+                );
+            } else {
+                next.extend(node.children().filter(|n| matches(n, &step.name)));
+            }
+        }
+        // Overlapping descendant matches can select a node twice and out
+        // of document order; node ids follow document order.
+        next.sort_by_key(|n| n.id().get());
+        next.dedup_by_key(|n| n.id());
+        nodes = next;
+    }
+    nodes
+}
+
+/// The text content of an element, concatenated and trimmed.
+fn element_text(node: &Node<'_, '_>) -> String {
+    node.descendants()
+        .filter(Node::is_text)
+        .filter_map(|n| n.text())
+        .collect::<String>()
+        .trim()
+        .to_string()
+}
+
+/// Read a column value relative to the row element.
+///
+/// Returns `Ok(None)` when the path matches nothing and an error when it
+/// matches more than one element.
+fn column_value(row: &Node<'_, '_>, path: &str) -> Result<Option<String>, String> {
+    let mut nodes = vec![*row];
+    for segment in path.split('/') {
+        if let Some(attr) = segment.strip_prefix('@') {
+            return match nodes.as_slice() {
+                [] => Ok(None),
+                [node] => Ok(node.attribute(attr).map(str::to_string)),
+                _ => Err(format!("path '{path}' matches more than one element")),
+            };
+        }
+        if segment != "." {
+            nodes = nodes
+                .iter()
+                .flat_map(|n| n.children().filter(|c| matches(c, segment)))
+                .collect();
+        }
+    }
+    match nodes.as_slice() {
+        [] => Ok(None),
+        [node] => Ok(Some(element_text(node))),
+        _ => Err(format!("path '{path}' matches more than one element")),
+    }
+}
+
+/// XML parsing stage: one file in, N record items out.
+#[derive(Debug)]
+pub struct XmlParseStage {
+    config: XmlParseConfig,
+    rows: Vec<Step>,
+}
+
+impl XmlParseStage {
+    /// Create from stage params JSON.
+    ///
+    /// # Errors
+    ///
+    /// Returns `StageError::Permanent` if the config is invalid or the
+    /// `rows` path does not parse.
+    pub fn from_params(params: &serde_json::Value) -> Result<Self, StageError> {
+        let invalid = |message: String| StageError::Permanent {
+            stage: "xml_parse".to_string(),
+            item_id: String::new(),
+            message: format!("invalid xml_parse config: {message}"),
+        };
+        let config: XmlParseConfig =
+            serde_json::from_value(params.clone()).map_err(|e| invalid(e.to_string()))?;
+        let rows = parse_row_path(&config.rows).map_err(invalid)?;
+        Ok(Self { config, rows })
+    }
+
+    /// Build the record for one row element, or describe why it is bad.
+    fn parse_row(&self, row: &Node<'_, '_>) -> Result<Record, String> {
+        let mut record = Record::new();
+        for col in &self.config.columns {
+            let path = col.path.as_deref().unwrap_or(&col.column.name);
+            let value = match column_value(row, path)? {
+                Some(raw) => convert_value(&raw, &col.column.r#type),
+                None => serde_json::Value::Null,
+            };
+            record.insert(col.column.name.clone(), value);
+        }
+        Ok(record)
+    }
+}
+
+#[async_trait]
+impl Stage for XmlParseStage {
+    fn name(&self) -> &str {
+        "xml_parse"
+    }
+
+    async fn process(
+        &self,
+        item: PipelineItem,
+        _ctx: &StageContext,
+    ) -> Result<Vec<PipelineItem>, StageError> {
+        let invalid = |message: String| StageError::Permanent {
+            stage: "xml_parse".to_string(),
+            item_id: item.id.clone(),
+            message,
+        };
+        let text = std::str::from_utf8(&item.content)
+            .map_err(|e| invalid(format!("XML is not valid UTF-8: {e}")))?;
+        let doc = Document::parse(text).map_err(|e| invalid(format!("invalid XML: {e}")))?;
+
+        let mut output = Vec::new();
+        for (idx, row) in select_rows(&doc, &self.rows).iter().enumerate() {
+            let row_number = idx + 1;
+            match self.parse_row(row) {
+                Ok(record) => {
+                    let raw = text[row.range()].as_bytes().to_vec();
+                    output.push(row_item(&item, row_number, record, raw, "application/xml"));
+                }
+                Err(e) => {
+                    row_error("xml_parse", &self.config.on_row_error, &item, row_number, e)?;
+                }
+            }
+        }
+
+        Ok(output)
+    }
+
+    fn transform_schema(
+        &self,
+        inputs: &StreamSchemas,
+    ) -> Result<Option<StreamSchemas>, SchemaError> {
+        let columns = self.config.columns.iter().map(|c| &c.column);
+        Ok(Some(columns_schema(columns, inputs)))
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use ecl_pipeline_state::{Blake3Hash, ItemProvenance};
+    use serde_json::json;
+    use std::collections::BTreeMap;
+    use std::sync::Arc;
+
+    const ORDERS: &str = r#"<?xml version="1.0"?>
+<export xmlns:x="urn:example">
+  <orders>
+    <order id="A1"><qty>3</qty><customer><name>Ann</name></customer></order>
+    <order id="B2"><qty>5</qty><customer><name>Bob</name></customer></order>
+    <x:order id="C3"><qty>1</qty><qty>2</qty></x:order>
+  </orders>
+</export>"#;
+
+    fn make_test_item(content: &str) -> PipelineItem {
+        PipelineItem {
+            id: "file-1".to_string(),
+            display_name: "test.xml".to_string(),
+            content: Arc::from(content.as_bytes()),
+            mime_type: "application/xml".to_string(),
+            source_name: "test".to_string(),
+            source_content_hash: Blake3Hash::new("abc"),
+            provenance: ItemProvenance {
+                source_kind: "filesystem".to_string(),
+                metadata: BTreeMap::new(),
+                source_modified: None,
+                extracted_at: chrono::Utc::now(),
+            },
+            metadata: BTreeMap::new(),
+            record: None,
+            stream: None,
+        }
+    }
+
+    fn make_ctx() -> StageContext {
+        StageContext {
+            spec: Arc::new(
+                ecl_pipeline_spec::PipelineSpec::from_toml(
+                    "name = \"t\"\nversion = 1\noutput_dir = \"./o\"\n[sources.s]\nkind = \"filesystem\"\nroot = \"/tmp\"\n[stages.x]\nadapter = \"x\"\nresources = { creates = [\"y\"] }",
+                ).unwrap(),
+            ),
+            output_dir: std::path::PathBuf::from("/tmp"),
+            params: serde_json::Value::Null,
+            span: tracing::Span::none(),
+        }
+    }
+
+    fn stage(rows: &str, on_row_error: &str) -> XmlParseStage {
+        XmlParseStage::from_params(&json!({
+            "rows": rows,
+            "on_row_error": on_row_error,
+            "columns": [
+                {"name": "id", "path": "@id"},
+                {"name": "qty", "type": "integer"},
+                {"name": "customer", "path": "customer/name"},
+            ]
+        }))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_xml_parse_absolute_rows() {
+        let result = stage("/export/orders/order", "skip")
+            .process(make_test_item(ORDERS), &make_ctx())
+            .await
+            .unwrap();
+        // The row with two <qty> children is ambiguous and skipped.
+        assert_eq!(result.len(), 2);
+        let rec = result[1].record.as_ref().unwrap();
+        assert_eq!(rec["id"], json!("B2"));
+        assert_eq!(rec["qty"], json!(5));
+        assert_eq!(rec["customer"], json!("Bob"));
+        assert!(result[0].content.starts_with(b"<order id=\"A1\">"));
+    }
+
+    #[tokio::test]
+    async fn test_xml_parse_ambiguous_column_fails() {
+        let err = stage("//order", "fail")
+            .process(make_test_item(ORDERS), &make_ctx())
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("row 3"), "unexpected error: {err}");
+    }
+
+    #[tokio::test]
+    async fn test_xml_parse_missing_column_is_null() {
+        let result = stage("//orders/*", "skip")
+            .process(
+                make_test_item("<orders><o id=\"Z\"/></orders>"),
+                &make_ctx(),
+            )
+            .await
+            .unwrap();
+        let rec = result[0].record.as_ref().unwrap();
+        assert_eq!(rec["id"], json!("Z"));
+        assert_eq!(rec["qty"], json!(null));
+    }
+
+    #[test]
+    fn test_parse_row_path() {
+        assert_eq!(
+            parse_row_path("//a/*").unwrap(),
+            vec![
+                Step {
+                    descendant: true,
+                    name: "a".to_string()
+                },
+                Step {
+                    descendant: false,
+                    name: "*".to_string()
+                },
+            ]
+        );
+        assert!(parse_row_path("a/b").is_err());
+        assert!(parse_row_path("/a//").is_err());
+        assert!(parse_row_path("/a/@id").is_err());
+    }
+}
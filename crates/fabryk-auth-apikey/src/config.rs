@@ -0,0 +1,76 @@
+//! API key configuration.
+
+use std::path::Path;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::ApiKeyError;
+use crate::store::{ApiKeyStore, FileKeyStore, RedisKeyStore};
+
+/// Where API keys are stored and whether the server accepts them.
+///
+/// Keys live in Redis when `redis_url` is set, otherwise in the JSON file
+/// at `file` (or a default path chosen by the application).
+///
+/// ```toml
+/// [api_keys]
+/// enabled = true
+/// redis_url = "redis://keys.internal:6379"
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ApiKeyConfig {
+    /// Whether the server accepts API keys.
+    pub enabled: bool,
+    /// JSON file holding the keys.
+    pub file: Option<String>,
+    /// Redis holding the keys, shared by every server instance.
+    pub redis_url: Option<String>,
+}
+
+impl ApiKeyConfig {
+    /// Open the configured store, using `default_file` if neither `file`
+    /// nor `redis_url` is set.
+    pub async fn open_store(
+        &self,
+        default_file: &Path,
+    ) -> Result<Arc<dyn ApiKeyStore>, ApiKeyError> {
+        if let Some(url) = self.redis_url.as_deref().filter(|u| !u.is_empty()) {
+            let redis = fabryk_redis::RedisClient::new(url).await?;
+            return Ok(Arc::new(RedisKeyStore::new(Arc::new(redis))));
+        }
+        let path = match self.file.as_deref().filter(|f| !f.is_empty()) {
+            Some(file) => Path::new(file),
+            None => default_file,
+        };
+        Ok(Arc::new(FileKeyStore::new(path)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_open_file_store() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let default_file = dir.path().join("default.json");
+
+        let store = ApiKeyConfig::default()
+            .open_store(&default_file)
+            .await
+            .unwrap();
+        assert_eq!(store.name(), "file");
+
+        let config = ApiKeyConfig {
+            file: Some(dir.path().join("keys.json").to_string_lossy().into_owned()),
+            ..Default::default()
+        };
+        let store = config.open_store(&default_file).await.unwrap();
+        let (key, _) = crate::ApiKey::mint("ci@example.com", ["mcp"], None);
+        store.put(&key).await.unwrap();
+        assert!(dir.path().join("keys.json").exists());
+        assert!(!default_file.exists());
+    }
+}
@@ -0,0 +1,33 @@
+//! API key error types.
+
+use fabryk_redis::RedisError;
+
+/// Errors from managing or storing API keys.
+#[derive(Debug, thiserror::Error)]
+pub enum ApiKeyError {
+    /// No key with this ID exists.
+    #[error("no API key with ID '{0}'")]
+    NotFound(String),
+
+    /// The key store could not be read or written.
+    #[error("API key store error: {0}")]
+    Store(String),
+}
+
+impl From<RedisError> for ApiKeyError {
+    fn from(e: RedisError) -> Self {
+        ApiKeyError::Store(e.to_string())
+    }
+}
+
+impl From<std::io::Error> for ApiKeyError {
+    fn from(e: std::io::Error) -> Self {
+        ApiKeyError::Store(e.to_string())
+    }
+}
+
+impl From<serde_json::Error> for ApiKeyError {
+    fn from(e: serde_json::Error) -> Self {
+        ApiKeyError::Store(e.to_string())
+    }
+}
@@ -0,0 +1,160 @@
+//! API key records and tokens.
+
+use std::fmt;
+
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use chrono::{DateTime, Utc};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+/// Prefix of every API key token, so validators can recognise them without
+/// a lookup.
+pub const KEY_PREFIX: &str = "fbk_";
+
+/// A stored API key.
+///
+/// Only a hash of the secret is kept; the token itself is shown once, when
+/// the key is minted. Tokens look like `fbk_<id>_<secret>`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApiKey {
+    /// Public key ID, part of the token.
+    pub id: String,
+    /// Who the key acts as, e.g. `ci-bot@example.com`. Reported as the
+    /// user's email to audit logs and rate limits.
+    pub owner: String,
+    /// Scopes the key is limited to.
+    #[serde(default)]
+    pub scopes: Vec<String>,
+    /// BLAKE3 hash of the secret, hex-encoded.
+    pub hash: String,
+    /// When the key was minted.
+    pub created_at: DateTime<Utc>,
+    /// When the key stops working, if ever.
+    #[serde(default)]
+    pub expires_at: Option<DateTime<Utc>>,
+    /// When the key was last used (updated at most once per validator
+    /// touch interval).
+    #[serde(default)]
+    pub last_used_at: Option<DateTime<Utc>>,
+    /// When the key was revoked.
+    #[serde(default)]
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+/// Whether a key can be used.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KeyStatus {
+    /// The key is valid.
+    Active,
+    /// The key is past its expiry time.
+    Expired,
+    /// The key has been revoked.
+    Revoked,
+}
+
+impl fmt::Display for KeyStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Active => write!(f, "active"),
+            Self::Expired => write!(f, "expired"),
+            Self::Revoked => write!(f, "revoked"),
+        }
+    }
+}
+
+impl ApiKey {
+    /// Mint a new key. Returns the record to store and the token to hand to
+    /// the client.
+    pub fn mint(
+        owner: impl Into<String>,
+        scopes: impl IntoIterator<Item = impl Into<String>>,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> (Self, String) {
+        let mut rng = rand::thread_rng();
+        let id = format!("{:016x}", rng.r#gen::<u64>());
+        let secret = URL_SAFE_NO_PAD.encode(rng.r#gen::<[u8; 32]>());
+
+        let key = Self {
+            id: id.clone(),
+            owner: owner.into(),
+            scopes: scopes.into_iter().map(Into::into).collect(),
+            hash: hash_secret(&secret),
+            created_at: Utc::now(),
+            expires_at,
+            last_used_at: None,
+            revoked_at: None,
+        };
+        (key, format!("{KEY_PREFIX}{id}_{secret}"))
+    }
+
+    /// Whether `secret` is this key's secret. Compared in constant time.
+    pub fn verify(&self, secret: &str) -> bool {
+        blake3::Hash::from_hex(&self.hash).is_ok_and(|hash| hash == blake3::hash(secret.as_bytes()))
+    }
+
+    /// Whether the key can be used at `now`.
+    pub fn status(&self, now: DateTime<Utc>) -> KeyStatus {
+        if self.revoked_at.is_some() {
+            KeyStatus::Revoked
+        } else if self.expires_at.is_some_and(|at| at <= now) {
+            KeyStatus::Expired
+        } else {
+            KeyStatus::Active
+        }
+    }
+}
+
+/// Split a token into key ID and secret. Returns `None` if the token is not
+/// an API key.
+pub fn parse_token(token: &str) -> Option<(&str, &str)> {
+    let (id, secret) = token.strip_prefix(KEY_PREFIX)?.split_once('_')?;
+    if id.is_empty() || secret.is_empty() || !id.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+    Some((id, secret))
+}
+
+fn hash_secret(secret: &str) -> String {
+    blake3::hash(secret.as_bytes()).to_hex().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn test_mint_and_verify() {
+        let (key, token) = ApiKey::mint("ci@example.com", ["mcp"], None);
+        let (id, secret) = parse_token(&token).unwrap();
+        assert_eq!(id, key.id);
+        assert!(key.verify(secret));
+        assert!(!key.verify("not-the-secret"));
+        assert!(!token.contains(&key.hash));
+        assert_eq!(key.scopes, ["mcp"]);
+
+        let (other, _) = ApiKey::mint("ci@example.com", Vec::<String>::new(), None);
+        assert_ne!(other.id, key.id);
+    }
+
+    #[test]
+    fn test_parse_token() {
+        assert_eq!(parse_token("fbk_00ff_s3cr_et"), Some(("00ff", "s3cr_et")));
+        assert!(parse_token("eyJhbGciOi.x.y").is_none());
+        assert!(parse_token("fbk_nothex_secret").is_none());
+        assert!(parse_token("fbk_00ff").is_none());
+        assert!(parse_token("fbk_00ff_").is_none());
+    }
+
+    #[test]
+    fn test_status() {
+        let now = Utc::now();
+        let (mut key, _) = ApiKey::mint("ci@example.com", ["mcp"], Some(now + Duration::days(1)));
+        assert_eq!(key.status(now), KeyStatus::Active);
+        assert_eq!(key.status(now + Duration::days(2)), KeyStatus::Expired);
+        key.revoked_at = Some(now);
+        assert_eq!(key.status(now), KeyStatus::Revoked);
+        assert_eq!(KeyStatus::Revoked.to_string(), "revoked");
+    }
+}
@@ -0,0 +1,39 @@
+//! API key authentication for machine clients of Fabryk servers.
+//!
+//! Provides:
+//! - [`ApiKey`] — a minted key: owner, scopes, expiry, last use and
+//!   revocation, with only a hash of the secret stored
+//! - [`ApiKeyStore`] — key storage, in a JSON file ([`FileKeyStore`]) or
+//!   Redis ([`RedisKeyStore`])
+//! - [`ApiKeyValidator`] — a [`fabryk_auth::TokenValidator`] for
+//!   `fbk_<id>_<secret>` bearer tokens
+//! - [`ApiKeyConfig`] — the `[api_keys]` configuration section
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use fabryk_auth::{AuthLayer, ChainValidator};
+//! use fabryk_auth_apikey::{ApiKey, ApiKeyValidator, FileKeyStore};
+//!
+//! let store = Arc::new(FileKeyStore::new("/var/lib/fabryk/api-keys.json"));
+//! let (key, token) = ApiKey::mint("ci-bot@example.com", ["mcp"], None);
+//! store.put(&key).await?;
+//!
+//! // API keys first, interactive logins for everything else.
+//! let validator = ChainValidator::new()
+//!     .with(ApiKeyValidator::new(store))
+//!     .with(GoogleTokenValidator::new(jwks_url));
+//! let app = router.layer(AuthLayer::new(Arc::new(validator), auth_config));
+//! ```
+
+mod config;
+mod error;
+mod key;
+mod store;
+mod validator;
+
+pub use config::ApiKeyConfig;
+pub use error::ApiKeyError;
+pub use key::{ApiKey, KEY_PREFIX, KeyStatus, parse_token};
+pub use store::{ApiKeyStore, FileKeyStore, RedisKeyStore};
+pub use validator::ApiKeyValidator;
@@ -0,0 +1,329 @@
+//! API key storage.
+//!
+//! - [`FileKeyStore`] — a JSON file, for single instances and local use
+//! - [`RedisKeyStore`] — keys shared across instances through Redis
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use fabryk_redis::RedisOps;
+use tokio::sync::Mutex;
+
+use crate::error::ApiKeyError;
+use crate::key::ApiKey;
+
+/// Where API keys are kept.
+#[async_trait]
+pub trait ApiKeyStore: Send + Sync {
+    /// The key with this ID, if any.
+    async fn get(&self, id: &str) -> Result<Option<ApiKey>, ApiKeyError>;
+
+    /// Insert or replace a key.
+    async fn put(&self, key: &ApiKey) -> Result<(), ApiKeyError>;
+
+    /// Every key, revoked and expired ones included, oldest first.
+    async fn list(&self) -> Result<Vec<ApiKey>, ApiKeyError>;
+
+    /// Store name for logs.
+    fn name(&self) -> &'static str;
+
+    /// Apply `change` to the key with this ID and store the result.
+    ///
+    /// The default reads and writes the key separately; stores that can
+    /// make the change atomically should override this.
+    async fn update(
+        &self,
+        id: &str,
+        change: &(dyn for<'k> Fn(&'k mut ApiKey) + Send + Sync),
+    ) -> Result<ApiKey, ApiKeyError> {
+        let mut key = self
+            .get(id)
+            .await?
+            .ok_or_else(|| ApiKeyError::NotFound(id.to_string()))?;
+        change(&mut key);
+        self.put(&key).await?;
+        Ok(key)
+    }
+
+    /// Revoke a key. Revoked keys stay listed so their use can be traced.
+    async fn revoke(&self, id: &str) -> Result<ApiKey, ApiKeyError> {
+        let now = Utc::now();
+        self.update(id, &|key| {
+            key.revoked_at.get_or_insert(now);
+        })
+        .await
+    }
+
+    /// Record that a key was used at `at`.
+    async fn touch(&self, id: &str, at: DateTime<Utc>) -> Result<(), ApiKeyError> {
+        self.update(id, &|key| key.last_used_at = Some(at)).await?;
+        Ok(())
+    }
+}
+
+// ============================================================================
+// File
+// ============================================================================
+
+/// Keys kept in a JSON file.
+///
+/// The file is read on every lookup, so keys minted or revoked by the CLI
+/// take effect on a running server. Writes replace the file atomically.
+/// Each read-modify-write holds an OS lock on `<path>.lock`, so a server
+/// recording key use and a CLI revoking a key never undo each other.
+#[derive(Debug)]
+pub struct FileKeyStore {
+    path: PathBuf,
+    lock: Mutex<()>,
+}
+
+impl FileKeyStore {
+    /// Keep keys in the file at `path`. It is created on the first write.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            lock: Mutex::new(()),
+        }
+    }
+
+    /// The file's path.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    async fn read(&self) -> Result<Vec<ApiKey>, ApiKeyError> {
+        match tokio::fs::read(&self.path).await {
+            Ok(bytes) => Ok(serde_json::from_slice(&bytes)?),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn write(&self, keys: &[ApiKey]) -> Result<(), ApiKeyError> {
+        let tmp = self
+            .path
+            .with_extension(format!("json.{}.tmp", std::process::id()));
+        tokio::fs::write(&tmp, serde_json::to_vec_pretty(keys)?).await?;
+        tokio::fs::rename(&tmp, &self.path).await?;
+        Ok(())
+    }
+
+    /// Take the exclusive lock shared with other processes using this file.
+    /// It is released when the returned file is dropped.
+    async fn lock_file(&self) -> Result<std::fs::File, ApiKeyError> {
+        if let Some(parent) = self.path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        let lock_path = self.path.with_extension("json.lock");
+        let file = tokio::task::spawn_blocking(move || {
+            let file = std::fs::OpenOptions::new()
+                .create(true)
+                .truncate(false)
+                .write(true)
+                .open(lock_path)?;
+            file.lock()?;
+            Ok::<_, std::io::Error>(file)
+        })
+        .await
+        .map_err(std::io::Error::other)??;
+        Ok(file)
+    }
+}
+
+#[async_trait]
+impl ApiKeyStore for FileKeyStore {
+    async fn get(&self, id: &str) -> Result<Option<ApiKey>, ApiKeyError> {
+        Ok(self.read().await?.into_iter().find(|k| k.id == id))
+    }
+
+    async fn put(&self, key: &ApiKey) -> Result<(), ApiKeyError> {
+        let _guard = self.lock.lock().await;
+        let _file = self.lock_file().await?;
+        let mut keys = self.read().await?;
+        match keys.iter_mut().find(|k| k.id == key.id) {
+            Some(existing) => *existing = key.clone(),
+            None => keys.push(key.clone()),
+        }
+        self.write(&keys).await
+    }
+
+    async fn list(&self) -> Result<Vec<ApiKey>, ApiKeyError> {
+        self.read().await
+    }
+
+    fn name(&self) -> &'static str {
+        "file"
+    }
+
+    async fn update(
+        &self,
+        id: &str,
+        change: &(dyn for<'k> Fn(&'k mut ApiKey) + Send + Sync),
+    ) -> Result<ApiKey, ApiKeyError> {
+        let _guard = self.lock.lock().await;
+        let _file = self.lock_file().await?;
+        let mut keys = self.read().await?;
+        let key = keys
+            .iter_mut()
+            .find(|k| k.id == id)
+            .ok_or_else(|| ApiKeyError::NotFound(id.to_string()))?;
+        change(key);
+        let key = key.clone();
+        self.write(&keys).await?;
+        Ok(key)
+    }
+}
+
+// ============================================================================
+// Redis
+// ============================================================================
+
+/// Keys kept in Redis, one JSON value per key at `<prefix>:<id>`.
+#[derive(Debug)]
+pub struct RedisKeyStore {
+    redis: Arc<dyn RedisOps>,
+    prefix: String,
+}
+
+impl RedisKeyStore {
+    /// Keep keys in the given Redis instance under `fabryk:apikey`.
+    pub fn new(redis: Arc<dyn RedisOps>) -> Self {
+        Self {
+            redis,
+            prefix: "fabryk:apikey".to_string(),
+        }
+    }
+
+    /// Use a different Redis key prefix.
+    pub fn with_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = prefix.into();
+        self
+    }
+
+    fn redis_key(&self, id: &str) -> String {
+        format!("{}:{id}", self.prefix)
+    }
+}
+
+#[async_trait]
+impl ApiKeyStore for RedisKeyStore {
+    async fn get(&self, id: &str) -> Result<Option<ApiKey>, ApiKeyError> {
+        Ok(fabryk_redis::get_json(&*self.redis, &self.redis_key(id)).await?)
+    }
+
+    async fn put(&self, key: &ApiKey) -> Result<(), ApiKeyError> {
+        Ok(fabryk_redis::set_json(&*self.redis, &self.redis_key(&key.id), key).await?)
+    }
+
+    async fn list(&self) -> Result<Vec<ApiKey>, ApiKeyError> {
+        let ids = self.redis.scan_keys(&format!("{}:*", self.prefix)).await?;
+        let mut keys = self
+            .redis
+            .get_multi_str(&ids)
+            .await?
+            .iter()
+            .map(|json| serde_json::from_str(json))
+            .collect::<Result<Vec<ApiKey>, _>>()?;
+        keys.sort_by_key(|k| k.created_at);
+        Ok(keys)
+    }
+
+    fn name(&self) -> &'static str {
+        "redis"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fabryk_redis::MockRedis;
+
+    async fn exercise(store: &dyn ApiKeyStore) {
+        assert!(store.list().await.unwrap().is_empty());
+        let (first, _) = ApiKey::mint("ci@example.com", ["mcp"], None);
+        let (mut second, _) = ApiKey::mint("etl@example.com", ["mcp", "graph"], None);
+        second.created_at = first.created_at + chrono::Duration::seconds(1);
+        store.put(&first).await.unwrap();
+        store.put(&second).await.unwrap();
+
+        assert_eq!(store.get(&second.id).await.unwrap().as_ref(), Some(&second));
+        assert!(store.get("0000").await.unwrap().is_none());
+
+        let now = Utc::now();
+        store.touch(&first.id, now).await.unwrap();
+        let revoked = store.revoke(&second.id).await.unwrap();
+        assert!(revoked.revoked_at.is_some());
+        assert!(matches!(
+            store.revoke("0000").await,
+            Err(ApiKeyError::NotFound(_))
+        ));
+
+        let keys = store.list().await.unwrap();
+        assert_eq!(keys.len(), 2);
+        assert_eq!(keys[0].id, first.id);
+        assert_eq!(keys[0].last_used_at, Some(now));
+        assert_eq!(keys[1].revoked_at, revoked.revoked_at);
+    }
+
+    #[tokio::test]
+    async fn test_file_store() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let store = FileKeyStore::new(dir.path().join("keys/api-keys.json"));
+        exercise(&store).await;
+        assert!(store.path().exists());
+
+        // A second handle sees the same keys.
+        let reopened = FileKeyStore::new(store.path());
+        assert_eq!(reopened.list().await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_file_store_handles_do_not_lose_writes() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("api-keys.json");
+        // Separate handles stand in for a server and the `keys` CLI.
+        let server = Arc::new(FileKeyStore::new(&path));
+        let cli = Arc::new(FileKeyStore::new(&path));
+        let (key, _) = ApiKey::mint("ci@example.com", ["mcp"], None);
+        cli.put(&key).await.unwrap();
+
+        let mut tasks = Vec::new();
+        for i in 0..20 {
+            let server = server.clone();
+            let id = key.id.clone();
+            tasks.push(tokio::spawn(async move {
+                server.touch(&id, Utc::now()).await.unwrap();
+            }));
+            let cli = cli.clone();
+            tasks.push(tokio::spawn(async move {
+                let (other, _) = ApiKey::mint(format!("{i}@example.com"), ["mcp"], None);
+                cli.put(&other).await.unwrap();
+            }));
+        }
+        let revoke = {
+            let cli = cli.clone();
+            let id = key.id.clone();
+            tokio::spawn(async move { cli.revoke(&id).await.unwrap() })
+        };
+        for task in tasks {
+            task.await.unwrap();
+        }
+        revoke.await.unwrap();
+
+        let keys = server.list().await.unwrap();
+        assert_eq!(keys.len(), 21);
+        let stored = keys.iter().find(|k| k.id == key.id).unwrap();
+        assert!(stored.revoked_at.is_some());
+        assert!(stored.last_used_at.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_redis_store() {
+        let redis = Arc::new(MockRedis::new());
+        let store = RedisKeyStore::new(redis.clone()).with_prefix("test:keys");
+        exercise(&store).await;
+        assert_eq!(redis.scan_keys("test:keys:*").await.unwrap().len(), 2);
+    }
+}
@@ -0,0 +1,223 @@
+//! The API key token validator.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::Utc;
+use fabryk_auth::{AuthConfig, AuthError, AuthenticatedUser, TokenValidator};
+
+use crate::key::{KeyStatus, parse_token};
+use crate::store::ApiKeyStore;
+
+/// Validates API key tokens (`fbk_<id>_<secret>`) against an
+/// [`ApiKeyStore`].
+///
+/// Other tokens fail with [`AuthError::UnsupportedToken`], so this
+/// validator goes first in a [`ChainValidator`](fabryk_auth::ChainValidator)
+/// in front of the interactive-login providers.
+///
+/// The resulting user has the key's owner as email, `apikey:<id>` as
+/// subject and the key's scopes. `AuthConfig.audience` and `domain` apply
+/// to identity provider tokens only and are not checked.
+///
+/// Each successful use updates the key's `last_used_at`, at most once per
+/// touch interval (one minute by default) to keep store writes down.
+pub struct ApiKeyValidator {
+    store: Arc<dyn ApiKeyStore>,
+    touch_interval: Duration,
+}
+
+impl ApiKeyValidator {
+    /// Validate keys held in `store`.
+    pub fn new(store: Arc<dyn ApiKeyStore>) -> Self {
+        Self {
+            store,
+            touch_interval: Duration::from_secs(60),
+        }
+    }
+
+    /// Set how often `last_used_at` is updated for a key in constant use.
+    pub fn with_touch_interval(mut self, interval: Duration) -> Self {
+        self.touch_interval = interval;
+        self
+    }
+
+    async fn validate_key(&self, token: &str) -> Result<AuthenticatedUser, AuthError> {
+        let (id, secret) = parse_token(token).ok_or(AuthError::UnsupportedToken)?;
+
+        let key = self
+            .store
+            .get(id)
+            .await
+            .map_err(|e| AuthError::Store(e.to_string()))?
+            .filter(|key| key.verify(secret))
+            .ok_or_else(|| AuthError::InvalidSignature("unknown API key".to_string()))?;
+
+        let now = Utc::now();
+        match key.status(now) {
+            KeyStatus::Active => {}
+            KeyStatus::Expired => return Err(AuthError::Expired),
+            KeyStatus::Revoked => {
+                return Err(AuthError::InvalidSignature(format!(
+                    "API key {id} has been revoked"
+                )));
+            }
+        }
+
+        let stale = key.last_used_at.is_none_or(|at| {
+            (now - at)
+                .to_std()
+                .is_ok_and(|since| since >= self.touch_interval)
+        });
+        if stale && let Err(e) = self.store.touch(id, now).await {
+            log::warn!(
+                "Failed to record use of API key {id} in {} store: {e}",
+                self.store.name()
+            );
+        }
+
+        Ok(AuthenticatedUser::new(key.owner, format!("apikey:{}", key.id)).with_scopes(key.scopes))
+    }
+}
+
+impl TokenValidator for ApiKeyValidator {
+    fn validate(
+        &self,
+        token: &str,
+        _config: &AuthConfig,
+    ) -> Pin<Box<dyn Future<Output = Result<AuthenticatedUser, AuthError>> + Send + '_>> {
+        let token = token.to_string();
+        Box::pin(async move { self.validate_key(&token).await })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ApiKeyError;
+    use crate::key::ApiKey;
+    use crate::store::FileKeyStore;
+    use async_trait::async_trait;
+    use chrono::{DateTime, Duration as ChronoDuration};
+    use fabryk_auth::ChainValidator;
+
+    fn store() -> (tempfile::TempDir, Arc<FileKeyStore>) {
+        let dir = tempfile::TempDir::new().unwrap();
+        let store = Arc::new(FileKeyStore::new(dir.path().join("api-keys.json")));
+        (dir, store)
+    }
+
+    async fn mint(store: &FileKeyStore, expires_at: Option<DateTime<Utc>>) -> (ApiKey, String) {
+        let (key, token) = ApiKey::mint("ci-bot@example.com", ["mcp:read"], expires_at);
+        store.put(&key).await.unwrap();
+        (key, token)
+    }
+
+    async fn validate(
+        validator: &impl TokenValidator,
+        token: &str,
+    ) -> Result<AuthenticatedUser, AuthError> {
+        validator.validate(token, &AuthConfig::default()).await
+    }
+
+    #[tokio::test]
+    async fn test_valid_key_and_last_used() {
+        let (_dir, store) = store();
+        let (key, token) = mint(&store, None).await;
+        let validator = ApiKeyValidator::new(store.clone());
+
+        let user = validate(&validator, &token).await.unwrap();
+        assert_eq!(user.email, "ci-bot@example.com");
+        assert_eq!(user.subject, format!("apikey:{}", key.id));
+        assert!(user.has_scope("mcp:read"));
+
+        let first_use = store.get(&key.id).await.unwrap().unwrap().last_used_at;
+        assert!(first_use.is_some());
+
+        // Within the touch interval the timestamp is left alone.
+        validate(&validator, &token).await.unwrap();
+        assert_eq!(
+            store.get(&key.id).await.unwrap().unwrap().last_used_at,
+            first_use
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rejected_keys() {
+        let (_dir, store) = store();
+        let validator = ApiKeyValidator::new(store.clone());
+
+        let (_, expired) = mint(&store, Some(Utc::now() - ChronoDuration::hours(1))).await;
+        assert!(matches!(
+            validate(&validator, &expired).await,
+            Err(AuthError::Expired)
+        ));
+
+        let (revoked_key, revoked) = mint(&store, None).await;
+        store.revoke(&revoked_key.id).await.unwrap();
+        assert!(matches!(
+            validate(&validator, &revoked).await,
+            Err(AuthError::InvalidSignature(ref m)) if m.contains("revoked")
+        ));
+
+        let (key, _) = mint(&store, None).await;
+        let wrong_secret = format!("fbk_{}_guessed", key.id);
+        assert!(matches!(
+            validate(&validator, &wrong_secret).await,
+            Err(AuthError::InvalidSignature(_))
+        ));
+        assert!(matches!(
+            validate(&validator, "fbk_0123_unknown").await,
+            Err(AuthError::InvalidSignature(_))
+        ));
+        assert!(matches!(
+            validate(&validator, "eyJhbGciOiJSUzI1NiJ9.e30.sig").await,
+            Err(AuthError::UnsupportedToken)
+        ));
+    }
+
+    /// A store that is always down.
+    struct DownStore;
+
+    #[async_trait]
+    impl ApiKeyStore for DownStore {
+        async fn get(&self, _id: &str) -> Result<Option<ApiKey>, ApiKeyError> {
+            Err(ApiKeyError::Store("connection refused".to_string()))
+        }
+
+        async fn put(&self, _key: &ApiKey) -> Result<(), ApiKeyError> {
+            Err(ApiKeyError::Store("connection refused".to_string()))
+        }
+
+        async fn list(&self) -> Result<Vec<ApiKey>, ApiKeyError> {
+            Err(ApiKeyError::Store("connection refused".to_string()))
+        }
+
+        fn name(&self) -> &'static str {
+            "down"
+        }
+    }
+
+    #[tokio::test]
+    async fn test_store_errors_are_server_errors() {
+        let validator = ApiKeyValidator::new(Arc::new(DownStore));
+        let err = validate(&validator, "fbk_0123_secret").await.unwrap_err();
+        assert!(matches!(err, AuthError::Store(_)));
+        assert!(!err.is_client_error());
+    }
+
+    #[tokio::test]
+    async fn test_in_chain_before_identity_provider() {
+        let (_dir, store) = store();
+        let (_, token) = mint(&store, None).await;
+        let chain = ChainValidator::new().with(ApiKeyValidator::new(store.clone()));
+
+        assert!(validate(&chain, &token).await.is_ok());
+        assert!(matches!(
+            validate(&chain, "not-a-key").await,
+            Err(AuthError::UnsupportedToken)
+        ));
+    }
+}
@@ -153,10 +153,7 @@ impl GoogleTokenValidator {
 
         Self::check_domain(&email, claims.hd, domain)?;
 
-        Ok(AuthenticatedUser {
-            email,
-            subject: claims.sub,
-        })
+        Ok(AuthenticatedUser::new(email, claims.sub).with_issuer("https://accounts.google.com"))
     }
 
     /// Validate an opaque Google access token via the userinfo endpoint.
@@ -199,10 +196,7 @@ impl GoogleTokenValidator {
 
         log::info!("Access token validated via userinfo for {email}");
 
-        Ok(AuthenticatedUser {
-            email,
-            subject: info.sub,
-        })
+        Ok(AuthenticatedUser::new(email, info.sub))
     }
 
     /// Verify that the user's domain matches the configured domain.
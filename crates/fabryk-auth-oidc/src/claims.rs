@@ -0,0 +1,214 @@
+//! Mapping token claims onto an [`AuthenticatedUser`].
+
+use fabryk_auth::{AuthError, AuthenticatedUser};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Which claims hold the user's email, groups and roles.
+///
+/// Each entry is a claim path. Nested claims are separated by dots, e.g.
+/// `realm_access.roles`; a key that itself contains dots (such as an Auth0
+/// namespaced claim `https://example.com/roles`) is matched whole first.
+///
+/// ```toml
+/// [oidc.claims]
+/// email = ["email", "preferred_username"]
+/// groups = ["groups"]
+/// roles = ["realm_access.roles"]
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ClaimMapping {
+    /// Claims tried in order for the email; the first string wins.
+    pub email: Vec<String>,
+    /// Claims whose values are collected into `groups`.
+    pub groups: Vec<String>,
+    /// Claims whose values are collected into `roles`.
+    pub roles: Vec<String>,
+}
+
+impl Default for ClaimMapping {
+    fn default() -> Self {
+        Self {
+            email: vec!["email".to_string()],
+            groups: vec!["groups".to_string()],
+            roles: Vec::new(),
+        }
+    }
+}
+
+impl ClaimMapping {
+    /// Set the email claims.
+    pub fn with_email(mut self, paths: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.email = paths.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Set the group claims.
+    pub fn with_groups(mut self, paths: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.groups = paths.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Set the role claims.
+    pub fn with_roles(mut self, paths: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.roles = paths.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Build the user described by a validated token's claims.
+    ///
+    /// Fails if `sub` or the email is missing, or if the token says the
+    /// email is unverified (`email_verified: false`).
+    pub fn user(&self, claims: &Value) -> Result<AuthenticatedUser, AuthError> {
+        let subject = claims
+            .get("sub")
+            .and_then(Value::as_str)
+            .ok_or_else(|| AuthError::InvalidFormat("missing sub claim".to_string()))?;
+
+        let email = self
+            .email
+            .iter()
+            .find_map(|path| lookup_claim(claims, path).and_then(Value::as_str))
+            .ok_or(AuthError::MissingEmail)?;
+
+        if claims.get("email_verified").and_then(Value::as_bool) == Some(false) {
+            return Err(AuthError::InvalidFormat(
+                "email is not verified".to_string(),
+            ));
+        }
+
+        let mut user = AuthenticatedUser::new(email, subject)
+            .with_groups(collect_values(claims, &self.groups))
+            .with_roles(collect_values(claims, &self.roles));
+        user.issuer = claims.get("iss").and_then(Value::as_str).map(String::from);
+        Ok(user)
+    }
+}
+
+/// Find the claim at `path` in a token's claims.
+///
+/// At each level the remaining path is first tried as a single key, then
+/// split at each dot in turn, so both `realm_access.roles` and keys
+/// containing dots resolve.
+pub fn lookup_claim<'a>(claims: &'a Value, path: &str) -> Option<&'a Value> {
+    let object = claims.as_object()?;
+    if let Some(value) = object.get(path) {
+        return Some(value);
+    }
+    path.match_indices('.').find_map(|(i, _)| {
+        object
+            .get(&path[..i])
+            .and_then(|nested| lookup_claim(nested, &path[i + 1..]))
+    })
+}
+
+/// Every string at the given claims, in order and without duplicates.
+///
+/// A claim may hold a single string or an array of strings.
+fn collect_values(claims: &Value, paths: &[String]) -> Vec<String> {
+    let mut values: Vec<String> = Vec::new();
+    for value in paths.iter().filter_map(|path| lookup_claim(claims, path)) {
+        let items = match value {
+            Value::Array(items) => items.iter().filter_map(Value::as_str).collect(),
+            Value::String(item) => vec![item.as_str()],
+            _ => Vec::new(),
+        };
+        for item in items {
+            if !values.iter().any(|v| v == item) {
+                values.push(item.to_string());
+            }
+        }
+    }
+    values
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn keycloak_claims() -> Value {
+        json!({
+            "iss": "https://sso.example.com/realms/eng",
+            "sub": "f1c2",
+            "email": "alice@example.com",
+            "email_verified": true,
+            "groups": ["/engineering", "/oncall"],
+            "realm_access": { "roles": ["admin", "offline_access"] },
+            "resource_access": { "fabryk.mcp": { "roles": ["writer", "admin"] } },
+            "https://example.com/roles": ["auditor"],
+        })
+    }
+
+    #[test]
+    fn test_lookup_claim_paths() {
+        let claims = keycloak_claims();
+        assert_eq!(lookup_claim(&claims, "sub"), Some(&json!("f1c2")));
+        assert_eq!(
+            lookup_claim(&claims, "realm_access.roles"),
+            Some(&json!(["admin", "offline_access"]))
+        );
+        assert_eq!(
+            lookup_claim(&claims, "resource_access.fabryk.mcp.roles"),
+            Some(&json!(["writer", "admin"]))
+        );
+        assert_eq!(
+            lookup_claim(&claims, "https://example.com/roles"),
+            Some(&json!(["auditor"]))
+        );
+        assert!(lookup_claim(&claims, "realm_access.groups").is_none());
+        assert!(lookup_claim(&claims, "sub.inner").is_none());
+    }
+
+    #[test]
+    fn test_user_from_claims() {
+        let mapping = ClaimMapping::default().with_roles([
+            "realm_access.roles",
+            "resource_access.fabryk.mcp.roles",
+            "https://example.com/roles",
+        ]);
+        let user = mapping.user(&keycloak_claims()).unwrap();
+        assert_eq!(user.email, "alice@example.com");
+        assert_eq!(user.subject, "f1c2");
+        assert_eq!(
+            user.issuer.as_deref(),
+            Some("https://sso.example.com/realms/eng")
+        );
+        assert_eq!(user.groups, ["/engineering", "/oncall"]);
+        assert_eq!(user.roles, ["admin", "offline_access", "writer", "auditor"]);
+    }
+
+    #[test]
+    fn test_email_fallback_and_single_string_claims() {
+        let mapping = ClaimMapping::default()
+            .with_email(["email", "preferred_username"])
+            .with_roles(["roles"]);
+        let claims = json!({
+            "sub": "oid-1",
+            "preferred_username": "bob@contoso.com",
+            "roles": "Fabryk.Reader",
+        });
+        let user = mapping.user(&claims).unwrap();
+        assert_eq!(user.email, "bob@contoso.com");
+        assert_eq!(user.roles, ["Fabryk.Reader"]);
+        assert!(user.groups.is_empty());
+    }
+
+    #[test]
+    fn test_user_rejects_missing_or_unverified_email() {
+        let mapping = ClaimMapping::default();
+        assert!(matches!(
+            mapping.user(&json!({ "sub": "1" })),
+            Err(AuthError::MissingEmail)
+        ));
+        assert!(matches!(
+            mapping.user(&json!({ "sub": "1", "email": "a@b.com", "email_verified": false })),
+            Err(AuthError::InvalidFormat(_))
+        ));
+        assert!(matches!(
+            mapping.user(&json!({ "email": "a@b.com" })),
+            Err(AuthError::InvalidFormat(_))
+        ));
+    }
+}
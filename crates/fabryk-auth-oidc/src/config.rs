@@ -0,0 +1,219 @@
+//! OIDC provider configuration and presets.
+
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::claims::ClaimMapping;
+
+/// Configuration for [`OidcTokenValidator`](crate::OidcTokenValidator).
+///
+/// The issuer is the only required setting; everything else is discovered
+/// from `<issuer>/.well-known/openid-configuration`. Tokens are accepted
+/// for [`AuthConfig::audience`](fabryk_auth::AuthConfig) and for any extra
+/// `audiences` listed here.
+///
+/// ```toml
+/// [oidc]
+/// issuer = "https://dev-123456.okta.com/oauth2/default"
+/// audiences = ["api://fabryk"]
+///
+/// [oidc.claims]
+/// groups = ["groups"]
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OidcConfig {
+    /// The issuer URL. Must match the discovery document and the `iss`
+    /// claim of every token exactly.
+    pub issuer: String,
+    /// Discovery document URL, if not at the standard location.
+    #[serde(default)]
+    pub discovery_url: Option<String>,
+    /// Audiences accepted in addition to `AuthConfig.audience`.
+    #[serde(default)]
+    pub audiences: Vec<String>,
+    /// Where to find the email, groups and roles in the token.
+    #[serde(default)]
+    pub claims: ClaimMapping,
+    /// How long fetched signing keys are trusted before a refresh.
+    #[serde(default = "default_jwks_cache_secs")]
+    pub jwks_cache_secs: u64,
+    /// Minimum time between refreshes triggered by an unknown `kid`, so
+    /// tokens with made-up key IDs can't hammer the provider.
+    #[serde(default = "default_jwks_min_refresh_secs")]
+    pub jwks_min_refresh_secs: u64,
+    /// Clock skew allowed when checking `exp` and `nbf`.
+    #[serde(default = "default_leeway_secs")]
+    pub leeway_secs: u64,
+}
+
+fn default_jwks_cache_secs() -> u64 {
+    3600
+}
+
+fn default_jwks_min_refresh_secs() -> u64 {
+    30
+}
+
+fn default_leeway_secs() -> u64 {
+    60
+}
+
+impl OidcConfig {
+    /// Configure a provider by issuer URL, with the default claim mapping
+    /// (`email` and `groups`).
+    pub fn new(issuer: impl Into<String>) -> Self {
+        Self {
+            issuer: issuer.into(),
+            discovery_url: None,
+            audiences: Vec::new(),
+            claims: ClaimMapping::default(),
+            jwks_cache_secs: default_jwks_cache_secs(),
+            jwks_min_refresh_secs: default_jwks_min_refresh_secs(),
+            leeway_secs: default_leeway_secs(),
+        }
+    }
+
+    /// Okta, given an authorization server issuer such as
+    /// `https://dev-123456.okta.com/oauth2/default`.
+    ///
+    /// Groups come from the `groups` claim, which needs a groups claim
+    /// configured on the authorization server.
+    pub fn okta(issuer: impl Into<String>) -> Self {
+        Self::new(issuer)
+    }
+
+    /// Auth0, given the tenant domain (e.g. `example.eu.auth0.com`) and the
+    /// namespace used for custom claims (e.g. `https://fabryk.example.com`).
+    ///
+    /// Auth0 only emits groups and roles through an Action that sets
+    /// `<namespace>/groups` and `<namespace>/roles`.
+    pub fn auth0(domain: &str, namespace: &str) -> Self {
+        let namespace = namespace.trim_end_matches('/');
+        let mut config = Self::new(format!("https://{}/", domain.trim_end_matches('/')));
+        config.claims = ClaimMapping::default()
+            .with_groups([format!("{namespace}/groups")])
+            .with_roles([format!("{namespace}/roles")]);
+        config
+    }
+
+    /// Keycloak, given the server URL and realm.
+    ///
+    /// Roles come from `realm_access.roles`; add
+    /// `resource_access.<client>.roles` to [`ClaimMapping::roles`] for
+    /// client roles. Groups need a group membership mapper.
+    pub fn keycloak(base_url: &str, realm: &str) -> Self {
+        let mut config = Self::new(format!("{}/realms/{realm}", base_url.trim_end_matches('/')));
+        config.claims = ClaimMapping::default().with_roles(["realm_access.roles"]);
+        config
+    }
+
+    /// Azure AD (Microsoft Entra ID), given the tenant ID.
+    ///
+    /// Uses the v2.0 endpoint, so the application must issue v2 access
+    /// tokens. Work accounts often lack `email`, so `preferred_username`
+    /// and `upn` are used as fallbacks; app roles come from `roles`.
+    pub fn azure_ad(tenant_id: &str) -> Self {
+        let mut config = Self::new(format!(
+            "https://login.microsoftonline.com/{tenant_id}/v2.0"
+        ));
+        config.claims = ClaimMapping::default()
+            .with_email(["email", "preferred_username", "upn"])
+            .with_roles(["roles"]);
+        config
+    }
+
+    /// Accept tokens for an additional audience.
+    pub fn with_audience(mut self, audience: impl Into<String>) -> Self {
+        self.audiences.push(audience.into());
+        self
+    }
+
+    /// Fetch the discovery document from a non-standard URL.
+    pub fn with_discovery_url(mut self, url: impl Into<String>) -> Self {
+        self.discovery_url = Some(url.into());
+        self
+    }
+
+    /// Replace the claim mapping.
+    pub fn with_claims(mut self, claims: ClaimMapping) -> Self {
+        self.claims = claims;
+        self
+    }
+
+    /// Set the JWKS cache TTL and the minimum interval between refreshes.
+    pub fn with_jwks_refresh(mut self, cache: Duration, min_refresh: Duration) -> Self {
+        self.jwks_cache_secs = cache.as_secs();
+        self.jwks_min_refresh_secs = min_refresh.as_secs();
+        self
+    }
+
+    /// The discovery document URL.
+    pub fn discovery_url(&self) -> String {
+        self.discovery_url.clone().unwrap_or_else(|| {
+            format!(
+                "{}/.well-known/openid-configuration",
+                self.issuer.trim_end_matches('/')
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_discovery_url() {
+        assert_eq!(
+            OidcConfig::okta("https://dev-1.okta.com/oauth2/default").discovery_url(),
+            "https://dev-1.okta.com/oauth2/default/.well-known/openid-configuration"
+        );
+        let auth0 = OidcConfig::auth0("example.auth0.com", "https://fabryk.example.com/");
+        assert_eq!(auth0.issuer, "https://example.auth0.com/");
+        assert_eq!(
+            auth0.discovery_url(),
+            "https://example.auth0.com/.well-known/openid-configuration"
+        );
+        assert_eq!(
+            OidcConfig::new("https://idp")
+                .with_discovery_url("https://idp/meta")
+                .discovery_url(),
+            "https://idp/meta"
+        );
+    }
+
+    #[test]
+    fn test_presets() {
+        let auth0 = OidcConfig::auth0("example.auth0.com", "https://fabryk.example.com/");
+        assert_eq!(auth0.claims.roles, ["https://fabryk.example.com/roles"]);
+        assert_eq!(auth0.claims.groups, ["https://fabryk.example.com/groups"]);
+
+        let keycloak = OidcConfig::keycloak("https://sso.example.com/", "eng");
+        assert_eq!(keycloak.issuer, "https://sso.example.com/realms/eng");
+        assert_eq!(keycloak.claims.roles, ["realm_access.roles"]);
+
+        let azure = OidcConfig::azure_ad("72f988bf");
+        assert_eq!(
+            azure.issuer,
+            "https://login.microsoftonline.com/72f988bf/v2.0"
+        );
+        assert_eq!(azure.claims.email[1], "preferred_username");
+        assert_eq!(azure.claims.roles, ["roles"]);
+    }
+
+    #[test]
+    fn test_deserialize_defaults() {
+        let config: OidcConfig = serde_json::from_value(serde_json::json!({
+            "issuer": "https://idp.example.com",
+            "audiences": ["api://fabryk"],
+            "claims": { "roles": ["roles"] },
+        }))
+        .unwrap();
+        assert_eq!(config.audiences, ["api://fabryk"]);
+        assert_eq!(config.claims.email, ["email"]);
+        assert_eq!(config.claims.roles, ["roles"]);
+        assert_eq!(config.jwks_cache_secs, 3600);
+        assert_eq!(config.jwks_min_refresh_secs, 30);
+    }
+}
@@ -0,0 +1,102 @@
+//! OIDC provider discovery.
+
+use fabryk_auth::AuthError;
+use jsonwebtoken::Algorithm;
+use serde::{Deserialize, Serialize};
+
+/// The parts of a provider's `.well-known/openid-configuration` document
+/// used for token validation.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProviderMetadata {
+    /// The provider's issuer identifier.
+    pub issuer: String,
+    /// URL of the provider's JSON Web Key Set.
+    pub jwks_uri: String,
+    /// Algorithms the provider signs ID tokens with.
+    #[serde(default)]
+    pub id_token_signing_alg_values_supported: Vec<String>,
+    /// The userinfo endpoint, if the provider has one.
+    #[serde(default)]
+    pub userinfo_endpoint: Option<String>,
+}
+
+impl ProviderMetadata {
+    /// Fetch the discovery document and check it belongs to `issuer`.
+    pub(crate) async fn fetch(
+        client: &reqwest::Client,
+        url: &str,
+        issuer: &str,
+    ) -> Result<Self, AuthError> {
+        let response = client
+            .get(url)
+            .send()
+            .await
+            .map_err(|e| AuthError::Discovery(format!("{url}: {e}")))?;
+        if !response.status().is_success() {
+            return Err(AuthError::Discovery(format!(
+                "{url}: HTTP {}",
+                response.status()
+            )));
+        }
+        let metadata: Self = response
+            .json()
+            .await
+            .map_err(|e| AuthError::Discovery(format!("{url}: {e}")))?;
+
+        // OIDC Discovery §4.3: the issuer must match the one we asked for,
+        // or a compromised document could vouch for another provider.
+        if metadata.issuer != issuer {
+            return Err(AuthError::Discovery(format!(
+                "issuer mismatch: document is for '{}', expected '{issuer}'",
+                metadata.issuer
+            )));
+        }
+        Ok(metadata)
+    }
+
+    /// Asymmetric algorithms the provider signs with; `RS256` if it lists
+    /// none.
+    ///
+    /// HMAC algorithms are never accepted: their key is the client secret,
+    /// not something published in the JWKS.
+    pub fn signing_algorithms(&self) -> Vec<Algorithm> {
+        let algorithms: Vec<Algorithm> = self
+            .id_token_signing_alg_values_supported
+            .iter()
+            .filter_map(|name| name.parse().ok())
+            .filter(|alg| !matches!(alg, Algorithm::HS256 | Algorithm::HS384 | Algorithm::HS512))
+            .collect();
+        if algorithms.is_empty() {
+            vec![Algorithm::RS256]
+        } else {
+            algorithms
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn metadata(algs: &[&str]) -> ProviderMetadata {
+        ProviderMetadata {
+            issuer: "https://idp".to_string(),
+            jwks_uri: "https://idp/keys".to_string(),
+            id_token_signing_alg_values_supported: algs.iter().map(|a| a.to_string()).collect(),
+            userinfo_endpoint: None,
+        }
+    }
+
+    #[test]
+    fn test_signing_algorithms() {
+        assert_eq!(metadata(&[]).signing_algorithms(), [Algorithm::RS256]);
+        assert_eq!(
+            metadata(&["RS256", "ES256", "HS256", "none"]).signing_algorithms(),
+            [Algorithm::RS256, Algorithm::ES256]
+        );
+        assert_eq!(
+            metadata(&["HS256"]).signing_algorithms(),
+            [Algorithm::RS256]
+        );
+    }
+}
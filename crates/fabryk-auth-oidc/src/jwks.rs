@@ -0,0 +1,152 @@
+//! Signing key cache with rotation.
+
+use std::sync::RwLock;
+use std::time::{Duration, Instant};
+
+use fabryk_auth::AuthError;
+use jsonwebtoken::jwk::{AlgorithmParameters, Jwk, JwkSet, PublicKeyUse};
+
+/// Cached signing keys with fetch timestamp.
+struct CachedKeys {
+    keys: Vec<Jwk>,
+    fetched_at: Instant,
+}
+
+/// The provider's signing keys, refreshed when they expire or when a token
+/// names a key the cache doesn't have (the provider rotated its keys).
+pub(crate) struct JwksCache {
+    cached: RwLock<Option<CachedKeys>>,
+    ttl: Duration,
+    min_refresh: Duration,
+}
+
+impl JwksCache {
+    pub(crate) fn new(ttl: Duration, min_refresh: Duration) -> Self {
+        Self {
+            cached: RwLock::new(None),
+            ttl,
+            min_refresh,
+        }
+    }
+
+    /// Find the key for a token's `kid`, fetching from `jwks_uri` as needed.
+    ///
+    /// A token without a `kid` is accepted only if the provider publishes a
+    /// single signing key.
+    pub(crate) async fn find(
+        &self,
+        client: &reqwest::Client,
+        jwks_uri: &str,
+        kid: Option<&str>,
+    ) -> Result<Jwk, AuthError> {
+        let needs_refresh = match self.lookup(kid) {
+            Lookup::Found(key) => return Ok(*key),
+            Lookup::Expired | Lookup::Empty => true,
+            Lookup::Unknown { age } => age >= self.min_refresh,
+        };
+
+        if needs_refresh {
+            log::debug!("Refreshing JWKS from {jwks_uri}");
+            self.refresh(client, jwks_uri).await?;
+            if let Lookup::Found(key) = self.lookup(kid) {
+                return Ok(*key);
+            }
+        }
+
+        Err(AuthError::NoMatchingKey(
+            kid.unwrap_or("<none>").to_string(),
+        ))
+    }
+
+    fn lookup(&self, kid: Option<&str>) -> Lookup {
+        let Ok(cache) = self.cached.read() else {
+            return Lookup::Empty;
+        };
+        let Some(cached) = cache.as_ref() else {
+            return Lookup::Empty;
+        };
+        let age = cached.fetched_at.elapsed();
+        if age > self.ttl {
+            return Lookup::Expired;
+        }
+
+        let key = match kid {
+            Some(kid) => cached
+                .keys
+                .iter()
+                .find(|k| k.common.key_id.as_deref() == Some(kid)),
+            None if cached.keys.len() == 1 => cached.keys.first(),
+            None => None,
+        };
+        match key {
+            Some(key) => Lookup::Found(Box::new(key.clone())),
+            None => Lookup::Unknown { age },
+        }
+    }
+
+    async fn refresh(&self, client: &reqwest::Client, jwks_uri: &str) -> Result<(), AuthError> {
+        let response = client
+            .get(jwks_uri)
+            .send()
+            .await
+            .map_err(|e| AuthError::JwksFetchError(e.to_string()))?;
+        if !response.status().is_success() {
+            return Err(AuthError::JwksFetchError(format!(
+                "{jwks_uri}: HTTP {}",
+                response.status()
+            )));
+        }
+        let set: JwkSet = response
+            .json()
+            .await
+            .map_err(|e| AuthError::JwksFetchError(e.to_string()))?;
+
+        let keys = set.keys.into_iter().filter(is_signing_key).collect();
+        let mut cache = self
+            .cached
+            .write()
+            .map_err(|e| AuthError::JwksFetchError(e.to_string()))?;
+        *cache = Some(CachedKeys {
+            keys,
+            fetched_at: Instant::now(),
+        });
+        Ok(())
+    }
+}
+
+enum Lookup {
+    Found(Box<Jwk>),
+    Unknown { age: Duration },
+    Expired,
+    Empty,
+}
+
+/// Public signing keys only: symmetric (`oct`) keys and encryption keys
+/// are never used to verify tokens.
+fn is_signing_key(key: &Jwk) -> bool {
+    !matches!(key.algorithm, AlgorithmParameters::OctetKey(_))
+        && !matches!(key.common.public_key_use, Some(PublicKeyUse::Encryption))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_is_signing_key() {
+        let keys: JwkSet = serde_json::from_value(json!({ "keys": [
+            { "kty": "RSA", "kid": "sig", "use": "sig", "n": "AQAB", "e": "AQAB" },
+            { "kty": "RSA", "kid": "enc", "use": "enc", "n": "AQAB", "e": "AQAB" },
+            { "kty": "oct", "kid": "secret", "k": "c2VjcmV0" },
+        ]}))
+        .unwrap();
+        let kept: Vec<_> = keys
+            .keys
+            .iter()
+            .filter(|k| is_signing_key(k))
+            .filter_map(|k| k.common.key_id.as_deref())
+            .collect();
+        assert_eq!(kept, ["sig"]);
+    }
+}
@@ -0,0 +1,40 @@
+//! Generic OpenID Connect token validation for Fabryk.
+//!
+//! Implements [`fabryk_auth::TokenValidator`] for any OIDC provider:
+//! - Provider discovery via `.well-known/openid-configuration`
+//! - JWKS caching with TTL refresh and rotation on unknown `kid`
+//! - Issuer, audience and signing algorithm validation
+//! - Configurable mapping of email, group and role claims into
+//!   [`fabryk_auth::AuthenticatedUser`]
+//!
+//! Presets cover the claim layouts of common providers:
+//! [`OidcConfig::okta`], [`OidcConfig::auth0`], [`OidcConfig::keycloak`]
+//! and [`OidcConfig::azure_ad`].
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use fabryk_auth::{AuthConfig, AuthLayer};
+//! use fabryk_auth_oidc::{OidcConfig, OidcTokenValidator};
+//!
+//! let validator = OidcTokenValidator::new(OidcConfig::keycloak(
+//!     "https://sso.example.com",
+//!     "engineering",
+//! ));
+//! validator.discover().await?; // fail fast on a bad issuer
+//!
+//! let app = axum::Router::new()
+//!     .nest_service("/mcp", server.into_http_service())
+//!     .layer(AuthLayer::new(Arc::new(validator), auth_config));
+//! ```
+
+mod claims;
+mod config;
+mod discovery;
+mod jwks;
+mod validator;
+
+pub use claims::{ClaimMapping, lookup_claim};
+pub use config::OidcConfig;
+pub use discovery::ProviderMetadata;
+pub use validator::OidcTokenValidator;
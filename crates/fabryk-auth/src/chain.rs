@@ -0,0 +1,172 @@
+//! Trying several token validators in order.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use crate::{AuthConfig, AuthError, AuthenticatedUser, TokenValidator};
+
+/// A validator that tries several validators in order.
+///
+/// Each validator either handles the token — accepting or rejecting it —
+/// or passes with [`AuthError::UnsupportedToken`]. The first validator
+/// that handles the token decides; the rest are never called, so a
+/// rejected API key is not sent on to an identity provider.
+///
+/// Put validators that recognise their tokens cheaply first (API keys,
+/// OIDC providers matched by issuer) and catch-all validators last.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let validator = ChainValidator::new()
+///     .with(ApiKeyValidator::new(store))
+///     .with(OidcTokenValidator::new(OidcConfig::okta(issuer)))
+///     .with(GoogleTokenValidator::new(jwks_url));
+/// let layer = AuthLayer::new(Arc::new(validator), auth_config);
+/// ```
+#[derive(Clone, Default)]
+pub struct ChainValidator {
+    validators: Vec<Arc<dyn TokenValidator>>,
+}
+
+impl ChainValidator {
+    /// Create an empty chain. It rejects every token until validators are
+    /// added.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a validator.
+    pub fn with<V: TokenValidator>(self, validator: V) -> Self {
+        self.with_shared(Arc::new(validator))
+    }
+
+    /// Append a validator that is shared elsewhere.
+    pub fn with_shared(mut self, validator: Arc<dyn TokenValidator>) -> Self {
+        self.validators.push(validator);
+        self
+    }
+
+    /// Number of validators in the chain.
+    pub fn len(&self) -> usize {
+        self.validators.len()
+    }
+
+    /// Whether the chain has no validators.
+    pub fn is_empty(&self) -> bool {
+        self.validators.is_empty()
+    }
+}
+
+impl std::fmt::Debug for ChainValidator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ChainValidator")
+            .field("validators", &self.validators.len())
+            .finish()
+    }
+}
+
+impl TokenValidator for ChainValidator {
+    fn validate(
+        &self,
+        token: &str,
+        config: &AuthConfig,
+    ) -> Pin<Box<dyn Future<Output = Result<AuthenticatedUser, AuthError>> + Send + '_>> {
+        let token = token.to_string();
+        let config = config.clone();
+        Box::pin(async move {
+            for validator in &self.validators {
+                match validator.validate(&token, &config).await {
+                    Err(AuthError::UnsupportedToken) => continue,
+                    result => return result,
+                }
+            }
+            Err(AuthError::UnsupportedToken)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Accepts tokens starting with its prefix and rejects `<prefix>bad`.
+    struct PrefixValidator {
+        prefix: &'static str,
+        calls: Arc<AtomicUsize>,
+    }
+
+    impl PrefixValidator {
+        fn new(prefix: &'static str) -> (Self, Arc<AtomicUsize>) {
+            let calls = Arc::new(AtomicUsize::new(0));
+            (
+                Self {
+                    prefix,
+                    calls: calls.clone(),
+                },
+                calls,
+            )
+        }
+    }
+
+    impl TokenValidator for PrefixValidator {
+        fn validate(
+            &self,
+            token: &str,
+            _config: &AuthConfig,
+        ) -> Pin<Box<dyn Future<Output = Result<AuthenticatedUser, AuthError>> + Send + '_>>
+        {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            let result = match token.strip_prefix(self.prefix) {
+                None => Err(AuthError::UnsupportedToken),
+                Some("bad") => Err(AuthError::InvalidSignature("bad token".to_string())),
+                Some(name) => Ok(AuthenticatedUser::new(format!("{name}@banyan.com"), name)),
+            };
+            Box::pin(async move { result })
+        }
+    }
+
+    async fn validate(chain: &ChainValidator, token: &str) -> Result<AuthenticatedUser, AuthError> {
+        chain.validate(token, &AuthConfig::default()).await
+    }
+
+    #[tokio::test]
+    async fn test_first_handling_validator_decides() {
+        let (keys, key_calls) = PrefixValidator::new("key_");
+        let (idp, idp_calls) = PrefixValidator::new("");
+        let chain = ChainValidator::new().with(keys).with(idp);
+        assert_eq!(chain.len(), 2);
+
+        let user = validate(&chain, "key_ci").await.unwrap();
+        assert_eq!(user.subject, "ci");
+        assert_eq!(idp_calls.load(Ordering::SeqCst), 0);
+
+        let user = validate(&chain, "alice").await.unwrap();
+        assert_eq!(user.email, "alice@banyan.com");
+        assert_eq!(key_calls.load(Ordering::SeqCst), 2);
+
+        // A rejected key is not passed on.
+        let result = validate(&chain, "key_bad").await;
+        assert!(matches!(result, Err(AuthError::InvalidSignature(_))));
+        assert_eq!(idp_calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_unsupported_by_all() {
+        let (keys, _) = PrefixValidator::new("key_");
+        let chain = ChainValidator::new().with(keys);
+        assert!(matches!(
+            validate(&chain, "jwt").await,
+            Err(AuthError::UnsupportedToken)
+        ));
+
+        let empty = ChainValidator::new();
+        assert!(empty.is_empty());
+        assert!(matches!(
+            validate(&empty, "anything").await,
+            Err(AuthError::UnsupportedToken)
+        ));
+    }
+}
@@ -35,6 +35,20 @@ pub enum AuthError {
     #[error("failed to fetch JWKS: {0}")]
     JwksFetchError(String),
 
+    /// The token is not of a kind this validator handles (e.g. not an API
+    /// key, or issued by another provider). [`ChainValidator`](crate::ChainValidator)
+    /// moves on to the next validator.
+    #[error("unsupported token")]
+    UnsupportedToken,
+
+    /// The credential store could not be read or updated.
+    #[error("credential store error: {0}")]
+    Store(String),
+
+    /// Failed to fetch or accept the provider's OIDC discovery document.
+    #[error("OIDC discovery failed: {0}")]
+    Discovery(String),
+
     /// No key in the JWKS matches the token's kid.
     #[error("no matching key for kid '{0}'")]
     NoMatchingKey(String),
@@ -53,6 +67,7 @@ impl AuthError {
                 | AuthError::InvalidDomain { .. }
                 | AuthError::MissingEmail
                 | AuthError::NoMatchingKey(_)
+                | AuthError::UnsupportedToken
         )
     }
 }
@@ -85,5 +100,8 @@ mod tests {
         assert!(AuthError::Expired.is_client_error());
         // JwksFetchError is a server-side issue, not a client error
         assert!(!AuthError::JwksFetchError("err".into()).is_client_error());
+        assert!(!AuthError::Discovery("err".into()).is_client_error());
+        assert!(AuthError::UnsupportedToken.is_client_error());
+        assert!(!AuthError::Store("err".into()).is_client_error());
     }
 }
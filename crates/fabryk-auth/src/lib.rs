@@ -3,14 +3,17 @@
 //! Provides:
 //! - [`AuthenticatedUser`] — Identity extracted from a validated token
 //! - [`TokenValidator`] — Trait for async token validation (implement per provider)
+//! - [`ChainValidator`] — Tries several validators in order
 //! - [`AuthLayer`] / [`AuthService`] — Tower middleware parameterised over `TokenValidator`
 //! - [`AuthConfig`] — Configuration for the auth layer
 //! - [`AuthError`] — Auth-specific error types
 
+mod chain;
 mod error;
 mod middleware;
 mod user;
 
+pub use chain::ChainValidator;
 pub use error::AuthError;
 pub use middleware::{AuthLayer, AuthService};
 pub use user::{AuthenticatedUser, email_from_parts, user_from_parts};
@@ -28,9 +31,11 @@ pub struct AuthConfig {
 
 /// Trait for validating tokens and extracting user identity.
 ///
-/// Implement this for each identity provider (Google, Auth0, etc.).
+/// Implement this for each identity provider (Google, generic OIDC, etc.).
 /// The middleware calls `validate()` with the bearer token and returns
-/// the authenticated user on success.
+/// the authenticated user on success. Validators that only handle some
+/// tokens return [`AuthError::UnsupportedToken`] for the rest, so they can
+/// be combined in a [`ChainValidator`].
 pub trait TokenValidator: Send + Sync + 'static {
     /// Validate a token and return the authenticated user.
     fn validate(
@@ -192,10 +192,7 @@ mod tests {
             let token = token.to_string();
             Box::pin(async move {
                 if token == "valid-token" {
-                    Ok(AuthenticatedUser {
-                        email: "alice@banyan.com".to_string(),
-                        subject: "sub_123".to_string(),
-                    })
+                    Ok(AuthenticatedUser::new("alice@banyan.com", "sub_123"))
                 } else {
                     Err(AuthError::InvalidSignature("bad token".to_string()))
                 }
@@ -4,12 +4,74 @@
 ///
 /// Stored in HTTP request extensions by the auth middleware and propagated
 /// through rmcp into MCP tool handler context via `Extension<Parts>`.
-#[derive(Debug, Clone)]
+///
+/// `issuer`, `groups`, `roles` and `scopes` are filled in by validators
+/// whose credentials carry them (see `fabryk-auth-oidc` and
+/// `fabryk-auth-apikey`); they are empty otherwise.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub struct AuthenticatedUser {
     /// The user's email address.
     pub email: String,
     /// The user's unique subject identifier (from the `sub` claim).
     pub subject: String,
+    /// The token issuer (the `iss` claim), if known.
+    pub issuer: Option<String>,
+    /// Groups the user belongs to, as asserted by the identity provider.
+    pub groups: Vec<String>,
+    /// Roles granted to the user, as asserted by the identity provider.
+    pub roles: Vec<String>,
+    /// Scopes the credential is limited to.
+    pub scopes: Vec<String>,
+}
+
+impl AuthenticatedUser {
+    /// Create a user with an email and subject and no groups or roles.
+    pub fn new(email: impl Into<String>, subject: impl Into<String>) -> Self {
+        Self {
+            email: email.into(),
+            subject: subject.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Set the token issuer.
+    pub fn with_issuer(mut self, issuer: impl Into<String>) -> Self {
+        self.issuer = Some(issuer.into());
+        self
+    }
+
+    /// Set the user's groups.
+    pub fn with_groups(mut self, groups: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.groups = groups.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Set the user's roles.
+    pub fn with_roles(mut self, roles: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.roles = roles.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Set the credential's scopes.
+    pub fn with_scopes(mut self, scopes: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.scopes = scopes.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Whether the user belongs to `group`.
+    pub fn has_group(&self, group: &str) -> bool {
+        self.groups.iter().any(|g| g == group)
+    }
+
+    /// Whether the user has been granted `role`.
+    pub fn has_role(&self, role: &str) -> bool {
+        self.roles.iter().any(|r| r == role)
+    }
+
+    /// Whether the credential carries `scope`.
+    pub fn has_scope(&self, scope: &str) -> bool {
+        self.scopes.iter().any(|s| s == scope)
+    }
 }
 
 /// Extract the `AuthenticatedUser` from HTTP request `Parts`, if present.
@@ -34,10 +96,9 @@ mod tests {
 
     fn parts_with_user() -> http::request::Parts {
         let (mut parts, _body) = http::Request::new(()).into_parts();
-        parts.extensions.insert(AuthenticatedUser {
-            email: "alice@banyan.com".to_string(),
-            subject: "sub_123".to_string(),
-        });
+        parts
+            .extensions
+            .insert(AuthenticatedUser::new("alice@banyan.com", "sub_123"));
         parts
     }
 
@@ -74,12 +135,25 @@ mod tests {
 
     #[test]
     fn test_authenticated_user_clone() {
-        let user = AuthenticatedUser {
-            email: "bob@banyan.com".to_string(),
-            subject: "sub_456".to_string(),
-        };
+        let user = AuthenticatedUser::new("bob@banyan.com", "sub_456");
         let cloned = user.clone();
         assert_eq!(cloned.email, "bob@banyan.com");
         assert_eq!(cloned.subject, "sub_456");
     }
+
+    #[test]
+    fn test_authenticated_user_groups_and_roles() {
+        let user = AuthenticatedUser::new("carol@banyan.com", "sub_789")
+            .with_issuer("https://idp.banyan.com")
+            .with_groups(["engineering", "oncall"])
+            .with_roles(["admin"])
+            .with_scopes(["mcp:read"]);
+        assert_eq!(user.issuer.as_deref(), Some("https://idp.banyan.com"));
+        assert!(user.has_group("oncall"));
+        assert!(!user.has_group("sales"));
+        assert!(user.has_role("admin"));
+        assert!(user.has_scope("mcp:read"));
+        assert!(!user.has_scope("mcp:write"));
+        assert!(!AuthenticatedUser::new("d@banyan.com", "s").has_role("admin"));
+    }
 }
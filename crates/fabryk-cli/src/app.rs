@@ -4,10 +4,14 @@
 //! with their own [`ConfigProvider`] implementation.
 
 use crate::cli::{BaseCommand, CliArgs, GraphSubcommand};
-use crate::config::FabrykConfig;
-use crate::{config_handlers, graph_handlers};
+use crate::config::{FabrykConfig, ServiceSettings};
+use crate::extractors::Extractors;
+use crate::index_handlers::IndexOptions;
+use crate::serve_handlers::ServeOptions;
+use crate::{config_handlers, graph_handlers, index_handlers, serve_handlers};
 use fabryk_core::Result;
 use fabryk_core::traits::ConfigProvider;
+use fabryk_graph::GraphExtractor;
 use std::sync::Arc;
 use tracing_subscriber::EnvFilter;
 
@@ -17,18 +21,31 @@ use tracing_subscriber::EnvFilter;
 
 /// Generic CLI application parameterized over a config provider.
 ///
-/// Domain applications create a `FabrykCli<MyConfig>` and call `run()`.
+/// Domain applications create a `FabrykCli<MyConfig>`, register their
+/// extractors for `serve` and `index`, and call `run()`:
+///
+/// ```rust,ignore
+/// let cli = FabrykCli::from_args("music-theory", &args)?
+///     .with_graph_extractor(MusicTheoryExtractor::new())
+///     .with_document_extractor(MusicTheoryDocuments);
+/// cli.run(args).await
+/// ```
 pub struct FabrykCli<C: ConfigProvider> {
     name: String,
     config: Arc<C>,
     version: String,
+    settings: ServiceSettings,
+    extractors: Extractors,
 }
 
 impl FabrykCli<FabrykConfig> {
     /// Create from CLI args, loading config from file/env.
+    ///
+    /// The `serve` and `index` settings come from the same config.
     pub fn from_args(name: impl Into<String>, args: &CliArgs) -> Result<Self> {
         let config = FabrykConfig::load(args.config.as_deref())?;
-        Ok(Self::new(name, config))
+        let settings = ServiceSettings::from(&config);
+        Ok(Self::new(name, config).with_settings(settings))
     }
 }
 
@@ -39,6 +56,8 @@ impl<C: ConfigProvider> FabrykCli<C> {
             name: name.into(),
             config: Arc::new(config),
             version: env!("CARGO_PKG_VERSION").to_string(),
+            settings: ServiceSettings::default(),
+            extractors: Extractors::new(),
         }
     }
 
@@ -48,14 +67,53 @@ impl<C: ConfigProvider> FabrykCli<C> {
         self
     }
 
+    /// Set the server, TLS, OAuth and backend settings used by `serve`
+    /// and `index` (default: [`ServiceSettings::default`]).
+    pub fn with_settings(mut self, settings: ServiceSettings) -> Self {
+        self.settings = settings;
+        self
+    }
+
+    /// Set the extractor used to build the knowledge graph.
+    pub fn with_graph_extractor<E: GraphExtractor + 'static>(mut self, extractor: E) -> Self {
+        self.extractors = self.extractors.with_graph(extractor);
+        self
+    }
+
+    /// Set the extractor used to build the full-text index.
+    #[cfg(feature = "fts-tantivy")]
+    pub fn with_document_extractor<E: fabryk_fts::DocumentExtractor + 'static>(
+        mut self,
+        extractor: E,
+    ) -> Self {
+        self.extractors = self.extractors.with_document(extractor);
+        self
+    }
+
+    /// Set the extractor used to build the vector index.
+    #[cfg(feature = "vector")]
+    pub fn with_vector_extractor<E: fabryk_vector::VectorExtractor + 'static>(
+        mut self,
+        extractor: E,
+    ) -> Self {
+        self.extractors = self.extractors.with_vector(extractor);
+        self
+    }
+
     /// Get a reference to the config provider.
     pub fn config(&self) -> &C {
         &self.config
     }
 
+    /// Get the `serve` and `index` settings.
+    pub fn settings(&self) -> &ServiceSettings {
+        &self.settings
+    }
+
     /// Initialise tracing-based logging.
     ///
     /// Uses `RUST_LOG` env var if set, otherwise defaults based on verbosity flags.
+    /// Logs go to stderr so they never mix with `serve --stdio` traffic.
     pub fn init_logging(&self, verbose: bool, quiet: bool) {
         let filter = if std::env::var("RUST_LOG").is_ok() {
             EnvFilter::from_default_env()
@@ -68,7 +126,10 @@ impl<C: ConfigProvider> FabrykCli<C> {
         };
 
         // Ignore error if a subscriber is already set (e.g. in tests).
-        let _ = tracing_subscriber::fmt().with_env_filter(filter).try_init();
+        let _ = tracing_subscriber::fmt()
+            .with_env_filter(filter)
+            .with_writer(std::io::stderr)
+            .try_init();
     }
 
     /// Run the CLI with the given arguments.
@@ -84,19 +145,25 @@ impl<C: ConfigProvider> FabrykCli<C> {
                 println!("{}: healthy", self.name);
                 Ok(())
             }
-            Some(BaseCommand::Serve { port }) => {
-                println!("Starting {} server on port {}...", self.name, port);
-                // Placeholder — domain applications override serve behaviour.
-                Ok(())
+            Some(BaseCommand::Serve { port, stdio }) => {
+                serve_handlers::handle_serve(
+                    &self.name,
+                    &self.version,
+                    &*self.config,
+                    &self.settings,
+                    &self.extractors,
+                    ServeOptions { port, stdio },
+                )
+                .await
             }
             Some(BaseCommand::Index { force, check }) => {
-                if check {
-                    println!("Checking index freshness...");
-                } else {
-                    println!("Building index{}...", if force { " (forced)" } else { "" });
-                }
-                // Placeholder — domain applications override index behaviour.
-                Ok(())
+                index_handlers::handle_index(
+                    &*self.config,
+                    &self.settings,
+                    &self.extractors,
+                    IndexOptions { force, check },
+                )
+                .await
             }
             Some(BaseCommand::Graph(graph_cmd)) => self.handle_graph(graph_cmd.command).await,
             Some(BaseCommand::Config(config_cmd)) => {
@@ -106,6 +173,19 @@ impl<C: ConfigProvider> FabrykCli<C> {
             Some(BaseCommand::Vectordb(cmd)) => {
                 crate::vectordb_handlers::handle_vectordb_command(cmd.command)
             }
+            #[cfg(feature = "audit")]
+            Some(BaseCommand::Audit(cmd)) => {
+                crate::audit_handlers::handle_audit_command(cmd.command)
+            }
+            #[cfg(feature = "api-keys")]
+            Some(BaseCommand::Keys(cmd)) => {
+                crate::keys_handlers::handle_keys_command(
+                    &*self.config,
+                    &self.settings,
+                    cmd.command,
+                )
+                .await
+            }
             None => {
                 println!("{} {} — use --help for usage", self.name, self.version);
                 Ok(())
@@ -220,28 +300,54 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    fn content_config() -> (tempfile::TempDir, TestConfig) {
+        let dir = tempfile::TempDir::new().unwrap();
+        let content = dir.path().join("concepts");
+        std::fs::create_dir_all(&content).unwrap();
+        std::fs::write(
+            content.join("a.md"),
+            "---\ntitle: A\ncategory: basics\n---\nBody",
+        )
+        .unwrap();
+        let config = TestConfig {
+            base: dir.path().to_path_buf(),
+        };
+        (dir, config)
+    }
+
     #[tokio::test]
-    async fn test_run_serve_command() {
-        let cli = FabrykCli::new("test-app", test_config());
+    async fn test_run_serve_command_validates_tls() {
+        let mut settings = ServiceSettings::default();
+        settings.tls.cert_path = Some("/nonexistent/cert.pem".into());
+        let cli = FabrykCli::new("test-app", test_config()).with_settings(settings);
         let args = CliArgs::parse_from(["test", "serve", "--port", "9090"]);
         let result = cli.run(args).await;
-        assert!(result.is_ok());
+        assert!(result.is_err());
     }
 
     #[tokio::test]
     async fn test_run_index_command() {
-        let cli = FabrykCli::new("test-app", test_config());
+        let (dir, config) = content_config();
+        let cli =
+            FabrykCli::new("test-app", config).with_graph_extractor(fabryk_graph::MockExtractor);
         let args = CliArgs::parse_from(["test", "index"]);
         let result = cli.run(args).await;
         assert!(result.is_ok());
+        assert!(dir.path().join("data/graphs/graph.json").exists());
     }
 
     #[tokio::test]
     async fn test_run_index_check() {
-        let cli = FabrykCli::new("test-app", test_config());
-        let args = CliArgs::parse_from(["test", "index", "--check"]);
-        let result = cli.run(args).await;
-        assert!(result.is_ok());
+        let (_dir, config) = content_config();
+        let cli =
+            FabrykCli::new("test-app", config).with_graph_extractor(fabryk_graph::MockExtractor);
+        let check = || CliArgs::parse_from(["test", "index", "--check"]);
+        assert!(cli.run(check()).await.is_err());
+
+        cli.run(CliArgs::parse_from(["test", "index"]))
+            .await
+            .unwrap();
+        assert!(cli.run(check()).await.is_ok());
     }
 
     #[test]
@@ -0,0 +1,184 @@
+//! Handler functions for audit CLI commands.
+//!
+//! Provides `audit usage`, which reads a JSONL audit log written by
+//! [`fabryk_mcp_audit::JsonlSink`] and prints per-user tool usage.
+
+use crate::cli::AuditAction;
+use chrono::{DateTime, NaiveDate, Utc};
+use fabryk_core::{Error, Result};
+use fabryk_mcp_audit::{UsageFilter, UsageReport, read_jsonl};
+
+// ============================================================================
+// Command dispatch
+// ============================================================================
+
+/// Handle an audit subcommand.
+pub fn handle_audit_command(action: AuditAction) -> Result<()> {
+    match action {
+        AuditAction::Usage {
+            log,
+            user,
+            since,
+            until,
+            json,
+        } => {
+            let filter = UsageFilter {
+                user,
+                since: since.as_deref().map(parse_time).transpose()?,
+                until: until.as_deref().map(parse_time).transpose()?,
+            };
+            cmd_audit_usage(&log, &filter, json)
+        }
+    }
+}
+
+// ============================================================================
+// Generic command handlers
+// ============================================================================
+
+/// Print per-user usage from the audit log at `log`.
+pub fn cmd_audit_usage(log: &str, filter: &UsageFilter, json: bool) -> Result<()> {
+    let records =
+        read_jsonl(log).map_err(|e| Error::operation(format!("Cannot read {log}: {e}")))?;
+    let report = UsageReport::from_records(&records, filter);
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        print!("{}", format_report(&report));
+    }
+    Ok(())
+}
+
+/// Render a usage report as a plain-text table.
+pub fn format_report(report: &UsageReport) -> String {
+    if report.users.is_empty() {
+        return "No tool calls recorded.\n".to_string();
+    }
+
+    let width = report
+        .users
+        .iter()
+        .map(|u| u.user.len())
+        .max()
+        .unwrap_or(0)
+        .max("USER".len());
+
+    let mut out = format!(
+        "{:<width$}  {:>7}  {:>6}  {:>8}  TOP TOOLS\n",
+        "USER", "CALLS", "ERRORS", "MEAN MS"
+    );
+    for usage in &report.users {
+        let mut tools: Vec<_> = usage.tools.iter().collect();
+        tools.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
+        let top: Vec<String> = tools
+            .iter()
+            .take(3)
+            .map(|(tool, calls)| format!("{tool} ({calls})"))
+            .collect();
+        out.push_str(&format!(
+            "{:<width$}  {:>7}  {:>6}  {:>8}  {}\n",
+            usage.user,
+            usage.calls,
+            usage.errors,
+            usage.mean_latency_ms(),
+            top.join(", ")
+        ));
+    }
+    out.push_str(&format!(
+        "\n{} calls by {} users\n",
+        report.total_calls(),
+        report.users.len()
+    ));
+    out
+}
+
+/// Parse an RFC 3339 timestamp, or a date meaning midnight UTC.
+fn parse_time(value: &str) -> Result<DateTime<Utc>> {
+    if let Ok(time) = DateTime::parse_from_rfc3339(value) {
+        return Ok(time.with_timezone(&Utc));
+    }
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .map(|date| date.and_time(chrono::NaiveTime::MIN).and_utc())
+        .map_err(|_| {
+            Error::parse(format!(
+                "Invalid time '{value}': expected RFC 3339 or YYYY-MM-DD"
+            ))
+        })
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fabryk_mcp_audit::AuditRecord;
+
+    fn record(user: &str, tool: &str, day: u32) -> AuditRecord {
+        AuditRecord {
+            timestamp: parse_time(&format!("2026-10-{day:02}")).unwrap(),
+            user: Some(user.to_string()),
+            subject: None,
+            tool: tool.to_string(),
+            arguments: serde_json::json!({}),
+            latency_ms: 40,
+            result_bytes: 100,
+            error: None,
+        }
+    }
+
+    #[test]
+    fn test_parse_time() {
+        assert_eq!(
+            parse_time("2026-10-01").unwrap().to_rfc3339(),
+            "2026-10-01T00:00:00+00:00"
+        );
+        assert_eq!(
+            parse_time("2026-10-01T12:30:00+02:00")
+                .unwrap()
+                .to_rfc3339(),
+            "2026-10-01T10:30:00+00:00"
+        );
+        assert!(parse_time("last week").is_err());
+    }
+
+    #[test]
+    fn test_format_report() {
+        let records = vec![
+            record("alice@example.com", "search", 1),
+            record("alice@example.com", "search", 2),
+            record("alice@example.com", "graph_validate", 2),
+            record("bob@example.com", "search", 3),
+        ];
+        let report = UsageReport::from_records(&records, &UsageFilter::default());
+        let text = format_report(&report);
+
+        let lines: Vec<&str> = text.lines().collect();
+        assert!(lines[0].starts_with("USER"));
+        assert!(lines[1].starts_with("alice@example.com"));
+        assert!(lines[1].contains("search (2), graph_validate (1)"));
+        assert!(lines[2].starts_with("bob@example.com"));
+        assert!(text.ends_with("4 calls by 2 users\n"));
+        assert_eq!(
+            format_report(&UsageReport::default()),
+            "No tool calls recorded.\n"
+        );
+    }
+
+    #[test]
+    fn test_cmd_audit_usage() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("audit.jsonl");
+        let line = serde_json::to_string(&record("alice@example.com", "search", 1)).unwrap();
+        std::fs::write(&path, format!("{line}\n")).unwrap();
+        let log = path.to_str().unwrap();
+
+        assert!(cmd_audit_usage(log, &UsageFilter::default(), false).is_ok());
+        assert!(cmd_audit_usage(log, &UsageFilter::default(), true).is_ok());
+        assert!(
+            cmd_audit_usage("/nonexistent/audit.jsonl", &UsageFilter::default(), false).is_err()
+        );
+    }
+}
@@ -37,9 +37,13 @@ pub struct CliArgs {
 pub enum BaseCommand {
     /// Start the MCP server.
     Serve {
-        /// Port to listen on.
-        #[arg(short, long, default_value = "3000")]
-        port: u16,
+        /// Port to listen on (default: `server.port` from config).
+        #[arg(short, long)]
+        port: Option<u16>,
+
+        /// Serve over stdio instead of HTTP.
+        #[arg(long, conflicts_with = "port")]
+        stdio: bool,
     },
 
     /// Build or refresh the content index.
@@ -68,6 +72,14 @@ pub enum BaseCommand {
     /// Vector database operations.
     #[cfg(feature = "vector-fastembed")]
     Vectordb(VectordbCommand),
+
+    /// Audit log operations.
+    #[cfg(feature = "audit")]
+    Audit(AuditCommand),
+
+    /// API key operations.
+    #[cfg(feature = "api-keys")]
+    Keys(KeysCommand),
 }
 
 /// Config-specific subcommands.
@@ -195,6 +207,93 @@ pub enum VectordbAction {
     },
 }
 
+// ============================================================================
+// Audit commands (feature-gated)
+// ============================================================================
+
+/// Audit-specific subcommands.
+#[cfg(feature = "audit")]
+#[derive(Parser, Debug)]
+pub struct AuditCommand {
+    /// Audit subcommand to execute.
+    #[command(subcommand)]
+    pub command: AuditAction,
+}
+
+/// Available audit subcommands.
+#[cfg(feature = "audit")]
+#[derive(Subcommand, Debug)]
+pub enum AuditAction {
+    /// Show per-user tool usage from a JSONL audit log.
+    Usage {
+        /// Path to the JSONL audit log.
+        #[arg(short, long, env = "FABRYK_AUDIT_LOG")]
+        log: String,
+
+        /// Only show this user (email, or "anonymous").
+        #[arg(short, long)]
+        user: Option<String>,
+
+        /// Only count calls at or after this time (RFC 3339 or YYYY-MM-DD).
+        #[arg(long)]
+        since: Option<String>,
+
+        /// Only count calls before this time (RFC 3339 or YYYY-MM-DD).
+        #[arg(long)]
+        until: Option<String>,
+
+        /// Print the report as JSON.
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+// ============================================================================
+// API key commands (feature-gated)
+// ============================================================================
+
+/// API key subcommands.
+#[cfg(feature = "api-keys")]
+#[derive(Parser, Debug)]
+pub struct KeysCommand {
+    /// Keys subcommand to execute.
+    #[command(subcommand)]
+    pub command: KeysAction,
+}
+
+/// Available API key subcommands.
+#[cfg(feature = "api-keys")]
+#[derive(Subcommand, Debug)]
+pub enum KeysAction {
+    /// Mint a new API key and print its token.
+    Mint {
+        /// Who the key acts as (e.g., "ci-bot@example.com").
+        #[arg(short, long)]
+        owner: String,
+
+        /// Scope to grant (repeatable).
+        #[arg(short, long = "scope")]
+        scopes: Vec<String>,
+
+        /// Lifetime of the key (e.g., "90d", "12h"). Omit for no expiry.
+        #[arg(long)]
+        expires_in: Option<String>,
+    },
+
+    /// List API keys.
+    List {
+        /// Print the keys as JSON.
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Revoke an API key.
+    Revoke {
+        /// ID of the key to revoke.
+        id: String,
+    },
+}
+
 // ============================================================================
 // CliExtension trait
 // ============================================================================
@@ -256,7 +355,10 @@ mod tests {
     fn test_serve_command() {
         let args = CliArgs::parse_from(["test", "serve"]);
         match args.command {
-            Some(BaseCommand::Serve { port }) => assert_eq!(port, 3000),
+            Some(BaseCommand::Serve { port, stdio }) => {
+                assert_eq!(port, None);
+                assert!(!stdio);
+            }
             _ => panic!("Expected Serve command"),
         }
     }
@@ -265,11 +367,24 @@ mod tests {
     fn test_serve_command_custom_port() {
         let args = CliArgs::parse_from(["test", "serve", "--port", "8080"]);
         match args.command {
-            Some(BaseCommand::Serve { port }) => assert_eq!(port, 8080),
+            Some(BaseCommand::Serve { port, .. }) => assert_eq!(port, Some(8080)),
             _ => panic!("Expected Serve command"),
         }
     }
 
+    #[test]
+    fn test_serve_command_stdio() {
+        let args = CliArgs::parse_from(["test", "serve", "--stdio"]);
+        match args.command {
+            Some(BaseCommand::Serve { port, stdio }) => {
+                assert_eq!(port, None);
+                assert!(stdio);
+            }
+            _ => panic!("Expected Serve command"),
+        }
+        assert!(CliArgs::try_parse_from(["test", "serve", "--stdio", "--port", "8080"]).is_err());
+    }
+
     #[test]
     fn test_index_command() {
         let args = CliArgs::parse_from(["test", "index"]);
@@ -505,6 +620,94 @@ mod tests {
         }
     }
 
+    // ------------------------------------------------------------------------
+    // Audit command tests (feature-gated)
+    // ------------------------------------------------------------------------
+
+    #[cfg(feature = "audit")]
+    #[test]
+    fn test_audit_usage_command() {
+        let args = CliArgs::parse_from([
+            "test",
+            "audit",
+            "usage",
+            "--log",
+            "audit.jsonl",
+            "--user",
+            "alice@example.com",
+            "--since",
+            "2026-10-01",
+        ]);
+        match args.command {
+            Some(BaseCommand::Audit(AuditCommand {
+                command:
+                    AuditAction::Usage {
+                        log,
+                        user,
+                        since,
+                        until,
+                        json,
+                    },
+            })) => {
+                assert_eq!(log, "audit.jsonl");
+                assert_eq!(user.as_deref(), Some("alice@example.com"));
+                assert_eq!(since.as_deref(), Some("2026-10-01"));
+                assert!(until.is_none());
+                assert!(!json);
+            }
+            _ => panic!("Expected Audit Usage command"),
+        }
+    }
+
+    // ------------------------------------------------------------------------
+    // Keys command tests (feature-gated)
+    // ------------------------------------------------------------------------
+
+    #[cfg(feature = "api-keys")]
+    #[test]
+    fn test_keys_mint_command() {
+        let args = CliArgs::parse_from([
+            "test",
+            "keys",
+            "mint",
+            "--owner",
+            "ci-bot@example.com",
+            "--scope",
+            "mcp:read",
+            "-s",
+            "graph",
+            "--expires-in",
+            "90d",
+        ]);
+        match args.command {
+            Some(BaseCommand::Keys(KeysCommand {
+                command:
+                    KeysAction::Mint {
+                        owner,
+                        scopes,
+                        expires_in,
+                    },
+            })) => {
+                assert_eq!(owner, "ci-bot@example.com");
+                assert_eq!(scopes, ["mcp:read", "graph"]);
+                assert_eq!(expires_in.as_deref(), Some("90d"));
+            }
+            _ => panic!("Expected Keys Mint command"),
+        }
+    }
+
+    #[cfg(feature = "api-keys")]
+    #[test]
+    fn test_keys_revoke_command() {
+        let args = CliArgs::parse_from(["test", "keys", "revoke", "00ff00ff00ff00ff"]);
+        match args.command {
+            Some(BaseCommand::Keys(KeysCommand {
+                command: KeysAction::Revoke { id },
+            })) => assert_eq!(id, "00ff00ff00ff00ff"),
+            _ => panic!("Expected Keys Revoke command"),
+        }
+    }
+
     // ------------------------------------------------------------------------
     // Vectordb command tests (feature-gated)
     // ------------------------------------------------------------------------
@@ -10,11 +10,19 @@
 //! 3. XDG default: `~/.config/fabryk/config.toml`
 //! 4. Built-in defaults
 
+use crate::config_sections::{OAuthConfig, ScopeConfig, TlsConfig};
 use confyg::{Confygery, env};
+#[cfg(feature = "api-keys")]
+use fabryk_auth_apikey::ApiKeyConfig;
+use fabryk_core::store::{ContentStore, FsContentStore};
 use fabryk_core::traits::ConfigProvider;
 use fabryk_core::{Error, Result};
+use fabryk_fts::SearchConfig;
+#[cfg(feature = "vector")]
+use fabryk_vector::VectorConfig;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
+use std::sync::Arc;
 
 // ============================================================================
 // Configuration structs
@@ -38,21 +46,98 @@ pub struct FabrykConfig {
 
     /// Server configuration.
     pub server: ServerConfig,
+
+    /// Full-text search configuration.
+    pub fts: SearchConfig,
+
+    /// TLS configuration for the HTTP server.
+    pub tls: TlsConfig,
+
+    /// OAuth2 configuration for the HTTP server.
+    pub oauth: OAuthConfig,
+
+    /// Tools each credential scope may call over HTTP.
+    pub scopes: ScopeConfig,
+
+    /// Vector search configuration.
+    #[cfg(feature = "vector")]
+    pub vector: VectorConfig,
+
+    /// API keys for machine clients of the HTTP server.
+    #[cfg(feature = "api-keys")]
+    pub api_keys: ApiKeyConfig,
+}
+
+/// Settings used by the `serve` and `index` commands.
+///
+/// [`FabrykCli::from_args`](crate::FabrykCli::from_args) takes these from
+/// [`FabrykConfig`]. Domain applications with their own config type build
+/// them from their own sections and pass them to
+/// [`FabrykCli::with_settings`](crate::FabrykCli::with_settings).
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ServiceSettings {
+    /// Server bind address.
+    pub server: ServerConfig,
+
+    /// Graph output location.
+    pub graph: GraphConfig,
+
+    /// Full-text search backend and index location.
+    pub fts: SearchConfig,
+
+    /// TLS certificate and key for HTTPS.
+    pub tls: TlsConfig,
+
+    /// OAuth2 settings for the HTTP transport.
+    pub oauth: OAuthConfig,
+
+    /// Tools each credential scope may call over HTTP.
+    pub scopes: ScopeConfig,
+
+    /// Embedding provider and vector cache location.
+    #[cfg(feature = "vector")]
+    pub vector: VectorConfig,
+
+    /// API key store, and whether the HTTP transport accepts API keys.
+    #[cfg(feature = "api-keys")]
+    pub api_keys: ApiKeyConfig,
+}
+
+impl From<&FabrykConfig> for ServiceSettings {
+    fn from(config: &FabrykConfig) -> Self {
+        Self {
+            server: config.server.clone(),
+            graph: config.graph.clone(),
+            fts: config.fts.clone(),
+            tls: config.tls.clone(),
+            oauth: config.oauth.clone(),
+            scopes: config.scopes.clone(),
+            #[cfg(feature = "vector")]
+            vector: config.vector.clone(),
+            #[cfg(feature = "api-keys")]
+            api_keys: config.api_keys.clone(),
+        }
+    }
 }
 
 /// Content storage configuration.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(default)]
 pub struct ContentConfig {
-    /// Path to content directory.
+    /// Path to content directory, or a `gs://bucket/prefix` URL (requires
+    /// the `gcs` feature).
     pub path: Option<String>,
+
+    /// Service account key file for `gs://` content (default: Application
+    /// Default Credentials).
+    pub credentials_file: Option<String>,
 }
 
 /// Graph storage configuration.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(default)]
 pub struct GraphConfig {
-    /// Output path for graph files.
+    /// Directory for graph files (default: `<base>/data/graphs`).
     pub output_path: Option<String>,
 }
 
@@ -65,6 +150,10 @@ pub struct ServerConfig {
 
     /// Host address to bind to.
     pub host: String,
+
+    /// Public base URL advertised in OAuth discovery documents (default:
+    /// derived from host and port).
+    pub public_url: Option<String>,
 }
 
 // ============================================================================
@@ -79,6 +168,14 @@ impl Default for FabrykConfig {
             content: ContentConfig::default(),
             graph: GraphConfig::default(),
             server: ServerConfig::default(),
+            fts: SearchConfig::default(),
+            tls: TlsConfig::default(),
+            oauth: OAuthConfig::default(),
+            scopes: ScopeConfig::default(),
+            #[cfg(feature = "vector")]
+            vector: VectorConfig::default(),
+            #[cfg(feature = "api-keys")]
+            api_keys: ApiKeyConfig::default(),
         }
     }
 }
@@ -88,6 +185,7 @@ impl Default for ServerConfig {
         Self {
             port: 3000,
             host: "127.0.0.1".to_string(),
+            public_url: None,
         }
     }
 }
@@ -120,6 +218,13 @@ impl FabrykConfig {
         env_opts.add_section("content");
         env_opts.add_section("graph");
         env_opts.add_section("server");
+        env_opts.add_section("fts");
+        env_opts.add_section("tls");
+        env_opts.add_section("oauth");
+        #[cfg(feature = "vector")]
+        env_opts.add_section("vector");
+        #[cfg(feature = "api-keys")]
+        env_opts.add_section("api_keys");
         builder
             .add_env(env_opts)
             .map_err(|e| Error::config(format!("config env: {e}")))?;
@@ -216,6 +321,45 @@ impl ConfigProvider for FabrykConfig {
             None => Ok(self.base_path()?.join(content_type)),
         }
     }
+
+    fn content_store(&self, content_type: &str) -> Result<Arc<dyn ContentStore>> {
+        match self
+            .content
+            .path
+            .as_deref()
+            .and_then(|p| p.strip_prefix("gs://"))
+        {
+            Some(location) => self.gcs_content_store(location),
+            None => Ok(Arc::new(FsContentStore::new(
+                self.content_path(content_type)?,
+            ))),
+        }
+    }
+}
+
+impl FabrykConfig {
+    /// Store for a `gs://` content path, given as `bucket/prefix`.
+    #[cfg(feature = "gcs")]
+    fn gcs_content_store(&self, location: &str) -> Result<Arc<dyn ContentStore>> {
+        use fabryk_store_gcs::{CredentialRef, GcsContentStore};
+
+        let (bucket, prefix) = location.split_once('/').unwrap_or((location, ""));
+        if bucket.is_empty() {
+            return Err(Error::config("content.path gs:// URL has no bucket"));
+        }
+        let credentials = match &self.content.credentials_file {
+            Some(path) => CredentialRef::File { path: path.into() },
+            None => CredentialRef::ApplicationDefault,
+        };
+        Ok(Arc::new(GcsContentStore::new(bucket, prefix, credentials)))
+    }
+
+    #[cfg(not(feature = "gcs"))]
+    fn gcs_content_store(&self, _location: &str) -> Result<Arc<dyn ContentStore>> {
+        Err(Error::config(
+            "gs:// content paths require the `gcs` feature",
+        ))
+    }
 }
 
 // ============================================================================
@@ -283,6 +427,58 @@ mod tests {
         assert!(config.graph.output_path.is_none());
         assert_eq!(config.server.port, 3000);
         assert_eq!(config.server.host, "127.0.0.1");
+        assert_eq!(config.fts.backend, "tantivy");
+        assert!(!config.tls.enabled());
+        assert!(!config.oauth.enabled);
+    }
+
+    #[test]
+    fn test_service_settings_from_config() {
+        let config: FabrykConfig = toml::from_str(
+            r#"
+                [server]
+                port = 8080
+
+                [fts]
+                index_path = "/data/fts"
+
+                [oauth]
+                enabled = true
+                client_id = "client"
+
+                [scopes]
+                "mcp:read" = ["search"]
+            "#,
+        )
+        .unwrap();
+
+        let settings = ServiceSettings::from(&config);
+        assert_eq!(settings.server.port, 8080);
+        assert_eq!(settings.fts.index_path.as_deref(), Some("/data/fts"));
+        assert!(settings.oauth.enabled);
+        assert_eq!(settings.oauth.client_id, "client");
+        assert_eq!(settings.scopes.grants["mcp:read"], ["search"]);
+    }
+
+    #[cfg(feature = "api-keys")]
+    #[test]
+    fn test_service_settings_api_keys() {
+        let config: FabrykConfig = toml::from_str(
+            r#"
+                [api_keys]
+                enabled = true
+                redis_url = "redis://keys:6379"
+            "#,
+        )
+        .unwrap();
+
+        let settings = ServiceSettings::from(&config);
+        assert!(settings.api_keys.enabled);
+        assert_eq!(
+            settings.api_keys.redis_url.as_deref(),
+            Some("redis://keys:6379")
+        );
+        assert!(settings.api_keys.file.is_none());
     }
 
     // ------------------------------------------------------------------------
@@ -456,6 +652,7 @@ mod tests {
         let config = FabrykConfig {
             content: ContentConfig {
                 path: Some("/custom/content".into()),
+                ..Default::default()
             },
             ..Default::default()
         };
@@ -463,6 +660,29 @@ mod tests {
         assert_eq!(path, PathBuf::from("/custom/content"));
     }
 
+    #[test]
+    fn test_fabryk_config_provider_content_store() {
+        let config = FabrykConfig {
+            base_path: Some("/project".into()),
+            ..Default::default()
+        };
+        let store = config.content_store("concepts").unwrap();
+        assert_eq!(store.location(), "/project/concepts");
+
+        let config = FabrykConfig {
+            content: ContentConfig {
+                path: Some("gs://kb-content/concepts".into()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let store = config.content_store("concepts");
+        #[cfg(feature = "gcs")]
+        assert_eq!(store.unwrap().location(), "gs://kb-content/concepts");
+        #[cfg(not(feature = "gcs"))]
+        assert!(store.unwrap_err().to_string().contains("gcs"));
+    }
+
     // ------------------------------------------------------------------------
     // to_env_vars tests
     // ------------------------------------------------------------------------
@@ -5,7 +5,10 @@
 //! in their domain-specific `Config` struct.
 
 use fabryk_core::{Error, Result};
+use fabryk_mcp_auth::scopes::ScopePolicy;
+use fabryk_mcp_auth::server::MIN_SIGNING_KEY_LEN;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 // ============================================================================
 // TLS configuration
@@ -121,6 +124,24 @@ pub struct OAuthConfig {
     /// JWKS URL for fetching Google public keys.
     #[serde(default = "default_jwks_url")]
     pub jwks_url: String,
+
+    /// Google OAuth2 client secret (required when `authorization_server`
+    /// is true).
+    #[serde(default)]
+    pub client_secret: String,
+
+    /// Serve a built-in OAuth authorization server — client registration,
+    /// PKCE login through Google and short-lived tokens — for MCP clients
+    /// that expect one.
+    #[serde(default)]
+    pub authorization_server: bool,
+
+    /// Key the built-in authorization server signs access tokens with (at
+    /// least 32 bytes). Give every instance the same key for tokens to
+    /// survive restarts and work across instances; when empty, a random
+    /// key is generated at startup.
+    #[serde(default)]
+    pub signing_key: String,
 }
 
 impl Default for OAuthConfig {
@@ -130,6 +151,9 @@ impl Default for OAuthConfig {
             client_id: String::new(),
             domain: String::new(),
             jwks_url: default_jwks_url(),
+            client_secret: String::new(),
+            authorization_server: false,
+            signing_key: String::new(),
         }
     }
 }
@@ -139,6 +163,11 @@ impl OAuthConfig {
     ///
     /// When enabled:
     /// - `client_id` must be non-empty (hard error)
+    /// - `client_secret` must be non-empty with `authorization_server`
+    ///   (hard error)
+    /// - `signing_key`, if set, must be at least 32 bytes (hard error); if
+    ///   unset with `authorization_server`, tokens do not survive restarts
+    ///   (logged warning)
     /// - `domain` should be set (logged warning)
     ///
     /// When disabled, no validation is performed.
@@ -154,6 +183,27 @@ impl OAuthConfig {
             )));
         }
 
+        if self.authorization_server && self.client_secret.is_empty() {
+            return Err(Error::config(format!(
+                "oauth.client_secret is required when oauth.authorization_server is true. \
+                 Set {env_prefix}_OAUTH_CLIENT_SECRET or [oauth] client_secret in config file."
+            )));
+        }
+
+        if self.authorization_server {
+            if self.signing_key.is_empty() {
+                log::warn!(
+                    "oauth.signing_key is not set — issued tokens will not survive a restart \
+                     or work across instances. Set {env_prefix}_OAUTH_SIGNING_KEY to share a key."
+                );
+            } else if self.signing_key.len() < MIN_SIGNING_KEY_LEN {
+                return Err(Error::config(format!(
+                    "oauth.signing_key must be at least {MIN_SIGNING_KEY_LEN} bytes. \
+                     Set {env_prefix}_OAUTH_SIGNING_KEY or [oauth] signing_key in config file."
+                )));
+            }
+        }
+
         if self.domain.is_empty() {
             log::warn!(
                 "oauth.domain is not set — any Google account can authenticate. \
@@ -165,6 +215,57 @@ impl OAuthConfig {
     }
 }
 
+// ============================================================================
+// Scope configuration
+// ============================================================================
+
+/// The scope the built-in authorization server issues, and the usual
+/// scope for API keys.
+const DEFAULT_SCOPE: &str = "mcp";
+
+/// Which tools each credential scope may call over HTTP.
+///
+/// Maps a scope to tool names; a trailing `*` matches a prefix and `*`
+/// alone matches every tool. Credentials without scopes are not limited.
+/// Unless configured here, `mcp` grants every tool.
+///
+/// ```toml
+/// [scopes]
+/// "mcp:read" = ["search", "graph_*"]
+/// ```
+///
+/// # Example
+///
+/// ```
+/// use fabryk_cli::config_sections::ScopeConfig;
+///
+/// let scopes = ScopeConfig::default();
+/// assert!(scopes.policy().has_scope("mcp"));
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ScopeConfig {
+    /// Tool patterns granted to each scope.
+    pub grants: BTreeMap<String, Vec<String>>,
+}
+
+impl ScopeConfig {
+    /// The policy the HTTP transport enforces.
+    pub fn policy(&self) -> ScopePolicy {
+        let policy = self
+            .grants
+            .iter()
+            .fold(ScopePolicy::new(), |policy, (scope, tools)| {
+                policy.grant(scope, tools)
+            });
+        if policy.has_scope(DEFAULT_SCOPE) {
+            policy
+        } else {
+            policy.grant(DEFAULT_SCOPE, ["*"])
+        }
+    }
+}
+
 // ============================================================================
 // Tests
 // ============================================================================
@@ -286,6 +387,38 @@ mod tests {
         assert!(oauth.validate("APP").is_ok());
     }
 
+    #[test]
+    fn test_oauth_validate_authorization_server_needs_secret() {
+        let mut oauth = OAuthConfig {
+            enabled: true,
+            client_id: "my-client-id".to_string(),
+            authorization_server: true,
+            ..Default::default()
+        };
+        let err = oauth.validate("APP").unwrap_err();
+        assert!(err.to_string().contains("APP_OAUTH_CLIENT_SECRET"));
+
+        oauth.client_secret = "my-secret".to_string();
+        assert!(oauth.validate("APP").is_ok());
+    }
+
+    #[test]
+    fn test_oauth_validate_signing_key_length() {
+        let mut oauth = OAuthConfig {
+            enabled: true,
+            client_id: "my-client-id".to_string(),
+            client_secret: "my-secret".to_string(),
+            authorization_server: true,
+            signing_key: "too-short".to_string(),
+            ..Default::default()
+        };
+        let err = oauth.validate("APP").unwrap_err();
+        assert!(err.to_string().contains("APP_OAUTH_SIGNING_KEY"));
+
+        oauth.signing_key = "k".repeat(MIN_SIGNING_KEY_LEN);
+        assert!(oauth.validate("APP").is_ok());
+    }
+
     #[test]
     fn test_oauth_default_jwks_url() {
         let oauth = OAuthConfig::default();
@@ -321,4 +454,27 @@ mod tests {
         assert_eq!(parsed.domain, oauth.domain);
         assert_eq!(parsed.jwks_url, oauth.jwks_url);
     }
+
+    // -- ScopeConfig tests --
+
+    #[test]
+    fn test_scope_config_policy() {
+        use fabryk_auth::AuthenticatedUser;
+
+        let scopes: ScopeConfig = toml::from_str(
+            r#"
+                "mcp:read" = ["search", "graph_*"]
+            "#,
+        )
+        .unwrap();
+        let policy = scopes.policy();
+        let user = |scope: &str| AuthenticatedUser::new("a@example.com", "a").with_scopes([scope]);
+
+        assert!(policy.allows(&user("mcp:read"), "graph_related"));
+        assert!(!policy.allows(&user("mcp:read"), "debug_config"));
+        assert!(policy.allows(&user("mcp"), "debug_config"));
+
+        let scopes: ScopeConfig = toml::from_str(r#"mcp = ["search"]"#).unwrap();
+        assert!(!scopes.policy().allows(&user("mcp"), "debug_config"));
+    }
 }
@@ -0,0 +1,292 @@
+//! Domain extractors used by the `index` and `serve` commands.
+//!
+//! Fabryk builds every backend from content, but only the domain knows how
+//! to read its content. Applications register their extractors once, on
+//! [`FabrykCli`](crate::FabrykCli), and both commands use them:
+//!
+//! - a [`GraphExtractor`] for the knowledge graph
+//! - a `DocumentExtractor` for the full-text index (`fts-tantivy` feature)
+//! - a `VectorExtractor` for the vector index (`vector` feature)
+
+use std::future::Future;
+use std::path::Path;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use fabryk_core::Result;
+use fabryk_core::store::ContentStore;
+use fabryk_graph::{BuildStats, Edge, GraphBuilder, GraphData, GraphExtractor, Node};
+
+#[cfg(feature = "fts-tantivy")]
+use fabryk_fts::{DocumentExtractor, SearchDocument};
+#[cfg(feature = "vector")]
+use fabryk_vector::{VectorDocument, VectorExtractor};
+
+type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+// ============================================================================
+// Extractors
+// ============================================================================
+
+/// The extractors a domain application has registered.
+#[derive(Clone, Default)]
+pub struct Extractors {
+    graph: Option<Arc<dyn GraphIndexer>>,
+    #[cfg(feature = "fts-tantivy")]
+    document: Option<Arc<dyn DocumentExtractor>>,
+    #[cfg(feature = "vector")]
+    vector: Option<Arc<dyn VectorExtractor>>,
+}
+
+impl Extractors {
+    /// Create an empty set of extractors.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the extractor used to build the knowledge graph.
+    pub fn with_graph<E: GraphExtractor + 'static>(mut self, extractor: E) -> Self {
+        self.graph = Some(Arc::new(Shared(Arc::new(extractor))));
+        self
+    }
+
+    /// Set the extractor used to build the full-text index.
+    #[cfg(feature = "fts-tantivy")]
+    pub fn with_document<E: DocumentExtractor + 'static>(mut self, extractor: E) -> Self {
+        self.document = Some(Arc::new(extractor));
+        self
+    }
+
+    /// Set the extractor used to build the vector index.
+    #[cfg(feature = "vector")]
+    pub fn with_vector<E: VectorExtractor + 'static>(mut self, extractor: E) -> Self {
+        self.vector = Some(Arc::new(extractor));
+        self
+    }
+
+    /// Whether a graph extractor is registered.
+    pub fn has_graph(&self) -> bool {
+        self.graph.is_some()
+    }
+
+    /// Whether a vector extractor is registered.
+    #[cfg(feature = "vector")]
+    pub fn has_vector(&self) -> bool {
+        self.vector.is_some()
+    }
+
+    /// Build the graph from `store`, reusing the cache at
+    /// `cache_path` when it is fresh unless `force` is set.
+    ///
+    /// Returns `None` if no graph extractor is registered.
+    pub(crate) async fn build_graph(
+        &self,
+        store: &Arc<dyn ContentStore>,
+        cache_path: &Path,
+        force: bool,
+    ) -> Option<Result<(GraphData, BuildStats)>> {
+        match &self.graph {
+            Some(indexer) => Some(indexer.build(store, cache_path, force).await),
+            None => None,
+        }
+    }
+
+    /// The document extractor, if registered.
+    #[cfg(feature = "fts-tantivy")]
+    pub(crate) fn document(&self) -> Option<Box<dyn DocumentExtractor>> {
+        self.document
+            .clone()
+            .map(|e| Box::new(Shared(e)) as Box<dyn DocumentExtractor>)
+    }
+
+    /// The vector extractor, if registered.
+    #[cfg(feature = "vector")]
+    pub(crate) fn vector(&self) -> Option<impl VectorExtractor + use<>> {
+        self.vector.clone().map(Shared)
+    }
+}
+
+impl std::fmt::Debug for Extractors {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut s = f.debug_struct("Extractors");
+        s.field("graph", &self.graph.is_some());
+        #[cfg(feature = "fts-tantivy")]
+        s.field("document", &self.document.is_some());
+        #[cfg(feature = "vector")]
+        s.field("vector", &self.vector.is_some());
+        s.finish()
+    }
+}
+
+// ============================================================================
+// Type erasure
+// ============================================================================
+
+/// Builds a graph with a registered [`GraphExtractor`].
+///
+/// `GraphExtractor` has associated types, so it can't be stored as a trait
+/// object; this trait hides them.
+trait GraphIndexer: Send + Sync {
+    fn build<'a>(
+        &'a self,
+        store: &'a Arc<dyn ContentStore>,
+        cache_path: &'a Path,
+        force: bool,
+    ) -> BoxFuture<'a, Result<(GraphData, BuildStats)>>;
+}
+
+/// A shared extractor. The builders take their extractor by value, so each
+/// build gets a new handle to the registered one.
+struct Shared<E: ?Sized>(Arc<E>);
+
+impl<E: GraphExtractor + 'static> GraphIndexer for Shared<E> {
+    fn build<'a>(
+        &'a self,
+        store: &'a Arc<dyn ContentStore>,
+        cache_path: &'a Path,
+        force: bool,
+    ) -> BoxFuture<'a, Result<(GraphData, BuildStats)>> {
+        let mut builder = GraphBuilder::new(Shared(self.0.clone()))
+            .with_content_store(store.clone())
+            .with_cache_path(cache_path);
+        if force {
+            builder = builder.skip_cache();
+        }
+        Box::pin(builder.build())
+    }
+}
+
+impl<E: GraphExtractor> GraphExtractor for Shared<E> {
+    type NodeData = E::NodeData;
+    type EdgeData = E::EdgeData;
+
+    fn extract_node(
+        &self,
+        base_path: &Path,
+        file_path: &Path,
+        frontmatter: &yaml_serde::Value,
+        content: &str,
+    ) -> Result<Self::NodeData> {
+        self.0
+            .extract_node(base_path, file_path, frontmatter, content)
+    }
+
+    fn extract_edges(
+        &self,
+        frontmatter: &yaml_serde::Value,
+        content: &str,
+    ) -> Result<Option<Self::EdgeData>> {
+        self.0.extract_edges(frontmatter, content)
+    }
+
+    fn to_graph_node(&self, node_data: &Self::NodeData) -> Node {
+        self.0.to_graph_node(node_data)
+    }
+
+    fn to_graph_edges(&self, from_id: &str, edge_data: &Self::EdgeData) -> Vec<Edge> {
+        self.0.to_graph_edges(from_id, edge_data)
+    }
+
+    fn content_glob(&self) -> &str {
+        self.0.content_glob()
+    }
+
+    fn name(&self) -> &str {
+        self.0.name()
+    }
+}
+
+#[cfg(feature = "fts-tantivy")]
+impl<E: DocumentExtractor + ?Sized> DocumentExtractor for Shared<E> {
+    fn extract(&self, path: &Path, content: &str) -> Option<SearchDocument> {
+        self.0.extract(path, content)
+    }
+
+    fn supported_extensions(&self) -> &[&str] {
+        self.0.supported_extensions()
+    }
+
+    fn supports_extension(&self, ext: &str) -> bool {
+        self.0.supports_extension(ext)
+    }
+}
+
+#[cfg(feature = "vector")]
+impl<E: VectorExtractor + ?Sized> VectorExtractor for Shared<E> {
+    fn extract_document(
+        &self,
+        base_path: &Path,
+        file_path: &Path,
+        frontmatter: &yaml_serde::Value,
+        content: &str,
+    ) -> Result<VectorDocument> {
+        self.0
+            .extract_document(base_path, file_path, frontmatter, content)
+    }
+
+    fn content_glob(&self) -> &str {
+        self.0.content_glob()
+    }
+
+    fn name(&self) -> &str {
+        self.0.name()
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fabryk_graph::MockExtractor;
+
+    #[tokio::test]
+    async fn test_build_graph_with_registered_extractor() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let content = dir.path().join("content");
+        std::fs::create_dir_all(&content).unwrap();
+        std::fs::write(
+            content.join("a.md"),
+            "---\ntitle: A\ncategory: basics\n---\nBody",
+        )
+        .unwrap();
+        let content: Arc<dyn ContentStore> =
+            Arc::new(fabryk_core::store::FsContentStore::new(content));
+        let cache = dir.path().join("graph.json");
+
+        let extractors = Extractors::new();
+        assert!(!extractors.has_graph());
+        assert!(
+            extractors
+                .build_graph(&content, &cache, false)
+                .await
+                .is_none()
+        );
+
+        let extractors = extractors.with_graph(MockExtractor);
+        let (graph, stats) = extractors
+            .build_graph(&content, &cache, false)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(graph.node_count(), 1);
+        assert!(!stats.from_cache);
+        assert!(cache.exists());
+
+        let (_, stats) = extractors
+            .build_graph(&content, &cache, false)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(stats.from_cache);
+
+        let (_, stats) = extractors
+            .build_graph(&content, &cache, true)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(!stats.from_cache);
+    }
+}
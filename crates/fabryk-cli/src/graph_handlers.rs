@@ -40,7 +40,7 @@ pub struct QueryOptions {
 // ============================================================================
 
 /// Resolve the default graph file path from config.
-fn graph_path<C: ConfigProvider>(config: &C) -> Result<PathBuf> {
+pub(crate) fn graph_path<C: ConfigProvider>(config: &C) -> Result<PathBuf> {
     let base = config.base_path()?;
     Ok(base.join("data").join("graphs").join("graph.json"))
 }
@@ -57,16 +57,16 @@ pub async fn handle_build<C: ConfigProvider, E: GraphExtractor>(
     extractor: E,
     options: BuildOptions,
 ) -> Result<()> {
-    let content_path = config.content_path("concepts")?;
+    let store = config.content_store("concepts")?;
     let output_path = match options.output {
         Some(ref p) => PathBuf::from(p),
         None => graph_path(config)?,
     };
 
-    println!("Building graph from: {}", content_path.display());
+    println!("Building graph from: {}", store.location());
 
     let (graph, stats) = GraphBuilder::new(extractor)
-        .with_content_path(&content_path)
+        .with_content_store(store)
         .build()
         .await?;
 
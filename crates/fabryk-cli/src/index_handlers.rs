@@ -0,0 +1,439 @@
+//! Handler functions for the `index` command.
+//!
+//! `index` builds the full-text index, the knowledge graph and the vector
+//! index from the content store (a local directory, or a bucket with the
+//! `gcs` feature). Each builder reuses its existing
+//! output when the freshness metadata still matches the content; `--force`
+//! rebuilds everything. `index --check` only compares the metadata against
+//! the content and fails if anything is stale.
+//!
+//! An index is built only when it can be:
+//! - full-text: the `fts-tantivy` feature (with the domain's
+//!   `DocumentExtractor`, or the default one)
+//! - graph: a registered [`GraphExtractor`](fabryk_graph::GraphExtractor)
+//! - vector: the `vector` feature, a registered `VectorExtractor` and
+//!   `vector.enabled`
+
+use crate::config::ServiceSettings;
+use crate::extractors::Extractors;
+use crate::graph_handlers;
+use fabryk_core::store::ContentStore;
+use fabryk_core::traits::ConfigProvider;
+use fabryk_core::{Error, Result};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Content type passed to [`ConfigProvider::content_store`] for indexing.
+pub const CONTENT_TYPE: &str = "concepts";
+
+// ============================================================================
+// Option types
+// ============================================================================
+
+/// Options for the `index` command.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct IndexOptions {
+    /// Rebuild even when the existing index is fresh.
+    pub force: bool,
+    /// Report freshness without building anything.
+    pub check: bool,
+}
+
+/// Freshness of an index relative to the content it was built from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Freshness {
+    /// Built from the current content.
+    Fresh,
+    /// Built from older content.
+    Stale,
+    /// Not built yet.
+    Missing,
+}
+
+impl fmt::Display for Freshness {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Fresh => write!(f, "fresh"),
+            Self::Stale => write!(f, "stale"),
+            Self::Missing => write!(f, "missing"),
+        }
+    }
+}
+
+// ============================================================================
+// Paths
+// ============================================================================
+
+/// Full-text index directory: `fts.index_path`, or `<base>/data/fts`.
+pub fn fts_index_path<C: ConfigProvider>(
+    config: &C,
+    settings: &ServiceSettings,
+) -> Result<PathBuf> {
+    match &settings.fts.index_path {
+        Some(path) => Ok(PathBuf::from(path)),
+        None => Ok(config.base_path()?.join("data").join("fts")),
+    }
+}
+
+/// Graph file: `graph.json` in `graph.output_path`, or
+/// `<base>/data/graphs/graph.json`.
+pub fn graph_file_path<C: ConfigProvider>(
+    config: &C,
+    settings: &ServiceSettings,
+) -> Result<PathBuf> {
+    match &settings.graph.output_path {
+        Some(dir) => Ok(PathBuf::from(dir).join("graph.json")),
+        None => graph_handlers::graph_path(config),
+    }
+}
+
+/// Vector cache file: `vectors.json` in `vector.db_path`, or
+/// `<base>/data/vectors/vectors.json`.
+#[cfg(feature = "vector")]
+pub fn vector_cache_path<C: ConfigProvider>(
+    config: &C,
+    settings: &ServiceSettings,
+) -> Result<PathBuf> {
+    let dir = match &settings.vector.db_path {
+        Some(dir) => PathBuf::from(dir),
+        None => config.base_path()?.join("data").join("vectors"),
+    };
+    Ok(dir.join("vectors.json"))
+}
+
+/// HNSW settings for the vector index, or `None` for a flat index, from
+/// `vector.index_type`.
+#[cfg(feature = "vector")]
+pub fn vector_hnsw_config(
+    config: &fabryk_vector::VectorConfig,
+) -> Result<Option<fabryk_vector::HnswConfig>> {
+    match config.index_type.as_str() {
+        "hnsw" => Ok(Some(fabryk_vector::HnswConfig::from_vector_config(config))),
+        "flat" => Ok(None),
+        other => Err(Error::config(format!(
+            "Unknown vector index type: '{other}'. Supported: flat, hnsw"
+        ))),
+    }
+}
+
+// ============================================================================
+// Handlers
+// ============================================================================
+
+/// Build (or with `check`, verify) every index that can be built.
+pub async fn handle_index<C: ConfigProvider>(
+    config: &C,
+    settings: &ServiceSettings,
+    extractors: &Extractors,
+    options: IndexOptions,
+) -> Result<()> {
+    if options.check {
+        return handle_check(config, settings, extractors).await;
+    }
+
+    let store = config.content_store(CONTENT_TYPE)?;
+    println!("Indexing {}", store.location());
+    let mut built = 0;
+
+    #[cfg(feature = "fts-tantivy")]
+    {
+        let index_path = fts_index_path(config, settings)?;
+        let mut builder = fabryk_fts::IndexBuilder::new();
+        if let Some(extractor) = extractors.document() {
+            builder = builder.with_extractor(extractor);
+        }
+        if options.force {
+            builder = builder.force_rebuild();
+        }
+        let stats = builder
+            .build_from_store(store.as_ref(), &index_path)
+            .await?;
+        println!(
+            "  fts:    {} documents ({} files processed, {} skipped)",
+            stats.documents_indexed, stats.files_processed, stats.files_skipped
+        );
+        built += 1;
+    }
+
+    let graph_path = graph_file_path(config, settings)?;
+    if let Some(result) = extractors
+        .build_graph(&store, &graph_path, options.force)
+        .await
+    {
+        let (_, stats) = result?;
+        println!(
+            "  graph:  {} nodes, {} edges{}",
+            stats.nodes_created,
+            stats.edges_created,
+            if stats.from_cache { " (fresh)" } else { "" }
+        );
+        if !stats.errors.is_empty() {
+            println!("          {} files failed to extract", stats.errors.len());
+        }
+        built += 1;
+    }
+
+    #[cfg(feature = "vector")]
+    if settings.vector.enabled
+        && let Some(extractor) = extractors.vector()
+    {
+        let provider = fabryk_vector::create_embedding_provider(&settings.vector).await?;
+        let mut builder = fabryk_vector::VectorIndexBuilder::new(extractor)
+            .with_content_store(store.clone())
+            .with_embedding_provider(provider)
+            .with_cache_path(vector_cache_path(config, settings)?);
+        if let Some(ref path) = settings.vector.embedding_cache_path {
+            builder = builder.with_embedding_cache(path);
+        }
+        if let Some(hnsw) = vector_hnsw_config(&settings.vector)? {
+            builder = builder.with_hnsw(hnsw);
+        }
+        if options.force {
+            builder = builder.skip_cache();
+        }
+        let (_, stats) = builder.build().await?;
+        println!(
+            "  vector: {} documents, dimension {}{}",
+            stats.documents_indexed,
+            stats.embedding_dimension,
+            if stats.from_cache { " (fresh)" } else { "" }
+        );
+        built += 1;
+    }
+
+    if built == 0 {
+        println!("Nothing to index: register extractors or enable the fts-tantivy feature.");
+    }
+    Ok(())
+}
+
+/// Report the freshness of every index that can be built.
+///
+/// Fails if any of them is stale or missing, so `index --check` can gate
+/// deployments.
+async fn handle_check<C: ConfigProvider>(
+    config: &C,
+    settings: &ServiceSettings,
+    extractors: &Extractors,
+) -> Result<()> {
+    let store = config.content_store(CONTENT_TYPE)?;
+    let mut results: Vec<(&str, Freshness)> = Vec::new();
+
+    #[cfg(feature = "fts-tantivy")]
+    results.push((
+        "fts",
+        fts_freshness(&fts_index_path(config, settings)?, store.as_ref()).await?,
+    ));
+
+    if extractors.has_graph() {
+        results.push((
+            "graph",
+            graph_freshness(&graph_file_path(config, settings)?, store.as_ref()).await?,
+        ));
+    }
+
+    #[cfg(feature = "vector")]
+    if settings.vector.enabled && extractors.has_vector() {
+        results.push((
+            "vector",
+            vector_freshness(&vector_cache_path(config, settings)?, store.as_ref()).await?,
+        ));
+    }
+
+    if results.is_empty() {
+        println!("Nothing to check: register extractors or enable the fts-tantivy feature.");
+        return Ok(());
+    }
+
+    for (name, freshness) in &results {
+        println!("  {:<7} {freshness}", format!("{name}:"));
+    }
+
+    let stale: Vec<&str> = results
+        .iter()
+        .filter(|(_, f)| *f != Freshness::Fresh)
+        .map(|(name, _)| *name)
+        .collect();
+    if stale.is_empty() {
+        println!("All indexes are fresh.");
+        Ok(())
+    } else {
+        Err(Error::operation(format!(
+            "Indexes out of date: {}. Run `index` to rebuild.",
+            stale.join(", ")
+        )))
+    }
+}
+
+// ============================================================================
+// Freshness checks
+// ============================================================================
+
+/// Compare the full-text index metadata with the content.
+#[cfg(feature = "fts-tantivy")]
+pub async fn fts_freshness(index_path: &Path, store: &dyn ContentStore) -> Result<Freshness> {
+    if fabryk_fts::IndexMetadata::load(index_path)?.is_none() {
+        return Ok(Freshness::Missing);
+    }
+    Ok(
+        if fabryk_fts::is_index_fresh_for_store(index_path, store).await? {
+            Freshness::Fresh
+        } else {
+            Freshness::Stale
+        },
+    )
+}
+
+/// Compare the graph file's content hash with the content.
+pub async fn graph_freshness(graph_path: &Path, store: &dyn ContentStore) -> Result<Freshness> {
+    if !graph_path.exists() {
+        return Ok(Freshness::Missing);
+    }
+    let hash = fabryk_graph::compute_store_hash(store).await?;
+    Ok(if fabryk_graph::is_cache_fresh(graph_path, &hash) {
+        Freshness::Fresh
+    } else {
+        Freshness::Stale
+    })
+}
+
+/// Compare the vector cache's content hash with the content.
+#[cfg(feature = "vector")]
+pub async fn vector_freshness(cache_path: &Path, store: &dyn ContentStore) -> Result<Freshness> {
+    if !cache_path.exists() {
+        return Ok(Freshness::Missing);
+    }
+    let hash = fabryk_vector::builder::compute_store_hash(store).await?;
+    Ok(
+        if fabryk_vector::SimpleVectorBackend::is_cache_fresh(cache_path, &hash) {
+            Freshness::Fresh
+        } else {
+            Freshness::Stale
+        },
+    )
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::FabrykConfig;
+    use fabryk_graph::MockExtractor;
+
+    fn project() -> (tempfile::TempDir, FabrykConfig) {
+        let dir = tempfile::TempDir::new().unwrap();
+        let content = dir.path().join(CONTENT_TYPE);
+        std::fs::create_dir_all(&content).unwrap();
+        std::fs::write(
+            content.join("a.md"),
+            "---\ntitle: A\ncategory: basics\n---\nBody",
+        )
+        .unwrap();
+        let config = FabrykConfig {
+            base_path: Some(dir.path().to_string_lossy().into_owned()),
+            ..Default::default()
+        };
+        (dir, config)
+    }
+
+    #[test]
+    fn test_default_paths() {
+        let (dir, config) = project();
+        let settings = ServiceSettings::from(&config);
+        assert_eq!(
+            fts_index_path(&config, &settings).unwrap(),
+            dir.path().join("data/fts")
+        );
+        assert_eq!(
+            graph_file_path(&config, &settings).unwrap(),
+            dir.path().join("data/graphs/graph.json")
+        );
+
+        let mut settings = settings;
+        settings.graph.output_path = Some("/srv/graphs".into());
+        assert_eq!(
+            graph_file_path(&config, &settings).unwrap(),
+            PathBuf::from("/srv/graphs/graph.json")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_index_then_check() {
+        let (dir, config) = project();
+        let settings = ServiceSettings::from(&config);
+        let extractors = Extractors::new().with_graph(MockExtractor);
+        let check = IndexOptions {
+            check: true,
+            ..Default::default()
+        };
+
+        assert!(
+            handle_index(&config, &settings, &extractors, check)
+                .await
+                .is_err()
+        );
+        handle_index(&config, &settings, &extractors, IndexOptions::default())
+            .await
+            .unwrap();
+        assert!(dir.path().join("data/graphs/graph.json").exists());
+        handle_index(&config, &settings, &extractors, check)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_graph_freshness() {
+        let (dir, config) = project();
+        let store = config.content_store(CONTENT_TYPE).unwrap();
+        let graph = dir.path().join("graph.json");
+        assert_eq!(
+            graph_freshness(&graph, store.as_ref()).await.unwrap(),
+            Freshness::Missing
+        );
+
+        fabryk_graph::save_graph(&fabryk_graph::GraphData::new(), &graph, None).unwrap();
+        assert_eq!(
+            graph_freshness(&graph, store.as_ref()).await.unwrap(),
+            Freshness::Stale
+        );
+    }
+
+    #[cfg(feature = "vector")]
+    #[tokio::test]
+    async fn test_index_persists_embedding_cache() {
+        let (dir, config) = project();
+        let mut settings = ServiceSettings::from(&config);
+        let embeddings = dir.path().join("embeddings.json");
+        settings.vector.enabled = true;
+        settings.vector.provider = "mock".to_string();
+        settings.vector.embedding_cache_path = Some(embeddings.to_string_lossy().into_owned());
+        let extractors =
+            Extractors::new().with_vector(fabryk_vector::extractor::MockVectorExtractor);
+
+        handle_index(&config, &settings, &extractors, IndexOptions::default())
+            .await
+            .unwrap();
+        assert!(embeddings.exists());
+    }
+
+    #[tokio::test]
+    async fn test_nothing_to_index() {
+        let (_dir, config) = project();
+        let settings = ServiceSettings::from(&config);
+        let result = handle_index(
+            &config,
+            &settings,
+            &Extractors::new(),
+            IndexOptions {
+                check: true,
+                ..Default::default()
+            },
+        )
+        .await;
+        // Without extractors only the full-text index (fts-tantivy) is checked.
+        assert_eq!(result.is_ok(), !cfg!(feature = "fts-tantivy"));
+    }
+}
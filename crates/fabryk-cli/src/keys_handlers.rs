@@ -0,0 +1,277 @@
+//! Handler functions for API key CLI commands.
+//!
+//! Provides `keys mint`, `keys list` and `keys revoke` over the store named
+//! by the `[api_keys]` section: Redis when `redis_url` is set, otherwise a
+//! JSON file (default: `<base>/data/api-keys.json`). A running server reads
+//! the same store, so minted and revoked keys take effect without a restart.
+
+use crate::cli::KeysAction;
+use crate::config::ServiceSettings;
+use chrono::{DateTime, Duration, Utc};
+use fabryk_auth_apikey::{ApiKey, ApiKeyError, ApiKeyStore};
+use fabryk_core::traits::ConfigProvider;
+use fabryk_core::{Error, Result};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+// ============================================================================
+// Command dispatch
+// ============================================================================
+
+/// Handle a keys subcommand.
+pub async fn handle_keys_command<C: ConfigProvider>(
+    config: &C,
+    settings: &ServiceSettings,
+    action: KeysAction,
+) -> Result<()> {
+    let store = open_key_store(config, settings).await?;
+    match action {
+        KeysAction::Mint {
+            owner,
+            scopes,
+            expires_in,
+        } => cmd_keys_mint(&*store, &owner, scopes, expires_in.as_deref()).await,
+        KeysAction::List { json } => cmd_keys_list(&*store, json).await,
+        KeysAction::Revoke { id } => cmd_keys_revoke(&*store, &id).await,
+    }
+}
+
+/// Default API key file: `<base>/data/api-keys.json`.
+pub fn default_key_file<C: ConfigProvider>(config: &C) -> Result<PathBuf> {
+    Ok(config.base_path()?.join("data").join("api-keys.json"))
+}
+
+/// Open the API key store configured in `settings.api_keys`.
+pub async fn open_key_store<C: ConfigProvider>(
+    config: &C,
+    settings: &ServiceSettings,
+) -> Result<Arc<dyn ApiKeyStore>> {
+    settings
+        .api_keys
+        .open_store(&default_key_file(config)?)
+        .await
+        .map_err(store_error)
+}
+
+// ============================================================================
+// Generic command handlers
+// ============================================================================
+
+/// Mint a key and print its token. The token cannot be shown again.
+pub async fn cmd_keys_mint(
+    store: &dyn ApiKeyStore,
+    owner: &str,
+    scopes: Vec<String>,
+    expires_in: Option<&str>,
+) -> Result<()> {
+    let expires_at = expires_in
+        .map(parse_lifetime)
+        .transpose()?
+        .map(|lifetime| Utc::now() + lifetime);
+    let (key, token) = ApiKey::mint(owner, scopes, expires_at);
+    store.put(&key).await.map_err(store_error)?;
+
+    println!("Minted API key {} for {}", key.id, key.owner);
+    if let Some(at) = key.expires_at {
+        println!("Expires: {}", format_time(at));
+    }
+    println!("\n{token}\n");
+    println!("Store this token now; it cannot be shown again.");
+    Ok(())
+}
+
+/// Print every key in the store.
+pub async fn cmd_keys_list(store: &dyn ApiKeyStore, json: bool) -> Result<()> {
+    let keys = store.list().await.map_err(store_error)?;
+    let now = Utc::now();
+
+    if json {
+        // The secret hash stays in the store.
+        let keys: Vec<serde_json::Value> = keys
+            .iter()
+            .map(|key| {
+                serde_json::json!({
+                    "id": key.id,
+                    "owner": key.owner,
+                    "scopes": key.scopes,
+                    "created_at": key.created_at,
+                    "expires_at": key.expires_at,
+                    "last_used_at": key.last_used_at,
+                    "revoked_at": key.revoked_at,
+                    "status": key.status(now).to_string(),
+                })
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&keys)?);
+    } else {
+        print!("{}", format_keys(&keys, now));
+    }
+    Ok(())
+}
+
+/// Revoke a key.
+pub async fn cmd_keys_revoke(store: &dyn ApiKeyStore, id: &str) -> Result<()> {
+    let key = store.revoke(id).await.map_err(store_error)?;
+    println!("Revoked API key {} for {}", key.id, key.owner);
+    Ok(())
+}
+
+/// Render keys as a plain-text table, with their status at `now`.
+pub fn format_keys(keys: &[ApiKey], now: DateTime<Utc>) -> String {
+    if keys.is_empty() {
+        return "No API keys.\n".to_string();
+    }
+
+    let owner_width = keys
+        .iter()
+        .map(|k| k.owner.len())
+        .max()
+        .unwrap_or(0)
+        .max("OWNER".len());
+    let optional_time = |at: Option<DateTime<Utc>>| at.map_or_else(|| "-".to_string(), format_time);
+
+    let mut out = format!(
+        "{:<16}  {:<owner_width$}  {:<16}  {:<16}  {:<16}  {:<8}  SCOPES\n",
+        "ID", "OWNER", "CREATED", "EXPIRES", "LAST USED", "STATUS"
+    );
+    for key in keys {
+        out.push_str(&format!(
+            "{:<16}  {:<owner_width$}  {:<16}  {:<16}  {:<16}  {:<8}  {}\n",
+            key.id,
+            key.owner,
+            format_time(key.created_at),
+            optional_time(key.expires_at),
+            optional_time(key.last_used_at),
+            key.status(now).to_string(),
+            key.scopes.join(",")
+        ));
+    }
+    out
+}
+
+/// Parse a key lifetime such as `90d`, `12h`, `30m` or `2w`.
+pub fn parse_lifetime(value: &str) -> Result<Duration> {
+    let invalid = || {
+        Error::parse(format!(
+            "Invalid lifetime '{value}': expected a number followed by m, h, d or w"
+        ))
+    };
+    let split = value.len().saturating_sub(1);
+    let (count, unit) = value.split_at_checked(split).ok_or_else(invalid)?;
+    let count: i64 = count.parse().map_err(|_| invalid())?;
+    if count <= 0 {
+        return Err(invalid());
+    }
+    match unit {
+        "m" => Duration::try_minutes(count),
+        "h" => Duration::try_hours(count),
+        "d" => Duration::try_days(count),
+        "w" => Duration::try_weeks(count),
+        _ => None,
+    }
+    .ok_or_else(invalid)
+}
+
+fn format_time(at: DateTime<Utc>) -> String {
+    at.format("%Y-%m-%d %H:%M").to_string()
+}
+
+fn store_error(err: ApiKeyError) -> Error {
+    match err {
+        ApiKeyError::NotFound(id) => Error::not_found("API key", id),
+        other => Error::operation(other.to_string()),
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::FabrykConfig;
+    use fabryk_auth_apikey::KeyStatus;
+
+    fn project() -> (tempfile::TempDir, FabrykConfig) {
+        let dir = tempfile::TempDir::new().unwrap();
+        let config = FabrykConfig {
+            base_path: Some(dir.path().to_string_lossy().into_owned()),
+            ..Default::default()
+        };
+        (dir, config)
+    }
+
+    #[test]
+    fn test_parse_lifetime() {
+        assert_eq!(parse_lifetime("90d").unwrap(), Duration::days(90));
+        assert_eq!(parse_lifetime("12h").unwrap(), Duration::hours(12));
+        assert_eq!(parse_lifetime("30m").unwrap(), Duration::minutes(30));
+        assert_eq!(parse_lifetime("2w").unwrap(), Duration::weeks(2));
+        for bad in ["", "d", "90", "0d", "-1d", "90y", "1.5h"] {
+            assert!(parse_lifetime(bad).is_err(), "{bad}");
+        }
+    }
+
+    #[test]
+    fn test_format_keys() {
+        let now = Utc::now();
+        let (active, _) = ApiKey::mint("ci-bot@example.com", ["mcp:read", "graph"], None);
+        let (mut revoked, _) = ApiKey::mint("etl@example.com", ["mcp"], None);
+        revoked.revoked_at = Some(now);
+
+        let table = format_keys(&[active.clone(), revoked], now);
+        let lines: Vec<&str> = table.lines().collect();
+        assert!(lines[0].starts_with("ID"));
+        assert!(lines[1].starts_with(&active.id));
+        assert!(lines[1].contains("active"));
+        assert!(lines[1].ends_with("mcp:read,graph"));
+        assert!(lines[2].contains("revoked"));
+        assert!(!table.contains(&active.hash));
+
+        assert_eq!(format_keys(&[], now), "No API keys.\n");
+    }
+
+    #[tokio::test]
+    async fn test_mint_list_revoke() {
+        let (dir, config) = project();
+        let settings = ServiceSettings::from(&config);
+        assert_eq!(
+            default_key_file(&config).unwrap(),
+            dir.path().join("data/api-keys.json")
+        );
+
+        let mint = KeysAction::Mint {
+            owner: "ci-bot@example.com".to_string(),
+            scopes: vec!["mcp".to_string()],
+            expires_in: Some("90d".to_string()),
+        };
+        handle_keys_command(&config, &settings, mint).await.unwrap();
+        handle_keys_command(&config, &settings, KeysAction::List { json: true })
+            .await
+            .unwrap();
+
+        let store = open_key_store(&config, &settings).await.unwrap();
+        let keys = store.list().await.unwrap();
+        assert_eq!(keys.len(), 1);
+        assert_eq!(keys[0].owner, "ci-bot@example.com");
+        assert!(keys[0].expires_at.is_some());
+
+        let revoke = KeysAction::Revoke {
+            id: keys[0].id.clone(),
+        };
+        handle_keys_command(&config, &settings, revoke)
+            .await
+            .unwrap();
+        let key = store.get(&keys[0].id).await.unwrap().unwrap();
+        assert_eq!(key.status(Utc::now()), KeyStatus::Revoked);
+
+        let missing = KeysAction::Revoke {
+            id: "0000".to_string(),
+        };
+        let err = handle_keys_command(&config, &settings, missing)
+            .await
+            .unwrap_err();
+        assert!(err.is_not_found());
+    }
+}
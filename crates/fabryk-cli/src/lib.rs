@@ -8,22 +8,36 @@
 //! - [`FabrykCli<C>`]: Generic CLI parameterized over config provider
 //! - [`CliExtension`]: Trait for adding domain-specific subcommands
 //! - Built-in graph commands (validate, stats, query)
+//! - `serve` and `index` commands driven by domain [`Extractors`]
+//! - Audit log usage reports (`audit` feature)
+//! - API key management and API key auth for `serve` (`api-keys` feature)
 
 pub mod app;
+#[cfg(feature = "audit")]
+pub mod audit_handlers;
 pub mod cli;
 pub mod config;
 pub mod config_handlers;
 pub mod config_loader;
 pub mod config_sections;
 pub mod config_utils;
+pub mod extractors;
 pub mod graph_handlers;
+pub mod index_handlers;
+#[cfg(feature = "api-keys")]
+pub mod keys_handlers;
+pub mod serve_handlers;
 #[cfg(feature = "vector-fastembed")]
 pub mod vectordb_handlers;
 
 // Re-exports — CLI types
+#[cfg(feature = "audit")]
+pub use cli::{AuditAction, AuditCommand};
 pub use cli::{
     BaseCommand, CliArgs, CliExtension, ConfigAction, ConfigCommand, GraphCommand, GraphSubcommand,
 };
+#[cfg(feature = "api-keys")]
+pub use cli::{KeysAction, KeysCommand};
 #[cfg(feature = "vector-fastembed")]
 pub use cli::{VectordbAction, VectordbCommand};
 
@@ -31,9 +45,14 @@ pub use cli::{VectordbAction, VectordbCommand};
 pub use app::FabrykCli;
 
 // Re-exports — configuration
-pub use config::FabrykConfig;
+pub use config::{FabrykConfig, ServiceSettings};
 pub use config_loader::ConfigLoaderBuilder;
 pub use config_sections::{OAuthConfig, TlsConfig};
 
 // Re-exports — graph handler types
 pub use graph_handlers::{BuildOptions, QueryOptions};
+
+// Re-exports — serve and index
+pub use extractors::Extractors;
+pub use index_handlers::{Freshness, IndexOptions};
+pub use serve_handlers::ServeOptions;
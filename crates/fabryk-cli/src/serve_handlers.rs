@@ -0,0 +1,900 @@
+//! Handler functions for the `serve` command.
+//!
+//! Assembles a [`FabrykMcpServer`] with the full-text search, graph and
+//! (with the `vector` feature) semantic search tools, and serves it over
+//! stdio or HTTP.
+//!
+//! Backends load in the background, each behind a [`ServiceHandle`]: until
+//! a backend is ready its tools return a "not ready" error and `/health`
+//! reports `starting`. The graph and vector index are built with the
+//! registered extractors, which reuse their caches when fresh; without an
+//! extractor the files written by `index` are loaded as they are.
+//!
+//! Over HTTP the server honors:
+//! - `tls` — HTTPS with the configured certificate and key
+//! - `oauth` — Google bearer tokens on `/mcp`, plus the MCP OAuth
+//!   discovery documents; with `oauth.authorization_server`, a built-in
+//!   authorization server that logs users in through Google
+//! - `api_keys` (`api-keys` feature) — API keys on `/mcp`, checked before
+//!   Google tokens
+
+use crate::config::ServiceSettings;
+use crate::config_sections::TlsConfig;
+use crate::extractors::Extractors;
+use crate::index_handlers::{CONTENT_TYPE, fts_index_path, graph_file_path};
+use fabryk_auth::{AuthConfig, AuthLayer, ChainValidator};
+use fabryk_auth_google::GoogleTokenValidator;
+use fabryk_core::traits::ConfigProvider;
+use fabryk_core::{Error, Result};
+use fabryk_fts::SearchBackend;
+use fabryk_graph::GraphData;
+use fabryk_mcp_auth::scopes::ScopedRegistry;
+use fabryk_mcp_auth::server::{AuthServerConfig, AuthorizationServer, UpstreamConfig};
+use fabryk_mcp_core::{
+    CompositeRegistry, DiagnosticTools, FabrykMcpServer, HealthTools, ServiceAwareRegistry,
+    ServiceHandle, ServiceState, ToolRegistry, ValidatedRegistry, health_router,
+};
+use fabryk_mcp_fts::FtsTools;
+use fabryk_mcp_graph::GraphTools;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tokio::net::TcpListener;
+use tokio::sync::RwLock;
+
+/// Authorization server advertised in the OAuth discovery documents.
+const GOOGLE_AUTH_SERVER: &str = "https://accounts.google.com";
+
+// ============================================================================
+// Option types
+// ============================================================================
+
+/// Options for the `serve` command.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ServeOptions {
+    /// Port to listen on (defaults to `server.port`).
+    pub port: Option<u16>,
+    /// Serve over stdio instead of HTTP.
+    pub stdio: bool,
+}
+
+// ============================================================================
+// Backends
+// ============================================================================
+
+/// The backends behind the server's tools, with their readiness handles.
+pub struct Backends {
+    /// Full-text search backend.
+    pub fts: Arc<dyn SearchBackend>,
+    /// Knowledge graph; empty until the `graph` service is ready.
+    pub graph: Arc<RwLock<GraphData>>,
+    /// Vector backend; `None` until the `vector` service is ready.
+    #[cfg(feature = "vector")]
+    pub vector: fabryk_mcp_semantic::VectorSlot,
+    /// Readiness of the full-text search backend.
+    pub fts_service: ServiceHandle,
+    /// Readiness of the graph.
+    pub graph_service: ServiceHandle,
+    /// Readiness of the vector backend (`Stopped` when disabled).
+    #[cfg(feature = "vector")]
+    pub vector_service: ServiceHandle,
+}
+
+impl Backends {
+    /// All service handles, for health reporting.
+    pub fn services(&self) -> Vec<ServiceHandle> {
+        vec![
+            self.fts_service.clone(),
+            self.graph_service.clone(),
+            #[cfg(feature = "vector")]
+            self.vector_service.clone(),
+        ]
+    }
+}
+
+/// Open the full-text search backend and start loading the graph and
+/// vector index in the background.
+pub async fn load_backends<C: ConfigProvider>(
+    config: &C,
+    settings: &ServiceSettings,
+    extractors: &Extractors,
+) -> Result<Backends> {
+    let store = config.content_store(CONTENT_TYPE)?;
+
+    // Full-text search: opening an index is quick, and the tools need the
+    // backend up front.
+    let fts_service = ServiceHandle::new("fts");
+    fts_service.set_state(ServiceState::Starting);
+    let mut fts_config = settings.fts.clone();
+    fts_config.index_path = Some(
+        fts_index_path(config, settings)?
+            .to_string_lossy()
+            .into_owned(),
+    );
+    if let Some(root) = store.root() {
+        fts_config
+            .content_path
+            .get_or_insert_with(|| root.to_string_lossy().into_owned());
+    }
+    let fts: Arc<dyn SearchBackend> =
+        Arc::from(fabryk_fts::create_search_backend(&fts_config).await?);
+    log::info!("Full-text search backend: {}", fts.name());
+    fts_service.set_state(ServiceState::Ready);
+
+    // Graph
+    let graph = Arc::new(RwLock::new(GraphData::new()));
+    let graph_service = ServiceHandle::new("graph");
+    graph_service.set_state(ServiceState::Starting);
+    {
+        let graph = graph.clone();
+        let handle = graph_service.clone();
+        let extractors = extractors.clone();
+        let store = store.clone();
+        let graph_path = graph_file_path(config, settings)?;
+        tokio::spawn(async move {
+            let loaded = match extractors.build_graph(&store, &graph_path, false).await {
+                Some(result) => result.map(|(graph, _)| graph),
+                None => fabryk_graph::load_graph(&graph_path),
+            };
+            match loaded {
+                Ok(loaded) => {
+                    log::info!(
+                        "Graph loaded: {} nodes, {} edges",
+                        loaded.node_count(),
+                        loaded.edge_count()
+                    );
+                    *graph.write().await = loaded;
+                    handle.set_state(ServiceState::Ready);
+                }
+                Err(e) => {
+                    log::error!("Failed to load graph from {}: {e}", graph_path.display());
+                    handle.set_state(ServiceState::Failed(e.to_string()));
+                }
+            }
+        });
+    }
+
+    // Vector
+    #[cfg(feature = "vector")]
+    let (vector, vector_service) = {
+        let slot: fabryk_mcp_semantic::VectorSlot = Arc::new(RwLock::new(None));
+        let handle = ServiceHandle::new("vector");
+        if settings.vector.enabled {
+            handle.set_state(ServiceState::Starting);
+            let task_slot = slot.clone();
+            let task_handle = handle.clone();
+            let vector_config = settings.vector.clone();
+            let extractors = extractors.clone();
+            let store = store.clone();
+            let cache_path = crate::index_handlers::vector_cache_path(config, settings)?;
+            tokio::spawn(async move {
+                match load_vector(&vector_config, &extractors, store, &cache_path).await {
+                    Ok(backend) => {
+                        *task_slot.write().await = Some(Arc::new(backend));
+                        task_handle.set_state(ServiceState::Ready);
+                    }
+                    Err(e) => {
+                        log::error!("Failed to load vector index: {e}");
+                        task_handle.set_state(ServiceState::Failed(e.to_string()));
+                    }
+                }
+            });
+        }
+        (slot, handle)
+    };
+
+    Ok(Backends {
+        fts,
+        graph,
+        #[cfg(feature = "vector")]
+        vector,
+        fts_service,
+        graph_service,
+        #[cfg(feature = "vector")]
+        vector_service,
+    })
+}
+
+/// Build the vector index with the registered extractor, or load the
+/// cache written by `index`. With `index_type = "hnsw"` the index is
+/// searched through an HNSW graph built with the configured settings.
+#[cfg(feature = "vector")]
+async fn load_vector(
+    config: &fabryk_vector::VectorConfig,
+    extractors: &Extractors,
+    store: Arc<dyn fabryk_core::store::ContentStore>,
+    cache_path: &std::path::Path,
+) -> Result<fabryk_vector::SimpleVectorBackend> {
+    let hnsw = crate::index_handlers::vector_hnsw_config(config)?;
+    let provider = fabryk_vector::create_embedding_provider(config).await?;
+    let backend = match extractors.vector() {
+        Some(extractor) => {
+            let mut builder = fabryk_vector::VectorIndexBuilder::new(extractor)
+                .with_content_store(store)
+                .with_embedding_provider(provider)
+                .with_cache_path(cache_path);
+            if let Some(ref path) = config.embedding_cache_path {
+                builder = builder.with_embedding_cache(path);
+            }
+            if let Some(hnsw) = hnsw {
+                builder = builder.with_hnsw(hnsw);
+            }
+            builder.build().await?.0
+        }
+        None => {
+            let backend = fabryk_vector::SimpleVectorBackend::load_cache(cache_path, provider)?
+                .ok_or_else(|| {
+                    Error::operation(format!(
+                        "No vector index at {}; run `index` first",
+                        cache_path.display()
+                    ))
+                })?;
+            match hnsw {
+                Some(hnsw) => backend.with_hnsw(hnsw),
+                None => backend,
+            }
+        }
+    };
+    Ok(backend)
+}
+
+// ============================================================================
+// Server assembly
+// ============================================================================
+
+/// Assemble the MCP server over the given backends.
+///
+/// Each backend's tools are gated on its service; `debug_config` reports
+/// `settings` with the OAuth credentials redacted. Every call's arguments
+/// are checked against the tool's input schema before dispatch, and
+/// callers whose credential scopes don't grant a tool are refused.
+pub fn build_server(
+    name: &str,
+    version: &str,
+    settings: &ServiceSettings,
+    backends: &Backends,
+) -> FabrykMcpServer {
+    let tools = CompositeRegistry::new()
+        .add(ServiceAwareRegistry::new(
+            FtsTools::with_shared(backends.fts.clone()),
+            vec![backends.fts_service.clone()],
+        ))
+        .add(ServiceAwareRegistry::new(
+            GraphTools::with_shared(backends.graph.clone()),
+            vec![backends.graph_service.clone()],
+        ));
+
+    // Semantic search falls back to keyword search while the vector
+    // backend loads, so it only waits for full-text search. Reranking is
+    // library-only: servers that want it build `SemanticSearchTools` with
+    // `with_reranker` themselves.
+    #[cfg(feature = "vector")]
+    let tools = tools.add(ServiceAwareRegistry::new(
+        fabryk_mcp_semantic::SemanticSearchTools::with_vector_slot(
+            backends.fts.clone(),
+            backends.vector.clone(),
+        ),
+        vec![backends.fts_service.clone()],
+    ));
+
+    let tools = tools
+        .add(DiagnosticTools::new(Arc::new(settings.clone())).with_services(backends.services()));
+    let tool_count = tools.tool_count() + 1;
+    let registry = tools.add(HealthTools::new(name, version, tool_count));
+
+    let registry = ScopedRegistry::new(ValidatedRegistry::new(registry), settings.scopes.policy());
+
+    FabrykMcpServer::new(registry)
+        .with_name(name)
+        .with_version(version)
+        .with_services(backends.services())
+}
+
+/// How the HTTP transport authenticates `/mcp`.
+#[derive(Debug, Default)]
+pub struct HttpAuth {
+    /// Validator for `/mcp` bearer tokens; `None` leaves the endpoint open.
+    pub validator: Option<ChainValidator>,
+    /// The built-in authorization server, with `oauth.authorization_server`.
+    pub authorization_server: Option<Arc<AuthorizationServer>>,
+}
+
+/// Set up `/mcp` authentication from `settings`.
+///
+/// Tokens are tried against, in order: API keys (with the `api-keys`
+/// feature and `api_keys.enabled`), tokens from the built-in authorization
+/// server (with `oauth.authorization_server`) and Google tokens (with
+/// `oauth.enabled`).
+#[cfg_attr(not(feature = "api-keys"), allow(unused_variables))]
+pub async fn http_auth<C: ConfigProvider>(
+    config: &C,
+    settings: &ServiceSettings,
+    port: u16,
+) -> Result<HttpAuth> {
+    let mut chain = ChainValidator::new();
+
+    #[cfg(feature = "api-keys")]
+    if settings.api_keys.enabled {
+        let store = crate::keys_handlers::open_key_store(config, settings).await?;
+        log::info!("API keys enabled ({} store)", store.name());
+        chain = chain.with(fabryk_auth_apikey::ApiKeyValidator::new(store));
+    }
+
+    let mut authorization_server = None;
+    if settings.oauth.enabled {
+        let google = Arc::new(GoogleTokenValidator::new(settings.oauth.jwks_url.clone()));
+        if settings.oauth.authorization_server {
+            let upstream = UpstreamConfig::google(
+                settings.oauth.client_id.clone(),
+                settings.oauth.client_secret.clone(),
+            )
+            .with_domain(settings.oauth.domain.clone());
+            let mut server = AuthorizationServer::new(
+                AuthServerConfig::new(public_url(settings, port), upstream),
+                google.clone(),
+            );
+            if !settings.oauth.signing_key.is_empty() {
+                server = server
+                    .with_signing_key(settings.oauth.signing_key.as_bytes())
+                    .map_err(|e| Error::config(format!("oauth.signing_key: {e}")))?;
+            }
+            let server = Arc::new(server);
+            log::info!(
+                "OAuth authorization server enabled at {}",
+                server.config().issuer
+            );
+            chain = chain.with(server.validator());
+            authorization_server = Some(server);
+        }
+        chain = chain.with_shared(google);
+    }
+
+    Ok(HttpAuth {
+        validator: (!chain.is_empty()).then_some(chain),
+        authorization_server,
+    })
+}
+
+/// Build the HTTP router: `/health`, `/mcp` and, with OAuth enabled, the
+/// discovery documents or the built-in authorization server. Only `/mcp`
+/// requires a token, and only when `auth` has a validator.
+pub fn http_router(
+    server: FabrykMcpServer,
+    settings: &ServiceSettings,
+    port: u16,
+    services: Vec<ServiceHandle>,
+    auth: HttpAuth,
+) -> axum::Router {
+    let mcp = axum::Router::new().nest_service("/mcp", server.into_http_service());
+    let router = axum::Router::new().merge(health_router(services));
+
+    let Some(validator) = auth.validator else {
+        return router.merge(mcp);
+    };
+
+    let auth_config = AuthConfig {
+        enabled: true,
+        audience: settings.oauth.client_id.clone(),
+        domain: settings.oauth.domain.clone(),
+    };
+    let router = router.merge(mcp.layer(AuthLayer::new(Arc::new(validator), auth_config)));
+    if let Some(authorization_server) = auth.authorization_server {
+        return router.merge(fabryk_mcp_auth::server::routes(authorization_server));
+    }
+    if !settings.oauth.enabled {
+        return router;
+    }
+    router.merge(fabryk_mcp_auth::discovery_routes(
+        &public_url(settings, port),
+        GOOGLE_AUTH_SERVER,
+    ))
+}
+
+/// The server's public base URL: `server.public_url`, or derived from the
+/// bind address.
+pub fn public_url(settings: &ServiceSettings, port: u16) -> String {
+    match &settings.server.public_url {
+        Some(url) => url.trim_end_matches('/').to_string(),
+        None => {
+            let scheme = if settings.tls.enabled() {
+                "https"
+            } else {
+                "http"
+            };
+            format!("{scheme}://{}:{port}", settings.server.host)
+        }
+    }
+}
+
+// ============================================================================
+// Handlers
+// ============================================================================
+
+/// Validate the settings, load the backends and serve until shut down.
+pub async fn handle_serve<C: ConfigProvider>(
+    name: &str,
+    version: &str,
+    config: &C,
+    settings: &ServiceSettings,
+    extractors: &Extractors,
+    options: ServeOptions,
+) -> Result<()> {
+    settings.tls.validate()?;
+    settings
+        .oauth
+        .validate(&config.project_name().to_uppercase().replace('-', "_"))?;
+
+    let backends = load_backends(config, settings, extractors).await?;
+    let server = build_server(name, version, settings, &backends);
+
+    if options.stdio {
+        return server.serve_stdio().await;
+    }
+
+    let port = options.port.unwrap_or(settings.server.port);
+    let addr: SocketAddr = format!("{}:{port}", settings.server.host)
+        .parse()
+        .map_err(|e| Error::config(format!("Invalid server address: {e}")))?;
+    let auth = http_auth(config, settings, port).await?;
+    let router = http_router(server, settings, port, backends.services(), auth);
+
+    let listener = TcpListener::bind(addr)
+        .await
+        .map_err(|e| Error::operation(format!("bind failed: {e}")))?;
+    log::info!("{name} listening on {}", public_url(settings, port));
+
+    if settings.tls.enabled() {
+        serve_tls(listener, router, &settings.tls).await
+    } else {
+        axum::serve(listener, router)
+            .await
+            .map_err(|e| Error::operation(format!("HTTP server error: {e}")))
+    }
+}
+
+// ============================================================================
+// TLS
+// ============================================================================
+
+/// Load the certificate chain and private key named by `tls`.
+pub fn load_tls_config(tls: &TlsConfig) -> Result<rustls::ServerConfig> {
+    use rustls::pki_types::pem::PemObject;
+    use rustls::pki_types::{CertificateDer, PrivateKeyDer};
+
+    let cert_path = tls.cert_path.as_deref().unwrap_or_default();
+    let key_path = tls.key_path.as_deref().unwrap_or_default();
+
+    let certs = CertificateDer::pem_file_iter(cert_path)
+        .and_then(|certs| certs.collect::<std::result::Result<Vec<_>, _>>())
+        .map_err(|e| Error::config(format!("tls.cert_path '{cert_path}': {e}")))?;
+    let key = PrivateKeyDer::from_pem_file(key_path)
+        .map_err(|e| Error::config(format!("tls.key_path '{key_path}': {e}")))?;
+
+    let _ = rustls::crypto::ring::default_provider().install_default();
+    let mut config = rustls::ServerConfig::builder()
+        .with_no_client_auth()
+        .with_single_cert(certs, key)
+        .map_err(|e| Error::config(format!("Invalid TLS certificate or key: {e}")))?;
+    config.alpn_protocols = vec![b"http/1.1".to_vec()];
+    Ok(config)
+}
+
+/// Serve `router` over HTTPS on `listener`.
+async fn serve_tls(listener: TcpListener, router: axum::Router, tls: &TlsConfig) -> Result<()> {
+    use hyper_util::rt::TokioIo;
+    use hyper_util::service::TowerToHyperService;
+
+    let acceptor = tokio_rustls::TlsAcceptor::from(Arc::new(load_tls_config(tls)?));
+
+    loop {
+        let (stream, peer) = match listener.accept().await {
+            Ok(conn) => conn,
+            Err(e) => {
+                log::warn!("Failed to accept connection: {e}");
+                continue;
+            }
+        };
+        let acceptor = acceptor.clone();
+        let service = TowerToHyperService::new(router.clone());
+
+        tokio::spawn(async move {
+            let stream = match acceptor.accept(stream).await {
+                Ok(stream) => stream,
+                Err(e) => {
+                    log::debug!("TLS handshake with {peer} failed: {e}");
+                    return;
+                }
+            };
+            if let Err(e) = hyper::server::conn::http1::Builder::new()
+                .serve_connection(TokioIo::new(stream), service)
+                .await
+            {
+                log::debug!("Connection from {peer} closed with error: {e}");
+            }
+        });
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::FabrykConfig;
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode};
+    use fabryk_graph::MockExtractor;
+    use fabryk_mcp_core::StreamableHttpServerConfig;
+    use std::time::Duration;
+    use tower::ServiceExt;
+
+    fn project() -> (tempfile::TempDir, FabrykConfig) {
+        let dir = tempfile::TempDir::new().unwrap();
+        let content = dir.path().join(CONTENT_TYPE);
+        std::fs::create_dir_all(&content).unwrap();
+        std::fs::write(
+            content.join("a.md"),
+            "---\ntitle: A\ncategory: basics\n---\nBody",
+        )
+        .unwrap();
+        let config = FabrykConfig {
+            base_path: Some(dir.path().to_string_lossy().into_owned()),
+            ..Default::default()
+        };
+        (dir, config)
+    }
+
+    #[cfg(feature = "vector")]
+    #[tokio::test]
+    async fn test_load_vector_uses_hnsw() {
+        let (dir, config) = project();
+        let mut settings = ServiceSettings::from(&config);
+        settings.vector.provider = "mock".to_string();
+        settings.vector.index_type = "hnsw".to_string();
+        settings.vector.hnsw_m = 8;
+        let store = config.content_store(CONTENT_TYPE).unwrap();
+        let cache_path = dir.path().join("vectors.json");
+        let extractors =
+            Extractors::new().with_vector(fabryk_vector::extractor::MockVectorExtractor);
+
+        let built = load_vector(&settings.vector, &extractors, store.clone(), &cache_path)
+            .await
+            .unwrap();
+        assert_eq!(built.hnsw_config().unwrap().m, 8);
+
+        // Serving from the cache written by `index` keeps the HNSW graph.
+        let loaded = load_vector(
+            &settings.vector,
+            &Extractors::new(),
+            store.clone(),
+            &cache_path,
+        )
+        .await
+        .unwrap();
+        assert_eq!(loaded.hnsw_config().unwrap().m, 8);
+
+        settings.vector.index_type = "flat".to_string();
+        let flat_cache = dir.path().join("flat.json");
+        let flat = load_vector(&settings.vector, &extractors, store.clone(), &flat_cache)
+            .await
+            .unwrap();
+        assert!(flat.hnsw_config().is_none());
+
+        settings.vector.index_type = "ivf".to_string();
+        assert!(
+            load_vector(&settings.vector, &extractors, store, &cache_path)
+                .await
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_load_backends_builds_graph() {
+        let (_dir, config) = project();
+        let settings = ServiceSettings::from(&config);
+        let extractors = Extractors::new().with_graph(MockExtractor);
+
+        let backends = load_backends(&config, &settings, &extractors)
+            .await
+            .unwrap();
+        assert!(backends.fts_service.state().is_ready());
+        backends
+            .graph_service
+            .wait_ready(Duration::from_secs(5))
+            .await
+            .unwrap();
+        assert_eq!(backends.graph.read().await.node_count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_load_backends_without_graph_fails_graph_service() {
+        let (_dir, config) = project();
+        let settings = ServiceSettings::from(&config);
+
+        let backends = load_backends(&config, &settings, &Extractors::new())
+            .await
+            .unwrap();
+        let mut states = backends.graph_service.subscribe();
+        let state = states.wait_for(|s| s.is_terminal()).await.unwrap().clone();
+        assert!(matches!(state, ServiceState::Failed(_)));
+    }
+
+    #[tokio::test]
+    async fn test_build_server_tools() {
+        let (_dir, config) = project();
+        let settings = ServiceSettings::from(&config);
+        let backends = load_backends(&config, &settings, &Extractors::new())
+            .await
+            .unwrap();
+
+        let server = build_server("test-app", "1.0.0", &settings, &backends);
+        let names: Vec<String> = server
+            .registry()
+            .tools()
+            .into_iter()
+            .map(|t| t.name.to_string())
+            .collect();
+        assert!(names.contains(&"search".to_string()));
+        assert!(names.contains(&"graph_related".to_string()));
+        assert!(names.contains(&"debug_config".to_string()));
+        assert!(names.contains(&"health".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_served_tool_calls_reject_bad_arguments() {
+        let (_dir, config) = project();
+        let settings = ServiceSettings::from(&config);
+        let backends = load_backends(&config, &settings, &Extractors::new())
+            .await
+            .unwrap();
+        let server = build_server("test-app", "1.0.0", &settings, &backends);
+        let service = server.into_http_service_with_config(StreamableHttpServerConfig {
+            stateful_mode: false,
+            json_response: true,
+            ..Default::default()
+        });
+        let call = |arguments: serde_json::Value| {
+            let body = serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "method": "tools/call",
+                "params": { "name": "search", "arguments": arguments }
+            });
+            Request::post("/")
+                .header("content-type", "application/json")
+                .header("accept", "application/json, text/event-stream")
+                .header("mcp-protocol-version", "2025-06-18")
+                .body(Body::from(body.to_string()))
+                .unwrap()
+        };
+        let response_json = |response: axum::response::Response| async move {
+            let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            serde_json::from_slice::<serde_json::Value>(&body).unwrap()
+        };
+
+        let response = service
+            .clone()
+            .oneshot(call(serde_json::json!({ "query": "a", "limit": 0 })))
+            .await
+            .unwrap();
+        let body = response_json(response.map(Body::new)).await;
+        assert_eq!(body["error"]["code"], -32602, "{body}");
+        assert_eq!(body["error"]["data"]["errors"][0]["path"], "limit");
+
+        let response = service
+            .oneshot(call(serde_json::json!({ "query": "a" })))
+            .await
+            .unwrap();
+        let body = response_json(response.map(Body::new)).await;
+        assert!(body.get("error").is_none(), "{body}");
+    }
+
+    #[tokio::test]
+    async fn test_served_tool_calls_enforce_scopes() {
+        let (_dir, config) = project();
+        let mut settings = ServiceSettings::from(&config);
+        settings
+            .scopes
+            .grants
+            .insert("mcp:read".into(), vec!["search".into()]);
+        let backends = load_backends(&config, &settings, &Extractors::new())
+            .await
+            .unwrap();
+        let server = build_server("test-app", "1.0.0", &settings, &backends);
+        let service = server.into_http_service_with_config(StreamableHttpServerConfig {
+            stateful_mode: false,
+            json_response: true,
+            ..Default::default()
+        });
+        // The user AuthLayer would attach for an API key minted with
+        // `--scope mcp:read`.
+        let call = |tool: &str| {
+            let body = serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "method": "tools/call",
+                "params": { "name": tool, "arguments": { "query": "a" } }
+            });
+            let mut request = Request::post("/")
+                .header("content-type", "application/json")
+                .header("accept", "application/json, text/event-stream")
+                .header("mcp-protocol-version", "2025-06-18")
+                .body(Body::from(body.to_string()))
+                .unwrap();
+            request.extensions_mut().insert(
+                fabryk_auth::AuthenticatedUser::new("ci@example.com", "apikey:1")
+                    .with_scopes(["mcp:read"]),
+            );
+            request
+        };
+        let response_json = |response: axum::response::Response| async move {
+            let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            serde_json::from_slice::<serde_json::Value>(&body).unwrap()
+        };
+
+        let response = service.clone().oneshot(call("search")).await.unwrap();
+        let body = response_json(response.map(Body::new)).await;
+        assert!(body.get("error").is_none(), "{body}");
+
+        let response = service.oneshot(call("debug_config")).await.unwrap();
+        let body = response_json(response.map(Body::new)).await;
+        assert_eq!(body["error"]["code"], -32600, "{body}");
+        assert_eq!(body["error"]["data"]["tool"], "debug_config");
+    }
+
+    #[tokio::test]
+    async fn test_http_router_oauth() {
+        let (_dir, config) = project();
+        let mut settings = ServiceSettings::from(&config);
+        settings.oauth.enabled = true;
+        settings.oauth.client_id = "client".into();
+        let backends = load_backends(&config, &settings, &Extractors::new())
+            .await
+            .unwrap();
+        let server = build_server("test-app", "1.0.0", &settings, &backends);
+        let auth = http_auth(&config, &settings, 8443).await.unwrap();
+        let router = http_router(server, &settings, 8443, backends.services(), auth);
+
+        // Health and discovery are public; only /mcp needs a token.
+        let get = |uri: &str| Request::get(uri).body(Body::empty()).unwrap();
+        let response = router.clone().oneshot(get("/health")).await.unwrap();
+        assert_ne!(response.status(), StatusCode::UNAUTHORIZED);
+        let response = router
+            .clone()
+            .oneshot(get("/.well-known/oauth-protected-resource"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let response = router.oneshot(get("/mcp")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_http_auth_signing_key() {
+        let (_dir, config) = project();
+        let mut settings = ServiceSettings::from(&config);
+        settings.oauth.enabled = true;
+        settings.oauth.client_id = "client".into();
+        settings.oauth.client_secret = "secret".into();
+        settings.oauth.authorization_server = true;
+        settings.oauth.signing_key = "too-short".into();
+        let err = http_auth(&config, &settings, 8443).await.unwrap_err();
+        assert!(err.to_string().contains("oauth.signing_key"), "{err}");
+
+        settings.oauth.signing_key = "k".repeat(32);
+        let auth = http_auth(&config, &settings, 8443).await.unwrap();
+        assert!(auth.authorization_server.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_http_router_authorization_server() {
+        let (_dir, config) = project();
+        let mut settings = ServiceSettings::from(&config);
+        settings.oauth.enabled = true;
+        settings.oauth.client_id = "client".into();
+        settings.oauth.client_secret = "secret".into();
+        settings.oauth.authorization_server = true;
+        settings.server.public_url = Some("https://kb.example.com".into());
+        let backends = load_backends(&config, &settings, &Extractors::new())
+            .await
+            .unwrap();
+        let server = build_server("test-app", "1.0.0", &settings, &backends);
+        let auth = http_auth(&config, &settings, 8443).await.unwrap();
+        assert!(auth.authorization_server.is_some());
+        assert_eq!(auth.validator.as_ref().map(ChainValidator::len), Some(2));
+        let router = http_router(server, &settings, 8443, backends.services(), auth);
+
+        let response = router
+            .clone()
+            .oneshot(
+                Request::get("/.well-known/oauth-authorization-server")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let metadata: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(metadata["issuer"], "https://kb.example.com");
+        assert_eq!(
+            metadata["registration_endpoint"],
+            "https://kb.example.com/register"
+        );
+
+        let response = router
+            .oneshot(Request::get("/mcp").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[cfg(feature = "api-keys")]
+    #[tokio::test]
+    async fn test_http_router_api_keys() {
+        let (_dir, config) = project();
+        let mut settings = ServiceSettings::from(&config);
+        settings.api_keys.enabled = true;
+        let store = crate::keys_handlers::open_key_store(&config, &settings)
+            .await
+            .unwrap();
+        let (key, token) = fabryk_auth_apikey::ApiKey::mint("ci-bot@example.com", ["mcp"], None);
+        store.put(&key).await.unwrap();
+
+        let backends = load_backends(&config, &settings, &Extractors::new())
+            .await
+            .unwrap();
+        let server = build_server("test-app", "1.0.0", &settings, &backends);
+        let auth = http_auth(&config, &settings, 3000).await.unwrap();
+        assert_eq!(auth.validator.as_ref().map(ChainValidator::len), Some(1));
+        let router = http_router(server, &settings, 3000, backends.services(), auth);
+
+        let mcp = |token: &str| {
+            Request::get("/mcp")
+                .header("Authorization", format!("Bearer {token}"))
+                .body(Body::empty())
+                .unwrap()
+        };
+        let response = router.clone().oneshot(mcp(&token)).await.unwrap();
+        assert_ne!(response.status(), StatusCode::UNAUTHORIZED);
+        let response = router.clone().oneshot(mcp("fbk_00ff_wrong")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let response = router
+            .oneshot(Request::get("/health").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_ne!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[test]
+    fn test_public_url() {
+        let mut settings = ServiceSettings::default();
+        assert_eq!(public_url(&settings, 3000), "http://127.0.0.1:3000");
+
+        settings.server.public_url = Some("https://kb.example.com/".into());
+        assert_eq!(public_url(&settings, 3000), "https://kb.example.com");
+    }
+
+    #[test]
+    fn test_load_tls_config_rejects_bad_pem() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let cert = dir.path().join("cert.pem");
+        let key = dir.path().join("key.pem");
+        std::fs::write(&cert, "not a certificate").unwrap();
+        std::fs::write(&key, "not a key").unwrap();
+
+        let tls = TlsConfig {
+            cert_path: Some(cert.to_string_lossy().into_owned()),
+            key_path: Some(key.to_string_lossy().into_owned()),
+        };
+        assert!(load_tls_config(&tls).is_err());
+    }
+}
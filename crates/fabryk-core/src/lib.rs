@@ -7,6 +7,7 @@
 //!
 //! - [`error`]: Error types and Result alias
 //! - [`state`]: Generic application state container
+//! - [`store`]: Content stores (local directory, in-memory)
 //! - [`traits`]: Core traits for domain abstraction
 //! - [`util`]: File, path, and ID utilities
 
@@ -16,6 +17,7 @@ pub mod deploy;
 pub mod error;
 pub mod service;
 pub mod state;
+pub mod store;
 pub mod traits;
 pub mod util;
 
@@ -25,6 +27,7 @@ pub use service::{
     RetryConfig, ServiceHandle, ServiceState, Transition, spawn_with_retry, wait_all_ready,
 };
 pub use state::AppState;
+pub use store::{ContentEntry, ContentStore};
 pub use traits::ConfigManager;
 pub use traits::ConfigProvider;
 
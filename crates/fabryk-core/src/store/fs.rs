@@ -0,0 +1,131 @@
+//! Content store backed by a local directory.
+
+use std::path::{Path, PathBuf};
+
+use async_trait::async_trait;
+
+use super::{ContentEntry, ContentStore, validate_key};
+use crate::util::files::{FindOptions, find_all_files};
+use crate::{Error, Result};
+
+/// Content in a directory on local disk.
+///
+/// Keys are file paths relative to the directory.
+#[derive(Clone, Debug)]
+pub struct FsContentStore {
+    root: PathBuf,
+}
+
+impl FsContentStore {
+    /// Serve the files under `root`.
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    fn file_path(&self, key: &str) -> Result<PathBuf> {
+        validate_key(key)?;
+        Ok(self.root.join(key))
+    }
+}
+
+/// Build an entry from file metadata.
+fn entry(key: String, metadata: &std::fs::Metadata) -> ContentEntry {
+    let entry = ContentEntry::new(key, metadata.len());
+    match metadata.modified() {
+        Ok(modified) => entry.with_modified(modified),
+        Err(_) => entry,
+    }
+}
+
+/// A relative path as a `/`-separated key.
+fn key_for(relative: &Path) -> String {
+    relative
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+#[async_trait]
+impl ContentStore for FsContentStore {
+    fn location(&self) -> String {
+        self.root.to_string_lossy().to_string()
+    }
+
+    fn root(&self) -> Option<&Path> {
+        Some(&self.root)
+    }
+
+    async fn list(&self) -> Result<Vec<ContentEntry>> {
+        if !self.root.is_dir() {
+            return Err(Error::not_found("content directory", self.location()));
+        }
+
+        let mut entries = Vec::new();
+        for file in find_all_files(&self.root, FindOptions::default()).await? {
+            match tokio::fs::metadata(&file.path).await {
+                Ok(metadata) => entries.push(entry(key_for(&file.relative_path), &metadata)),
+                Err(e) => log::warn!("Skipping {}: {e}", file.path.display()),
+            }
+        }
+        entries.sort_by(|a, b| a.key.cmp(&b.key));
+        Ok(entries)
+    }
+
+    async fn get(&self, key: &str) -> Result<Vec<u8>> {
+        let path = self.file_path(key)?;
+        tokio::fs::read(&path).await.map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => Error::not_found("content", key),
+            _ => Error::io_with_path(e, &path),
+        })
+    }
+
+    async fn stat(&self, key: &str) -> Result<Option<ContentEntry>> {
+        let path = self.file_path(key)?;
+        match tokio::fs::metadata(&path).await {
+            Ok(metadata) if metadata.is_file() => Ok(Some(entry(key.to_string(), &metadata))),
+            Ok(_) => Ok(None),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(Error::io_with_path(e, &path)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(root: &Path, key: &str, content: &str) {
+        let path = root.join(key);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_fs_store() {
+        let dir = tempfile::tempdir().unwrap();
+        write(dir.path(), "b.md", "bee");
+        write(dir.path(), "a/c.md", "sea");
+        let store = FsContentStore::new(dir.path());
+
+        let entries = store.list().await.unwrap();
+        let keys: Vec<&str> = entries.iter().map(|e| e.key.as_str()).collect();
+        assert_eq!(keys, ["a/c.md", "b.md"]);
+        assert_eq!(entries[0].size, 3);
+        assert!(entries[0].modified.is_some());
+
+        assert_eq!(store.get_string("a/c.md").await.unwrap(), "sea");
+        assert_eq!(store.stat("b.md").await.unwrap().unwrap().size, 3);
+        assert_eq!(store.stat("missing.md").await.unwrap(), None);
+        assert_eq!(store.stat("a").await.unwrap(), None);
+        assert!(store.get("missing.md").await.unwrap_err().is_not_found());
+        assert!(store.get("../escape.md").await.unwrap_err().is_path_error());
+        assert_eq!(store.path("a/c.md"), dir.path().join("a/c.md"));
+    }
+
+    #[tokio::test]
+    async fn test_fs_store_missing_root() {
+        let store = FsContentStore::new("/nonexistent/fabryk/content");
+        assert!(store.list().await.unwrap_err().is_not_found());
+    }
+}
@@ -0,0 +1,142 @@
+//! In-memory content store.
+
+use std::collections::BTreeMap;
+use std::sync::RwLock;
+use std::time::SystemTime;
+
+use async_trait::async_trait;
+
+use super::{ContentEntry, ContentStore};
+use crate::{Error, Result};
+
+/// Content held in memory.
+///
+/// Useful for tests and for content generated at runtime. Each
+/// [`insert`](Self::insert) bumps the item's modification time and version.
+#[derive(Debug, Default)]
+pub struct MemoryContentStore {
+    items: RwLock<BTreeMap<String, MemoryItem>>,
+}
+
+#[derive(Debug)]
+struct MemoryItem {
+    data: Vec<u8>,
+    modified: SystemTime,
+    version: u64,
+}
+
+impl MemoryItem {
+    fn entry(&self, key: &str) -> ContentEntry {
+        ContentEntry::new(key, self.data.len() as u64)
+            .with_modified(self.modified)
+            .with_version(self.version.to_string())
+    }
+}
+
+impl MemoryContentStore {
+    /// An empty store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add `data` under `key`, replacing any existing item.
+    pub fn insert(&self, key: impl Into<String>, data: impl Into<Vec<u8>>) {
+        let mut items = self.items.write().unwrap();
+        let key = key.into();
+        let version = items.get(&key).map_or(1, |item| item.version + 1);
+        items.insert(
+            key,
+            MemoryItem {
+                data: data.into(),
+                modified: SystemTime::now(),
+                version,
+            },
+        );
+    }
+
+    /// Add `data` under `key`, builder-style.
+    pub fn with_item(self, key: impl Into<String>, data: impl Into<Vec<u8>>) -> Self {
+        self.insert(key, data);
+        self
+    }
+
+    /// Remove the item under `key`, returning whether there was one.
+    pub fn remove(&self, key: &str) -> bool {
+        self.items.write().unwrap().remove(key).is_some()
+    }
+
+    /// Number of items.
+    pub fn len(&self) -> usize {
+        self.items.read().unwrap().len()
+    }
+
+    /// Whether the store is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[async_trait]
+impl ContentStore for MemoryContentStore {
+    fn location(&self) -> String {
+        "memory".to_string()
+    }
+
+    async fn list(&self) -> Result<Vec<ContentEntry>> {
+        let items = self.items.read().unwrap();
+        Ok(items.iter().map(|(key, item)| item.entry(key)).collect())
+    }
+
+    async fn get(&self, key: &str) -> Result<Vec<u8>> {
+        self.items
+            .read()
+            .unwrap()
+            .get(key)
+            .map(|item| item.data.clone())
+            .ok_or_else(|| Error::not_found("content", key))
+    }
+
+    async fn stat(&self, key: &str) -> Result<Option<ContentEntry>> {
+        Ok(self
+            .items
+            .read()
+            .unwrap()
+            .get(key)
+            .map(|item| item.entry(key)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_memory_store() {
+        let store = MemoryContentStore::new()
+            .with_item("b.md", "bee")
+            .with_item("a.md", "ay");
+        assert_eq!(store.len(), 2);
+
+        let keys: Vec<String> = store
+            .list()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|e| e.key)
+            .collect();
+        assert_eq!(keys, ["a.md", "b.md"]);
+        assert_eq!(store.get_string("b.md").await.unwrap(), "bee");
+
+        let first = store.stat("a.md").await.unwrap().unwrap();
+        store.insert("a.md", "ay!");
+        let second = store.stat("a.md").await.unwrap().unwrap();
+        assert_eq!(second.size, 3);
+        assert_ne!(first.version, second.version);
+
+        assert!(store.remove("a.md"));
+        assert!(!store.remove("a.md"));
+        assert_eq!(store.stat("a.md").await.unwrap(), None);
+        assert!(store.get("a.md").await.unwrap_err().is_not_found());
+        assert_eq!(store.path("b.md"), std::path::PathBuf::from("b.md"));
+    }
+}
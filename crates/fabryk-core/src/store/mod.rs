@@ -0,0 +1,399 @@
+//! Content stores: where domain content is read from.
+//!
+//! A [`ContentStore`] is a flat set of keyed blobs (usually markdown
+//! files), addressed by `/`-separated keys such as `harmony/cadence.md`.
+//! The index builders and content tools read through it, so content can
+//! live on local disk, in a bucket or in memory.
+//!
+//! # Implementations
+//!
+//! - [`FsContentStore`]: a directory on local disk
+//! - [`MemoryContentStore`]: an in-memory map, for tests and generated content
+//!
+//! Remote stores live in their own crates (e.g., `fabryk-store-gcs`).
+//!
+//! # Example
+//!
+//! ```no_run
+//! use fabryk_core::store::{ContentStore, FsContentStore};
+//!
+//! # async fn example() -> fabryk_core::Result<()> {
+//! let store = FsContentStore::new("/data/concepts");
+//! for entry in store.list().await? {
+//!     let text = store.get_string(&entry.key).await?;
+//!     println!("{}: {} bytes", entry.key, text.len());
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+mod fs;
+mod memory;
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use async_trait::async_trait;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+
+use crate::{Error, Result};
+
+pub use fs::FsContentStore;
+pub use memory::MemoryContentStore;
+
+/// Metadata about one item in a [`ContentStore`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ContentEntry {
+    /// Key of the item, relative to the store root, with `/` separators.
+    pub key: String,
+    /// Size in bytes.
+    pub size: u64,
+    /// Last modification time, if the store tracks it.
+    pub modified: Option<SystemTime>,
+    /// Store-specific version (e.g., a GCS object generation), if any.
+    pub version: Option<String>,
+}
+
+impl ContentEntry {
+    /// An entry for `key` with `size` bytes.
+    pub fn new(key: impl Into<String>, size: u64) -> Self {
+        Self {
+            key: key.into(),
+            size,
+            modified: None,
+            version: None,
+        }
+    }
+
+    /// Set the modification time.
+    pub fn with_modified(mut self, modified: SystemTime) -> Self {
+        self.modified = Some(modified);
+        self
+    }
+
+    /// Set the store-specific version.
+    pub fn with_version(mut self, version: impl Into<String>) -> Self {
+        self.version = Some(version.into());
+        self
+    }
+
+    /// The key's file extension, without the dot.
+    pub fn extension(&self) -> Option<&str> {
+        let name = self.key.rsplit('/').next()?;
+        let (stem, ext) = name.rsplit_once('.')?;
+        (!stem.is_empty()).then_some(ext)
+    }
+
+    /// Modification time in whole seconds since the epoch, or 0 if unknown.
+    pub fn modified_secs(&self) -> u64 {
+        self.modified
+            .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+            .map(|d| d.as_secs())
+            .unwrap_or(0)
+    }
+}
+
+/// How a content item changed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ChangeKind {
+    /// The item is new.
+    Added,
+    /// The item's size, modification time or version changed.
+    Modified,
+    /// The item is gone.
+    Removed,
+}
+
+/// A change reported by [`ContentStore::watch`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ContentChange {
+    /// Key of the changed item.
+    pub key: String,
+    /// What happened to it.
+    pub kind: ChangeKind,
+}
+
+/// A source of content items.
+///
+/// Keys are `/`-separated paths relative to the store root. [`list`]
+/// returns entries sorted by key.
+///
+/// [`list`]: ContentStore::list
+#[async_trait]
+pub trait ContentStore: Send + Sync + std::fmt::Debug + 'static {
+    /// Where the content lives, for logs and freshness keys (e.g., a
+    /// directory or `gs://bucket/prefix`).
+    fn location(&self) -> String;
+
+    /// The local directory backing the store, if there is one.
+    fn root(&self) -> Option<&Path> {
+        None
+    }
+
+    /// The path extractors see for `key`: the file's path for local
+    /// stores, the key itself otherwise.
+    fn path(&self, key: &str) -> PathBuf {
+        match self.root() {
+            Some(root) => root.join(key),
+            None => PathBuf::from(key),
+        }
+    }
+
+    /// List every item, sorted by key.
+    async fn list(&self) -> Result<Vec<ContentEntry>>;
+
+    /// Read an item.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::NotFound`] if there is no item under `key`.
+    async fn get(&self, key: &str) -> Result<Vec<u8>>;
+
+    /// Metadata for an item, or `None` if there is no item under `key`.
+    async fn stat(&self, key: &str) -> Result<Option<ContentEntry>>;
+
+    /// Read an item as UTF-8 text.
+    async fn get_string(&self, key: &str) -> Result<String> {
+        String::from_utf8(self.get(key).await?)
+            .map_err(|e| Error::parse(format!("{key} is not valid UTF-8: {e}")))
+    }
+
+    /// Report changes to the store's items.
+    ///
+    /// The default implementation lists the store every `interval` and
+    /// reports the difference from the previous listing. Changes made
+    /// before the first listing are not reported.
+    fn watch(self: Arc<Self>, interval: Duration) -> ContentWatch {
+        let (tx, rx) = mpsc::channel(16);
+        let task = tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            let mut previous: Option<Vec<ContentEntry>> = None;
+            loop {
+                ticker.tick().await;
+                let current = match self.list().await {
+                    Ok(entries) => entries,
+                    Err(e) => {
+                        log::warn!("Failed to list {}: {e}", self.location());
+                        continue;
+                    }
+                };
+                let changes = previous
+                    .as_deref()
+                    .map(|before| diff_entries(before, &current))
+                    .unwrap_or_default();
+                previous = Some(current);
+                if !changes.is_empty() && tx.send(changes).await.is_err() {
+                    break;
+                }
+            }
+        });
+        ContentWatch { rx, task }
+    }
+}
+
+/// Changes reported by [`ContentStore::watch`].
+///
+/// Watching stops when this is dropped.
+#[derive(Debug)]
+pub struct ContentWatch {
+    rx: mpsc::Receiver<Vec<ContentChange>>,
+    task: JoinHandle<()>,
+}
+
+impl ContentWatch {
+    /// Wait for the next batch of changes.
+    ///
+    /// Returns `None` once the watcher has stopped.
+    pub async fn next(&mut self) -> Option<Vec<ContentChange>> {
+        self.rx.recv().await
+    }
+}
+
+impl Drop for ContentWatch {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// The changes between two listings, in key order.
+pub fn diff_entries(before: &[ContentEntry], after: &[ContentEntry]) -> Vec<ContentChange> {
+    let before: BTreeMap<&str, &ContentEntry> =
+        before.iter().map(|e| (e.key.as_str(), e)).collect();
+    let after: BTreeMap<&str, &ContentEntry> = after.iter().map(|e| (e.key.as_str(), e)).collect();
+
+    let mut changes: Vec<ContentChange> = Vec::new();
+    for (key, entry) in &after {
+        let kind = match before.get(key) {
+            None => ChangeKind::Added,
+            Some(old) if old != entry => ChangeKind::Modified,
+            Some(_) => continue,
+        };
+        changes.push(ContentChange {
+            key: key.to_string(),
+            kind,
+        });
+    }
+    for key in before.keys().filter(|key| !after.contains_key(*key)) {
+        changes.push(ContentChange {
+            key: key.to_string(),
+            kind: ChangeKind::Removed,
+        });
+    }
+    changes.sort_by(|a, b| a.key.cmp(&b.key));
+    changes
+}
+
+/// A freshness hash of store entries.
+///
+/// Hashes each entry's key, size, modification time and version in key
+/// order. Only listing metadata is read, so checking a remote store never
+/// downloads content.
+pub fn hash_entries<'a>(entries: impl IntoIterator<Item = &'a ContentEntry>) -> String {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+
+    let mut entries: Vec<&ContentEntry> = entries.into_iter().collect();
+    entries.sort_by(|a, b| a.key.cmp(&b.key));
+
+    let mut hasher = DefaultHasher::new();
+    for entry in entries {
+        entry.key.hash(&mut hasher);
+        entry.size.hash(&mut hasher);
+        entry.modified_secs().hash(&mut hasher);
+        entry.version.hash(&mut hasher);
+    }
+    format!("{:016x}", hasher.finish())
+}
+
+/// A freshness hash of every item in `store`; see [`hash_entries`].
+pub async fn compute_store_hash(store: &dyn ContentStore) -> Result<String> {
+    Ok(hash_entries(&store.list().await?))
+}
+
+/// Check that `key` is a relative, `/`-separated path without `.` or `..`
+/// segments.
+pub fn validate_key(key: &str) -> Result<()> {
+    let valid = !key.is_empty()
+        && !key.starts_with('/')
+        && !key.contains('\\')
+        && key
+            .split('/')
+            .all(|segment| !segment.is_empty() && segment != "." && segment != "..");
+    if valid {
+        Ok(())
+    } else {
+        Err(Error::invalid_path(key, "not a valid content key"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_entry_extension() {
+        assert_eq!(ContentEntry::new("a/b.md", 0).extension(), Some("md"));
+        assert_eq!(ContentEntry::new("a.b/c", 0).extension(), None);
+        assert_eq!(ContentEntry::new(".hidden", 0).extension(), None);
+        assert_eq!(
+            ContentEntry::new("x", 0)
+                .with_modified(UNIX_EPOCH + Duration::from_secs(42))
+                .modified_secs(),
+            42
+        );
+    }
+
+    #[tokio::test]
+    async fn test_compute_store_hash() {
+        let entry = ContentEntry::new("a.md", 3).with_modified(UNIX_EPOCH);
+        let hash = hash_entries([&entry]);
+
+        assert_eq!(hash, hash_entries([&entry.clone()]));
+        assert_ne!(
+            hash,
+            hash_entries([&ContentEntry {
+                size: 4,
+                ..entry.clone()
+            }])
+        );
+        assert_ne!(hash, hash_entries([&entry.clone().with_version("2")]));
+        assert_ne!(
+            hash,
+            hash_entries([&entry
+                .clone()
+                .with_modified(UNIX_EPOCH + Duration::from_secs(1))])
+        );
+
+        let store = MemoryContentStore::new()
+            .with_item("b.md", "x")
+            .with_item("a.md", "y");
+        let listed = store.list().await.unwrap();
+        assert_eq!(
+            compute_store_hash(&store).await.unwrap(),
+            hash_entries(listed.iter().rev())
+        );
+    }
+
+    #[test]
+    fn test_diff_entries() {
+        let before = vec![
+            ContentEntry::new("a.md", 1),
+            ContentEntry::new("b.md", 1),
+            ContentEntry::new("c.md", 1),
+        ];
+        let after = vec![
+            ContentEntry::new("a.md", 1),
+            ContentEntry::new("b.md", 2),
+            ContentEntry::new("d.md", 1),
+        ];
+        let changes = diff_entries(&before, &after);
+        let summary: Vec<(&str, ChangeKind)> =
+            changes.iter().map(|c| (c.key.as_str(), c.kind)).collect();
+        assert_eq!(
+            summary,
+            [
+                ("b.md", ChangeKind::Modified),
+                ("c.md", ChangeKind::Removed),
+                ("d.md", ChangeKind::Added),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_key() {
+        assert!(validate_key("a/b.md").is_ok());
+        for key in ["", "/a.md", "a/../b.md", "./a.md", "a//b.md", "a\\b.md"] {
+            assert!(validate_key(key).is_err(), "{key}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_watch_reports_changes() {
+        let store = Arc::new(MemoryContentStore::new());
+        store.insert("a.md", "one");
+        let mut watch = store.clone().watch(Duration::from_millis(10));
+
+        // Let the first listing happen before changing anything.
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        store.insert("b.md", "two");
+        store.remove("a.md");
+
+        let mut changes = Vec::new();
+        while changes.len() < 2 {
+            let batch = tokio::time::timeout(Duration::from_secs(2), watch.next())
+                .await
+                .unwrap()
+                .unwrap();
+            changes.extend(batch);
+        }
+        changes.sort_by(|a, b| a.key.cmp(&b.key));
+        assert_eq!(changes[0].key, "a.md");
+        assert_eq!(changes[0].kind, ChangeKind::Removed);
+        assert_eq!(changes[1].key, "b.md");
+        assert_eq!(changes[1].kind, ChangeKind::Added);
+    }
+}
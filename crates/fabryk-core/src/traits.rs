@@ -5,8 +5,10 @@
 //! which abstracts domain-specific configuration.
 
 use std::path::PathBuf;
+use std::sync::Arc;
 
 use crate::Result;
+use crate::store::{ContentStore, FsContentStore};
 
 /// Trait for domain-specific configuration.
 ///
@@ -119,6 +121,23 @@ pub trait ConfigProvider: Send + Sync + Clone + 'static {
     /// ```
     fn content_path(&self, content_type: &str) -> Result<PathBuf>;
 
+    /// Store holding a specific content type.
+    ///
+    /// Index builders and content tools read content through this. The
+    /// default implementation serves the directory at
+    /// [`content_path()`](Self::content_path); override it to read content
+    /// from elsewhere, such as a bucket.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the content type is unknown or the store
+    /// cannot be set up.
+    fn content_store(&self, content_type: &str) -> Result<Arc<dyn ContentStore>> {
+        Ok(Arc::new(FsContentStore::new(
+            self.content_path(content_type)?,
+        )))
+    }
+
     /// Path for a specific cache type.
     ///
     /// `cache_type` is a framework-defined key like `"graph"`, `"fts"`,
@@ -312,6 +331,16 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_config_provider_content_store_default() {
+        let config = TestConfig {
+            name: "test".into(),
+            base: PathBuf::from("/data"),
+        };
+        let store = config.content_store("concepts").unwrap();
+        assert_eq!(store.root(), Some(std::path::Path::new("/data/concepts")));
+    }
+
     #[test]
     fn test_config_provider_is_clone() {
         let config = TestConfig {
@@ -34,12 +34,16 @@
 //!
 //! let stats = builder.build(&content_path, &index_path).await?;
 //! println!("Indexed {} documents", stats.documents_indexed);
+//!
+//! // Or from any content store
+//! let stats = builder.build_from_store(store.as_ref(), &index_path).await?;
 //! ```
 
 use std::collections::HashSet;
 use std::path::{Path, PathBuf};
 
 use async_walkdir::WalkDir;
+use fabryk_core::store::{ContentStore, FsContentStore};
 use fabryk_core::{Error, Result};
 use futures::StreamExt;
 
@@ -206,13 +210,8 @@ impl IndexBuilder {
 
     /// Build an index from the given content path.
     ///
-    /// This method:
-    /// 1. Computes content hash for freshness checking
-    /// 2. Checks if index is already fresh (unless forced)
-    /// 3. Discovers all supported files
-    /// 4. Extracts documents using the configured extractor
-    /// 5. Indexes documents in batches
-    /// 6. Saves metadata for freshness checking
+    /// Equivalent to [`build_from_store`](Self::build_from_store) with an
+    /// [`FsContentStore`] for `content_path`.
     ///
     /// # Errors
     ///
@@ -229,8 +228,29 @@ impl IndexBuilder {
             ));
         }
 
+        self.build_from_store(&FsContentStore::new(content_path), index_path)
+            .await
+    }
+
+    /// Build an index from a content store.
+    ///
+    /// This method:
+    /// 1. Computes content hash for freshness checking
+    /// 2. Checks if index is already fresh (unless forced)
+    /// 3. Discovers all supported items
+    /// 4. Extracts documents using the configured extractor
+    /// 5. Indexes documents in batches
+    /// 6. Saves metadata for freshness checking
+    ///
+    /// The extractor sees each item's local path, or its key for stores
+    /// without a local directory.
+    pub async fn build_from_store(
+        &self,
+        store: &dyn ContentStore,
+        index_path: &Path,
+    ) -> Result<IndexStats> {
         // Compute content hash
-        let content_hash = IndexMetadata::compute_hash(content_path).await?;
+        let content_hash = IndexMetadata::compute_store_hash(store).await?;
 
         // Check freshness (unless forced)
         if !self.skip_freshness_check
@@ -245,7 +265,7 @@ impl IndexBuilder {
             });
         }
 
-        log::info!("Building index from {:?}", content_path);
+        log::info!("Building index from {}", store.location());
 
         // Create schema and indexer
         let schema = SearchSchema::build();
@@ -254,71 +274,11 @@ impl IndexBuilder {
         // Clear existing documents
         indexer.clear()?;
 
-        // Collect supported extensions
-        let extensions: HashSet<_> = self
-            .extractor
-            .supported_extensions()
-            .iter()
-            .map(|s| s.to_lowercase())
-            .collect();
-
-        // Find all files
-        let files = find_files_with_extensions(content_path, &extensions).await?;
-
         let mut stats = IndexStats {
             content_hash: content_hash.clone(),
             ..Default::default()
         };
-
-        let mut batch_count = 0;
-
-        // Process files
-        for file_path in files {
-            stats.files_processed += 1;
-
-            // Read file content
-            let content = match tokio::fs::read_to_string(&file_path).await {
-                Ok(c) => c,
-                Err(e) => {
-                    log::warn!("Failed to read {:?}: {}", file_path, e);
-                    stats.errors += 1;
-                    continue;
-                }
-            };
-
-            stats.bytes_processed += content.len();
-
-            // Extract document
-            let doc = match self.extractor.extract(&file_path, &content) {
-                Some(d) => d,
-                None => {
-                    log::debug!("Skipped {:?} (extraction returned None)", file_path);
-                    stats.files_skipped += 1;
-                    continue;
-                }
-            };
-
-            // Add to index
-            if let Err(e) = indexer.add_document(&doc) {
-                log::warn!("Failed to index {:?}: {}", file_path, e);
-                stats.errors += 1;
-                continue;
-            }
-
-            stats.documents_indexed += 1;
-            batch_count += 1;
-
-            // Commit batch
-            if batch_count >= self.batch_size {
-                indexer.commit()?;
-                batch_count = 0;
-            }
-        }
-
-        // Final commit
-        if batch_count > 0 {
-            indexer.commit()?;
-        }
+        self.index_store(store, &mut indexer, &mut stats).await?;
 
         // Save metadata
         let metadata = IndexMetadata::new(content_hash.clone(), stats.documents_indexed);
@@ -358,33 +318,81 @@ impl IndexBuilder {
             ));
         }
 
+        self.build_append_from_store(&FsContentStore::new(content_path), index_path)
+            .await
+    }
+
+    /// Append documents to an existing index from a content store.
+    ///
+    /// Like [`build_append`](Self::build_append); freshness is tracked per
+    /// store location.
+    pub async fn build_append_from_store(
+        &self,
+        store: &dyn ContentStore,
+        index_path: &Path,
+    ) -> Result<IndexStats> {
+        let content_hash = IndexMetadata::compute_store_hash(store).await?;
+        let append_key = format!("append:{}", store.location());
+
         // Check per-source freshness (unless forced)
-        if !self.skip_freshness_check {
-            let content_hash = IndexMetadata::compute_hash(content_path).await?;
-            let append_key = format!("append:{}", content_path.to_string_lossy());
-            if let Ok(Some(metadata)) = AppendMetadata::load(index_path)
-                && metadata.is_source_fresh(&append_key, &content_hash)
-            {
-                let cached_count = metadata.source_doc_count(&append_key);
-                log::info!(
-                    "Append source {} is fresh, skipping ({} documents)",
-                    content_path.display(),
-                    cached_count
-                );
-                return Ok(IndexStats {
-                    documents_indexed: cached_count,
-                    content_hash,
-                    ..Default::default()
-                });
-            }
+        if !self.skip_freshness_check
+            && let Ok(Some(metadata)) = AppendMetadata::load(index_path)
+            && metadata.is_source_fresh(&append_key, &content_hash)
+        {
+            let cached_count = metadata.source_doc_count(&append_key);
+            log::info!(
+                "Append source {} is fresh, skipping ({} documents)",
+                store.location(),
+                cached_count
+            );
+            return Ok(IndexStats {
+                documents_indexed: cached_count,
+                content_hash,
+                ..Default::default()
+            });
         }
 
-        log::info!("Appending to index from {:?}", content_path);
+        log::info!("Appending to index from {}", store.location());
 
         let schema = SearchSchema::build();
         let mut indexer = Indexer::new(index_path, &schema)?;
         // Note: no clear() — we're appending
 
+        let mut stats = IndexStats {
+            content_hash: content_hash.clone(),
+            ..Default::default()
+        };
+        self.index_store(store, &mut indexer, &mut stats).await?;
+
+        // Save per-source freshness metadata
+        let mut append_metadata = AppendMetadata::load(index_path)
+            .ok()
+            .flatten()
+            .unwrap_or_default();
+        append_metadata.set_source(&append_key, content_hash, stats.documents_indexed);
+        if let Err(e) = append_metadata.save(index_path) {
+            log::warn!("Failed to save append metadata: {e}");
+        }
+
+        log::info!(
+            "Appended {} documents ({} bytes, {} errors)",
+            stats.documents_indexed,
+            stats.bytes_processed,
+            stats.errors
+        );
+
+        Ok(stats)
+    }
+
+    /// Extract and index every supported item in `store`, committing in
+    /// batches.
+    async fn index_store(
+        &self,
+        store: &dyn ContentStore,
+        indexer: &mut Indexer,
+        stats: &mut IndexStats,
+    ) -> Result<()> {
+        // Collect supported extensions
         let extensions: HashSet<_> = self
             .extractor
             .supported_extensions()
@@ -392,20 +400,22 @@ impl IndexBuilder {
             .map(|s| s.to_lowercase())
             .collect();
 
-        let files = find_files_with_extensions(content_path, &extensions).await?;
-
-        let content_hash = IndexMetadata::compute_hash(content_path).await?;
-
-        let mut stats = IndexStats {
-            content_hash: content_hash.clone(),
-            ..Default::default()
-        };
+        // Find all supported items
+        let entries = store.list().await?;
         let mut batch_count = 0;
 
-        for file_path in files {
+        for entry in &entries {
+            let supported = entry
+                .extension()
+                .is_some_and(|ext| extensions.contains(&ext.to_lowercase()));
+            if !supported {
+                continue;
+            }
+            let file_path = store.path(&entry.key);
             stats.files_processed += 1;
 
-            let content = match tokio::fs::read_to_string(&file_path).await {
+            // Read item content
+            let content = match store.get_string(&entry.key).await {
                 Ok(c) => c,
                 Err(e) => {
                     log::warn!("Failed to read {:?}: {}", file_path, e);
@@ -416,14 +426,17 @@ impl IndexBuilder {
 
             stats.bytes_processed += content.len();
 
+            // Extract document
             let doc = match self.extractor.extract(&file_path, &content) {
                 Some(d) => d,
                 None => {
+                    log::debug!("Skipped {:?} (extraction returned None)", file_path);
                     stats.files_skipped += 1;
                     continue;
                 }
             };
 
+            // Add to index
             if let Err(e) = indexer.add_document(&doc) {
                 log::warn!("Failed to index {:?}: {}", file_path, e);
                 stats.errors += 1;
@@ -433,35 +446,19 @@ impl IndexBuilder {
             stats.documents_indexed += 1;
             batch_count += 1;
 
+            // Commit batch
             if batch_count >= self.batch_size {
                 indexer.commit()?;
                 batch_count = 0;
             }
         }
 
+        // Final commit
         if batch_count > 0 {
             indexer.commit()?;
         }
 
-        // Save per-source freshness metadata
-        let append_key = format!("append:{}", content_path.to_string_lossy());
-        let mut append_metadata = AppendMetadata::load(index_path)
-            .ok()
-            .flatten()
-            .unwrap_or_default();
-        append_metadata.set_source(&append_key, content_hash, stats.documents_indexed);
-        if let Err(e) = append_metadata.save(index_path) {
-            log::warn!("Failed to save append metadata: {e}");
-        }
-
-        log::info!(
-            "Appended {} documents ({} bytes, {} errors)",
-            stats.documents_indexed,
-            stats.bytes_processed,
-            stats.errors
-        );
-
-        Ok(stats)
+        Ok(())
     }
 }
 
@@ -703,6 +700,37 @@ mod tests {
         assert!(!stats.content_hash.is_empty());
     }
 
+    #[tokio::test]
+    async fn test_index_builder_build_from_store() {
+        use fabryk_core::store::MemoryContentStore;
+
+        let index_dir = TempDir::new().unwrap();
+        let store = MemoryContentStore::new()
+            .with_item("guides/doc1.md", "# Title\n\nContent here")
+            .with_item("doc2.txt", "More content")
+            .with_item("image.png", vec![0x89, 0x50]);
+
+        let stats = IndexBuilder::new()
+            .build_from_store(&store, index_dir.path())
+            .await
+            .unwrap();
+        assert_eq!(stats.documents_indexed, 2);
+        assert_eq!(stats.files_processed, 2);
+
+        // Unchanged store: fresh
+        assert!(
+            crate::freshness::is_index_fresh_for_store(index_dir.path(), &store)
+                .await
+                .unwrap()
+        );
+        store.insert("doc3.md", "New");
+        assert!(
+            !crate::freshness::is_index_fresh_for_store(index_dir.path(), &store)
+                .await
+                .unwrap()
+        );
+    }
+
     #[tokio::test]
     async fn test_index_builder_freshness_skip() {
         let content_dir = TempDir::new().unwrap();
@@ -1,8 +1,8 @@
 //! Index freshness and content hashing.
 //!
 //! This module provides `IndexMetadata` for tracking content changes and determining
-//! when re-indexing is needed. The freshness check is based on file paths and
-//! modification times (not content hashing) for efficiency.
+//! when re-indexing is needed. The freshness check is based on file listing
+//! metadata (not content hashing) for efficiency.
 //!
 //! This module is only available with the `fts-tantivy` feature.
 //!
@@ -10,13 +10,14 @@
 //!
 //! The freshness check uses a hash of:
 //! - File paths (sorted for determinism)
-//! - File modification times
+//! - File sizes and modification times
+//! - Store versions (e.g., GCS object generations), where the store has them
 //! - Schema version
 //!
 //! This approach is fast (no content reading) and catches:
 //! - New files added
 //! - Files deleted
-//! - Files modified (mtime change)
+//! - Files modified (size, mtime or version change)
 //! - Schema version changes
 //!
 //! # Usage
@@ -42,10 +43,9 @@ use std::collections::hash_map::DefaultHasher;
 use std::hash::{Hash, Hasher};
 use std::path::Path;
 
-use async_walkdir::WalkDir;
 use chrono::{DateTime, Utc};
+use fabryk_core::store::{self, ContentStore, FsContentStore};
 use fabryk_core::{Error, Result};
-use futures::StreamExt;
 use serde::{Deserialize, Serialize};
 
 use crate::schema::SCHEMA_VERSION;
@@ -56,7 +56,7 @@ const METADATA_FILE: &str = "fabryk-fts-metadata.json";
 /// Metadata about an index, including content hash for freshness checks.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IndexMetadata {
-    /// Hash of the indexed content (paths + mtimes + schema version).
+    /// Hash of the indexed content (schema version, paths, sizes and mtimes).
     pub content_hash: String,
 
     /// Timestamp of last indexing (ISO 8601 format).
@@ -130,71 +130,22 @@ impl IndexMetadata {
 
     /// Compute hash of content directory.
     ///
-    /// The hash is based on:
-    /// - Sorted list of file paths
-    /// - File modification times
-    /// - Current schema version
-    ///
-    /// This is efficient (no content reading) and deterministic.
+    /// The hash covers the schema version and each file's path, size and
+    /// modification time (see [`fabryk_core::store::hash_entries`]). This is
+    /// efficient (no content reading) and deterministic.
     pub async fn compute_hash(content_path: &Path) -> Result<String> {
-        let mut hasher = DefaultHasher::new();
+        Self::compute_store_hash(&FsContentStore::new(content_path)).await
+    }
 
-        // Include schema version in hash
+    /// Compute hash of a content store.
+    ///
+    /// The store counterpart of [`compute_hash`](Self::compute_hash), from
+    /// the store listing. For a directory store both give the same hash.
+    pub async fn compute_store_hash(store: &dyn ContentStore) -> Result<String> {
+        let mut hasher = DefaultHasher::new();
         SCHEMA_VERSION.hash(&mut hasher);
-
-        // Collect file info (path, mtime)
-        let mut file_info: Vec<(String, u64)> = Vec::new();
-        let mut walker = WalkDir::new(content_path);
-
-        while let Some(entry) = walker.next().await {
-            let entry = match entry {
-                Ok(e) => e,
-                Err(e) => {
-                    log::warn!("Walk error during hash computation: {}", e);
-                    continue;
-                }
-            };
-
-            let path = entry.path();
-
-            // Skip directories
-            if path.is_dir() {
-                continue;
-            }
-
-            // Get relative path for consistency
-            let relative = path
-                .strip_prefix(content_path)
-                .unwrap_or(&path)
-                .to_string_lossy()
-                .to_string();
-
-            // Get modification time as seconds since epoch
-            let mtime = match std::fs::metadata(&path) {
-                Ok(meta) => meta
-                    .modified()
-                    .ok()
-                    .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
-                    .map(|d| d.as_secs())
-                    .unwrap_or(0),
-                Err(_) => 0,
-            };
-
-            file_info.push((relative, mtime));
-        }
-
-        // Sort for deterministic ordering
-        file_info.sort_by(|a, b| a.0.cmp(&b.0));
-
-        // Hash all file info
-        for (path, mtime) in file_info {
-            path.hash(&mut hasher);
-            mtime.hash(&mut hasher);
-        }
-
-        // Format as hex string
-        let hash_value = hasher.finish();
-        Ok(format!("{:016x}", hash_value))
+        store::compute_store_hash(store).await?.hash(&mut hasher);
+        Ok(format!("{:016x}", hasher.finish()))
     }
 
     /// Check if the index is fresh (matches current content).
@@ -213,19 +164,37 @@ impl IndexMetadata {
             return Ok(false);
         }
 
-        // Compute current hash
-        let current_hash = Self::compute_hash(content_path).await?;
+        Ok(self.matches_hash(&Self::compute_hash(content_path).await?))
+    }
+
+    /// Check if the index is fresh for a content store.
+    ///
+    /// Like [`is_fresh`](Self::is_fresh), using
+    /// [`compute_store_hash`](Self::compute_store_hash).
+    pub async fn is_store_fresh(&self, store: &dyn ContentStore) -> Result<bool> {
+        if self.schema_version != SCHEMA_VERSION {
+            log::info!(
+                "Schema version mismatch: stored={}, current={}",
+                self.schema_version,
+                SCHEMA_VERSION
+            );
+            return Ok(false);
+        }
+
+        Ok(self.matches_hash(&Self::compute_store_hash(store).await?))
+    }
 
+    fn matches_hash(&self, current_hash: &str) -> bool {
         if self.content_hash != current_hash {
             log::debug!(
                 "Content hash mismatch: stored={}, current={}",
                 self.content_hash,
                 current_hash
             );
-            return Ok(false);
+            return false;
         }
 
-        Ok(true)
+        true
     }
 
     /// Get the indexed timestamp as a DateTime.
@@ -340,6 +309,16 @@ pub async fn is_index_fresh(index_path: &Path, content_path: &Path) -> Result<bo
     }
 }
 
+/// Check if an index exists and is fresh for a content store.
+///
+/// Like [`is_index_fresh`], for content read through a [`ContentStore`].
+pub async fn is_index_fresh_for_store(index_path: &Path, store: &dyn ContentStore) -> Result<bool> {
+    match IndexMetadata::load(index_path)? {
+        Some(metadata) => metadata.is_store_fresh(store).await,
+        None => Ok(false),
+    }
+}
+
 // ============================================================================
 // Tests
 // ============================================================================
@@ -453,6 +432,19 @@ mod tests {
         assert!(!hash.is_empty());
     }
 
+    #[tokio::test]
+    async fn test_compute_store_hash_matches_directory_hash() {
+        let temp_dir = TempDir::new().unwrap();
+        create_test_file(temp_dir.path(), "a.txt", "aaa");
+        create_test_file(temp_dir.path(), "sub/b.md", "bbb");
+        let store = fabryk_core::store::FsContentStore::new(temp_dir.path());
+
+        assert_eq!(
+            IndexMetadata::compute_store_hash(&store).await.unwrap(),
+            IndexMetadata::compute_hash(temp_dir.path()).await.unwrap()
+        );
+    }
+
     #[tokio::test]
     async fn test_compute_hash_deterministic() {
         let temp_dir = TempDir::new().unwrap();
@@ -480,6 +472,18 @@ mod tests {
         assert_ne!(hash1, hash2);
     }
 
+    #[tokio::test]
+    async fn test_compute_store_hash_changes_with_version() {
+        let store = fabryk_core::store::MemoryContentStore::new().with_item("a.md", "x");
+        let hash1 = IndexMetadata::compute_store_hash(&store).await.unwrap();
+
+        // Same key and size; the store version still changes.
+        store.insert("a.md", "y");
+        let hash2 = IndexMetadata::compute_store_hash(&store).await.unwrap();
+
+        assert_ne!(hash1, hash2);
+    }
+
     // ------------------------------------------------------------------------
     // Freshness tests
     // ------------------------------------------------------------------------
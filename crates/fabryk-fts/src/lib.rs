@@ -120,7 +120,7 @@ pub use indexer::Indexer;
 pub use builder::{DocumentExtractor, IndexBuilder, IndexStats};
 
 #[cfg(feature = "fts-tantivy")]
-pub use freshness::{AppendMetadata, IndexMetadata, is_index_fresh};
+pub use freshness::{AppendMetadata, IndexMetadata, is_index_fresh, is_index_fresh_for_store};
 
 #[cfg(feature = "fts-tantivy")]
 pub use stopwords::StopwordFilter;
@@ -18,7 +18,7 @@ use petgraph::visit::EdgeRef;
 use std::collections::{HashMap, HashSet, VecDeque};
 
 /// Maximum allowed BFS depth to prevent runaway traversals.
-const MAX_BFS_DEPTH: usize = 10;
+pub(crate) const MAX_BFS_DEPTH: usize = 10;
 
 // ============================================================================
 // Result types
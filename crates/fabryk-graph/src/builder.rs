@@ -2,7 +2,7 @@
 //!
 //! The builder orchestrates content discovery and graph construction:
 //!
-//! 1. Discover content files using glob patterns
+//! 1. Discover markdown files in the content store
 //! 2. Parse frontmatter and content
 //! 3. Call GraphExtractor methods to extract nodes/edges
 //! 4. Build the final GraphData structure
@@ -15,14 +15,36 @@
 //!   in `BuildStats::dangling_refs` instead of silently dropped.
 //! - **Bidirectional edge deduplication**: Prevents duplicate edges when both
 //!   sides of a relationship declare each other.
+//!
+//! # Incremental Rebuilds
+//!
+//! Every build records a [`FileRecord`] per content file in
+//! [`GraphMetadata::files`]: the file's content hash, the node it produced
+//! and the edges it declared. With that, only changed files need to be
+//! re-extracted:
+//!
+//! - [`GraphBuilder::build`] updates a stale cached graph in place instead
+//!   of rebuilding it from scratch
+//! - [`GraphBuilder::update`] rescans the content store against a live graph
+//! - [`GraphBuilder::apply_file_change`] applies a single added, modified or
+//!   deleted file to a live graph
+//!
+//! Removing a changed file's node also removes edges other files declared
+//! into it; those are re-resolved from the recorded declarations, as are
+//! previously dangling references that the change now satisfies.
 
-use crate::persistence::{self, GraphMetadata};
-use crate::{Edge, EdgeOrigin, GraphData, GraphExtractor, Relationship};
+use crate::persistence::{self, FileRecord, GraphMetadata};
+use crate::{Edge, EdgeOrigin, GraphData, GraphExtractor, Node, Relationship};
 use fabryk_content::markdown::extract_frontmatter;
+use fabryk_core::store::{ContentEntry, ContentStore, FsContentStore, hash_entries, validate_key};
 use fabryk_core::{Error, Result};
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::collections::{BTreeSet, HashSet};
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Deduplication key for edges: (from, to, relationship name).
+type EdgeKey = (String, String, String);
 
 // ============================================================================
 // Builder configuration types
@@ -83,6 +105,49 @@ pub struct BuildStats {
     pub deduped_edges: usize,
     /// Whether the result was loaded from cache.
     pub from_cache: bool,
+    /// Files reused unchanged from the cache by an incremental rebuild.
+    pub files_unchanged: usize,
+}
+
+/// Statistics from an incremental graph update.
+#[derive(Debug, Clone, Default)]
+pub struct UpdateStats {
+    /// New content files.
+    pub files_added: usize,
+    /// Content files whose contents changed.
+    pub files_modified: usize,
+    /// Content files that no longer exist.
+    pub files_removed: usize,
+    /// Content files left as they were.
+    pub files_unchanged: usize,
+    /// Files re-extracted (changed files plus files sharing their node IDs).
+    pub files_extracted: usize,
+    /// Nodes removed from the graph.
+    pub nodes_removed: usize,
+    /// Nodes added to the graph.
+    pub nodes_added: usize,
+    /// Extracted edges added (or re-resolved) in the graph.
+    pub edges_added: usize,
+    /// Manual edges re-added in the graph.
+    pub manual_edges_added: usize,
+    /// Dangling references across the whole graph after the update.
+    pub dangling_refs: Vec<String>,
+    /// Errors encountered (if not fail-fast).
+    pub errors: Vec<BuildError>,
+}
+
+impl UpdateStats {
+    /// Whether no file was added, modified or removed.
+    pub fn is_noop(&self) -> bool {
+        self.files_added == 0 && self.files_modified == 0 && self.files_removed == 0
+    }
+}
+
+/// The result of extracting one content file.
+struct ExtractedFile {
+    relative: String,
+    node: Node,
+    record: FileRecord,
 }
 
 // ============================================================================
@@ -98,9 +163,10 @@ pub struct BuildStats {
 /// When a cache path is configured via [`with_cache_path`](Self::with_cache_path),
 /// the builder checks if the cached graph is fresh before rebuilding. On cache hit,
 /// the graph is loaded from disk in milliseconds instead of re-parsing all content files.
+/// On a miss, only files whose content hash changed are re-extracted.
 pub struct GraphBuilder<E: GraphExtractor> {
     extractor: E,
-    content_path: Option<PathBuf>,
+    content: Option<Arc<dyn ContentStore>>,
     manual_edges_path: Option<PathBuf>,
     error_handling: ErrorHandling,
     cache_path: Option<PathBuf>,
@@ -112,7 +178,7 @@ impl<E: GraphExtractor> GraphBuilder<E> {
     pub fn new(extractor: E) -> Self {
         Self {
             extractor,
-            content_path: None,
+            content: None,
             manual_edges_path: None,
             error_handling: ErrorHandling::default(),
             cache_path: None,
@@ -122,7 +188,13 @@ impl<E: GraphExtractor> GraphBuilder<E> {
 
     /// Sets the content directory path.
     pub fn with_content_path(mut self, path: impl Into<PathBuf>) -> Self {
-        self.content_path = Some(path.into());
+        self.content = Some(Arc::new(FsContentStore::new(path)));
+        self
+    }
+
+    /// Reads content from a store instead of a directory.
+    pub fn with_content_store(mut self, store: Arc<dyn ContentStore>) -> Self {
+        self.content = Some(store);
         self
     }
 
@@ -143,13 +215,14 @@ impl<E: GraphExtractor> GraphBuilder<E> {
     /// When set, the builder will:
     /// 1. Check if the cache is fresh before building (by comparing content hashes)
     /// 2. Load from cache on hit (fast path)
-    /// 3. Save to cache after a successful build (for next time)
+    /// 3. Update a stale cache incrementally, re-extracting only changed files
+    /// 4. Save to cache after a successful build (for next time)
     pub fn with_cache_path(mut self, path: impl Into<PathBuf>) -> Self {
         self.cache_path = Some(path.into());
         self
     }
 
-    /// Forces a rebuild even if the cache is fresh.
+    /// Forces a full rebuild even if a cache exists.
     pub fn skip_cache(mut self) -> Self {
         self.skip_cache = true;
         self
@@ -163,40 +236,80 @@ impl<E: GraphExtractor> GraphBuilder<E> {
     ///
     /// If a cache path is configured and the cache is fresh, loads from cache instead.
     pub async fn build(self) -> Result<(GraphData, BuildStats)> {
-        let content_path = self
-            .content_path
-            .as_ref()
-            .ok_or_else(|| Error::config("Content path not set. Use with_content_path() first."))?
-            .clone();
+        let (graph, _, stats) = self.build_with_metadata().await?;
+        Ok((graph, stats))
+    }
 
-        // Check cache freshness (if cache configured and not skipped)
+    /// Builds the graph and returns its metadata.
+    ///
+    /// Keep the metadata alongside a live graph to apply later changes with
+    /// [`update`](Self::update) or [`apply_file_change`](Self::apply_file_change).
+    pub async fn build_with_metadata(self) -> Result<(GraphData, GraphMetadata, BuildStats)> {
+        let store = self.require_store()?;
+
+        // Try the cache (if configured and not skipped)
         if let Some(ref cache_path) = self.cache_path
             && !self.skip_cache
+            && cache_path.exists()
         {
-            let content_hash = compute_content_hash(&content_path)?;
-            if persistence::is_cache_fresh(cache_path, &content_hash) {
-                log::info!(
-                    "Graph cache is fresh, loading from {}",
-                    cache_path.display()
-                );
-                let graph = persistence::load_graph(cache_path)?;
-                let stats = BuildStats {
-                    nodes_created: graph.node_count(),
-                    edges_created: graph.edge_count(),
-                    files_processed: 0,
-                    files_skipped: 0,
-                    errors: Vec::new(),
-                    manual_edges_loaded: 0,
-                    dangling_refs: Vec::new(),
-                    deduped_edges: 0,
-                    from_cache: true,
-                };
-                return Ok((graph, stats));
+            let content_hash = compute_store_hash(store.as_ref()).await?;
+            match persistence::load_graph_with_metadata(cache_path) {
+                Ok((graph, Some(metadata)))
+                    if metadata.content_hash.as_deref() == Some(content_hash.as_str()) =>
+                {
+                    log::info!(
+                        "Graph cache is fresh, loading from {}",
+                        cache_path.display()
+                    );
+                    let stats = BuildStats {
+                        nodes_created: graph.node_count(),
+                        edges_created: graph.edge_count(),
+                        files_processed: 0,
+                        files_skipped: 0,
+                        errors: Vec::new(),
+                        manual_edges_loaded: 0,
+                        dangling_refs: Vec::new(),
+                        deduped_edges: 0,
+                        from_cache: true,
+                        files_unchanged: metadata.files.len(),
+                    };
+                    return Ok((graph, metadata, stats));
+                }
+                Ok((mut graph, Some(mut metadata))) if !metadata.files.is_empty() => {
+                    let update = self.update(&mut graph, &mut metadata).await?;
+                    log::info!(
+                        "Graph cache updated incrementally: {} added, {} modified, {} removed, {} unchanged",
+                        update.files_added,
+                        update.files_modified,
+                        update.files_removed,
+                        update.files_unchanged
+                    );
+                    metadata.content_hash = Some(content_hash);
+                    metadata.source_file_count = Some(metadata.files.len());
+                    metadata.built_at = GraphMetadata::default().built_at;
+                    self.save_cache(&graph, &metadata)?;
+
+                    let stats = BuildStats {
+                        nodes_created: graph.node_count(),
+                        edges_created: graph.edge_count(),
+                        files_processed: update.files_extracted,
+                        files_skipped: update.errors.len(),
+                        errors: update.errors,
+                        manual_edges_loaded: update.manual_edges_added,
+                        dangling_refs: update.dangling_refs,
+                        deduped_edges: 0,
+                        from_cache: false,
+                        files_unchanged: update.files_unchanged,
+                    };
+                    return Ok((graph, metadata, stats));
+                }
+                Ok(_) => {}
+                Err(e) => log::warn!("Ignoring unreadable graph cache: {e}"),
             }
         }
 
         // Discover files
-        let files = discover_files(&content_path).await?;
+        let files = discover_files(store.as_ref()).await?;
 
         let mut stats = BuildStats {
             nodes_created: 0,
@@ -208,30 +321,27 @@ impl<E: GraphExtractor> GraphBuilder<E> {
             dangling_refs: Vec::new(),
             deduped_edges: 0,
             from_cache: false,
+            files_unchanged: 0,
         };
 
         let mut graph = GraphData::new();
 
-        // Temporary storage for edge data (processed in phase 2)
-        let mut pending_edges: Vec<(String, E::EdgeData)> = Vec::new();
+        // Per-file records, in discovery order (edges are processed in phase 2)
+        let mut records: Vec<(String, FileRecord)> = Vec::new();
 
         // ================================================================
         // Phase 1: Extract and add all nodes
         // ================================================================
-        for file_path in &files {
-            match self.process_file(&content_path, file_path) {
-                Ok((node_data, edge_data)) => {
-                    let node = self.extractor.to_graph_node(&node_data);
-                    graph.add_node(node.clone());
+        for file in &files {
+            match self.extract_file(store.as_ref(), &file.key).await {
+                Ok(extracted) => {
+                    graph.add_node(extracted.node);
                     stats.nodes_created += 1;
-
-                    if let Some(edges) = edge_data {
-                        pending_edges.push((node.id.clone(), edges));
-                    }
+                    records.push((extracted.relative, extracted.record));
                 }
                 Err(e) => {
                     let build_error = BuildError {
-                        file: file_path.clone(),
+                        file: store.path(&file.key),
                         message: e.to_string(),
                     };
 
@@ -251,77 +361,265 @@ impl<E: GraphExtractor> GraphBuilder<E> {
         // ================================================================
         // Phase 2: Add all edges (with dedup and dangling ref tracking)
         // ================================================================
-        let mut seen_edges: HashSet<(String, String, String)> = HashSet::new();
-
-        for (from_id, edge_data) in &pending_edges {
-            let edges = self.extractor.to_graph_edges(from_id, edge_data);
-            for edge in edges {
-                // Check for dangling references
-                if !graph.contains_node(&edge.from) || !graph.contains_node(&edge.to) {
-                    stats.dangling_refs.push(format!(
-                        "{} -[{}]-> {}",
-                        edge.from,
-                        edge.relationship.name(),
-                        edge.to
-                    ));
-                    continue;
-                }
-
-                // Bidirectional edge deduplication
-                let edge_key = (
-                    edge.from.clone(),
-                    edge.to.clone(),
-                    edge.relationship.name().to_string(),
-                );
-                if !seen_edges.insert(edge_key) {
-                    stats.deduped_edges += 1;
-                    continue;
-                }
-
-                if graph.add_edge(edge).is_ok() {
-                    stats.edges_created += 1;
-                }
-            }
+        let mut seen_edges: HashSet<EdgeKey> = HashSet::new();
+
+        for (_, record) in &records {
+            let (added, deduped) = add_declared_edges(
+                &mut graph,
+                &record.edges,
+                &mut seen_edges,
+                &mut stats.dangling_refs,
+            );
+            stats.edges_created += added;
+            stats.deduped_edges += deduped;
         }
 
         // ================================================================
         // Phase 3: Load manual edges
         // ================================================================
         if let Some(ref manual_path) = self.manual_edges_path {
-            stats.manual_edges_loaded =
-                load_manual_edges(manual_path, &mut graph, &mut seen_edges, &mut stats)?;
+            let (loaded, deduped) = add_manual_edges(
+                read_manual_edges(manual_path)?,
+                &mut graph,
+                &mut seen_edges,
+                &mut stats.dangling_refs,
+            );
+            stats.manual_edges_loaded = loaded;
+            stats.deduped_edges += deduped;
         }
 
+        let metadata = GraphMetadata {
+            content_hash: Some(compute_store_hash(store.as_ref()).await?),
+            source_file_count: Some(stats.files_processed),
+            files: records.into_iter().collect(),
+            ..Default::default()
+        };
+
         // Save to cache after successful build
-        if let Some(ref cache_path) = self.cache_path {
-            let content_hash = compute_content_hash(&content_path)?;
-            let metadata = GraphMetadata {
-                content_hash: Some(content_hash),
-                source_file_count: Some(stats.files_processed),
-                ..Default::default()
-            };
-            // Ensure parent directory exists
-            if let Some(parent) = cache_path.parent()
-                && !parent.exists()
-            {
-                std::fs::create_dir_all(parent).map_err(|e| Error::io_with_path(e, parent))?;
+        if self.cache_path.is_some() {
+            self.save_cache(&graph, &metadata)?;
+        }
+
+        Ok((graph, metadata, stats))
+    }
+
+    /// Bring a graph up to date with the content store.
+    ///
+    /// Compares each content file's hash with the record in `metadata`,
+    /// then re-extracts added and modified files and drops removed ones.
+    /// `metadata.files` is updated to match; the freshness fields
+    /// (`content_hash`, `built_at`) are left to the caller.
+    pub async fn update(
+        &self,
+        graph: &mut GraphData,
+        metadata: &mut GraphMetadata,
+    ) -> Result<UpdateStats> {
+        let store = self.require_store()?;
+        let files = discover_files(store.as_ref()).await?;
+
+        let mut stats = UpdateStats::default();
+        let mut changed: BTreeSet<String> = BTreeSet::new();
+        let mut present: HashSet<String> = HashSet::new();
+
+        for file in files {
+            let relative = file.key;
+            match metadata.files.get(&relative) {
+                None => stats.files_added += 1,
+                Some(record)
+                    if file_hash(store.as_ref(), &relative).await.as_ref()
+                        == Some(&record.content_hash) =>
+                {
+                    stats.files_unchanged += 1;
+                    present.insert(relative);
+                    continue;
+                }
+                Some(_) => stats.files_modified += 1,
             }
-            if let Err(e) = persistence::save_graph(&graph, cache_path, Some(metadata)) {
-                log::warn!("Failed to save graph cache: {e}");
+            present.insert(relative.clone());
+            changed.insert(relative);
+        }
+
+        for relative in metadata.files.keys() {
+            if !present.contains(relative) {
+                stats.files_removed += 1;
+                changed.insert(relative.clone());
             }
         }
 
-        Ok((graph, stats))
+        if changed.is_empty() {
+            return Ok(stats);
+        }
+        self.apply_changes(store.as_ref(), graph, metadata, changed, stats)
+            .await
     }
 
-    /// Process a single file to extract node and edge data.
-    fn process_file(
+    /// Apply a change to a single content file to a live graph.
+    ///
+    /// `file_path` may be absolute or relative to the content path (for
+    /// stores without a local directory, it is the item's key). The file is
+    /// treated as added, modified or deleted depending on whether it exists
+    /// and whether `metadata` has a record for it. Unchanged files are a
+    /// no-op.
+    pub async fn apply_file_change(
         &self,
-        base_path: &Path,
-        file_path: &Path,
-    ) -> Result<(E::NodeData, Option<E::EdgeData>)> {
-        let content =
-            std::fs::read_to_string(file_path).map_err(|e| Error::io_with_path(e, file_path))?;
+        graph: &mut GraphData,
+        metadata: &mut GraphMetadata,
+        file_path: impl AsRef<Path>,
+    ) -> Result<UpdateStats> {
+        let store = self.require_store()?;
+        let relative = match store.root() {
+            Some(content_path) => {
+                let file_path = content_path.join(file_path.as_ref());
+                if !file_path.starts_with(content_path) {
+                    return Err(Error::config(format!(
+                        "{} is outside the content path {}",
+                        file_path.display(),
+                        content_path.display()
+                    )));
+                }
+                relative_key(content_path, &file_path)
+            }
+            None => relative_key(Path::new(""), file_path.as_ref()),
+        };
+        validate_key(&relative)?;
+
+        let mut stats = UpdateStats::default();
+        let exists = store.stat(&relative).await?.is_some();
+
+        match (exists, metadata.files.get(&relative)) {
+            (true, Some(record))
+                if file_hash(store.as_ref(), &relative).await.as_ref()
+                    == Some(&record.content_hash) =>
+            {
+                stats.files_unchanged = 1;
+                return Ok(stats);
+            }
+            (true, Some(_)) => stats.files_modified = 1,
+            (true, None) => stats.files_added = 1,
+            (false, Some(_)) => stats.files_removed = 1,
+            (false, None) => return Ok(stats),
+        }
+
+        self.apply_changes(
+            store.as_ref(),
+            graph,
+            metadata,
+            BTreeSet::from([relative]),
+            stats,
+        )
+        .await
+    }
+
+    /// Re-extract changed files and patch the graph.
+    async fn apply_changes(
+        &self,
+        store: &dyn ContentStore,
+        graph: &mut GraphData,
+        metadata: &mut GraphMetadata,
+        changed: BTreeSet<String>,
+        mut stats: UpdateStats,
+    ) -> Result<UpdateStats> {
+        // Removing a changed file's node removes it for every file that
+        // produced the same ID, so those files must be re-extracted too.
+        let stale_ids: HashSet<&str> = changed
+            .iter()
+            .filter_map(|relative| metadata.files.get(relative))
+            .map(|record| record.node_id.as_str())
+            .collect();
+        let mut candidates: BTreeSet<String> = changed.clone();
+        for (relative, record) in &metadata.files {
+            if stale_ids.contains(record.node_id.as_str()) {
+                candidates.insert(relative.clone());
+            }
+        }
+        let mut to_extract: BTreeSet<String> = BTreeSet::new();
+        for relative in candidates {
+            if store.stat(&relative).await?.is_some() {
+                to_extract.insert(relative);
+            }
+        }
+
+        // Extract before touching the graph so fail-fast leaves it intact
+        let mut extracted: Vec<ExtractedFile> = Vec::new();
+        for relative in &to_extract {
+            match self.extract_file(store, relative).await {
+                Ok(file) => extracted.push(file),
+                Err(e) => match self.error_handling {
+                    ErrorHandling::FailFast => return Err(e),
+                    ErrorHandling::Collect | ErrorHandling::Skip => {
+                        stats.errors.push(BuildError {
+                            file: store.path(relative),
+                            message: e.to_string(),
+                        });
+                    }
+                },
+            }
+        }
+        stats.files_extracted = to_extract.len();
+
+        // Remove stale nodes (and with them, every edge touching them)
+        let mut touched: HashSet<String> = HashSet::new();
+        for relative in changed.iter().chain(&to_extract) {
+            if let Some(record) = metadata.files.remove(relative) {
+                if graph.remove_node(&record.node_id).is_some() {
+                    stats.nodes_removed += 1;
+                }
+                touched.insert(record.node_id);
+            }
+        }
+
+        // Add re-extracted nodes
+        let mut reextracted: HashSet<String> = HashSet::new();
+        for file in extracted {
+            if !graph.contains_node(&file.node.id) {
+                stats.nodes_added += 1;
+            }
+            graph.add_node(file.node);
+            touched.insert(file.record.node_id.clone());
+            reextracted.insert(file.relative.clone());
+            metadata.files.insert(file.relative, file.record);
+        }
+
+        // Re-resolve edges: everything the re-extracted files declare, plus
+        // edges other files declare to or from a touched node
+        let mut seen_edges: HashSet<EdgeKey> = graph.edges.iter().map(edge_key).collect();
+        let mut ignored: Vec<String> = Vec::new();
+        for (relative, record) in &metadata.files {
+            let rescan = reextracted.contains(relative);
+            let edges = record
+                .edges
+                .iter()
+                .filter(|e| rescan || touched.contains(&e.from) || touched.contains(&e.to));
+            let (added, _) = add_declared_edges(graph, edges, &mut seen_edges, &mut ignored);
+            stats.edges_added += added;
+        }
+
+        let manual_edges = match self.manual_edges_path {
+            Some(ref path) => read_manual_edges(path)?,
+            None => Vec::new(),
+        };
+        let touching: Vec<ManualEdge> = manual_edges
+            .iter()
+            .filter(|m| touched.contains(&m.from) || touched.contains(&m.to))
+            .cloned()
+            .collect();
+        (stats.manual_edges_added, _) =
+            add_manual_edges(touching, graph, &mut seen_edges, &mut ignored);
+
+        stats.dangling_refs = dangling_refs(graph, metadata, &manual_edges);
+        metadata.source_file_count = Some(metadata.files.len());
+
+        Ok(stats)
+    }
+
+    /// Extract a single file's node and declared edges.
+    ///
+    /// The extractor sees the file's local path, or its key for stores
+    /// without a local directory.
+    async fn extract_file(&self, store: &dyn ContentStore, key: &str) -> Result<ExtractedFile> {
+        let content = store.get_string(key).await?;
+        let base_path = store.root().unwrap_or(Path::new(""));
+        let file_path = store.path(key);
 
         let fm_result = extract_frontmatter(&content)?;
 
@@ -333,11 +631,48 @@ impl<E: GraphExtractor> GraphBuilder<E> {
 
         let node_data = self
             .extractor
-            .extract_node(base_path, file_path, &frontmatter, body)?;
-
+            .extract_node(base_path, &file_path, &frontmatter, body)?;
         let edge_data = self.extractor.extract_edges(&frontmatter, body)?;
 
-        Ok((node_data, edge_data))
+        let node = self.extractor.to_graph_node(&node_data);
+        let edges = edge_data
+            .map(|data| self.extractor.to_graph_edges(&node.id, &data))
+            .unwrap_or_default();
+
+        Ok(ExtractedFile {
+            relative: key.to_string(),
+            record: FileRecord {
+                content_hash: hash_content(content.as_bytes()),
+                node_id: node.id.clone(),
+                edges,
+            },
+            node,
+        })
+    }
+
+    fn require_store(&self) -> Result<Arc<dyn ContentStore>> {
+        self.content.clone().ok_or_else(|| {
+            Error::config(
+                "Content path not set. Use with_content_path() or with_content_store() first.",
+            )
+        })
+    }
+
+    fn save_cache(&self, graph: &GraphData, metadata: &GraphMetadata) -> Result<()> {
+        let Some(ref cache_path) = self.cache_path else {
+            return Ok(());
+        };
+
+        // Ensure parent directory exists
+        if let Some(parent) = cache_path.parent()
+            && !parent.exists()
+        {
+            std::fs::create_dir_all(parent).map_err(|e| Error::io_with_path(e, parent))?;
+        }
+        if let Err(e) = persistence::save_graph(graph, cache_path, Some(metadata.clone())) {
+            log::warn!("Failed to save graph cache: {e}");
+        }
+        Ok(())
     }
 }
 
@@ -363,29 +698,92 @@ fn parse_relationship(s: &str) -> Relationship {
     }
 }
 
-/// Load manual edges from a JSON file.
-fn load_manual_edges(
-    path: &Path,
+fn edge_key(edge: &Edge) -> EdgeKey {
+    (
+        edge.from.clone(),
+        edge.to.clone(),
+        edge.relationship.name().to_string(),
+    )
+}
+
+fn format_dangling(edge: &Edge) -> String {
+    format!(
+        "{} -[{}]-> {}",
+        edge.from,
+        edge.relationship.name(),
+        edge.to
+    )
+}
+
+fn format_manual_dangling(manual: &ManualEdge) -> String {
+    format!(
+        "manual: {} -[{}]-> {}",
+        manual.from, manual.relationship, manual.to
+    )
+}
+
+/// Add extracted edges, skipping duplicates and recording dangling references.
+///
+/// Returns the number of edges added and the number deduplicated.
+fn add_declared_edges<'a>(
     graph: &mut GraphData,
-    seen_edges: &mut HashSet<(String, String, String)>,
-    stats: &mut BuildStats,
-) -> Result<usize> {
+    edges: impl IntoIterator<Item = &'a Edge>,
+    seen_edges: &mut HashSet<EdgeKey>,
+    dangling_refs: &mut Vec<String>,
+) -> (usize, usize) {
+    let mut added = 0;
+    let mut deduped = 0;
+
+    for edge in edges {
+        // Check for dangling references
+        if !graph.contains_node(&edge.from) || !graph.contains_node(&edge.to) {
+            dangling_refs.push(format_dangling(edge));
+            continue;
+        }
+
+        // Bidirectional edge deduplication
+        if !seen_edges.insert(edge_key(edge)) {
+            deduped += 1;
+            continue;
+        }
+
+        if graph.add_edge(edge.clone()).is_ok() {
+            added += 1;
+        }
+    }
+
+    (added, deduped)
+}
+
+/// Load manual edges from a JSON file.
+///
+/// A missing file yields no edges.
+fn read_manual_edges(path: &Path) -> Result<Vec<ManualEdge>> {
     if !path.exists() {
-        return Ok(0);
+        return Ok(Vec::new());
     }
 
     let json = std::fs::read_to_string(path).map_err(|e| Error::io_with_path(e, path))?;
 
-    let manual_edges: Vec<ManualEdge> = serde_json::from_str(&json)
-        .map_err(|e| Error::parse(format!("Failed to parse manual edges: {e}")))?;
+    serde_json::from_str(&json)
+        .map_err(|e| Error::parse(format!("Failed to parse manual edges: {e}")))
+}
 
+/// Add manual edges, skipping duplicates and recording dangling references.
+///
+/// Returns the number of edges added and the number deduplicated.
+fn add_manual_edges(
+    manual_edges: Vec<ManualEdge>,
+    graph: &mut GraphData,
+    seen_edges: &mut HashSet<EdgeKey>,
+    dangling_refs: &mut Vec<String>,
+) -> (usize, usize) {
     let mut loaded = 0;
+    let mut deduped = 0;
+
     for manual in manual_edges {
         if !graph.contains_node(&manual.from) || !graph.contains_node(&manual.to) {
-            stats.dangling_refs.push(format!(
-                "manual: {} -[{}]-> {}",
-                manual.from, manual.relationship, manual.to
-            ));
+            dangling_refs.push(format_manual_dangling(&manual));
             continue;
         }
 
@@ -395,7 +793,7 @@ fn load_manual_edges(
             manual.relationship.clone(),
         );
         if !seen_edges.insert(edge_key) {
-            stats.deduped_edges += 1;
+            deduped += 1;
             continue;
         }
 
@@ -417,63 +815,105 @@ fn load_manual_edges(
         }
     }
 
-    Ok(loaded)
+    (loaded, deduped)
 }
 
-/// Compute a content hash for cache freshness checking.
-///
-/// Uses file paths and modification times (not content) for speed.
-/// Deterministic: sorted paths ensure consistent hashing.
-fn compute_content_hash(dir: &Path) -> Result<String> {
+/// All declared and manual edges whose endpoints are missing from the graph.
+fn dangling_refs(
+    graph: &GraphData,
+    metadata: &GraphMetadata,
+    manual_edges: &[ManualEdge],
+) -> Vec<String> {
+    let declared = metadata
+        .files
+        .values()
+        .flat_map(|record| &record.edges)
+        .filter(|e| !graph.contains_node(&e.from) || !graph.contains_node(&e.to))
+        .map(format_dangling);
+    let manual = manual_edges
+        .iter()
+        .filter(|m| !graph.contains_node(&m.from) || !graph.contains_node(&m.to))
+        .map(format_manual_dangling);
+
+    declared.chain(manual).collect()
+}
+
+/// Key for a content file in `GraphMetadata::files`: its path relative to
+/// the content root, with `/` separators.
+fn relative_key(base: &Path, file_path: &Path) -> String {
+    let relative = file_path.strip_prefix(base).unwrap_or(file_path);
+    relative
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Hash file contents for change detection.
+fn hash_content(content: &[u8]) -> String {
     use std::collections::hash_map::DefaultHasher;
     use std::hash::{Hash, Hasher};
 
     let mut hasher = DefaultHasher::new();
-    let mut file_info: Vec<(String, u64)> = Vec::new();
+    content.hash(&mut hasher);
+    format!("{:016x}", hasher.finish())
+}
 
-    fn collect_files(dir: &Path, base: &Path, file_info: &mut Vec<(String, u64)>) -> Result<()> {
+/// Hash a content item, or `None` if it can't be read.
+async fn file_hash(store: &dyn ContentStore, key: &str) -> Option<String> {
+    store.get(key).await.ok().map(|bytes| hash_content(&bytes))
+}
+
+/// Compute a content hash for cache freshness checking.
+///
+/// Uses markdown file paths, sizes and modification times (not content)
+/// for speed. Deterministic: sorted paths ensure consistent hashing.
+/// Compare the result with [`is_cache_fresh`](crate::is_cache_fresh) to
+/// check a cached graph without rebuilding it.
+pub fn compute_content_hash(dir: &Path) -> Result<String> {
+    let mut entries: Vec<ContentEntry> = Vec::new();
+
+    fn collect_files(dir: &Path, base: &Path, entries: &mut Vec<ContentEntry>) -> Result<()> {
         for entry in std::fs::read_dir(dir).map_err(|e| Error::io_with_path(e, dir))? {
             let entry = entry.map_err(Error::io)?;
             let path = entry.path();
             if path.is_dir() {
-                collect_files(&path, base, file_info)?;
+                collect_files(&path, base, entries)?;
             } else if path.extension().is_some_and(|e| e == "md") {
                 let relative = path
                     .strip_prefix(base)
                     .unwrap_or(&path)
                     .to_string_lossy()
                     .to_string();
-                let mtime = std::fs::metadata(&path)
-                    .ok()
-                    .and_then(|m| m.modified().ok())
-                    .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
-                    .map(|d| d.as_secs())
-                    .unwrap_or(0);
-                file_info.push((relative, mtime));
+                let metadata = std::fs::metadata(&path).ok();
+                let mut content_entry =
+                    ContentEntry::new(relative, metadata.as_ref().map_or(0, |m| m.len()));
+                if let Some(modified) = metadata.and_then(|m| m.modified().ok()) {
+                    content_entry = content_entry.with_modified(modified);
+                }
+                entries.push(content_entry);
             }
         }
         Ok(())
     }
 
-    collect_files(dir, dir, &mut file_info)?;
-    file_info.sort_by(|a, b| a.0.cmp(&b.0));
-
-    for (path, mtime) in &file_info {
-        path.hash(&mut hasher);
-        mtime.hash(&mut hasher);
-    }
-
-    Ok(format!("{:016x}", hasher.finish()))
+    collect_files(dir, dir, &mut entries)?;
+    Ok(hash_entries(&entries))
 }
 
-/// Discover markdown content files in a directory.
-async fn discover_files(base_path: &Path) -> Result<Vec<PathBuf>> {
-    use fabryk_core::util::files::{FindOptions, find_all_files};
-
-    let files = find_all_files(base_path, FindOptions::markdown()).await?;
-    let paths: Vec<PathBuf> = files.into_iter().map(|f| f.path).collect();
+/// Compute a content hash for a content store.
+///
+/// The store counterpart of [`compute_content_hash`], from the listing of
+/// markdown items; for a directory store both give the same hash.
+pub async fn compute_store_hash(store: &dyn ContentStore) -> Result<String> {
+    Ok(hash_entries(&discover_files(store).await?))
+}
 
-    Ok(paths)
+/// Discover markdown content files in a store.
+async fn discover_files(store: &dyn ContentStore) -> Result<Vec<ContentEntry>> {
+    let mut files = store.list().await?;
+    files.retain(|entry| entry.extension() == Some("md"));
+    Ok(files)
 }
 
 // ============================================================================
@@ -517,6 +957,52 @@ mod tests {
         assert!(graph.contains_node("concept-b"));
     }
 
+    #[tokio::test]
+    async fn test_builder_content_store() {
+        use fabryk_core::store::MemoryContentStore;
+
+        let store = Arc::new(
+            MemoryContentStore::new()
+                .with_item(
+                    "basics/concept-a.md",
+                    "---\ntitle: \"Concept A\"\nprerequisites:\n  - concept-b\n---\n\n# A\n",
+                )
+                .with_item("concept-b.md", "---\ntitle: \"Concept B\"\n---\n\n# B\n")
+                .with_item("notes.txt", "not markdown"),
+        );
+        let (mut graph, mut metadata, stats) = GraphBuilder::new(MockExtractor)
+            .with_content_store(store.clone())
+            .build_with_metadata()
+            .await
+            .unwrap();
+        assert_eq!(stats.files_processed, 2);
+        assert!(graph.contains_node("concept-a"));
+        assert_eq!(graph.edge_count(), 1);
+        assert!(metadata.files.contains_key("basics/concept-a.md"));
+
+        store.remove("concept-b.md");
+        let stats = GraphBuilder::new(MockExtractor)
+            .with_content_store(store)
+            .apply_file_change(&mut graph, &mut metadata, "concept-b.md")
+            .await
+            .unwrap();
+        assert_eq!(stats.files_removed, 1);
+        assert!(!graph.contains_node("concept-b"));
+        assert_eq!(graph.edge_count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_compute_store_hash_matches_directory_hash() {
+        let (_dir, content_dir) = setup_test_files().await;
+        std::fs::write(content_dir.join("ignored.txt"), "x").unwrap();
+        let store = FsContentStore::new(&content_dir);
+
+        assert_eq!(
+            compute_store_hash(&store).await.unwrap(),
+            compute_content_hash(&content_dir).unwrap()
+        );
+    }
+
     #[tokio::test]
     async fn test_builder_extracts_edges() {
         let (_dir, content_dir) = setup_test_files().await;
@@ -810,6 +1296,245 @@ mod tests {
         assert_eq!(stats.files_processed, 2);
     }
 
+    // ================================================================
+    // Incremental rebuild tests
+    // ================================================================
+
+    fn edge_set(graph: &GraphData) -> BTreeSet<EdgeKey> {
+        graph.edges.iter().map(edge_key).collect()
+    }
+
+    fn node_set(graph: &GraphData) -> BTreeSet<String> {
+        graph.node_ids().map(String::from).collect()
+    }
+
+    #[tokio::test]
+    async fn test_build_records_files() {
+        let (_dir, content_dir) = setup_test_files().await;
+
+        let (_graph, metadata, _stats) = GraphBuilder::new(MockExtractor)
+            .with_content_path(&content_dir)
+            .build_with_metadata()
+            .await
+            .unwrap();
+
+        assert_eq!(metadata.files.len(), 2);
+        let record = &metadata.files["concept-a.md"];
+        assert_eq!(record.node_id, "concept-a");
+        assert_eq!(record.edges.len(), 1);
+        assert_eq!(record.edges[0].to, "concept-b");
+        assert!(metadata.files["concept-b.md"].edges.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_update_modified_file_reresolves_incoming_edges() {
+        let (_dir, content_dir) = setup_test_files().await;
+        let builder = GraphBuilder::new(MockExtractor).with_content_path(&content_dir);
+        let (mut graph, mut metadata, _) = GraphBuilder::new(MockExtractor)
+            .with_content_path(&content_dir)
+            .build_with_metadata()
+            .await
+            .unwrap();
+
+        std::fs::write(
+            content_dir.join("concept-b.md"),
+            "---\ntitle: \"Concept B v2\"\n---\n\n# Concept B\n",
+        )
+        .unwrap();
+
+        let stats = builder.update(&mut graph, &mut metadata).await.unwrap();
+        assert_eq!(stats.files_modified, 1);
+        assert_eq!(stats.files_unchanged, 1);
+        assert_eq!(stats.files_extracted, 1);
+        assert_eq!(stats.nodes_removed, 1);
+        assert_eq!(stats.nodes_added, 1);
+
+        assert_eq!(graph.get_node("concept-b").unwrap().title, "Concept B v2");
+        // concept-a (unchanged) still points at the re-extracted concept-b
+        assert_eq!(stats.edges_added, 1);
+        assert!(edge_set(&graph).contains(&(
+            "concept-a".to_string(),
+            "concept-b".to_string(),
+            "prerequisite".to_string()
+        )));
+
+        let stats = builder.update(&mut graph, &mut metadata).await.unwrap();
+        assert!(stats.is_noop());
+    }
+
+    #[tokio::test]
+    async fn test_update_matches_full_rebuild() {
+        let (_dir, content_dir) = setup_test_files().await;
+        let manual_path = content_dir.parent().unwrap().join("manual.json");
+        std::fs::write(
+            &manual_path,
+            r#"[{"from": "concept-c", "to": "concept-a", "relationship": "extends"}]"#,
+        )
+        .unwrap();
+
+        let builder = GraphBuilder::new(MockExtractor)
+            .with_content_path(&content_dir)
+            .with_manual_edges(&manual_path);
+        let (mut graph, mut metadata, stats) = GraphBuilder::new(MockExtractor)
+            .with_content_path(&content_dir)
+            .with_manual_edges(&manual_path)
+            .build_with_metadata()
+            .await
+            .unwrap();
+        assert_eq!(stats.dangling_refs.len(), 1);
+
+        // Add C (resolves the manual edge), drop B (a -> b dangles), edit A
+        std::fs::write(
+            content_dir.join("concept-c.md"),
+            "---\ntitle: \"C\"\nrelated:\n  - concept-a\n---\n",
+        )
+        .unwrap();
+        std::fs::remove_file(content_dir.join("concept-b.md")).unwrap();
+        std::fs::write(
+            content_dir.join("concept-a.md"),
+            "---\ntitle: \"A\"\nprerequisites:\n  - concept-b\nrelated:\n  - concept-c\n---\n",
+        )
+        .unwrap();
+
+        let update = builder.update(&mut graph, &mut metadata).await.unwrap();
+        assert_eq!(update.files_added, 1);
+        assert_eq!(update.files_modified, 1);
+        assert_eq!(update.files_removed, 1);
+        assert_eq!(update.manual_edges_added, 1);
+        assert_eq!(
+            update.dangling_refs,
+            vec!["concept-a -[prerequisite]-> concept-b"]
+        );
+
+        let (full, full_metadata, full_stats) = GraphBuilder::new(MockExtractor)
+            .with_content_path(&content_dir)
+            .with_manual_edges(&manual_path)
+            .build_with_metadata()
+            .await
+            .unwrap();
+        assert_eq!(node_set(&graph), node_set(&full));
+        assert_eq!(edge_set(&graph), edge_set(&full));
+        assert_eq!(metadata.files, full_metadata.files);
+        assert_eq!(update.dangling_refs, full_stats.dangling_refs);
+    }
+
+    #[tokio::test]
+    async fn test_apply_file_change_resolves_dangling_ref() {
+        let dir = tempdir().unwrap();
+        let content_dir = dir.path().join("content");
+        std::fs::create_dir(&content_dir).unwrap();
+        std::fs::write(
+            content_dir.join("orphan.md"),
+            "---\ntitle: \"Orphan\"\nprerequisites:\n  - parent\n---\n",
+        )
+        .unwrap();
+
+        let builder = GraphBuilder::new(MockExtractor).with_content_path(&content_dir);
+        let (mut graph, mut metadata, stats) = GraphBuilder::new(MockExtractor)
+            .with_content_path(&content_dir)
+            .build_with_metadata()
+            .await
+            .unwrap();
+        assert_eq!(stats.dangling_refs.len(), 1);
+
+        // Added file, given relative to the content path
+        std::fs::write(
+            content_dir.join("parent.md"),
+            "---\ntitle: Parent
+---
+",
+        )
+        .unwrap();
+        let stats = builder
+            .apply_file_change(&mut graph, &mut metadata, "parent.md")
+            .await
+            .unwrap();
+        assert_eq!(stats.files_added, 1);
+        assert_eq!(stats.edges_added, 1);
+        assert!(stats.dangling_refs.is_empty());
+        assert_eq!(graph.edge_count(), 1);
+
+        // Unchanged file is a no-op
+        let stats = builder
+            .apply_file_change(&mut graph, &mut metadata, content_dir.join("parent.md"))
+            .await
+            .unwrap();
+        assert_eq!(stats.files_unchanged, 1);
+        assert!(stats.is_noop());
+
+        // Deleted file
+        std::fs::remove_file(content_dir.join("parent.md")).unwrap();
+        let stats = builder
+            .apply_file_change(&mut graph, &mut metadata, "parent.md")
+            .await
+            .unwrap();
+        assert_eq!(stats.files_removed, 1);
+        assert_eq!(stats.nodes_removed, 1);
+        assert_eq!(graph.node_count(), 1);
+        assert_eq!(graph.edge_count(), 0);
+        assert_eq!(stats.dangling_refs.len(), 1);
+        assert!(!metadata.files.contains_key("parent.md"));
+    }
+
+    #[tokio::test]
+    async fn test_apply_file_change_outside_content_path() {
+        let (dir, content_dir) = setup_test_files().await;
+        let builder = GraphBuilder::new(MockExtractor).with_content_path(&content_dir);
+        let mut graph = GraphData::new();
+        let mut metadata = GraphMetadata::default();
+
+        let result = builder
+            .apply_file_change(&mut graph, &mut metadata, dir.path().join("other.md"))
+            .await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_builder_cache_incremental_on_file_removal() {
+        let (_dir, content_dir) = setup_test_files().await;
+        let cache_path = content_dir.parent().unwrap().join("graph-cache.json");
+
+        GraphBuilder::new(MockExtractor)
+            .with_content_path(&content_dir)
+            .with_cache_path(&cache_path)
+            .build()
+            .await
+            .unwrap();
+
+        std::fs::remove_file(content_dir.join("concept-b.md")).unwrap();
+
+        let (graph, stats) = GraphBuilder::new(MockExtractor)
+            .with_content_path(&content_dir)
+            .with_cache_path(&cache_path)
+            .build()
+            .await
+            .unwrap();
+        assert!(!stats.from_cache);
+        assert_eq!(stats.files_processed, 0);
+        assert_eq!(stats.files_unchanged, 1);
+        assert_eq!(graph.node_count(), 1);
+        assert_eq!(graph.edge_count(), 0);
+        assert_eq!(stats.dangling_refs.len(), 1);
+
+        // The updated cache is now fresh
+        let (_, stats) = GraphBuilder::new(MockExtractor)
+            .with_content_path(&content_dir)
+            .with_cache_path(&cache_path)
+            .build()
+            .await
+            .unwrap();
+        assert!(stats.from_cache);
+    }
+
+    #[test]
+    fn test_relative_key_uses_forward_slashes() {
+        let base = Path::new("/content");
+        assert_eq!(
+            relative_key(base, Path::new("/content/sub/dir/a.md")),
+            "sub/dir/a.md"
+        );
+    }
+
     #[test]
     fn test_compute_content_hash_deterministic() {
         let dir = tempdir().unwrap();
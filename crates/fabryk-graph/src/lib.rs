@@ -17,10 +17,12 @@
 //!   for domain-specific relationships
 //! - `NodeType` enum: Distinguishes domain vs user-query nodes
 //! - `GraphData`: Core graph structure with runtime mutation support
+//! - `GraphQuery`: JSON pattern-matching queries evaluated by `execute_query`
 
 pub mod algorithms;
 pub mod builder;
 pub mod extractor;
+pub mod pattern;
 pub mod persistence;
 pub mod query;
 pub mod stats;
@@ -34,14 +36,24 @@ pub use algorithms::{
 };
 
 // Re-exports — builder
-pub use builder::{BuildError, BuildStats, ErrorHandling, GraphBuilder, ManualEdge};
+pub use builder::{
+    BuildError, BuildStats, ErrorHandling, GraphBuilder, ManualEdge, UpdateStats,
+    compute_content_hash, compute_store_hash,
+};
 
 // Re-exports — extractor
 pub use extractor::GraphExtractor;
 
+// Re-exports — pattern
+pub use pattern::{
+    EdgeDirection, EdgePattern, GraphQuery, Hop, MetadataFilter, MetadataOp, NodePattern,
+    QueryLimits, QueryMatch, QueryResult, QueryReturn, execute_query,
+};
+
 // Re-exports — persistence
 pub use persistence::{
-    GraphMetadata, SerializableGraph, is_cache_fresh, load_graph, load_graph_from_str, save_graph,
+    FileRecord, GraphMetadata, SerializableGraph, is_cache_fresh, load_graph, load_graph_from_str,
+    load_graph_with_metadata, save_graph,
 };
 
 // Re-exports — query
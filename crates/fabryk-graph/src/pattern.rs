@@ -0,0 +1,1046 @@
+//! Pattern-matching queries over `GraphData`.
+//!
+//! The fixed algorithms in [`crate::algorithms`] answer common questions,
+//! but agents often need ad-hoc traversals such as "concepts in category X
+//! that are prerequisites of anything in source Y within 3 hops". This
+//! module provides a small JSON query DSL for those cases.
+//!
+//! A [`GraphQuery`] is a linear path pattern: a start [`NodePattern`]
+//! followed by zero or more [`Hop`]s, each pairing an [`EdgePattern`]
+//! (relationship, direction, hop bounds) with the node pattern the hop
+//! must end on.
+//!
+//! ```json
+//! {
+//!   "start": { "category": "harmony" },
+//!   "hops": [
+//!     {
+//!       "edge": { "relationships": ["prerequisite"], "min_hops": 1, "max_hops": 3 },
+//!       "node": { "source_id": "tymoczko" }
+//!     }
+//!   ],
+//!   "return": "start",
+//!   "limit": 20
+//! }
+//! ```
+//!
+//! # Semantics
+//!
+//! - Variable-length hops are evaluated breadth-first: every node reachable
+//!   within the hop's bounds is matched once, at its shortest distance.
+//! - Start candidates and neighbours are visited in node-ID order, so
+//!   results are deterministic.
+//! - Evaluation cost is bounded by [`QueryLimits`]. Queries exceeding the
+//!   structural limits are rejected up front; queries exhausting the
+//!   expansion budget return the matches found so far with
+//!   `budget_exhausted` set.
+
+use crate::algorithms::MAX_BFS_DEPTH;
+use crate::{Edge, GraphData, Node, NodeType};
+use fabryk_core::{Error, Result};
+use petgraph::Direction;
+use petgraph::graph::{EdgeIndex, NodeIndex};
+use petgraph::visit::EdgeRef;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// Default number of matches returned when a query sets no `limit`.
+pub const DEFAULT_QUERY_LIMIT: usize = 50;
+
+// ============================================================================
+// Query types
+// ============================================================================
+
+/// A path pattern query over the graph.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct GraphQuery {
+    /// Pattern the first node of each match must satisfy.
+    #[serde(default)]
+    pub start: NodePattern,
+    /// Hops to follow from the start node, in order.
+    #[serde(default)]
+    pub hops: Vec<Hop>,
+    /// What each match returns.
+    #[serde(default, rename = "return")]
+    pub returns: QueryReturn,
+    /// Maximum number of matches (default [`DEFAULT_QUERY_LIMIT`]).
+    #[serde(default)]
+    pub limit: Option<usize>,
+}
+
+impl GraphQuery {
+    /// Creates a query matching nodes that satisfy `start`.
+    pub fn new(start: NodePattern) -> Self {
+        Self {
+            start,
+            ..Default::default()
+        }
+    }
+
+    /// Appends a hop to the pattern.
+    pub fn with_hop(mut self, edge: EdgePattern, node: NodePattern) -> Self {
+        self.hops.push(Hop { edge, node });
+        self
+    }
+
+    /// Sets what each match returns.
+    pub fn with_return(mut self, returns: QueryReturn) -> Self {
+        self.returns = returns;
+        self
+    }
+
+    /// Sets the result limit.
+    pub fn with_limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Checks the query against structural limits.
+    ///
+    /// Returns a config error describing the first violation.
+    pub fn validate(&self, limits: &QueryLimits) -> Result<()> {
+        if self.hops.len() > limits.max_hops {
+            return Err(Error::config(format!(
+                "query has {} hops; at most {} are allowed",
+                self.hops.len(),
+                limits.max_hops
+            )));
+        }
+
+        let mut total_depth = 0;
+        for (i, hop) in self.hops.iter().enumerate() {
+            let edge = &hop.edge;
+            if edge.min_hops > edge.max_hops {
+                return Err(Error::config(format!(
+                    "hop {i}: min_hops ({}) exceeds max_hops ({})",
+                    edge.min_hops, edge.max_hops
+                )));
+            }
+            if edge.max_hops > limits.max_depth {
+                return Err(Error::config(format!(
+                    "hop {i}: max_hops ({}) exceeds the limit of {}",
+                    edge.max_hops, limits.max_depth
+                )));
+            }
+            total_depth += edge.max_hops;
+        }
+
+        if total_depth > limits.max_depth {
+            return Err(Error::config(format!(
+                "query spans up to {total_depth} edges; at most {} are allowed",
+                limits.max_depth
+            )));
+        }
+
+        if self.limit == Some(0) {
+            return Err(Error::config("limit must be at least 1"));
+        }
+
+        Ok(())
+    }
+}
+
+/// One step of a path pattern: follow matching edges, land on a matching node.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Hop {
+    /// Edges that may be traversed.
+    #[serde(default)]
+    pub edge: EdgePattern,
+    /// Pattern the node at the end of the hop must satisfy.
+    #[serde(default)]
+    pub node: NodePattern,
+}
+
+/// What each query match returns.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryReturn {
+    /// Full paths: every node and edge along the match.
+    #[default]
+    Paths,
+    /// Distinct start nodes.
+    Start,
+    /// Distinct end nodes.
+    End,
+}
+
+// ============================================================================
+// Node patterns
+// ============================================================================
+
+/// Constraints on a node. An empty pattern matches every node.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct NodePattern {
+    /// Exact node ID.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// Any of these node IDs.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ids: Vec<String>,
+    /// Exact category.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+    /// Exact source ID.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_id: Option<String>,
+    /// Node type (`domain`, `user_query` or a custom type name).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub node_type: Option<String>,
+    /// Canonical (`true`) or variant (`false`) nodes only.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub is_canonical: Option<bool>,
+    /// Case-insensitive substring of the title.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title_contains: Option<String>,
+    /// Metadata filters; all must hold.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub metadata: Vec<MetadataFilter>,
+}
+
+impl NodePattern {
+    /// Creates a pattern matching any node.
+    pub fn any() -> Self {
+        Self::default()
+    }
+
+    /// Creates a pattern matching a single node ID.
+    pub fn id(id: impl Into<String>) -> Self {
+        Self {
+            id: Some(id.into()),
+            ..Default::default()
+        }
+    }
+
+    /// Requires the given category.
+    pub fn with_category(mut self, category: impl Into<String>) -> Self {
+        self.category = Some(category.into());
+        self
+    }
+
+    /// Requires the given source ID.
+    pub fn with_source(mut self, source_id: impl Into<String>) -> Self {
+        self.source_id = Some(source_id.into());
+        self
+    }
+
+    /// Requires the given node type name.
+    pub fn with_node_type(mut self, node_type: impl Into<String>) -> Self {
+        self.node_type = Some(node_type.into());
+        self
+    }
+
+    /// Adds a metadata filter.
+    pub fn with_metadata(mut self, filter: MetadataFilter) -> Self {
+        self.metadata.push(filter);
+        self
+    }
+
+    /// Returns `true` if `node` satisfies every constraint.
+    pub fn matches(&self, node: &Node) -> bool {
+        if self.id.as_ref().is_some_and(|id| *id != node.id) {
+            return false;
+        }
+        if !self.ids.is_empty() && !self.ids.contains(&node.id) {
+            return false;
+        }
+        if self.category.is_some() && self.category != node.category {
+            return false;
+        }
+        if self.source_id.is_some() && self.source_id != node.source_id {
+            return false;
+        }
+        if let Some(node_type) = &self.node_type {
+            let name = match &node.node_type {
+                NodeType::Domain => "domain",
+                NodeType::UserQuery => "user_query",
+                NodeType::Custom(name) => name,
+            };
+            if !name.eq_ignore_ascii_case(node_type) {
+                return false;
+            }
+        }
+        if self.is_canonical.is_some_and(|c| c != node.is_canonical) {
+            return false;
+        }
+        if let Some(needle) = &self.title_contains
+            && !node.title.to_lowercase().contains(&needle.to_lowercase())
+        {
+            return false;
+        }
+        self.metadata.iter().all(|filter| filter.matches(node))
+    }
+
+    /// IDs this pattern is pinned to, if any.
+    fn pinned_ids(&self) -> Option<Vec<&str>> {
+        match &self.id {
+            Some(id) => Some(vec![id.as_str()]),
+            None if !self.ids.is_empty() => Some(self.ids.iter().map(String::as_str).collect()),
+            None => None,
+        }
+    }
+}
+
+/// A filter on one node metadata key.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct MetadataFilter {
+    /// Metadata key.
+    pub key: String,
+    /// Comparison operator (default `eq`).
+    #[serde(default)]
+    pub op: MetadataOp,
+    /// Operand; ignored by `exists`.
+    #[serde(default)]
+    pub value: Value,
+}
+
+impl MetadataFilter {
+    /// Creates a filter comparing `key` against `value` with `op`.
+    pub fn new(key: impl Into<String>, op: MetadataOp, value: impl Into<Value>) -> Self {
+        Self {
+            key: key.into(),
+            op,
+            value: value.into(),
+        }
+    }
+
+    /// Returns `true` if `node` passes this filter.
+    pub fn matches(&self, node: &Node) -> bool {
+        let actual = node.metadata.get(&self.key);
+        match self.op {
+            MetadataOp::Exists => actual.is_some(),
+            MetadataOp::Ne => actual != Some(&self.value),
+            MetadataOp::Eq => actual == Some(&self.value),
+            MetadataOp::In => actual.is_some_and(|a| {
+                self.value
+                    .as_array()
+                    .is_some_and(|options| options.contains(a))
+            }),
+            MetadataOp::Contains => actual.is_some_and(|a| match (a, &self.value) {
+                (Value::Array(items), needle) => items.contains(needle),
+                (Value::String(s), Value::String(needle)) => {
+                    s.to_lowercase().contains(&needle.to_lowercase())
+                }
+                _ => false,
+            }),
+            MetadataOp::Gt => compare(actual, &self.value) == Some(Ordering::Greater),
+            MetadataOp::Gte => matches!(
+                compare(actual, &self.value),
+                Some(Ordering::Greater | Ordering::Equal)
+            ),
+            MetadataOp::Lt => compare(actual, &self.value) == Some(Ordering::Less),
+            MetadataOp::Lte => matches!(
+                compare(actual, &self.value),
+                Some(Ordering::Less | Ordering::Equal)
+            ),
+        }
+    }
+}
+
+/// Comparison operators for [`MetadataFilter`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MetadataOp {
+    /// Value equals the operand.
+    #[default]
+    Eq,
+    /// Value is missing or differs from the operand.
+    Ne,
+    /// Key is present.
+    Exists,
+    /// Value is one of the operand's array elements.
+    In,
+    /// Array value contains the operand, or string value contains it
+    /// (case-insensitive).
+    Contains,
+    /// Value is greater than the operand (numbers or strings).
+    Gt,
+    /// Value is greater than or equal to the operand.
+    Gte,
+    /// Value is less than the operand.
+    Lt,
+    /// Value is less than or equal to the operand.
+    Lte,
+}
+
+fn compare(actual: Option<&Value>, operand: &Value) -> Option<Ordering> {
+    match (actual?, operand) {
+        (Value::Number(a), Value::Number(b)) => a.as_f64()?.partial_cmp(&b.as_f64()?),
+        (Value::String(a), Value::String(b)) => Some(a.cmp(b)),
+        _ => None,
+    }
+}
+
+// ============================================================================
+// Edge patterns
+// ============================================================================
+
+/// Constraints on the edges a hop may traverse.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct EdgePattern {
+    /// Allowed relationship names (e.g. `prerequisite`); empty allows all.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub relationships: Vec<String>,
+    /// Direction to traverse edges in.
+    #[serde(default)]
+    pub direction: EdgeDirection,
+    /// Minimum number of edges in the hop (default 1; 0 lets the hop
+    /// match its own starting node).
+    #[serde(default = "default_hops")]
+    pub min_hops: usize,
+    /// Maximum number of edges in the hop (default 1).
+    #[serde(default = "default_hops")]
+    pub max_hops: usize,
+    /// Minimum edge weight.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_weight: Option<f32>,
+}
+
+fn default_hops() -> usize {
+    1
+}
+
+impl Default for EdgePattern {
+    fn default() -> Self {
+        Self {
+            relationships: Vec::new(),
+            direction: EdgeDirection::default(),
+            min_hops: 1,
+            max_hops: 1,
+            min_weight: None,
+        }
+    }
+}
+
+impl EdgePattern {
+    /// Creates a single-edge outgoing pattern over any relationship.
+    pub fn any() -> Self {
+        Self::default()
+    }
+
+    /// Restricts the pattern to the given relationship names.
+    pub fn with_relationships<I, S>(mut self, relationships: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.relationships = relationships.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Sets the traversal direction.
+    pub fn with_direction(mut self, direction: EdgeDirection) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    /// Sets the hop bounds.
+    pub fn with_hops(mut self, min_hops: usize, max_hops: usize) -> Self {
+        self.min_hops = min_hops;
+        self.max_hops = max_hops;
+        self
+    }
+
+    /// Returns `true` if `edge` may be traversed.
+    pub fn matches(&self, edge: &Edge) -> bool {
+        if !self.relationships.is_empty()
+            && !self
+                .relationships
+                .iter()
+                .any(|r| r.eq_ignore_ascii_case(edge.relationship.name()))
+        {
+            return false;
+        }
+        self.min_weight.is_none_or(|w| edge.weight >= w)
+    }
+}
+
+/// Direction in which a hop follows edges.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EdgeDirection {
+    /// Follow edges from `from` to `to`.
+    #[default]
+    Outgoing,
+    /// Follow edges from `to` back to `from`.
+    Incoming,
+    /// Follow edges either way.
+    Both,
+}
+
+// ============================================================================
+// Limits and results
+// ============================================================================
+
+/// Cost guardrails for query evaluation.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QueryLimits {
+    /// Maximum number of hops in a pattern.
+    pub max_hops: usize,
+    /// Maximum edges a match may span, summed over every hop's `max_hops`.
+    pub max_depth: usize,
+    /// Upper bound on the query's `limit`.
+    pub max_results: usize,
+    /// Maximum number of edge expansions before evaluation stops.
+    pub max_expansions: usize,
+}
+
+impl Default for QueryLimits {
+    fn default() -> Self {
+        Self {
+            max_hops: 5,
+            max_depth: MAX_BFS_DEPTH,
+            max_results: 500,
+            max_expansions: 100_000,
+        }
+    }
+}
+
+impl QueryLimits {
+    /// Sets the maximum number of hops.
+    pub fn with_max_hops(mut self, max_hops: usize) -> Self {
+        self.max_hops = max_hops;
+        self
+    }
+
+    /// Sets the maximum total path depth.
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Sets the maximum result count.
+    pub fn with_max_results(mut self, max_results: usize) -> Self {
+        self.max_results = max_results;
+        self
+    }
+
+    /// Sets the edge expansion budget.
+    pub fn with_max_expansions(mut self, max_expansions: usize) -> Self {
+        self.max_expansions = max_expansions;
+        self
+    }
+}
+
+/// One match of a query.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct QueryMatch {
+    /// Matched nodes: the whole path for `paths`, otherwise the single
+    /// start or end node.
+    pub nodes: Vec<Node>,
+    /// Edges along the path, in traversal order (empty unless `paths`).
+    pub edges: Vec<Edge>,
+}
+
+/// Result of evaluating a query.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct QueryResult {
+    /// Matches in evaluation order.
+    pub matches: Vec<QueryMatch>,
+    /// Whether more matches existed beyond the limit.
+    pub truncated: bool,
+    /// Whether evaluation stopped because the expansion budget ran out.
+    pub budget_exhausted: bool,
+    /// Number of edge expansions performed.
+    pub expansions: usize,
+}
+
+// ============================================================================
+// Evaluation
+// ============================================================================
+
+/// Evaluates a pattern query against the graph.
+///
+/// Fails if the query violates `limits`. Otherwise returns up to
+/// `query.limit` matches (capped at `limits.max_results`).
+///
+/// # Example
+///
+/// ```rust
+/// use fabryk_graph::{
+///     Edge, EdgePattern, GraphData, GraphQuery, Node, NodePattern, QueryLimits, QueryReturn,
+///     Relationship, execute_query,
+/// };
+///
+/// let mut graph = GraphData::new();
+/// graph.add_node(Node::new("a", "A").with_category("basics"));
+/// graph.add_node(Node::new("b", "B").with_source("book"));
+/// graph.add_edge(Edge::new("a", "b", Relationship::Prerequisite)).unwrap();
+///
+/// let query = GraphQuery::new(NodePattern::any().with_category("basics"))
+///     .with_hop(
+///         EdgePattern::any().with_relationships(["prerequisite"]).with_hops(1, 3),
+///         NodePattern::any().with_source("book"),
+///     )
+///     .with_return(QueryReturn::Start);
+///
+/// let result = execute_query(&graph, &query, &QueryLimits::default()).unwrap();
+/// assert_eq!(result.matches[0].nodes[0].id, "a");
+/// ```
+pub fn execute_query(
+    graph: &GraphData,
+    query: &GraphQuery,
+    limits: &QueryLimits,
+) -> Result<QueryResult> {
+    query.validate(limits)?;
+
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_QUERY_LIMIT)
+        .min(limits.max_results);
+
+    let mut starts: Vec<(&str, NodeIndex)> = match query.start.pinned_ids() {
+        Some(ids) => ids
+            .into_iter()
+            .filter_map(|id| graph.get_index(id).map(|idx| (id, idx)))
+            .collect(),
+        None => graph
+            .node_indices
+            .iter()
+            .map(|(id, idx)| (id.as_str(), *idx))
+            .collect(),
+    };
+    starts.sort_by(|a, b| a.0.cmp(b.0));
+    starts.dedup_by(|a, b| a.0 == b.0);
+
+    let mut eval = Evaluator {
+        graph,
+        query,
+        limit,
+        budget: limits.max_expansions,
+        result: QueryResult {
+            matches: Vec::new(),
+            truncated: false,
+            budget_exhausted: false,
+            expansions: 0,
+        },
+        seen: HashSet::new(),
+    };
+
+    for (_, idx) in starts {
+        if eval.done() {
+            break;
+        }
+        if query.start.matches(&graph.graph[idx]) {
+            eval.extend(0, &mut vec![idx], &mut Vec::new());
+        }
+    }
+
+    Ok(eval.result)
+}
+
+struct Evaluator<'a> {
+    graph: &'a GraphData,
+    query: &'a GraphQuery,
+    limit: usize,
+    budget: usize,
+    result: QueryResult,
+    /// Node IDs already returned (for `start`/`end` returns).
+    seen: HashSet<String>,
+}
+
+impl Evaluator<'_> {
+    fn done(&self) -> bool {
+        self.result.truncated || self.result.budget_exhausted
+    }
+
+    fn extend(&mut self, step: usize, nodes: &mut Vec<NodeIndex>, edges: &mut Vec<EdgeIndex>) {
+        if self.query.returns == QueryReturn::Start
+            && self.seen.contains(&self.graph.graph[nodes[0]].id)
+        {
+            return;
+        }
+
+        let Some(hop) = self.query.hops.get(step) else {
+            self.emit(nodes, edges);
+            return;
+        };
+
+        let Some(&current) = nodes.last() else {
+            return;
+        };
+        for (target, path) in self.reachable(current, hop) {
+            if self.done() {
+                return;
+            }
+            nodes.push(target);
+            let len = edges.len();
+            edges.extend(path);
+            self.extend(step + 1, nodes, edges);
+            edges.truncate(len);
+            nodes.pop();
+        }
+    }
+
+    fn emit(&mut self, nodes: &[NodeIndex], edges: &[EdgeIndex]) {
+        let graph = &self.graph.graph;
+        let single = match self.query.returns {
+            QueryReturn::Paths => None,
+            QueryReturn::Start => nodes.first(),
+            QueryReturn::End => nodes.last(),
+        };
+
+        if let Some(&idx) = single
+            && self.seen.contains(&graph[idx].id)
+        {
+            return;
+        }
+
+        if self.result.matches.len() >= self.limit {
+            self.result.truncated = true;
+            return;
+        }
+
+        let query_match = match single {
+            Some(&idx) => {
+                self.seen.insert(graph[idx].id.clone());
+                QueryMatch {
+                    nodes: vec![graph[idx].clone()],
+                    edges: Vec::new(),
+                }
+            }
+            None => QueryMatch {
+                nodes: nodes.iter().map(|&idx| graph[idx].clone()).collect(),
+                edges: edges.iter().map(|&idx| graph[idx].clone()).collect(),
+            },
+        };
+        self.result.matches.push(query_match);
+    }
+
+    /// Breadth-first search from `from` along `hop.edge`, returning each
+    /// matching node within the hop bounds with its shortest edge path.
+    fn reachable(&mut self, from: NodeIndex, hop: &Hop) -> Vec<(NodeIndex, Vec<EdgeIndex>)> {
+        let graph = &self.graph.graph;
+        let pattern = &hop.edge;
+
+        let mut parents: HashMap<NodeIndex, (NodeIndex, EdgeIndex)> = HashMap::new();
+        let mut visited = HashSet::from([from]);
+        let mut queue = VecDeque::from([(from, 0usize)]);
+        let mut found = Vec::new();
+
+        while let Some((current, depth)) = queue.pop_front() {
+            if depth >= pattern.min_hops && hop.node.matches(&graph[current]) {
+                found.push((current, path_to(&parents, current)));
+            }
+            if depth >= pattern.max_hops {
+                continue;
+            }
+
+            let mut next: Vec<(NodeIndex, EdgeIndex)> = Vec::new();
+            let directions: &[Direction] = match pattern.direction {
+                EdgeDirection::Outgoing => &[Direction::Outgoing],
+                EdgeDirection::Incoming => &[Direction::Incoming],
+                EdgeDirection::Both => &[Direction::Outgoing, Direction::Incoming],
+            };
+            for &direction in directions {
+                for edge_ref in graph.edges_directed(current, direction) {
+                    if self.result.expansions >= self.budget {
+                        self.result.budget_exhausted = true;
+                        return found;
+                    }
+                    self.result.expansions += 1;
+
+                    if !pattern.matches(edge_ref.weight()) {
+                        continue;
+                    }
+                    let neighbor = match direction {
+                        Direction::Outgoing => edge_ref.target(),
+                        Direction::Incoming => edge_ref.source(),
+                    };
+                    next.push((neighbor, edge_ref.id()));
+                }
+            }
+            next.sort_by(|a, b| graph[a.0].id.cmp(&graph[b.0].id));
+
+            for (neighbor, edge) in next {
+                if visited.insert(neighbor) {
+                    parents.insert(neighbor, (current, edge));
+                    queue.push_back((neighbor, depth + 1));
+                }
+            }
+        }
+
+        found
+    }
+}
+
+fn path_to(
+    parents: &HashMap<NodeIndex, (NodeIndex, EdgeIndex)>,
+    node: NodeIndex,
+) -> Vec<EdgeIndex> {
+    let mut path = Vec::new();
+    let mut current = node;
+    while let Some(&(parent, edge)) = parents.get(&current) {
+        path.push(edge);
+        current = parent;
+    }
+    path.reverse();
+    path
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Relationship;
+    use serde_json::json;
+
+    /// Builds: a -> b -> c -> d (prerequisite), a -> e (relates_to).
+    fn create_test_graph() -> GraphData {
+        let mut graph = GraphData::new();
+        graph.add_node(
+            Node::new("a", "Alpha")
+                .with_category("basics")
+                .with_metadata("level", 1),
+        );
+        graph.add_node(
+            Node::new("b", "Beta")
+                .with_category("basics")
+                .with_metadata("level", 2)
+                .with_metadata("tags", json!(["core", "intro"])),
+        );
+        graph.add_node(Node::new("c", "Gamma").with_source("book"));
+        graph.add_node(
+            Node::new("d", "Delta")
+                .with_source("book")
+                .with_metadata("level", 4),
+        );
+        graph.add_node(Node::new("e", "Epsilon").with_source("paper"));
+
+        for edge in [
+            Edge::new("a", "b", Relationship::Prerequisite),
+            Edge::new("b", "c", Relationship::Prerequisite),
+            Edge::new("c", "d", Relationship::Prerequisite),
+            Edge::new("a", "e", Relationship::RelatesTo),
+        ] {
+            graph.add_edge(edge).unwrap();
+        }
+        graph
+    }
+
+    fn ids(result: &QueryResult) -> Vec<Vec<&str>> {
+        result
+            .matches
+            .iter()
+            .map(|m| m.nodes.iter().map(|n| n.id.as_str()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_start_only_filters_nodes() {
+        let graph = create_test_graph();
+        let query = GraphQuery::new(NodePattern::any().with_category("basics"));
+        let result = execute_query(&graph, &query, &QueryLimits::default()).unwrap();
+        assert_eq!(ids(&result), vec![vec!["a"], vec!["b"]]);
+        assert!(!result.truncated);
+    }
+
+    #[test]
+    fn test_variable_length_hop() {
+        let graph = create_test_graph();
+        let query = GraphQuery::new(NodePattern::any().with_category("basics")).with_hop(
+            EdgePattern::any()
+                .with_relationships(["prerequisite"])
+                .with_hops(1, 3),
+            NodePattern::any().with_source("book"),
+        );
+        let result = execute_query(&graph, &query, &QueryLimits::default()).unwrap();
+        assert_eq!(
+            ids(&result),
+            vec![
+                vec!["a", "c"],
+                vec!["a", "d"],
+                vec!["b", "c"],
+                vec!["b", "d"]
+            ]
+        );
+        assert_eq!(result.matches[1].edges.len(), 3);
+    }
+
+    #[test]
+    fn test_hop_bounds_exclude_out_of_range() {
+        let graph = create_test_graph();
+        let query = GraphQuery::new(NodePattern::id("a"))
+            .with_hop(EdgePattern::any().with_hops(2, 2), NodePattern::any());
+        let result = execute_query(&graph, &query, &QueryLimits::default()).unwrap();
+        assert_eq!(ids(&result), vec![vec!["a", "c"]]);
+    }
+
+    #[test]
+    fn test_zero_min_hops_matches_self() {
+        let graph = create_test_graph();
+        let query = GraphQuery::new(NodePattern::id("a"))
+            .with_hop(EdgePattern::any().with_hops(0, 1), NodePattern::any());
+        let result = execute_query(&graph, &query, &QueryLimits::default()).unwrap();
+        assert_eq!(
+            ids(&result),
+            vec![vec!["a", "a"], vec!["a", "b"], vec!["a", "e"]]
+        );
+    }
+
+    #[test]
+    fn test_incoming_direction() {
+        let graph = create_test_graph();
+        let query = GraphQuery::new(NodePattern::id("d")).with_hop(
+            EdgePattern::any()
+                .with_direction(EdgeDirection::Incoming)
+                .with_hops(1, 10),
+            NodePattern::any(),
+        );
+        let result = execute_query(&graph, &query, &QueryLimits::default()).unwrap();
+        assert_eq!(
+            ids(&result),
+            vec![vec!["d", "c"], vec!["d", "b"], vec!["d", "a"]]
+        );
+    }
+
+    #[test]
+    fn test_return_start_is_distinct() {
+        let graph = create_test_graph();
+        let query = GraphQuery::new(NodePattern::any().with_category("basics"))
+            .with_hop(
+                EdgePattern::any().with_hops(1, 3),
+                NodePattern::any().with_source("book"),
+            )
+            .with_return(QueryReturn::Start);
+        let result = execute_query(&graph, &query, &QueryLimits::default()).unwrap();
+        assert_eq!(ids(&result), vec![vec!["a"], vec!["b"]]);
+        assert!(result.matches[0].edges.is_empty());
+    }
+
+    #[test]
+    fn test_return_end_is_distinct() {
+        let graph = create_test_graph();
+        let query = GraphQuery::new(NodePattern::any().with_category("basics"))
+            .with_hop(EdgePattern::any().with_hops(1, 3), NodePattern::any())
+            .with_return(QueryReturn::End);
+        let result = execute_query(&graph, &query, &QueryLimits::default()).unwrap();
+        assert_eq!(
+            ids(&result),
+            vec![vec!["b"], vec!["e"], vec!["c"], vec!["d"]]
+        );
+    }
+
+    #[test]
+    fn test_metadata_filters() {
+        let graph = create_test_graph();
+        let cases = [
+            (
+                MetadataFilter::new("level", MetadataOp::Gte, 2),
+                vec!["b", "d"],
+            ),
+            (MetadataFilter::new("level", MetadataOp::Eq, 1), vec!["a"]),
+            (
+                MetadataFilter::new("level", MetadataOp::In, json!([1, 4])),
+                vec!["a", "d"],
+            ),
+            (
+                MetadataFilter::new("tags", MetadataOp::Contains, "core"),
+                vec!["b"],
+            ),
+            (
+                MetadataFilter::new("tags", MetadataOp::Exists, Value::Null),
+                vec!["b"],
+            ),
+            (
+                MetadataFilter::new("level", MetadataOp::Ne, 1),
+                vec!["b", "c", "d", "e"],
+            ),
+        ];
+        for (filter, expected) in cases {
+            let query = GraphQuery::new(NodePattern::any().with_metadata(filter.clone()));
+            let result = execute_query(&graph, &query, &QueryLimits::default()).unwrap();
+            let got: Vec<&str> = result
+                .matches
+                .iter()
+                .map(|m| m.nodes[0].id.as_str())
+                .collect();
+            assert_eq!(got, expected, "filter {filter:?}");
+        }
+    }
+
+    #[test]
+    fn test_limit_truncates() {
+        let graph = create_test_graph();
+        let query = GraphQuery::new(NodePattern::any()).with_limit(2);
+        let result = execute_query(&graph, &query, &QueryLimits::default()).unwrap();
+        assert_eq!(result.matches.len(), 2);
+        assert!(result.truncated);
+    }
+
+    #[test]
+    fn test_limit_capped_by_max_results() {
+        let graph = create_test_graph();
+        let query = GraphQuery::new(NodePattern::any()).with_limit(100);
+        let limits = QueryLimits::default().with_max_results(3);
+        let result = execute_query(&graph, &query, &limits).unwrap();
+        assert_eq!(result.matches.len(), 3);
+        assert!(result.truncated);
+    }
+
+    #[test]
+    fn test_expansion_budget() {
+        let graph = create_test_graph();
+        let query = GraphQuery::new(NodePattern::any())
+            .with_hop(EdgePattern::any().with_hops(1, 5), NodePattern::any());
+        let limits = QueryLimits::default().with_max_expansions(2);
+        let result = execute_query(&graph, &query, &limits).unwrap();
+        assert!(result.budget_exhausted);
+        assert_eq!(result.expansions, 2);
+    }
+
+    #[test]
+    fn test_validate_rejects_costly_queries() {
+        let graph = create_test_graph();
+        let limits = QueryLimits::default();
+
+        let too_deep = GraphQuery::new(NodePattern::any())
+            .with_hop(EdgePattern::any().with_hops(1, 20), NodePattern::any());
+        assert!(
+            execute_query(&graph, &too_deep, &limits)
+                .unwrap_err()
+                .is_config()
+        );
+
+        let inverted = GraphQuery::new(NodePattern::any())
+            .with_hop(EdgePattern::any().with_hops(3, 1), NodePattern::any());
+        assert!(inverted.validate(&limits).is_err());
+
+        let mut too_many = GraphQuery::new(NodePattern::any());
+        for _ in 0..6 {
+            too_many = too_many.with_hop(EdgePattern::any(), NodePattern::any());
+        }
+        assert!(too_many.validate(&limits).is_err());
+
+        let zero = GraphQuery::new(NodePattern::any()).with_limit(0);
+        assert!(zero.validate(&limits).is_err());
+    }
+
+    #[test]
+    fn test_query_from_json() {
+        let graph = create_test_graph();
+        let query: GraphQuery = serde_json::from_value(json!({
+            "start": { "category": "basics", "title_contains": "ALP" },
+            "hops": [{
+                "edge": { "relationships": ["Prerequisite"], "max_hops": 3 },
+                "node": { "source_id": "book" }
+            }],
+            "return": "end"
+        }))
+        .unwrap();
+        let result = execute_query(&graph, &query, &QueryLimits::default()).unwrap();
+        assert_eq!(ids(&result), vec![vec!["c"], vec!["d"]]);
+    }
+
+    #[test]
+    fn test_unknown_start_id_yields_nothing() {
+        let graph = create_test_graph();
+        let query = GraphQuery::new(NodePattern::id("missing"));
+        let result = execute_query(&graph, &query, &QueryLimits::default()).unwrap();
+        assert!(result.matches.is_empty());
+    }
+}
@@ -14,7 +14,7 @@ use crate::{Edge, GraphData, Node};
 use fabryk_core::{Error, Result};
 use petgraph::graph::DiGraph;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::Path;
 
 // ============================================================================
@@ -47,6 +47,24 @@ pub struct GraphMetadata {
     pub content_hash: Option<String>,
     /// Number of source files processed.
     pub source_file_count: Option<usize>,
+    /// Per-file records keyed by path relative to the content root.
+    ///
+    /// Used by incremental rebuilds to find changed files and re-resolve
+    /// the edges they declared.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub files: BTreeMap<String, FileRecord>,
+}
+
+/// What a single content file contributed to the graph.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct FileRecord {
+    /// Hash of the file's contents.
+    pub content_hash: String,
+    /// ID of the node extracted from the file.
+    pub node_id: String,
+    /// Edges the file declared, including ones that did not resolve.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub edges: Vec<Edge>,
 }
 
 impl Default for GraphMetadata {
@@ -56,6 +74,7 @@ impl Default for GraphMetadata {
             builder_version: env!("CARGO_PKG_VERSION").to_string(),
             content_hash: None,
             source_file_count: None,
+            files: BTreeMap::new(),
         }
     }
 }
@@ -103,6 +122,20 @@ pub fn load_graph(path: impl AsRef<Path>) -> Result<GraphData> {
     load_graph_from_str(&json)
 }
 
+/// Load a graph and its metadata from a JSON file.
+pub fn load_graph_with_metadata(
+    path: impl AsRef<Path>,
+) -> Result<(GraphData, Option<GraphMetadata>)> {
+    let json = std::fs::read_to_string(path.as_ref())
+        .map_err(|e| Error::io_with_path(e, path.as_ref()))?;
+
+    let mut serializable: SerializableGraph = serde_json::from_str(&json)
+        .map_err(|e| Error::parse(format!("Failed to parse graph JSON: {e}")))?;
+    let metadata = serializable.metadata.take();
+
+    Ok((to_graph_data(serializable)?, metadata))
+}
+
 /// Load a graph from a JSON string.
 ///
 /// Useful for testing or loading from non-file sources.
@@ -383,6 +416,30 @@ mod tests {
         assert!(!meta.builder_version.is_empty());
         assert!(meta.content_hash.is_none());
         assert!(meta.source_file_count.is_none());
+        assert!(meta.files.is_empty());
+    }
+
+    #[test]
+    fn test_load_graph_with_metadata() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("graph.json");
+
+        let mut metadata = GraphMetadata::default();
+        metadata.files.insert(
+            "a.md".to_string(),
+            FileRecord {
+                content_hash: "h1".to_string(),
+                node_id: "a".to_string(),
+                edges: vec![Edge::new("a", "b", Relationship::Prerequisite)],
+            },
+        );
+        save_graph(&create_test_graph(), &path, Some(metadata)).unwrap();
+
+        let (graph, metadata) = load_graph_with_metadata(&path).unwrap();
+        assert_eq!(graph.node_count(), 2);
+        let record = &metadata.unwrap().files["a.md"];
+        assert_eq!(record.node_id, "a");
+        assert_eq!(record.edges.len(), 1);
     }
 
     #[test]
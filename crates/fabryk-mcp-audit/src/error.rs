@@ -0,0 +1,25 @@
+//! Audit logging error types.
+
+use fabryk_redis::RedisError;
+
+/// Errors from writing or reading audit records.
+#[derive(Debug, thiserror::Error)]
+pub enum AuditError {
+    /// Reading or writing an audit log file failed.
+    #[error("audit log I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// A record could not be serialized or parsed.
+    #[error("audit record serialization error: {0}")]
+    Serialization(#[from] serde_json::Error),
+
+    /// The Redis stream could not be written.
+    #[error("audit stream error: {0}")]
+    Redis(String),
+}
+
+impl From<RedisError> for AuditError {
+    fn from(e: RedisError) -> Self {
+        AuditError::Redis(e.to_string())
+    }
+}
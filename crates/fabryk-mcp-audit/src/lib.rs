@@ -0,0 +1,37 @@
+//! Audit logging of Fabryk MCP tool calls.
+//!
+//! Provides:
+//! - [`AuditedRegistry`] — `ToolRegistry` wrapper recording who called
+//!   which tool, with redacted arguments, latency, result size and error
+//! - [`AuditSink`] — pluggable destinations: [`JsonlSink`],
+//!   [`RedisStreamSink`] and [`TracingSink`]
+//! - [`UsageReport`] — per-user usage aggregated from a JSONL log, as
+//!   shown by `fabryk audit usage`
+//!
+//! Redaction uses the same [`Redactor`](fabryk_mcp_core::Redactor) as the
+//! `debug_config` diagnostics tool, so rules can be loaded from config.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use fabryk_mcp_audit::{AuditedRegistry, JsonlSink, RedisStreamSink};
+//!
+//! let registry = AuditedRegistry::new(composite)
+//!     .with_sink(Arc::new(JsonlSink::new(&config.audit.path)))
+//!     .with_sink(Arc::new(RedisStreamSink::new(redis.clone())))
+//!     .with_redactor(config.audit.redact.clone());
+//!
+//! FabrykMcpServer::new(registry).into_http_service();
+//! ```
+
+mod error;
+mod record;
+mod registry;
+mod report;
+mod sink;
+
+pub use error::AuditError;
+pub use record::{ANONYMOUS, AuditRecord};
+pub use registry::AuditedRegistry;
+pub use report::{UsageFilter, UsageReport, UserUsage, read_jsonl};
+pub use sink::{AuditSink, JsonlSink, RedisStreamSink, TracingSink};
@@ -0,0 +1,44 @@
+//! The audit record written for each tool call.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Caller name used in reports when a call had no authenticated user.
+pub const ANONYMOUS: &str = "anonymous";
+
+/// One tool call.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AuditRecord {
+    /// When the call started.
+    pub timestamp: DateTime<Utc>,
+    /// Email of the authenticated caller, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user: Option<String>,
+    /// Subject identifier of the authenticated caller, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subject: Option<String>,
+    /// The tool that was called.
+    pub tool: String,
+    /// The call's arguments, after redaction.
+    pub arguments: Value,
+    /// Wall-clock time the tool took.
+    pub latency_ms: u64,
+    /// Size of the serialized result in bytes (0 on protocol errors).
+    pub result_bytes: usize,
+    /// The error message, if the call failed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl AuditRecord {
+    /// Whether the call failed.
+    pub fn is_error(&self) -> bool {
+        self.error.is_some()
+    }
+
+    /// The caller's email, or [`ANONYMOUS`].
+    pub fn user_or_anonymous(&self) -> &str {
+        self.user.as_deref().unwrap_or(ANONYMOUS)
+    }
+}
@@ -0,0 +1,300 @@
+//! The auditing registry wrapper.
+
+use std::sync::Arc;
+use std::time::Instant;
+
+use chrono::Utc;
+use fabryk_auth::AuthenticatedUser;
+use fabryk_mcp_core::model::{CallToolResult, ErrorData, Tool};
+use fabryk_mcp_core::{Redactor, ToolContext, ToolRegistry, ToolResult};
+use serde_json::Value;
+
+use crate::record::AuditRecord;
+use crate::sink::AuditSink;
+
+/// Argument keys redacted by default: common credential names.
+const DEFAULT_REDACTED_KEYS: &[&str] = &[
+    "password",
+    "secret",
+    "token",
+    "api_key",
+    "access_token",
+    "authorization",
+];
+
+/// A registry wrapper that records every tool call.
+///
+/// Follows the same wrapper pattern as
+/// [`ValidatedRegistry`](fabryk_mcp_core::ValidatedRegistry):
+/// - `tools()` returns the inner registry's tools unchanged.
+/// - `call()` dispatches to the inner registry, then writes an
+///   [`AuditRecord`] to every sink once the call finishes.
+///
+/// The caller is taken from the [`AuthenticatedUser`] that `AuthLayer`
+/// stores on the HTTP request, reached through
+/// [`ToolContext::extensions`]. Calls without one (stdio, or auth
+/// disabled) are recorded without a user.
+///
+/// Arguments pass through a [`Redactor`] before they are recorded; by
+/// default it redacts common credential keys such as `password` and
+/// `token`. Sink failures are logged and never fail the call. Calls the
+/// client cancels are not recorded.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let registry = AuditedRegistry::new(composite)
+///     .with_sink(Arc::new(JsonlSink::new("/var/log/fabryk/audit.jsonl")))
+///     .with_sink(Arc::new(TracingSink::new()))
+///     .with_redactor(Redactor::new().with_path("query"));
+/// ```
+pub struct AuditedRegistry {
+    inner: Box<dyn ToolRegistry>,
+    sinks: Arc<Vec<Arc<dyn AuditSink>>>,
+    redactor: Redactor,
+}
+
+impl AuditedRegistry {
+    /// Wrap a registry with audit logging. Add sinks with
+    /// [`with_sink`](Self::with_sink); without any, nothing is recorded.
+    pub fn new<R: ToolRegistry + 'static>(registry: R) -> Self {
+        let redactor = DEFAULT_REDACTED_KEYS
+            .iter()
+            .fold(Redactor::new(), |r, key| r.with_key(*key));
+        Self {
+            inner: Box::new(registry),
+            sinks: Arc::new(Vec::new()),
+            redactor,
+        }
+    }
+
+    /// Add a destination for audit records.
+    pub fn with_sink(mut self, sink: Arc<dyn AuditSink>) -> Self {
+        Arc::make_mut(&mut self.sinks).push(sink);
+        self
+    }
+
+    /// Replace the rules used to redact recorded arguments.
+    pub fn with_redactor(mut self, redactor: Redactor) -> Self {
+        self.redactor = redactor;
+        self
+    }
+}
+
+impl ToolRegistry for AuditedRegistry {
+    fn tools(&self) -> Vec<Tool> {
+        self.inner.tools()
+    }
+
+    fn call(&self, name: &str, args: Value) -> Option<ToolResult> {
+        self.call_with_context(name, args, ToolContext::new())
+    }
+
+    fn call_with_context(&self, name: &str, args: Value, ctx: ToolContext) -> Option<ToolResult> {
+        if self.sinks.is_empty() {
+            return self.inner.call_with_context(name, args, ctx);
+        }
+
+        let user = caller(&ctx);
+        let arguments = self.redactor.redacted(&args);
+        let future = self.inner.call_with_context(name, args, ctx)?;
+        let sinks = self.sinks.clone();
+        let tool = name.to_string();
+
+        Some(Box::pin(async move {
+            let timestamp = Utc::now();
+            let started = Instant::now();
+            let result = future.await;
+
+            let record = AuditRecord {
+                timestamp,
+                user: user.as_ref().map(|u| u.email.clone()),
+                subject: user.map(|u| u.subject),
+                tool,
+                arguments,
+                latency_ms: started.elapsed().as_millis() as u64,
+                result_bytes: result_bytes(&result),
+                error: error_message(&result),
+            };
+            for sink in sinks.iter() {
+                if let Err(e) = sink.write(&record).await {
+                    log::warn!("Failed to write audit record to {} sink: {e}", sink.name());
+                }
+            }
+
+            result
+        }))
+    }
+}
+
+/// The authenticated caller, from the HTTP request parts or set directly
+/// on the context.
+fn caller(ctx: &ToolContext) -> Option<AuthenticatedUser> {
+    let extensions = ctx.extensions();
+    extensions
+        .get::<http::request::Parts>()
+        .and_then(|parts| parts.extensions.get::<AuthenticatedUser>())
+        .or_else(|| extensions.get::<AuthenticatedUser>())
+        .cloned()
+}
+
+fn result_bytes(result: &Result<CallToolResult, ErrorData>) -> usize {
+    match result {
+        Ok(result) => serde_json::to_vec(result).map(|v| v.len()).unwrap_or(0),
+        Err(_) => 0,
+    }
+}
+
+fn error_message(result: &Result<CallToolResult, ErrorData>) -> Option<String> {
+    match result {
+        Ok(result) if result.is_error == Some(true) => {
+            let text: Vec<&str> = result
+                .content
+                .iter()
+                .filter_map(|c| c.as_text().map(|t| t.text.as_str()))
+                .collect();
+            Some(if text.is_empty() {
+                "tool returned an error".to_string()
+            } else {
+                text.join("\n")
+            })
+        }
+        Ok(_) => None,
+        Err(e) => Some(e.message.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::AuditError;
+    use async_trait::async_trait;
+    use fabryk_mcp_core::model::Content;
+    use serde_json::json;
+    use std::sync::Mutex;
+
+    #[derive(Debug, Default)]
+    struct MemorySink {
+        records: Mutex<Vec<AuditRecord>>,
+    }
+
+    #[async_trait]
+    impl AuditSink for MemorySink {
+        async fn write(&self, record: &AuditRecord) -> Result<(), AuditError> {
+            self.records.lock().unwrap().push(record.clone());
+            Ok(())
+        }
+
+        fn name(&self) -> &'static str {
+            "memory"
+        }
+    }
+
+    struct TestTools;
+
+    impl ToolRegistry for TestTools {
+        fn tools(&self) -> Vec<Tool> {
+            vec![Tool::new(
+                "search",
+                "Search",
+                fabryk_mcp_core::empty_input_schema(),
+            )]
+        }
+
+        fn call(&self, name: &str, args: Value) -> Option<ToolResult> {
+            match name {
+                "search" => Some(Box::pin(async move {
+                    if args["query"] == "fail" {
+                        return Err(ErrorData::internal_error("index offline", None));
+                    }
+                    if args["query"].is_null() {
+                        return Ok(CallToolResult::error(vec![Content::text("query required")]));
+                    }
+                    Ok(CallToolResult::success(vec![Content::text("3 results")]))
+                })),
+                _ => None,
+            }
+        }
+    }
+
+    fn audited() -> (AuditedRegistry, Arc<MemorySink>) {
+        let sink = Arc::new(MemorySink::default());
+        (
+            AuditedRegistry::new(TestTools).with_sink(sink.clone()),
+            sink,
+        )
+    }
+
+    fn alice() -> AuthenticatedUser {
+        AuthenticatedUser::new("alice@example.com", "123")
+    }
+
+    #[tokio::test]
+    async fn test_records_http_caller_and_redacts_arguments() {
+        let (registry, sink) = audited();
+        let (mut parts, ()) = http::Request::new(()).into_parts();
+        parts.extensions.insert(alice());
+        let ctx = ToolContext::new().with_extension(parts);
+
+        registry
+            .call_with_context(
+                "search",
+                json!({ "query": "modes", "api_key": "k-123" }),
+                ctx,
+            )
+            .unwrap()
+            .await
+            .unwrap();
+
+        let records = sink.records.lock().unwrap();
+        assert_eq!(records.len(), 1);
+        let record = &records[0];
+        assert_eq!(record.user.as_deref(), Some("alice@example.com"));
+        assert_eq!(record.subject.as_deref(), Some("123"));
+        assert_eq!(record.tool, "search");
+        assert_eq!(record.arguments["query"], "modes");
+        assert_eq!(record.arguments["api_key"], fabryk_mcp_core::REDACTED);
+        assert!(record.result_bytes > 0);
+        assert!(!record.is_error());
+    }
+
+    #[tokio::test]
+    async fn test_records_errors() {
+        let (registry, sink) = audited();
+        let ctx = ToolContext::new().with_extension(alice());
+
+        let result = registry
+            .call_with_context("search", json!({ "query": "fail" }), ctx)
+            .unwrap()
+            .await;
+        assert!(result.is_err());
+        registry.call("search", json!({})).unwrap().await.unwrap();
+
+        let records = sink.records.lock().unwrap();
+        assert_eq!(records[0].error.as_deref(), Some("index offline"));
+        assert_eq!(records[0].result_bytes, 0);
+        assert_eq!(records[0].user.as_deref(), Some("alice@example.com"));
+        assert_eq!(records[1].error.as_deref(), Some("query required"));
+        assert_eq!(records[1].user_or_anonymous(), "anonymous");
+    }
+
+    #[tokio::test]
+    async fn test_custom_redactor_and_unknown_tool() {
+        let sink = Arc::new(MemorySink::default());
+        let registry = AuditedRegistry::new(TestTools)
+            .with_sink(sink.clone())
+            .with_redactor(Redactor::new().with_path("query"));
+
+        registry
+            .call("search", json!({ "query": "private" }))
+            .unwrap()
+            .await
+            .unwrap();
+        assert!(registry.call("missing", json!({})).is_none());
+
+        let records = sink.records.lock().unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].arguments["query"], fabryk_mcp_core::REDACTED);
+        assert_eq!(registry.tool_count(), 1);
+    }
+}
@@ -0,0 +1,210 @@
+//! Per-user usage reports built from audit records.
+
+use std::collections::BTreeMap;
+use std::io::BufRead;
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::error::AuditError;
+use crate::record::AuditRecord;
+
+/// Read the records in a JSON Lines audit log.
+///
+/// Blank lines are skipped. Lines that don't parse (e.g. a record cut off
+/// by a crash) are skipped with a warning rather than failing the read.
+pub fn read_jsonl(path: impl AsRef<Path>) -> Result<Vec<AuditRecord>, AuditError> {
+    let path = path.as_ref();
+    let reader = std::io::BufReader::new(std::fs::File::open(path)?);
+
+    let mut records = Vec::new();
+    for (index, line) in reader.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        match serde_json::from_str(&line) {
+            Ok(record) => records.push(record),
+            Err(e) => log::warn!(
+                "Skipping malformed audit record at {}:{}: {e}",
+                path.display(),
+                index + 1
+            ),
+        }
+    }
+    Ok(records)
+}
+
+/// Which records a report covers.
+#[derive(Clone, Debug, Default)]
+pub struct UsageFilter {
+    /// Only this caller (email, or `anonymous`).
+    pub user: Option<String>,
+    /// Only calls at or after this time.
+    pub since: Option<DateTime<Utc>>,
+    /// Only calls before this time.
+    pub until: Option<DateTime<Utc>>,
+}
+
+impl UsageFilter {
+    /// Whether a record passes the filter.
+    pub fn matches(&self, record: &AuditRecord) -> bool {
+        self.user
+            .as_deref()
+            .is_none_or(|user| record.user_or_anonymous() == user)
+            && self.since.is_none_or(|since| record.timestamp >= since)
+            && self.until.is_none_or(|until| record.timestamp < until)
+    }
+}
+
+/// Usage by one caller.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct UserUsage {
+    /// Caller email, or `anonymous`.
+    pub user: String,
+    /// Number of calls.
+    pub calls: u64,
+    /// Number of failed calls.
+    pub errors: u64,
+    /// Sum of call latencies.
+    pub total_latency_ms: u64,
+    /// Sum of result sizes.
+    pub result_bytes: u64,
+    /// Time of the first call.
+    pub first_call: DateTime<Utc>,
+    /// Time of the last call.
+    pub last_call: DateTime<Utc>,
+    /// Calls per tool.
+    pub tools: BTreeMap<String, u64>,
+}
+
+impl UserUsage {
+    /// Mean call latency.
+    pub fn mean_latency_ms(&self) -> u64 {
+        self.total_latency_ms.checked_div(self.calls).unwrap_or(0)
+    }
+}
+
+/// Usage per caller, busiest first.
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct UsageReport {
+    /// One entry per caller.
+    pub users: Vec<UserUsage>,
+}
+
+impl UsageReport {
+    /// Aggregate the records that pass `filter`.
+    pub fn from_records<'a>(
+        records: impl IntoIterator<Item = &'a AuditRecord>,
+        filter: &UsageFilter,
+    ) -> Self {
+        let mut users: BTreeMap<&str, UserUsage> = BTreeMap::new();
+        for record in records.into_iter().filter(|r| filter.matches(r)) {
+            let user = record.user_or_anonymous();
+            let usage = users.entry(user).or_insert_with(|| UserUsage {
+                user: user.to_string(),
+                calls: 0,
+                errors: 0,
+                total_latency_ms: 0,
+                result_bytes: 0,
+                first_call: record.timestamp,
+                last_call: record.timestamp,
+                tools: BTreeMap::new(),
+            });
+            usage.calls += 1;
+            usage.errors += u64::from(record.is_error());
+            usage.total_latency_ms += record.latency_ms;
+            usage.result_bytes += record.result_bytes as u64;
+            usage.first_call = usage.first_call.min(record.timestamp);
+            usage.last_call = usage.last_call.max(record.timestamp);
+            *usage.tools.entry(record.tool.clone()).or_default() += 1;
+        }
+
+        let mut users: Vec<UserUsage> = users.into_values().collect();
+        users.sort_by(|a, b| b.calls.cmp(&a.calls).then_with(|| a.user.cmp(&b.user)));
+        Self { users }
+    }
+
+    /// Total calls across all callers.
+    pub fn total_calls(&self) -> u64 {
+        self.users.iter().map(|u| u.calls).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use serde_json::json;
+
+    fn record(user: Option<&str>, tool: &str, hour: u32, error: bool) -> AuditRecord {
+        AuditRecord {
+            timestamp: Utc.with_ymd_and_hms(2026, 10, 1, hour, 0, 0).unwrap(),
+            user: user.map(str::to_string),
+            subject: None,
+            tool: tool.to_string(),
+            arguments: json!({}),
+            latency_ms: 10 * u64::from(hour),
+            result_bytes: 100,
+            error: error.then(|| "failed".to_string()),
+        }
+    }
+
+    fn records() -> Vec<AuditRecord> {
+        vec![
+            record(Some("alice@example.com"), "search", 1, false),
+            record(Some("alice@example.com"), "search", 2, true),
+            record(Some("alice@example.com"), "graph_validate", 3, false),
+            record(Some("bob@example.com"), "search", 4, false),
+            record(None, "health", 5, false),
+        ]
+    }
+
+    #[test]
+    fn test_report_aggregates_per_user() {
+        let report = UsageReport::from_records(&records(), &UsageFilter::default());
+        assert_eq!(report.total_calls(), 5);
+        assert_eq!(report.users.len(), 3);
+
+        let alice = &report.users[0];
+        assert_eq!(alice.user, "alice@example.com");
+        assert_eq!(alice.calls, 3);
+        assert_eq!(alice.errors, 1);
+        assert_eq!(alice.mean_latency_ms(), 20);
+        assert_eq!(alice.result_bytes, 300);
+        assert_eq!(alice.tools["search"], 2);
+        assert_eq!(alice.tools["graph_validate"], 1);
+        assert_eq!(alice.first_call.to_rfc3339(), "2026-10-01T01:00:00+00:00");
+        assert_eq!(alice.last_call.to_rfc3339(), "2026-10-01T03:00:00+00:00");
+
+        // Ties are ordered by name.
+        assert_eq!(report.users[1].user, "anonymous");
+        assert_eq!(report.users[2].user, "bob@example.com");
+    }
+
+    #[test]
+    fn test_report_filters() {
+        let filter = UsageFilter {
+            user: Some("alice@example.com".to_string()),
+            since: Some(Utc.with_ymd_and_hms(2026, 10, 1, 2, 0, 0).unwrap()),
+            until: Some(Utc.with_ymd_and_hms(2026, 10, 1, 3, 0, 0).unwrap()),
+        };
+        let report = UsageReport::from_records(&records(), &filter);
+        assert_eq!(report.users.len(), 1);
+        assert_eq!(report.users[0].calls, 1);
+        assert_eq!(report.users[0].errors, 1);
+    }
+
+    #[test]
+    fn test_read_jsonl_skips_bad_lines() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("audit.jsonl");
+        let good = serde_json::to_string(&records()[0]).unwrap();
+        std::fs::write(&path, format!("{good}\n\n{{\"truncated\n{good}\n")).unwrap();
+
+        let records = read_jsonl(&path).unwrap();
+        assert_eq!(records.len(), 2);
+        assert!(read_jsonl(dir.path().join("missing.jsonl")).is_err());
+    }
+}
@@ -0,0 +1,260 @@
+//! Destinations for audit records.
+//!
+//! - [`JsonlSink`] — one JSON object per line in a local file; what
+//!   `fabryk audit usage` reads
+//! - [`RedisStreamSink`] — entries in a Redis stream, shared by all
+//!   instances of a deployment
+//! - [`TracingSink`] — `tracing` events under the `fabryk::audit` target,
+//!   for platforms that already collect logs (e.g. Cloud Logging)
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use fabryk_redis::RedisOps;
+use tokio::fs::{File, OpenOptions};
+use tokio::io::AsyncWriteExt;
+use tokio::sync::Mutex;
+
+use crate::error::AuditError;
+use crate::record::AuditRecord;
+
+/// A destination for audit records.
+#[async_trait]
+pub trait AuditSink: Send + Sync + std::fmt::Debug {
+    /// Write one record.
+    async fn write(&self, record: &AuditRecord) -> Result<(), AuditError>;
+
+    /// Sink name for log messages.
+    fn name(&self) -> &'static str;
+}
+
+// ============================================================================
+// JSONL file
+// ============================================================================
+
+/// Appends records to a JSON Lines file.
+///
+/// The file and its parent directories are created on the first write.
+#[derive(Debug)]
+pub struct JsonlSink {
+    path: PathBuf,
+    file: Mutex<Option<File>>,
+}
+
+impl JsonlSink {
+    /// Create a sink writing to `path`.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            file: Mutex::new(None),
+        }
+    }
+
+    /// The log file path.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+#[async_trait]
+impl AuditSink for JsonlSink {
+    async fn write(&self, record: &AuditRecord) -> Result<(), AuditError> {
+        let mut line = serde_json::to_vec(record)?;
+        line.push(b'\n');
+
+        let mut file = self.file.lock().await;
+        if file.is_none() {
+            if let Some(parent) = self.path.parent() {
+                tokio::fs::create_dir_all(parent).await?;
+            }
+            let opened = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&self.path)
+                .await?;
+            *file = Some(opened);
+        }
+        if let Some(file) = file.as_mut() {
+            file.write_all(&line).await?;
+            file.flush().await?;
+        }
+        Ok(())
+    }
+
+    fn name(&self) -> &'static str {
+        "jsonl"
+    }
+}
+
+// ============================================================================
+// Redis stream
+// ============================================================================
+
+/// Adds records to a Redis stream.
+///
+/// Each entry has `user`, `tool` and `record` fields, the last holding
+/// the full record as JSON. The stream is capped at roughly `max_len`
+/// entries (100,000 by default).
+#[derive(Debug, Clone)]
+pub struct RedisStreamSink {
+    redis: Arc<dyn RedisOps>,
+    stream: String,
+    max_len: Option<u64>,
+}
+
+impl RedisStreamSink {
+    /// Create a sink writing to the `fabryk:audit` stream.
+    pub fn new(redis: Arc<dyn RedisOps>) -> Self {
+        Self {
+            redis,
+            stream: "fabryk:audit".to_string(),
+            max_len: Some(100_000),
+        }
+    }
+
+    /// Use a different stream key.
+    pub fn with_stream(mut self, stream: impl Into<String>) -> Self {
+        self.stream = stream.into();
+        self
+    }
+
+    /// Cap the stream at roughly `max_len` entries, or never trim with `None`.
+    pub fn with_max_len(mut self, max_len: Option<u64>) -> Self {
+        self.max_len = max_len;
+        self
+    }
+}
+
+#[async_trait]
+impl AuditSink for RedisStreamSink {
+    async fn write(&self, record: &AuditRecord) -> Result<(), AuditError> {
+        let json = serde_json::to_string(record)?;
+        let fields = [
+            ("user", record.user_or_anonymous()),
+            ("tool", record.tool.as_str()),
+            ("record", json.as_str()),
+        ];
+        self.redis.xadd(&self.stream, &fields, self.max_len).await?;
+        Ok(())
+    }
+
+    fn name(&self) -> &'static str {
+        "redis"
+    }
+}
+
+// ============================================================================
+// Tracing
+// ============================================================================
+
+/// Emits each record as a `tracing` event with target `fabryk::audit`.
+///
+/// Successful calls are logged at `INFO`, failed ones at `WARN`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TracingSink;
+
+impl TracingSink {
+    /// Create a tracing sink.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl AuditSink for TracingSink {
+    async fn write(&self, record: &AuditRecord) -> Result<(), AuditError> {
+        let arguments = serde_json::to_string(&record.arguments)?;
+        match &record.error {
+            None => tracing::info!(
+                target: "fabryk::audit",
+                user = record.user_or_anonymous(),
+                tool = %record.tool,
+                arguments = %arguments,
+                latency_ms = record.latency_ms,
+                result_bytes = record.result_bytes,
+                "tool call"
+            ),
+            Some(error) => tracing::warn!(
+                target: "fabryk::audit",
+                user = record.user_or_anonymous(),
+                tool = %record.tool,
+                arguments = %arguments,
+                latency_ms = record.latency_ms,
+                error = %error,
+                "tool call failed"
+            ),
+        }
+        Ok(())
+    }
+
+    fn name(&self) -> &'static str {
+        "tracing"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use fabryk_redis::MockRedis;
+    use serde_json::json;
+
+    fn record(tool: &str) -> AuditRecord {
+        AuditRecord {
+            timestamp: Utc::now(),
+            user: Some("alice@example.com".to_string()),
+            subject: Some("123".to_string()),
+            tool: tool.to_string(),
+            arguments: json!({ "query": "harmony" }),
+            latency_ms: 12,
+            result_bytes: 340,
+            error: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_jsonl_sink_appends_lines() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("logs").join("audit.jsonl");
+        let sink = JsonlSink::new(&path);
+
+        sink.write(&record("search")).await.unwrap();
+        sink.write(&record("graph_validate")).await.unwrap();
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        let records: Vec<AuditRecord> = contents
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[1].tool, "graph_validate");
+        assert_eq!(records[0].arguments["query"], "harmony");
+    }
+
+    #[tokio::test]
+    async fn test_redis_stream_sink() {
+        let redis = Arc::new(MockRedis::new());
+        let sink = RedisStreamSink::new(redis.clone())
+            .with_stream("test:audit")
+            .with_max_len(Some(1));
+
+        sink.write(&record("search")).await.unwrap();
+        sink.write(&record("graph_validate")).await.unwrap();
+
+        let entries = redis.stream("test:audit");
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0][0], ("user".into(), "alice@example.com".into()));
+        assert_eq!(entries[0][1], ("tool".into(), "graph_validate".into()));
+        let stored: AuditRecord = serde_json::from_str(&entries[0][2].1).unwrap();
+        assert_eq!(stored.latency_ms, 12);
+    }
+
+    #[tokio::test]
+    async fn test_tracing_sink_never_fails() {
+        let mut failed = record("search");
+        failed.error = Some("boom".to_string());
+        assert!(TracingSink::new().write(&record("search")).await.is_ok());
+        assert!(TracingSink::new().write(&failed).await.is_ok());
+    }
+}
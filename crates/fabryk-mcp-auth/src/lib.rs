@@ -10,6 +10,19 @@
 //!
 //! Both are configurable: pass the resource URL and authorization server URL
 //! when creating the routes.
+//!
+//! With the `scopes` feature, [`scopes`] limits which tools a scoped
+//! credential may call.
+//!
+//! With the `server` feature, [`server`] provides a built-in authorization
+//! server — dynamic client registration, PKCE authorization codes and
+//! short-lived tokens — that delegates login to an upstream identity
+//! provider.
+
+#[cfg(feature = "scopes")]
+pub mod scopes;
+#[cfg(feature = "server")]
+pub mod server;
 
 use axum::Json;
 use serde_json::{Value, json};
@@ -0,0 +1,231 @@
+//! Tool access by credential scope (`scopes` feature).
+//!
+//! API keys and tokens from the built-in authorization server carry scopes
+//! ([`AuthenticatedUser::scopes`]). A [`ScopePolicy`] says which tools each
+//! scope grants, and [`ScopedRegistry`] refuses calls to tools the caller's
+//! scopes don't grant.
+//!
+//! Credentials without scopes — Google logins, keys minted without any,
+//! and unauthenticated callers (stdio, or auth disabled) — are not
+//! limited.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use fabryk_mcp_auth::scopes::{ScopePolicy, ScopedRegistry};
+//!
+//! let policy = ScopePolicy::new()
+//!     .grant("mcp", ["*"])
+//!     .grant("mcp:read", ["search", "graph_*"]);
+//! let registry = ScopedRegistry::new(composite, policy);
+//! FabrykMcpServer::new(registry).into_http_service();
+//! ```
+
+use std::collections::BTreeMap;
+
+use fabryk_auth::AuthenticatedUser;
+use fabryk_mcp_core::model::{ErrorData, Tool};
+use fabryk_mcp_core::{ToolContext, ToolRegistry, ToolResult};
+use serde_json::{Value, json};
+
+/// Which tools each scope grants.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ScopePolicy {
+    grants: BTreeMap<String, Vec<String>>,
+}
+
+impl ScopePolicy {
+    /// A policy granting nothing: scoped credentials can call no tools.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Let credentials with `scope` call `tools`. A trailing `*` matches a
+    /// prefix, e.g. `graph_*`; `*` alone matches every tool.
+    pub fn grant(
+        mut self,
+        scope: impl Into<String>,
+        tools: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        self.grants
+            .entry(scope.into())
+            .or_default()
+            .extend(tools.into_iter().map(Into::into));
+        self
+    }
+
+    /// Whether `scope` has been granted any tools.
+    pub fn has_scope(&self, scope: &str) -> bool {
+        self.grants.contains_key(scope)
+    }
+
+    /// Whether `user` may call `tool`: the user has no scopes, or one of
+    /// them grants the tool.
+    pub fn allows(&self, user: &AuthenticatedUser, tool: &str) -> bool {
+        user.scopes.is_empty()
+            || self.grants.iter().any(|(scope, patterns)| {
+                user.has_scope(scope) && patterns.iter().any(|p| matches(p, tool))
+            })
+    }
+}
+
+fn matches(pattern: &str, tool: &str) -> bool {
+    match pattern.strip_suffix('*') {
+        Some(prefix) => tool.starts_with(prefix),
+        None => pattern == tool,
+    }
+}
+
+/// A registry wrapper that enforces a [`ScopePolicy`].
+///
+/// Follows the same wrapper pattern as
+/// [`ValidatedRegistry`](fabryk_mcp_core::ValidatedRegistry):
+/// - `tools()` returns the inner registry's tools unchanged.
+/// - `call()` returns an `invalid_request` error instead of dispatching
+///   when the caller's scopes don't grant the tool.
+///
+/// The caller is the [`AuthenticatedUser`] that `AuthLayer` stores on the
+/// HTTP request, reached through [`ToolContext::extensions`].
+pub struct ScopedRegistry {
+    inner: Box<dyn ToolRegistry>,
+    policy: ScopePolicy,
+}
+
+impl ScopedRegistry {
+    /// Wrap a registry with scope checks.
+    pub fn new<R: ToolRegistry + 'static>(registry: R, policy: ScopePolicy) -> Self {
+        Self {
+            inner: Box::new(registry),
+            policy,
+        }
+    }
+}
+
+impl ToolRegistry for ScopedRegistry {
+    fn tools(&self) -> Vec<Tool> {
+        self.inner.tools()
+    }
+
+    fn call(&self, name: &str, args: Value) -> Option<ToolResult> {
+        self.call_with_context(name, args, ToolContext::new())
+    }
+
+    fn call_with_context(&self, name: &str, args: Value, ctx: ToolContext) -> Option<ToolResult> {
+        if let Some(user) = caller(&ctx)
+            && !self.policy.allows(user, name)
+        {
+            if !self.inner.has_tool(name) {
+                return None;
+            }
+            log::info!(
+                "Denied {} calling '{name}' with scopes [{}]",
+                user.email,
+                user.scopes.join(", ")
+            );
+            let error = ErrorData::invalid_request(
+                format!("The credential's scopes do not allow calling tool '{name}'"),
+                Some(json!({ "tool": name, "scopes": user.scopes })),
+            );
+            return Some(Box::pin(async move { Err(error) }));
+        }
+        self.inner.call_with_context(name, args, ctx)
+    }
+}
+
+/// The authenticated caller, from the HTTP request parts or set directly
+/// on the context.
+fn caller(ctx: &ToolContext) -> Option<&AuthenticatedUser> {
+    let extensions = ctx.extensions();
+    extensions
+        .get::<http::request::Parts>()
+        .and_then(|parts| parts.extensions.get::<AuthenticatedUser>())
+        .or_else(|| extensions.get::<AuthenticatedUser>())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fabryk_mcp_core::model::{CallToolResult, Content, ErrorCode};
+
+    struct TestTools;
+
+    impl ToolRegistry for TestTools {
+        fn tools(&self) -> Vec<Tool> {
+            ["search", "graph_related", "debug_config"]
+                .into_iter()
+                .map(|name| Tool::new(name, name, fabryk_mcp_core::empty_input_schema()))
+                .collect()
+        }
+
+        fn call(&self, name: &str, _args: Value) -> Option<ToolResult> {
+            self.has_tool(name).then(|| -> ToolResult {
+                Box::pin(async { Ok(CallToolResult::success(vec![Content::text("ok")])) })
+            })
+        }
+    }
+
+    fn policy() -> ScopePolicy {
+        ScopePolicy::new()
+            .grant("mcp", ["*"])
+            .grant("mcp:read", ["search", "graph_*"])
+    }
+
+    fn context(user: AuthenticatedUser) -> ToolContext {
+        let (mut parts, _) = http::Request::new(()).into_parts();
+        parts.extensions.insert(user);
+        ToolContext::new().with_extension(parts)
+    }
+
+    #[test]
+    fn test_policy_allows() {
+        let policy = policy();
+        let user = |scopes: &[&str]| {
+            AuthenticatedUser::new("a@example.com", "a").with_scopes(scopes.to_vec())
+        };
+
+        assert!(policy.allows(&user(&["mcp"]), "debug_config"));
+        assert!(policy.allows(&user(&["mcp:read"]), "graph_related"));
+        assert!(!policy.allows(&user(&["mcp:read"]), "debug_config"));
+        assert!(!policy.allows(&user(&["unknown"]), "search"));
+        // Unscoped credentials are not limited.
+        assert!(policy.allows(&user(&[]), "debug_config"));
+        assert!(policy.has_scope("mcp:read"));
+        assert!(!policy.has_scope("unknown"));
+    }
+
+    #[tokio::test]
+    async fn test_scoped_registry_denies_ungranted_tools() {
+        let registry = ScopedRegistry::new(TestTools, policy());
+        let reader =
+            AuthenticatedUser::new("key@example.com", "apikey:1").with_scopes(["mcp:read"]);
+
+        let ok = registry
+            .call_with_context("search", Value::Null, context(reader.clone()))
+            .unwrap()
+            .await;
+        assert!(ok.is_ok());
+
+        let err = registry
+            .call_with_context("debug_config", Value::Null, context(reader.clone()))
+            .unwrap()
+            .await
+            .unwrap_err();
+        assert_eq!(err.code, ErrorCode::INVALID_REQUEST);
+        assert!(err.message.contains("debug_config"));
+        assert_eq!(err.data.unwrap()["scopes"][0], "mcp:read");
+
+        // Unknown tools stay unknown; calls without a user are not limited.
+        assert!(
+            registry
+                .call_with_context("missing", Value::Null, context(reader))
+                .is_none()
+        );
+        assert!(
+            registry
+                .call("debug_config", Value::Null)
+                .unwrap()
+                .await
+                .is_ok()
+        );
+    }
+}
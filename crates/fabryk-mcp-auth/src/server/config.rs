@@ -0,0 +1,179 @@
+//! Authorization server configuration.
+
+use std::time::Duration;
+
+/// Google's authorization endpoint.
+const GOOGLE_AUTHORIZATION_ENDPOINT: &str = "https://accounts.google.com/o/oauth2/v2/auth";
+
+/// Google's token endpoint.
+const GOOGLE_TOKEN_ENDPOINT: &str = "https://oauth2.googleapis.com/token";
+
+/// The identity provider users log in with.
+///
+/// The authorization server is registered with the provider as a
+/// confidential client whose redirect URI is `<issuer>/callback`.
+#[derive(Clone, Debug)]
+pub struct UpstreamConfig {
+    /// The provider's authorization endpoint.
+    pub authorization_endpoint: String,
+    /// The provider's token endpoint.
+    pub token_endpoint: String,
+    /// Client ID registered with the provider.
+    pub client_id: String,
+    /// Client secret registered with the provider.
+    pub client_secret: String,
+    /// Scopes requested from the provider.
+    pub scopes: Vec<String>,
+    /// Allowed email domain (e.g., "example.com"). Empty means any domain.
+    pub domain: String,
+}
+
+impl UpstreamConfig {
+    /// Log in with the provider at the given endpoints, requesting
+    /// `openid email profile`.
+    pub fn new(
+        authorization_endpoint: impl Into<String>,
+        token_endpoint: impl Into<String>,
+        client_id: impl Into<String>,
+        client_secret: impl Into<String>,
+    ) -> Self {
+        Self {
+            authorization_endpoint: authorization_endpoint.into(),
+            token_endpoint: token_endpoint.into(),
+            client_id: client_id.into(),
+            client_secret: client_secret.into(),
+            scopes: vec!["openid".into(), "email".into(), "profile".into()],
+            domain: String::new(),
+        }
+    }
+
+    /// Log in with Google.
+    pub fn google(client_id: impl Into<String>, client_secret: impl Into<String>) -> Self {
+        Self::new(
+            GOOGLE_AUTHORIZATION_ENDPOINT,
+            GOOGLE_TOKEN_ENDPOINT,
+            client_id,
+            client_secret,
+        )
+    }
+
+    /// Request these scopes from the provider instead.
+    pub fn with_scopes(mut self, scopes: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.scopes = scopes.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Only let users from this email domain log in.
+    pub fn with_domain(mut self, domain: impl Into<String>) -> Self {
+        self.domain = domain.into();
+        self
+    }
+}
+
+/// Configuration for the built-in authorization server.
+#[derive(Clone, Debug)]
+pub struct AuthServerConfig {
+    /// Public base URL of the server (e.g., `https://kb.example.com`). The
+    /// OAuth endpoints live under it, and it is the `iss` of issued tokens.
+    pub issuer: String,
+    /// Audience of issued tokens: the protected resource (default: the
+    /// issuer).
+    pub resource: String,
+    /// The identity provider users log in with.
+    pub upstream: UpstreamConfig,
+    /// Scopes clients may be granted. Clients that ask for none get all.
+    pub scopes_supported: Vec<String>,
+    /// Lifetime of access tokens.
+    pub access_token_ttl: Duration,
+    /// Lifetime of refresh tokens. Each refresh issues a new one.
+    pub refresh_token_ttl: Duration,
+    /// Lifetime of authorization codes.
+    pub code_ttl: Duration,
+    /// How long a user has to log in with the provider.
+    pub login_ttl: Duration,
+    /// Most clients registered at once. Registration fails while full.
+    pub max_clients: usize,
+    /// How long a registered client may go unused before it is forgotten.
+    pub client_idle_ttl: Duration,
+}
+
+impl AuthServerConfig {
+    /// Serve at `issuer`, logging users in with `upstream`.
+    ///
+    /// Access tokens last 15 minutes, refresh tokens 7 days, authorization
+    /// codes 1 minute and logins 10 minutes. Up to 1000 clients may be
+    /// registered, each forgotten after 30 days unused.
+    pub fn new(issuer: impl Into<String>, upstream: UpstreamConfig) -> Self {
+        let issuer = issuer.into().trim_end_matches('/').to_string();
+        Self {
+            resource: issuer.clone(),
+            issuer,
+            upstream,
+            scopes_supported: vec!["mcp".into()],
+            access_token_ttl: Duration::from_secs(15 * 60),
+            refresh_token_ttl: Duration::from_secs(7 * 24 * 60 * 60),
+            code_ttl: Duration::from_secs(60),
+            login_ttl: Duration::from_secs(10 * 60),
+            max_clients: 1000,
+            client_idle_ttl: Duration::from_secs(30 * 24 * 60 * 60),
+        }
+    }
+
+    /// Set the audience of issued tokens.
+    pub fn with_resource(mut self, resource: impl Into<String>) -> Self {
+        self.resource = resource.into();
+        self
+    }
+
+    /// Set the scopes clients may be granted.
+    pub fn with_scopes(mut self, scopes: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.scopes_supported = scopes.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Set the access and refresh token lifetimes.
+    pub fn with_token_ttls(mut self, access: Duration, refresh: Duration) -> Self {
+        self.access_token_ttl = access;
+        self.refresh_token_ttl = refresh;
+        self
+    }
+
+    /// Set how many clients may be registered and how long each may go
+    /// unused.
+    pub fn with_client_limits(mut self, max_clients: usize, idle_ttl: Duration) -> Self {
+        self.max_clients = max_clients;
+        self.client_idle_ttl = idle_ttl;
+        self
+    }
+
+    /// The URL of an endpoint under the issuer, e.g. `endpoint("token")`.
+    pub fn endpoint(&self, path: &str) -> String {
+        format!("{}/{path}", self.issuer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config_defaults() {
+        let config = AuthServerConfig::new(
+            "https://kb.example.com/",
+            UpstreamConfig::google("client", "secret").with_domain("example.com"),
+        );
+        assert_eq!(config.issuer, "https://kb.example.com");
+        assert_eq!(config.resource, "https://kb.example.com");
+        assert_eq!(config.endpoint("token"), "https://kb.example.com/token");
+        assert_eq!(config.scopes_supported, ["mcp"]);
+        assert_eq!(config.max_clients, 1000);
+        assert_eq!(config.upstream.scopes, ["openid", "email", "profile"]);
+        assert_eq!(config.upstream.domain, "example.com");
+        assert!(
+            config
+                .upstream
+                .authorization_endpoint
+                .starts_with("https://accounts.google.com")
+        );
+    }
+}
@@ -0,0 +1,568 @@
+//! Authorization server endpoints.
+
+use std::sync::Arc;
+
+use axum::Json;
+use axum::extract::{Form, Query, State};
+use axum::http::{StatusCode, header};
+use axum::response::{IntoResponse, Redirect, Response};
+use reqwest::Url;
+use serde::Deserialize;
+use serde_json::{Value, json};
+
+use super::AuthorizationServer;
+use super::store::{Grant, PendingLogin, Poisoned, RegisteredClient};
+
+type ServerState = State<Arc<AuthorizationServer>>;
+
+/// Grant types clients may register for.
+const GRANT_TYPES: [&str; 2] = ["authorization_code", "refresh_token"];
+
+// ============================================================================
+// Errors
+// ============================================================================
+
+/// An OAuth error response (RFC 6749 §5.2).
+#[derive(Debug)]
+pub(crate) struct OAuthError {
+    status: StatusCode,
+    error: &'static str,
+    description: String,
+}
+
+impl OAuthError {
+    fn new(error: &'static str, description: impl Into<String>) -> Self {
+        Self {
+            status: StatusCode::BAD_REQUEST,
+            error,
+            description: description.into(),
+        }
+    }
+
+    fn invalid_request(description: impl Into<String>) -> Self {
+        Self::new("invalid_request", description)
+    }
+
+    fn invalid_grant(description: impl Into<String>) -> Self {
+        Self::new("invalid_grant", description)
+    }
+
+    fn temporarily_unavailable(description: impl Into<String>) -> Self {
+        Self {
+            status: StatusCode::SERVICE_UNAVAILABLE,
+            ..Self::new("temporarily_unavailable", description)
+        }
+    }
+
+    fn server_error(description: impl Into<String>) -> Self {
+        Self {
+            status: StatusCode::INTERNAL_SERVER_ERROR,
+            ..Self::new("server_error", description)
+        }
+    }
+}
+
+impl From<Poisoned> for OAuthError {
+    fn from(_: Poisoned) -> Self {
+        Self::server_error("authorization server state is unavailable")
+    }
+}
+
+impl IntoResponse for OAuthError {
+    fn into_response(self) -> Response {
+        let body = Json(json!({
+            "error": self.error,
+            "error_description": self.description,
+        }));
+        (self.status, body).into_response()
+    }
+}
+
+// ============================================================================
+// Discovery
+// ============================================================================
+
+/// Authorization Server Metadata (RFC 8414) for this server.
+pub(crate) async fn authorization_server_metadata(State(server): ServerState) -> Json<Value> {
+    let config = server.config();
+    Json(json!({
+        "issuer": config.issuer,
+        "authorization_endpoint": config.endpoint("authorize"),
+        "token_endpoint": config.endpoint("token"),
+        "registration_endpoint": config.endpoint("register"),
+        "response_types_supported": ["code"],
+        "grant_types_supported": GRANT_TYPES,
+        "token_endpoint_auth_methods_supported": ["none"],
+        "code_challenge_methods_supported": ["S256"],
+        "scopes_supported": config.scopes_supported,
+    }))
+}
+
+/// Protected Resource Metadata (RFC 9728) naming this server.
+pub(crate) async fn protected_resource_metadata(State(server): ServerState) -> Json<Value> {
+    let config = server.config();
+    Json(json!({
+        "resource": config.resource,
+        "authorization_servers": [config.issuer],
+        "scopes_supported": config.scopes_supported,
+        "bearer_methods_supported": ["header"],
+    }))
+}
+
+// ============================================================================
+// Registration
+// ============================================================================
+
+/// Client metadata sent to `/register` (RFC 7591 §2).
+#[derive(Debug, Deserialize)]
+pub(crate) struct RegistrationRequest {
+    #[serde(default)]
+    redirect_uris: Vec<String>,
+    client_name: Option<String>,
+    token_endpoint_auth_method: Option<String>,
+    #[serde(default)]
+    grant_types: Vec<String>,
+}
+
+/// Register a public client.
+pub(crate) async fn register(
+    State(server): ServerState,
+    Json(request): Json<RegistrationRequest>,
+) -> Result<(StatusCode, Json<Value>), OAuthError> {
+    if request.redirect_uris.is_empty() {
+        return Err(OAuthError::new(
+            "invalid_redirect_uri",
+            "at least one redirect_uri is required",
+        ));
+    }
+    if let Some(uri) = request
+        .redirect_uris
+        .iter()
+        .find(|u| !valid_redirect_uri(u))
+    {
+        return Err(OAuthError::new(
+            "invalid_redirect_uri",
+            format!("redirect_uri '{uri}' must use https, loopback http or an app scheme"),
+        ));
+    }
+    if let Some(method) = request
+        .token_endpoint_auth_method
+        .as_deref()
+        .filter(|m| *m != "none")
+    {
+        return Err(OAuthError::new(
+            "invalid_client_metadata",
+            format!("token_endpoint_auth_method '{method}' is not supported; use 'none'"),
+        ));
+    }
+    if let Some(grant) = request
+        .grant_types
+        .iter()
+        .find(|g| !GRANT_TYPES.contains(&g.as_str()))
+    {
+        return Err(OAuthError::new(
+            "invalid_client_metadata",
+            format!("grant type '{grant}' is not supported"),
+        ));
+    }
+
+    let client_id = super::random_token();
+    let client = RegisteredClient {
+        client_name: request.client_name,
+        redirect_uris: request.redirect_uris,
+    };
+    let body = json!({
+        "client_id": client_id,
+        "client_id_issued_at": jsonwebtoken::get_current_timestamp(),
+        "client_name": client.client_name,
+        "redirect_uris": client.redirect_uris,
+        "grant_types": GRANT_TYPES,
+        "response_types": ["code"],
+        "token_endpoint_auth_method": "none",
+    });
+    let config = server.config();
+    if !server.store.clients.register(
+        client_id.clone(),
+        client,
+        config.max_clients,
+        config.client_idle_ttl,
+    )? {
+        log::warn!(
+            "Rejected OAuth client registration: {} clients registered",
+            config.max_clients
+        );
+        return Err(OAuthError::temporarily_unavailable(
+            "too many registered clients; try again later",
+        ));
+    }
+    log::info!(
+        "Registered OAuth client {client_id} ({})",
+        body["client_name"].as_str().unwrap_or("unnamed")
+    );
+    Ok((StatusCode::CREATED, Json(body)))
+}
+
+/// Redirect URIs must be HTTPS, HTTP on a loopback address, or a native
+/// app's private-use scheme (RFC 8252), without a fragment.
+fn valid_redirect_uri(uri: &str) -> bool {
+    let Ok(url) = Url::parse(uri) else {
+        return false;
+    };
+    if url.fragment().is_some() {
+        return false;
+    }
+    match url.scheme() {
+        "https" => true,
+        "http" => matches!(url.host_str(), Some("localhost" | "127.0.0.1" | "[::1]")),
+        "javascript" | "data" | "file" => false,
+        _ => true,
+    }
+}
+
+// ============================================================================
+// Authorization
+// ============================================================================
+
+/// Query parameters of `/authorize`.
+#[derive(Debug, Deserialize)]
+pub(crate) struct AuthorizeParams {
+    response_type: Option<String>,
+    client_id: Option<String>,
+    redirect_uri: Option<String>,
+    code_challenge: Option<String>,
+    code_challenge_method: Option<String>,
+    state: Option<String>,
+    scope: Option<String>,
+}
+
+/// Start an authorization: check the request and send the user to the
+/// upstream provider to log in.
+pub(crate) async fn authorize(
+    State(server): ServerState,
+    Query(params): Query<AuthorizeParams>,
+) -> Result<Redirect, OAuthError> {
+    // Until the client and redirect URI check out, errors are shown to the
+    // user rather than redirected.
+    let client_id = params
+        .client_id
+        .ok_or_else(|| OAuthError::invalid_request("client_id is required"))?;
+    let client = server
+        .store
+        .clients
+        .get(&client_id)?
+        .ok_or_else(|| OAuthError::new("invalid_client", "unknown client_id"))?;
+    let redirect_uri = match params.redirect_uri {
+        Some(uri) if client.redirect_uris.contains(&uri) => uri,
+        Some(_) => {
+            return Err(OAuthError::invalid_request(
+                "redirect_uri is not registered for this client",
+            ));
+        }
+        None if client.redirect_uris.len() == 1 => client.redirect_uris[0].clone(),
+        None => return Err(OAuthError::invalid_request("redirect_uri is required")),
+    };
+
+    let state = params.state;
+    let fail = |error: &str, description: &str| {
+        client_redirect(
+            &redirect_uri,
+            &[("error", error), ("error_description", description)],
+            state.as_deref(),
+        )
+    };
+    if params.response_type.as_deref() != Some("code") {
+        return fail("unsupported_response_type", "response_type must be 'code'");
+    }
+    let Some(code_challenge) = params.code_challenge else {
+        return fail("invalid_request", "PKCE code_challenge is required");
+    };
+    if params.code_challenge_method.as_deref() != Some("S256") {
+        return fail("invalid_request", "code_challenge_method must be 'S256'");
+    }
+
+    let upstream_state = super::random_token();
+    let upstream_verifier = super::random_token();
+    let upstream = &server.config().upstream;
+    let mut login_url = Url::parse(&upstream.authorization_endpoint)
+        .map_err(|e| OAuthError::server_error(format!("invalid upstream endpoint: {e}")))?;
+    login_url
+        .query_pairs_mut()
+        .append_pair("response_type", "code")
+        .append_pair("client_id", &upstream.client_id)
+        .append_pair("redirect_uri", &server.config().endpoint("callback"))
+        .append_pair("scope", &upstream.scopes.join(" "))
+        .append_pair("state", &upstream_state)
+        .append_pair("code_challenge", &super::pkce_challenge(&upstream_verifier))
+        .append_pair("code_challenge_method", "S256");
+
+    let login = PendingLogin {
+        client_id,
+        redirect_uri,
+        state,
+        code_challenge,
+        scopes: server.granted_scopes(params.scope.as_deref()),
+        upstream_verifier,
+    };
+    server
+        .store
+        .logins
+        .insert(upstream_state, login, server.config().login_ttl)?;
+    Ok(Redirect::to(login_url.as_str()))
+}
+
+/// Query parameters of the upstream provider's redirect to `/callback`.
+#[derive(Debug, Deserialize)]
+pub(crate) struct CallbackParams {
+    code: Option<String>,
+    state: Option<String>,
+    error: Option<String>,
+}
+
+/// Finish the upstream login and send the user back to the client with an
+/// authorization code.
+pub(crate) async fn callback(
+    State(server): ServerState,
+    Query(params): Query<CallbackParams>,
+) -> Result<Redirect, OAuthError> {
+    let login = match params.state.as_deref() {
+        Some(state) => server.store.logins.take(state)?,
+        None => None,
+    }
+    .ok_or_else(|| OAuthError::invalid_request("unknown or expired login"))?;
+    let back = |pairs: &[(&str, &str)]| {
+        client_redirect(&login.redirect_uri, pairs, login.state.as_deref())
+    };
+
+    let upstream_code = match (params.code, params.error) {
+        (Some(code), None) => code,
+        (_, error) => {
+            let error = error.unwrap_or_else(|| "no code returned".to_string());
+            log::info!(
+                "Upstream login for client {} failed: {error}",
+                login.client_id
+            );
+            return back(&[
+                ("error", "access_denied"),
+                (
+                    "error_description",
+                    "login with the identity provider failed",
+                ),
+            ]);
+        }
+    };
+
+    let user = match server
+        .upstream_login(&upstream_code, &login.upstream_verifier)
+        .await
+    {
+        Ok(user) => user,
+        Err(e) => {
+            log::warn!(
+                "Upstream login for client {} rejected: {e}",
+                login.client_id
+            );
+            return back(&[
+                ("error", "access_denied"),
+                (
+                    "error_description",
+                    "the identity provider login was rejected",
+                ),
+            ]);
+        }
+    };
+
+    log::info!("{} authorized OAuth client {}", user.email, login.client_id);
+    let code = super::random_token();
+    let grant = Grant {
+        client_id: login.client_id.clone(),
+        user,
+        scopes: login.scopes.clone(),
+        redirect_uri: Some(login.redirect_uri.clone()),
+        code_challenge: Some(login.code_challenge.clone()),
+    };
+    server
+        .store
+        .codes
+        .insert(code.clone(), grant, server.config().code_ttl)?;
+    back(&[("code", &code)])
+}
+
+/// A redirect to `redirect_uri` with `pairs` and the client's `state`
+/// appended.
+fn client_redirect(
+    redirect_uri: &str,
+    pairs: &[(&str, &str)],
+    state: Option<&str>,
+) -> Result<Redirect, OAuthError> {
+    // Registered redirect URIs were checked to parse when registered.
+    let mut url = Url::parse(redirect_uri)
+        .map_err(|e| OAuthError::server_error(format!("invalid registered redirect_uri: {e}")))?;
+    {
+        let mut query = url.query_pairs_mut();
+        query.extend_pairs(pairs);
+        if let Some(state) = state {
+            query.append_pair("state", state);
+        }
+    }
+    Ok(Redirect::to(url.as_str()))
+}
+
+// ============================================================================
+// Tokens
+// ============================================================================
+
+/// Form parameters of `/token`.
+#[derive(Debug, Deserialize)]
+pub(crate) struct TokenRequest {
+    grant_type: String,
+    code: Option<String>,
+    redirect_uri: Option<String>,
+    client_id: Option<String>,
+    code_verifier: Option<String>,
+    refresh_token: Option<String>,
+}
+
+/// Exchange an authorization code or refresh token for tokens.
+pub(crate) async fn token(
+    State(server): ServerState,
+    Form(request): Form<TokenRequest>,
+) -> Result<Response, OAuthError> {
+    let grant = match request.grant_type.as_str() {
+        "authorization_code" => code_grant(&server, &request)?,
+        "refresh_token" => {
+            let refresh_token = request
+                .refresh_token
+                .as_deref()
+                .ok_or_else(|| OAuthError::invalid_request("refresh_token is required"))?;
+            server
+                .store
+                .refresh_tokens
+                .take(refresh_token)?
+                .ok_or_else(|| OAuthError::invalid_grant("invalid or expired refresh token"))?
+        }
+        other => {
+            return Err(OAuthError::new(
+                "unsupported_grant_type",
+                format!("grant_type '{other}' is not supported"),
+            ));
+        }
+    };
+    if request.client_id.as_deref() != Some(grant.client_id.as_str()) {
+        return Err(OAuthError::invalid_grant(
+            "the grant was issued to another client",
+        ));
+    }
+    // Token requests count as use, so active clients are not forgotten.
+    server.store.clients.get(&grant.client_id)?;
+
+    let config = server.config();
+    let access_token = super::token::sign_access_token(
+        &server.signing_key,
+        &config.issuer,
+        &config.resource,
+        &grant.client_id,
+        &grant.user,
+        &grant.scopes,
+        config.access_token_ttl,
+    )
+    .map_err(|e| OAuthError::server_error(format!("failed to sign token: {e}")))?;
+    let refresh_token = super::random_token();
+    let body = json!({
+        "access_token": access_token,
+        "token_type": "Bearer",
+        "expires_in": config.access_token_ttl.as_secs(),
+        "refresh_token": refresh_token,
+        "scope": grant.scopes.join(" "),
+    });
+    server.store.refresh_tokens.insert(
+        refresh_token,
+        Grant {
+            redirect_uri: None,
+            code_challenge: None,
+            ..grant
+        },
+        config.refresh_token_ttl,
+    )?;
+
+    Ok((
+        [
+            (header::CACHE_CONTROL, "no-store"),
+            (header::PRAGMA, "no-cache"),
+        ],
+        Json(body),
+    )
+        .into_response())
+}
+
+/// Redeem an authorization code, checking the redirect URI and PKCE
+/// verifier.
+fn code_grant(server: &AuthorizationServer, request: &TokenRequest) -> Result<Grant, OAuthError> {
+    let code = request
+        .code
+        .as_deref()
+        .ok_or_else(|| OAuthError::invalid_request("code is required"))?;
+    let verifier = request
+        .code_verifier
+        .as_deref()
+        .ok_or_else(|| OAuthError::invalid_request("code_verifier is required"))?;
+    let grant = server
+        .store
+        .codes
+        .take(code)?
+        .ok_or_else(|| OAuthError::invalid_grant("invalid or expired authorization code"))?;
+
+    if request.redirect_uri.is_some() && request.redirect_uri != grant.redirect_uri {
+        return Err(OAuthError::invalid_grant("redirect_uri does not match"));
+    }
+    if grant.code_challenge.as_deref() != Some(super::pkce_challenge(verifier).as_str()) {
+        return Err(OAuthError::invalid_grant("PKCE verification failed"));
+    }
+    Ok(grant)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_valid_redirect_uri() {
+        assert!(valid_redirect_uri("https://app.example.com/callback"));
+        assert!(valid_redirect_uri("http://127.0.0.1:33418/callback"));
+        assert!(valid_redirect_uri("http://localhost/callback"));
+        assert!(valid_redirect_uri("http://[::1]:8080/"));
+        assert!(valid_redirect_uri(
+            "cursor://anysphere.cursor-mcp/oauth/callback"
+        ));
+
+        assert!(!valid_redirect_uri("http://app.example.com/callback"));
+        assert!(!valid_redirect_uri("https://app.example.com/callback#frag"));
+        assert!(!valid_redirect_uri("javascript:alert(1)"));
+        assert!(!valid_redirect_uri("not a url"));
+    }
+
+    #[test]
+    fn test_client_redirect() {
+        let redirect = client_redirect(
+            "http://127.0.0.1:9999/cb?keep=1",
+            &[("code", "abc")],
+            Some("xyz"),
+        )
+        .unwrap()
+        .into_response();
+        assert_eq!(
+            redirect.headers()[header::LOCATION],
+            "http://127.0.0.1:9999/cb?keep=1&code=abc&state=xyz"
+        );
+
+        let err = client_redirect("not a url", &[], None).unwrap_err();
+        assert_eq!(err.status, StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(err.error, "server_error");
+    }
+
+    #[test]
+    fn test_poisoned_store_is_server_error() {
+        let err = OAuthError::from(Poisoned);
+        assert_eq!(err.status, StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(err.error, "server_error");
+    }
+}
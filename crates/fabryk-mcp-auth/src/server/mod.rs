@@ -0,0 +1,680 @@
+//! Built-in OAuth authorization server for MCP clients (`server` feature).
+//!
+//! MCP clients that follow the MCP auth spec register themselves and log in
+//! with an authorization-code flow and PKCE. This server provides:
+//!
+//! - `POST /register` — dynamic client registration (RFC 7591) for public
+//!   clients
+//! - `GET /authorize` — authorization requests with PKCE (`S256`); the user
+//!   is sent to the upstream identity provider to log in
+//! - `GET /callback` — the provider's redirect back: its code is exchanged
+//!   and the user checked with the upstream [`TokenValidator`]
+//! - `POST /token` — the `authorization_code` and `refresh_token` grants
+//! - the RFC 8414 and RFC 9728 discovery documents, pointing at this server
+//!
+//! Issued access tokens are short-lived HS256 JWTs. [`IssuedTokenValidator`]
+//! validates them, so an [`AuthLayer`](fabryk_auth::AuthLayer) accepts them
+//! directly or in a [`ChainValidator`](fabryk_auth::ChainValidator).
+//!
+//! Clients, codes and refresh tokens are kept in memory. Give every instance
+//! the same signing key ([`AuthorizationServer::with_signing_key`], at least
+//! [`MIN_SIGNING_KEY_LEN`] bytes) for access tokens to survive restarts and
+//! work across instances.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use fabryk_mcp_auth::server::{AuthServerConfig, AuthorizationServer, UpstreamConfig};
+//!
+//! let upstream = UpstreamConfig::google(client_id, client_secret).with_domain("example.com");
+//! let server = Arc::new(AuthorizationServer::new(
+//!     AuthServerConfig::new("https://kb.example.com", upstream),
+//!     Arc::new(GoogleTokenValidator::new(jwks_url)),
+//! ));
+//!
+//! let app = axum::Router::new()
+//!     .nest_service("/mcp", mcp_service)
+//!     .layer(AuthLayer::new(Arc::new(server.validator()), auth_config))
+//!     .merge(fabryk_mcp_auth::server::routes(server));
+//! ```
+
+mod config;
+mod handlers;
+mod store;
+mod token;
+
+use std::sync::Arc;
+
+use axum::routing::{get, post};
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use fabryk_auth::{AuthConfig, AuthenticatedUser, TokenValidator};
+use rand::RngCore;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+
+pub use config::{AuthServerConfig, UpstreamConfig};
+pub use token::IssuedTokenValidator;
+
+use store::AuthStore;
+
+/// Shortest signing key accepted by [`AuthorizationServer::with_signing_key`].
+pub const MIN_SIGNING_KEY_LEN: usize = 32;
+
+/// A signing key shorter than [`MIN_SIGNING_KEY_LEN`] bytes.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WeakSigningKey {
+    /// Length of the rejected key in bytes.
+    pub len: usize,
+}
+
+impl std::fmt::Display for WeakSigningKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "signing key is {} bytes; at least {MIN_SIGNING_KEY_LEN} are required",
+            self.len
+        )
+    }
+}
+
+impl std::error::Error for WeakSigningKey {}
+
+/// The authorization server's configuration and state.
+pub struct AuthorizationServer {
+    config: AuthServerConfig,
+    upstream_validator: Arc<dyn TokenValidator>,
+    http_client: reqwest::Client,
+    signing_key: Vec<u8>,
+    store: AuthStore,
+}
+
+impl AuthorizationServer {
+    /// Create a server that checks upstream logins with
+    /// `upstream_validator`, signing tokens with a random key.
+    pub fn new(config: AuthServerConfig, upstream_validator: Arc<dyn TokenValidator>) -> Self {
+        let mut signing_key = vec![0; MIN_SIGNING_KEY_LEN];
+        rand::thread_rng().fill_bytes(&mut signing_key);
+        Self {
+            config,
+            upstream_validator,
+            http_client: reqwest::Client::new(),
+            signing_key,
+            store: AuthStore::new(),
+        }
+    }
+
+    /// Sign access tokens with `key` instead of a random one.
+    ///
+    /// Fails if `key` is shorter than [`MIN_SIGNING_KEY_LEN`] bytes.
+    pub fn with_signing_key(mut self, key: impl Into<Vec<u8>>) -> Result<Self, WeakSigningKey> {
+        let key = key.into();
+        if key.len() < MIN_SIGNING_KEY_LEN {
+            return Err(WeakSigningKey { len: key.len() });
+        }
+        self.signing_key = key;
+        Ok(self)
+    }
+
+    /// The server's configuration.
+    pub fn config(&self) -> &AuthServerConfig {
+        &self.config
+    }
+
+    /// A validator for the access tokens this server issues.
+    pub fn validator(&self) -> IssuedTokenValidator {
+        IssuedTokenValidator::new(
+            &self.config.issuer,
+            &self.config.resource,
+            &self.signing_key,
+        )
+    }
+
+    /// The requested scopes this server supports, or all of them if none
+    /// are requested.
+    fn granted_scopes(&self, requested: Option<&str>) -> Vec<String> {
+        let requested: Vec<&str> = requested.unwrap_or_default().split_whitespace().collect();
+        let granted: Vec<String> = self
+            .config
+            .scopes_supported
+            .iter()
+            .filter(|scope| requested.contains(&scope.as_str()))
+            .cloned()
+            .collect();
+        if granted.is_empty() {
+            self.config.scopes_supported.clone()
+        } else {
+            granted
+        }
+    }
+
+    /// Exchange an upstream authorization code and validate the resulting
+    /// token with the upstream validator.
+    async fn upstream_login(
+        &self,
+        code: &str,
+        verifier: &str,
+    ) -> Result<AuthenticatedUser, String> {
+        #[derive(Deserialize)]
+        struct UpstreamTokens {
+            access_token: String,
+            id_token: Option<String>,
+        }
+
+        let upstream = &self.config.upstream;
+        let callback = self.config.endpoint("callback");
+        let response = self
+            .http_client
+            .post(&upstream.token_endpoint)
+            .form(&[
+                ("grant_type", "authorization_code"),
+                ("code", code),
+                ("redirect_uri", &callback),
+                ("client_id", &upstream.client_id),
+                ("client_secret", &upstream.client_secret),
+                ("code_verifier", verifier),
+            ])
+            .send()
+            .await
+            .map_err(|e| format!("token request failed: {e}"))?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(format!("token endpoint returned {status}: {body}"));
+        }
+        let tokens: UpstreamTokens = response
+            .json()
+            .await
+            .map_err(|e| format!("invalid token response: {e}"))?;
+
+        let auth_config = AuthConfig {
+            enabled: true,
+            audience: upstream.client_id.clone(),
+            domain: upstream.domain.clone(),
+        };
+        let token = tokens.id_token.unwrap_or(tokens.access_token);
+        self.upstream_validator
+            .validate(&token, &auth_config)
+            .await
+            .map_err(|e| e.to_string())
+    }
+}
+
+impl std::fmt::Debug for AuthorizationServer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AuthorizationServer")
+            .field("issuer", &self.config.issuer)
+            .field("resource", &self.config.resource)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Create an axum `Router` with the authorization server's endpoints and
+/// discovery documents.
+///
+/// Mount it at the root of `config.issuer`: the endpoints are advertised as
+/// `<issuer>/register`, `<issuer>/authorize` and so on. It replaces
+/// [`discovery_routes`](crate::discovery_routes), which points clients at an
+/// external authorization server.
+pub fn routes(server: Arc<AuthorizationServer>) -> axum::Router {
+    axum::Router::new()
+        .route(
+            "/.well-known/oauth-authorization-server",
+            get(handlers::authorization_server_metadata),
+        )
+        .route(
+            "/.well-known/oauth-authorization-server/mcp",
+            get(handlers::authorization_server_metadata),
+        )
+        .route(
+            "/.well-known/oauth-protected-resource",
+            get(handlers::protected_resource_metadata),
+        )
+        .route(
+            "/.well-known/oauth-protected-resource/mcp",
+            get(handlers::protected_resource_metadata),
+        )
+        .route("/register", post(handlers::register))
+        .route("/authorize", get(handlers::authorize))
+        .route("/callback", get(handlers::callback))
+        .route("/token", post(handlers::token))
+        .with_state(server)
+}
+
+/// A random URL-safe token for client IDs, codes and states.
+fn random_token() -> String {
+    let mut bytes = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    URL_SAFE_NO_PAD.encode(bytes)
+}
+
+/// The PKCE `S256` challenge for `verifier` (RFC 7636 §4.2).
+fn pkce_challenge(verifier: &str) -> String {
+    URL_SAFE_NO_PAD.encode(Sha256::digest(verifier.as_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode, header};
+    use fabryk_auth::{AuthError, AuthLayer};
+    use reqwest::Url;
+    use serde_json::{Value, json};
+    use std::collections::HashMap;
+    use std::future::Future;
+    use std::pin::Pin;
+    use tower::ServiceExt;
+
+    const UPSTREAM_CODE: &str = "upstream-code";
+    const REDIRECT_URI: &str = "http://127.0.0.1:9999/callback";
+    const VERIFIER: &str = "dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gXFG2uQ-kA";
+
+    /// Accepts `id-token:<email>` tokens from the mock provider.
+    struct MockUpstreamValidator;
+
+    impl TokenValidator for MockUpstreamValidator {
+        fn validate(
+            &self,
+            token: &str,
+            config: &AuthConfig,
+        ) -> Pin<Box<dyn Future<Output = Result<AuthenticatedUser, AuthError>> + Send + '_>>
+        {
+            let result = match token.strip_prefix("id-token:") {
+                Some(_) if config.audience != "upstream-client" => Err(AuthError::InvalidAudience),
+                Some(email) if !email.ends_with(&format!("@{}", config.domain)) => {
+                    Err(AuthError::InvalidDomain {
+                        domain: email.rsplit('@').next().unwrap_or_default().to_string(),
+                        expected: config.domain.clone(),
+                    })
+                }
+                Some(email) => Ok(AuthenticatedUser::new(email, format!("sub-{email}"))
+                    .with_issuer("https://idp.example.com")),
+                None => Err(AuthError::InvalidSignature("not from the mock".into())),
+            };
+            Box::pin(async move { result })
+        }
+    }
+
+    /// Start a mock upstream token endpoint that logs in `email`.
+    async fn mock_upstream(email: &'static str) -> String {
+        let app = axum::Router::new().route(
+            "/token",
+            post(
+                move |axum::Form(form): axum::Form<HashMap<String, String>>| async move {
+                    let valid = form.get("code").map(String::as_str) == Some(UPSTREAM_CODE)
+                        && form.get("client_secret").map(String::as_str) == Some("upstream-secret")
+                        && form.get("redirect_uri").map(String::as_str)
+                            == Some("https://kb.example.com/callback")
+                        && form.contains_key("code_verifier");
+                    if valid {
+                        (
+                            StatusCode::OK,
+                            axum::Json(json!({
+                                "access_token": "upstream-access-token",
+                                "id_token": format!("id-token:{email}"),
+                                "token_type": "Bearer",
+                            })),
+                        )
+                    } else {
+                        (
+                            StatusCode::BAD_REQUEST,
+                            axum::Json(json!({ "error": "invalid_grant" })),
+                        )
+                    }
+                },
+            ),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        format!("http://{addr}/token")
+    }
+
+    async fn server(email: &'static str) -> Arc<AuthorizationServer> {
+        let upstream = UpstreamConfig::new(
+            "https://idp.example.com/authorize",
+            mock_upstream(email).await,
+            "upstream-client",
+            "upstream-secret",
+        )
+        .with_domain("example.com");
+        let config = AuthServerConfig::new("https://kb.example.com", upstream);
+        Arc::new(AuthorizationServer::new(
+            config,
+            Arc::new(MockUpstreamValidator),
+        ))
+    }
+
+    async fn send(
+        router: &axum::Router,
+        request: Request<Body>,
+    ) -> (StatusCode, Option<String>, Value) {
+        let response = router.clone().oneshot(request).await.unwrap();
+        let status = response.status();
+        let location = response
+            .headers()
+            .get(header::LOCATION)
+            .map(|l| l.to_str().unwrap().to_string());
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body = serde_json::from_slice(&bytes).unwrap_or(Value::Null);
+        (status, location, body)
+    }
+
+    fn get(uri: &str) -> Request<Body> {
+        Request::get(uri).body(Body::empty()).unwrap()
+    }
+
+    fn post_form(uri: &str, form: &[(&str, &str)]) -> Request<Body> {
+        let mut encoded = Url::parse("http://form/").unwrap();
+        encoded.query_pairs_mut().extend_pairs(form);
+        let body = encoded.query().unwrap_or_default().to_string();
+        Request::post(uri)
+            .header(header::CONTENT_TYPE, "application/x-www-form-urlencoded")
+            .body(Body::from(body))
+            .unwrap()
+    }
+
+    fn query(location: &str) -> HashMap<String, String> {
+        Url::parse(location)
+            .unwrap()
+            .query_pairs()
+            .into_owned()
+            .collect()
+    }
+
+    async fn register(router: &axum::Router) -> String {
+        let request = Request::post("/register")
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(
+                json!({ "redirect_uris": [REDIRECT_URI], "client_name": "test client" })
+                    .to_string(),
+            ))
+            .unwrap();
+        let (status, _, body) = send(router, request).await;
+        assert_eq!(status, StatusCode::CREATED);
+        assert_eq!(body["token_endpoint_auth_method"], "none");
+        body["client_id"].as_str().unwrap().to_string()
+    }
+
+    /// Run `/authorize` and `/callback`, returning the client redirect's
+    /// query parameters.
+    async fn authorize(router: &axum::Router, client_id: &str) -> HashMap<String, String> {
+        let uri = format!(
+            "/authorize?response_type=code&client_id={client_id}&redirect_uri={}\
+             &code_challenge={}&code_challenge_method=S256&state=client-state&scope=mcp",
+            REDIRECT_URI,
+            pkce_challenge(VERIFIER)
+        );
+        let (status, location, _) = send(router, get(&uri)).await;
+        assert!(status.is_redirection());
+        let location = location.unwrap();
+        assert!(location.starts_with("https://idp.example.com/authorize?"));
+        let upstream = query(&location);
+        assert_eq!(upstream["client_id"], "upstream-client");
+        assert_eq!(upstream["redirect_uri"], "https://kb.example.com/callback");
+        assert_eq!(upstream["code_challenge_method"], "S256");
+
+        let uri = format!("/callback?code={UPSTREAM_CODE}&state={}", upstream["state"]);
+        let (status, location, _) = send(router, get(&uri)).await;
+        assert!(status.is_redirection());
+        let location = location.unwrap();
+        assert!(location.starts_with(REDIRECT_URI));
+        query(&location)
+    }
+
+    #[tokio::test]
+    async fn test_metadata() {
+        let router = routes(server("alice@example.com").await);
+
+        let (status, _, body) = send(&router, get("/.well-known/oauth-authorization-server")).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["issuer"], "https://kb.example.com");
+        assert_eq!(
+            body["registration_endpoint"],
+            "https://kb.example.com/register"
+        );
+        assert_eq!(body["token_endpoint"], "https://kb.example.com/token");
+        assert_eq!(body["code_challenge_methods_supported"][0], "S256");
+
+        let (_, _, body) = send(&router, get("/.well-known/oauth-protected-resource/mcp")).await;
+        assert_eq!(body["authorization_servers"][0], "https://kb.example.com");
+    }
+
+    #[tokio::test]
+    async fn test_full_flow() {
+        let server = server("alice@example.com").await;
+        let router = routes(server.clone());
+        let client_id = register(&router).await;
+
+        let redirect = authorize(&router, &client_id).await;
+        assert_eq!(redirect["state"], "client-state");
+        let code = &redirect["code"];
+
+        let exchange = [
+            ("grant_type", "authorization_code"),
+            ("code", code.as_str()),
+            ("redirect_uri", REDIRECT_URI),
+            ("client_id", client_id.as_str()),
+            ("code_verifier", VERIFIER),
+        ];
+        let (status, _, tokens) = send(&router, post_form("/token", &exchange)).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(tokens["token_type"], "Bearer");
+        assert_eq!(tokens["expires_in"], 900);
+        assert_eq!(tokens["scope"], "mcp");
+        let access_token = tokens["access_token"].as_str().unwrap();
+
+        let user = server
+            .validator()
+            .validate(access_token, &AuthConfig::default())
+            .await
+            .unwrap();
+        assert_eq!(user.email, "alice@example.com");
+        assert_eq!(user.issuer.as_deref(), Some("https://idp.example.com"));
+        assert!(user.has_scope("mcp"));
+
+        // Codes are single use.
+        let (status, _, body) = send(&router, post_form("/token", &exchange)).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error"], "invalid_grant");
+
+        // Refresh tokens rotate.
+        let refresh_token = tokens["refresh_token"].as_str().unwrap();
+        let refresh = [
+            ("grant_type", "refresh_token"),
+            ("refresh_token", refresh_token),
+            ("client_id", client_id.as_str()),
+        ];
+        let (status, _, refreshed) = send(&router, post_form("/token", &refresh)).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_ne!(refreshed["refresh_token"], tokens["refresh_token"]);
+        let (status, _, _) = send(&router, post_form("/token", &refresh)).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_tokens_pass_auth_layer() {
+        let server = server("alice@example.com").await;
+        let router = routes(server.clone());
+        let client_id = register(&router).await;
+        let code = authorize(&router, &client_id).await["code"].clone();
+        let exchange = [
+            ("grant_type", "authorization_code"),
+            ("code", code.as_str()),
+            ("client_id", client_id.as_str()),
+            ("code_verifier", VERIFIER),
+        ];
+        let (_, _, tokens) = send(&router, post_form("/token", &exchange)).await;
+
+        let auth_config = AuthConfig {
+            enabled: true,
+            ..Default::default()
+        };
+        let mcp = axum::Router::new()
+            .route("/mcp", axum::routing::get(|| async { "ok" }))
+            .layer(AuthLayer::new(Arc::new(server.validator()), auth_config));
+        let request = |token: &str| {
+            Request::get("/mcp")
+                .header(header::AUTHORIZATION, format!("Bearer {token}"))
+                .body(Body::empty())
+                .unwrap()
+        };
+        let access_token = tokens["access_token"].as_str().unwrap();
+        let (status, _, _) = send(&mcp, request(access_token)).await;
+        assert_eq!(status, StatusCode::OK);
+        let (status, _, _) = send(&mcp, request("upstream-access-token")).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_pkce_mismatch() {
+        let router = routes(server("alice@example.com").await);
+        let client_id = register(&router).await;
+        let code = authorize(&router, &client_id).await["code"].clone();
+
+        let exchange = [
+            ("grant_type", "authorization_code"),
+            ("code", code.as_str()),
+            ("client_id", client_id.as_str()),
+            ("code_verifier", "not-the-verifier"),
+        ];
+        let (status, _, body) = send(&router, post_form("/token", &exchange)).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error"], "invalid_grant");
+    }
+
+    #[tokio::test]
+    async fn test_upstream_rejection_denies_access() {
+        let router = routes(server("mallory@elsewhere.com").await);
+        let client_id = register(&router).await;
+
+        let redirect = authorize(&router, &client_id).await;
+        assert_eq!(redirect["error"], "access_denied");
+        assert_eq!(redirect["state"], "client-state");
+        assert!(!redirect.contains_key("code"));
+    }
+
+    #[tokio::test]
+    async fn test_authorize_errors() {
+        let router = routes(server("alice@example.com").await);
+        let client_id = register(&router).await;
+
+        // Unknown clients and redirect URIs are not redirected to.
+        let (status, location, body) = send(
+            &router,
+            get("/authorize?response_type=code&client_id=unknown"),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert!(location.is_none());
+        assert_eq!(body["error"], "invalid_client");
+        let uri = format!(
+            "/authorize?response_type=code&client_id={client_id}\
+             &redirect_uri=https://evil.example.com/cb"
+        );
+        let (status, location, _) = send(&router, get(&uri)).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert!(location.is_none());
+
+        // Missing PKCE goes back to the client.
+        let uri = format!("/authorize?response_type=code&client_id={client_id}&state=s");
+        let (status, location, _) = send(&router, get(&uri)).await;
+        assert!(status.is_redirection());
+        let redirect = query(&location.unwrap());
+        assert_eq!(redirect["error"], "invalid_request");
+        assert_eq!(redirect["state"], "s");
+    }
+
+    #[tokio::test]
+    async fn test_register_rejects_bad_metadata() {
+        let router = routes(server("alice@example.com").await);
+        for metadata in [
+            json!({ "redirect_uris": [] }),
+            json!({ "redirect_uris": ["http://app.example.com/cb"] }),
+            json!({ "redirect_uris": [REDIRECT_URI], "token_endpoint_auth_method": "client_secret_basic" }),
+            json!({ "redirect_uris": [REDIRECT_URI], "grant_types": ["client_credentials"] }),
+        ] {
+            let request = Request::post("/register")
+                .header(header::CONTENT_TYPE, "application/json")
+                .body(Body::from(metadata.to_string()))
+                .unwrap();
+            let (status, _, body) = send(&router, request).await;
+            assert_eq!(status, StatusCode::BAD_REQUEST, "{metadata}");
+            assert!(body["error"].as_str().unwrap().starts_with("invalid_"));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_register_is_capped() {
+        let config = AuthServerConfig::new(
+            "https://kb.example.com",
+            UpstreamConfig::google("client", "secret"),
+        )
+        .with_client_limits(1, std::time::Duration::from_secs(60));
+        let router = routes(Arc::new(AuthorizationServer::new(
+            config,
+            Arc::new(MockUpstreamValidator),
+        )));
+        register(&router).await;
+
+        let request = Request::post("/register")
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(
+                json!({ "redirect_uris": [REDIRECT_URI] }).to_string(),
+            ))
+            .unwrap();
+        let (status, _, body) = send(&router, request).await;
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(body["error"], "temporarily_unavailable");
+    }
+
+    #[test]
+    fn test_with_signing_key() {
+        let server = || {
+            AuthorizationServer::new(
+                AuthServerConfig::new(
+                    "https://kb.example.com",
+                    UpstreamConfig::google("client", "secret"),
+                ),
+                Arc::new(MockUpstreamValidator),
+            )
+        };
+        let err = server().with_signing_key("too short").unwrap_err();
+        assert_eq!(err, WeakSigningKey { len: 9 });
+        assert!(err.to_string().contains("at least 32"));
+
+        let key = [7u8; MIN_SIGNING_KEY_LEN];
+        let server = server().with_signing_key(key).unwrap();
+        assert_eq!(server.signing_key, key);
+    }
+
+    #[test]
+    fn test_pkce_challenge() {
+        // BASE64URL(SHA256(verifier)), unpadded.
+        assert_eq!(
+            pkce_challenge("dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gXFG2uQ-k"),
+            "puTe5bnEK7Ut7OMvy-RD7f1GRSoZo_eknS3E0bSJx1Q"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_granted_scopes() {
+        let server = AuthorizationServer::new(
+            AuthServerConfig::new(
+                "https://kb.example.com",
+                UpstreamConfig::google("client", "secret"),
+            )
+            .with_scopes(["mcp:read", "mcp:write"]),
+            Arc::new(MockUpstreamValidator),
+        );
+        assert_eq!(server.granted_scopes(Some("mcp:read")), ["mcp:read"]);
+        assert_eq!(server.granted_scopes(None), ["mcp:read", "mcp:write"]);
+        assert_eq!(
+            server.granted_scopes(Some("openid email")),
+            ["mcp:read", "mcp:write"]
+        );
+    }
+}
@@ -0,0 +1,191 @@
+//! In-memory authorization server state.
+//!
+//! Registered clients, logins in progress, authorization codes and refresh
+//! tokens live in process memory, so they are lost on restart and not
+//! shared between instances. Registration is open to anyone, so the number
+//! of clients is capped and clients that go unused are forgotten.
+
+use std::collections::HashMap;
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use fabryk_auth::AuthenticatedUser;
+
+/// A client registered through `/register`.
+#[derive(Clone, Debug)]
+pub(crate) struct RegisteredClient {
+    pub client_name: Option<String>,
+    pub redirect_uris: Vec<String>,
+}
+
+/// A client's authorization request, waiting for the user to log in with
+/// the upstream provider.
+#[derive(Clone, Debug)]
+pub(crate) struct PendingLogin {
+    pub client_id: String,
+    pub redirect_uri: String,
+    pub state: Option<String>,
+    pub code_challenge: String,
+    pub scopes: Vec<String>,
+    /// PKCE verifier for the upstream code exchange.
+    pub upstream_verifier: String,
+}
+
+/// What an authorization code or refresh token was issued for.
+#[derive(Clone, Debug)]
+pub(crate) struct Grant {
+    pub client_id: String,
+    pub user: AuthenticatedUser,
+    pub scopes: Vec<String>,
+    /// Set for authorization codes: the redirect URI and PKCE challenge the
+    /// token request must match.
+    pub redirect_uri: Option<String>,
+    pub code_challenge: Option<String>,
+}
+
+/// A store lock was poisoned by a panic while it was held.
+#[derive(Debug)]
+pub(crate) struct Poisoned;
+
+fn lock<T>(mutex: &Mutex<T>) -> Result<MutexGuard<'_, T>, Poisoned> {
+    mutex.lock().map_err(|_| Poisoned)
+}
+
+/// Single-use values that expire.
+pub(crate) struct ExpiringMap<T> {
+    entries: Mutex<HashMap<String, (T, Instant)>>,
+}
+
+impl<T> ExpiringMap<T> {
+    pub fn new() -> Self {
+        Self {
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Store `value` under `key` for `ttl`, dropping expired entries.
+    pub fn insert(&self, key: String, value: T, ttl: Duration) -> Result<(), Poisoned> {
+        let now = Instant::now();
+        let mut entries = lock(&self.entries)?;
+        entries.retain(|_, (_, expires_at)| *expires_at > now);
+        entries.insert(key, (value, now + ttl));
+        Ok(())
+    }
+
+    /// Remove and return the value under `key`, if it has not expired.
+    pub fn take(&self, key: &str) -> Result<Option<T>, Poisoned> {
+        let entry = lock(&self.entries)?.remove(key);
+        let now = Instant::now();
+        Ok(entry.and_then(|(value, expires_at)| (expires_at > now).then_some(value)))
+    }
+}
+
+/// Registered clients and when each was last used.
+pub(crate) struct ClientRegistry {
+    clients: Mutex<HashMap<String, (RegisteredClient, Instant)>>,
+}
+
+impl ClientRegistry {
+    pub fn new() -> Self {
+        Self {
+            clients: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Register `client` under `client_id`, first forgetting clients unused
+    /// for `idle_ttl`. Returns `false` if `max_clients` are still
+    /// registered.
+    pub fn register(
+        &self,
+        client_id: String,
+        client: RegisteredClient,
+        max_clients: usize,
+        idle_ttl: Duration,
+    ) -> Result<bool, Poisoned> {
+        let now = Instant::now();
+        let mut clients = lock(&self.clients)?;
+        clients.retain(|_, (_, last_used)| now.duration_since(*last_used) < idle_ttl);
+        if clients.len() >= max_clients {
+            return Ok(false);
+        }
+        clients.insert(client_id, (client, now));
+        Ok(true)
+    }
+
+    /// The client registered under `client_id`, marking it used.
+    pub fn get(&self, client_id: &str) -> Result<Option<RegisteredClient>, Poisoned> {
+        let mut clients = lock(&self.clients)?;
+        Ok(clients.get_mut(client_id).map(|(client, last_used)| {
+            *last_used = Instant::now();
+            client.clone()
+        }))
+    }
+}
+
+/// Everything the authorization server remembers.
+pub(crate) struct AuthStore {
+    pub clients: ClientRegistry,
+    pub logins: ExpiringMap<PendingLogin>,
+    pub codes: ExpiringMap<Grant>,
+    pub refresh_tokens: ExpiringMap<Grant>,
+}
+
+impl AuthStore {
+    pub fn new() -> Self {
+        Self {
+            clients: ClientRegistry::new(),
+            logins: ExpiringMap::new(),
+            codes: ExpiringMap::new(),
+            refresh_tokens: ExpiringMap::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expiring_map() {
+        let map = ExpiringMap::new();
+        map.insert("a".into(), 1, Duration::from_secs(60)).unwrap();
+        map.insert("b".into(), 2, Duration::ZERO).unwrap();
+
+        assert_eq!(map.take("a").unwrap(), Some(1));
+        // Single use.
+        assert_eq!(map.take("a").unwrap(), None);
+        // Expired.
+        assert_eq!(map.take("b").unwrap(), None);
+    }
+
+    #[test]
+    fn test_client_registry_cap_and_expiry() {
+        let client = RegisteredClient {
+            client_name: None,
+            redirect_uris: vec!["http://127.0.0.1/cb".into()],
+        };
+        let ttl = Duration::from_secs(60);
+        let registry = ClientRegistry::new();
+        assert!(
+            registry
+                .register("a".into(), client.clone(), 1, ttl)
+                .unwrap()
+        );
+        assert!(
+            !registry
+                .register("b".into(), client.clone(), 1, ttl)
+                .unwrap()
+        );
+        assert!(registry.get("a").unwrap().is_some());
+        assert!(registry.get("b").unwrap().is_none());
+
+        // Unused clients make way for new ones.
+        assert!(
+            registry
+                .register("c".into(), client, 1, Duration::ZERO)
+                .unwrap()
+        );
+        assert!(registry.get("a").unwrap().is_none());
+        assert!(registry.get("c").unwrap().is_some());
+    }
+}
@@ -0,0 +1,262 @@
+//! Access tokens issued by the authorization server.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::time::Duration;
+
+use fabryk_auth::{AuthConfig, AuthError, AuthenticatedUser, TokenValidator};
+use jsonwebtoken::errors::ErrorKind;
+use jsonwebtoken::{Algorithm, DecodingKey, EncodingKey, Header, Validation};
+use serde::{Deserialize, Serialize};
+
+/// Claims of an issued access token.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct AccessClaims {
+    iss: String,
+    aud: String,
+    sub: String,
+    email: String,
+    iat: u64,
+    exp: u64,
+    client_id: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    scope: String,
+    /// Issuer of the upstream login.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    idp: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    groups: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    roles: Vec<String>,
+}
+
+/// Sign an access token for `user`, valid for `ttl`.
+pub(crate) fn sign_access_token(
+    key: &[u8],
+    issuer: &str,
+    audience: &str,
+    client_id: &str,
+    user: &AuthenticatedUser,
+    scopes: &[String],
+    ttl: Duration,
+) -> Result<String, jsonwebtoken::errors::Error> {
+    let now = jsonwebtoken::get_current_timestamp();
+    let claims = AccessClaims {
+        iss: issuer.to_string(),
+        aud: audience.to_string(),
+        sub: user.subject.clone(),
+        email: user.email.clone(),
+        iat: now,
+        exp: now + ttl.as_secs(),
+        client_id: client_id.to_string(),
+        scope: scopes.join(" "),
+        idp: user.issuer.clone(),
+        groups: user.groups.clone(),
+        roles: user.roles.clone(),
+    };
+    jsonwebtoken::encode(
+        &Header::new(Algorithm::HS256),
+        &claims,
+        &EncodingKey::from_secret(key),
+    )
+}
+
+/// Validates access tokens issued by an
+/// [`AuthorizationServer`](super::AuthorizationServer).
+///
+/// Tokens from other issuers, and anything that is not a JWT, fail with
+/// [`AuthError::UnsupportedToken`], so the validator can share a
+/// [`ChainValidator`](fabryk_auth::ChainValidator) with other providers.
+///
+/// The email domain was checked when the user logged in, and the audience
+/// is the server's resource, so `AuthConfig.audience` and `domain` are not
+/// used.
+#[derive(Clone)]
+pub struct IssuedTokenValidator {
+    issuer: String,
+    audience: String,
+    key: DecodingKey,
+}
+
+impl IssuedTokenValidator {
+    /// Validate tokens from `issuer` for `audience`, signed with `key`.
+    pub fn new(issuer: impl Into<String>, audience: impl Into<String>, key: &[u8]) -> Self {
+        Self {
+            issuer: issuer.into(),
+            audience: audience.into(),
+            key: DecodingKey::from_secret(key),
+        }
+    }
+
+    fn validate_token(&self, token: &str) -> Result<AuthenticatedUser, AuthError> {
+        let header = jsonwebtoken::decode_header(token).map_err(|_| AuthError::UnsupportedToken)?;
+        if header.alg != Algorithm::HS256
+            || unverified_issuer(token).as_deref() != Some(self.issuer.as_str())
+        {
+            return Err(AuthError::UnsupportedToken);
+        }
+
+        let mut validation = Validation::new(Algorithm::HS256);
+        validation.set_audience(&[&self.audience]);
+        validation.set_issuer(&[&self.issuer]);
+        validation.set_required_spec_claims(&["exp", "iss", "aud", "sub"]);
+
+        let claims = jsonwebtoken::decode::<AccessClaims>(token, &self.key, &validation)
+            .map_err(|e| match e.kind() {
+                ErrorKind::ExpiredSignature => AuthError::Expired,
+                ErrorKind::InvalidAudience => AuthError::InvalidAudience,
+                _ => AuthError::InvalidSignature(e.to_string()),
+            })?
+            .claims;
+
+        // Report the provider the user logged in with, like its own
+        // validator would.
+        Ok(AuthenticatedUser::new(claims.email, claims.sub)
+            .with_issuer(claims.idp.unwrap_or(claims.iss))
+            .with_groups(claims.groups)
+            .with_roles(claims.roles)
+            .with_scopes(claims.scope.split_whitespace()))
+    }
+}
+
+impl std::fmt::Debug for IssuedTokenValidator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("IssuedTokenValidator")
+            .field("issuer", &self.issuer)
+            .field("audience", &self.audience)
+            .finish_non_exhaustive()
+    }
+}
+
+impl TokenValidator for IssuedTokenValidator {
+    fn validate(
+        &self,
+        token: &str,
+        _config: &AuthConfig,
+    ) -> Pin<Box<dyn Future<Output = Result<AuthenticatedUser, AuthError>> + Send + '_>> {
+        let result = self.validate_token(token);
+        Box::pin(async move { result })
+    }
+}
+
+/// The `iss` claim, read without checking the signature.
+fn unverified_issuer(token: &str) -> Option<String> {
+    jsonwebtoken::dangerous::insecure_decode::<serde_json::Value>(token)
+        .ok()?
+        .claims
+        .get("iss")?
+        .as_str()
+        .map(String::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY: &[u8] = b"test-signing-key-of-32-bytes-min";
+    const ISSUER: &str = "https://kb.example.com";
+
+    fn user() -> AuthenticatedUser {
+        AuthenticatedUser::new("alice@example.com", "alice-sub")
+            .with_issuer("https://idp.example.com")
+            .with_groups(["eng"])
+    }
+
+    fn sign(key: &[u8], issuer: &str, audience: &str, ttl: Duration) -> String {
+        sign_access_token(
+            key,
+            issuer,
+            audience,
+            "client-1",
+            &user(),
+            &["mcp".to_string()],
+            ttl,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let validator = IssuedTokenValidator::new(ISSUER, ISSUER, KEY);
+        let token = sign(KEY, ISSUER, ISSUER, Duration::from_secs(60));
+
+        let user = validator.validate_token(&token).unwrap();
+        assert_eq!(user.email, "alice@example.com");
+        assert_eq!(user.subject, "alice-sub");
+        assert_eq!(user.issuer.as_deref(), Some("https://idp.example.com"));
+        assert!(user.has_group("eng"));
+        assert!(user.has_scope("mcp"));
+    }
+
+    #[test]
+    fn test_rejections() {
+        let validator = IssuedTokenValidator::new(ISSUER, ISSUER, KEY);
+
+        let other_key = sign(
+            b"another-key-another-key-another!",
+            ISSUER,
+            ISSUER,
+            Duration::from_secs(60),
+        );
+        assert!(matches!(
+            validator.validate_token(&other_key),
+            Err(AuthError::InvalidSignature(_))
+        ));
+
+        let other_audience = sign(
+            KEY,
+            ISSUER,
+            "https://other.example.com",
+            Duration::from_secs(60),
+        );
+        assert!(matches!(
+            validator.validate_token(&other_audience),
+            Err(AuthError::InvalidAudience)
+        ));
+
+        // Other issuers and non-JWTs belong to other validators.
+        let other_issuer = sign(
+            KEY,
+            "https://other.example.com",
+            ISSUER,
+            Duration::from_secs(60),
+        );
+        assert!(matches!(
+            validator.validate_token(&other_issuer),
+            Err(AuthError::UnsupportedToken)
+        ));
+        assert!(matches!(
+            validator.validate_token("fbk_00ff_secret"),
+            Err(AuthError::UnsupportedToken)
+        ));
+    }
+
+    #[test]
+    fn test_expired() {
+        let validator = IssuedTokenValidator::new(ISSUER, ISSUER, KEY);
+        let now = jsonwebtoken::get_current_timestamp();
+        let claims = AccessClaims {
+            iss: ISSUER.into(),
+            aud: ISSUER.into(),
+            sub: "alice-sub".into(),
+            email: "alice@example.com".into(),
+            iat: now - 3600,
+            exp: now - 600,
+            client_id: "client-1".into(),
+            scope: String::new(),
+            idp: None,
+            groups: Vec::new(),
+            roles: Vec::new(),
+        };
+        let token = jsonwebtoken::encode(
+            &Header::new(Algorithm::HS256),
+            &claims,
+            &EncodingKey::from_secret(KEY),
+        )
+        .unwrap();
+        assert!(matches!(
+            validator.validate_token(&token),
+            Err(AuthError::Expired)
+        ));
+    }
+}
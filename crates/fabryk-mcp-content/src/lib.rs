@@ -6,6 +6,8 @@
 //! - `SourceProvider` — access source materials (books, papers)
 //! - `ContentTools<P>` — MCP tools backed by a content provider
 //! - `SourceTools<P>` — MCP tools for source access
+//! - `StoreContentProvider` — a ready-made provider over the markdown items
+//!   in a `ContentStore`
 //!
 //! # Example
 //!
@@ -25,11 +27,15 @@
 //! let tools = ContentTools::new(provider).with_prefix("concepts");
 //! ```
 
+pub mod store;
 pub mod tools;
 pub mod traits;
 
 // Re-exports — traits
 pub use traits::{CategoryInfo, ChapterInfo, ContentItemProvider, SourceProvider};
 
+// Re-exports — store provider
+pub use store::{StoreContentProvider, StoreItemDetail, StoreItemSummary};
+
 // Re-exports — tools
 pub use tools::{ContentTools, GetChapterArgs, GetItemArgs, ListItemsArgs, SourceTools};
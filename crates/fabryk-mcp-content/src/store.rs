@@ -0,0 +1,245 @@
+//! Content item provider backed by a [`ContentStore`].
+//!
+//! Serves the markdown items in a store without domain-specific code:
+//! an item's ID is its file stem, its category is its parent directory,
+//! and its title comes from the frontmatter `title` or the first heading.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use fabryk_content::markdown::{extract_first_heading, extract_frontmatter};
+use fabryk_core::store::{ContentEntry, ContentStore};
+use fabryk_core::{Error, Result};
+use serde::Serialize;
+
+use crate::traits::{CategoryInfo, ContentItemProvider};
+
+/// Summary of a markdown item in a store.
+#[derive(Clone, Debug, Serialize)]
+pub struct StoreItemSummary {
+    /// Item ID (the file stem).
+    pub id: String,
+    /// Title from frontmatter or the first heading, else the ID.
+    pub title: String,
+    /// Parent directory key, if the item is not at the store root.
+    pub category: Option<String>,
+    /// Key of the item in the store.
+    pub key: String,
+}
+
+/// A markdown item in a store, with its content.
+#[derive(Clone, Debug, Serialize)]
+pub struct StoreItemDetail {
+    /// Item ID (the file stem).
+    pub id: String,
+    /// Title from frontmatter or the first heading, else the ID.
+    pub title: String,
+    /// Parent directory key, if the item is not at the store root.
+    pub category: Option<String>,
+    /// Key of the item in the store.
+    pub key: String,
+    /// Full markdown content, including frontmatter.
+    pub content: String,
+}
+
+/// A [`ContentItemProvider`] over the markdown items in a [`ContentStore`].
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let store = config.content_store("concepts")?;
+/// let provider = StoreContentProvider::new(store).with_type_names("concept", "concepts");
+/// let tools = ContentTools::new(provider).with_prefix("concepts");
+/// ```
+#[derive(Debug)]
+pub struct StoreContentProvider {
+    store: Arc<dyn ContentStore>,
+    type_name: String,
+    type_name_plural: String,
+}
+
+impl StoreContentProvider {
+    /// Serve the markdown items in `store`.
+    pub fn new(store: Arc<dyn ContentStore>) -> Self {
+        Self {
+            store,
+            type_name: "item".to_string(),
+            type_name_plural: "items".to_string(),
+        }
+    }
+
+    /// Set the content type names used in tool descriptions.
+    pub fn with_type_names(
+        mut self,
+        singular: impl Into<String>,
+        plural: impl Into<String>,
+    ) -> Self {
+        self.type_name = singular.into();
+        self.type_name_plural = plural.into();
+        self
+    }
+
+    /// The underlying store.
+    pub fn store(&self) -> &Arc<dyn ContentStore> {
+        &self.store
+    }
+
+    /// Markdown entries in the store, sorted by key.
+    async fn markdown_entries(&self) -> Result<Vec<ContentEntry>> {
+        let mut entries = self.store.list().await?;
+        entries.retain(|e| e.extension() == Some("md"));
+        Ok(entries)
+    }
+
+    async fn read(&self, entry: &ContentEntry) -> Result<(StoreItemSummary, String)> {
+        let content = self.store.get_string(&entry.key).await?;
+        let (id, category) = split_key(&entry.key);
+        let title = title_of(&content).unwrap_or_else(|| id.clone());
+        let summary = StoreItemSummary {
+            id,
+            title,
+            category,
+            key: entry.key.clone(),
+        };
+        Ok((summary, content))
+    }
+}
+
+/// The item ID and category for a key.
+fn split_key(key: &str) -> (String, Option<String>) {
+    let (category, name) = match key.rsplit_once('/') {
+        Some((dir, name)) => (Some(dir.to_string()), name),
+        None => (None, key),
+    };
+    let id = name.strip_suffix(".md").unwrap_or(name).to_string();
+    (id, category)
+}
+
+/// The frontmatter `title`, or else the first heading.
+fn title_of(content: &str) -> Option<String> {
+    if let Ok(frontmatter) = extract_frontmatter(content)
+        && let Some(title) = frontmatter.get_str("title")
+    {
+        return Some(title.to_string());
+    }
+    extract_first_heading(content).map(|(_, heading)| heading)
+}
+
+#[async_trait]
+impl ContentItemProvider for StoreContentProvider {
+    type ItemSummary = StoreItemSummary;
+    type ItemDetail = StoreItemDetail;
+
+    async fn list_items(
+        &self,
+        category: Option<&str>,
+        limit: Option<usize>,
+    ) -> Result<Vec<Self::ItemSummary>> {
+        let mut items = Vec::new();
+        for entry in self.markdown_entries().await? {
+            if category.is_some() && split_key(&entry.key).1.as_deref() != category {
+                continue;
+            }
+            if limit.is_some_and(|max| items.len() >= max) {
+                break;
+            }
+            items.push(self.read(&entry).await?.0);
+        }
+        Ok(items)
+    }
+
+    async fn get_item(&self, id: &str) -> Result<Self::ItemDetail> {
+        let entry = self
+            .markdown_entries()
+            .await?
+            .into_iter()
+            .find(|e| split_key(&e.key).0 == id)
+            .ok_or_else(|| Error::not_found(&self.type_name, id))?;
+        let (summary, content) = self.read(&entry).await?;
+        Ok(StoreItemDetail {
+            id: summary.id,
+            title: summary.title,
+            category: summary.category,
+            key: summary.key,
+            content,
+        })
+    }
+
+    async fn list_categories(&self) -> Result<Vec<CategoryInfo>> {
+        let mut counts: BTreeMap<String, usize> = BTreeMap::new();
+        for entry in self.markdown_entries().await? {
+            if let Some(category) = split_key(&entry.key).1 {
+                *counts.entry(category).or_default() += 1;
+            }
+        }
+        Ok(counts
+            .into_iter()
+            .map(|(id, count)| CategoryInfo {
+                name: id.clone(),
+                id,
+                count,
+                description: None,
+            })
+            .collect())
+    }
+
+    async fn count(&self) -> Result<usize> {
+        Ok(self.markdown_entries().await?.len())
+    }
+
+    fn content_type_name(&self) -> &str {
+        &self.type_name
+    }
+
+    fn content_type_name_plural(&self) -> &str {
+        &self.type_name_plural
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fabryk_core::store::MemoryContentStore;
+
+    fn provider() -> StoreContentProvider {
+        let store = MemoryContentStore::new()
+            .with_item("harmony/cadence.md", "---\ntitle: Cadence\n---\n\nBody.\n")
+            .with_item("harmony/voice-leading.md", "# Voice Leading\n\nBody.\n")
+            .with_item("rhythm/meter.md", "No title here.\n")
+            .with_item("index.md", "# Index\n")
+            .with_item("harmony/notes.txt", "ignored");
+        StoreContentProvider::new(Arc::new(store)).with_type_names("concept", "concepts")
+    }
+
+    #[tokio::test]
+    async fn test_store_provider_list_and_get() {
+        let provider = provider();
+        assert_eq!(provider.count().await.unwrap(), 4);
+        assert_eq!(provider.content_type_name_plural(), "concepts");
+
+        let harmony = provider.list_items(Some("harmony"), None).await.unwrap();
+        let titles: Vec<&str> = harmony.iter().map(|i| i.title.as_str()).collect();
+        assert_eq!(titles, ["Cadence", "Voice Leading"]);
+        assert_eq!(provider.list_items(None, Some(1)).await.unwrap().len(), 1);
+
+        let meter = provider.get_item("meter").await.unwrap();
+        assert_eq!(meter.title, "meter");
+        assert_eq!(meter.category.as_deref(), Some("rhythm"));
+        assert_eq!(meter.content, "No title here.\n");
+        assert!(
+            provider
+                .get_item("missing")
+                .await
+                .unwrap_err()
+                .is_not_found()
+        );
+
+        let categories = provider.list_categories().await.unwrap();
+        let counts: Vec<(&str, usize)> = categories
+            .iter()
+            .map(|c| (c.id.as_str(), c.count))
+            .collect();
+        assert_eq!(counts, [("harmony", 2), ("rhythm", 1)]);
+    }
+}
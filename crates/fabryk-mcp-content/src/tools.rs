@@ -4,6 +4,7 @@
 //! `ToolRegistry` by delegating to domain-specific providers.
 
 use crate::traits::{ContentItemProvider, SourceProvider};
+use fabryk_mcp_core::args::parse_args;
 use fabryk_mcp_core::error::McpErrorExt;
 use fabryk_mcp_core::model::{CallToolResult, Content, ErrorData, Tool};
 use fabryk_mcp_core::registry::{ToolRegistry, ToolResult};
@@ -230,9 +231,9 @@ impl<P: ContentItemProvider + 'static> ToolRegistry for ContentTools<P> {
         let provider = Arc::clone(&self.provider);
 
         if name == self.tool_name("list") {
+            let tool = name.to_string();
             return Some(Box::pin(async move {
-                let args: ListItemsArgs = serde_json::from_value(args)
-                    .map_err(|e| ErrorData::invalid_params(e.to_string(), None))?;
+                let args: ListItemsArgs = parse_args(&tool, args)?;
                 let items = provider
                     .list_items(args.category.as_deref(), args.limit)
                     .await
@@ -242,9 +243,9 @@ impl<P: ContentItemProvider + 'static> ToolRegistry for ContentTools<P> {
         }
 
         if name == self.tool_name("get") {
+            let tool = name.to_string();
             return Some(Box::pin(async move {
-                let args: GetItemArgs = serde_json::from_value(args)
-                    .map_err(|e| ErrorData::invalid_params(e.to_string(), None))?;
+                let args: GetItemArgs = parse_args(&tool, args)?;
                 let item = provider
                     .get_item(&args.id)
                     .await
@@ -405,9 +406,9 @@ impl<P: SourceProvider + 'static> ToolRegistry for SourceTools<P> {
         }
 
         if name == self.tool_name(Self::SLOT_CHAPTERS) {
+            let tool = name.to_string();
             return Some(Box::pin(async move {
-                let args: ListChaptersArgs = serde_json::from_value(args)
-                    .map_err(|e| ErrorData::invalid_params(e.to_string(), None))?;
+                let args: ListChaptersArgs = parse_args(&tool, args)?;
                 let chapters = provider
                     .list_chapters(&args.source_id)
                     .await
@@ -417,9 +418,9 @@ impl<P: SourceProvider + 'static> ToolRegistry for SourceTools<P> {
         }
 
         if name == self.tool_name(Self::SLOT_GET_CHAPTER) {
+            let tool = name.to_string();
             return Some(Box::pin(async move {
-                let args: GetChapterArgs = serde_json::from_value(args)
-                    .map_err(|e| ErrorData::invalid_params(e.to_string(), None))?;
+                let args: GetChapterArgs = parse_args(&tool, args)?;
                 let content = provider
                     .get_chapter(&args.source_id, &args.chapter, args.section.as_deref())
                     .await
@@ -0,0 +1,734 @@
+//! Runtime tool argument validation and typed tool handlers.
+//!
+//! [`validate_arguments`] checks a tool call's arguments against the tool's
+//! declared `inputSchema`, and [`ValidatedRegistry`] applies it to every
+//! call before dispatch. Invalid calls fail with a uniform
+//! `invalid_params` error built by [`invalid_arguments`].
+//!
+//! For tool authors, [`typed_tool`] derives a tool's input schema from a
+//! Rust struct and [`parse_args`] deserializes the arguments into it,
+//! reporting failures in the same format.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use fabryk_mcp::args::{parse_args, typed_tool};
+//!
+//! /// Arguments for the search tool.
+//! #[derive(Deserialize, JsonSchema)]
+//! struct SearchArgs {
+//!     /// Search query string.
+//!     query: String,
+//!     /// Maximum results to return.
+//!     limit: Option<usize>,
+//! }
+//!
+//! fn tools(&self) -> Vec<Tool> {
+//!     vec![typed_tool::<SearchArgs>("search", "Full-text search")]
+//! }
+//!
+//! fn call(&self, name: &str, args: Value) -> Option<ToolResult> {
+//!     let name = name.to_string();
+//!     Some(Box::pin(async move {
+//!         let args: SearchArgs = parse_args(&name, args)?;
+//!         // ...
+//!     }))
+//! }
+//! ```
+
+use crate::context::ToolContext;
+use crate::registry::{ToolRegistry, ToolResult};
+use rmcp::model::{ErrorData, Tool};
+use schemars::JsonSchema;
+use schemars::generate::SchemaSettings;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// A single problem with a tool call's arguments.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ArgumentIssue {
+    /// Where the problem is, e.g. `limit` or `filters[0].name`.
+    /// Empty for the arguments object itself.
+    pub path: String,
+    /// What's wrong.
+    pub message: String,
+}
+
+impl std::fmt::Display for ArgumentIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.path.is_empty() {
+            write!(f, "{}", self.message)
+        } else {
+            write!(f, "{}: {}", self.path, self.message)
+        }
+    }
+}
+
+/// Build the `invalid_params` error returned for bad tool arguments.
+///
+/// The message lists every issue; the error data carries them as
+/// `{"tool": ..., "errors": [{"path": ..., "message": ...}]}` for clients
+/// that want to act on them.
+pub fn invalid_arguments(tool: &str, issues: &[ArgumentIssue]) -> ErrorData {
+    let summary = issues
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join("; ");
+    ErrorData::invalid_params(
+        format!("Invalid arguments for tool '{tool}': {summary}"),
+        Some(serde_json::json!({ "tool": tool, "errors": issues })),
+    )
+}
+
+/// Validate tool arguments against an `inputSchema`.
+///
+/// Supports the JSON Schema keywords tool schemas use in practice: `type`,
+/// `properties`, `required`, `additionalProperties`, `items`, `enum`,
+/// `const`, numeric and length bounds, `anyOf`/`oneOf`/`allOf` (`oneOf`
+/// requiring exactly one matching branch), and local `$ref`s. Unknown
+/// keywords are ignored. Missing (`null`) arguments are treated as an empty
+/// object. Returns an empty vec if the arguments are valid.
+pub fn validate_arguments(schema: &Map<String, Value>, args: &Value) -> Vec<ArgumentIssue> {
+    let empty = Value::Object(Map::new());
+    let args = if args.is_null() { &empty } else { args };
+    let mut issues = Vec::new();
+    check(schema, schema, args, "", &mut issues);
+    issues
+}
+
+fn check(
+    root: &Map<String, Value>,
+    schema: &Map<String, Value>,
+    value: &Value,
+    path: &str,
+    issues: &mut Vec<ArgumentIssue>,
+) {
+    let mut issue = |message: String| {
+        issues.push(ArgumentIssue {
+            path: path.to_string(),
+            message,
+        })
+    };
+
+    if let Some(Value::String(reference)) = schema.get("$ref") {
+        match resolve_ref(root, reference) {
+            Some(target) => check(root, target, value, path, issues),
+            None => log::warn!("tool schema has unresolvable $ref '{reference}'"),
+        }
+        return;
+    }
+
+    if let Some(expected) = schema.get("type") {
+        let types: Vec<&str> = match expected {
+            Value::String(t) => vec![t.as_str()],
+            Value::Array(ts) => ts.iter().filter_map(Value::as_str).collect(),
+            _ => Vec::new(),
+        };
+        if !types.is_empty() && !types.iter().any(|t| has_type(value, t)) {
+            issue(format!(
+                "expected {}, got {}",
+                types.join(" or "),
+                type_name(value)
+            ));
+            return;
+        }
+    }
+
+    if let Some(Value::Array(allowed)) = schema.get("enum")
+        && !allowed.contains(value)
+    {
+        let allowed: Vec<String> = allowed.iter().map(ToString::to_string).collect();
+        issue(format!("must be one of {}", allowed.join(", ")));
+    }
+    if let Some(constant) = schema.get("const")
+        && constant != value
+    {
+        issue(format!("must be {constant}"));
+    }
+
+    match value {
+        Value::Number(n) => {
+            if let Some(x) = n.as_f64() {
+                check_bounds(schema, x, &mut issue);
+            }
+        }
+        Value::String(s) => {
+            let len = s.chars().count() as u64;
+            if let Some(min) = schema.get("minLength").and_then(Value::as_u64)
+                && len < min
+            {
+                issue(format!("must be at least {min} characters"));
+            }
+            if let Some(max) = schema.get("maxLength").and_then(Value::as_u64)
+                && len > max
+            {
+                issue(format!("must be at most {max} characters"));
+            }
+        }
+        _ => {}
+    }
+
+    for (keyword, all) in [("anyOf", false), ("oneOf", false), ("allOf", true)] {
+        let Some(Value::Array(branches)) = schema.get(keyword) else {
+            continue;
+        };
+        let results: Vec<Vec<ArgumentIssue>> = branches
+            .iter()
+            .filter_map(Value::as_object)
+            .map(|branch| {
+                let mut branch_issues = Vec::new();
+                check(root, branch, value, path, &mut branch_issues);
+                branch_issues
+            })
+            .collect();
+        let matched = results.iter().filter(|r| r.is_empty()).count();
+        if all {
+            issues.extend(results.into_iter().flatten());
+        } else if !results.is_empty() && matched == 0 {
+            issues.push(ArgumentIssue {
+                path: path.to_string(),
+                message: format!("does not match any allowed form ({keyword})"),
+            });
+        } else if keyword == "oneOf" && matched > 1 {
+            issues.push(ArgumentIssue {
+                path: path.to_string(),
+                message: format!("matches {matched} allowed forms; exactly one is allowed (oneOf)"),
+            });
+        }
+    }
+
+    match value {
+        Value::Object(object) => check_object(root, schema, object, path, issues),
+        Value::Array(items) => check_array(root, schema, items, path, issues),
+        _ => {}
+    }
+}
+
+fn check_bounds(schema: &Map<String, Value>, x: f64, issue: &mut impl FnMut(String)) {
+    if let Some(min) = schema.get("minimum").and_then(Value::as_f64)
+        && x < min
+    {
+        issue(format!("must be >= {min}"));
+    }
+    if let Some(max) = schema.get("maximum").and_then(Value::as_f64)
+        && x > max
+    {
+        issue(format!("must be <= {max}"));
+    }
+    if let Some(min) = schema.get("exclusiveMinimum").and_then(Value::as_f64)
+        && x <= min
+    {
+        issue(format!("must be > {min}"));
+    }
+    if let Some(max) = schema.get("exclusiveMaximum").and_then(Value::as_f64)
+        && x >= max
+    {
+        issue(format!("must be < {max}"));
+    }
+}
+
+fn check_object(
+    root: &Map<String, Value>,
+    schema: &Map<String, Value>,
+    object: &Map<String, Value>,
+    path: &str,
+    issues: &mut Vec<ArgumentIssue>,
+) {
+    if let Some(Value::Array(required)) = schema.get("required") {
+        for name in required.iter().filter_map(Value::as_str) {
+            if !object.contains_key(name) {
+                issues.push(ArgumentIssue {
+                    path: join_path(path, name),
+                    message: "is required".to_string(),
+                });
+            }
+        }
+    }
+
+    let properties = schema.get("properties").and_then(Value::as_object);
+    for (name, value) in object {
+        let child = join_path(path, name);
+        match properties.and_then(|p| p.get(name)) {
+            Some(Value::Object(property)) => check(root, property, value, &child, issues),
+            Some(_) => {}
+            None => match schema.get("additionalProperties") {
+                Some(Value::Bool(false)) => issues.push(ArgumentIssue {
+                    path: child,
+                    message: "is not a recognized argument".to_string(),
+                }),
+                Some(Value::Object(extra)) => check(root, extra, value, &child, issues),
+                _ => {}
+            },
+        }
+    }
+}
+
+fn check_array(
+    root: &Map<String, Value>,
+    schema: &Map<String, Value>,
+    items: &[Value],
+    path: &str,
+    issues: &mut Vec<ArgumentIssue>,
+) {
+    let len = items.len() as u64;
+    if let Some(min) = schema.get("minItems").and_then(Value::as_u64)
+        && len < min
+    {
+        issues.push(ArgumentIssue {
+            path: path.to_string(),
+            message: format!("must have at least {min} items"),
+        });
+    }
+    if let Some(max) = schema.get("maxItems").and_then(Value::as_u64)
+        && len > max
+    {
+        issues.push(ArgumentIssue {
+            path: path.to_string(),
+            message: format!("must have at most {max} items"),
+        });
+    }
+    if let Some(Value::Object(item_schema)) = schema.get("items") {
+        for (i, item) in items.iter().enumerate() {
+            check(root, item_schema, item, &format!("{path}[{i}]"), issues);
+        }
+    }
+}
+
+/// Resolve a local `$ref` such as `#/$defs/Filter`.
+fn resolve_ref<'a>(
+    root: &'a Map<String, Value>,
+    reference: &str,
+) -> Option<&'a Map<String, Value>> {
+    let pointer = reference.strip_prefix('#')?;
+    if pointer.is_empty() {
+        return Some(root);
+    }
+    let mut current = root;
+    for segment in pointer.trim_start_matches('/').split('/') {
+        let segment = segment.replace("~1", "/").replace("~0", "~");
+        current = current.get(&segment)?.as_object()?;
+    }
+    Some(current)
+}
+
+fn has_type(value: &Value, expected: &str) -> bool {
+    match expected {
+        "object" => value.is_object(),
+        "array" => value.is_array(),
+        "string" => value.is_string(),
+        "boolean" => value.is_boolean(),
+        "null" => value.is_null(),
+        "number" => value.is_number(),
+        "integer" => {
+            value.is_i64() || value.is_u64() || value.as_f64().is_some_and(|x| x.fract() == 0.0)
+        }
+        _ => true,
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(n) if n.is_f64() => "number",
+        Value::Number(_) => "integer",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+fn join_path(path: &str, name: &str) -> String {
+    if path.is_empty() {
+        name.to_string()
+    } else {
+        format!("{path}.{name}")
+    }
+}
+
+// ============================================================================
+// Typed handlers
+// ============================================================================
+
+/// Derive a tool `inputSchema` from an argument struct.
+///
+/// Field doc comments become property descriptions and `Option` fields are
+/// optional. Subschemas are inlined so clients see a self-contained schema.
+pub fn input_schema_for<T: JsonSchema>() -> Arc<Map<String, Value>> {
+    let mut settings = SchemaSettings::draft2020_12().for_deserialize();
+    settings.meta_schema = None;
+    settings.inline_subschemas = true;
+    let schema = settings.into_generator().into_root_schema_for::<T>();
+
+    let mut map = match schema.to_value() {
+        Value::Object(map) => map,
+        _ => Map::new(),
+    };
+    map.remove("title");
+    map.entry("type")
+        .or_insert_with(|| Value::String("object".to_string()));
+    Arc::new(map)
+}
+
+/// Create a tool whose input schema is derived from `T`.
+pub fn typed_tool<T: JsonSchema>(name: impl Into<String>, description: impl Into<String>) -> Tool {
+    Tool::new(name.into(), description.into(), input_schema_for::<T>())
+}
+
+/// Deserialize tool arguments into `T`.
+///
+/// Missing (`null`) arguments deserialize as an empty object.
+///
+/// # Errors
+///
+/// Returns the same `invalid_params` error as [`invalid_arguments`] if
+/// the arguments don't fit `T`.
+pub fn parse_args<T: DeserializeOwned>(tool: &str, args: Value) -> Result<T, ErrorData> {
+    let args = if args.is_null() {
+        Value::Object(Map::new())
+    } else {
+        args
+    };
+    serde_json::from_value(args).map_err(|e| {
+        invalid_arguments(
+            tool,
+            &[ArgumentIssue {
+                path: String::new(),
+                message: e.to_string(),
+            }],
+        )
+    })
+}
+
+// ============================================================================
+// ValidatedRegistry
+// ============================================================================
+
+/// A registry wrapper that validates arguments before dispatch.
+///
+/// Follows the same wrapper pattern as
+/// [`ServiceAwareRegistry`](crate::ServiceAwareRegistry):
+/// - `tools()` returns the inner registry's tools unchanged.
+/// - `call()` validates the arguments against the tool's `inputSchema` and
+///   returns an [`invalid_arguments`] error instead of dispatching when
+///   they don't match.
+///
+/// Schemas are read from the inner registry once, when it is wrapped.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let registry = ValidatedRegistry::new(
+///     CompositeRegistry::new().add(content_tools).add(search_tools),
+/// );
+/// FabrykMcpServer::new(registry).serve_stdio().await?;
+/// ```
+pub struct ValidatedRegistry {
+    inner: Box<dyn ToolRegistry>,
+    schemas: HashMap<String, Arc<Map<String, Value>>>,
+}
+
+impl ValidatedRegistry {
+    /// Wrap a registry with argument validation.
+    pub fn new<R: ToolRegistry + 'static>(registry: R) -> Self {
+        let schemas = registry
+            .tools()
+            .into_iter()
+            .map(|tool| (tool.name.to_string(), tool.input_schema))
+            .collect();
+        Self {
+            inner: Box::new(registry),
+            schemas,
+        }
+    }
+}
+
+impl ToolRegistry for ValidatedRegistry {
+    fn tools(&self) -> Vec<Tool> {
+        self.inner.tools()
+    }
+
+    fn call(&self, name: &str, args: Value) -> Option<ToolResult> {
+        self.call_with_context(name, args, ToolContext::new())
+    }
+
+    fn call_with_context(&self, name: &str, args: Value, ctx: ToolContext) -> Option<ToolResult> {
+        let schema = self.schemas.get(name)?;
+
+        let issues = validate_arguments(schema, &args);
+        if !issues.is_empty() {
+            let error = invalid_arguments(name, &issues);
+            return Some(Box::pin(async move { Err(error) }));
+        }
+
+        self.inner.call_with_context(name, args, ctx)
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rmcp::model::{CallToolResult, Content, ErrorCode};
+    use serde::Deserialize;
+    use serde_json::json;
+
+    fn schema(value: Value) -> Map<String, Value> {
+        match value {
+            Value::Object(map) => map,
+            _ => panic!("schema must be an object"),
+        }
+    }
+
+    fn search_schema() -> Map<String, Value> {
+        schema(json!({
+            "type": "object",
+            "properties": {
+                "query": { "type": "string", "minLength": 1 },
+                "limit": { "type": "integer", "minimum": 1, "maximum": 100 },
+                "mode": { "type": "string", "enum": ["fts", "semantic"] },
+                "tags": { "type": "array", "items": { "type": "string" } }
+            },
+            "required": ["query"],
+            "additionalProperties": false
+        }))
+    }
+
+    /// Arguments for a test tool.
+    #[derive(Debug, Deserialize, JsonSchema)]
+    #[allow(dead_code)]
+    struct TestArgs {
+        /// Concept identifier.
+        id: String,
+        /// Maximum depth.
+        depth: Option<usize>,
+        /// Optional filter.
+        filter: Option<TestFilter>,
+    }
+
+    #[derive(Debug, Deserialize, JsonSchema)]
+    #[allow(dead_code)]
+    struct TestFilter {
+        category: String,
+    }
+
+    #[test]
+    fn test_valid_arguments() {
+        let args = json!({"query": "modes", "limit": 10, "mode": "fts", "tags": ["a"]});
+        assert!(validate_arguments(&search_schema(), &args).is_empty());
+        assert!(
+            validate_arguments(&search_schema(), &json!({"query": "x", "limit": 5.0})).is_empty()
+        );
+    }
+
+    #[test]
+    fn test_missing_required_and_unknown_arguments() {
+        let issues = validate_arguments(&search_schema(), &json!({"qurey": "typo"}));
+        assert_eq!(issues.len(), 2);
+        assert_eq!(issues[0].path, "query");
+        assert_eq!(issues[0].message, "is required");
+        assert_eq!(issues[1].path, "qurey");
+        assert_eq!(issues[1].message, "is not a recognized argument");
+    }
+
+    #[test]
+    fn test_null_arguments_treated_as_empty_object() {
+        let issues = validate_arguments(&search_schema(), &Value::Null);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].path, "query");
+        assert!(validate_arguments(&crate::empty_input_schema(), &Value::Null).is_empty());
+    }
+
+    #[test]
+    fn test_type_enum_bound_and_item_errors() {
+        let args = json!({"query": "", "limit": "ten", "mode": "graph", "tags": ["ok", 3]});
+        let issues = validate_arguments(&search_schema(), &args);
+        let rendered: Vec<String> = issues.iter().map(ToString::to_string).collect();
+        assert!(rendered.contains(&"query: must be at least 1 characters".to_string()));
+        assert!(rendered.contains(&"limit: expected integer, got string".to_string()));
+        assert!(rendered.contains(&r#"mode: must be one of "fts", "semantic""#.to_string()));
+        assert!(rendered.contains(&"tags[1]: expected string, got integer".to_string()));
+
+        let issues = validate_arguments(&search_schema(), &json!({"query": "x", "limit": 0}));
+        assert_eq!(issues[0].to_string(), "limit: must be >= 1");
+        let issues = validate_arguments(&search_schema(), &json!({"query": "x", "limit": 1.5}));
+        assert_eq!(issues[0].to_string(), "limit: expected integer, got number");
+    }
+
+    #[test]
+    fn test_any_of_and_ref() {
+        let schema = schema(json!({
+            "type": "object",
+            "properties": {
+                "filter": { "anyOf": [{ "$ref": "#/$defs/Filter" }, { "type": "null" }] }
+            },
+            "$defs": {
+                "Filter": {
+                    "type": "object",
+                    "properties": { "category": { "type": "string" } },
+                    "required": ["category"]
+                }
+            }
+        }));
+        assert!(validate_arguments(&schema, &json!({"filter": null})).is_empty());
+        assert!(validate_arguments(&schema, &json!({"filter": {"category": "x"}})).is_empty());
+        let issues = validate_arguments(&schema, &json!({"filter": {}}));
+        assert_eq!(issues[0].path, "filter");
+        assert!(issues[0].message.contains("anyOf"));
+    }
+
+    #[test]
+    fn test_one_of_requires_exactly_one_match() {
+        let schema = schema(json!({
+            "type": "object",
+            "properties": {
+                "limit": { "oneOf": [{ "type": "integer" }, { "type": "number" }] }
+            }
+        }));
+        // 2.5 is only a number; 3 is both an integer and a number.
+        assert!(validate_arguments(&schema, &json!({"limit": 2.5})).is_empty());
+        let issues = validate_arguments(&schema, &json!({"limit": 3}));
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].path, "limit");
+        assert!(issues[0].message.contains("exactly one"));
+        let issues = validate_arguments(&schema, &json!({"limit": "x"}));
+        assert!(
+            issues[0]
+                .message
+                .contains("does not match any allowed form (oneOf)")
+        );
+    }
+
+    #[test]
+    fn test_invalid_arguments_error_payload() {
+        let issues = vec![ArgumentIssue {
+            path: "query".to_string(),
+            message: "is required".to_string(),
+        }];
+        let err = invalid_arguments("search", &issues);
+        assert_eq!(err.code, ErrorCode::INVALID_PARAMS);
+        assert_eq!(
+            err.message,
+            "Invalid arguments for tool 'search': query: is required"
+        );
+        let data = err.data.unwrap();
+        assert_eq!(data["tool"], "search");
+        assert_eq!(data["errors"][0]["path"], "query");
+        assert_eq!(data["errors"][0]["message"], "is required");
+    }
+
+    #[test]
+    fn test_input_schema_for_derives_schema() {
+        let schema = input_schema_for::<TestArgs>();
+        assert_eq!(schema["type"], "object");
+        assert!(schema.get("$schema").is_none());
+        assert!(schema.get("title").is_none());
+        assert_eq!(schema["required"], json!(["id"]));
+        assert_eq!(
+            schema["properties"]["id"]["description"],
+            "Concept identifier."
+        );
+
+        // The derived schema validates its own struct's arguments.
+        assert!(validate_arguments(&schema, &json!({"id": "a", "depth": 2})).is_empty());
+        assert!(
+            validate_arguments(&schema, &json!({"id": "a", "filter": {"category": "c"}}))
+                .is_empty()
+        );
+        assert!(!validate_arguments(&schema, &json!({"id": "a", "depth": -1})).is_empty());
+        assert!(!validate_arguments(&schema, &json!({"id": "a", "filter": {}})).is_empty());
+
+        let tool = typed_tool::<TestArgs>("concept_get", "Get a concept");
+        assert!(crate::validate::validate_tool(&tool).is_empty());
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let args: TestArgs = parse_args("concept_get", json!({"id": "a"})).unwrap();
+        assert_eq!(args.id, "a");
+        assert!(args.depth.is_none());
+
+        let err = parse_args::<TestArgs>("concept_get", Value::Null).unwrap_err();
+        assert_eq!(err.code, ErrorCode::INVALID_PARAMS);
+        assert!(
+            err.message
+                .starts_with("Invalid arguments for tool 'concept_get'")
+        );
+        assert!(err.message.contains("missing field `id`"));
+    }
+
+    struct EchoRegistry;
+
+    impl ToolRegistry for EchoRegistry {
+        fn tools(&self) -> Vec<Tool> {
+            vec![Tool::new("search", "Search", Arc::new(search_schema()))]
+        }
+
+        fn call(&self, name: &str, args: Value) -> Option<ToolResult> {
+            (name == "search").then(|| -> ToolResult {
+                Box::pin(async move {
+                    Ok(CallToolResult::success(vec![Content::text(
+                        args.to_string(),
+                    )]))
+                })
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_validated_registry() {
+        let registry = ValidatedRegistry::new(EchoRegistry);
+        assert_eq!(registry.tool_count(), 1);
+
+        let ok = registry
+            .call("search", json!({"query": "x"}))
+            .unwrap()
+            .await;
+        assert!(ok.is_ok());
+
+        let err = registry
+            .call("search", json!({"limit": 3}))
+            .unwrap()
+            .await
+            .unwrap_err();
+        assert_eq!(err.code, ErrorCode::INVALID_PARAMS);
+        assert!(err.message.contains("query: is required"));
+
+        assert!(registry.call("unknown", json!({})).is_none());
+    }
+
+    struct CountingRegistry(Arc<std::sync::atomic::AtomicUsize>);
+
+    impl ToolRegistry for CountingRegistry {
+        fn tools(&self) -> Vec<Tool> {
+            self.0.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            EchoRegistry.tools()
+        }
+
+        fn call(&self, name: &str, args: Value) -> Option<ToolResult> {
+            EchoRegistry.call(name, args)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_validated_registry_lists_tools_once() {
+        let listings = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let registry = ValidatedRegistry::new(CountingRegistry(listings.clone()));
+
+        for _ in 0..3 {
+            let result = registry
+                .call("search", json!({"query": "x"}))
+                .unwrap()
+                .await;
+            assert!(result.is_ok());
+        }
+        assert_eq!(listings.load(std::sync::atomic::Ordering::SeqCst), 1);
+    }
+}
@@ -0,0 +1,266 @@
+//! Per-call context for tool handlers.
+//!
+//! A [`ToolContext`] carries the client's MCP progress token and a
+//! cancellation token for one `tools/call` request. Handlers that do
+//! long-running work receive it through
+//! [`ToolRegistry::call_with_context`](crate::ToolRegistry::call_with_context),
+//! report progress with [`ToolContext::report_progress`], and check
+//! [`ToolContext::is_cancelled`] between steps.
+//!
+//! The context also carries the request's protocol extensions. Over the
+//! streamable HTTP transport these include the request's
+//! `http::request::Parts`, through which wrappers such as audit logging
+//! can see the authenticated caller.
+//!
+//! The server cancels the token when the client sends
+//! `notifications/cancelled` and drops the in-flight handler future, so
+//! handlers only need to check cancellation to stop blocking work early.
+
+use crate::notifier::Notifier;
+use rmcp::RoleServer;
+use rmcp::model::{ErrorData, Extensions, ProgressNotificationParam, ProgressToken};
+use rmcp::service::Peer;
+use std::future::Future;
+use tokio_util::sync::CancellationToken;
+
+/// Context for a single tool call.
+///
+/// Cheaply cloneable. A context created with [`ToolContext::new`] is
+/// detached: it is never cancelled by a client and progress reports are
+/// dropped, which makes it suitable for tests and for plain
+/// [`ToolRegistry::call`](crate::ToolRegistry::call) dispatch.
+#[derive(Clone, Default)]
+pub struct ToolContext {
+    progress_token: Option<ProgressToken>,
+    cancellation: CancellationToken,
+    extensions: Extensions,
+    client: Option<(Notifier, Peer<RoleServer>)>,
+}
+
+impl ToolContext {
+    /// Create a detached context.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Context for a request from a connected client.
+    pub(crate) fn for_request(
+        notifier: Notifier,
+        peer: Peer<RoleServer>,
+        progress_token: Option<ProgressToken>,
+        cancellation: CancellationToken,
+        extensions: Extensions,
+    ) -> Self {
+        Self {
+            progress_token,
+            cancellation,
+            extensions,
+            client: Some((notifier, peer)),
+        }
+    }
+
+    /// Set the progress token.
+    pub fn with_progress_token(mut self, token: ProgressToken) -> Self {
+        self.progress_token = Some(token);
+        self
+    }
+
+    /// Set the cancellation token.
+    pub fn with_cancellation(mut self, token: CancellationToken) -> Self {
+        self.cancellation = token;
+        self
+    }
+
+    /// Attach a request extension, e.g. HTTP request parts in tests.
+    pub fn with_extension<T: Clone + Send + Sync + 'static>(mut self, value: T) -> Self {
+        self.extensions.insert(value);
+        self
+    }
+
+    /// Protocol extensions of the request.
+    ///
+    /// Over HTTP, `extensions().get::<http::request::Parts>()` returns
+    /// the request's headers and the extensions set by middleware such
+    /// as authentication.
+    pub fn extensions(&self) -> &Extensions {
+        &self.extensions
+    }
+
+    /// The progress token the client sent with the request, if any.
+    pub fn progress_token(&self) -> Option<&ProgressToken> {
+        self.progress_token.as_ref()
+    }
+
+    /// The token cancelled when the client cancels the request.
+    pub fn cancellation_token(&self) -> &CancellationToken {
+        &self.cancellation
+    }
+
+    /// Whether the client has cancelled the request.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancellation.is_cancelled()
+    }
+
+    /// Wait until the client cancels the request.
+    pub async fn cancelled(&self) {
+        self.cancellation.cancelled().await;
+    }
+
+    /// Return an error if the client has cancelled the request.
+    ///
+    /// Convenient between steps of a long-running handler:
+    /// `ctx.check_cancelled("graph_validate")?;`.
+    pub fn check_cancelled(&self, tool: &str) -> Result<(), ErrorData> {
+        if self.is_cancelled() {
+            Err(cancelled_error(tool))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Report progress to the client.
+    ///
+    /// `progress` should increase with every call; `total` is the
+    /// expected final value if known. Returns `true` if a notification
+    /// was sent, and `false` if the client didn't ask for progress, the
+    /// context is detached, or the client is gone.
+    pub async fn report_progress(
+        &self,
+        progress: f64,
+        total: Option<f64>,
+        message: Option<&str>,
+    ) -> bool {
+        let (Some(token), Some((notifier, peer))) = (&self.progress_token, &self.client) else {
+            return false;
+        };
+
+        let mut param = ProgressNotificationParam::new(token.clone(), progress);
+        if let Some(total) = total {
+            param = param.with_total(total);
+        }
+        if let Some(message) = message {
+            param = param.with_message(message);
+        }
+        notifier.progress(peer, param).await
+    }
+}
+
+impl std::fmt::Debug for ToolContext {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ToolContext")
+            .field("progress_token", &self.progress_token)
+            .field("cancelled", &self.is_cancelled())
+            .field("connected", &self.client.is_some())
+            .finish()
+    }
+}
+
+/// The error returned for a tool call the client cancelled.
+pub fn cancelled_error(tool: &str) -> ErrorData {
+    ErrorData::internal_error(format!("Tool '{tool}' was cancelled"), None)
+}
+
+/// Run a tool future until it finishes or `cancellation` fires.
+///
+/// On cancellation the future is dropped, so any work it hasn't finished
+/// is abandoned.
+pub(crate) async fn run_cancellable<F>(
+    tool: &str,
+    future: F,
+    cancellation: &CancellationToken,
+) -> Result<rmcp::model::CallToolResult, ErrorData>
+where
+    F: Future<Output = Result<rmcp::model::CallToolResult, ErrorData>>,
+{
+    tokio::select! {
+        result = future => result,
+        () = cancellation.cancelled() => {
+            log::debug!("Tool '{tool}' cancelled by client");
+            Err(cancelled_error(tool))
+        }
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rmcp::model::{CallToolResult, NumberOrString};
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    #[test]
+    fn test_detached_context() {
+        let ctx = ToolContext::new();
+        assert!(ctx.progress_token().is_none());
+        assert!(!ctx.is_cancelled());
+        assert!(ctx.check_cancelled("search").is_ok());
+    }
+
+    #[test]
+    fn test_with_extension() {
+        let ctx = ToolContext::new().with_extension("alice".to_string());
+        assert_eq!(ctx.extensions().get::<String>().unwrap(), "alice");
+        assert!(ctx.extensions().get::<u32>().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_report_progress_without_client_is_noop() {
+        let ctx = ToolContext::new().with_progress_token(ProgressToken(NumberOrString::Number(7)));
+        assert!(ctx.progress_token().is_some());
+        assert!(!ctx.report_progress(1.0, Some(2.0), Some("half")).await);
+    }
+
+    #[tokio::test]
+    async fn test_cancellation_is_observable() {
+        let token = CancellationToken::new();
+        let ctx = ToolContext::new().with_cancellation(token.clone());
+        token.cancel();
+        assert!(ctx.is_cancelled());
+        ctx.cancelled().await;
+        let err = ctx.check_cancelled("graph_validate").unwrap_err();
+        assert_eq!(err.message, "Tool 'graph_validate' was cancelled");
+    }
+
+    #[tokio::test]
+    async fn test_run_cancellable_completes() {
+        let token = CancellationToken::new();
+        let result = run_cancellable(
+            "search",
+            async { Ok(CallToolResult::success(vec![])) },
+            &token,
+        )
+        .await;
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_run_cancellable_drops_future() {
+        struct DropFlag(Arc<AtomicBool>);
+        impl Drop for DropFlag {
+            fn drop(&mut self) {
+                self.0.store(true, Ordering::SeqCst);
+            }
+        }
+
+        let dropped = Arc::new(AtomicBool::new(false));
+        let flag = DropFlag(Arc::clone(&dropped));
+        let token = CancellationToken::new();
+        let canceller = token.clone();
+
+        let pending = async move {
+            let _flag = flag;
+            std::future::pending::<Result<CallToolResult, ErrorData>>().await
+        };
+        tokio::spawn(async move { canceller.cancel() });
+
+        let err = run_cancellable("graph_centrality", pending, &token)
+            .await
+            .unwrap_err();
+        assert_eq!(err.message, "Tool 'graph_centrality' was cancelled");
+        assert!(dropped.load(Ordering::SeqCst));
+    }
+}
@@ -35,6 +35,7 @@
 //!     .await?;
 //! ```
 
+use crate::context::ToolContext;
 use crate::registry::{ToolRegistry, ToolResult};
 use rmcp::model::{CallToolResult, Content, Tool};
 use serde::{Deserialize, Serialize};
@@ -281,6 +282,12 @@ impl<R: ToolRegistry> DiscoverableRegistry<R> {
         self
     }
 
+    /// The task strategies, e.g. to expose them as prompts with
+    /// [`TemplatePrompts::with_task_strategies`](crate::TemplatePrompts::with_task_strategies).
+    pub fn task_strategies(&self) -> &[TaskStrategy] {
+        &self.task_strategies
+    }
+
     /// Set summary filter values for common parameters.
     pub fn with_filter_summary(mut self, summary: FilterSummary) -> Self {
         self.filter_summary = Some(summary);
@@ -543,13 +550,17 @@ impl<R: ToolRegistry + Send + Sync> ToolRegistry for DiscoverableRegistry<R> {
     }
 
     fn call(&self, name: &str, args: Value) -> Option<ToolResult> {
+        self.call_with_context(name, args, ToolContext::new())
+    }
+
+    fn call_with_context(&self, name: &str, args: Value, ctx: ToolContext) -> Option<ToolResult> {
         // Handle directory tool call
         if name == self.directory_tool_name() {
             return Some(self.handle_directory());
         }
 
         // Delegate everything else to inner
-        self.inner.call(name, args)
+        self.inner.call_with_context(name, args, ctx)
     }
 }
 
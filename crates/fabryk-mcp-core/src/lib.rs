@@ -11,11 +11,15 @@
 //! ├─────────────────────────────────────────────────────────────┤
 //! │  ToolRegistry trait — tool registration and dispatch        │
 //! │  CompositeRegistry — combine multiple tool sources          │
+//! │  ValidatedRegistry — check arguments against inputSchema    │
+//! │  ToolContext — progress reporting and cancellation          │
 //! ├─────────────────────────────────────────────────────────────┤
 //! │  FabrykMcpServer — generic server (implements ServerHandler)│
 //! │  ServerConfig — server metadata (name, version, description)│
+//! │  ResourceRegistry / PromptRegistry — resources and prompts  │
 //! ├─────────────────────────────────────────────────────────────┤
 //! │  McpErrorExt — fabryk_core::Error → rmcp::ErrorData         │
+//! │  Redactor — redact sensitive values in JSON                 │
 //! ├─────────────────────────────────────────────────────────────┤
 //! │  Built-in tools:                                            │
 //! │  ├── health — server status and tool count                  │
@@ -44,12 +48,16 @@
 //!     .await?;
 //! ```
 
+pub mod args;
+pub mod context;
 pub mod discoverable;
 pub mod error;
 pub mod guidance;
 #[cfg(feature = "http")]
 pub mod health_router;
 pub mod notifier;
+pub mod prompt;
+pub mod redact;
 pub mod registry;
 pub mod resource;
 pub mod server;
@@ -60,6 +68,10 @@ pub mod validate;
 // Re-exports — registry
 pub use registry::{CompositeRegistry, ToolRegistry, ToolResult};
 
+// Re-exports — tool call context
+pub use context::{ToolContext, cancelled_error};
+pub use tokio_util::sync::CancellationToken;
+
 // Re-exports — service registry
 pub use service_registry::ServiceAwareRegistry;
 
@@ -84,18 +96,31 @@ pub use discoverable::{
 // Re-exports — resource registry
 pub use resource::{ResourceFuture, ResourceRegistry};
 
+// Re-exports — prompt registry
+pub use prompt::{PromptFuture, PromptRegistry, PromptTemplate, TemplatePrompts};
+
+// Re-exports — redaction
+pub use redact::{REDACTED, Redactor};
+
 // Re-exports — built-in tools
-pub use tools::{DiagnosticTools, HealthResponse, HealthTools, handle_health};
+pub use tools::{DiagnosticTools, HealthResponse, HealthTools, StatusSection, handle_health};
 
 // Re-exports — validation
 pub use validate::{assert_tools_valid, validate_tools, warn_on_invalid_tools};
 
+// Re-exports — argument validation and typed handlers
+pub use args::{
+    ArgumentIssue, ValidatedRegistry, input_schema_for, invalid_arguments, parse_args, typed_tool,
+    validate_arguments,
+};
+
 // Re-exports — rmcp types used by downstream crates
 pub mod model {
     //! Re-exported rmcp model types.
     pub use rmcp::model::{
-        AnnotateAble, Annotated, CallToolResult, Content, ErrorCode, ErrorData, LoggingLevel,
-        RawResource, Resource, ResourceContents, Tool,
+        AnnotateAble, Annotated, CallToolResult, Content, ErrorCode, ErrorData, Extensions,
+        GetPromptResult, JsonObject, LoggingLevel, ProgressToken, Prompt, PromptArgument,
+        PromptMessage, PromptMessageRole, RawResource, Resource, ResourceContents, Tool,
     };
 }
 
@@ -9,7 +9,8 @@ use std::sync::Arc;
 
 use rmcp::RoleServer;
 use rmcp::model::{
-    LoggingLevel, LoggingMessageNotificationParam, ResourceUpdatedNotificationParam,
+    LoggingLevel, LoggingMessageNotificationParam, ProgressNotificationParam,
+    ResourceUpdatedNotificationParam,
 };
 use rmcp::service::Peer;
 use tokio::sync::RwLock;
@@ -171,6 +172,24 @@ impl Notifier {
         success
     }
 
+    /// Send a progress notification for an in-flight request.
+    ///
+    /// Progress goes only to the client that issued the request, not to
+    /// every connected peer. Returns `true` if the notification was sent.
+    pub async fn progress(
+        &self,
+        peer: &Peer<RoleServer>,
+        param: ProgressNotificationParam,
+    ) -> bool {
+        match peer.notify_progress(param).await {
+            Ok(()) => true,
+            Err(e) => {
+                log::debug!("Failed to send progress notification: {e}");
+                false
+            }
+        }
+    }
+
     /// Return the number of currently connected clients.
     pub async fn client_count(&self) -> usize {
         self.peers.read().await.len()
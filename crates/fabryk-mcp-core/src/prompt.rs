@@ -0,0 +1,495 @@
+//! Prompt registry trait and templated prompts for MCP servers.
+//!
+//! The [`PromptRegistry`] trait abstracts over prompt listing and rendering,
+//! parallel to [`ResourceRegistry`](crate::ResourceRegistry). Clients surface
+//! prompts as slash-commands.
+//!
+//! [`PromptTemplate`] is a ready-made prompt whose messages contain
+//! `{{argument}}` placeholders, and [`TemplatePrompts`] serves a set of
+//! them. Templates can be built from [`ServerGuidance`] workflows and
+//! [`TaskStrategy`] entries.
+
+use crate::discoverable::TaskStrategy;
+use crate::guidance::ServerGuidance;
+use rmcp::model::{
+    ErrorData, GetPromptResult, JsonObject, Prompt, PromptArgument, PromptMessage,
+    PromptMessageRole,
+};
+use serde_json::Value;
+use std::future::Future;
+use std::pin::Pin;
+
+/// Type alias for async prompt rendering results.
+pub type PromptFuture = Pin<Box<dyn Future<Output = Result<GetPromptResult, ErrorData>> + Send>>;
+
+/// Trait for registering and rendering MCP prompts.
+///
+/// Implement this to expose domain-specific prompts that clients can
+/// list and fill in with arguments.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// struct MyPrompts { /* ... */ }
+///
+/// impl PromptRegistry for MyPrompts {
+///     fn prompts(&self) -> Vec<Prompt> {
+///         vec![/* prompt definitions */]
+///     }
+///
+///     fn get(&self, name: &str, args: JsonObject) -> Option<PromptFuture> {
+///         match name {
+///             "explain" => Some(Box::pin(async move { /* ... */ })),
+///             _ => None,
+///         }
+///     }
+/// }
+/// ```
+pub trait PromptRegistry: Send + Sync {
+    /// Returns all available prompts.
+    fn prompts(&self) -> Vec<Prompt>;
+
+    /// Render a prompt by name with the client's arguments.
+    ///
+    /// Returns `None` if the prompt is not recognized.
+    fn get(&self, name: &str, args: JsonObject) -> Option<PromptFuture>;
+}
+
+/// A prompt whose messages are rendered from `{{argument}}` templates.
+///
+/// Required arguments must be supplied. A template line that mentions an
+/// optional argument the client left out is dropped, so optional context
+/// disappears cleanly instead of leaving an empty label behind.
+#[derive(Clone, Debug)]
+pub struct PromptTemplate {
+    /// Prompt name (the slash-command).
+    pub name: String,
+    /// What the prompt does.
+    pub description: Option<String>,
+    /// Declared arguments.
+    pub arguments: Vec<PromptArgument>,
+    /// Message templates, rendered in order.
+    pub messages: Vec<(PromptMessageRole, String)>,
+}
+
+impl PromptTemplate {
+    /// Create an empty template with the given name.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            description: None,
+            arguments: Vec::new(),
+            messages: Vec::new(),
+        }
+    }
+
+    /// Set the description.
+    pub fn description(mut self, text: impl Into<String>) -> Self {
+        self.description = Some(text.into());
+        self
+    }
+
+    /// Declare an argument.
+    pub fn argument(
+        mut self,
+        name: impl Into<String>,
+        description: impl Into<String>,
+        required: bool,
+    ) -> Self {
+        self.arguments.push(
+            PromptArgument::new(name)
+                .with_description(description)
+                .with_required(required),
+        );
+        self
+    }
+
+    /// Add a user message template.
+    pub fn user(mut self, template: impl Into<String>) -> Self {
+        self.messages
+            .push((PromptMessageRole::User, template.into()));
+        self
+    }
+
+    /// Add an assistant message template.
+    pub fn assistant(mut self, template: impl Into<String>) -> Self {
+        self.messages
+            .push((PromptMessageRole::Assistant, template.into()));
+        self
+    }
+
+    /// Build a prompt from a task strategy.
+    ///
+    /// The prompt is named after the task (e.g. `"Explain a concept"` becomes
+    /// `explain_a_concept`) and asks the model to follow the strategy's steps.
+    /// A required `request` argument carries what the user wants done.
+    pub fn from_task_strategy(strategy: &TaskStrategy) -> Self {
+        let steps = numbered(&strategy.steps);
+        Self::new(slug(&strategy.task))
+            .description(strategy.task.clone())
+            .argument(
+                "request",
+                "What to do, e.g. the concept or question to address",
+                true,
+            )
+            .user(format!(
+                "Task: {}\nRequest: {{{{request}}}}\n\nFollow these steps:\n{steps}",
+                strategy.task
+            ))
+    }
+
+    /// Build a `{domain}_workflow` prompt from server guidance.
+    ///
+    /// The prompt carries the guidance context, workflow steps, conventions
+    /// and constraints, plus an optional `request` argument. Returns `None`
+    /// if the guidance has no workflow.
+    pub fn from_guidance(guidance: &ServerGuidance) -> Option<Self> {
+        if guidance.workflow.is_empty() {
+            return None;
+        }
+
+        let mut sections = Vec::new();
+        if let Some(ctx) = &guidance.context {
+            sections.push(ctx.clone());
+        }
+        sections.push("Request: {{request}}".to_string());
+        sections.push(format!("Workflow:\n{}", numbered(&guidance.workflow)));
+        if !guidance.conventions.is_empty() {
+            sections.push(format!("Conventions:\n{}", bulleted(&guidance.conventions)));
+        }
+        if !guidance.constraints.is_empty() {
+            sections.push(format!("Constraints:\n{}", bulleted(&guidance.constraints)));
+        }
+
+        Some(
+            Self::new(format!("{}_workflow", guidance.domain))
+                .description(format!("Work through the {} workflow", guidance.domain))
+                .argument("request", "What to accomplish", false)
+                .user(sections.join("\n\n")),
+        )
+    }
+
+    /// The MCP prompt definition for `prompts/list`.
+    pub fn prompt(&self) -> Prompt {
+        let arguments = (!self.arguments.is_empty()).then(|| self.arguments.clone());
+        Prompt::new(self.name.clone(), self.description.clone(), arguments)
+    }
+
+    /// Render the messages with the given arguments.
+    ///
+    /// # Errors
+    ///
+    /// Returns `invalid_params` if a required argument is missing.
+    pub fn render(&self, args: &JsonObject) -> Result<GetPromptResult, ErrorData> {
+        for arg in &self.arguments {
+            if arg.required == Some(true) && argument_value(args, &arg.name).is_none() {
+                return Err(ErrorData::invalid_params(
+                    format!(
+                        "Missing required argument '{}' for prompt '{}'",
+                        arg.name, self.name
+                    ),
+                    None,
+                ));
+            }
+        }
+
+        let messages = self
+            .messages
+            .iter()
+            .map(|(role, template)| PromptMessage::new_text(role.clone(), fill(template, args)))
+            .collect();
+
+        let mut result = GetPromptResult::new(messages);
+        result.description = self.description.clone();
+        Ok(result)
+    }
+}
+
+/// A prompt registry serving a fixed set of [`PromptTemplate`]s.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let prompts = TemplatePrompts::new()
+///     .with_guidance(&guidance)
+///     .with_task_strategies(&strategies)
+///     .add(PromptTemplate::new("summarize").user("Summarize {{topic}}"));
+///
+/// let server = FabrykMcpServer::new(registry).with_prompts(prompts);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct TemplatePrompts {
+    templates: Vec<PromptTemplate>,
+}
+
+impl TemplatePrompts {
+    /// Create an empty set of prompts.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a prompt template.
+    #[allow(clippy::should_implement_trait)]
+    pub fn add(mut self, template: PromptTemplate) -> Self {
+        self.templates.push(template);
+        self
+    }
+
+    /// Add the guidance workflow prompt, if the guidance has a workflow.
+    pub fn with_guidance(self, guidance: &ServerGuidance) -> Self {
+        match PromptTemplate::from_guidance(guidance) {
+            Some(template) => self.add(template),
+            None => self,
+        }
+    }
+
+    /// Add one prompt per task strategy.
+    pub fn with_task_strategies(mut self, strategies: &[TaskStrategy]) -> Self {
+        self.templates
+            .extend(strategies.iter().map(PromptTemplate::from_task_strategy));
+        self
+    }
+
+    /// Number of prompts.
+    pub fn len(&self) -> usize {
+        self.templates.len()
+    }
+
+    /// Whether there are no prompts.
+    pub fn is_empty(&self) -> bool {
+        self.templates.is_empty()
+    }
+}
+
+impl PromptRegistry for TemplatePrompts {
+    fn prompts(&self) -> Vec<Prompt> {
+        self.templates.iter().map(PromptTemplate::prompt).collect()
+    }
+
+    fn get(&self, name: &str, args: JsonObject) -> Option<PromptFuture> {
+        let template = self.templates.iter().find(|t| t.name == name)?;
+        let result = template.render(&args);
+        Some(Box::pin(async move { result }))
+    }
+}
+
+/// Look up an argument as text. Non-string values use their JSON form;
+/// null and empty strings count as missing.
+fn argument_value(args: &JsonObject, name: &str) -> Option<String> {
+    match args.get(name)? {
+        Value::Null => None,
+        Value::String(s) if s.is_empty() => None,
+        Value::String(s) => Some(s.clone()),
+        other => Some(other.to_string()),
+    }
+}
+
+/// Substitute `{{name}}` placeholders, dropping lines whose placeholders
+/// have no value.
+fn fill(template: &str, args: &JsonObject) -> String {
+    let mut lines = Vec::new();
+    'lines: for line in template.lines() {
+        let mut out = String::with_capacity(line.len());
+        let mut rest = line;
+        while let Some(start) = rest.find("{{") {
+            let Some(len) = rest[start + 2..].find("}}") else {
+                break;
+            };
+            let name = rest[start + 2..start + 2 + len].trim();
+            let Some(value) = argument_value(args, name) else {
+                continue 'lines;
+            };
+            out.push_str(&rest[..start]);
+            out.push_str(&value);
+            rest = &rest[start + 2 + len + 2..];
+        }
+        out.push_str(rest);
+        lines.push(out);
+    }
+    lines.join("\n")
+}
+
+/// Turn a task description into a prompt name.
+fn slug(text: &str) -> String {
+    text.split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(str::to_ascii_lowercase)
+        .collect::<Vec<_>>()
+        .join("_")
+}
+
+fn numbered(items: &[String]) -> String {
+    items
+        .iter()
+        .enumerate()
+        .map(|(i, s)| format!("{}. {s}", i + 1))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn bulleted(items: &[String]) -> String {
+    items
+        .iter()
+        .map(|s| format!("- {s}"))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rmcp::model::PromptMessageContent;
+
+    fn args(pairs: &[(&str, Value)]) -> JsonObject {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.clone()))
+            .collect()
+    }
+
+    fn text(result: &GetPromptResult, idx: usize) -> &str {
+        match &result.messages[idx].content {
+            PromptMessageContent::Text { text } => text,
+            other => panic!("expected text content, got {other:?}"),
+        }
+    }
+
+    /// Verify `PromptRegistry` is object-safe.
+    #[test]
+    fn test_trait_object_safety() {
+        fn _assert_object_safe(_: &dyn PromptRegistry) {}
+    }
+
+    #[test]
+    fn test_render_substitutes_arguments() {
+        let template = PromptTemplate::new("explain")
+            .description("Explain a concept")
+            .argument("concept", "The concept", true)
+            .argument("depth", "Detail level", false)
+            .user("Explain {{concept}} using the graph.\nDepth: {{ depth }}")
+            .assistant("Looking up {{concept}}.");
+
+        let result = template
+            .render(&args(&[
+                ("concept", Value::from("tritone substitution")),
+                ("depth", Value::from(2)),
+            ]))
+            .unwrap();
+        assert_eq!(result.description.as_deref(), Some("Explain a concept"));
+        assert_eq!(result.messages.len(), 2);
+        assert_eq!(
+            text(&result, 0),
+            "Explain tritone substitution using the graph.\nDepth: 2"
+        );
+        assert_eq!(result.messages[1].role, PromptMessageRole::Assistant);
+    }
+
+    #[test]
+    fn test_render_drops_lines_with_missing_optional_arguments() {
+        let template = PromptTemplate::new("explain")
+            .argument("concept", "The concept", true)
+            .argument("depth", "Detail level", false)
+            .user("Explain {{concept}}.\nDepth: {{depth}}\nBe concise.");
+
+        let result = template
+            .render(&args(&[("concept", Value::from("modes"))]))
+            .unwrap();
+        assert_eq!(text(&result, 0), "Explain modes.\nBe concise.");
+    }
+
+    #[test]
+    fn test_render_missing_required_argument_is_invalid_params() {
+        let template = PromptTemplate::new("explain")
+            .argument("concept", "The concept", true)
+            .user("Explain {{concept}}.");
+
+        let err = template.render(&JsonObject::new()).unwrap_err();
+        assert_eq!(err.code, rmcp::model::ErrorCode::INVALID_PARAMS);
+        assert!(err.message.contains("concept"));
+
+        let err = template
+            .render(&args(&[("concept", Value::from(""))]))
+            .unwrap_err();
+        assert!(err.message.contains("explain"));
+    }
+
+    #[test]
+    fn test_prompt_definition() {
+        let prompt = PromptTemplate::new("explain")
+            .description("Explain a concept")
+            .argument("concept", "The concept", true)
+            .prompt();
+        assert_eq!(prompt.name, "explain");
+        let arguments = prompt.arguments.unwrap();
+        assert_eq!(arguments[0].name, "concept");
+        assert_eq!(arguments[0].required, Some(true));
+
+        assert!(PromptTemplate::new("bare").prompt().arguments.is_none());
+    }
+
+    #[test]
+    fn test_from_task_strategy() {
+        let strategy = TaskStrategy {
+            task: "Explain a concept (using the graph)".to_string(),
+            steps: vec![
+                "Call concept_get".to_string(),
+                "Call graph_neighbors".to_string(),
+            ],
+        };
+        let template = PromptTemplate::from_task_strategy(&strategy);
+        assert_eq!(template.name, "explain_a_concept_using_the_graph");
+
+        let result = template
+            .render(&args(&[("request", Value::from("voice leading"))]))
+            .unwrap();
+        let body = text(&result, 0);
+        assert!(body.contains("Request: voice leading"));
+        assert!(body.contains("1. Call concept_get\n2. Call graph_neighbors"));
+    }
+
+    #[test]
+    fn test_from_guidance() {
+        let guidance = ServerGuidance::for_domain("nms")
+            .context("Galactic copilot")
+            .workflow("Call where_am_i")
+            .workflow("Plan the route")
+            .constraint("Never sell exotics");
+        let template = PromptTemplate::from_guidance(&guidance).unwrap();
+        assert_eq!(template.name, "nms_workflow");
+
+        let result = template.render(&JsonObject::new()).unwrap();
+        let body = text(&result, 0);
+        assert!(body.starts_with("Galactic copilot"));
+        assert!(!body.contains("Request:"));
+        assert!(body.contains("1. Call where_am_i\n2. Plan the route"));
+        assert!(body.contains("- Never sell exotics"));
+
+        assert!(PromptTemplate::from_guidance(&ServerGuidance::for_domain("x")).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_template_prompts_registry() {
+        let prompts = TemplatePrompts::new()
+            .with_guidance(&ServerGuidance::for_domain("nms").workflow("Look around"))
+            .with_task_strategies(&[TaskStrategy {
+                task: "Find a planet".to_string(),
+                steps: vec!["Call planet_search".to_string()],
+            }])
+            .add(PromptTemplate::new("hello").user("Hello"));
+        assert_eq!(prompts.len(), 3);
+
+        let names: Vec<String> = prompts.prompts().into_iter().map(|p| p.name).collect();
+        assert_eq!(names, ["nms_workflow", "find_a_planet", "hello"]);
+
+        let result = prompts.get("hello", JsonObject::new()).unwrap().await;
+        assert_eq!(text(&result.unwrap(), 0), "Hello");
+        assert!(prompts.get("unknown", JsonObject::new()).is_none());
+        assert!(
+            prompts
+                .get("find_a_planet", JsonObject::new())
+                .unwrap()
+                .await
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,199 @@
+//! Redaction of sensitive values in JSON.
+//!
+//! A [`Redactor`] replaces selected values with [`REDACTED`]. It is used
+//! by the `debug_config` diagnostics tool and by anything else that writes
+//! user-supplied or configuration data somewhere it may be read later,
+//! such as audit logs.
+//!
+//! Rules come in two forms:
+//! - **paths** — dotted paths from the root, where `*` matches any object
+//!   key or array element (`oauth.client_secret`, `sources.*.token`)
+//! - **keys** — object keys redacted wherever they appear, compared
+//!   case-insensitively (`password`, `api_key`)
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// The value that replaces redacted data.
+pub const REDACTED: &str = "***redacted***";
+
+/// Rules for redacting values in a JSON document.
+///
+/// Deserializable so rules can live in a config file:
+///
+/// ```toml
+/// [audit.redact]
+/// paths = ["query"]
+/// keys = ["password", "token"]
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Redactor {
+    /// Dotted paths to redact; `*` matches any key or element.
+    #[serde(default)]
+    pub paths: Vec<String>,
+    /// Object keys to redact at any depth (case-insensitive).
+    #[serde(default)]
+    pub keys: Vec<String>,
+}
+
+impl Redactor {
+    /// Create a redactor with no rules.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The rules `debug_config` applies to server configuration: the
+    /// OAuth client credentials and the API key store's Redis URL, which
+    /// may carry a password.
+    pub fn for_config() -> Self {
+        Self::new()
+            .with_path("oauth.client_id")
+            .with_path("oauth.client_secret")
+            .with_path("api_keys.redis_url")
+    }
+
+    /// Add a dotted path rule.
+    pub fn with_path(mut self, path: impl Into<String>) -> Self {
+        self.paths.push(path.into());
+        self
+    }
+
+    /// Add a key rule.
+    pub fn with_key(mut self, key: impl Into<String>) -> Self {
+        self.keys.push(key.into());
+        self
+    }
+
+    /// Whether the redactor has no rules.
+    pub fn is_empty(&self) -> bool {
+        self.paths.is_empty() && self.keys.is_empty()
+    }
+
+    /// Redact `value` in place. Only values that exist are replaced.
+    pub fn redact(&self, value: &mut Value) {
+        for path in &self.paths {
+            let segments: Vec<&str> = path.split('.').collect();
+            redact_path(value, &segments);
+        }
+        if !self.keys.is_empty() {
+            self.redact_keys(value);
+        }
+    }
+
+    /// Return a redacted copy of `value`.
+    pub fn redacted(&self, value: &Value) -> Value {
+        let mut value = value.clone();
+        self.redact(&mut value);
+        value
+    }
+
+    fn redact_keys(&self, value: &mut Value) {
+        match value {
+            Value::Object(map) => {
+                for (key, child) in map.iter_mut() {
+                    if self.keys.iter().any(|k| k.eq_ignore_ascii_case(key)) {
+                        *child = Value::String(REDACTED.to_string());
+                    } else {
+                        self.redact_keys(child);
+                    }
+                }
+            }
+            Value::Array(items) => items.iter_mut().for_each(|item| self.redact_keys(item)),
+            _ => {}
+        }
+    }
+}
+
+fn redact_path(value: &mut Value, segments: &[&str]) {
+    let Some((head, rest)) = segments.split_first() else {
+        *value = Value::String(REDACTED.to_string());
+        return;
+    };
+
+    match value {
+        Value::Object(map) if *head == "*" => {
+            map.values_mut().for_each(|child| redact_path(child, rest));
+        }
+        Value::Object(map) => {
+            if let Some(child) = map.get_mut(*head) {
+                redact_path(child, rest);
+            }
+        }
+        Value::Array(items) if *head == "*" => {
+            items.iter_mut().for_each(|child| redact_path(child, rest));
+        }
+        Value::Array(items) => {
+            if let Some(child) = head.parse::<usize>().ok().and_then(|i| items.get_mut(i)) {
+                redact_path(child, rest);
+            }
+        }
+        _ => {}
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_for_config_redacts_oauth_credentials() {
+        let redacted = Redactor::for_config().redacted(&json!({
+            "port": 3000,
+            "oauth": { "client_id": "id", "client_secret": "secret", "enabled": true },
+            "api_keys": { "enabled": true, "redis_url": "redis://:hunter2@keys:6379" }
+        }));
+        assert_eq!(redacted["port"], 3000);
+        assert_eq!(redacted["api_keys"]["redis_url"], REDACTED);
+        assert_eq!(redacted["api_keys"]["enabled"], true);
+        assert_eq!(redacted["oauth"]["client_id"], REDACTED);
+        assert_eq!(redacted["oauth"]["client_secret"], REDACTED);
+        assert_eq!(redacted["oauth"]["enabled"], true);
+    }
+
+    #[test]
+    fn test_missing_paths_are_not_added() {
+        let redacted = Redactor::for_config().redacted(&json!({ "oauth": { "enabled": false } }));
+        assert_eq!(redacted, json!({ "oauth": { "enabled": false } }));
+    }
+
+    #[test]
+    fn test_wildcard_and_index_paths() {
+        let redactor = Redactor::new()
+            .with_path("sources.*.token")
+            .with_path("queries.0");
+        let redacted = redactor.redacted(&json!({
+            "sources": { "a": { "token": "t1" }, "b": { "token": "t2", "name": "b" } },
+            "queries": ["first", "second"]
+        }));
+        assert_eq!(redacted["sources"]["a"]["token"], REDACTED);
+        assert_eq!(redacted["sources"]["b"]["token"], REDACTED);
+        assert_eq!(redacted["sources"]["b"]["name"], "b");
+        assert_eq!(redacted["queries"], json!([REDACTED, "second"]));
+    }
+
+    #[test]
+    fn test_keys_match_at_any_depth() {
+        let redactor = Redactor::new().with_key("password");
+        let redacted = redactor.redacted(&json!({
+            "Password": "a",
+            "nested": [{ "password": "b", "user": "c" }]
+        }));
+        assert_eq!(redacted["Password"], REDACTED);
+        assert_eq!(redacted["nested"][0]["password"], REDACTED);
+        assert_eq!(redacted["nested"][0]["user"], "c");
+    }
+
+    #[test]
+    fn test_deserialize_rules() {
+        let redactor: Redactor = serde_json::from_value(json!({ "keys": ["token"] })).unwrap();
+        assert!(redactor.paths.is_empty());
+        assert_eq!(redactor.keys, vec!["token"]);
+        assert!(!redactor.is_empty());
+        assert!(Redactor::new().is_empty());
+    }
+}
@@ -8,6 +8,7 @@
 //! enabling composition of tools from separate sources (content,
 //! search, graph, etc.).
 
+use crate::context::ToolContext;
 use rmcp::model::{CallToolResult, ErrorData, Tool};
 use serde_json::Value;
 use std::future::Future;
@@ -49,6 +50,16 @@ pub trait ToolRegistry: Send + Sync {
     /// Returns `None` if the tool is not recognized by this registry.
     fn call(&self, name: &str, args: Value) -> Option<ToolResult>;
 
+    /// Dispatches a tool call with its request context.
+    ///
+    /// The server calls this instead of [`call`](Self::call). Override it
+    /// in registries with long-running tools to report progress or stop
+    /// early on cancellation; the default ignores the context.
+    fn call_with_context(&self, name: &str, args: Value, ctx: ToolContext) -> Option<ToolResult> {
+        let _ = ctx;
+        self.call(name, args)
+    }
+
     /// Returns the number of registered tools.
     fn tool_count(&self) -> usize {
         self.tools().len()
@@ -107,8 +118,12 @@ impl ToolRegistry for CompositeRegistry {
     }
 
     fn call(&self, name: &str, args: Value) -> Option<ToolResult> {
+        self.call_with_context(name, args, ToolContext::new())
+    }
+
+    fn call_with_context(&self, name: &str, args: Value, ctx: ToolContext) -> Option<ToolResult> {
         for registry in &self.registries {
-            if let Some(result) = registry.call(name, args.clone()) {
+            if let Some(result) = registry.call_with_context(name, args.clone(), ctx.clone()) {
                 return Some(result);
             }
         }
@@ -258,6 +273,60 @@ mod tests {
         assert_eq!(composite.tool_count(), 3);
     }
 
+    /// Reports whether its context was cancelled.
+    struct ContextRegistry;
+
+    impl ToolRegistry for ContextRegistry {
+        fn tools(&self) -> Vec<Tool> {
+            vec![make_tool("slow", "Slow tool")]
+        }
+
+        fn call(&self, name: &str, args: Value) -> Option<ToolResult> {
+            self.call_with_context(name, args, ToolContext::new())
+        }
+
+        fn call_with_context(
+            &self,
+            name: &str,
+            _args: Value,
+            ctx: ToolContext,
+        ) -> Option<ToolResult> {
+            (name == "slow").then(|| -> ToolResult {
+                Box::pin(async move {
+                    let state = if ctx.is_cancelled() {
+                        "cancelled"
+                    } else {
+                        "running"
+                    };
+                    Ok(CallToolResult::success(vec![Content::text(state)]))
+                })
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_composite_registry_forwards_context() {
+        let composite = CompositeRegistry::new()
+            .add(TestRegistry {
+                tool_list: vec![make_tool("a", "A")],
+            })
+            .add(ContextRegistry);
+
+        let token = tokio_util::sync::CancellationToken::new();
+        token.cancel();
+        let ctx = ToolContext::new().with_cancellation(token);
+        let result = composite
+            .call_with_context("slow", json!({}), ctx)
+            .unwrap()
+            .await
+            .unwrap();
+        assert_eq!(result.content[0].as_text().unwrap().text, "cancelled");
+
+        // Plain `call` uses a detached context.
+        let result = composite.call("slow", json!({})).unwrap().await.unwrap();
+        assert_eq!(result.content[0].as_text().unwrap().text, "running");
+    }
+
     #[test]
     fn test_trait_object_safety() {
         fn _assert_object_safe(_: &dyn ToolRegistry) {}
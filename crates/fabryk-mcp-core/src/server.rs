@@ -4,12 +4,15 @@
 //! tool dispatch. The server implements rmcp's `ServerHandler` trait,
 //! delegating tool listing and dispatch to the registry.
 
+use crate::context::{ToolContext, run_cancellable};
 use crate::notifier::Notifier;
+use crate::prompt::PromptRegistry;
 use crate::registry::ToolRegistry;
 use crate::resource::ResourceRegistry;
 use fabryk_core::service::{ServiceHandle, ServiceState};
 use rmcp::model::{
-    CallToolResult, Content, ErrorData, Implementation, ListResourcesResult, ProtocolVersion,
+    CallToolResult, Content, ErrorData, GetPromptRequestParams, GetPromptResult, Implementation,
+    ListPromptsResult, ListResourcesResult, PromptsCapability, ProtocolVersion,
     ReadResourceRequestParams, ReadResourceResult, ServerCapabilities, ServerInfo,
     SubscribeRequestParams, UnsubscribeRequestParams,
 };
@@ -74,6 +77,7 @@ pub struct FabrykMcpServer {
     services: Vec<ServiceHandle>,
     notifier: Notifier,
     resource_registry: Option<Arc<dyn ResourceRegistry>>,
+    prompt_registry: Option<Arc<dyn PromptRegistry>>,
 }
 
 impl FabrykMcpServer {
@@ -85,6 +89,7 @@ impl FabrykMcpServer {
             services: Vec::new(),
             notifier: Notifier::new(),
             resource_registry: None,
+            prompt_registry: None,
         }
     }
 
@@ -173,6 +178,14 @@ impl FabrykMcpServer {
         self
     }
 
+    /// Register a prompt registry for MCP prompt support.
+    ///
+    /// Enables `prompts/list` and `prompts/get` in the server capabilities.
+    pub fn with_prompts<P: PromptRegistry + 'static>(mut self, registry: P) -> Self {
+        self.prompt_registry = Some(Arc::new(registry));
+        self
+    }
+
     /// Get the server configuration.
     pub fn config(&self) -> &ServerConfig {
         &self.config
@@ -290,7 +303,7 @@ impl FabrykMcpServer {
 
 impl ServerHandler for FabrykMcpServer {
     fn get_info(&self) -> ServerInfo {
-        let capabilities = if self.resource_registry.is_some() {
+        let mut capabilities = if self.resource_registry.is_some() {
             ServerCapabilities::builder()
                 .enable_tools()
                 .enable_logging()
@@ -304,6 +317,9 @@ impl ServerHandler for FabrykMcpServer {
                 .enable_logging()
                 .build()
         };
+        if self.prompt_registry.is_some() {
+            capabilities.prompts = Some(PromptsCapability::default());
+        }
 
         let mut info = ServerInfo::new(capabilities)
             .with_protocol_version(ProtocolVersion::LATEST)
@@ -350,17 +366,25 @@ impl ServerHandler for FabrykMcpServer {
     fn call_tool(
         &self,
         request: rmcp::model::CallToolRequestParams,
-        _context: RequestContext<RoleServer>,
+        context: RequestContext<RoleServer>,
     ) -> impl std::future::Future<Output = Result<CallToolResult, ErrorData>> + Send + '_ {
         let name = request.name.to_string();
         let args = request
             .arguments
             .map(serde_json::Value::Object)
             .unwrap_or(serde_json::Value::Null);
+        let cancellation = context.ct.clone();
+        let ctx = ToolContext::for_request(
+            self.notifier.clone(),
+            context.peer,
+            context.meta.get_progress_token(),
+            context.ct,
+            context.extensions,
+        );
 
         async move {
-            match self.registry.call(&name, args) {
-                Some(future) => future.await,
+            match self.registry.call_with_context(&name, args, ctx) {
+                Some(future) => run_cancellable(&name, future, &cancellation).await,
                 None => Ok(CallToolResult::error(vec![Content::text(format!(
                     "Unknown tool: {name}"
                 ))])),
@@ -408,6 +432,43 @@ impl ServerHandler for FabrykMcpServer {
         }
     }
 
+    #[allow(clippy::manual_async_fn)]
+    fn list_prompts(
+        &self,
+        _request: Option<rmcp::model::PaginatedRequestParams>,
+        _context: RequestContext<RoleServer>,
+    ) -> impl std::future::Future<Output = Result<ListPromptsResult, ErrorData>> + Send + '_ {
+        async move {
+            match &self.prompt_registry {
+                Some(registry) => Ok(ListPromptsResult::with_all_items(registry.prompts())),
+                None => Ok(ListPromptsResult::default()),
+            }
+        }
+    }
+
+    #[allow(clippy::manual_async_fn)]
+    fn get_prompt(
+        &self,
+        request: GetPromptRequestParams,
+        _context: RequestContext<RoleServer>,
+    ) -> impl std::future::Future<Output = Result<GetPromptResult, ErrorData>> + Send + '_ {
+        async move {
+            let registry = self
+                .prompt_registry
+                .as_ref()
+                .ok_or_else(|| ErrorData::invalid_params("Prompts not enabled", None))?;
+
+            let name = &request.name;
+            match registry.get(name, request.arguments.unwrap_or_default()) {
+                Some(future) => future.await,
+                None => Err(ErrorData::invalid_params(
+                    format!("Unknown prompt: {name}"),
+                    None,
+                )),
+            }
+        }
+    }
+
     #[allow(clippy::manual_async_fn)]
     fn subscribe(
         &self,
@@ -720,6 +781,21 @@ mod tests {
         assert!(info.capabilities.resources.is_some());
     }
 
+    #[test]
+    fn test_server_get_info_with_prompts() {
+        use crate::prompt::{PromptTemplate, TemplatePrompts};
+
+        let server = FabrykMcpServer::new(MockRegistry).with_name("test");
+        assert!(server.get_info().capabilities.prompts.is_none());
+
+        let server = server
+            .with_prompts(TemplatePrompts::new().add(PromptTemplate::new("hello").user("Hello")));
+        let info = server.get_info();
+        assert!(info.capabilities.prompts.is_some());
+        assert!(info.capabilities.tools.is_some());
+        assert!(info.capabilities.resources.is_none());
+    }
+
     #[tokio::test]
     async fn test_server_wait_ready_timeout() {
         let svc = ServiceHandle::new("slow");
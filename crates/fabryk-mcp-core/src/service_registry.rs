@@ -14,6 +14,7 @@
 //! let gated = ServiceAwareRegistry::new(fts_tools, vec![fts_svc]);
 //! ```
 
+use crate::context::ToolContext;
 use crate::registry::{ToolRegistry, ToolResult};
 use fabryk_core::service::ServiceHandle;
 use rmcp::model::{CallToolResult, Content, Tool};
@@ -49,6 +50,10 @@ impl ToolRegistry for ServiceAwareRegistry {
     }
 
     fn call(&self, name: &str, args: Value) -> Option<ToolResult> {
+        self.call_with_context(name, args, ToolContext::new())
+    }
+
+    fn call_with_context(&self, name: &str, args: Value, ctx: ToolContext) -> Option<ToolResult> {
         // Check if tool exists in inner registry first
         if !self.inner.has_tool(name) {
             return None;
@@ -74,7 +79,7 @@ impl ToolRegistry for ServiceAwareRegistry {
         }
 
         // All services ready — delegate
-        self.inner.call(name, args)
+        self.inner.call_with_context(name, args, ctx)
     }
 }
 
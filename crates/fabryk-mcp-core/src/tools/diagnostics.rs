@@ -6,6 +6,7 @@
 //! - `debug_config` — show server configuration (with optional redaction)
 //! - `service_status` — report status of all background services
 
+use crate::redact::Redactor;
 use crate::registry::{ToolRegistry, ToolResult};
 use fabryk_core::service::ServiceHandle;
 use rmcp::model::{CallToolResult, Content, ErrorData, Tool};
@@ -31,6 +32,22 @@ fn serialize_response<T: Serialize>(value: &T) -> Result<CallToolResult, ErrorDa
     Ok(CallToolResult::success(vec![Content::text(json)]))
 }
 
+// ---------------------------------------------------------------------------
+// StatusSection
+// ---------------------------------------------------------------------------
+
+/// An extra section in the `service_status` output.
+///
+/// Implement this for components that aren't background services but
+/// whose state operators want to see, such as rate limiters.
+pub trait StatusSection: Send + Sync {
+    /// Key of the section in the `service_status` response.
+    fn name(&self) -> &str;
+
+    /// Current state of the component.
+    fn status(&self) -> Value;
+}
+
 // ---------------------------------------------------------------------------
 // DiagnosticTools
 // ---------------------------------------------------------------------------
@@ -43,8 +60,9 @@ fn serialize_response<T: Serialize>(value: &T) -> Result<CallToolResult, ErrorDa
 /// # Redaction
 ///
 /// Fields named `client_id` or `client_secret` under an `oauth` key are
-/// automatically redacted in the `debug_config` output. For custom
-/// redaction, pre-process the config before passing it.
+/// redacted in the `debug_config` output by default
+/// ([`Redactor::for_config`]). Use [`DiagnosticTools::with_redactor`] to
+/// change the rules.
 ///
 /// # Example
 ///
@@ -59,6 +77,8 @@ fn serialize_response<T: Serialize>(value: &T) -> Result<CallToolResult, ErrorDa
 pub struct DiagnosticTools<C: Serialize + Send + Sync> {
     config: Arc<C>,
     services: Vec<ServiceHandle>,
+    sections: Vec<Arc<dyn StatusSection>>,
+    redactor: Arc<Redactor>,
 }
 
 impl<C: Serialize + Send + Sync> DiagnosticTools<C> {
@@ -67,6 +87,8 @@ impl<C: Serialize + Send + Sync> DiagnosticTools<C> {
         Self {
             config,
             services: Vec::new(),
+            sections: Vec::new(),
+            redactor: Arc::new(Redactor::for_config()),
         }
     }
 
@@ -75,6 +97,18 @@ impl<C: Serialize + Send + Sync> DiagnosticTools<C> {
         self.services = services;
         self
     }
+
+    /// Add an extra section to the `service_status` output.
+    pub fn with_status_section(mut self, section: Arc<dyn StatusSection>) -> Self {
+        self.sections.push(section);
+        self
+    }
+
+    /// Replace the rules used to redact the `debug_config` output.
+    pub fn with_redactor(mut self, redactor: Redactor) -> Self {
+        self.redactor = Arc::new(redactor);
+        self
+    }
 }
 
 impl<C: Serialize + Send + Sync + 'static> ToolRegistry for DiagnosticTools<C> {
@@ -95,29 +129,17 @@ impl<C: Serialize + Send + Sync + 'static> ToolRegistry for DiagnosticTools<C> {
         match name {
             "debug_config" => {
                 let config = self.config.clone();
+                let redactor = self.redactor.clone();
                 Some(Box::pin(async move {
                     let mut config_json = serde_json::to_value(&*config)
                         .map_err(|e| ErrorData::internal_error(e.to_string(), None))?;
-
-                    // Redact sensitive fields under oauth
-                    if let Some(oauth) = config_json.get_mut("oauth")
-                        && let Some(obj) = oauth.as_object_mut()
-                    {
-                        for key in &["client_id", "client_secret"] {
-                            if obj.contains_key(*key) {
-                                obj.insert(
-                                    key.to_string(),
-                                    Value::String("***redacted***".to_string()),
-                                );
-                            }
-                        }
-                    }
-
+                    redactor.redact(&mut config_json);
                     serialize_response(&config_json)
                 }))
             }
             "service_status" => {
                 let services = self.services.clone();
+                let sections = self.sections.clone();
                 Some(Box::pin(async move {
                     let statuses: Vec<_> = services
                         .iter()
@@ -130,10 +152,13 @@ impl<C: Serialize + Send + Sync + 'static> ToolRegistry for DiagnosticTools<C> {
                         })
                         .collect();
                     let all_ready = services.iter().all(|s| s.state().is_ready());
-                    let response = serde_json::json!({
+                    let mut response = serde_json::json!({
                         "status": if all_ready { "ready" } else { "starting" },
                         "services": statuses,
                     });
+                    for section in &sections {
+                        response[section.name()] = section.status();
+                    }
                     serialize_response(&response)
                 }))
             }
@@ -223,6 +248,20 @@ mod tests {
         assert!(!text.contains("secret-secret"));
     }
 
+    #[tokio::test]
+    async fn test_debug_config_custom_redactor() {
+        let tools = test_tools().with_redactor(Redactor::new().with_key("name"));
+        let result = tools
+            .call("debug_config", Value::Null)
+            .unwrap()
+            .await
+            .unwrap();
+
+        let text = format!("{result:?}");
+        assert!(!text.contains("test-server"));
+        assert!(text.contains("secret-id"));
+    }
+
     #[tokio::test]
     async fn test_service_status_no_services() {
         let tools = test_tools();
@@ -254,6 +293,35 @@ mod tests {
         assert!(text.contains("test-svc"));
     }
 
+    struct QuotaSection;
+
+    impl StatusSection for QuotaSection {
+        fn name(&self) -> &str {
+            "rate_limits"
+        }
+
+        fn status(&self) -> Value {
+            serde_json::json!({"backend": "memory", "rejected": 3})
+        }
+    }
+
+    #[tokio::test]
+    async fn test_service_status_with_section() {
+        let tools = test_tools().with_status_section(Arc::new(QuotaSection));
+
+        let result = tools
+            .call("service_status", Value::Null)
+            .unwrap()
+            .await
+            .unwrap();
+
+        let text = result.content[0].as_text().unwrap().text.clone();
+        let json: Value = serde_json::from_str(&text).unwrap();
+        assert_eq!(json["status"], "ready");
+        assert_eq!(json["rate_limits"]["backend"], "memory");
+        assert_eq!(json["rate_limits"]["rejected"], 3);
+    }
+
     #[tokio::test]
     async fn test_service_status_starting() {
         let svc = ServiceHandle::new("building-svc");
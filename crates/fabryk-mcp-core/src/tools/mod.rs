@@ -6,5 +6,5 @@
 pub mod diagnostics;
 pub mod health;
 
-pub use diagnostics::DiagnosticTools;
+pub use diagnostics::{DiagnosticTools, StatusSection};
 pub use health::{HealthResponse, HealthTools, handle_health};
@@ -3,11 +3,13 @@
 //! Provides `FtsTools` that implements `ToolRegistry` by delegating
 //! search queries to a `fabryk_fts::SearchBackend`.
 
+use fabryk_mcp_core::args::{parse_args, typed_tool};
 use fabryk_mcp_core::error::McpErrorExt;
 use fabryk_mcp_core::model::{CallToolResult, Content, ErrorData, Tool};
 use fabryk_mcp_core::registry::{ToolRegistry, ToolResult};
 
 use fabryk_fts::{SearchBackend, SearchParams};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
@@ -44,7 +46,7 @@ fn serialize_response<T: serde::Serialize>(value: &T) -> Result<CallToolResult,
 // ---------------------------------------------------------------------------
 
 /// Arguments for the search tool.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, JsonSchema)]
 pub struct SearchArgs {
     /// Search query string.
     pub query: String,
@@ -53,6 +55,7 @@ pub struct SearchArgs {
     /// Optional source filter.
     pub source: Option<String>,
     /// Maximum results to return (default 10).
+    #[schemars(range(min = 1))]
     pub limit: Option<usize>,
     /// Optional content type filter.
     pub content_type: Option<String>,
@@ -200,35 +203,9 @@ impl FtsTools {
 impl ToolRegistry for FtsTools {
     fn tools(&self) -> Vec<Tool> {
         vec![
-            make_tool(
-                &self.tool_name(Self::SLOT_SEARCH),
-                &self.tool_description(Self::SLOT_SEARCH, "Full-text search across all content"),
-                serde_json::json!({
-                    "type": "object",
-                    "properties": {
-                        "query": {
-                            "type": "string",
-                            "description": "Search query"
-                        },
-                        "category": {
-                            "type": "string",
-                            "description": "Filter by category"
-                        },
-                        "source": {
-                            "type": "string",
-                            "description": "Filter by source"
-                        },
-                        "limit": {
-                            "type": "integer",
-                            "description": "Maximum results (default 10)"
-                        },
-                        "content_type": {
-                            "type": "string",
-                            "description": "Filter by content type"
-                        }
-                    },
-                    "required": ["query"]
-                }),
+            typed_tool::<SearchArgs>(
+                self.tool_name(Self::SLOT_SEARCH),
+                self.tool_description(Self::SLOT_SEARCH, "Full-text search across all content"),
             ),
             make_tool(
                 &self.tool_name(Self::SLOT_STATUS),
@@ -245,9 +222,9 @@ impl ToolRegistry for FtsTools {
         let backend = Arc::clone(&self.backend);
 
         if name == self.tool_name(Self::SLOT_SEARCH) {
+            let tool = name.to_string();
             return Some(Box::pin(async move {
-                let args: SearchArgs = serde_json::from_value(args)
-                    .map_err(|e| ErrorData::invalid_params(e.to_string(), None))?;
+                let args: SearchArgs = parse_args(&tool, args)?;
 
                 let start = Instant::now();
 
@@ -453,6 +430,24 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_fts_search_schema_derived_from_args() {
+        let tools = FtsTools::new(MockSearchBackend::new());
+        let search = &tools.tools()[0];
+        assert_eq!(search.input_schema["required"], serde_json::json!(["query"]));
+        assert_eq!(
+            search.input_schema["properties"]["query"]["description"],
+            "Search query string."
+        );
+
+        let issues = fabryk_mcp_core::validate_arguments(
+            &search.input_schema,
+            &serde_json::json!({"query": "test", "limit": 0}),
+        );
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].path, "limit");
+    }
+
     #[tokio::test]
     async fn test_fts_search_status() {
         let tools = FtsTools::new(MockSearchBackend::new());
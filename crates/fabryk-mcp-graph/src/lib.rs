@@ -12,6 +12,7 @@
 //! - `graph_validate` — structure validation
 //! - `graph_centrality` — most important nodes
 //! - `graph_bridges` — gateway nodes
+//! - `graph_query` — pattern-matching queries with cost limits
 //!
 //! # Example
 //!
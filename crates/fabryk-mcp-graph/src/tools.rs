@@ -3,17 +3,22 @@
 //! Provides `GraphTools` that implements `ToolRegistry` by delegating
 //! queries to `fabryk_graph` algorithms.
 
+use fabryk_mcp_core::args::parse_args;
+use fabryk_mcp_core::context::ToolContext;
 use fabryk_mcp_core::error::McpErrorExt;
 use fabryk_mcp_core::model::{CallToolResult, Content, ErrorData, Tool};
 use fabryk_mcp_core::registry::{ToolRegistry, ToolResult};
 
 use fabryk_graph::{
-    EdgeInfo, GraphData, NeighborInfo, NodeSummary, PathStep, Relationship, calculate_centrality,
-    compute_stats, find_bridges, neighborhood, prerequisites_sorted, shortest_path, validate_graph,
+    EdgeInfo, GraphBuilder, GraphData, GraphExtractor, GraphMetadata, GraphQuery, NeighborInfo,
+    NodeSummary, PathStep, QueryLimits, Relationship, UpdateStats, calculate_centrality,
+    compute_stats, execute_query, find_bridges, neighborhood, prerequisites_sorted, shortest_path,
+    validate_graph,
 };
 use serde::Deserialize;
 use serde_json::{Value, json};
 use std::collections::HashMap;
+use std::path::Path;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
@@ -103,7 +108,7 @@ pub struct NeighborhoodArgs {
 
 /// MCP tools for graph queries.
 ///
-/// Generates eight tools:
+/// Generates nine tools:
 /// - `graph_related` — find related nodes
 /// - `graph_path` — shortest path between nodes
 /// - `graph_prerequisites` — learning order prerequisites
@@ -112,6 +117,7 @@ pub struct NeighborhoodArgs {
 /// - `graph_validate` — structure validation
 /// - `graph_centrality` — most central/important nodes
 /// - `graph_bridges` — bridge nodes connecting different areas
+/// - `graph_query` — pattern-matching queries (see [`fabryk_graph::pattern`])
 ///
 /// # Example
 ///
@@ -126,6 +132,7 @@ pub struct GraphTools {
     graph: Arc<RwLock<GraphData>>,
     custom_names: HashMap<String, String>,
     custom_descriptions: HashMap<String, String>,
+    query_limits: QueryLimits,
 }
 
 impl GraphTools {
@@ -145,6 +152,8 @@ impl GraphTools {
     pub const SLOT_CENTRALITY: &str = "graph_centrality";
     /// Slot key for the bridges tool.
     pub const SLOT_BRIDGES: &str = "graph_bridges";
+    /// Slot key for the pattern query tool.
+    pub const SLOT_QUERY: &str = "graph_query";
 
     /// Create new graph tools with owned graph data.
     pub fn new(graph: GraphData) -> Self {
@@ -152,6 +161,7 @@ impl GraphTools {
             graph: Arc::new(RwLock::new(graph)),
             custom_names: HashMap::new(),
             custom_descriptions: HashMap::new(),
+            query_limits: QueryLimits::default(),
         }
     }
 
@@ -161,6 +171,7 @@ impl GraphTools {
             graph,
             custom_names: HashMap::new(),
             custom_descriptions: HashMap::new(),
+            query_limits: QueryLimits::default(),
         }
     }
 
@@ -176,12 +187,35 @@ impl GraphTools {
         self
     }
 
+    /// Override the cost limits applied to `graph_query`.
+    pub fn with_query_limits(mut self, limits: QueryLimits) -> Self {
+        self.query_limits = limits;
+        self
+    }
+
     /// Update the graph data (e.g., after rebuild).
     pub async fn update_graph(&self, graph: GraphData) {
         let mut lock = self.graph.write().await;
         *lock = graph;
     }
 
+    /// Apply a change to one content file to the live graph.
+    ///
+    /// Delegates to [`GraphBuilder::apply_file_change`] under the graph's
+    /// write lock, so queries never see a half-applied change. `metadata`
+    /// is the file index returned by `GraphBuilder::build_with_metadata`.
+    pub async fn apply_file_change<E: GraphExtractor>(
+        &self,
+        builder: &GraphBuilder<E>,
+        metadata: &mut GraphMetadata,
+        file_path: impl AsRef<Path>,
+    ) -> fabryk_core::Result<UpdateStats> {
+        let mut lock = self.graph.write().await;
+        builder
+            .apply_file_change(&mut lock, metadata, file_path)
+            .await
+    }
+
     fn tool_name(&self, slot: &str) -> String {
         self.custom_names
             .get(slot)
@@ -330,16 +364,29 @@ impl ToolRegistry for GraphTools {
                     }
                 }),
             ),
+            make_tool(
+                &self.tool_name(Self::SLOT_QUERY),
+                &self.tool_description(
+                    Self::SLOT_QUERY,
+                    "Match path patterns: start node filters followed by hops over \
+                     relationships, with metadata filters and variable-length hops",
+                ),
+                query_schema(&self.query_limits),
+            ),
         ]
     }
 
     fn call(&self, name: &str, args: Value) -> Option<ToolResult> {
+        self.call_with_context(name, args, ToolContext::new())
+    }
+
+    fn call_with_context(&self, name: &str, args: Value, ctx: ToolContext) -> Option<ToolResult> {
         let graph = Arc::clone(&self.graph);
 
         if name == self.tool_name(Self::SLOT_RELATED) {
+            let tool = name.to_string();
             return Some(Box::pin(async move {
-                let args: RelatedArgs = serde_json::from_value(args)
-                    .map_err(|e| ErrorData::invalid_params(e.to_string(), None))?;
+                let args: RelatedArgs = parse_args(&tool, args)?;
                 let graph = graph.read().await;
 
                 let rel_filter = args
@@ -368,9 +415,9 @@ impl ToolRegistry for GraphTools {
         }
 
         if name == self.tool_name(Self::SLOT_PATH) {
+            let tool = name.to_string();
             return Some(Box::pin(async move {
-                let args: PathArgs = serde_json::from_value(args)
-                    .map_err(|e| ErrorData::invalid_params(e.to_string(), None))?;
+                let args: PathArgs = parse_args(&tool, args)?;
                 let graph = graph.read().await;
 
                 let result =
@@ -411,9 +458,9 @@ impl ToolRegistry for GraphTools {
         }
 
         if name == self.tool_name(Self::SLOT_PREREQUISITES) {
+            let tool = name.to_string();
             return Some(Box::pin(async move {
-                let args: PrerequisitesArgs = serde_json::from_value(args)
-                    .map_err(|e| ErrorData::invalid_params(e.to_string(), None))?;
+                let args: PrerequisitesArgs = parse_args(&tool, args)?;
                 let graph = graph.read().await;
 
                 let result =
@@ -434,9 +481,9 @@ impl ToolRegistry for GraphTools {
         }
 
         if name == self.tool_name(Self::SLOT_NEIGHBORHOOD) {
+            let tool = name.to_string();
             return Some(Box::pin(async move {
-                let args: NeighborhoodArgs = serde_json::from_value(args)
-                    .map_err(|e| ErrorData::invalid_params(e.to_string(), None))?;
+                let args: NeighborhoodArgs = parse_args(&tool, args)?;
                 let graph = graph.read().await;
 
                 let radius = args.radius.unwrap_or(1);
@@ -482,14 +529,20 @@ impl ToolRegistry for GraphTools {
         }
 
         if name == self.tool_name(Self::SLOT_VALIDATE) {
+            let tool = name.to_string();
             return Some(Box::pin(async move {
                 let graph = graph.read().await;
+                ctx.check_cancelled(&tool)?;
+                ctx.report_progress(0.0, Some(1.0), Some("Validating graph"))
+                    .await;
                 let result = validate_graph(&graph);
+                ctx.report_progress(1.0, Some(1.0), None).await;
                 serialize_response(&result)
             }));
         }
 
         if name == self.tool_name(Self::SLOT_CENTRALITY) {
+            let tool = name.to_string();
             return Some(Box::pin(async move {
                 let limit = args
                     .get("limit")
@@ -498,7 +551,11 @@ impl ToolRegistry for GraphTools {
                     .unwrap_or(10);
 
                 let graph = graph.read().await;
+                ctx.check_cancelled(&tool)?;
+                ctx.report_progress(0.0, Some(1.0), Some("Calculating centrality"))
+                    .await;
                 let scores = calculate_centrality(&graph);
+                ctx.report_progress(1.0, Some(1.0), None).await;
 
                 let top: Vec<_> = scores.into_iter().take(limit).collect();
                 serialize_response(&top)
@@ -521,10 +578,119 @@ impl ToolRegistry for GraphTools {
             }));
         }
 
+        if name == self.tool_name(Self::SLOT_QUERY) {
+            let limits = self.query_limits.clone();
+            let tool = name.to_string();
+            return Some(Box::pin(async move {
+                let query: GraphQuery = parse_args(&tool, args)?;
+                let graph = graph.read().await;
+
+                let result =
+                    execute_query(&graph, &query, &limits).map_err(|e| e.to_mcp_error())?;
+
+                let matches: Vec<Value> = result
+                    .matches
+                    .iter()
+                    .map(|m| {
+                        let nodes: Vec<NodeSummary> =
+                            m.nodes.iter().map(NodeSummary::from).collect();
+                        let edges: Vec<EdgeInfo> = m.edges.iter().map(EdgeInfo::from).collect();
+                        json!({ "nodes": nodes, "edges": edges })
+                    })
+                    .collect();
+
+                let response = json!({
+                    "matches": matches,
+                    "count": matches.len(),
+                    "truncated": result.truncated,
+                    "budget_exhausted": result.budget_exhausted,
+                    "expansions": result.expansions
+                });
+                serialize_response(&response)
+            }));
+        }
+
         None
     }
 }
 
+fn query_schema(limits: &QueryLimits) -> Value {
+    let node_pattern = json!({
+        "type": "object",
+        "description": "Node constraints; all given fields must match",
+        "properties": {
+            "id": { "type": "string" },
+            "ids": { "type": "array", "items": { "type": "string" } },
+            "category": { "type": "string" },
+            "source_id": { "type": "string" },
+            "node_type": { "type": "string", "description": "domain, user_query or a custom type" },
+            "is_canonical": { "type": "boolean" },
+            "title_contains": { "type": "string", "description": "Case-insensitive substring" },
+            "metadata": {
+                "type": "array",
+                "items": {
+                    "type": "object",
+                    "properties": {
+                        "key": { "type": "string" },
+                        "op": {
+                            "type": "string",
+                            "enum": ["eq", "ne", "exists", "in", "contains", "gt", "gte", "lt", "lte"]
+                        },
+                        "value": {}
+                    },
+                    "required": ["key"]
+                }
+            }
+        }
+    });
+
+    json!({
+        "type": "object",
+        "properties": {
+            "start": node_pattern,
+            "hops": {
+                "type": "array",
+                "description": format!("Hops to follow from the start node (at most {})", limits.max_hops),
+                "items": {
+                    "type": "object",
+                    "properties": {
+                        "edge": {
+                            "type": "object",
+                            "properties": {
+                                "relationships": {
+                                    "type": "array",
+                                    "items": { "type": "string" },
+                                    "description": "Allowed relationship types (empty allows all)"
+                                },
+                                "direction": {
+                                    "type": "string",
+                                    "enum": ["outgoing", "incoming", "both"]
+                                },
+                                "min_hops": { "type": "integer", "description": "Default 1" },
+                                "max_hops": {
+                                    "type": "integer",
+                                    "description": format!("Default 1; total across hops at most {}", limits.max_depth)
+                                },
+                                "min_weight": { "type": "number" }
+                            }
+                        },
+                        "node": node_pattern
+                    }
+                }
+            },
+            "return": {
+                "type": "string",
+                "enum": ["paths", "start", "end"],
+                "description": "Full paths (default) or distinct start/end nodes"
+            },
+            "limit": {
+                "type": "integer",
+                "description": format!("Maximum matches (default 50, at most {})", limits.max_results)
+            }
+        }
+    })
+}
+
 // ============================================================================
 // Tests
 // ============================================================================
@@ -549,12 +715,43 @@ mod tests {
         graph
     }
 
+    // -- Live update tests ---------------------------------------------------
+
+    #[tokio::test]
+    async fn test_apply_file_change_updates_live_graph() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("a.md"),
+            "---\ntitle: A\nprerequisites:\n  - b\n---\n",
+        )
+        .unwrap();
+
+        let builder = GraphBuilder::new(fabryk_graph::MockExtractor).with_content_path(dir.path());
+        let (graph, mut metadata, _) = GraphBuilder::new(fabryk_graph::MockExtractor)
+            .with_content_path(dir.path())
+            .build_with_metadata()
+            .await
+            .unwrap();
+        let tools = GraphTools::new(graph);
+
+        std::fs::write(dir.path().join("b.md"), "---\ntitle: B\n---\n").unwrap();
+        let stats = tools
+            .apply_file_change(&builder, &mut metadata, "b.md")
+            .await
+            .unwrap();
+        assert_eq!(stats.files_added, 1);
+
+        let graph = tools.graph.read().await;
+        assert_eq!(graph.node_count(), 2);
+        assert_eq!(graph.edge_count(), 1);
+    }
+
     // -- Tool creation tests ------------------------------------------------
 
     #[test]
     fn test_graph_tools_creation() {
         let tools = GraphTools::new(GraphData::new());
-        assert_eq!(tools.tool_count(), 8);
+        assert_eq!(tools.tool_count(), 9);
     }
 
     #[test]
@@ -570,6 +767,7 @@ mod tests {
         assert!(names.contains(&"graph_validate"));
         assert!(names.contains(&"graph_centrality"));
         assert!(names.contains(&"graph_bridges"));
+        assert!(names.contains(&"graph_query"));
     }
 
     #[test]
@@ -616,6 +814,19 @@ mod tests {
         assert_eq!(result.is_error, Some(false));
     }
 
+    #[tokio::test]
+    async fn test_graph_validate_cancelled() {
+        let tools = GraphTools::new(make_test_graph());
+        let token = fabryk_mcp_core::CancellationToken::new();
+        token.cancel();
+        let ctx = ToolContext::new().with_cancellation(token);
+        let future = tools
+            .call_with_context("graph_validate", json!({}), ctx)
+            .unwrap();
+        let err = future.await.unwrap_err();
+        assert_eq!(err.message, "Tool 'graph_validate' was cancelled");
+    }
+
     // -- graph_related tests ------------------------------------------------
 
     #[tokio::test]
@@ -725,6 +936,54 @@ mod tests {
         assert_eq!(result.is_error, Some(false));
     }
 
+    // -- graph_query tests --------------------------------------------------
+
+    #[tokio::test]
+    async fn test_graph_query_pattern() {
+        let tools = GraphTools::new(make_test_graph());
+        let future = tools
+            .call(
+                "graph_query",
+                json!({
+                    "start": {"category": "alpha"},
+                    "hops": [{
+                        "edge": {"relationships": ["prerequisite"], "max_hops": 2},
+                        "node": {"category": "beta"}
+                    }]
+                }),
+            )
+            .unwrap();
+        let result = future.await.unwrap();
+        let text = result.content[0].as_text().unwrap().text.clone();
+        let response: Value = serde_json::from_str(&text).unwrap();
+        assert_eq!(response["count"], 1);
+        assert_eq!(response["matches"][0]["nodes"][0]["id"], "node-a");
+        assert_eq!(response["matches"][0]["nodes"][1]["id"], "node-b");
+        assert_eq!(
+            response["matches"][0]["edges"][0]["relationship"],
+            "prerequisite"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_graph_query_rejects_costly_query() {
+        let tools = GraphTools::new(make_test_graph())
+            .with_query_limits(QueryLimits::default().with_max_depth(2));
+        let future = tools
+            .call("graph_query", json!({"hops": [{"edge": {"max_hops": 3}}]}))
+            .unwrap();
+        assert!(future.await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_graph_query_invalid_args() {
+        let tools = GraphTools::new(make_test_graph());
+        let future = tools
+            .call("graph_query", json!({"return": "everything"}))
+            .unwrap();
+        assert!(future.await.is_err());
+    }
+
     // -- Shared state tests -------------------------------------------------
 
     #[tokio::test]
@@ -0,0 +1,206 @@
+//! Rate limit configuration.
+
+use serde::{Deserialize, Serialize};
+
+/// Name of the category used for tools that match no configured category.
+pub const DEFAULT_CATEGORY: &str = "default";
+
+/// A token bucket: `burst` calls at once, refilled at `per_minute`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BucketConfig {
+    /// Maximum number of calls that can be made back to back.
+    pub burst: u32,
+    /// Tokens added per minute.
+    pub per_minute: u32,
+}
+
+impl BucketConfig {
+    /// A bucket refilled at `per_minute` with a burst of the same size.
+    pub fn per_minute(per_minute: u32) -> Self {
+        Self {
+            burst: per_minute,
+            per_minute,
+        }
+    }
+
+    /// Set the burst size.
+    pub fn with_burst(mut self, burst: u32) -> Self {
+        self.burst = burst;
+        self
+    }
+
+    /// Seconds needed to refill an empty bucket.
+    pub fn refill_secs(&self) -> u64 {
+        if self.per_minute == 0 {
+            return u64::MAX;
+        }
+        (u64::from(self.burst) * 60)
+            .div_ceil(u64::from(self.per_minute))
+            .max(1)
+    }
+}
+
+/// Limits for a group of tools.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CategoryConfig {
+    /// Tool names in this category. A trailing `*` matches a prefix,
+    /// e.g. `graph_*`.
+    pub tools: Vec<String>,
+    /// The bucket each caller gets for this category.
+    #[serde(flatten)]
+    pub bucket: BucketConfig,
+}
+
+impl CategoryConfig {
+    fn matches(&self, tool: &str) -> bool {
+        self.tools
+            .iter()
+            .any(|pattern| match pattern.strip_suffix('*') {
+                Some(prefix) => tool.starts_with(prefix),
+                None => pattern == tool,
+            })
+    }
+}
+
+/// Configuration for [`RateLimiter`](crate::RateLimiter).
+///
+/// Each caller gets one bucket per category. Tools are assigned to the
+/// first category that lists them; everything else shares the `default`
+/// bucket.
+///
+/// ```toml
+/// [rate_limit]
+/// enabled = true
+/// trusted_proxies = 1
+/// default = { burst = 60, per_minute = 60 }
+///
+/// [rate_limit.categories.semantic]
+/// tools = ["semantic_search", "hybrid_*"]
+/// burst = 5
+/// per_minute = 10
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RateLimitConfig {
+    /// Whether rate limiting is enabled. When false, all calls pass.
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// Bucket for tools outside every category.
+    #[serde(default = "default_bucket")]
+    pub default: BucketConfig,
+    /// Named tool categories, checked in name order.
+    #[serde(default)]
+    pub categories: std::collections::BTreeMap<String, CategoryConfig>,
+    /// Number of reverse proxies in front of the server that append to
+    /// `X-Forwarded-For` (e.g. 1 on Cloud Run). Unauthenticated callers
+    /// are keyed by the address the outermost of them saw; with 0 the
+    /// header is ignored and the socket address is used.
+    #[serde(default)]
+    pub trusted_proxies: usize,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_bucket() -> BucketConfig {
+    BucketConfig::per_minute(120)
+}
+
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_enabled(),
+            default: default_bucket(),
+            categories: Default::default(),
+            trusted_proxies: 0,
+        }
+    }
+}
+
+impl RateLimitConfig {
+    /// Set the default bucket.
+    pub fn with_default(mut self, bucket: BucketConfig) -> Self {
+        self.default = bucket;
+        self
+    }
+
+    /// Set the number of trusted reverse proxies.
+    pub fn with_trusted_proxies(mut self, count: usize) -> Self {
+        self.trusted_proxies = count;
+        self
+    }
+
+    /// Add a tool category.
+    pub fn with_category(
+        mut self,
+        name: impl Into<String>,
+        tools: impl IntoIterator<Item = impl Into<String>>,
+        bucket: BucketConfig,
+    ) -> Self {
+        self.categories.insert(
+            name.into(),
+            CategoryConfig {
+                tools: tools.into_iter().map(Into::into).collect(),
+                bucket,
+            },
+        );
+        self
+    }
+
+    /// The category name and bucket that apply to a tool.
+    pub fn category_for(&self, tool: &str) -> (&str, &BucketConfig) {
+        self.categories
+            .iter()
+            .find(|(_, category)| category.matches(tool))
+            .map(|(name, category)| (name.as_str(), &category.bucket))
+            .unwrap_or((DEFAULT_CATEGORY, &self.default))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_category_for() {
+        let config = RateLimitConfig::default()
+            .with_category(
+                "semantic",
+                ["semantic_search"],
+                BucketConfig::per_minute(10),
+            )
+            .with_category("graph", ["graph_*"], BucketConfig::per_minute(30));
+
+        assert_eq!(config.category_for("semantic_search").0, "semantic");
+        assert_eq!(config.category_for("graph_validate").0, "graph");
+        assert_eq!(config.category_for("graph_centrality").1.per_minute, 30);
+        assert_eq!(config.category_for("search").0, DEFAULT_CATEGORY);
+        assert_eq!(config.category_for("search").1.per_minute, 120);
+    }
+
+    #[test]
+    fn test_refill_secs() {
+        assert_eq!(BucketConfig::per_minute(60).refill_secs(), 60);
+        assert_eq!(BucketConfig::per_minute(10).with_burst(5).refill_secs(), 30);
+        assert_eq!(BucketConfig::per_minute(600).with_burst(1).refill_secs(), 1);
+        assert_eq!(BucketConfig::per_minute(0).refill_secs(), u64::MAX);
+    }
+
+    #[test]
+    fn test_config_deserialize() {
+        let config: RateLimitConfig = serde_json::from_value(serde_json::json!({
+            "default": { "burst": 20, "per_minute": 60 },
+            "categories": {
+                "semantic": { "tools": ["semantic_search"], "burst": 5, "per_minute": 10 }
+            }
+        }))
+        .unwrap();
+
+        assert!(config.enabled);
+        assert_eq!(config.trusted_proxies, 0);
+        assert_eq!(config.default.burst, 20);
+        let (name, bucket) = config.category_for("semantic_search");
+        assert_eq!(name, "semantic");
+        assert_eq!(*bucket, BucketConfig::per_minute(10).with_burst(5));
+    }
+}
@@ -0,0 +1,17 @@
+//! Rate limiting error types.
+
+use fabryk_redis::RedisError;
+
+/// Errors from a quota store.
+#[derive(Debug, thiserror::Error)]
+pub enum RateLimitError {
+    /// The backing store could not be reached or returned an error.
+    #[error("quota store error: {0}")]
+    Store(String),
+}
+
+impl From<RedisError> for RateLimitError {
+    fn from(e: RedisError) -> Self {
+        RateLimitError::Store(e.to_string())
+    }
+}
@@ -0,0 +1,416 @@
+//! Tower middleware applying a [`RateLimiter`] to MCP HTTP requests.
+//!
+//! The layer reads each JSON-RPC message in a POST body, and for every
+//! `tools/call` takes a token from the caller's bucket for that tool's
+//! category. Callers are identified by [`AuthenticatedUser`] email when
+//! [`AuthLayer`](fabryk_auth::AuthLayer) runs in front of this layer, and
+//! by client IP otherwise (see [`subject_from_parts`]).
+//!
+//! A batch is checked as a whole: if any of its calls would be limited,
+//! none of them take a token and every call in it is rejected.
+//!
+//! Rejected calls get a JSON-RPC error response with code
+//! [`RATE_LIMITED`] and a `Retry-After` header; the request never reaches
+//! the MCP service.
+
+use std::convert::Infallible;
+use std::future::Future;
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use axum::body::Body;
+use axum::extract::ConnectInfo;
+use axum::response::IntoResponse;
+use fabryk_auth::AuthenticatedUser;
+use fabryk_mcp_core::model::{ErrorCode, ErrorData};
+use http::{Method, Request, StatusCode};
+use serde_json::{Value, json};
+use tower::{Layer, Service};
+
+use crate::limiter::{RateLimiter, Rejection};
+
+/// JSON-RPC error code for rate-limited tool calls.
+pub const RATE_LIMITED: ErrorCode = ErrorCode(-32029);
+
+/// Largest request body the layer will buffer.
+const MAX_BODY_BYTES: usize = 4 * 1024 * 1024;
+
+/// Tower `Layer` that rate-limits MCP tool calls.
+#[derive(Clone, Debug)]
+pub struct RateLimitLayer {
+    limiter: RateLimiter,
+}
+
+impl RateLimitLayer {
+    /// Create a layer enforcing the given limiter.
+    pub fn new(limiter: RateLimiter) -> Self {
+        Self { limiter }
+    }
+}
+
+impl<S> Layer<S> for RateLimitLayer {
+    type Service = RateLimitService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RateLimitService {
+            inner,
+            limiter: self.limiter.clone(),
+        }
+    }
+}
+
+/// Tower `Service` that checks tool calls against a [`RateLimiter`].
+#[derive(Clone, Debug)]
+pub struct RateLimitService<S> {
+    inner: S,
+    limiter: RateLimiter,
+}
+
+impl<S> Service<Request<Body>> for RateLimitService<S>
+where
+    S: Service<Request<Body>, Error = Infallible> + Clone + Send + 'static,
+    S::Response: IntoResponse,
+    S::Future: Send,
+{
+    type Response = axum::response::Response;
+    type Error = Infallible;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<Body>) -> Self::Future {
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        let limiter = self.limiter.clone();
+
+        Box::pin(async move {
+            if req.method() != Method::POST || !limiter.config().enabled {
+                let resp = inner
+                    .call(req)
+                    .await
+                    .unwrap_or_else(|infallible| match infallible {});
+                return Ok(resp.into_response());
+            }
+
+            let (parts, body) = req.into_parts();
+            let bytes = match axum::body::to_bytes(body, MAX_BODY_BYTES).await {
+                Ok(bytes) => bytes,
+                Err(e) => {
+                    log::warn!("Rejecting unreadable MCP request body: {e}");
+                    return Ok(StatusCode::PAYLOAD_TOO_LARGE.into_response());
+                }
+            };
+
+            if let Ok(message) = serde_json::from_slice::<Value>(&bytes) {
+                let calls = tool_calls(&message);
+                if !calls.is_empty() {
+                    let subject = subject_from_parts(&parts, limiter.config().trusted_proxies);
+                    let tools: Vec<&str> = calls.iter().map(|(_, tool)| tool.as_str()).collect();
+                    if let Err(rejection) = limiter.check_all(&subject, &tools).await {
+                        let ids: Vec<Value> = calls.iter().map(|(id, _)| id.clone()).collect();
+                        return Ok(rate_limited_response(&message, &ids, &rejection));
+                    }
+                }
+            }
+
+            let req = Request::from_parts(parts, Body::from(bytes));
+            let resp = inner
+                .call(req)
+                .await
+                .unwrap_or_else(|infallible| match infallible {});
+            Ok(resp.into_response())
+        })
+    }
+}
+
+/// Identify the caller: authenticated email, else client IP.
+///
+/// Clients can put anything in `X-Forwarded-For`, so only the entries
+/// appended by the `trusted_proxies` reverse proxies in front of the
+/// server are believed: the IP is the one the outermost of them saw, the
+/// `trusted_proxies`-th entry from the right. With no trusted proxies, or
+/// fewer entries than proxies, the IP is the socket address when the
+/// router is served with `into_make_service_with_connect_info`.
+pub fn subject_from_parts(parts: &http::request::Parts, trusted_proxies: usize) -> String {
+    if let Some(user) = parts.extensions.get::<AuthenticatedUser>() {
+        return format!("user:{}", user.email);
+    }
+
+    let forwarded = (trusted_proxies > 0)
+        .then(|| parts.headers.get("x-forwarded-for"))
+        .flatten()
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.rsplit(',').nth(trusted_proxies - 1))
+        .map(str::trim)
+        .filter(|ip| !ip.is_empty());
+    if let Some(ip) = forwarded {
+        return format!("ip:{ip}");
+    }
+
+    match parts.extensions.get::<ConnectInfo<SocketAddr>>() {
+        Some(ConnectInfo(addr)) => format!("ip:{}", addr.ip()),
+        None => "ip:unknown".to_string(),
+    }
+}
+
+/// The `(id, tool name)` of every `tools/call` request in a message or batch.
+fn tool_calls(message: &Value) -> Vec<(Value, String)> {
+    let messages = match message {
+        Value::Array(batch) => batch.iter().collect(),
+        single => vec![single],
+    };
+    messages
+        .into_iter()
+        .filter(|m| m.get("method").and_then(Value::as_str) == Some("tools/call"))
+        .filter_map(|m| {
+            let tool = m.get("params")?.get("name")?.as_str()?;
+            Some((
+                m.get("id").cloned().unwrap_or(Value::Null),
+                tool.to_string(),
+            ))
+        })
+        .collect()
+}
+
+/// The MCP error returned for a rejected call.
+pub fn rate_limited_error(rejection: &Rejection) -> ErrorData {
+    let retry_after_secs = rejection.retry_after.as_secs_f64().ceil() as u64;
+    ErrorData::new(
+        RATE_LIMITED,
+        format!(
+            "Rate limit exceeded for tool '{}' ({} calls/minute). Retry in {retry_after_secs}s.",
+            rejection.tool, rejection.bucket.per_minute
+        ),
+        Some(json!({
+            "tool": rejection.tool,
+            "category": rejection.category,
+            "burst": rejection.bucket.burst,
+            "per_minute": rejection.bucket.per_minute,
+            "retry_after_secs": retry_after_secs,
+        })),
+    )
+}
+
+fn rate_limited_response(
+    message: &Value,
+    ids: &[Value],
+    rejection: &Rejection,
+) -> axum::response::Response {
+    let error = rate_limited_error(rejection);
+    let responses: Vec<Value> = ids
+        .iter()
+        .map(|id| json!({ "jsonrpc": "2.0", "id": id, "error": error }))
+        .collect();
+    let body = match message {
+        Value::Array(_) => Value::Array(responses),
+        _ => responses.into_iter().next().unwrap_or(Value::Null),
+    };
+
+    let retry_after = rejection.retry_after.as_secs_f64().ceil() as u64;
+    (
+        StatusCode::OK,
+        [
+            (http::header::CONTENT_TYPE, "application/json".to_string()),
+            (http::header::RETRY_AFTER, retry_after.to_string()),
+        ],
+        body.to_string(),
+    )
+        .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{BucketConfig, RateLimitConfig};
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tower::ServiceExt;
+
+    /// Mock MCP service counting the requests that reach it.
+    #[derive(Clone, Default)]
+    struct CountingService {
+        calls: Arc<AtomicUsize>,
+    }
+
+    impl Service<Request<Body>> for CountingService {
+        type Response = axum::response::Response;
+        type Error = Infallible;
+        type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+        fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, req: Request<Body>) -> Self::Future {
+            let calls = self.calls.clone();
+            Box::pin(async move {
+                // The body must still be readable downstream.
+                let body = axum::body::to_bytes(req.into_body(), usize::MAX)
+                    .await
+                    .unwrap();
+                assert!(!body.is_empty());
+                calls.fetch_add(1, Ordering::SeqCst);
+                Ok((StatusCode::OK, "ok").into_response())
+            })
+        }
+    }
+
+    fn limiter() -> RateLimiter {
+        RateLimiter::in_memory(RateLimitConfig::default().with_category(
+            "semantic",
+            ["semantic_search"],
+            BucketConfig::per_minute(6).with_burst(1),
+        ))
+    }
+
+    fn tool_call(id: u64, tool: &str) -> Request<Body> {
+        let body = json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "method": "tools/call",
+            "params": { "name": tool, "arguments": {} }
+        });
+        Request::builder()
+            .method(Method::POST)
+            .header("x-forwarded-for", "203.0.113.7, 10.0.0.1")
+            .body(Body::from(body.to_string()))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_rejects_over_limit_with_jsonrpc_error() {
+        let inner = CountingService::default();
+        let calls = inner.calls.clone();
+        let service = RateLimitLayer::new(limiter()).layer(inner);
+
+        let resp = service
+            .clone()
+            .oneshot(tool_call(1, "semantic_search"))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        let resp = service
+            .clone()
+            .oneshot(tool_call(2, "semantic_search"))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(resp.headers()[http::header::RETRY_AFTER], "10");
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["id"], 2);
+        assert_eq!(body["error"]["code"], -32029);
+        assert_eq!(body["error"]["data"]["category"], "semantic");
+        assert_eq!(body["error"]["data"]["retry_after_secs"], 10);
+
+        // Only the first call reached the MCP service.
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        // Other tools use the default bucket.
+        service.oneshot(tool_call(3, "search")).await.unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_non_tool_messages_pass() {
+        let inner = CountingService::default();
+        let calls = inner.calls.clone();
+        let service = RateLimitLayer::new(limiter()).layer(inner);
+
+        for id in 0..3 {
+            let body = json!({"jsonrpc": "2.0", "id": id, "method": "tools/list"});
+            let req = Request::builder()
+                .method(Method::POST)
+                .body(Body::from(body.to_string()))
+                .unwrap();
+            service.clone().oneshot(req).await.unwrap();
+        }
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_batch_rejected_as_a_whole() {
+        let service = RateLimitLayer::new(limiter()).layer(CountingService::default());
+        let batch = json!([
+            {"jsonrpc": "2.0", "id": 1, "method": "tools/call", "params": {"name": "semantic_search"}},
+            {"jsonrpc": "2.0", "id": 2, "method": "tools/call", "params": {"name": "semantic_search"}}
+        ]);
+        let req = Request::builder()
+            .method(Method::POST)
+            .body(Body::from(batch.to_string()))
+            .unwrap();
+
+        let resp = service.oneshot(req).await.unwrap();
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: Value = serde_json::from_slice(&body).unwrap();
+        let errors = body.as_array().unwrap();
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0]["id"], 1);
+        assert_eq!(errors[1]["error"]["code"], -32029);
+    }
+
+    #[tokio::test]
+    async fn test_batch_takes_no_tokens_when_rejected() {
+        let limiter = RateLimiter::in_memory(RateLimitConfig::default().with_category(
+            "semantic",
+            ["semantic_search"],
+            BucketConfig::per_minute(6).with_burst(2),
+        ));
+        let service = RateLimitLayer::new(limiter).layer(CountingService::default());
+        let batch = json!([
+            {"jsonrpc": "2.0", "id": 1, "method": "tools/call", "params": {"name": "semantic_search"}},
+            {"jsonrpc": "2.0", "id": 2, "method": "tools/call", "params": {"name": "semantic_search"}},
+            {"jsonrpc": "2.0", "id": 3, "method": "tools/call", "params": {"name": "semantic_search"}}
+        ]);
+        let req = Request::builder()
+            .method(Method::POST)
+            .body(Body::from(batch.to_string()))
+            .unwrap();
+        let resp = service.clone().oneshot(req).await.unwrap();
+        assert!(resp.headers().contains_key(http::header::RETRY_AFTER));
+
+        // The rejected batch left both tokens in the bucket.
+        for id in 4..6 {
+            let resp = service
+                .clone()
+                .oneshot(tool_call(id, "semantic_search"))
+                .await
+                .unwrap();
+            assert!(!resp.headers().contains_key(http::header::RETRY_AFTER));
+        }
+    }
+
+    #[test]
+    fn test_subject_from_parts() {
+        let (mut parts, _) = Request::builder()
+            .header("x-forwarded-for", "198.51.100.1, 203.0.113.7, 10.0.0.1")
+            .body(())
+            .unwrap()
+            .into_parts();
+        assert_eq!(subject_from_parts(&parts, 1), "ip:10.0.0.1");
+        assert_eq!(subject_from_parts(&parts, 2), "ip:203.0.113.7");
+        // Too few entries, or no trusted proxies: the header is ignored.
+        assert_eq!(subject_from_parts(&parts, 4), "ip:unknown");
+        assert_eq!(subject_from_parts(&parts, 0), "ip:unknown");
+        parts
+            .extensions
+            .insert(ConnectInfo(SocketAddr::from(([192, 0, 2, 1], 4000))));
+        assert_eq!(subject_from_parts(&parts, 0), "ip:192.0.2.1");
+
+        parts
+            .extensions
+            .insert(AuthenticatedUser::new("alice@banyan.com", "sub_123"));
+        assert_eq!(subject_from_parts(&parts, 1), "user:alice@banyan.com");
+
+        let (parts, _) = Request::new(()).into_parts();
+        assert_eq!(subject_from_parts(&parts, 1), "ip:unknown");
+    }
+}
@@ -0,0 +1,42 @@
+//! Per-user rate limiting for Fabryk MCP HTTP servers.
+//!
+//! Provides:
+//! - [`RateLimitConfig`] — token bucket limits per tool category
+//! - [`RateLimiter`] — shared quota checks, reported by `service_status`
+//! - [`RateLimitLayer`] / [`RateLimitService`] — Tower middleware that
+//!   rejects over-limit `tools/call` requests with a JSON-RPC error
+//! - [`MemoryQuotaStore`] / [`RedisQuotaStore`] — quota state for single
+//!   instances or shared across instances
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use fabryk_mcp_ratelimit::{BucketConfig, RateLimitConfig, RateLimitLayer, RateLimiter};
+//!
+//! let config = RateLimitConfig::default()
+//!     .with_category("semantic", ["semantic_search"], BucketConfig::per_minute(10));
+//! let limiter = RateLimiter::new(config, Arc::new(RedisQuotaStore::new(redis)));
+//!
+//! let diagnostics = DiagnosticTools::new(config)
+//!     .with_status_section(Arc::new(limiter.clone()));
+//!
+//! // Rate limit runs inside auth so callers are keyed by email.
+//! let app = axum::Router::new()
+//!     .nest_service("/mcp", server.into_http_service())
+//!     .layer(RateLimitLayer::new(limiter))
+//!     .layer(AuthLayer::new(validator, auth_config));
+//! ```
+
+mod config;
+mod error;
+mod layer;
+mod limiter;
+mod store;
+
+pub use config::{BucketConfig, CategoryConfig, DEFAULT_CATEGORY, RateLimitConfig};
+pub use error::RateLimitError;
+pub use layer::{
+    RATE_LIMITED, RateLimitLayer, RateLimitService, rate_limited_error, subject_from_parts,
+};
+pub use limiter::{CategoryStatus, RateLimitStatus, RateLimiter, Rejection};
+pub use store::{Decision, MemoryQuotaStore, QuotaStore, RedisQuotaStore};
@@ -0,0 +1,374 @@
+//! The shared rate limiter.
+
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use fabryk_mcp_core::StatusSection;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::config::{BucketConfig, DEFAULT_CATEGORY, RateLimitConfig};
+use crate::error::RateLimitError;
+use crate::store::{Decision, MemoryQuotaStore, QuotaStore};
+
+/// A rejected call.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Rejection {
+    /// The tool that was called.
+    pub tool: String,
+    /// The category whose bucket was empty.
+    pub category: String,
+    /// The bucket that was exhausted.
+    pub bucket: BucketConfig,
+    /// How long until the caller may retry.
+    pub retry_after: Duration,
+}
+
+/// Checks tool calls against per-caller quotas.
+///
+/// Cheaply cloneable; clones share the quota store and counters. Pass
+/// one clone to [`RateLimitLayer`](crate::RateLimitLayer) and another to
+/// [`DiagnosticTools::with_status_section`](fabryk_mcp_core::DiagnosticTools::with_status_section)
+/// to expose the limits through `service_status`.
+///
+/// If the store fails (e.g. Redis is unreachable) calls are allowed and a
+/// warning is logged, so an outage of the quota backend doesn't take the
+/// server down with it.
+#[derive(Clone, Debug)]
+pub struct RateLimiter {
+    inner: Arc<Inner>,
+}
+
+#[derive(Debug)]
+struct Inner {
+    config: RateLimitConfig,
+    store: Arc<dyn QuotaStore>,
+    allowed: AtomicU64,
+    store_errors: AtomicU64,
+    rejected: Mutex<BTreeMap<String, u64>>,
+}
+
+impl RateLimiter {
+    /// Create a limiter backed by the given store.
+    pub fn new(config: RateLimitConfig, store: Arc<dyn QuotaStore>) -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                config,
+                store,
+                allowed: AtomicU64::new(0),
+                store_errors: AtomicU64::new(0),
+                rejected: Mutex::new(BTreeMap::new()),
+            }),
+        }
+    }
+
+    /// Create a limiter with an in-memory store.
+    pub fn in_memory(config: RateLimitConfig) -> Self {
+        Self::new(config, Arc::new(MemoryQuotaStore::new()))
+    }
+
+    /// The limiter's configuration.
+    pub fn config(&self) -> &RateLimitConfig {
+        &self.inner.config
+    }
+
+    /// Take a token for `subject` calling `tool`.
+    ///
+    /// `subject` identifies the caller, e.g. `user:alice@example.com` or
+    /// `ip:203.0.113.7`.
+    pub async fn check(&self, subject: &str, tool: &str) -> Result<(), Rejection> {
+        self.check_all(subject, &[tool]).await
+    }
+
+    /// Take a token for each of `tools`, called together by `subject` (e.g.
+    /// in a JSON-RPC batch).
+    ///
+    /// Every bucket involved is checked for enough tokens before any are
+    /// taken, so a rejected batch uses none of the caller's quota.
+    pub async fn check_all(&self, subject: &str, tools: &[&str]) -> Result<(), Rejection> {
+        let config = &self.inner.config;
+        if !config.enabled {
+            return Ok(());
+        }
+
+        // Calls per category, with the first tool for error messages.
+        let mut demand: BTreeMap<&str, (&BucketConfig, &str, u32)> = BTreeMap::new();
+        for tool in tools {
+            let (category, bucket) = config.category_for(tool);
+            demand.entry(category).or_insert((bucket, tool, 0)).2 += 1;
+        }
+
+        let mut unchecked = Vec::new();
+        for (category, (bucket, tool, calls)) in &demand {
+            let key = format!("{category}:{subject}");
+            match self.inner.store.peek(&key, bucket, *calls).await {
+                Ok(Decision::Allowed { .. }) => {}
+                Ok(Decision::Limited { retry_after }) => {
+                    return Err(self.reject(subject, tool, category, bucket, *calls, retry_after));
+                }
+                Err(e) => {
+                    self.store_failed(e);
+                    unchecked.push(*category);
+                }
+            }
+        }
+        // Another request may have taken tokens since the check; it then
+        // wins and this one is limited.
+        for (category, (bucket, tool, calls)) in &demand {
+            if unchecked.contains(category) {
+                continue;
+            }
+            let key = format!("{category}:{subject}");
+            match self.inner.store.acquire(&key, bucket, *calls).await {
+                Ok(Decision::Allowed { .. }) => {}
+                Ok(Decision::Limited { retry_after }) => {
+                    return Err(self.reject(subject, tool, category, bucket, *calls, retry_after));
+                }
+                Err(e) => self.store_failed(e),
+            }
+        }
+
+        self.inner
+            .allowed
+            .fetch_add(tools.len() as u64, Ordering::Relaxed);
+        Ok(())
+    }
+
+    fn reject(
+        &self,
+        subject: &str,
+        tool: &str,
+        category: &str,
+        bucket: &BucketConfig,
+        calls: u32,
+        retry_after: Duration,
+    ) -> Rejection {
+        *self
+            .inner
+            .rejected
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .entry(category.to_string())
+            .or_default() += u64::from(calls);
+        log::info!("Rate limited {subject} calling '{tool}' ({category})");
+        Rejection {
+            tool: tool.to_string(),
+            category: category.to_string(),
+            bucket: *bucket,
+            retry_after,
+        }
+    }
+
+    fn store_failed(&self, e: RateLimitError) {
+        self.inner.store_errors.fetch_add(1, Ordering::Relaxed);
+        log::warn!("Rate limit check failed, allowing call: {e}");
+    }
+
+    /// Snapshot of limits and counters.
+    pub fn status(&self) -> RateLimitStatus {
+        let config = &self.inner.config;
+        let rejected = self
+            .inner
+            .rejected
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone();
+        let rejected_for = |name: &str| rejected.get(name).copied().unwrap_or(0);
+
+        let mut categories = vec![CategoryStatus {
+            name: DEFAULT_CATEGORY.to_string(),
+            tools: vec!["*".to_string()],
+            burst: config.default.burst,
+            per_minute: config.default.per_minute,
+            rejected: rejected_for(DEFAULT_CATEGORY),
+        }];
+        categories.extend(
+            config
+                .categories
+                .iter()
+                .map(|(name, category)| CategoryStatus {
+                    name: name.clone(),
+                    tools: category.tools.clone(),
+                    burst: category.bucket.burst,
+                    per_minute: category.bucket.per_minute,
+                    rejected: rejected_for(name),
+                }),
+        );
+
+        RateLimitStatus {
+            enabled: config.enabled,
+            backend: self.inner.store.backend(),
+            tracked_keys: self.inner.store.tracked_keys(),
+            allowed: self.inner.allowed.load(Ordering::Relaxed),
+            store_errors: self.inner.store_errors.load(Ordering::Relaxed),
+            categories,
+        }
+    }
+}
+
+impl StatusSection for RateLimiter {
+    fn name(&self) -> &str {
+        "rate_limits"
+    }
+
+    fn status(&self) -> Value {
+        serde_json::to_value(RateLimiter::status(self)).unwrap_or(Value::Null)
+    }
+}
+
+/// Rate limiter state reported by `service_status`.
+#[derive(Clone, Debug, Serialize)]
+pub struct RateLimitStatus {
+    /// Whether limits are enforced.
+    pub enabled: bool,
+    /// Quota store backend (`memory` or `redis`).
+    pub backend: &'static str,
+    /// Buckets currently tracked, if the backend knows.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tracked_keys: Option<usize>,
+    /// Calls allowed since startup.
+    pub allowed: u64,
+    /// Checks that failed open because the store errored.
+    pub store_errors: u64,
+    /// Limits and rejections per category.
+    pub categories: Vec<CategoryStatus>,
+}
+
+/// One category in [`RateLimitStatus`].
+#[derive(Clone, Debug, Serialize)]
+pub struct CategoryStatus {
+    /// Category name.
+    pub name: String,
+    /// Tool name patterns in the category.
+    pub tools: Vec<String>,
+    /// Burst size.
+    pub burst: u32,
+    /// Refill rate per minute.
+    pub per_minute: u32,
+    /// Calls rejected since startup.
+    pub rejected: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+
+    fn config() -> RateLimitConfig {
+        RateLimitConfig::default()
+            .with_default(BucketConfig::per_minute(60))
+            .with_category(
+                "semantic",
+                ["semantic_search"],
+                BucketConfig::per_minute(10).with_burst(1),
+            )
+    }
+
+    #[tokio::test]
+    async fn test_check_limits_per_category_and_subject() {
+        let limiter = RateLimiter::in_memory(config());
+
+        assert!(limiter.check("user:alice", "semantic_search").await.is_ok());
+        let rejection = limiter
+            .check("user:alice", "semantic_search")
+            .await
+            .unwrap_err();
+        assert_eq!(rejection.category, "semantic");
+        assert_eq!(rejection.tool, "semantic_search");
+        assert_eq!(rejection.retry_after.as_secs_f64().round(), 6.0);
+
+        // Other categories and other users are unaffected.
+        assert!(limiter.check("user:alice", "search").await.is_ok());
+        assert!(limiter.check("user:bob", "semantic_search").await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_check_all_takes_nothing_when_rejected() {
+        let limiter = RateLimiter::in_memory(config());
+
+        // The semantic bucket holds one token, so the batch fails without
+        // spending the default bucket's tokens either.
+        let rejection = limiter
+            .check_all(
+                "user:alice",
+                &["search", "semantic_search", "semantic_search"],
+            )
+            .await
+            .unwrap_err();
+        assert_eq!(rejection.category, "semantic");
+        assert!(limiter.check("user:alice", "semantic_search").await.is_ok());
+        assert_eq!(limiter.status().allowed, 1);
+        assert_eq!(limiter.status().categories[1].rejected, 2);
+
+        assert!(
+            limiter
+                .check_all("user:bob", &["search", "semantic_search"])
+                .await
+                .is_ok()
+        );
+        assert_eq!(limiter.status().allowed, 3);
+    }
+
+    #[tokio::test]
+    async fn test_disabled_allows_everything() {
+        let mut config = config();
+        config.enabled = false;
+        let limiter = RateLimiter::in_memory(config);
+        for _ in 0..5 {
+            assert!(limiter.check("user:alice", "semantic_search").await.is_ok());
+        }
+    }
+
+    #[derive(Debug)]
+    struct FailingStore;
+
+    #[async_trait]
+    impl QuotaStore for FailingStore {
+        async fn acquire(
+            &self,
+            _: &str,
+            _: &BucketConfig,
+            _: u32,
+        ) -> Result<Decision, RateLimitError> {
+            Err(RateLimitError::Store("connection refused".to_string()))
+        }
+
+        async fn peek(
+            &self,
+            _: &str,
+            _: &BucketConfig,
+            _: u32,
+        ) -> Result<Decision, RateLimitError> {
+            Err(RateLimitError::Store("connection refused".to_string()))
+        }
+
+        fn backend(&self) -> &'static str {
+            "failing"
+        }
+    }
+
+    #[tokio::test]
+    async fn test_store_errors_fail_open() {
+        let limiter = RateLimiter::new(config(), Arc::new(FailingStore));
+        assert!(limiter.check("user:alice", "semantic_search").await.is_ok());
+        assert_eq!(limiter.status().store_errors, 1);
+    }
+
+    #[tokio::test]
+    async fn test_status_section() {
+        let limiter = RateLimiter::in_memory(config());
+        limiter.check("user:alice", "semantic_search").await.ok();
+        limiter.check("user:alice", "semantic_search").await.ok();
+
+        assert_eq!(StatusSection::name(&limiter), "rate_limits");
+        let status = StatusSection::status(&limiter);
+        assert_eq!(status["backend"], "memory");
+        assert_eq!(status["allowed"], 1);
+        assert_eq!(status["tracked_keys"], 1);
+        assert_eq!(status["categories"][0]["name"], "default");
+        assert_eq!(status["categories"][1]["name"], "semantic");
+        assert_eq!(status["categories"][1]["rejected"], 1);
+    }
+}
@@ -0,0 +1,454 @@
+//! Quota state backends.
+//!
+//! - [`MemoryQuotaStore`] — exact token buckets in process memory, for
+//!   single-instance servers.
+//! - [`RedisQuotaStore`] — shared counters in Redis, for deployments with
+//!   several instances behind a load balancer (e.g. Cloud Run).
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use async_trait::async_trait;
+use fabryk_redis::RedisOps;
+
+use crate::config::BucketConfig;
+use crate::error::RateLimitError;
+
+/// Outcome of taking a token from a bucket.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Decision {
+    /// The call may proceed.
+    Allowed {
+        /// Calls left before the bucket is empty.
+        remaining: u32,
+    },
+    /// The bucket is empty.
+    Limited {
+        /// How long until a token is available.
+        retry_after: Duration,
+    },
+}
+
+impl Decision {
+    /// Whether the call may proceed.
+    pub fn is_allowed(&self) -> bool {
+        matches!(self, Decision::Allowed { .. })
+    }
+}
+
+/// Storage for per-caller token buckets.
+#[async_trait]
+pub trait QuotaStore: Send + Sync + std::fmt::Debug {
+    /// Take `tokens` tokens from the bucket at `key`, or none if it holds
+    /// fewer.
+    async fn acquire(
+        &self,
+        key: &str,
+        bucket: &BucketConfig,
+        tokens: u32,
+    ) -> Result<Decision, RateLimitError>;
+
+    /// Whether the bucket at `key` holds `tokens` tokens, without taking
+    /// them.
+    async fn peek(
+        &self,
+        key: &str,
+        bucket: &BucketConfig,
+        tokens: u32,
+    ) -> Result<Decision, RateLimitError>;
+
+    /// Backend name for diagnostics.
+    fn backend(&self) -> &'static str;
+
+    /// Number of buckets currently tracked, if the backend knows.
+    fn tracked_keys(&self) -> Option<usize> {
+        None
+    }
+}
+
+// ============================================================================
+// In-memory
+// ============================================================================
+
+#[derive(Debug, Clone, Copy)]
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+/// Buckets tracked before full ones are pruned.
+const PRUNE_THRESHOLD: usize = 10_000;
+
+/// Token buckets held in process memory.
+///
+/// Buckets are refilled continuously, so a caller who waits
+/// `60 / per_minute` seconds always gets another call. Buckets that have
+/// refilled completely are dropped once many callers are tracked.
+#[derive(Debug, Default)]
+pub struct MemoryQuotaStore {
+    buckets: Mutex<HashMap<String, Bucket>>,
+}
+
+impl MemoryQuotaStore {
+    /// Create an empty store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Check for `tokens` tokens at `now`, taking them if `take` is set.
+    fn acquire_at(
+        &self,
+        key: &str,
+        config: &BucketConfig,
+        tokens: u32,
+        take: bool,
+        now: Instant,
+    ) -> Decision {
+        let mut buckets = self.buckets.lock().unwrap_or_else(|e| e.into_inner());
+        let capacity = f64::from(config.burst);
+        let rate_per_sec = f64::from(config.per_minute) / 60.0;
+
+        if buckets.len() >= PRUNE_THRESHOLD && !buckets.contains_key(key) {
+            let refill = Duration::from_secs(config.refill_secs());
+            buckets.retain(|_, b| now.saturating_duration_since(b.updated) < refill);
+        }
+
+        let bucket = buckets.entry(key.to_string()).or_insert(Bucket {
+            tokens: capacity,
+            updated: now,
+        });
+        let elapsed = now.saturating_duration_since(bucket.updated).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * rate_per_sec).min(capacity);
+        bucket.updated = now;
+
+        let needed = f64::from(tokens);
+        if bucket.tokens >= needed {
+            let remaining = bucket.tokens - needed;
+            if take {
+                bucket.tokens = remaining;
+            }
+            Decision::Allowed {
+                remaining: remaining as u32,
+            }
+        } else if rate_per_sec > 0.0 {
+            Decision::Limited {
+                retry_after: Duration::from_secs_f64((needed - bucket.tokens) / rate_per_sec),
+            }
+        } else {
+            Decision::Limited {
+                retry_after: Duration::MAX,
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl QuotaStore for MemoryQuotaStore {
+    async fn acquire(
+        &self,
+        key: &str,
+        bucket: &BucketConfig,
+        tokens: u32,
+    ) -> Result<Decision, RateLimitError> {
+        Ok(self.acquire_at(key, bucket, tokens, true, Instant::now()))
+    }
+
+    async fn peek(
+        &self,
+        key: &str,
+        bucket: &BucketConfig,
+        tokens: u32,
+    ) -> Result<Decision, RateLimitError> {
+        Ok(self.acquire_at(key, bucket, tokens, false, Instant::now()))
+    }
+
+    fn backend(&self) -> &'static str {
+        "memory"
+    }
+
+    fn tracked_keys(&self) -> Option<usize> {
+        Some(self.buckets.lock().unwrap_or_else(|e| e.into_inner()).len())
+    }
+}
+
+// ============================================================================
+// Redis
+// ============================================================================
+
+/// Quota counters shared through Redis.
+///
+/// Redis has no atomic token bucket without scripting, so this store
+/// approximates one with fixed windows: each window lasts as long as it
+/// takes to refill an empty bucket and admits `burst` calls. The long-run
+/// rate matches the bucket's; a caller can get up to two bursts across a
+/// window boundary. Window counters expire on their own.
+///
+/// A request for several tokens that would overflow the window takes none
+/// of them; concurrent requests can still push a counter past `burst`, in
+/// which case the later ones are limited.
+#[derive(Debug, Clone)]
+pub struct RedisQuotaStore {
+    redis: Arc<dyn RedisOps>,
+    prefix: String,
+}
+
+impl RedisQuotaStore {
+    /// Create a store using keys under `fabryk:ratelimit`.
+    pub fn new(redis: Arc<dyn RedisOps>) -> Self {
+        Self {
+            redis,
+            prefix: "fabryk:ratelimit".to_string(),
+        }
+    }
+
+    /// Use a different key prefix, e.g. to separate servers sharing one
+    /// Redis instance.
+    pub fn with_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = prefix.into();
+        self
+    }
+
+    /// Check for `tokens` calls in the window containing `now_secs`,
+    /// counting them if `take` is set.
+    async fn acquire_at(
+        &self,
+        key: &str,
+        config: &BucketConfig,
+        tokens: u32,
+        take: bool,
+        now_secs: u64,
+    ) -> Result<Decision, RateLimitError> {
+        if config.per_minute == 0 {
+            return Ok(Decision::Limited {
+                retry_after: Duration::MAX,
+            });
+        }
+
+        let window_secs = config.refill_secs();
+        let window = now_secs / window_secs;
+        let redis_key = format!("{}:{key}:{window}", self.prefix);
+        let window_end = (window + 1) * window_secs;
+        let limited = Decision::Limited {
+            retry_after: Duration::from_secs(window_end - now_secs),
+        };
+
+        let burst = u64::from(config.burst);
+        let tokens = u64::from(tokens);
+        let used = self.redis.get_u64(&redis_key).await?;
+        if used + tokens > burst {
+            return Ok(limited);
+        }
+        if !take {
+            return Ok(Decision::Allowed {
+                remaining: (burst - used - tokens) as u32,
+            });
+        }
+
+        let count = self.redis.incr_by(&redis_key, tokens).await?;
+        if count == tokens {
+            self.redis.expire(&redis_key, window_secs * 2).await?;
+        }
+        if count <= burst {
+            Ok(Decision::Allowed {
+                remaining: (burst - count) as u32,
+            })
+        } else {
+            Ok(limited)
+        }
+    }
+}
+
+#[async_trait]
+impl QuotaStore for RedisQuotaStore {
+    async fn acquire(
+        &self,
+        key: &str,
+        bucket: &BucketConfig,
+        tokens: u32,
+    ) -> Result<Decision, RateLimitError> {
+        self.acquire_at(key, bucket, tokens, true, unix_secs())
+            .await
+    }
+
+    async fn peek(
+        &self,
+        key: &str,
+        bucket: &BucketConfig,
+        tokens: u32,
+    ) -> Result<Decision, RateLimitError> {
+        self.acquire_at(key, bucket, tokens, false, unix_secs())
+            .await
+    }
+
+    fn backend(&self) -> &'static str {
+        "redis"
+    }
+}
+
+fn unix_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fabryk_redis::MockRedis;
+
+    #[test]
+    fn test_memory_store_burst_then_limited() {
+        let store = MemoryQuotaStore::new();
+        let bucket = BucketConfig::per_minute(60).with_burst(2);
+        let now = Instant::now();
+
+        assert_eq!(
+            store.acquire_at("alice", &bucket, 1, true, now),
+            Decision::Allowed { remaining: 1 }
+        );
+        assert_eq!(
+            store.acquire_at("alice", &bucket, 1, true, now),
+            Decision::Allowed { remaining: 0 }
+        );
+        match store.acquire_at("alice", &bucket, 1, true, now) {
+            Decision::Limited { retry_after } => assert_eq!(retry_after, Duration::from_secs(1)),
+            other => panic!("expected limited, got {other:?}"),
+        }
+
+        // Other callers have their own bucket.
+        assert!(store.acquire_at("bob", &bucket, 1, true, now).is_allowed());
+        assert_eq!(store.tracked_keys(), Some(2));
+    }
+
+    #[test]
+    fn test_memory_store_refills() {
+        let store = MemoryQuotaStore::new();
+        let bucket = BucketConfig::per_minute(60).with_burst(1);
+        let now = Instant::now();
+
+        assert!(
+            store
+                .acquire_at("alice", &bucket, 1, true, now)
+                .is_allowed()
+        );
+        assert!(
+            !store
+                .acquire_at("alice", &bucket, 1, true, now)
+                .is_allowed()
+        );
+        let later = now + Duration::from_secs(1);
+        assert!(
+            store
+                .acquire_at("alice", &bucket, 1, true, later)
+                .is_allowed()
+        );
+
+        // Refill never exceeds the burst size.
+        let much_later = later + Duration::from_secs(3600);
+        assert_eq!(
+            store.acquire_at("alice", &bucket, 1, true, much_later),
+            Decision::Allowed { remaining: 0 }
+        );
+    }
+
+    #[test]
+    fn test_memory_store_takes_all_or_nothing() {
+        let store = MemoryQuotaStore::new();
+        let bucket = BucketConfig::per_minute(60).with_burst(3);
+        let now = Instant::now();
+
+        assert_eq!(
+            store.acquire_at("alice", &bucket, 2, false, now),
+            Decision::Allowed { remaining: 1 }
+        );
+        assert_eq!(
+            store.acquire_at("alice", &bucket, 2, true, now),
+            Decision::Allowed { remaining: 1 }
+        );
+        // Two more don't fit, so neither is taken.
+        assert!(
+            !store
+                .acquire_at("alice", &bucket, 2, true, now)
+                .is_allowed()
+        );
+        assert!(
+            store
+                .acquire_at("alice", &bucket, 1, true, now)
+                .is_allowed()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_redis_store_takes_all_or_nothing() {
+        let redis = Arc::new(MockRedis::new());
+        let store = RedisQuotaStore::new(redis.clone()).with_prefix("test");
+        let bucket = BucketConfig::per_minute(10).with_burst(5);
+        let now = 1_000_000_000;
+
+        assert!(
+            store
+                .acquire_at("user:alice", &bucket, 4, false, now)
+                .await
+                .unwrap()
+                .is_allowed()
+        );
+        assert_eq!(redis.get_u64("test:user:alice:33333333").await.unwrap(), 0);
+        assert_eq!(
+            store
+                .acquire_at("user:alice", &bucket, 4, true, now)
+                .await
+                .unwrap(),
+            Decision::Allowed { remaining: 1 }
+        );
+        assert!(
+            !store
+                .acquire_at("user:alice", &bucket, 2, true, now)
+                .await
+                .unwrap()
+                .is_allowed()
+        );
+        assert_eq!(redis.get_u64("test:user:alice:33333333").await.unwrap(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_redis_store_fixed_window() {
+        let redis = Arc::new(MockRedis::new());
+        let store = RedisQuotaStore::new(redis.clone()).with_prefix("test");
+        // Window of 30s admitting 5 calls.
+        let bucket = BucketConfig::per_minute(10).with_burst(5);
+        let now = 1_000_000_000; // 10s into window 33_333_333
+
+        for expected in (0..5).rev() {
+            assert_eq!(
+                store
+                    .acquire_at("user:alice", &bucket, 1, true, now)
+                    .await
+                    .unwrap(),
+                Decision::Allowed {
+                    remaining: expected
+                }
+            );
+        }
+        assert_eq!(
+            store
+                .acquire_at("user:alice", &bucket, 1, true, now)
+                .await
+                .unwrap(),
+            Decision::Limited {
+                retry_after: Duration::from_secs(20)
+            }
+        );
+        assert_eq!(redis.ttl("test:user:alice:33333333"), Some(60));
+
+        // The next window starts fresh.
+        assert!(
+            store
+                .acquire_at("user:alice", &bucket, 1, true, now + 20)
+                .await
+                .unwrap()
+                .is_allowed()
+        );
+    }
+}
@@ -5,7 +5,8 @@
 //!
 //! # Tools
 //!
-//! - `semantic_search` — search using keyword, vector, or hybrid (RRF) mode
+//! - `semantic_search` — search using keyword, vector, or hybrid (RRF) mode,
+//!   with optional reranking of hybrid results
 //!
 //! # Example
 //!
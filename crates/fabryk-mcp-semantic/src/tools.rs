@@ -7,10 +7,12 @@ use std::collections::HashMap;
 use std::sync::Arc;
 
 use fabryk_fts::{SearchBackend, SearchParams};
+use fabryk_mcp_core::args::parse_args;
 use fabryk_mcp_core::model::{CallToolResult, Content, ErrorData, Tool};
 use fabryk_mcp_core::registry::{ToolRegistry, ToolResult};
 use fabryk_vector::{
-    FtsResult, HybridSearchResult, VectorBackend, VectorSearchParams, reciprocal_rank_fusion,
+    FtsResult, HybridSearchResult, Reranker, VectorBackend, VectorSearchParams,
+    reciprocal_rank_fusion, rerank_results,
 };
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
@@ -57,6 +59,8 @@ pub struct SemanticSearchArgs {
     pub source: Option<String>,
     /// Maximum results to return (default 10, max 50).
     pub limit: Option<usize>,
+    /// Whether to rerank hybrid results (default true when a reranker is configured).
+    pub rerank: Option<bool>,
 }
 
 // ---------------------------------------------------------------------------
@@ -74,6 +78,15 @@ pub struct HybridResult {
     pub rrf_score: f32,
     /// Source of the result: "vector", "keyword", or "hybrid".
     pub source: String,
+    /// Similarity score from the vector phase.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vector_score: Option<f32>,
+    /// Relevance score from the keyword phase.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub keyword_score: Option<f32>,
+    /// Score from the rerank phase.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rerank_score: Option<f32>,
     /// Metadata snapshot.
     #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub metadata: HashMap<String, String>,
@@ -85,6 +98,9 @@ impl From<HybridSearchResult> for HybridResult {
             id: r.id,
             rrf_score: r.score,
             source: r.source,
+            vector_score: r.vector_score,
+            keyword_score: r.keyword_score,
+            rerank_score: r.rerank_score,
             metadata: r.metadata,
         }
     }
@@ -108,6 +124,44 @@ fn to_fts_results(results: &fabryk_fts::SearchResults) -> Vec<FtsResult> {
         .collect()
 }
 
+/// Build the text a reranker sees for each document.
+///
+/// FTS hits contribute title, description and snippet. Documents only found
+/// by vector search fall back to their `title`/`description` metadata, then
+/// to the document ID.
+fn rerank_texts(fts: &fabryk_fts::SearchResults) -> HashMap<String, String> {
+    fts.items
+        .iter()
+        .map(|item| {
+            let parts: Vec<&str> = [
+                Some(item.title.as_str()),
+                item.description.as_deref(),
+                item.snippet.as_deref(),
+            ]
+            .into_iter()
+            .flatten()
+            .filter(|p| !p.is_empty())
+            .collect();
+            (item.id.clone(), parts.join("\n"))
+        })
+        .collect()
+}
+
+fn rerank_text(texts: &HashMap<String, String>, result: &HybridSearchResult) -> String {
+    if let Some(text) = texts.get(&result.id) {
+        return text.clone();
+    }
+    let parts: Vec<&str> = ["title", "description"]
+        .iter()
+        .filter_map(|key| result.metadata.get(*key).map(String::as_str))
+        .collect();
+    if parts.is_empty() {
+        result.id.clone()
+    } else {
+        parts.join("\n")
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Type aliases
 // ---------------------------------------------------------------------------
@@ -129,6 +183,10 @@ pub type VectorSlot = Arc<tokio::sync::RwLock<Option<Arc<dyn VectorBackend>>>>;
 ///
 /// When no vector backend is available, hybrid mode falls back to keyword-only.
 ///
+/// With a [`Reranker`] configured via [`with_reranker`](Self::with_reranker),
+/// the top fused hybrid results are rescored and reordered before the limit
+/// is applied.
+///
 /// # Example
 ///
 /// ```rust,ignore
@@ -140,6 +198,8 @@ pub struct SemanticSearchTools {
     fts: Arc<dyn SearchBackend>,
     vector: Option<Arc<dyn VectorBackend>>,
     vector_slot: Option<VectorSlot>,
+    reranker: Option<Arc<dyn Reranker>>,
+    rerank_top_k: usize,
     custom_names: HashMap<String, String>,
     custom_descriptions: HashMap<String, String>,
 }
@@ -148,12 +208,17 @@ impl SemanticSearchTools {
     /// Slot key for the semantic search tool.
     pub const SLOT_SEMANTIC_SEARCH: &str = "semantic_search";
 
+    /// Default number of fused hybrid results passed to the reranker.
+    pub const DEFAULT_RERANK_TOP_K: usize = 20;
+
     /// Create semantic search tools with FTS and optional vector backends.
     pub fn new(fts: Arc<dyn SearchBackend>, vector: Option<Arc<dyn VectorBackend>>) -> Self {
         Self {
             fts,
             vector,
             vector_slot: None,
+            reranker: None,
+            rerank_top_k: Self::DEFAULT_RERANK_TOP_K,
             custom_names: HashMap::new(),
             custom_descriptions: HashMap::new(),
         }
@@ -165,6 +230,8 @@ impl SemanticSearchTools {
             fts: Arc::from(fts),
             vector: vector.map(Arc::from),
             vector_slot: None,
+            reranker: None,
+            rerank_top_k: Self::DEFAULT_RERANK_TOP_K,
             custom_names: HashMap::new(),
             custom_descriptions: HashMap::new(),
         }
@@ -187,11 +254,20 @@ impl SemanticSearchTools {
             fts,
             vector: None,
             vector_slot: Some(vector_slot),
+            reranker: None,
+            rerank_top_k: Self::DEFAULT_RERANK_TOP_K,
             custom_names: HashMap::new(),
             custom_descriptions: HashMap::new(),
         }
     }
 
+    /// Rerank the top `top_k` hybrid results with the given reranker.
+    pub fn with_reranker(mut self, reranker: Arc<dyn Reranker>, top_k: usize) -> Self {
+        self.reranker = Some(reranker);
+        self.rerank_top_k = top_k;
+        self
+    }
+
     /// Override tool names by slot key.
     pub fn with_names(mut self, names: HashMap<String, String>) -> Self {
         self.custom_names = names;
@@ -264,6 +340,10 @@ impl ToolRegistry for SemanticSearchTools {
                     "limit": {
                         "type": "integer",
                         "description": "Maximum results (default: 10)"
+                    },
+                    "rerank": {
+                        "type": "boolean",
+                        "description": "Rerank hybrid results for relevance (default: true if available)"
                     }
                 },
                 "required": ["query"]
@@ -278,10 +358,12 @@ impl ToolRegistry for SemanticSearchTools {
 
         let fts = self.fts.clone();
         let vector = self.resolve_vector();
+        let reranker = self.reranker.clone();
+        let rerank_top_k = self.rerank_top_k;
 
+        let tool = name.to_string();
         Some(Box::pin(async move {
-            let args: SemanticSearchArgs = serde_json::from_value(args)
-                .map_err(|e| ErrorData::invalid_params(e.to_string(), None))?;
+            let args: SemanticSearchArgs = parse_args(&tool, args)?;
 
             let mode = args.mode.as_deref().unwrap_or("hybrid");
             let limit = args.limit.unwrap_or(10).min(50);
@@ -316,10 +398,17 @@ impl ToolRegistry for SemanticSearchTools {
                     serialize_response(&results)
                 }
                 _ => {
-                    // Hybrid: run both and merge via reciprocal rank fusion
+                    // Hybrid: run both and merge via reciprocal rank fusion.
+                    // With a reranker, each backend supplies enough candidates
+                    // to fill the rerank pool.
+                    let reranker = reranker.filter(|_| args.rerank.unwrap_or(true));
+                    let candidates = match reranker {
+                        Some(_) => (limit * 2).max(rerank_top_k),
+                        None => limit * 2,
+                    };
                     let fts_params = SearchParams {
                         query: args.query.clone(),
-                        limit: Some(limit * 2),
+                        limit: Some(candidates),
                         category: args.category.clone(),
                         source: args.source.clone(),
                         ..Default::default()
@@ -333,7 +422,7 @@ impl ToolRegistry for SemanticSearchTools {
                     // If vector is available, do hybrid; otherwise fall back to FTS only
                     if let Some(ref backend) = vector {
                         let vector_params =
-                            VectorSearchParams::new(&args.query).with_limit(limit * 2);
+                            VectorSearchParams::new(&args.query).with_limit(candidates);
                         let vector_results = backend
                             .search(vector_params)
                             .await
@@ -341,8 +430,31 @@ impl ToolRegistry for SemanticSearchTools {
 
                         // Convert FTS results to the adapter type and run RRF
                         let fts_adapted = to_fts_results(&fts_results);
-                        let merged =
-                            reciprocal_rank_fusion(&vector_results.items, &fts_adapted, limit, 60);
+                        let fused_limit = match reranker {
+                            Some(_) => limit.max(rerank_top_k),
+                            None => limit,
+                        };
+                        let mut merged = reciprocal_rank_fusion(
+                            &vector_results.items,
+                            &fts_adapted,
+                            fused_limit,
+                            60,
+                        );
+
+                        if let Some(reranker) = reranker {
+                            let texts = rerank_texts(&fts_results);
+                            merged = rerank_results(
+                                reranker.as_ref(),
+                                &args.query,
+                                merged,
+                                rerank_top_k,
+                                |r| rerank_text(&texts, r),
+                            )
+                            .await
+                            .map_err(|e| ErrorData::internal_error(e.to_string(), None))?;
+                            merged.truncate(limit);
+                        }
+
                         let results: Vec<HybridResult> =
                             merged.into_iter().map(HybridResult::from).collect();
                         serialize_response(&results)
@@ -412,8 +524,10 @@ mod tests {
 
     #[async_trait::async_trait]
     impl SearchBackend for MockFts {
-        async fn search(&self, _params: SearchParams) -> fabryk_core::Result<SearchResults> {
-            Ok(self.results.clone())
+        async fn search(&self, params: SearchParams) -> fabryk_core::Result<SearchResults> {
+            let mut results = self.results.clone();
+            results.items.truncate(params.limit.unwrap_or(usize::MAX));
+            Ok(results)
         }
 
         fn name(&self) -> &str {
@@ -455,9 +569,11 @@ mod tests {
     impl VectorBackend for MockVector {
         async fn search(
             &self,
-            _params: VectorSearchParams,
+            params: VectorSearchParams,
         ) -> fabryk_core::Result<fabryk_vector::VectorSearchResults> {
-            Ok(self.results.clone())
+            let mut results = self.results.clone();
+            results.items.truncate(params.limit.unwrap_or(usize::MAX));
+            Ok(results)
         }
 
         fn name(&self) -> &str {
@@ -624,6 +740,101 @@ mod tests {
         assert!(!result.is_error.unwrap_or(false));
     }
 
+    // -- Rerank tests ------------------------------------------------------
+
+    fn make_titled_fts_result(id: &str, title: &str, relevance: f32) -> SearchResult {
+        SearchResult {
+            title: title.to_string(),
+            ..make_fts_result(id, relevance)
+        }
+    }
+
+    async fn hybrid_call(tools: &SemanticSearchTools, args: Value) -> Vec<HybridResult> {
+        let result = tools.call("semantic_search", args).unwrap().await.unwrap();
+        let text = result.content[0].as_text().unwrap().text.clone();
+        serde_json::from_str(&text).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_hybrid_mode_with_reranker() {
+        let fts_items = vec![
+            make_titled_fts_result("fused-first", "Unrelated topic", 0.9),
+            make_titled_fts_result("fused-second", "Voice leading basics", 0.5),
+        ];
+        let tools = make_tools_with_vector(fts_items, &["fused-first", "fused-second"])
+            .with_reranker(Arc::new(fabryk_vector::MockReranker::new()), 5);
+
+        let results = hybrid_call(
+            &tools,
+            serde_json::json!({"query": "voice leading", "limit": 1}),
+        )
+        .await;
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].id, "fused-second");
+        assert_eq!(results[0].rerank_score, Some(1.0));
+        assert!(results[0].vector_score.is_some());
+        assert!(results[0].keyword_score.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_hybrid_mode_reranker_sees_top_k_candidates() {
+        let tools = make_tools_with_vector(vec![], &["first", "second", "third", "voice-leading"])
+            .with_reranker(Arc::new(fabryk_vector::MockReranker::new()), 5);
+
+        let results = hybrid_call(
+            &tools,
+            serde_json::json!({"query": "voice leading", "limit": 1}),
+        )
+        .await;
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].id, "voice-leading");
+        assert_eq!(results[0].rerank_score, Some(1.0));
+    }
+
+    #[tokio::test]
+    async fn test_hybrid_mode_rerank_disabled_per_call() {
+        let fts_items = vec![
+            make_titled_fts_result("fused-first", "Unrelated topic", 0.9),
+            make_titled_fts_result("fused-second", "Voice leading basics", 0.5),
+        ];
+        let tools = make_tools_with_vector(fts_items, &["fused-first", "fused-second"])
+            .with_reranker(Arc::new(fabryk_vector::MockReranker::new()), 5);
+
+        let results = hybrid_call(
+            &tools,
+            serde_json::json!({"query": "voice leading", "rerank": false}),
+        )
+        .await;
+
+        assert_eq!(results[0].id, "fused-first");
+        assert!(results.iter().all(|r| r.rerank_score.is_none()));
+    }
+
+    #[test]
+    fn test_rerank_text_fallbacks() {
+        let texts = HashMap::from([("fts".to_string(), "FTS text".to_string())]);
+        let mut result = HybridSearchResult {
+            id: "fts".to_string(),
+            score: 0.1,
+            source: "hybrid".to_string(),
+            vector_score: None,
+            keyword_score: None,
+            rerank_score: None,
+            metadata: HashMap::new(),
+        };
+        assert_eq!(rerank_text(&texts, &result), "FTS text");
+
+        result.id = "vec".to_string();
+        assert_eq!(rerank_text(&texts, &result), "vec");
+
+        result
+            .metadata
+            .insert("title".to_string(), "Vector title".to_string());
+        assert_eq!(rerank_text(&texts, &result), "Vector title");
+    }
+
     // -- Argument validation -----------------------------------------------
 
     #[tokio::test]
@@ -765,12 +976,18 @@ mod tests {
             id: "doc-1".to_string(),
             score: 0.5,
             source: "hybrid".to_string(),
+            vector_score: Some(0.9),
+            keyword_score: Some(3.2),
+            rerank_score: Some(0.7),
             metadata: HashMap::new(),
         };
         let result = HybridResult::from(search_result);
         assert_eq!(result.id, "doc-1");
         assert!((result.rrf_score - 0.5).abs() < f32::EPSILON);
         assert_eq!(result.source, "hybrid");
+        assert_eq!(result.vector_score, Some(0.9));
+        assert_eq!(result.keyword_score, Some(3.2));
+        assert_eq!(result.rerank_score, Some(0.7));
     }
 
     #[test]
@@ -779,6 +996,9 @@ mod tests {
             id: "test-id".to_string(),
             rrf_score: 0.5,
             source: "keyword".to_string(),
+            vector_score: None,
+            keyword_score: None,
+            rerank_score: None,
             metadata: HashMap::new(),
         };
         let json = serde_json::to_string(&result).unwrap();
@@ -794,6 +1014,9 @@ mod tests {
             id: "x".to_string(),
             rrf_score: 1.0,
             source: "vector".to_string(),
+            vector_score: None,
+            keyword_score: None,
+            rerank_score: None,
             metadata: HashMap::new(),
         };
         let cloned = result.clone();
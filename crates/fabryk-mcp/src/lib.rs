@@ -8,8 +8,10 @@
 // remain available at `fabryk_mcp::`.
 pub use fabryk_mcp_core::*;
 
+pub use fabryk_mcp_audit as audit;
 pub use fabryk_mcp_auth as auth;
 pub use fabryk_mcp_content as content;
 pub use fabryk_mcp_fts as fts;
 pub use fabryk_mcp_graph as graph;
+pub use fabryk_mcp_ratelimit as ratelimit;
 pub use fabryk_mcp_semantic as semantic;
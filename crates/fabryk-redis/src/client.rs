@@ -50,6 +50,35 @@ impl RedisOps for RedisClient {
             .map_err(|e| RedisError::Command(e.to_string()))
     }
 
+    async fn expire(&self, key: &str, seconds: u64) -> Result<(), RedisError> {
+        redis::cmd("EXPIRE")
+            .arg(key)
+            .arg(seconds)
+            .exec_async(&mut self.conn.clone())
+            .await
+            .map_err(|e| RedisError::Command(e.to_string()))
+    }
+
+    async fn xadd(
+        &self,
+        stream: &str,
+        fields: &[(&str, &str)],
+        max_len: Option<u64>,
+    ) -> Result<String, RedisError> {
+        let mut cmd = redis::cmd("XADD");
+        cmd.arg(stream);
+        if let Some(max_len) = max_len {
+            cmd.arg("MAXLEN").arg("~").arg(max_len);
+        }
+        cmd.arg("*");
+        for (field, value) in fields {
+            cmd.arg(*field).arg(*value);
+        }
+        cmd.query_async(&mut self.conn.clone())
+            .await
+            .map_err(|e| RedisError::Command(e.to_string()))
+    }
+
     async fn get_u64(&self, key: &str) -> Result<u64, RedisError> {
         let val: Option<String> = redis::cmd("GET")
             .arg(key)
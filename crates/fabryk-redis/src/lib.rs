@@ -15,7 +15,7 @@ mod mock;
 
 pub use client::RedisClient;
 pub use error::RedisError;
-pub use mock::MockRedis;
+pub use mock::{MockRedis, StreamEntry};
 
 use async_trait::async_trait;
 use serde::Serialize;
@@ -36,6 +36,19 @@ pub trait RedisOps: Send + Sync + std::fmt::Debug {
     /// Increment a key's integer value by the given amount. Returns the new value.
     async fn incr_by(&self, key: &str, amount: u64) -> Result<u64, RedisError>;
 
+    /// Set a key to expire after the given number of seconds.
+    async fn expire(&self, key: &str, seconds: u64) -> Result<(), RedisError>;
+
+    /// Append an entry to a stream (`XADD`). Returns the entry ID.
+    ///
+    /// With `max_len`, the stream is trimmed to roughly that many entries.
+    async fn xadd(
+        &self,
+        stream: &str,
+        fields: &[(&str, &str)],
+        max_len: Option<u64>,
+    ) -> Result<String, RedisError>;
+
     /// Get a key's integer value. Returns 0 if key doesn't exist.
     async fn get_u64(&self, key: &str) -> Result<u64, RedisError>;
 
@@ -121,6 +134,27 @@ mod tests {
         assert_eq!(v2, 15);
     }
 
+    #[tokio::test]
+    async fn test_mock_redis_expire_records_ttl() {
+        let redis = MockRedis::new();
+        redis.incr_by("window", 1).await.unwrap();
+        redis.expire("window", 120).await.unwrap();
+        assert_eq!(redis.ttl("window"), Some(120));
+        assert_eq!(redis.ttl("missing"), None);
+    }
+
+    #[tokio::test]
+    async fn test_mock_redis_xadd_trims_to_max_len() {
+        let redis = MockRedis::new();
+        for n in ["1", "2", "3"] {
+            redis.xadd("events", &[("n", n)], Some(2)).await.unwrap();
+        }
+        let entries = redis.stream("events");
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0], vec![("n".to_string(), "2".to_string())]);
+        assert!(redis.stream("missing").is_empty());
+    }
+
     #[tokio::test]
     async fn test_mock_redis_get_u64_default() {
         let redis = MockRedis::new();
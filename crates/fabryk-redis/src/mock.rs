@@ -11,18 +11,42 @@ use crate::error::RedisError;
 /// In-memory mock Redis backed by a `HashMap<String, String>`.
 ///
 /// Useful for unit tests that need Redis operations without a real server.
+/// Expiry times are recorded but never enforced. Streams are kept
+/// separately from string keys and trimmed exactly to `max_len`.
 #[derive(Debug)]
 pub struct MockRedis {
     store: Mutex<HashMap<String, String>>,
+    ttls: Mutex<HashMap<String, u64>>,
+    streams: Mutex<HashMap<String, Vec<StreamEntry>>>,
 }
 
+/// Fields of one stream entry, in insertion order.
+pub type StreamEntry = Vec<(String, String)>;
+
 impl MockRedis {
     /// Create an empty mock Redis store.
     pub fn new() -> Self {
         Self {
             store: Mutex::new(HashMap::new()),
+            ttls: Mutex::new(HashMap::new()),
+            streams: Mutex::new(HashMap::new()),
         }
     }
+
+    /// The expiry last set on a key with `expire`, in seconds.
+    pub fn ttl(&self, key: &str) -> Option<u64> {
+        self.ttls.lock().unwrap().get(key).copied()
+    }
+
+    /// The entries of a stream, oldest first.
+    pub fn stream(&self, key: &str) -> Vec<StreamEntry> {
+        self.streams
+            .lock()
+            .unwrap()
+            .get(key)
+            .cloned()
+            .unwrap_or_default()
+    }
 }
 
 impl Default for MockRedis {
@@ -54,6 +78,35 @@ impl RedisOps for MockRedis {
         Ok(new_val)
     }
 
+    async fn expire(&self, key: &str, seconds: u64) -> Result<(), RedisError> {
+        if self.store.lock().unwrap().contains_key(key) {
+            self.ttls.lock().unwrap().insert(key.to_string(), seconds);
+        }
+        Ok(())
+    }
+
+    async fn xadd(
+        &self,
+        stream: &str,
+        fields: &[(&str, &str)],
+        max_len: Option<u64>,
+    ) -> Result<String, RedisError> {
+        let mut streams = self.streams.lock().unwrap();
+        let entries = streams.entry(stream.to_string()).or_default();
+        entries.push(
+            fields
+                .iter()
+                .map(|(f, v)| (f.to_string(), v.to_string()))
+                .collect(),
+        );
+        let id = format!("{}-0", entries.len());
+        if let Some(max_len) = max_len {
+            let excess = entries.len().saturating_sub(max_len as usize);
+            entries.drain(..excess);
+        }
+        Ok(id)
+    }
+
     async fn get_u64(&self, key: &str) -> Result<u64, RedisError> {
         let store = self.store.lock().unwrap();
         match store.get(key) {
@@ -0,0 +1,28 @@
+//! Google Cloud Storage content store for Fabryk.
+//!
+//! Provides [`GcsContentStore`], a [`fabryk_core::ContentStore`] that
+//! serves the objects under a bucket prefix, e.g. the output of an ECL
+//! pipeline with a GCS sink. Credentials are resolved by the ECL GCS
+//! adapter's [`TokenProvider`](ecl_adapter_gcs::auth::TokenProvider):
+//! a service account key file, a bearer token in an environment variable,
+//! or Application Default Credentials (including the metadata server on
+//! Cloud Run).
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use std::sync::Arc;
+//! use fabryk_graph::GraphBuilder;
+//! use fabryk_store_gcs::{CredentialRef, GcsContentStore};
+//!
+//! let store = GcsContentStore::new("kb-content", "concepts/", CredentialRef::ApplicationDefault);
+//! let (graph, stats) = GraphBuilder::new(extractor)
+//!     .with_content_store(Arc::new(store))
+//!     .build()
+//!     .await?;
+//! ```
+
+mod store;
+
+pub use ecl_pipeline_spec::CredentialRef;
+pub use store::GcsContentStore;
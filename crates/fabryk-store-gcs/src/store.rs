@@ -0,0 +1,348 @@
+//! Content store backed by a GCS bucket prefix.
+
+use std::time::SystemTime;
+
+use async_trait::async_trait;
+use ecl_adapter_gcs::auth::TokenProvider;
+use ecl_adapter_gcs::error::GcsAdapterError;
+use ecl_adapter_gcs::types::{GCS_API_BASE_URL, GcsObject, ObjectListResponse};
+use ecl_pipeline_spec::CredentialRef;
+use fabryk_core::store::{ContentEntry, ContentStore, validate_key};
+use fabryk_core::{Error, Result};
+use reqwest::{StatusCode, Url};
+
+/// Object fields requested from the JSON API.
+const OBJECT_FIELDS: &str = "name,size,updated,generation";
+
+/// Content in a Google Cloud Storage bucket.
+///
+/// Keys are object names relative to the prefix; "directory" placeholder
+/// objects (names ending in `/`) are skipped. Each entry's version is the
+/// object generation, so overwrites are detected even within a second.
+#[derive(Debug)]
+pub struct GcsContentStore {
+    bucket: String,
+    prefix: String,
+    http_client: reqwest::Client,
+    token_provider: TokenProvider,
+    base_url: String,
+}
+
+impl GcsContentStore {
+    /// Serve the objects in `bucket` under `prefix`.
+    ///
+    /// A non-empty prefix is treated as a directory: `concepts` and
+    /// `concepts/` both serve `concepts/cadence.md` as `cadence.md`.
+    pub fn new(
+        bucket: impl Into<String>,
+        prefix: impl Into<String>,
+        credentials: CredentialRef,
+    ) -> Self {
+        let mut prefix: String = prefix.into().trim_start_matches('/').to_string();
+        if !prefix.is_empty() && !prefix.ends_with('/') {
+            prefix.push('/');
+        }
+        let http_client = reqwest::Client::new();
+        Self {
+            bucket: bucket.into(),
+            prefix,
+            token_provider: TokenProvider::new(credentials, http_client.clone()),
+            http_client,
+            base_url: GCS_API_BASE_URL.to_string(),
+        }
+    }
+
+    /// Override the GCS API base URL (for testing).
+    pub fn with_base_url(mut self, url: impl Into<String>) -> Self {
+        self.base_url = url.into();
+        self
+    }
+
+    /// Override the token provider (e.g., a static token for testing).
+    pub fn with_token_provider(mut self, provider: TokenProvider) -> Self {
+        self.token_provider = provider;
+        self
+    }
+
+    /// The bucket name.
+    pub fn bucket(&self) -> &str {
+        &self.bucket
+    }
+
+    /// The object name prefix, with a trailing `/` unless empty.
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    /// `{base}/b/{bucket}/o`, plus the object name if given.
+    fn url(&self, object: Option<&str>) -> Result<Url> {
+        let mut url = Url::parse(&self.base_url)
+            .map_err(|e| Error::config(format!("Invalid GCS base URL {}: {e}", self.base_url)))?;
+        {
+            let mut segments = url
+                .path_segments_mut()
+                .map_err(|_| Error::config(format!("Invalid GCS base URL {}", self.base_url)))?;
+            segments
+                .pop_if_empty()
+                .extend(["b", self.bucket.as_str(), "o"]);
+            if let Some(object) = object {
+                segments.push(object);
+            }
+        }
+        Ok(url)
+    }
+
+    async fn send(&self, url: Url) -> Result<reqwest::Response> {
+        let token = self
+            .token_provider
+            .get_token()
+            .await
+            .map_err(|e| gcs_error(&self.bucket, e))?;
+        self.http_client
+            .get(url)
+            .bearer_auth(token)
+            .send()
+            .await
+            .map_err(|e| gcs_error(&self.bucket, e.into()))
+    }
+
+    /// Convert an object to an entry, or `None` for placeholders and
+    /// objects outside the prefix.
+    fn entry(&self, object: &GcsObject) -> Option<ContentEntry> {
+        let key = object.name.strip_prefix(&self.prefix)?;
+        if key.is_empty() || key.ends_with('/') {
+            return None;
+        }
+        let size = object
+            .size
+            .as_deref()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(0);
+        let mut entry = ContentEntry::new(key, size);
+        if let Some(modified) = object.updated.as_deref().and_then(parse_time) {
+            entry = entry.with_modified(modified);
+        }
+        if let Some(ref generation) = object.generation {
+            entry = entry.with_version(generation.clone());
+        }
+        Some(entry)
+    }
+}
+
+/// Parse an RFC 3339 timestamp from the JSON API.
+fn parse_time(value: &str) -> Option<SystemTime> {
+    chrono::DateTime::parse_from_rfc3339(value)
+        .ok()
+        .map(SystemTime::from)
+}
+
+fn gcs_error(bucket: &str, err: GcsAdapterError) -> Error {
+    Error::operation(format!("GCS bucket {bucket}: {err}"))
+}
+
+/// Fail on an unsuccessful response.
+async fn check(bucket: &str, response: reqwest::Response) -> Result<reqwest::Response> {
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response);
+    }
+    let message = response.text().await.unwrap_or_default();
+    Err(gcs_error(
+        bucket,
+        GcsAdapterError::ApiError {
+            status: status.as_u16(),
+            message,
+        },
+    ))
+}
+
+#[async_trait]
+impl ContentStore for GcsContentStore {
+    fn location(&self) -> String {
+        format!("gs://{}/{}", self.bucket, self.prefix)
+            .trim_end_matches('/')
+            .to_string()
+    }
+
+    async fn list(&self) -> Result<Vec<ContentEntry>> {
+        let mut entries = Vec::new();
+        let mut page_token: Option<String> = None;
+
+        loop {
+            let mut url = self.url(None)?;
+            {
+                let mut query = url.query_pairs_mut();
+                if !self.prefix.is_empty() {
+                    query.append_pair("prefix", &self.prefix);
+                }
+                if let Some(ref token) = page_token {
+                    query.append_pair("pageToken", token);
+                }
+                query.append_pair("fields", &format!("items({OBJECT_FIELDS}),nextPageToken"));
+            }
+
+            let response = check(&self.bucket, self.send(url).await?).await?;
+            let page: ObjectListResponse = response
+                .json()
+                .await
+                .map_err(|e| gcs_error(&self.bucket, e.into()))?;
+            entries.extend(page.items.iter().filter_map(|object| self.entry(object)));
+
+            match page.next_page_token {
+                Some(token) => page_token = Some(token),
+                None => break,
+            }
+        }
+
+        entries.sort_by(|a, b| a.key.cmp(&b.key));
+        Ok(entries)
+    }
+
+    async fn get(&self, key: &str) -> Result<Vec<u8>> {
+        validate_key(key)?;
+        let mut url = self.url(Some(&format!("{}{key}", self.prefix)))?;
+        url.query_pairs_mut().append_pair("alt", "media");
+
+        let response = self.send(url).await?;
+        if response.status() == StatusCode::NOT_FOUND {
+            return Err(Error::not_found("content", key));
+        }
+        let response = check(&self.bucket, response).await?;
+        let bytes = response
+            .bytes()
+            .await
+            .map_err(|e| gcs_error(&self.bucket, e.into()))?;
+        Ok(bytes.to_vec())
+    }
+
+    async fn stat(&self, key: &str) -> Result<Option<ContentEntry>> {
+        validate_key(key)?;
+        let mut url = self.url(Some(&format!("{}{key}", self.prefix)))?;
+        url.query_pairs_mut().append_pair("fields", OBJECT_FIELDS);
+
+        let response = self.send(url).await?;
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        let response = check(&self.bucket, response).await?;
+        let object: GcsObject = response
+            .json()
+            .await
+            .map_err(|e| gcs_error(&self.bucket, e.into()))?;
+        Ok(self.entry(&object))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn store(server: &MockServer) -> GcsContentStore {
+        GcsContentStore::new("kb", "content", CredentialRef::ApplicationDefault)
+            .with_base_url(server.uri())
+            .with_token_provider(TokenProvider::static_token("test-token".to_string()))
+    }
+
+    #[test]
+    fn test_location_and_prefix() {
+        let store = GcsContentStore::new("kb", "/content", CredentialRef::ApplicationDefault);
+        assert_eq!(store.prefix(), "content/");
+        assert_eq!(store.location(), "gs://kb/content");
+
+        let store = GcsContentStore::new("kb", "", CredentialRef::ApplicationDefault);
+        assert_eq!(store.prefix(), "");
+        assert_eq!(store.location(), "gs://kb");
+    }
+
+    #[tokio::test]
+    async fn test_list_paginates_and_strips_prefix() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/b/kb/o"))
+            .and(query_param("prefix", "content/"))
+            .and(query_param("pageToken", "page-2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "items": [
+                    { "name": "content/a.md", "bucket": "kb", "size": "5", "generation": "7" }
+                ]
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/b/kb/o"))
+            .and(query_param("prefix", "content/"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "items": [
+                    {
+                        "name": "content/guides/b.md",
+                        "bucket": "kb",
+                        "size": "12",
+                        "updated": "2026-03-15T10:00:00Z",
+                        "generation": "3"
+                    },
+                    { "name": "content/guides/", "bucket": "kb" }
+                ],
+                "nextPageToken": "page-2"
+            })))
+            .mount(&server)
+            .await;
+
+        let entries = store(&server).list().await.unwrap();
+        let keys: Vec<&str> = entries.iter().map(|e| e.key.as_str()).collect();
+        assert_eq!(keys, ["a.md", "guides/b.md"]);
+        assert_eq!(entries[0].size, 5);
+        assert_eq!(entries[0].version.as_deref(), Some("7"));
+        assert_eq!(entries[1].modified_secs(), 1_773_568_800);
+    }
+
+    #[tokio::test]
+    async fn test_get_and_stat() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/b/kb/o/content%2Fguides%2Fb.md"))
+            .and(query_param("alt", "media"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("# B\n"))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/b/kb/o/content%2Fguides%2Fb.md"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "name": "content/guides/b.md",
+                "bucket": "kb",
+                "size": "4"
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&server)
+            .await;
+
+        let store = store(&server);
+        assert_eq!(store.get_string("guides/b.md").await.unwrap(), "# B\n");
+        assert_eq!(store.stat("guides/b.md").await.unwrap().unwrap().size, 4);
+        assert_eq!(store.stat("missing.md").await.unwrap(), None);
+        assert!(store.get("missing.md").await.unwrap_err().is_not_found());
+        assert!(
+            store
+                .get("../other/x.md")
+                .await
+                .unwrap_err()
+                .is_path_error()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_api_error() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(403).set_body_string("denied"))
+            .mount(&server)
+            .await;
+
+        let err = store(&server).list().await.unwrap_err();
+        assert!(err.to_string().contains("403"), "{err}");
+    }
+}
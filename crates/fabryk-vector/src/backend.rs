@@ -7,16 +7,19 @@
 //! # Backends
 //!
 //! - `LancedbBackend`: Vector search with LanceDB (requires `vector-lancedb` feature)
-//! - `SimpleVectorBackend`: In-memory brute-force fallback for small collections
+//! - `SimpleVectorBackend`: In-memory backend with brute-force search, or an
+//!   optional HNSW index for larger collections
 
 use async_trait::async_trait;
 use fabryk_core::{Error, Result};
 use serde::{Deserialize, Serialize};
 
 use crate::embedding::EmbeddingProvider;
+use crate::hnsw::{HnswConfig, HnswIndex};
 use crate::types::{
     EmbeddedDocument, VectorConfig, VectorSearchParams, VectorSearchResult, VectorSearchResults,
 };
+use std::collections::HashSet;
 use std::path::Path;
 use std::sync::Arc;
 
@@ -24,7 +27,7 @@ use std::sync::Arc;
 ///
 /// Implementations provide different vector search strategies:
 /// - `LancedbBackend`: Approximate nearest neighbor with LanceDB
-/// - `SimpleVectorBackend`: Brute-force cosine similarity (fallback)
+/// - `SimpleVectorBackend`: Brute-force or HNSW cosine similarity (in-memory)
 ///
 /// # Async
 ///
@@ -55,7 +58,8 @@ pub trait VectorBackend: Send + Sync {
 ///
 /// Selection logic:
 /// 1. If `vector-lancedb` feature enabled and config says "lancedb" → `LancedbBackend`
-/// 2. Otherwise → `SimpleVectorBackend` (brute-force fallback)
+/// 2. Otherwise → `SimpleVectorBackend`, with an HNSW index if
+///    `index_type` is "hnsw"
 ///
 /// Note: This creates an empty backend. Use `VectorIndexBuilder` to populate it.
 pub fn create_vector_backend(
@@ -74,7 +78,18 @@ pub fn create_vector_backend(
             log::info!("LanceDB requested but requires async build(); returning simple backend");
             Ok(Box::new(SimpleVectorBackend::new(provider)))
         }
-        _ => Ok(Box::new(SimpleVectorBackend::new(provider))),
+        _ => {
+            let backend = SimpleVectorBackend::new(provider);
+            match config.index_type.as_str() {
+                "hnsw" => Ok(Box::new(
+                    backend.with_hnsw(HnswConfig::from_vector_config(config)),
+                )),
+                "flat" => Ok(Box::new(backend)),
+                other => Err(Error::config(format!(
+                    "Unknown vector index type: '{other}'. Supported: flat, hnsw"
+                ))),
+            }
+        }
     }
 }
 
@@ -87,6 +102,8 @@ pub fn create_vector_backend(
 struct VectorCache {
     content_hash: String,
     documents: Vec<EmbeddedDocument>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    hnsw: Option<HnswIndex>,
 }
 
 /// Lightweight header for checking freshness without loading all documents.
@@ -95,11 +112,21 @@ struct VectorCacheHeader {
     content_hash: String,
 }
 
-/// Brute-force vector search backend.
+/// In-memory vector search backend.
 ///
 /// Stores documents in memory and computes cosine similarity for each query.
 /// Used as a fallback when LanceDB is not available or for small collections.
 ///
+/// # HNSW Index
+///
+/// [`with_hnsw`](Self::with_hnsw) adds an approximate nearest-neighbor
+/// index for mid-size collections. Inserts via
+/// [`add_documents`](Self::add_documents) update the index incrementally;
+/// [`remove_documents`](Self::remove_documents) tombstones entries and
+/// compacts once half the index is tombstones. Category and metadata
+/// filters are applied during graph traversal, and searches fall back to
+/// exact scoring when few documents match.
+///
 /// # Caching
 ///
 /// Supports cache persistence via [`save_cache`](Self::save_cache) and
@@ -108,11 +135,12 @@ struct VectorCacheHeader {
 ///
 /// # Limitations
 ///
-/// - O(n) search time
+/// - O(n) search time without an HNSW index
 /// - All documents must fit in memory
 pub struct SimpleVectorBackend {
     provider: Arc<dyn EmbeddingProvider>,
     documents: Vec<EmbeddedDocument>,
+    hnsw: Option<HnswIndex>,
 }
 
 impl SimpleVectorBackend {
@@ -121,12 +149,96 @@ impl SimpleVectorBackend {
         Self {
             provider,
             documents: Vec::new(),
+            hnsw: None,
+        }
+    }
+
+    /// Index documents with HNSW.
+    ///
+    /// Builds the index over any documents already present. If an index
+    /// with the same graph parameters exists, only its search knobs
+    /// (`ef_search`, `exact_search_threshold`) are updated.
+    pub fn with_hnsw(mut self, config: HnswConfig) -> Self {
+        match self.hnsw {
+            Some(ref mut index) if index.config().same_graph(&config) => {
+                index.set_search_config(config);
+            }
+            _ => {
+                self.compact();
+                self.hnsw = Some(HnswIndex::build(config, &self.documents));
+            }
         }
+        self
+    }
+
+    /// The HNSW configuration, if the backend has an index.
+    pub fn hnsw_config(&self) -> Option<&HnswConfig> {
+        self.hnsw.as_ref().map(HnswIndex::config)
     }
 
     /// Add documents to the backend.
     pub fn add_documents(&mut self, documents: Vec<EmbeddedDocument>) {
         self.documents.extend(documents);
+        if let Some(ref mut index) = self.hnsw {
+            index.extend(&self.documents);
+        }
+    }
+
+    /// Remove documents by ID.
+    ///
+    /// Returns the number of documents removed.
+    pub fn remove_documents(&mut self, ids: &[&str]) -> usize {
+        let ids: HashSet<&str> = ids.iter().copied().collect();
+
+        let Some(ref mut index) = self.hnsw else {
+            let before = self.documents.len();
+            self.documents
+                .retain(|doc| !ids.contains(doc.document.id.as_str()));
+            return before - self.documents.len();
+        };
+
+        let mut removed = 0;
+        for (slot, doc) in self.documents.iter().enumerate() {
+            if ids.contains(doc.document.id.as_str()) && index.mark_deleted(slot) {
+                removed += 1;
+            }
+        }
+
+        if index.deleted_count() * 2 > index.len() {
+            self.compact();
+        }
+        removed
+    }
+
+    /// Drop removed documents and rebuild the HNSW index without them.
+    ///
+    /// A no-op without an index or when nothing has been removed.
+    pub fn compact(&mut self) {
+        let Some(index) = self.hnsw.take() else {
+            return;
+        };
+        if index.deleted_count() == 0 {
+            self.hnsw = Some(index);
+            return;
+        }
+
+        let mut slot = 0;
+        self.documents.retain(|_| {
+            let keep = !index.is_deleted(slot);
+            slot += 1;
+            keep
+        });
+        self.hnsw = Some(HnswIndex::build(index.config().clone(), &self.documents));
+        log::debug!(
+            "Compacted HNSW index: {} tombstones dropped",
+            index.deleted_count()
+        );
+    }
+
+    fn is_live(&self, slot: usize) -> bool {
+        self.hnsw
+            .as_ref()
+            .is_none_or(|index| !index.is_deleted(slot))
     }
 
     /// Save the backend's documents to a cache file.
@@ -137,6 +249,7 @@ impl SimpleVectorBackend {
         let cache = VectorCache {
             content_hash: content_hash.to_string(),
             documents: self.documents.clone(),
+            hnsw: self.hnsw.clone(),
         };
 
         // Ensure parent directory exists
@@ -176,6 +289,17 @@ impl SimpleVectorBackend {
 
         let mut backend = Self::new(provider);
         backend.documents = cache.documents;
+        backend.hnsw = match cache.hnsw {
+            Some(index) if index.len() == backend.documents.len() => Some(index),
+            Some(_) => {
+                log::warn!(
+                    "Discarding HNSW index in {}: node count does not match documents",
+                    path.display()
+                );
+                None
+            }
+            None => None,
+        };
 
         log::info!(
             "Loaded vector cache: {} documents from {}",
@@ -203,7 +327,7 @@ impl SimpleVectorBackend {
     }
 
     /// Compute cosine similarity between two vectors.
-    fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    pub(crate) fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
         if a.len() != b.len() || a.is_empty() {
             return 0.0;
         }
@@ -231,35 +355,57 @@ impl VectorBackend for SimpleVectorBackend {
         let limit = params.limit.unwrap_or(10);
         let threshold = params.similarity_threshold.unwrap_or(0.0);
 
-        let mut scored: Vec<(usize, f32)> = self
-            .documents
-            .iter()
-            .enumerate()
-            .map(|(i, doc)| {
-                let sim = Self::cosine_similarity(&query_embedding, &doc.embedding);
-                (i, sim)
-            })
-            .filter(|(_, sim)| *sim >= threshold)
-            .collect();
-
-        // Filter by category if specified
-        if let Some(ref category) = params.category {
-            scored.retain(|(i, _)| {
-                self.documents[*i].document.category.as_deref() == Some(category.as_str())
-            });
-        }
+        let matches_filters = |slot: usize| {
+            let doc = &self.documents[slot].document;
+            params
+                .category
+                .as_ref()
+                .is_none_or(|category| doc.category.as_deref() == Some(category.as_str()))
+                && params
+                    .metadata_filters
+                    .iter()
+                    .all(|(key, value)| doc.metadata.get(key) == Some(value))
+        };
+        let exact = |slots: &mut dyn Iterator<Item = usize>| -> Vec<(usize, f32)> {
+            slots
+                .map(|i| {
+                    let sim =
+                        Self::cosine_similarity(&query_embedding, &self.documents[i].embedding);
+                    (i, sim)
+                })
+                .collect()
+        };
 
-        // Filter by metadata
-        for (key, value) in &params.metadata_filters {
-            scored.retain(|(i, _)| {
-                self.documents[*i]
-                    .document
-                    .metadata
-                    .get(key)
-                    .map(|v| v == value)
-                    .unwrap_or(false)
-            });
-        }
+        let mut scored: Vec<(usize, f32)> = match self.hnsw {
+            Some(ref index) if self.document_count()? > index.config().exact_search_threshold => {
+                let ef = params.ef_search.unwrap_or(index.config().ef_search);
+                let has_filters = params.category.is_some() || !params.metadata_filters.is_empty();
+
+                // Pre-filter: when few documents match, scoring them exactly
+                // is cheaper and more accurate than a filtered graph walk.
+                let matching: Option<Vec<usize>> = has_filters.then(|| {
+                    (0..self.documents.len())
+                        .filter(|&i| !index.is_deleted(i) && matches_filters(i))
+                        .collect()
+                });
+                match matching {
+                    Some(slots) if slots.len() <= index.config().exact_search_threshold => {
+                        exact(&mut slots.into_iter())
+                    }
+                    _ => index.search(
+                        &query_embedding,
+                        &self.documents,
+                        limit,
+                        ef,
+                        &matches_filters,
+                    ),
+                }
+            }
+            _ => exact(
+                &mut (0..self.documents.len()).filter(|&i| self.is_live(i) && matches_filters(i)),
+            ),
+        };
+        scored.retain(|(_, sim)| *sim >= threshold);
 
         // Sort by similarity (highest first)
         scored.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
@@ -292,7 +438,8 @@ impl VectorBackend for SimpleVectorBackend {
     }
 
     fn document_count(&self) -> Result<usize> {
-        Ok(self.documents.len())
+        let deleted = self.hnsw.as_ref().map_or(0, HnswIndex::deleted_count);
+        Ok(self.documents.len() - deleted)
     }
 }
 
@@ -300,6 +447,7 @@ impl std::fmt::Debug for SimpleVectorBackend {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("SimpleVectorBackend")
             .field("documents", &self.documents.len())
+            .field("hnsw", &self.hnsw_config())
             .finish()
     }
 }
@@ -483,6 +631,165 @@ mod tests {
         assert_eq!(results.items.len(), 5);
     }
 
+    // ================================================================
+    // HNSW tests
+    // ================================================================
+
+    /// Documents spread around the unit circle in the first two dimensions.
+    fn circle_documents(count: usize) -> Vec<EmbeddedDocument> {
+        (0..count)
+            .map(|i| {
+                let angle = i as f32 / count as f32 * std::f32::consts::TAU;
+                let tier = if i % 10 == 0 { "rare" } else { "common" };
+                EmbeddedDocument::new(
+                    VectorDocument::new(format!("doc-{i}"), format!("text {i}"))
+                        .with_metadata("tier", tier),
+                    vec![angle.cos(), angle.sin(), 0.0, 0.0],
+                )
+            })
+            .collect()
+    }
+
+    fn hnsw_backend(count: usize) -> SimpleVectorBackend {
+        let provider = Arc::new(MockEmbeddingProvider::new(4));
+        let mut backend = SimpleVectorBackend::new(provider)
+            .with_hnsw(HnswConfig::default().with_exact_search_threshold(10));
+        backend.add_documents(circle_documents(count));
+        backend
+    }
+
+    #[tokio::test]
+    async fn test_hnsw_search_matches_flat() {
+        let hnsw = hnsw_backend(300);
+        let mut flat = SimpleVectorBackend::new(Arc::new(MockEmbeddingProvider::new(4)));
+        flat.add_documents(circle_documents(300));
+
+        let params = VectorSearchParams::new("query").with_limit(5);
+        let expected = flat.search(params.clone()).await.unwrap();
+        let results = hnsw.search(params).await.unwrap();
+
+        let ids =
+            |r: &VectorSearchResults| r.items.iter().map(|i| i.id.clone()).collect::<Vec<_>>();
+        assert_eq!(ids(&results), ids(&expected));
+        assert_eq!(results.backend, "simple");
+    }
+
+    #[tokio::test]
+    async fn test_hnsw_search_with_metadata_filter() {
+        let backend = hnsw_backend(300);
+
+        // 30 "rare" documents exceed the exact threshold: filtered graph walk
+        let params = VectorSearchParams::new("query")
+            .with_limit(5)
+            .with_filter("tier", "rare");
+        let results = backend.search(params).await.unwrap();
+        assert_eq!(results.items.len(), 5);
+        assert!(results.items.iter().all(|r| r.metadata["tier"] == "rare"));
+
+        // No matches at all: exact path over an empty set
+        let params = VectorSearchParams::new("query").with_filter("tier", "missing");
+        assert!(backend.search(params).await.unwrap().items.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_hnsw_remove_documents() {
+        let mut backend = hnsw_backend(100);
+        let top = backend
+            .search(VectorSearchParams::new("query").with_limit(1))
+            .await
+            .unwrap()
+            .items[0]
+            .id
+            .clone();
+
+        assert_eq!(backend.remove_documents(&[top.as_str(), "nonexistent"]), 1);
+        assert_eq!(backend.remove_documents(&[top.as_str()]), 0);
+        assert_eq!(backend.document_count().unwrap(), 99);
+
+        let results = backend
+            .search(VectorSearchParams::new("query").with_limit(10))
+            .await
+            .unwrap();
+        assert!(results.items.iter().all(|r| r.id != top));
+    }
+
+    #[test]
+    fn test_hnsw_compacts_after_many_removals() {
+        let mut backend = hnsw_backend(20);
+        let ids: Vec<String> = (0..11).map(|i| format!("doc-{i}")).collect();
+        let refs: Vec<&str> = ids.iter().map(String::as_str).collect();
+
+        assert_eq!(backend.remove_documents(&refs), 11);
+        assert_eq!(backend.documents.len(), 9);
+        assert_eq!(backend.hnsw.as_ref().unwrap().deleted_count(), 0);
+        assert_eq!(backend.document_count().unwrap(), 9);
+    }
+
+    #[test]
+    fn test_flat_remove_documents() {
+        let mut backend = SimpleVectorBackend::new(mock_provider());
+        backend.add_documents(circle_documents(3));
+        assert_eq!(backend.remove_documents(&["doc-1"]), 1);
+        assert_eq!(backend.document_count().unwrap(), 2);
+    }
+
+    #[test]
+    fn test_with_hnsw_same_graph_keeps_index() {
+        let backend = hnsw_backend(50);
+        let backend = backend.with_hnsw(
+            HnswConfig::default()
+                .with_exact_search_threshold(10)
+                .with_ef_search(7),
+        );
+        assert_eq!(backend.hnsw_config().unwrap().ef_search, 7);
+
+        let backend = backend.with_hnsw(HnswConfig::default().with_m(4));
+        assert_eq!(backend.hnsw_config().unwrap().m, 4);
+        assert_eq!(backend.document_count().unwrap(), 50);
+    }
+
+    #[test]
+    fn test_hnsw_save_and_load_cache() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache_path = dir.path().join("hnsw-cache.json");
+        let mut backend = hnsw_backend(40);
+        backend.remove_documents(&["doc-3"]);
+        backend.save_cache(&cache_path, "hash").unwrap();
+
+        let loaded = SimpleVectorBackend::load_cache(&cache_path, mock_provider())
+            .unwrap()
+            .unwrap();
+        assert_eq!(loaded.hnsw_config(), backend.hnsw_config());
+        assert_eq!(loaded.document_count().unwrap(), 39);
+        assert!(SimpleVectorBackend::is_cache_fresh(&cache_path, "hash"));
+    }
+
+    #[test]
+    fn test_create_vector_backend_hnsw() {
+        let config = VectorConfig {
+            backend: "simple".to_string(),
+            index_type: "hnsw".to_string(),
+            hnsw_ef_search: 32,
+            ..Default::default()
+        };
+        let backend = create_vector_backend(&config, mock_provider()).unwrap();
+        assert_eq!(backend.name(), "simple");
+
+        let config = VectorConfig {
+            backend: "simple".to_string(),
+            index_type: "ivf".to_string(),
+            ..Default::default()
+        };
+        let result = create_vector_backend(&config, mock_provider());
+        assert!(
+            result
+                .err()
+                .unwrap()
+                .to_string()
+                .contains("Unknown vector index type")
+        );
+    }
+
     #[test]
     fn test_cosine_similarity_identical() {
         let v = vec![1.0, 0.0, 0.0];
@@ -3,7 +3,7 @@
 //! The builder orchestrates content discovery, text extraction, batch
 //! embedding, and index population:
 //!
-//! 1. Discover content files using glob patterns
+//! 1. Discover markdown files in the content store
 //! 2. Parse frontmatter and content
 //! 3. Call VectorExtractor to produce VectorDocuments
 //! 4. Batch embed documents via EmbeddingProvider
@@ -16,9 +16,12 @@
 
 use crate::backend::{SimpleVectorBackend, VectorBackend};
 use crate::embedding::EmbeddingProvider;
+use crate::embedding_cache::CachedEmbeddingProvider;
 use crate::extractor::VectorExtractor;
+use crate::hnsw::HnswConfig;
 use crate::types::{BuildError, EmbeddedDocument, VectorDocument, VectorIndexStats};
 use fabryk_content::markdown::extract_frontmatter;
+use fabryk_core::store::{ContentEntry, ContentStore, FsContentStore, hash_entries};
 use fabryk_core::{Error, Result};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
@@ -66,12 +69,14 @@ pub enum ErrorHandling {
 /// ```
 pub struct VectorIndexBuilder<E: VectorExtractor> {
     extractor: E,
-    content_path: Option<PathBuf>,
+    content: Option<Arc<dyn ContentStore>>,
     provider: Option<Arc<dyn EmbeddingProvider>>,
     error_handling: ErrorHandling,
     batch_size: usize,
     cache_path: Option<PathBuf>,
     skip_cache: bool,
+    embedding_cache_path: Option<PathBuf>,
+    hnsw: Option<HnswConfig>,
 }
 
 impl<E: VectorExtractor> VectorIndexBuilder<E> {
@@ -79,18 +84,26 @@ impl<E: VectorExtractor> VectorIndexBuilder<E> {
     pub fn new(extractor: E) -> Self {
         Self {
             extractor,
-            content_path: None,
+            content: None,
             provider: None,
             error_handling: ErrorHandling::default(),
             batch_size: 64,
             cache_path: None,
             skip_cache: false,
+            embedding_cache_path: None,
+            hnsw: None,
         }
     }
 
     /// Sets the content directory path.
     pub fn with_content_path(mut self, path: impl Into<PathBuf>) -> Self {
-        self.content_path = Some(path.into());
+        self.content = Some(Arc::new(FsContentStore::new(path)));
+        self
+    }
+
+    /// Reads content from a store instead of a directory.
+    pub fn with_content_store(mut self, store: Arc<dyn ContentStore>) -> Self {
+        self.content = Some(store);
         self
     }
 
@@ -129,6 +142,26 @@ impl<E: VectorExtractor> VectorIndexBuilder<E> {
         self
     }
 
+    /// Sets the persistent embedding cache file path.
+    ///
+    /// Unlike [`with_cache_path`](Self::with_cache_path), which is all-or-nothing
+    /// on the content hash, the embedding cache is keyed per document by
+    /// `(model, blake3(text))`: a rebuild after editing one file only
+    /// embeds that file's text.
+    pub fn with_embedding_cache(mut self, path: impl Into<PathBuf>) -> Self {
+        self.embedding_cache_path = Some(path.into());
+        self
+    }
+
+    /// Index the built backend with HNSW instead of brute force.
+    ///
+    /// The index is saved with the vector cache. On a cache hit, a cached
+    /// index built with different graph parameters is rebuilt.
+    pub fn with_hnsw(mut self, config: HnswConfig) -> Self {
+        self.hnsw = Some(config);
+        self
+    }
+
     /// Builds the vector index.
     ///
     /// Returns a `SimpleVectorBackend` populated with embedded documents,
@@ -143,11 +176,7 @@ impl<E: VectorExtractor> VectorIndexBuilder<E> {
     pub async fn build(self) -> Result<(SimpleVectorBackend, VectorIndexStats)> {
         let start = Instant::now();
 
-        let content_path = self
-            .content_path
-            .as_ref()
-            .ok_or_else(|| Error::config("Content path not set. Use with_content_path() first."))?
-            .clone();
+        let store = self.require_store()?;
 
         let provider = self
             .provider
@@ -161,11 +190,14 @@ impl<E: VectorExtractor> VectorIndexBuilder<E> {
         if let Some(ref cache_path) = self.cache_path
             && !self.skip_cache
         {
-            let content_hash = compute_content_hash(&content_path).await?;
+            let content_hash = compute_store_hash(store.as_ref()).await?;
             if SimpleVectorBackend::is_cache_fresh(cache_path, &content_hash)
-                && let Ok(Some(backend)) =
+                && let Ok(Some(mut backend)) =
                     SimpleVectorBackend::load_cache(cache_path, provider.clone())
             {
+                if let Some(ref config) = self.hnsw {
+                    backend = backend.with_hnsw(config.clone());
+                }
                 let doc_count = backend.document_count().unwrap_or(0);
                 log::info!(
                     "Vector cache is fresh, loaded {} documents from {}",
@@ -181,13 +213,14 @@ impl<E: VectorExtractor> VectorIndexBuilder<E> {
                     build_duration_ms: start.elapsed().as_millis() as u64,
                     errors: Vec::new(),
                     from_cache: true,
+                    embeddings_cached: 0,
                 };
                 return Ok((backend, stats));
             }
         }
 
         // Discover files
-        let files = discover_files(&content_path).await?;
+        let files = discover_files(store.as_ref()).await?;
 
         let mut errors: Vec<BuildError> = Vec::new();
         let mut documents: Vec<VectorDocument> = Vec::new();
@@ -197,8 +230,9 @@ impl<E: VectorExtractor> VectorIndexBuilder<E> {
         // ================================================================
         // Phase 1: Discover + Extract documents
         // ================================================================
-        for file_path in &files {
-            match self.extract_file(&content_path, file_path) {
+        for file in &files {
+            let file_path = store.path(&file.key);
+            match self.extract_file(store.as_ref(), &file.key).await {
                 Ok(doc) => {
                     documents.push(doc);
                 }
@@ -228,25 +262,20 @@ impl<E: VectorExtractor> VectorIndexBuilder<E> {
         // ================================================================
         // Phase 2: Batch embed + insert
         // ================================================================
-        let mut embedded_documents: Vec<EmbeddedDocument> = Vec::with_capacity(documents.len());
-
-        for chunk in documents.chunks(self.batch_size) {
-            let texts: Vec<&str> = chunk.iter().map(|d| d.text.as_str()).collect();
-            let embeddings = provider.embed_batch(&texts).await?;
-
-            for (doc, embedding) in chunk.iter().zip(embeddings.into_iter()) {
-                embedded_documents.push(EmbeddedDocument::new(doc.clone(), embedding));
-            }
-        }
+        let (embedded_documents, embeddings_cached) =
+            self.embed_documents(&provider, &documents).await?;
 
         let documents_indexed = embedded_documents.len();
         let embedding_dimension = provider.dimension();
 
         // Compute content hash
-        let content_hash = compute_content_hash(&content_path).await?;
+        let content_hash = compute_store_hash(store.as_ref()).await?;
 
         // Build the backend
         let mut backend = SimpleVectorBackend::new(provider);
+        if let Some(ref config) = self.hnsw {
+            backend = backend.with_hnsw(config.clone());
+        }
         backend.add_documents(embedded_documents);
 
         let stats = VectorIndexStats {
@@ -258,6 +287,7 @@ impl<E: VectorExtractor> VectorIndexBuilder<E> {
             build_duration_ms: start.elapsed().as_millis() as u64,
             errors,
             from_cache: false,
+            embeddings_cached,
         };
 
         // Save to cache after successful build
@@ -270,10 +300,66 @@ impl<E: VectorExtractor> VectorIndexBuilder<E> {
         Ok((backend, stats))
     }
 
+    /// Embed documents in batches, consulting the embedding cache if configured.
+    ///
+    /// Returns the embedded documents and the number of embeddings served
+    /// from the cache.
+    async fn embed_documents(
+        &self,
+        provider: &Arc<dyn EmbeddingProvider>,
+        documents: &[VectorDocument],
+    ) -> Result<(Vec<EmbeddedDocument>, usize)> {
+        let cache = match self.embedding_cache_path {
+            Some(ref path) => Some(CachedEmbeddingProvider::open(path, provider.clone())?),
+            None => None,
+        };
+        let embedder: &dyn EmbeddingProvider = match cache {
+            Some(ref cache) => cache,
+            None => provider.as_ref(),
+        };
+
+        let mut embedded_documents: Vec<EmbeddedDocument> = Vec::with_capacity(documents.len());
+
+        for chunk in documents.chunks(self.batch_size) {
+            let texts: Vec<&str> = chunk.iter().map(|d| d.text.as_str()).collect();
+            let embeddings = embedder.embed_batch(&texts).await?;
+
+            for (doc, embedding) in chunk.iter().zip(embeddings) {
+                embedded_documents.push(EmbeddedDocument::new(doc.clone(), embedding));
+            }
+        }
+
+        let embeddings_cached = match cache {
+            Some(ref cache) => {
+                if cache.misses() > 0
+                    && let Err(e) = cache.save()
+                {
+                    log::warn!("Failed to save embedding cache: {e}");
+                }
+                cache.hits()
+            }
+            None => 0,
+        };
+
+        Ok((embedded_documents, embeddings_cached))
+    }
+
+    fn require_store(&self) -> Result<Arc<dyn ContentStore>> {
+        self.content.clone().ok_or_else(|| {
+            Error::config(
+                "Content path not set. Use with_content_path() or with_content_store() first.",
+            )
+        })
+    }
+
     /// Extract a single file to a VectorDocument.
-    fn extract_file(&self, base_path: &Path, file_path: &Path) -> Result<VectorDocument> {
-        let content =
-            std::fs::read_to_string(file_path).map_err(|e| Error::io_with_path(e, file_path))?;
+    ///
+    /// The extractor sees the file's local path, or its key for stores
+    /// without a local directory.
+    async fn extract_file(&self, store: &dyn ContentStore, key: &str) -> Result<VectorDocument> {
+        let content = store.get_string(key).await?;
+        let base_path = store.root().unwrap_or(Path::new(""));
+        let file_path = store.path(key);
 
         let fm_result = extract_frontmatter(&content)?;
 
@@ -284,7 +370,7 @@ impl<E: VectorExtractor> VectorIndexBuilder<E> {
         let body = fm_result.body();
 
         self.extractor
-            .extract_document(base_path, file_path, &frontmatter, body)
+            .extract_document(base_path, &file_path, &frontmatter, body)
     }
 
     /// Append documents from a content path into an existing backend.
@@ -314,11 +400,7 @@ impl<E: VectorExtractor> VectorIndexBuilder<E> {
     pub async fn build_append(self, backend: &mut SimpleVectorBackend) -> Result<VectorIndexStats> {
         let start = Instant::now();
 
-        let content_path = self
-            .content_path
-            .as_ref()
-            .ok_or_else(|| Error::config("Content path not set. Use with_content_path() first."))?
-            .clone();
+        let store = self.require_store()?;
 
         let provider = self
             .provider
@@ -328,7 +410,7 @@ impl<E: VectorExtractor> VectorIndexBuilder<E> {
             })?
             .clone();
 
-        let files = discover_files(&content_path).await?;
+        let files = discover_files(store.as_ref()).await?;
 
         let mut errors: Vec<BuildError> = Vec::new();
         let mut documents: Vec<VectorDocument> = Vec::new();
@@ -336,8 +418,9 @@ impl<E: VectorExtractor> VectorIndexBuilder<E> {
         let mut files_skipped = 0usize;
 
         // Phase 1: Discover + Extract
-        for file_path in &files {
-            match self.extract_file(&content_path, file_path) {
+        for file in &files {
+            let file_path = store.path(&file.key);
+            match self.extract_file(store.as_ref(), &file.key).await {
                 Ok(doc) => {
                     documents.push(doc);
                 }
@@ -365,20 +448,12 @@ impl<E: VectorExtractor> VectorIndexBuilder<E> {
         }
 
         // Phase 2: Batch embed + insert into existing backend
-        let mut embedded_documents: Vec<EmbeddedDocument> = Vec::with_capacity(documents.len());
-
-        for chunk in documents.chunks(self.batch_size) {
-            let texts: Vec<&str> = chunk.iter().map(|d| d.text.as_str()).collect();
-            let embeddings = provider.embed_batch(&texts).await?;
-
-            for (doc, embedding) in chunk.iter().zip(embeddings.into_iter()) {
-                embedded_documents.push(EmbeddedDocument::new(doc.clone(), embedding));
-            }
-        }
+        let (embedded_documents, embeddings_cached) =
+            self.embed_documents(&provider, &documents).await?;
 
         let documents_indexed = embedded_documents.len();
         let embedding_dimension = provider.dimension();
-        let content_hash = compute_content_hash(&content_path).await?;
+        let content_hash = compute_store_hash(store.as_ref()).await?;
 
         backend.add_documents(embedded_documents);
 
@@ -391,12 +466,13 @@ impl<E: VectorExtractor> VectorIndexBuilder<E> {
             build_duration_ms: start.elapsed().as_millis() as u64,
             errors,
             from_cache: false,
+            embeddings_cached,
         };
 
         log::info!(
             "Appended {} vector documents from {} ({} errors)",
             documents_indexed,
-            content_path.display(),
+            store.location(),
             stats.errors.len(),
         );
 
@@ -408,36 +484,27 @@ impl<E: VectorExtractor> VectorIndexBuilder<E> {
 // Helper functions
 // ============================================================================
 
-/// Discover content files in a directory.
-async fn discover_files(base_path: &Path) -> Result<Vec<PathBuf>> {
-    use fabryk_core::util::files::{FindOptions, find_all_files};
-
-    let files = find_all_files(base_path, FindOptions::markdown()).await?;
-    let paths: Vec<PathBuf> = files.into_iter().map(|f| f.path).collect();
-
-    Ok(paths)
+/// Discover markdown content files in a store.
+async fn discover_files(store: &dyn ContentStore) -> Result<Vec<ContentEntry>> {
+    let mut files = store.list().await?;
+    files.retain(|entry| entry.extension() == Some("md"));
+    Ok(files)
 }
 
 /// Compute a content hash for freshness checking.
 ///
-/// Hashes all markdown file contents in the directory using blake3.
+/// Hashes the paths, sizes and modification times of the markdown files in
+/// the directory, without reading them.
 pub async fn compute_content_hash(content_path: &Path) -> Result<String> {
-    use fabryk_core::util::files::{FindOptions, find_all_files};
-
-    let files = find_all_files(content_path, FindOptions::markdown()).await?;
-
-    let mut hasher = blake3::Hasher::new();
-    let mut paths: Vec<PathBuf> = files.into_iter().map(|f| f.path).collect();
-    paths.sort(); // Deterministic ordering
-
-    for path in &paths {
-        if let Ok(content) = std::fs::read(path) {
-            hasher.update(path.to_string_lossy().as_bytes());
-            hasher.update(&content);
-        }
-    }
+    compute_store_hash(&FsContentStore::new(content_path)).await
+}
 
-    Ok(hasher.finalize().to_hex().to_string())
+/// Compute a content hash for a content store.
+///
+/// Hashes the listing metadata of every markdown item (see
+/// [`hash_entries`]), so remote stores are not downloaded.
+pub async fn compute_store_hash(store: &dyn ContentStore) -> Result<String> {
+    Ok(hash_entries(&discover_files(store).await?))
 }
 
 // ============================================================================
@@ -486,6 +553,35 @@ mod tests {
         assert_eq!(backend.document_count().unwrap(), 2);
     }
 
+    #[tokio::test]
+    async fn test_builder_content_store() {
+        use fabryk_core::store::MemoryContentStore;
+
+        let store = Arc::new(
+            MemoryContentStore::new()
+                .with_item(
+                    "basics/concept-a.md",
+                    "---\ntitle: \"Concept A\"\n---\n\nContent for concept A.\n",
+                )
+                .with_item("notes.txt", "not markdown"),
+        );
+        let provider = Arc::new(MockEmbeddingProvider::new(8));
+
+        let (backend, stats) = VectorIndexBuilder::new(MockVectorExtractor)
+            .with_content_store(store.clone())
+            .with_embedding_provider(provider)
+            .build()
+            .await
+            .unwrap();
+
+        assert_eq!(stats.files_processed, 1);
+        assert_eq!(backend.document_count().unwrap(), 1);
+        assert_eq!(
+            stats.content_hash,
+            compute_store_hash(store.as_ref()).await.unwrap()
+        );
+    }
+
     #[tokio::test]
     async fn test_builder_content_hash() {
         let (_dir, content_dir) = setup_test_files().await;
@@ -529,7 +625,7 @@ mod tests {
 
         std::fs::write(
             content_dir.join("test.md"),
-            "---\ntitle: Test\n---\nModified content",
+            "---\ntitle: Test\n---\nModified content, now longer",
         )
         .unwrap();
 
@@ -690,6 +786,40 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_builder_hnsw_index_persisted_in_cache() {
+        let (_dir, content_dir) = setup_test_files().await;
+        let cache_path = content_dir.parent().unwrap().join("vector-cache.json");
+        let provider = Arc::new(MockEmbeddingProvider::new(8));
+        let config = HnswConfig::default().with_m(4);
+
+        let (backend1, _) = VectorIndexBuilder::new(MockVectorExtractor)
+            .with_content_path(&content_dir)
+            .with_embedding_provider(provider.clone())
+            .with_cache_path(&cache_path)
+            .with_hnsw(config.clone())
+            .build()
+            .await
+            .unwrap();
+        assert_eq!(backend1.hnsw_config(), Some(&config));
+
+        // Cache hit restores the index; a new ef_search applies without rebuild
+        let (backend2, stats2) = VectorIndexBuilder::new(MockVectorExtractor)
+            .with_content_path(&content_dir)
+            .with_embedding_provider(provider)
+            .with_cache_path(&cache_path)
+            .with_hnsw(config.clone().with_ef_search(8))
+            .build()
+            .await
+            .unwrap();
+        assert!(stats2.from_cache);
+        assert_eq!(backend2.hnsw_config().unwrap().ef_search, 8);
+        assert_eq!(
+            backend1.document_count().unwrap(),
+            backend2.document_count().unwrap()
+        );
+    }
+
     #[tokio::test]
     async fn test_builder_cache_miss_on_content_change() {
         let (_dir, content_dir) = setup_test_files().await;
@@ -763,4 +893,39 @@ mod tests {
             .unwrap();
         assert!(!stats.from_cache);
     }
+
+    #[tokio::test]
+    async fn test_builder_embedding_cache_reuses_unchanged_documents() {
+        let (dir, content_dir) = setup_test_files().await;
+        let embedding_cache = dir.path().join("embeddings.json");
+        let provider = Arc::new(MockEmbeddingProvider::new(8));
+
+        let (_, stats1) = VectorIndexBuilder::new(MockVectorExtractor)
+            .with_content_path(&content_dir)
+            .with_embedding_provider(provider.clone())
+            .with_embedding_cache(&embedding_cache)
+            .build()
+            .await
+            .unwrap();
+        assert_eq!(stats1.embeddings_cached, 0);
+        assert!(embedding_cache.exists());
+
+        // Edit one file; only it should be re-embedded
+        std::fs::write(
+            content_dir.join("concept-a.md"),
+            "---\ntitle: \"Concept A\"\n---\n\nRewritten.\n",
+        )
+        .unwrap();
+
+        let (_, stats2) = VectorIndexBuilder::new(MockVectorExtractor)
+            .with_content_path(&content_dir)
+            .with_embedding_provider(provider)
+            .with_embedding_cache(&embedding_cache)
+            .build()
+            .await
+            .unwrap();
+        assert!(!stats2.from_cache);
+        assert_eq!(stats2.documents_indexed, 2);
+        assert_eq!(stats2.embeddings_cached, 1);
+    }
 }
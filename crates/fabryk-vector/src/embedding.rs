@@ -7,9 +7,15 @@
 //!
 //! - `MockEmbeddingProvider`: Deterministic fixed-dimension vectors for testing
 //! - `FastEmbedProvider`: Local embedding via fastembed (requires `vector-fastembed` feature)
+//! - `RemoteEmbeddingProvider`: OpenAI-compatible, Ollama or Vertex AI over HTTP
+//!   (requires `vector-remote` feature)
+//! - `CachedEmbeddingProvider`: Persistent cache around any other provider
+//!   (applied by `VectorIndexBuilder::with_embedding_cache`)
 
+use crate::types::VectorConfig;
 use async_trait::async_trait;
-use fabryk_core::Result;
+use fabryk_core::{Error, Result};
+use std::sync::Arc;
 
 /// Trait for generating text embeddings.
 ///
@@ -110,6 +116,70 @@ impl EmbeddingProvider for MockEmbeddingProvider {
     }
 }
 
+// ============================================================================
+// Factory
+// ============================================================================
+
+/// Create an embedding provider based on configuration.
+///
+/// Selection is by `config.provider`:
+/// - `"mock"` → `MockEmbeddingProvider` (dimension defaults to 384)
+/// - `"fastembed"` → `FastEmbedProvider` (feature: `vector-fastembed`)
+/// - `"openai"`, `"ollama"`, `"vertex"` → `RemoteEmbeddingProvider`
+///   (feature: `vector-remote`)
+///
+/// `config.embedding_cache_path` is not applied here: the cache has to be
+/// saved once indexing finishes, so pass the path to
+/// [`VectorIndexBuilder::with_embedding_cache`](crate::VectorIndexBuilder::with_embedding_cache),
+/// which loads it and persists new embeddings at the end of each build.
+pub async fn create_embedding_provider(
+    config: &VectorConfig,
+) -> Result<Arc<dyn EmbeddingProvider>> {
+    let provider: Arc<dyn EmbeddingProvider> = match config.provider.as_str() {
+        "mock" => Arc::new(MockEmbeddingProvider::new(if config.dimension == 0 {
+            384
+        } else {
+            config.dimension
+        })),
+        #[cfg(feature = "vector-fastembed")]
+        "fastembed" => Arc::new(crate::fastembed::FastEmbedProvider::new(
+            &config.model,
+            config.cache_path.as_deref(),
+        )?),
+        #[cfg(feature = "vector-remote")]
+        name @ ("openai" | "ollama" | "vertex") => {
+            use crate::remote::{RemoteApi, RemoteEmbeddingConfig, RemoteEmbeddingProvider};
+
+            let api = RemoteApi::from_provider_name(name)
+                .ok_or_else(|| Error::config(format!("Unknown remote provider: '{name}'")))?;
+            let mut remote = RemoteEmbeddingConfig::new(api, &config.model)
+                .with_dimension(config.dimension)
+                .with_max_retries(config.max_retries)
+                .with_max_batch_size(config.batch_size);
+            if let Some(ref url) = config.base_url {
+                remote = remote.with_base_url(url);
+            }
+            if let Some(ref var) = config.api_key_env {
+                let key = std::env::var(var).map_err(|_| {
+                    Error::config(format!("Embedding API key variable '{var}' is not set"))
+                })?;
+                remote = remote.with_api_key(key);
+            }
+            if let Some(rps) = config.requests_per_second {
+                remote = remote.with_requests_per_second(rps);
+            }
+            Arc::new(RemoteEmbeddingProvider::new(remote).await?)
+        }
+        other => {
+            return Err(Error::config(format!(
+                "Embedding provider '{other}' is unknown or its feature is not enabled"
+            )));
+        }
+    };
+
+    Ok(provider)
+}
+
 // ============================================================================
 // Tests
 // ============================================================================
@@ -186,6 +256,43 @@ mod tests {
         assert!(embeddings.is_empty());
     }
 
+    #[tokio::test]
+    async fn test_create_embedding_provider_mock() {
+        let config = VectorConfig {
+            provider: "mock".to_string(),
+            dimension: 16,
+            ..Default::default()
+        };
+        let provider = create_embedding_provider(&config).await.unwrap();
+        assert_eq!(provider.name(), "mock");
+        assert_eq!(provider.dimension(), 16);
+    }
+
+    #[tokio::test]
+    async fn test_create_embedding_provider_unknown() {
+        let config = VectorConfig {
+            provider: "nonexistent".to_string(),
+            ..Default::default()
+        };
+        let err = create_embedding_provider(&config).await.err().unwrap();
+        assert!(err.to_string().contains("nonexistent"));
+    }
+
+    #[tokio::test]
+    async fn test_create_embedding_provider_leaves_cache_to_builder() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache_path = dir.path().join("embeddings.json");
+        let config = VectorConfig {
+            provider: "mock".to_string(),
+            embedding_cache_path: Some(cache_path.to_string_lossy().into_owned()),
+            ..Default::default()
+        };
+        let provider = create_embedding_provider(&config).await.unwrap();
+        assert_eq!(provider.name(), "mock");
+        assert_eq!(provider.embed("a").await.unwrap().len(), 384);
+        assert!(!cache_path.exists());
+    }
+
     #[test]
     fn test_trait_object_safety() {
         // Verify EmbeddingProvider can be used as a trait object
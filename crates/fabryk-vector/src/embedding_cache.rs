@@ -0,0 +1,324 @@
+//! Persistent embedding cache.
+//!
+//! `CachedEmbeddingProvider` wraps any `EmbeddingProvider` and memoizes
+//! embeddings keyed by `(model, blake3(text))`. The cache can be persisted
+//! to a JSON file so that `VectorIndexBuilder` rebuilds only embed documents
+//! whose text actually changed — important for remote providers where every
+//! call costs latency and money.
+//!
+//! # Cache Key
+//!
+//! The model component of the key defaults to the wrapped provider's
+//! [`name()`](EmbeddingProvider::name), which providers set to their model
+//! identifier. Embeddings produced by different models never collide.
+
+use crate::embedding::EmbeddingProvider;
+use async_trait::async_trait;
+use fabryk_core::{Error, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, MutexGuard};
+
+/// Cached embeddings: model → (blake3 hex of text → embedding).
+type CacheEntries = HashMap<String, HashMap<String, Vec<f32>>>;
+
+/// On-disk format of the embedding cache.
+#[derive(Default, Serialize, Deserialize)]
+struct EmbeddingCacheFile {
+    entries: CacheEntries,
+}
+
+/// An embedding provider that caches results from an inner provider.
+///
+/// Lookups hit the in-memory cache first; only texts without a cached
+/// embedding are forwarded (as a single batch) to the inner provider.
+/// Call [`save`](Self::save) to persist newly computed embeddings.
+pub struct CachedEmbeddingProvider {
+    inner: Arc<dyn EmbeddingProvider>,
+    model_key: String,
+    path: Option<PathBuf>,
+    entries: Mutex<CacheEntries>,
+    hits: AtomicUsize,
+    misses: AtomicUsize,
+}
+
+impl CachedEmbeddingProvider {
+    /// Wrap a provider with an empty, in-memory cache.
+    pub fn new(inner: Arc<dyn EmbeddingProvider>) -> Self {
+        let model_key = inner.name().to_string();
+        Self {
+            inner,
+            model_key,
+            path: None,
+            entries: Mutex::new(HashMap::new()),
+            hits: AtomicUsize::new(0),
+            misses: AtomicUsize::new(0),
+        }
+    }
+
+    /// Wrap a provider with a cache backed by the given file.
+    ///
+    /// Existing entries are loaded if the file exists. A missing file is
+    /// not an error; it will be created on the first [`save`](Self::save).
+    pub fn open(path: impl Into<PathBuf>, inner: Arc<dyn EmbeddingProvider>) -> Result<Self> {
+        let path = path.into();
+        let mut cache = Self::new(inner);
+
+        if path.exists() {
+            let json = std::fs::read_to_string(&path).map_err(|e| Error::io_with_path(e, &path))?;
+            let file: EmbeddingCacheFile = serde_json::from_str(&json)
+                .map_err(|e| Error::parse(format!("Failed to parse embedding cache: {e}")))?;
+            log::debug!(
+                "Loaded embedding cache with {} models from {}",
+                file.entries.len(),
+                path.display()
+            );
+            cache.entries = Mutex::new(file.entries);
+        }
+
+        cache.path = Some(path);
+        Ok(cache)
+    }
+
+    /// Override the model component of the cache key.
+    pub fn with_model_key(mut self, model_key: impl Into<String>) -> Self {
+        self.model_key = model_key.into();
+        self
+    }
+
+    /// Persist the cache to its backing file.
+    ///
+    /// A no-op for caches created with [`new`](Self::new).
+    pub fn save(&self) -> Result<()> {
+        let Some(ref path) = self.path else {
+            return Ok(());
+        };
+
+        if let Some(parent) = path.parent()
+            && !parent.as_os_str().is_empty()
+            && !parent.exists()
+        {
+            std::fs::create_dir_all(parent).map_err(|e| Error::io_with_path(e, parent))?;
+        }
+
+        let entries = self.lock()?;
+        let file = EmbeddingCacheFile {
+            entries: entries.clone(),
+        };
+        drop(entries);
+
+        let json = serde_json::to_string(&file)
+            .map_err(|e| Error::operation(format!("Failed to serialize embedding cache: {e}")))?;
+        std::fs::write(path, json).map_err(|e| Error::io_with_path(e, path))?;
+
+        log::info!(
+            "Saved embedding cache: {} entries to {}",
+            self.len(),
+            path.display()
+        );
+        Ok(())
+    }
+
+    /// Number of cached embeddings for the current model.
+    pub fn len(&self) -> usize {
+        self.lock()
+            .map(|entries| entries.get(&self.model_key).map_or(0, HashMap::len))
+            .unwrap_or(0)
+    }
+
+    /// Whether the cache holds no embeddings for the current model.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Number of texts served from the cache since creation.
+    pub fn hits(&self) -> usize {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    /// Number of texts forwarded to the inner provider since creation.
+    pub fn misses(&self) -> usize {
+        self.misses.load(Ordering::Relaxed)
+    }
+
+    /// The backing file path, if any.
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    fn lock(&self) -> Result<MutexGuard<'_, CacheEntries>> {
+        self.entries
+            .lock()
+            .map_err(|e| Error::operation(format!("Mutex poisoned: {e}")))
+    }
+}
+
+/// Hash text for use as a cache key.
+fn text_key(text: &str) -> String {
+    blake3::hash(text.as_bytes()).to_hex().to_string()
+}
+
+#[async_trait]
+impl EmbeddingProvider for CachedEmbeddingProvider {
+    async fn embed(&self, text: &str) -> Result<Vec<f32>> {
+        let mut results = self.embed_batch(&[text]).await?;
+        results
+            .pop()
+            .ok_or_else(|| Error::operation("No embedding returned"))
+    }
+
+    async fn embed_batch(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>> {
+        let keys: Vec<String> = texts.iter().map(|t| text_key(t)).collect();
+
+        let mut results: Vec<Option<Vec<f32>>> = {
+            let entries = self.lock()?;
+            let model = entries.get(&self.model_key);
+            keys.iter()
+                .map(|k| model.and_then(|m| m.get(k)).cloned())
+                .collect()
+        };
+
+        let missing: Vec<usize> = (0..texts.len()).filter(|&i| results[i].is_none()).collect();
+        self.hits
+            .fetch_add(texts.len() - missing.len(), Ordering::Relaxed);
+        self.misses.fetch_add(missing.len(), Ordering::Relaxed);
+
+        if !missing.is_empty() {
+            let missing_texts: Vec<&str> = missing.iter().map(|&i| texts[i]).collect();
+            let embeddings = self.inner.embed_batch(&missing_texts).await?;
+            if embeddings.len() != missing.len() {
+                return Err(Error::operation(format!(
+                    "Embedding provider returned {} embeddings for {} texts",
+                    embeddings.len(),
+                    missing.len()
+                )));
+            }
+
+            let mut entries = self.lock()?;
+            let model = entries.entry(self.model_key.clone()).or_default();
+            for (&i, embedding) in missing.iter().zip(embeddings) {
+                model.insert(keys[i].clone(), embedding.clone());
+                results[i] = Some(embedding);
+            }
+        }
+
+        Ok(results.into_iter().flatten().collect())
+    }
+
+    fn dimension(&self) -> usize {
+        self.inner.dimension()
+    }
+
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+}
+
+impl std::fmt::Debug for CachedEmbeddingProvider {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CachedEmbeddingProvider")
+            .field("inner", &self.inner.name())
+            .field("model_key", &self.model_key)
+            .field("path", &self.path)
+            .finish()
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::embedding::MockEmbeddingProvider;
+    use tempfile::tempdir;
+
+    fn mock() -> Arc<dyn EmbeddingProvider> {
+        Arc::new(MockEmbeddingProvider::new(8))
+    }
+
+    #[tokio::test]
+    async fn test_cache_miss_then_hit() {
+        let cache = CachedEmbeddingProvider::new(mock());
+
+        let first = cache.embed("hello").await.unwrap();
+        assert_eq!(cache.misses(), 1);
+        assert_eq!(cache.hits(), 0);
+
+        let second = cache.embed("hello").await.unwrap();
+        assert_eq!(cache.hits(), 1);
+        assert_eq!(first, second);
+    }
+
+    #[tokio::test]
+    async fn test_cache_batch_partial_hits_preserve_order() {
+        let cache = CachedEmbeddingProvider::new(mock());
+        let direct = MockEmbeddingProvider::new(8);
+
+        cache.embed("b").await.unwrap();
+        let results = cache.embed_batch(&["a", "b", "c"]).await.unwrap();
+
+        assert_eq!(cache.hits(), 1);
+        assert_eq!(cache.misses(), 3);
+        assert_eq!(results[0], direct.embed("a").await.unwrap());
+        assert_eq!(results[1], direct.embed("b").await.unwrap());
+        assert_eq!(results[2], direct.embed("c").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_cache_keyed_by_model() {
+        let cache = CachedEmbeddingProvider::new(mock());
+        cache.embed("hello").await.unwrap();
+        assert_eq!(cache.len(), 1);
+
+        let cache = cache.with_model_key("other-model");
+        assert!(cache.is_empty());
+        cache.embed("hello").await.unwrap();
+        assert_eq!(cache.misses(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_cache_persistence_roundtrip() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("nested/embeddings.json");
+
+        let cache = CachedEmbeddingProvider::open(&path, mock()).unwrap();
+        cache.embed_batch(&["one", "two"]).await.unwrap();
+        cache.save().unwrap();
+        assert!(path.exists());
+
+        let reloaded = CachedEmbeddingProvider::open(&path, mock()).unwrap();
+        assert_eq!(reloaded.len(), 2);
+        reloaded.embed_batch(&["one", "two"]).await.unwrap();
+        assert_eq!(reloaded.hits(), 2);
+        assert_eq!(reloaded.misses(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_cache_open_invalid_file() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("embeddings.json");
+        std::fs::write(&path, "not json").unwrap();
+
+        let err = CachedEmbeddingProvider::open(&path, mock()).unwrap_err();
+        assert!(err.to_string().contains("embedding cache"));
+    }
+
+    #[test]
+    fn test_cache_save_without_path_is_noop() {
+        let cache = CachedEmbeddingProvider::new(mock());
+        assert!(cache.path().is_none());
+        cache.save().unwrap();
+    }
+
+    #[test]
+    fn test_cache_delegates_metadata() {
+        let cache = CachedEmbeddingProvider::new(mock());
+        assert_eq!(cache.dimension(), 8);
+        assert_eq!(cache.name(), "mock");
+    }
+}
@@ -0,0 +1,679 @@
+//! Hierarchical Navigable Small World (HNSW) index.
+//!
+//! An in-crate approximate nearest-neighbor index for `SimpleVectorBackend`,
+//! for corpora where brute-force cosine similarity is too slow but LanceDB
+//! (and Arrow) is too heavy.
+//!
+//! The index stores only graph links; vectors stay in the backend's
+//! document list and are addressed by slot (position in that list).
+//! Deletes are tombstones: a deleted node still routes searches but is
+//! never returned. The backend compacts the index when tombstones pile up.
+//!
+//! # Knobs
+//!
+//! | Knob | Effect |
+//! |------|--------|
+//! | `m` | Links per node. Higher → better recall, more memory, slower inserts |
+//! | `ef_construction` | Candidate list size while inserting. Higher → better graph, slower inserts |
+//! | `ef_search` | Candidate list size while searching. Higher → better recall, slower queries |
+//! | `exact_search_threshold` | Below this many candidates, search exactly instead |
+
+use serde::{Deserialize, Serialize};
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BinaryHeap, HashSet};
+
+use crate::backend::SimpleVectorBackend;
+use crate::types::{EmbeddedDocument, VectorConfig};
+
+/// Upper bound on node levels; with `m >= 2` this is never reached in practice.
+const MAX_LEVEL: usize = 16;
+
+// ============================================================================
+// Configuration
+// ============================================================================
+
+/// HNSW index parameters.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HnswConfig {
+    /// Maximum links per node on upper layers (layer 0 allows `2 * m`).
+    pub m: usize,
+
+    /// Candidate list size used while inserting.
+    pub ef_construction: usize,
+
+    /// Default candidate list size used while searching.
+    pub ef_search: usize,
+
+    /// Search exactly when the index (or the set of documents matching the
+    /// metadata filters) has at most this many documents.
+    #[serde(default = "default_exact_search_threshold")]
+    pub exact_search_threshold: usize,
+
+    /// Seed for level assignment, so builds are reproducible.
+    #[serde(default = "default_seed")]
+    pub seed: u64,
+}
+
+fn default_exact_search_threshold() -> usize {
+    1000
+}
+
+fn default_seed() -> u64 {
+    0x5eed_f00d
+}
+
+impl Default for HnswConfig {
+    fn default() -> Self {
+        Self {
+            m: 16,
+            ef_construction: 200,
+            ef_search: 64,
+            exact_search_threshold: default_exact_search_threshold(),
+            seed: default_seed(),
+        }
+    }
+}
+
+impl HnswConfig {
+    /// Create a configuration with default parameters.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Build a configuration from the HNSW knobs in a `VectorConfig`,
+    /// clamped like the `with_*` setters.
+    pub fn from_vector_config(config: &VectorConfig) -> Self {
+        Self::default()
+            .with_m(config.hnsw_m)
+            .with_ef_construction(config.hnsw_ef_construction)
+            .with_ef_search(config.hnsw_ef_search)
+            .with_exact_search_threshold(config.hnsw_exact_search_threshold)
+    }
+
+    /// Set the number of links per node.
+    pub fn with_m(mut self, m: usize) -> Self {
+        self.m = m.max(2);
+        self
+    }
+
+    /// Set the insert-time candidate list size.
+    pub fn with_ef_construction(mut self, ef: usize) -> Self {
+        self.ef_construction = ef.max(1);
+        self
+    }
+
+    /// Set the default search-time candidate list size.
+    pub fn with_ef_search(mut self, ef: usize) -> Self {
+        self.ef_search = ef.max(1);
+        self
+    }
+
+    /// Set the size below which searches are exact.
+    pub fn with_exact_search_threshold(mut self, threshold: usize) -> Self {
+        self.exact_search_threshold = threshold;
+        self
+    }
+
+    /// Set the level-assignment seed.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Whether two configurations produce the same graph.
+    ///
+    /// Search-time knobs (`ef_search`, `exact_search_threshold`) can change
+    /// without rebuilding the index.
+    pub(crate) fn same_graph(&self, other: &Self) -> bool {
+        self.m == other.m
+            && self.ef_construction == other.ef_construction
+            && self.seed == other.seed
+    }
+}
+
+// ============================================================================
+// Index
+// ============================================================================
+
+/// A node's similarity to the current query.
+#[derive(Debug, Clone, Copy)]
+struct Scored {
+    sim: f32,
+    id: u32,
+}
+
+impl PartialEq for Scored {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Scored {}
+
+impl PartialOrd for Scored {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Scored {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.sim
+            .total_cmp(&other.sim)
+            .then_with(|| other.id.cmp(&self.id))
+    }
+}
+
+fn is_false(value: &bool) -> bool {
+    !*value
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Node {
+    /// Neighbor slots per layer; `neighbors.len() - 1` is the node's level.
+    neighbors: Vec<Vec<u32>>,
+    #[serde(default, skip_serializing_if = "is_false")]
+    deleted: bool,
+}
+
+impl Node {
+    fn level(&self) -> usize {
+        self.neighbors.len() - 1
+    }
+}
+
+/// HNSW graph over a slice of embedded documents.
+///
+/// Node `i` corresponds to `documents[i]`; every method that needs vectors
+/// takes the same document slice the index was built from.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct HnswIndex {
+    config: HnswConfig,
+    nodes: Vec<Node>,
+    entry_point: Option<u32>,
+    deleted: usize,
+    rng_state: u64,
+}
+
+impl HnswIndex {
+    /// Create an empty index.
+    pub(crate) fn new(config: HnswConfig) -> Self {
+        let rng_state = config.seed;
+        Self {
+            config,
+            nodes: Vec::new(),
+            entry_point: None,
+            deleted: 0,
+            rng_state,
+        }
+    }
+
+    /// Build an index over all documents.
+    pub(crate) fn build(config: HnswConfig, documents: &[EmbeddedDocument]) -> Self {
+        let mut index = Self::new(config);
+        index.extend(documents);
+        index
+    }
+
+    pub(crate) fn config(&self) -> &HnswConfig {
+        &self.config
+    }
+
+    /// Replace the search-time knobs without touching the graph.
+    pub(crate) fn set_search_config(&mut self, config: HnswConfig) {
+        debug_assert!(self.config.same_graph(&config));
+        self.config = config;
+    }
+
+    /// Number of nodes, including tombstones.
+    pub(crate) fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Number of tombstoned nodes.
+    pub(crate) fn deleted_count(&self) -> usize {
+        self.deleted
+    }
+
+    pub(crate) fn is_deleted(&self, slot: usize) -> bool {
+        self.nodes.get(slot).is_some_and(|n| n.deleted)
+    }
+
+    /// Insert every document past the last indexed slot.
+    pub(crate) fn extend(&mut self, documents: &[EmbeddedDocument]) {
+        while self.nodes.len() < documents.len() {
+            self.insert_next(documents);
+        }
+    }
+
+    /// Tombstone a slot. Returns `false` if it was already deleted or out of range.
+    pub(crate) fn mark_deleted(&mut self, slot: usize) -> bool {
+        match self.nodes.get_mut(slot) {
+            Some(node) if !node.deleted => {
+                node.deleted = true;
+                self.deleted += 1;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Find up to `k` live nodes closest to `query` that pass `accept`.
+    ///
+    /// Returns `(slot, cosine similarity)` pairs, most similar first.
+    pub(crate) fn search(
+        &self,
+        query: &[f32],
+        documents: &[EmbeddedDocument],
+        k: usize,
+        ef: usize,
+        accept: &dyn Fn(usize) -> bool,
+    ) -> Vec<(usize, f32)> {
+        let Some(entry) = self.entry_point else {
+            return Vec::new();
+        };
+
+        let mut entry_points = vec![Scored {
+            sim: similarity(query, entry, documents),
+            id: entry,
+        }];
+        for layer in (1..=self.nodes[entry as usize].level()).rev() {
+            entry_points = self.search_layer(query, &entry_points, 1, layer, documents, &|_| true);
+        }
+
+        let accept_live = |id: u32| !self.nodes[id as usize].deleted && accept(id as usize);
+        self.search_layer(query, &entry_points, ef.max(k), 0, documents, &accept_live)
+            .into_iter()
+            .take(k)
+            .map(|s| (s.id as usize, s.sim))
+            .collect()
+    }
+
+    /// Best-first search within one layer.
+    ///
+    /// Every reachable node is a routing candidate; only nodes passing
+    /// `accept` enter the result set. Results are sorted most similar first.
+    fn search_layer(
+        &self,
+        query: &[f32],
+        entry_points: &[Scored],
+        ef: usize,
+        layer: usize,
+        documents: &[EmbeddedDocument],
+        accept: &dyn Fn(u32) -> bool,
+    ) -> Vec<Scored> {
+        let mut visited: HashSet<u32> = entry_points.iter().map(|s| s.id).collect();
+        let mut candidates: BinaryHeap<Scored> = entry_points.iter().copied().collect();
+        let mut results: BinaryHeap<Reverse<Scored>> = entry_points
+            .iter()
+            .filter(|s| accept(s.id))
+            .map(|&s| Reverse(s))
+            .collect();
+
+        while let Some(current) = candidates.pop() {
+            if results.len() >= ef
+                && let Some(Reverse(worst)) = results.peek()
+                && current.sim < worst.sim
+            {
+                break;
+            }
+
+            for &neighbor in &self.nodes[current.id as usize].neighbors[layer] {
+                if !visited.insert(neighbor) {
+                    continue;
+                }
+
+                let scored = Scored {
+                    sim: similarity(query, neighbor, documents),
+                    id: neighbor,
+                };
+                let worst = results.peek().map_or(f32::NEG_INFINITY, |r| r.0.sim);
+                if results.len() < ef || scored.sim > worst {
+                    candidates.push(scored);
+                    if accept(neighbor) {
+                        results.push(Reverse(scored));
+                        if results.len() > ef {
+                            results.pop();
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut sorted: Vec<Scored> = results.into_iter().map(|r| r.0).collect();
+        sorted.sort_by(|a, b| b.cmp(a));
+        sorted
+    }
+
+    fn insert_next(&mut self, documents: &[EmbeddedDocument]) {
+        let id = self.nodes.len() as u32;
+        let query = &documents[id as usize].embedding;
+        let level = self.random_level();
+        self.nodes.push(Node {
+            neighbors: vec![Vec::new(); level + 1],
+            deleted: false,
+        });
+
+        let Some(entry) = self.entry_point else {
+            self.entry_point = Some(id);
+            return;
+        };
+
+        let max_level = self.nodes[entry as usize].level();
+        let mut entry_points = vec![Scored {
+            sim: similarity(query, entry, documents),
+            id: entry,
+        }];
+        for layer in (level + 1..=max_level).rev() {
+            entry_points = self.search_layer(query, &entry_points, 1, layer, documents, &|_| true);
+        }
+
+        for layer in (0..=level.min(max_level)).rev() {
+            let found = self.search_layer(
+                query,
+                &entry_points,
+                self.config.ef_construction,
+                layer,
+                documents,
+                &|_| true,
+            );
+            let neighbors = self.select_neighbors(&found, self.config.m, documents);
+            for &neighbor in &neighbors {
+                self.link(neighbor, id, layer, documents);
+            }
+            self.nodes[id as usize].neighbors[layer] = neighbors;
+            entry_points = found;
+        }
+
+        if level > max_level {
+            self.entry_point = Some(id);
+        }
+    }
+
+    /// Add a back-link, pruning the neighbor list if it overflows.
+    fn link(&mut self, from: u32, to: u32, layer: usize, documents: &[EmbeddedDocument]) {
+        let max_links = self.max_links(layer);
+        let links = &mut self.nodes[from as usize].neighbors[layer];
+        links.push(to);
+        if links.len() <= max_links {
+            return;
+        }
+
+        let base = &documents[from as usize].embedding;
+        let mut scored: Vec<Scored> = links
+            .iter()
+            .map(|&id| Scored {
+                sim: similarity(base, id, documents),
+                id,
+            })
+            .collect();
+        scored.sort_by(|a, b| b.cmp(a));
+        self.nodes[from as usize].neighbors[layer] =
+            self.select_neighbors(&scored, max_links, documents);
+    }
+
+    /// Pick up to `m` diverse neighbors from candidates sorted most similar first.
+    ///
+    /// A candidate is kept if it is closer to the base vector than to any
+    /// neighbor already kept; the remaining slots are filled with the
+    /// closest pruned candidates.
+    fn select_neighbors(
+        &self,
+        candidates: &[Scored],
+        m: usize,
+        documents: &[EmbeddedDocument],
+    ) -> Vec<u32> {
+        let mut selected: Vec<u32> = Vec::with_capacity(m);
+        let mut pruned: Vec<u32> = Vec::new();
+
+        for candidate in candidates {
+            if selected.len() >= m {
+                break;
+            }
+            let vector = &documents[candidate.id as usize].embedding;
+            let diverse = selected
+                .iter()
+                .all(|&kept| similarity(vector, kept, documents) < candidate.sim);
+            if diverse {
+                selected.push(candidate.id);
+            } else {
+                pruned.push(candidate.id);
+            }
+        }
+
+        let room = m.saturating_sub(selected.len());
+        selected.extend(pruned.into_iter().take(room));
+        selected
+    }
+
+    fn max_links(&self, layer: usize) -> usize {
+        if layer == 0 {
+            self.config.m * 2
+        } else {
+            self.config.m
+        }
+    }
+
+    /// Draw a level from the exponential distribution with `mL = 1 / ln(m)`.
+    fn random_level(&mut self) -> usize {
+        // splitmix64
+        self.rng_state = self.rng_state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.rng_state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^= z >> 31;
+
+        let uniform = ((z >> 11) as f64 + 1.0) / (1u64 << 53) as f64;
+        let ml = 1.0 / (self.config.m.max(2) as f64).ln();
+        ((-uniform.ln() * ml).floor() as usize).min(MAX_LEVEL)
+    }
+}
+
+fn similarity(query: &[f32], slot: u32, documents: &[EmbeddedDocument]) -> f32 {
+    SimpleVectorBackend::cosine_similarity(query, &documents[slot as usize].embedding)
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::VectorDocument;
+
+    /// Deterministic pseudo-random unit-ish vectors.
+    fn random_documents(count: usize, dim: usize, seed: u64) -> Vec<EmbeddedDocument> {
+        let mut state = seed;
+        (0..count)
+            .map(|i| {
+                let embedding = (0..dim)
+                    .map(|_| {
+                        state ^= state << 13;
+                        state ^= state >> 7;
+                        state ^= state << 17;
+                        (state % 2000) as f32 / 1000.0 - 1.0
+                    })
+                    .collect();
+                EmbeddedDocument::new(VectorDocument::new(format!("doc-{i}"), ""), embedding)
+            })
+            .collect()
+    }
+
+    fn exact_top_k(query: &[f32], documents: &[EmbeddedDocument], k: usize) -> Vec<usize> {
+        let mut scored: Vec<(usize, f32)> = documents
+            .iter()
+            .enumerate()
+            .map(|(i, d)| {
+                (
+                    i,
+                    SimpleVectorBackend::cosine_similarity(query, &d.embedding),
+                )
+            })
+            .collect();
+        scored.sort_by(|a, b| b.1.total_cmp(&a.1));
+        scored.into_iter().take(k).map(|(i, _)| i).collect()
+    }
+
+    #[test]
+    fn test_hnsw_config_defaults() {
+        let config = HnswConfig::default();
+        assert_eq!(config.m, 16);
+        assert_eq!(config.ef_construction, 200);
+        assert_eq!(config.ef_search, 64);
+        assert_eq!(config.exact_search_threshold, 1000);
+    }
+
+    #[test]
+    fn test_hnsw_config_from_vector_config() {
+        let vector_config = VectorConfig {
+            hnsw_m: 8,
+            hnsw_ef_construction: 50,
+            hnsw_ef_search: 20,
+            hnsw_exact_search_threshold: 10,
+            ..Default::default()
+        };
+        let config = HnswConfig::from_vector_config(&vector_config);
+        assert_eq!(config.m, 8);
+        assert_eq!(config.ef_construction, 50);
+        assert_eq!(config.ef_search, 20);
+        assert_eq!(config.exact_search_threshold, 10);
+
+        // Degenerate values are clamped as by the setters.
+        let vector_config = VectorConfig {
+            hnsw_m: 0,
+            hnsw_ef_construction: 0,
+            hnsw_ef_search: 0,
+            ..Default::default()
+        };
+        let config = HnswConfig::from_vector_config(&vector_config);
+        assert_eq!(config.m, 2);
+        assert_eq!(config.ef_construction, 1);
+        assert_eq!(config.ef_search, 1);
+    }
+
+    #[test]
+    fn test_same_graph_ignores_search_knobs() {
+        let a = HnswConfig::default();
+        let b = HnswConfig::default()
+            .with_ef_search(500)
+            .with_exact_search_threshold(0);
+        assert!(a.same_graph(&b));
+        assert!(!a.same_graph(&HnswConfig::default().with_m(8)));
+    }
+
+    #[test]
+    fn test_empty_index_search() {
+        let index = HnswIndex::new(HnswConfig::default());
+        assert!(index.search(&[1.0, 0.0], &[], 5, 10, &|_| true).is_empty());
+    }
+
+    #[test]
+    fn test_search_finds_exact_match() {
+        let documents = random_documents(300, 16, 7);
+        let index = HnswIndex::build(HnswConfig::default(), &documents);
+        assert_eq!(index.len(), 300);
+
+        let query = documents[42].embedding.clone();
+        let results = index.search(&query, &documents, 1, 64, &|_| true);
+        assert_eq!(results[0].0, 42);
+        assert!((results[0].1 - 1.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_recall_against_brute_force() {
+        let documents = random_documents(1000, 24, 11);
+        let index = HnswIndex::build(
+            HnswConfig::default().with_m(12).with_ef_construction(100),
+            &documents,
+        );
+        let queries = random_documents(20, 24, 99);
+
+        let k = 10;
+        let mut hits = 0;
+        for query in &queries {
+            let expected: HashSet<usize> = exact_top_k(&query.embedding, &documents, k)
+                .into_iter()
+                .collect();
+            let found = index.search(&query.embedding, &documents, k, 100, &|_| true);
+            hits += found.iter().filter(|(i, _)| expected.contains(i)).count();
+        }
+
+        let recall = hits as f32 / (queries.len() * k) as f32;
+        assert!(recall >= 0.9, "recall too low: {recall}");
+    }
+
+    #[test]
+    fn test_deleted_nodes_not_returned() {
+        let documents = random_documents(200, 8, 3);
+        let mut index = HnswIndex::build(HnswConfig::default(), &documents);
+
+        assert!(index.mark_deleted(5));
+        assert!(!index.mark_deleted(5));
+        assert!(!index.mark_deleted(1000));
+        assert_eq!(index.deleted_count(), 1);
+        assert!(index.is_deleted(5));
+
+        let results = index.search(&documents[5].embedding, &documents, 10, 64, &|_| true);
+        assert!(results.iter().all(|(i, _)| *i != 5));
+        assert_eq!(results.len(), 10);
+    }
+
+    #[test]
+    fn test_search_with_filter() {
+        let documents = random_documents(500, 8, 5);
+        let index = HnswIndex::build(HnswConfig::default(), &documents);
+
+        let results = index.search(&documents[0].embedding, &documents, 10, 64, &|i| i % 2 == 1);
+        assert_eq!(results.len(), 10);
+        assert!(results.iter().all(|(i, _)| i % 2 == 1));
+    }
+
+    #[test]
+    fn test_incremental_extend_matches_build() {
+        let documents = random_documents(100, 8, 13);
+        let built = HnswIndex::build(HnswConfig::default(), &documents);
+
+        let mut incremental = HnswIndex::build(HnswConfig::default(), &documents[..40]);
+        incremental.extend(&documents);
+        assert_eq!(incremental.len(), 100);
+
+        let query = &documents[77].embedding;
+        assert_eq!(
+            built.search(query, &documents, 5, 64, &|_| true),
+            incremental.search(query, &documents, 5, 64, &|_| true)
+        );
+    }
+
+    #[test]
+    fn test_neighbor_lists_bounded() {
+        let documents = random_documents(400, 8, 17);
+        let config = HnswConfig::default().with_m(4);
+        let index = HnswIndex::build(config, &documents);
+
+        for node in &index.nodes {
+            for (layer, links) in node.neighbors.iter().enumerate() {
+                assert!(links.len() <= index.max_links(layer));
+            }
+        }
+    }
+
+    #[test]
+    fn test_index_serialization_roundtrip() {
+        let documents = random_documents(50, 8, 19);
+        let mut index = HnswIndex::build(HnswConfig::default(), &documents);
+        index.mark_deleted(3);
+
+        let json = serde_json::to_string(&index).unwrap();
+        let restored: HnswIndex = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.len(), 50);
+        assert_eq!(restored.deleted_count(), 1);
+        assert!(restored.is_deleted(3));
+
+        let query = &documents[10].embedding;
+        assert_eq!(
+            index.search(query, &documents, 5, 32, &|_| true),
+            restored.search(query, &documents, 5, 32, &|_| true)
+        );
+    }
+}
@@ -28,6 +28,18 @@ pub struct HybridSearchResult {
     /// Source of the result: "vector", "keyword", or "hybrid".
     pub source: String,
 
+    /// Similarity score from the vector phase, if the document was found there.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vector_score: Option<f32>,
+
+    /// Relevance score from the keyword phase, if the document was found there.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub keyword_score: Option<f32>,
+
+    /// Score from the rerank phase, if the document was reranked.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rerank_score: Option<f32>,
+
     /// Metadata snapshot.
     #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub metadata: HashMap<String, String>,
@@ -71,7 +83,7 @@ pub fn reciprocal_rank_fusion(
 ) -> Vec<HybridSearchResult> {
     let mut scores: HashMap<String, f32> = HashMap::new();
     let mut metadata: HashMap<String, HashMap<String, String>> = HashMap::new();
-    let mut sources: HashMap<String, (bool, bool)> = HashMap::new(); // (has_vector, has_fts)
+    let mut phase_scores: HashMap<String, (Option<f32>, Option<f32>)> = HashMap::new(); // (vector, fts)
 
     // Score vector results
     for (rank, result) in vector_results.iter().enumerate() {
@@ -80,7 +92,11 @@ pub fn reciprocal_rank_fusion(
         metadata
             .entry(result.id.clone())
             .or_insert_with(|| result.metadata.clone());
-        sources.entry(result.id.clone()).or_insert((false, false)).0 = true;
+        phase_scores
+            .entry(result.id.clone())
+            .or_insert((None, None))
+            .0
+            .get_or_insert(result.score);
     }
 
     // Score FTS results
@@ -90,15 +106,20 @@ pub fn reciprocal_rank_fusion(
         metadata
             .entry(result.id.clone())
             .or_insert_with(|| result.metadata.clone());
-        sources.entry(result.id.clone()).or_insert((false, false)).1 = true;
+        phase_scores
+            .entry(result.id.clone())
+            .or_insert((None, None))
+            .1
+            .get_or_insert(result.score);
     }
 
     // Build results and sort by RRF score
     let mut results: Vec<HybridSearchResult> = scores
         .into_iter()
         .map(|(id, score)| {
-            let (has_vector, has_fts) = sources.get(&id).copied().unwrap_or((false, false));
-            let source = match (has_vector, has_fts) {
+            let (vector_score, keyword_score) =
+                phase_scores.get(&id).copied().unwrap_or((None, None));
+            let source = match (vector_score.is_some(), keyword_score.is_some()) {
                 (true, true) => "hybrid",
                 (true, false) => "vector",
                 (false, true) => "keyword",
@@ -110,6 +131,9 @@ pub fn reciprocal_rank_fusion(
                 id: id.clone(),
                 score,
                 source,
+                vector_score,
+                keyword_score,
+                rerank_score: None,
                 metadata: metadata.remove(&id).unwrap_or_default(),
             }
         })
@@ -209,6 +233,23 @@ mod tests {
         assert_eq!(results[0].id, "shared");
     }
 
+    #[test]
+    fn test_rrf_records_phase_scores() {
+        let vector = make_vector_results(&["shared", "vec-only"]);
+        let fts = make_fts_results(&["shared", "fts-only"]);
+
+        let results = reciprocal_rank_fusion(&vector, &fts, 10, 60);
+
+        let shared = results.iter().find(|r| r.id == "shared").unwrap();
+        assert_eq!(shared.vector_score, Some(vector[0].score));
+        assert_eq!(shared.keyword_score, Some(fts[0].score));
+        assert!(shared.rerank_score.is_none());
+
+        let vec_only = results.iter().find(|r| r.id == "vec-only").unwrap();
+        assert!(vec_only.vector_score.is_some());
+        assert!(vec_only.keyword_score.is_none());
+    }
+
     #[test]
     fn test_rrf_respects_limit() {
         let vector = make_vector_results(&["a", "b", "c", "d", "e"]);
@@ -285,13 +326,19 @@ mod tests {
             id: "doc-1".to_string(),
             score: 0.5,
             source: "hybrid".to_string(),
+            vector_score: Some(0.8),
+            keyword_score: None,
+            rerank_score: None,
             metadata: HashMap::new(),
         };
 
         let json = serde_json::to_string(&result).unwrap();
         assert!(json.contains("doc-1"));
         assert!(json.contains("hybrid"));
-        // Empty metadata should be omitted
+        assert!(json.contains("\"vector_score\":0.8"));
+        // Empty metadata and missing phase scores should be omitted
         assert!(!json.contains("metadata"));
+        assert!(!json.contains("keyword_score"));
+        assert!(!json.contains("rerank_score"));
     }
 }
@@ -1,13 +1,18 @@
 //! Vector search infrastructure for Fabryk.
 //!
 //! This crate provides semantic vector search with pluggable embedding
-//! providers and vector backends. It includes LanceDB and fastembed
-//! backends (feature-gated), plus in-memory fallbacks for testing.
+//! providers and vector backends. It includes LanceDB, fastembed and remote
+//! HTTP embedding backends (feature-gated), plus in-memory fallbacks for
+//! testing.
 //!
 //! # Features
 //!
 //! - `vector-lancedb`: Enable LanceDB-based vector storage and ANN search
 //! - `vector-fastembed`: Enable local embedding generation via fastembed
+//! - `vector-remote`: Enable remote embedding services (OpenAI-compatible, Ollama, Vertex AI)
+//!   and the HTTP rerank client
+//! - `rerank-cross-encoder`: Enable local cross-encoder reranking via fastembed
+//! - `rerank-llm`: Enable LLM-as-judge reranking via `ecl-core`
 //!
 //! # Architecture
 //!
@@ -17,16 +22,19 @@
 //! ├─────────────────────────────────────────────────────────────┤
 //! │  EmbeddingProvider trait                                    │
 //! │  ├── MockEmbeddingProvider (always available)               │
-//! │  └── FastEmbedProvider (feature: vector-fastembed)          │
+//! │  ├── FastEmbedProvider (feature: vector-fastembed)          │
+//! │  ├── RemoteEmbeddingProvider (feature: vector-remote)       │
+//! │  └── CachedEmbeddingProvider (persistent embedding cache)   │
 //! ├─────────────────────────────────────────────────────────────┤
 //! │  VectorBackend trait                                        │
-//! │  ├── SimpleVectorBackend (in-memory fallback)               │
+//! │  ├── SimpleVectorBackend (in-memory, optional HNSW index)   │
 //! │  └── LancedbBackend (feature: vector-lancedb)              │
 //! ├─────────────────────────────────────────────────────────────┤
 //! │  VectorExtractor trait (domain text composition)            │
 //! │  VectorIndexBuilder (batch embed + index orchestration)     │
 //! ├─────────────────────────────────────────────────────────────┤
 //! │  Hybrid search (RRF merge with FTS results)                │
+//! │  Reranker trait (cross-encoder, HTTP, LLM judge)            │
 //! │  Persistence (content hash freshness checking)              │
 //! └─────────────────────────────────────────────────────────────┘
 //! ```
@@ -56,15 +64,18 @@
 // Core modules (always available)
 pub mod backend;
 pub mod embedding;
+pub mod embedding_cache;
+pub mod hnsw;
 pub mod types;
 
 // Builder and extractor modules (always available)
 pub mod builder;
 pub mod extractor;
 
-// Hybrid search and persistence (always available)
+// Hybrid search, reranking and persistence (always available)
 pub mod hybrid;
 pub mod persistence;
+pub mod rerank;
 
 // Feature-gated backend modules
 #[cfg(feature = "vector-fastembed")]
@@ -73,6 +84,9 @@ pub mod fastembed;
 #[cfg(feature = "vector-lancedb")]
 pub mod lancedb;
 
+#[cfg(feature = "vector-remote")]
+pub mod remote;
+
 // Re-exports — core types
 pub use types::{
     BuildError, EmbeddedDocument, VectorConfig, VectorDocument, VectorIndexStats,
@@ -81,8 +95,10 @@ pub use types::{
 
 // Re-exports — traits
 pub use backend::{SimpleVectorBackend, VectorBackend};
-pub use embedding::{EmbeddingProvider, MockEmbeddingProvider};
+pub use embedding::{EmbeddingProvider, MockEmbeddingProvider, create_embedding_provider};
+pub use embedding_cache::CachedEmbeddingProvider;
 pub use extractor::VectorExtractor;
+pub use hnsw::HnswConfig;
 
 // Re-exports — builder
 pub use builder::VectorIndexBuilder;
@@ -90,6 +106,9 @@ pub use builder::VectorIndexBuilder;
 // Re-exports — hybrid search
 pub use hybrid::{FtsResult, HybridSearchResult, reciprocal_rank_fusion};
 
+// Re-exports — reranking
+pub use rerank::{MockReranker, RerankCandidate, Reranker, rerank_results};
+
 // Re-exports — persistence
 pub use persistence::is_index_fresh;
 
@@ -102,3 +121,15 @@ pub use fastembed::FastEmbedProvider;
 
 #[cfg(feature = "vector-lancedb")]
 pub use lancedb::LancedbBackend;
+
+#[cfg(feature = "vector-remote")]
+pub use remote::{RemoteApi, RemoteEmbeddingConfig, RemoteEmbeddingProvider};
+
+#[cfg(feature = "vector-remote")]
+pub use rerank::{HttpReranker, RerankApi};
+
+#[cfg(feature = "rerank-cross-encoder")]
+pub use rerank::CrossEncoderReranker;
+
+#[cfg(feature = "rerank-llm")]
+pub use rerank::LlmReranker;
@@ -0,0 +1,818 @@
+//! Remote (HTTP) embedding providers.
+//!
+//! Calls an embedding service instead of running a model in-process, which
+//! keeps deployments such as Cloud Run free of ONNX runtimes and model files.
+//!
+//! # Supported APIs
+//!
+//! | API | Endpoint | Auth |
+//! |-----|----------|------|
+//! | `openai` | `POST {base_url}/embeddings` | `Authorization: Bearer <api_key>` |
+//! | `ollama` | `POST {base_url}/api/embed` | none |
+//! | `vertex` | `POST {base_url}/{model}:predict` | bearer token or GCE metadata server |
+//!
+//! The `openai` API also covers OpenAI-compatible servers (vLLM, LiteLLM,
+//! text-embeddings-inference, Azure with a custom base URL, etc.).
+//!
+//! All three APIs batch natively: `embed_batch` sends up to
+//! `max_batch_size` texts per request. Requests are spaced by an optional
+//! client-side rate limit and retried with exponential backoff on
+//! transport errors, HTTP 429 and 5xx responses.
+//!
+//! # Feature Gate
+//!
+//! This module requires the `vector-remote` feature.
+
+use crate::embedding::EmbeddingProvider;
+use async_trait::async_trait;
+use backon::{ExponentialBuilder, Retryable};
+use fabryk_core::{Error, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::time::Duration;
+use tokio::sync::Mutex;
+use tokio::time::Instant;
+
+/// Default OpenAI API base URL.
+pub const OPENAI_BASE_URL: &str = "https://api.openai.com/v1";
+
+/// Default Ollama base URL.
+pub const OLLAMA_BASE_URL: &str = "http://localhost:11434";
+
+/// GCE/Cloud Run metadata server token endpoint.
+const METADATA_TOKEN_URL: &str =
+    "http://metadata.google.internal/computeMetadata/v1/instance/service-accounts/default/token";
+
+// ============================================================================
+// Configuration
+// ============================================================================
+
+/// The wire protocol spoken by the remote embedding service.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RemoteApi {
+    /// OpenAI `/embeddings` (and compatible servers).
+    OpenAi,
+    /// Ollama `/api/embed`.
+    Ollama,
+    /// Vertex AI `:predict` on a publisher text-embedding model.
+    Vertex,
+}
+
+impl RemoteApi {
+    /// Parse a provider name as used in `VectorConfig::provider`.
+    pub fn from_provider_name(name: &str) -> Option<Self> {
+        match name {
+            "openai" => Some(Self::OpenAi),
+            "ollama" => Some(Self::Ollama),
+            "vertex" => Some(Self::Vertex),
+            _ => None,
+        }
+    }
+
+    fn default_base_url(self) -> Option<&'static str> {
+        match self {
+            Self::OpenAi => Some(OPENAI_BASE_URL),
+            Self::Ollama => Some(OLLAMA_BASE_URL),
+            // Vertex URLs embed the project and region; there is no sane default.
+            Self::Vertex => None,
+        }
+    }
+
+    fn default_max_batch_size(self) -> usize {
+        match self {
+            Self::OpenAi => 256,
+            Self::Ollama => 64,
+            Self::Vertex => 250,
+        }
+    }
+}
+
+/// Configuration for a [`RemoteEmbeddingProvider`].
+///
+/// For Vertex, `base_url` is the publisher models collection, e.g.
+/// `https://us-central1-aiplatform.googleapis.com/v1/projects/my-project/locations/us-central1/publishers/google/models`.
+#[derive(Debug, Clone)]
+pub struct RemoteEmbeddingConfig {
+    /// Wire protocol.
+    pub api: RemoteApi,
+    /// Service base URL (defaults per API where one exists).
+    pub base_url: Option<String>,
+    /// Model identifier sent to the service.
+    pub model: String,
+    /// Expected embedding dimension (probed from the service if 0).
+    pub dimension: usize,
+    /// API key or bearer token.
+    ///
+    /// For Vertex, when unset the token is fetched from the metadata server.
+    pub api_key: Option<String>,
+    /// Client-side request rate limit (requests per second).
+    pub requests_per_second: Option<f64>,
+    /// Maximum retry attempts for retryable failures.
+    pub max_retries: u32,
+    /// Initial retry delay.
+    pub initial_backoff: Duration,
+    /// Maximum retry delay.
+    pub max_backoff: Duration,
+    /// Maximum texts per request (defaults per API if 0).
+    pub max_batch_size: usize,
+    /// Per-request timeout.
+    pub timeout: Duration,
+}
+
+impl RemoteEmbeddingConfig {
+    /// Create a configuration for the given API and model.
+    pub fn new(api: RemoteApi, model: impl Into<String>) -> Self {
+        Self {
+            api,
+            base_url: None,
+            model: model.into(),
+            dimension: 0,
+            api_key: None,
+            requests_per_second: None,
+            max_retries: 3,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(10),
+            max_batch_size: 0,
+            timeout: Duration::from_secs(30),
+        }
+    }
+
+    /// Set the service base URL.
+    pub fn with_base_url(mut self, url: impl Into<String>) -> Self {
+        self.base_url = Some(url.into());
+        self
+    }
+
+    /// Set the expected embedding dimension.
+    pub fn with_dimension(mut self, dimension: usize) -> Self {
+        self.dimension = dimension;
+        self
+    }
+
+    /// Set the API key or bearer token.
+    pub fn with_api_key(mut self, key: impl Into<String>) -> Self {
+        self.api_key = Some(key.into());
+        self
+    }
+
+    /// Set the client-side rate limit in requests per second.
+    pub fn with_requests_per_second(mut self, rps: f64) -> Self {
+        self.requests_per_second = Some(rps);
+        self
+    }
+
+    /// Set the maximum number of retries.
+    pub fn with_max_retries(mut self, retries: u32) -> Self {
+        self.max_retries = retries;
+        self
+    }
+
+    /// Set the initial and maximum retry delays.
+    pub fn with_backoff(mut self, initial: Duration, max: Duration) -> Self {
+        self.initial_backoff = initial;
+        self.max_backoff = max;
+        self
+    }
+
+    /// Set the maximum number of texts per request.
+    pub fn with_max_batch_size(mut self, size: usize) -> Self {
+        self.max_batch_size = size;
+        self
+    }
+
+    /// Set the per-request timeout.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+}
+
+// ============================================================================
+// Rate limiting and retries
+// ============================================================================
+
+/// Spaces requests evenly to stay under a requests-per-second budget.
+pub(crate) struct RateLimiter {
+    interval: Duration,
+    next_slot: Mutex<Instant>,
+}
+
+impl RateLimiter {
+    /// A limiter for `requests_per_second`, or `None` unless the rate is
+    /// finite, positive and gives a representable interval.
+    pub(crate) fn new(requests_per_second: f64) -> Option<Self> {
+        if !(requests_per_second.is_finite() && requests_per_second > 0.0) {
+            return None;
+        }
+        Some(Self {
+            interval: Duration::try_from_secs_f64(1.0 / requests_per_second).ok()?,
+            next_slot: Mutex::new(Instant::now()),
+        })
+    }
+
+    pub(crate) async fn acquire(&self) {
+        let mut next_slot = self.next_slot.lock().await;
+        let now = Instant::now();
+        if *next_slot > now {
+            tokio::time::sleep_until(*next_slot).await;
+        }
+        *next_slot = (*next_slot).max(now) + self.interval;
+    }
+}
+
+/// Failure of a single request attempt.
+#[derive(Debug)]
+pub(crate) struct AttemptError {
+    pub(crate) message: String,
+    pub(crate) retryable: bool,
+}
+
+impl AttemptError {
+    pub(crate) fn retryable(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            retryable: true,
+        }
+    }
+
+    pub(crate) fn fatal(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            retryable: false,
+        }
+    }
+}
+
+/// Cached OAuth access token from the metadata server.
+struct CachedToken {
+    token: String,
+    expires_at: Instant,
+}
+
+// ============================================================================
+// Provider
+// ============================================================================
+
+/// HTTP-based embedding provider for OpenAI-compatible, Ollama and Vertex
+/// AI embedding services.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use fabryk_vector::{RemoteApi, RemoteEmbeddingConfig, RemoteEmbeddingProvider};
+///
+/// let config = RemoteEmbeddingConfig::new(RemoteApi::OpenAi, "text-embedding-3-small")
+///     .with_api_key(std::env::var("OPENAI_API_KEY")?)
+///     .with_requests_per_second(5.0);
+/// let provider = RemoteEmbeddingProvider::new(config).await?;
+/// ```
+pub struct RemoteEmbeddingProvider {
+    config: RemoteEmbeddingConfig,
+    base_url: String,
+    max_batch_size: usize,
+    dimension: usize,
+    client: reqwest::Client,
+    rate_limiter: Option<RateLimiter>,
+    token_url: String,
+    token: Mutex<Option<CachedToken>>,
+}
+
+impl RemoteEmbeddingProvider {
+    /// Create a provider from configuration.
+    ///
+    /// If `config.dimension` is 0, a probe embedding is requested to detect
+    /// the dimension.
+    pub async fn new(config: RemoteEmbeddingConfig) -> Result<Self> {
+        let mut provider = Self::unprobed(config)?;
+        if provider.dimension == 0 {
+            let probe = provider.request_batch(&["dimension probe"]).await?;
+            provider.dimension = probe
+                .first()
+                .map(Vec::len)
+                .ok_or_else(|| Error::operation("Empty probe embedding"))?;
+        }
+        Ok(provider)
+    }
+
+    fn unprobed(config: RemoteEmbeddingConfig) -> Result<Self> {
+        let base_url = config
+            .base_url
+            .clone()
+            .or_else(|| config.api.default_base_url().map(str::to_string))
+            .ok_or_else(|| {
+                Error::config(format!(
+                    "base_url is required for the {:?} embedding API",
+                    config.api
+                ))
+            })?
+            .trim_end_matches('/')
+            .to_string();
+
+        if config.model.is_empty() {
+            return Err(Error::config("Remote embedding model must not be empty"));
+        }
+
+        let rate_limiter = config
+            .requests_per_second
+            .map(|rps| {
+                RateLimiter::new(rps).ok_or_else(|| {
+                    Error::config(format!(
+                        "requests_per_second must be a positive finite number, got {rps}"
+                    ))
+                })
+            })
+            .transpose()?;
+
+        let client = reqwest::Client::builder()
+            .timeout(config.timeout)
+            .build()
+            .map_err(|e| Error::config(format!("Failed to build HTTP client: {e}")))?;
+
+        let max_batch_size = if config.max_batch_size == 0 {
+            config.api.default_max_batch_size()
+        } else {
+            config.max_batch_size
+        };
+
+        Ok(Self {
+            dimension: config.dimension,
+            base_url,
+            max_batch_size,
+            client,
+            rate_limiter,
+            token_url: METADATA_TOKEN_URL.to_string(),
+            token: Mutex::new(None),
+            config,
+        })
+    }
+
+    /// Override the metadata-server token URL (for testing).
+    #[cfg(test)]
+    fn with_token_url(mut self, url: impl Into<String>) -> Self {
+        self.token_url = url.into();
+        self
+    }
+
+    /// The wire protocol in use.
+    pub fn api(&self) -> RemoteApi {
+        self.config.api
+    }
+
+    /// Embed one request's worth of texts, with rate limiting and retries.
+    async fn request_batch(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>> {
+        let backoff = ExponentialBuilder::default()
+            .with_min_delay(self.config.initial_backoff)
+            .with_max_delay(self.config.max_backoff)
+            .with_max_times(self.config.max_retries as usize);
+
+        let embeddings = (|| async { self.attempt(texts).await })
+            .retry(backoff)
+            .when(|e: &AttemptError| e.retryable)
+            .notify(|e: &AttemptError, delay: Duration| {
+                log::warn!(
+                    "Embedding request to {} failed, retrying in {delay:?}: {}",
+                    self.base_url,
+                    e.message
+                );
+            })
+            .await
+            .map_err(|e| Error::operation(format!("Remote embedding failed: {}", e.message)))?;
+
+        if embeddings.len() != texts.len() {
+            return Err(Error::operation(format!(
+                "Remote embedding service returned {} embeddings for {} texts",
+                embeddings.len(),
+                texts.len()
+            )));
+        }
+        if self.dimension > 0
+            && let Some(bad) = embeddings.iter().find(|e| e.len() != self.dimension)
+        {
+            return Err(Error::operation(format!(
+                "Remote embedding dimension mismatch: expected {}, got {}",
+                self.dimension,
+                bad.len()
+            )));
+        }
+
+        Ok(embeddings)
+    }
+
+    /// A single HTTP round trip.
+    async fn attempt(&self, texts: &[&str]) -> std::result::Result<Vec<Vec<f32>>, AttemptError> {
+        if let Some(ref limiter) = self.rate_limiter {
+            limiter.acquire().await;
+        }
+
+        let (url, body) = match self.config.api {
+            RemoteApi::OpenAi => (
+                format!("{}/embeddings", self.base_url),
+                json!({ "model": self.config.model, "input": texts }),
+            ),
+            RemoteApi::Ollama => (
+                format!("{}/api/embed", self.base_url),
+                json!({ "model": self.config.model, "input": texts }),
+            ),
+            RemoteApi::Vertex => {
+                let instances: Vec<_> = texts.iter().map(|t| json!({ "content": t })).collect();
+                (
+                    format!("{}/{}:predict", self.base_url, self.config.model),
+                    json!({ "instances": instances }),
+                )
+            }
+        };
+
+        let mut request = self.client.post(&url).json(&body);
+        if let Some(token) = self.bearer_token().await? {
+            request = request.bearer_auth(token);
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| AttemptError::retryable(format!("request to {url} failed: {e}")))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            let message = format!("{url} returned HTTP {status}: {body}");
+            return Err(if status.as_u16() == 429 || status.is_server_error() {
+                AttemptError::retryable(message)
+            } else {
+                AttemptError::fatal(message)
+            });
+        }
+
+        let value: serde_json::Value = response
+            .json()
+            .await
+            .map_err(|e| AttemptError::retryable(format!("invalid response from {url}: {e}")))?;
+
+        parse_embeddings(self.config.api, value).map_err(AttemptError::fatal)
+    }
+
+    /// Resolve the bearer token for the next request, if any.
+    async fn bearer_token(&self) -> std::result::Result<Option<String>, AttemptError> {
+        if let Some(ref key) = self.config.api_key {
+            return Ok(Some(key.clone()));
+        }
+        if self.config.api != RemoteApi::Vertex {
+            return Ok(None);
+        }
+
+        let mut cached = self.token.lock().await;
+        if let Some(ref token) = *cached
+            && token.expires_at > Instant::now()
+        {
+            return Ok(Some(token.token.clone()));
+        }
+
+        #[derive(Deserialize)]
+        struct TokenResponse {
+            access_token: String,
+            expires_in: u64,
+        }
+
+        let response = self
+            .client
+            .get(&self.token_url)
+            .header("Metadata-Flavor", "Google")
+            .send()
+            .await
+            .map_err(|e| AttemptError::retryable(format!("metadata token request failed: {e}")))?;
+        if !response.status().is_success() {
+            return Err(AttemptError::fatal(format!(
+                "metadata token request returned HTTP {}",
+                response.status()
+            )));
+        }
+        let token: TokenResponse = response
+            .json()
+            .await
+            .map_err(|e| AttemptError::fatal(format!("invalid metadata token response: {e}")))?;
+
+        // Refresh a minute early to avoid racing the expiry.
+        let lifetime = Duration::from_secs(token.expires_in.saturating_sub(60));
+        *cached = Some(CachedToken {
+            token: token.access_token.clone(),
+            expires_at: Instant::now() + lifetime,
+        });
+        Ok(Some(token.access_token))
+    }
+}
+
+/// Extract embeddings from an API response body.
+fn parse_embeddings(
+    api: RemoteApi,
+    value: serde_json::Value,
+) -> std::result::Result<Vec<Vec<f32>>, String> {
+    #[derive(Deserialize)]
+    struct OpenAiItem {
+        index: usize,
+        embedding: Vec<f32>,
+    }
+    #[derive(Deserialize)]
+    struct OpenAiResponse {
+        data: Vec<OpenAiItem>,
+    }
+    #[derive(Deserialize)]
+    struct OllamaResponse {
+        embeddings: Vec<Vec<f32>>,
+    }
+    #[derive(Deserialize)]
+    struct VertexValues {
+        values: Vec<f32>,
+    }
+    #[derive(Deserialize)]
+    struct VertexPrediction {
+        embeddings: VertexValues,
+    }
+    #[derive(Deserialize)]
+    struct VertexResponse {
+        predictions: Vec<VertexPrediction>,
+    }
+
+    let parsed = match api {
+        RemoteApi::OpenAi => serde_json::from_value::<OpenAiResponse>(value).map(|r| {
+            let mut data = r.data;
+            data.sort_by_key(|item| item.index);
+            data.into_iter().map(|item| item.embedding).collect()
+        }),
+        RemoteApi::Ollama => serde_json::from_value::<OllamaResponse>(value).map(|r| r.embeddings),
+        RemoteApi::Vertex => serde_json::from_value::<VertexResponse>(value).map(|r| {
+            r.predictions
+                .into_iter()
+                .map(|p| p.embeddings.values)
+                .collect()
+        }),
+    };
+
+    parsed.map_err(|e| format!("unexpected {api:?} embedding response: {e}"))
+}
+
+#[async_trait]
+impl EmbeddingProvider for RemoteEmbeddingProvider {
+    async fn embed(&self, text: &str) -> Result<Vec<f32>> {
+        self.request_batch(&[text])
+            .await?
+            .pop()
+            .ok_or_else(|| Error::operation("No embedding returned"))
+    }
+
+    async fn embed_batch(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>> {
+        let mut results = Vec::with_capacity(texts.len());
+        for chunk in texts.chunks(self.max_batch_size) {
+            results.extend(self.request_batch(chunk).await?);
+        }
+        Ok(results)
+    }
+
+    fn dimension(&self) -> usize {
+        self.dimension
+    }
+
+    fn name(&self) -> &str {
+        &self.config.model
+    }
+}
+
+impl std::fmt::Debug for RemoteEmbeddingProvider {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RemoteEmbeddingProvider")
+            .field("api", &self.config.api)
+            .field("base_url", &self.base_url)
+            .field("model", &self.config.model)
+            .field("dimension", &self.dimension)
+            .finish()
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{body_json, header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn fast_config(api: RemoteApi, server: &MockServer) -> RemoteEmbeddingConfig {
+        RemoteEmbeddingConfig::new(api, "test-model")
+            .with_base_url(server.uri())
+            .with_dimension(2)
+            .with_backoff(Duration::from_millis(1), Duration::from_millis(5))
+    }
+
+    #[test]
+    fn test_remote_api_from_provider_name() {
+        assert_eq!(
+            RemoteApi::from_provider_name("openai"),
+            Some(RemoteApi::OpenAi)
+        );
+        assert_eq!(
+            RemoteApi::from_provider_name("ollama"),
+            Some(RemoteApi::Ollama)
+        );
+        assert_eq!(
+            RemoteApi::from_provider_name("vertex"),
+            Some(RemoteApi::Vertex)
+        );
+        assert_eq!(RemoteApi::from_provider_name("fastembed"), None);
+    }
+
+    #[test]
+    fn test_vertex_requires_base_url() {
+        let config = RemoteEmbeddingConfig::new(RemoteApi::Vertex, "text-embedding-005");
+        let err = RemoteEmbeddingProvider::unprobed(config).unwrap_err();
+        assert!(err.to_string().contains("base_url is required"));
+    }
+
+    #[test]
+    fn test_invalid_rate_limit() {
+        for rps in [0.0, -1.0, f64::NAN, f64::INFINITY, 1e-300] {
+            let config =
+                RemoteEmbeddingConfig::new(RemoteApi::OpenAi, "m").with_requests_per_second(rps);
+            let err = RemoteEmbeddingProvider::unprobed(config).unwrap_err();
+            assert!(err.to_string().contains("requests_per_second"), "{rps}");
+        }
+    }
+
+    #[test]
+    fn test_default_batch_size_per_api() {
+        let config = RemoteEmbeddingConfig::new(RemoteApi::Ollama, "m");
+        let provider = RemoteEmbeddingProvider::unprobed(config).unwrap();
+        assert_eq!(provider.max_batch_size, 64);
+        assert_eq!(provider.base_url, OLLAMA_BASE_URL);
+    }
+
+    #[tokio::test]
+    async fn test_openai_batch_sorted_by_index() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/embeddings"))
+            .and(header("authorization", "Bearer sk-test"))
+            .and(body_json(
+                json!({ "model": "test-model", "input": ["a", "b"] }),
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "data": [
+                    { "index": 1, "embedding": [0.0, 1.0] },
+                    { "index": 0, "embedding": [1.0, 0.0] }
+                ]
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let config = fast_config(RemoteApi::OpenAi, &server).with_api_key("sk-test");
+        let provider = RemoteEmbeddingProvider::new(config).await.unwrap();
+        let embeddings = provider.embed_batch(&["a", "b"]).await.unwrap();
+
+        assert_eq!(embeddings, vec![vec![1.0, 0.0], vec![0.0, 1.0]]);
+        assert_eq!(provider.name(), "test-model");
+    }
+
+    #[tokio::test]
+    async fn test_ollama_probes_dimension() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/api/embed"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(json!({ "embeddings": [[0.1, 0.2, 0.3]] })),
+            )
+            .mount(&server)
+            .await;
+
+        let config = fast_config(RemoteApi::Ollama, &server).with_dimension(0);
+        let provider = RemoteEmbeddingProvider::new(config).await.unwrap();
+        assert_eq!(provider.dimension(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_vertex_predict_with_metadata_token() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/token"))
+            .and(header("metadata-flavor", "Google"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "access_token": "ya29.test",
+                "expires_in": 3600,
+                "token_type": "Bearer"
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/test-model:predict"))
+            .and(header("authorization", "Bearer ya29.test"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "predictions": [
+                    { "embeddings": { "values": [0.5, 0.5] } }
+                ]
+            })))
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        let config = fast_config(RemoteApi::Vertex, &server);
+        let provider = RemoteEmbeddingProvider::unprobed(config)
+            .unwrap()
+            .with_token_url(format!("{}/token", server.uri()));
+
+        assert_eq!(provider.embed("x").await.unwrap(), vec![0.5, 0.5]);
+        // Second call reuses the cached token.
+        assert_eq!(provider.embed("y").await.unwrap(), vec![0.5, 0.5]);
+    }
+
+    #[tokio::test]
+    async fn test_splits_into_max_batch_size_requests() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/api/embed"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(json!({ "embeddings": [[1.0, 0.0]] })),
+            )
+            .expect(3)
+            .mount(&server)
+            .await;
+
+        let config = fast_config(RemoteApi::Ollama, &server).with_max_batch_size(1);
+        let provider = RemoteEmbeddingProvider::new(config).await.unwrap();
+        let embeddings = provider.embed_batch(&["a", "b", "c"]).await.unwrap();
+        assert_eq!(embeddings.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_retries_on_server_error() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/api/embed"))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(2)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/embed"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(json!({ "embeddings": [[1.0, 0.0]] })),
+            )
+            .mount(&server)
+            .await;
+
+        let config = fast_config(RemoteApi::Ollama, &server);
+        let provider = RemoteEmbeddingProvider::new(config).await.unwrap();
+        assert_eq!(provider.embed("a").await.unwrap(), vec![1.0, 0.0]);
+    }
+
+    #[tokio::test]
+    async fn test_no_retry_on_client_error() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/embeddings"))
+            .respond_with(ResponseTemplate::new(401).set_body_string("bad key"))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let config = fast_config(RemoteApi::OpenAi, &server);
+        let provider = RemoteEmbeddingProvider::new(config).await.unwrap();
+        let err = provider.embed("a").await.unwrap_err();
+        assert!(err.to_string().contains("401"));
+    }
+
+    #[tokio::test]
+    async fn test_dimension_mismatch_is_error() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/api/embed"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(json!({ "embeddings": [[1.0]] })),
+            )
+            .mount(&server)
+            .await;
+
+        let config = fast_config(RemoteApi::Ollama, &server);
+        let provider = RemoteEmbeddingProvider::new(config).await.unwrap();
+        let err = provider.embed("a").await.unwrap_err();
+        assert!(err.to_string().contains("dimension mismatch"));
+    }
+
+    #[tokio::test]
+    async fn test_rate_limiter_spaces_requests() {
+        let limiter = RateLimiter::new(100.0).unwrap();
+        let start = Instant::now();
+        for _ in 0..3 {
+            limiter.acquire().await;
+        }
+        assert!(start.elapsed() >= Duration::from_millis(20));
+    }
+
+    #[test]
+    fn test_parse_embeddings_rejects_wrong_shape() {
+        let err = parse_embeddings(RemoteApi::Ollama, json!({ "data": [] })).unwrap_err();
+        assert!(err.contains("unexpected Ollama"));
+    }
+}
@@ -0,0 +1,163 @@
+//! Local cross-encoder reranker.
+//!
+//! Wraps `fastembed::TextRerank`, which runs a cross-encoder (e.g.
+//! BGE-reranker) over each query–document pair.
+//!
+//! # Thread Safety
+//!
+//! Like `FastEmbedProvider`, the model is wrapped in `Arc<Mutex<>>` and
+//! scored on `tokio::task::spawn_blocking`.
+//!
+//! # Feature Gate
+//!
+//! This module requires the `rerank-cross-encoder` feature.
+
+use super::{RerankCandidate, Reranker};
+use async_trait::async_trait;
+use fabryk_core::{Error, Result};
+use std::sync::{Arc, Mutex};
+
+/// Map a model name string to a fastembed `RerankerModel` enum variant.
+fn resolve_model(name: &str) -> Result<fastembed::RerankerModel> {
+    match name {
+        "bge-reranker-base" | "BGERerankerBase" => Ok(fastembed::RerankerModel::BGERerankerBase),
+        "bge-reranker-v2-m3" | "BGERerankerV2M3" => Ok(fastembed::RerankerModel::BGERerankerV2M3),
+        "jina-reranker-v1-turbo-en" | "JINARerankerV1TurboEn" => {
+            Ok(fastembed::RerankerModel::JINARerankerV1TurboEn)
+        }
+        "jina-reranker-v2-base-multilingual" | "JINARerankerV2BaseMultiligual" => {
+            Ok(fastembed::RerankerModel::JINARerankerV2BaseMultiligual)
+        }
+        other => Err(Error::config(format!(
+            "Unknown reranker model: '{other}'. Supported: bge-reranker-base, bge-reranker-v2-m3, jina-reranker-v1-turbo-en, jina-reranker-v2-base-multilingual"
+        ))),
+    }
+}
+
+/// Cross-encoder reranker backed by fastembed.
+///
+/// # Supported Models
+///
+/// | Name | Languages |
+/// |------|-----------|
+/// | `bge-reranker-base` | English, Chinese |
+/// | `bge-reranker-v2-m3` | Multilingual |
+/// | `jina-reranker-v1-turbo-en` | English |
+/// | `jina-reranker-v2-base-multilingual` | Multilingual |
+pub struct CrossEncoderReranker {
+    model: Arc<Mutex<fastembed::TextRerank>>,
+    model_name: String,
+}
+
+impl CrossEncoderReranker {
+    /// Create a new cross-encoder reranker.
+    ///
+    /// Downloads the model if not cached locally.
+    ///
+    /// # Arguments
+    ///
+    /// * `model_name` - Model identifier (e.g., "bge-reranker-base")
+    /// * `cache_path` - Optional directory for model file caching
+    pub fn new(model_name: &str, cache_path: Option<&str>) -> Result<Self> {
+        let model_enum = resolve_model(model_name)?;
+
+        let mut init = fastembed::RerankInitOptions::new(model_enum);
+        if let Some(path) = cache_path {
+            init = init.with_cache_dir(std::path::PathBuf::from(path));
+        }
+
+        let reranker = fastembed::TextRerank::try_new(init)
+            .map_err(|e| Error::operation(format!("Failed to initialize reranker model: {e}")))?;
+
+        Ok(Self {
+            model: Arc::new(Mutex::new(reranker)),
+            model_name: model_name.to_string(),
+        })
+    }
+}
+
+#[async_trait]
+impl Reranker for CrossEncoderReranker {
+    async fn score(&self, query: &str, candidates: &[RerankCandidate]) -> Result<Vec<f32>> {
+        if candidates.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let model = self.model.clone();
+        let query = query.to_string();
+        let documents: Vec<String> = candidates.iter().map(|c| c.text.clone()).collect();
+
+        tokio::task::spawn_blocking(move || {
+            let mut model = model
+                .lock()
+                .map_err(|e| Error::operation(format!("Mutex poisoned: {e}")))?;
+            let results = model
+                .rerank(query, documents.clone(), false, None)
+                .map_err(|e| Error::operation(format!("Reranking failed: {e}")))?;
+
+            // fastembed returns results sorted by score; restore candidate order
+            let mut scores = vec![0.0f32; documents.len()];
+            for result in results {
+                if let Some(slot) = scores.get_mut(result.index) {
+                    *slot = result.score;
+                }
+            }
+            Ok(scores)
+        })
+        .await
+        .map_err(|e| Error::operation(format!("spawn_blocking failed: {e}")))?
+    }
+
+    fn name(&self) -> &str {
+        &self.model_name
+    }
+}
+
+impl std::fmt::Debug for CrossEncoderReranker {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CrossEncoderReranker")
+            .field("model", &self.model_name)
+            .finish()
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_model_known() {
+        assert!(resolve_model("bge-reranker-base").is_ok());
+        assert!(resolve_model("bge-reranker-v2-m3").is_ok());
+        assert!(resolve_model("jina-reranker-v1-turbo-en").is_ok());
+        assert!(resolve_model("jina-reranker-v2-base-multilingual").is_ok());
+    }
+
+    #[test]
+    fn test_resolve_model_unknown() {
+        let err = resolve_model("nonexistent-model").unwrap_err();
+        assert!(err.to_string().contains("Unknown reranker model"));
+    }
+
+    #[tokio::test]
+    #[ignore = "requires model download (~280MB)"]
+    async fn test_cross_encoder_prefers_relevant_text() {
+        let reranker = CrossEncoderReranker::new("bge-reranker-base", None).unwrap();
+        let candidates = vec![
+            RerankCandidate::new("1", "The weather is sunny today."),
+            RerankCandidate::new(
+                "2",
+                "A cadence is a harmonic progression that ends a phrase.",
+            ),
+        ];
+        let scores = reranker
+            .score("what is a cadence in music?", &candidates)
+            .await
+            .unwrap();
+        assert!(scores[1] > scores[0]);
+    }
+}
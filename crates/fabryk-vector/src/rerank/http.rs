@@ -0,0 +1,356 @@
+//! HTTP rerank API client.
+//!
+//! Two request/response shapes are supported:
+//!
+//! | API | Endpoint | Request | Response |
+//! |-----|----------|---------|----------|
+//! | `cohere` | `POST {base_url}/rerank` | `{model, query, documents}` | `{results: [{index, relevance_score}]}` |
+//! | `tei` | `POST {base_url}/rerank` | `{query, texts}` | `[{index, score}]` |
+//!
+//! The `cohere` shape is also served by Jina, Voyage, vLLM and LiteLLM.
+//! `tei` is Hugging Face text-embeddings-inference.
+//!
+//! # Feature Gate
+//!
+//! This module requires the `vector-remote` feature.
+
+use super::{RerankCandidate, Reranker};
+use crate::remote::{AttemptError, RateLimiter};
+use async_trait::async_trait;
+use backon::{ExponentialBuilder, Retryable};
+use fabryk_core::{Error, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::time::Duration;
+
+/// The request/response shape of the rerank service.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RerankApi {
+    /// Cohere-compatible `/rerank`.
+    Cohere,
+    /// Hugging Face text-embeddings-inference `/rerank`.
+    Tei,
+}
+
+/// Reranker that calls a remote rerank API.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use fabryk_vector::{HttpReranker, RerankApi};
+///
+/// let reranker = HttpReranker::new(RerankApi::Cohere, "https://api.cohere.com/v2", "rerank-v3.5")?
+///     .with_api_key(std::env::var("COHERE_API_KEY")?);
+/// ```
+pub struct HttpReranker {
+    api: RerankApi,
+    base_url: String,
+    model: String,
+    api_key: Option<String>,
+    client: reqwest::Client,
+    rate_limiter: Option<RateLimiter>,
+    max_retries: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+}
+
+impl HttpReranker {
+    /// Create a reranker for the given API, base URL and model.
+    pub fn new(
+        api: RerankApi,
+        base_url: impl Into<String>,
+        model: impl Into<String>,
+    ) -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(30))
+            .build()
+            .map_err(|e| Error::config(format!("Failed to build HTTP client: {e}")))?;
+
+        Ok(Self {
+            api,
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            model: model.into(),
+            api_key: None,
+            client,
+            rate_limiter: None,
+            max_retries: 3,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(10),
+        })
+    }
+
+    /// Set the API key sent as a bearer token.
+    pub fn with_api_key(mut self, key: impl Into<String>) -> Self {
+        self.api_key = Some(key.into());
+        self
+    }
+
+    /// Set a client-side rate limit in requests per second. Rates that
+    /// are not positive and finite disable the limit.
+    pub fn with_requests_per_second(mut self, rps: f64) -> Self {
+        self.rate_limiter = RateLimiter::new(rps);
+        self
+    }
+
+    /// Set the maximum number of retries.
+    pub fn with_max_retries(mut self, retries: u32) -> Self {
+        self.max_retries = retries;
+        self
+    }
+
+    /// Set the initial and maximum retry delays.
+    pub fn with_backoff(mut self, initial: Duration, max: Duration) -> Self {
+        self.initial_backoff = initial;
+        self.max_backoff = max;
+        self
+    }
+
+    async fn attempt(
+        &self,
+        query: &str,
+        texts: &[&str],
+    ) -> std::result::Result<Vec<f32>, AttemptError> {
+        if let Some(ref limiter) = self.rate_limiter {
+            limiter.acquire().await;
+        }
+
+        let url = format!("{}/rerank", self.base_url);
+        let body = match self.api {
+            RerankApi::Cohere => json!({ "model": self.model, "query": query, "documents": texts }),
+            RerankApi::Tei => json!({ "query": query, "texts": texts }),
+        };
+
+        let mut request = self.client.post(&url).json(&body);
+        if let Some(ref key) = self.api_key {
+            request = request.bearer_auth(key);
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| AttemptError::retryable(format!("request to {url} failed: {e}")))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            let message = format!("{url} returned HTTP {status}: {body}");
+            return Err(if status.as_u16() == 429 || status.is_server_error() {
+                AttemptError::retryable(message)
+            } else {
+                AttemptError::fatal(message)
+            });
+        }
+
+        let value: serde_json::Value = response
+            .json()
+            .await
+            .map_err(|e| AttemptError::retryable(format!("invalid response from {url}: {e}")))?;
+
+        parse_scores(self.api, value, texts.len()).map_err(AttemptError::fatal)
+    }
+}
+
+/// Extract per-document scores (in document order) from a response body.
+fn parse_scores(
+    api: RerankApi,
+    value: serde_json::Value,
+    count: usize,
+) -> std::result::Result<Vec<f32>, String> {
+    #[derive(Deserialize)]
+    struct CohereItem {
+        index: usize,
+        relevance_score: f32,
+    }
+    #[derive(Deserialize)]
+    struct CohereResponse {
+        results: Vec<CohereItem>,
+    }
+    #[derive(Deserialize)]
+    struct TeiItem {
+        index: usize,
+        score: f32,
+    }
+
+    let pairs: Vec<(usize, f32)> = match api {
+        RerankApi::Cohere => serde_json::from_value::<CohereResponse>(value).map(|r| {
+            r.results
+                .into_iter()
+                .map(|i| (i.index, i.relevance_score))
+                .collect()
+        }),
+        RerankApi::Tei => serde_json::from_value::<Vec<TeiItem>>(value)
+            .map(|r| r.into_iter().map(|i| (i.index, i.score)).collect()),
+    }
+    .map_err(|e| format!("unexpected {api:?} rerank response: {e}"))?;
+
+    if pairs.len() != count {
+        return Err(format!(
+            "rerank service returned {} scores for {count} documents",
+            pairs.len()
+        ));
+    }
+
+    let mut scores = vec![0.0f32; count];
+    for (index, score) in pairs {
+        let slot = scores
+            .get_mut(index)
+            .ok_or_else(|| format!("rerank service returned out-of-range index {index}"))?;
+        *slot = score;
+    }
+    Ok(scores)
+}
+
+#[async_trait]
+impl Reranker for HttpReranker {
+    async fn score(&self, query: &str, candidates: &[RerankCandidate]) -> Result<Vec<f32>> {
+        if candidates.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let texts: Vec<&str> = candidates.iter().map(|c| c.text.as_str()).collect();
+        let backoff = ExponentialBuilder::default()
+            .with_min_delay(self.initial_backoff)
+            .with_max_delay(self.max_backoff)
+            .with_max_times(self.max_retries as usize);
+
+        (|| async { self.attempt(query, &texts).await })
+            .retry(backoff)
+            .when(|e: &AttemptError| e.retryable)
+            .notify(|e: &AttemptError, delay: Duration| {
+                log::warn!(
+                    "Rerank request to {} failed, retrying in {delay:?}: {}",
+                    self.base_url,
+                    e.message
+                );
+            })
+            .await
+            .map_err(|e| Error::operation(format!("Remote rerank failed: {}", e.message)))
+    }
+
+    fn name(&self) -> &str {
+        &self.model
+    }
+}
+
+impl std::fmt::Debug for HttpReranker {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HttpReranker")
+            .field("api", &self.api)
+            .field("base_url", &self.base_url)
+            .field("model", &self.model)
+            .finish()
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{body_json, header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn candidates() -> Vec<RerankCandidate> {
+        vec![
+            RerankCandidate::new("a", "first"),
+            RerankCandidate::new("b", "second"),
+        ]
+    }
+
+    #[tokio::test]
+    async fn test_cohere_scores_in_candidate_order() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/rerank"))
+            .and(header("authorization", "Bearer key"))
+            .and(body_json(json!({
+                "model": "rerank-test",
+                "query": "q",
+                "documents": ["first", "second"]
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "results": [
+                    { "index": 1, "relevance_score": 0.9 },
+                    { "index": 0, "relevance_score": 0.2 }
+                ]
+            })))
+            .mount(&server)
+            .await;
+
+        let reranker = HttpReranker::new(RerankApi::Cohere, server.uri(), "rerank-test")
+            .unwrap()
+            .with_api_key("key");
+        let scores = reranker.score("q", &candidates()).await.unwrap();
+        assert_eq!(scores, vec![0.2, 0.9]);
+        assert_eq!(reranker.name(), "rerank-test");
+    }
+
+    #[tokio::test]
+    async fn test_tei_shape() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/rerank"))
+            .and(body_json(
+                json!({ "query": "q", "texts": ["first", "second"] }),
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+                { "index": 0, "score": 0.7 },
+                { "index": 1, "score": 0.1 }
+            ])))
+            .mount(&server)
+            .await;
+
+        let reranker = HttpReranker::new(RerankApi::Tei, server.uri(), "tei").unwrap();
+        let scores = reranker.score("q", &candidates()).await.unwrap();
+        assert_eq!(scores, vec![0.7, 0.1]);
+    }
+
+    #[tokio::test]
+    async fn test_retries_on_rate_limit() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/rerank"))
+            .respond_with(ResponseTemplate::new(429))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/rerank"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+                { "index": 0, "score": 0.5 },
+                { "index": 1, "score": 0.5 }
+            ])))
+            .mount(&server)
+            .await;
+
+        let reranker = HttpReranker::new(RerankApi::Tei, server.uri(), "tei")
+            .unwrap()
+            .with_backoff(Duration::from_millis(1), Duration::from_millis(5));
+        assert_eq!(
+            reranker.score("q", &candidates()).await.unwrap(),
+            vec![0.5, 0.5]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_empty_candidates_skip_request() {
+        let reranker = HttpReranker::new(RerankApi::Tei, "http://127.0.0.1:9", "tei").unwrap();
+        assert!(reranker.score("q", &[]).await.unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_parse_scores_rejects_out_of_range_index() {
+        let err =
+            parse_scores(RerankApi::Tei, json!([{ "index": 5, "score": 1.0 }]), 1).unwrap_err();
+        assert!(err.contains("out-of-range"));
+    }
+
+    #[test]
+    fn test_parse_scores_rejects_count_mismatch() {
+        let err = parse_scores(RerankApi::Tei, json!([]), 2).unwrap_err();
+        assert!(err.contains("0 scores for 2"));
+    }
+}
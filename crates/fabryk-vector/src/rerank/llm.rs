@@ -0,0 +1,215 @@
+//! LLM-as-judge reranker.
+//!
+//! Asks an `ecl_core::LlmProvider` to grade each candidate passage against
+//! the query on a 0–10 scale, then normalizes the grades to 0.0–1.0.
+//! Slower and costlier than a cross-encoder, but needs no extra model and
+//! can follow domain-specific grading instructions.
+//!
+//! # Feature Gate
+//!
+//! This module requires the `rerank-llm` feature.
+
+use super::{RerankCandidate, Reranker};
+use async_trait::async_trait;
+use ecl_core::llm::{CompletionRequest, LlmProvider, Message};
+use fabryk_core::{Error, Result};
+use std::sync::Arc;
+
+const DEFAULT_SYSTEM_PROMPT: &str = "You are a search relevance judge. For each numbered \
+passage, rate how well it answers the query on a scale from 0 (irrelevant) to 10 (perfect \
+answer). Respond with only a JSON array of numbers, one per passage, in passage order.";
+
+/// Reranker that uses an LLM to grade relevance.
+///
+/// Candidates are sent in groups of `batch_size` passages per completion
+/// request; each passage is truncated to `max_passage_chars`.
+pub struct LlmReranker {
+    provider: Arc<dyn LlmProvider>,
+    system_prompt: String,
+    batch_size: usize,
+    max_passage_chars: usize,
+}
+
+impl LlmReranker {
+    /// Create a reranker around an LLM provider.
+    pub fn new(provider: Arc<dyn LlmProvider>) -> Self {
+        Self {
+            provider,
+            system_prompt: DEFAULT_SYSTEM_PROMPT.to_string(),
+            batch_size: 10,
+            max_passage_chars: 1000,
+        }
+    }
+
+    /// Override the grading instructions.
+    ///
+    /// The prompt must still ask for a JSON array of 0–10 grades.
+    pub fn with_system_prompt(mut self, prompt: impl Into<String>) -> Self {
+        self.system_prompt = prompt.into();
+        self
+    }
+
+    /// Set the number of passages graded per request.
+    pub fn with_batch_size(mut self, size: usize) -> Self {
+        self.batch_size = size.max(1);
+        self
+    }
+
+    /// Set the maximum characters of each passage sent to the LLM.
+    pub fn with_max_passage_chars(mut self, chars: usize) -> Self {
+        self.max_passage_chars = chars;
+        self
+    }
+
+    fn build_prompt(&self, query: &str, candidates: &[RerankCandidate]) -> String {
+        let mut prompt = format!("Query: {query}\n\nPassages:\n");
+        for (i, candidate) in candidates.iter().enumerate() {
+            let passage: String = candidate
+                .text
+                .chars()
+                .take(self.max_passage_chars)
+                .collect();
+            prompt.push_str(&format!("\n[{}] {}\n", i + 1, passage));
+        }
+        prompt.push_str(&format!(
+            "\nReturn a JSON array of {} grades.",
+            candidates.len()
+        ));
+        prompt
+    }
+}
+
+/// Parse a JSON array of grades out of an LLM response.
+///
+/// Tolerates surrounding prose or code fences by extracting the outermost
+/// `[...]`.
+fn parse_grades(content: &str, expected: usize) -> Result<Vec<f32>> {
+    let start = content.find('[');
+    let end = content.rfind(']');
+    let json = match (start, end) {
+        (Some(s), Some(e)) if s < e => &content[s..=e],
+        _ => {
+            return Err(Error::parse(format!(
+                "LLM reranker response contains no JSON array: {content}"
+            )));
+        }
+    };
+
+    let grades: Vec<f32> = serde_json::from_str(json)
+        .map_err(|e| Error::parse(format!("Invalid LLM reranker grades: {e}")))?;
+    if grades.len() != expected {
+        return Err(Error::parse(format!(
+            "LLM reranker returned {} grades for {expected} passages",
+            grades.len()
+        )));
+    }
+
+    Ok(grades
+        .into_iter()
+        .map(|g| (g / 10.0).clamp(0.0, 1.0))
+        .collect())
+}
+
+#[async_trait]
+impl Reranker for LlmReranker {
+    async fn score(&self, query: &str, candidates: &[RerankCandidate]) -> Result<Vec<f32>> {
+        let mut scores = Vec::with_capacity(candidates.len());
+
+        for chunk in candidates.chunks(self.batch_size) {
+            let request =
+                CompletionRequest::new(vec![Message::user(self.build_prompt(query, chunk))])
+                    .with_system_prompt(&self.system_prompt)
+                    .with_temperature(0.0)
+                    .with_max_tokens(256);
+
+            let response = self
+                .provider
+                .complete(request)
+                .await
+                .map_err(|e| Error::operation(format!("LLM reranking failed: {e}")))?;
+
+            scores.extend(parse_grades(&response.content, chunk.len())?);
+        }
+
+        Ok(scores)
+    }
+
+    fn name(&self) -> &str {
+        "llm"
+    }
+}
+
+impl std::fmt::Debug for LlmReranker {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LlmReranker")
+            .field("batch_size", &self.batch_size)
+            .field("max_passage_chars", &self.max_passage_chars)
+            .finish()
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ecl_core::llm::MockLlmProvider;
+
+    fn candidates(n: usize) -> Vec<RerankCandidate> {
+        (0..n)
+            .map(|i| RerankCandidate::new(format!("doc-{i}"), format!("passage {i}")))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_llm_reranker_normalizes_grades() {
+        let provider = Arc::new(MockLlmProvider::with_response("[10, 5, 0]"));
+        let reranker = LlmReranker::new(provider);
+
+        let scores = reranker.score("q", &candidates(3)).await.unwrap();
+        assert_eq!(scores, vec![1.0, 0.5, 0.0]);
+    }
+
+    #[tokio::test]
+    async fn test_llm_reranker_batches_requests() {
+        let provider = Arc::new(MockLlmProvider::new(vec![
+            "[8, 6]".to_string(),
+            "Here you go: [2]".to_string(),
+        ]));
+        let reranker = LlmReranker::new(provider).with_batch_size(2);
+
+        let scores = reranker.score("q", &candidates(3)).await.unwrap();
+        assert_eq!(scores, vec![0.8, 0.6, 0.2]);
+    }
+
+    #[tokio::test]
+    async fn test_llm_reranker_wrong_count_is_error() {
+        let provider = Arc::new(MockLlmProvider::with_response("[1]"));
+        let reranker = LlmReranker::new(provider);
+
+        let err = reranker.score("q", &candidates(2)).await.unwrap_err();
+        assert!(err.to_string().contains("1 grades for 2 passages"));
+    }
+
+    #[test]
+    fn test_parse_grades_code_fence_and_clamp() {
+        let grades = parse_grades("```json\n[12, -1]\n```", 2).unwrap();
+        assert_eq!(grades, vec![1.0, 0.0]);
+    }
+
+    #[test]
+    fn test_parse_grades_no_array() {
+        assert!(parse_grades("no idea", 1).is_err());
+    }
+
+    #[test]
+    fn test_build_prompt_truncates_passages() {
+        let provider = Arc::new(MockLlmProvider::with_response("[]"));
+        let reranker = LlmReranker::new(provider).with_max_passage_chars(3);
+        let prompt = reranker.build_prompt("query", &[RerankCandidate::new("a", "abcdef")]);
+        assert!(prompt.contains("[1] abc\n"));
+        assert!(!prompt.contains("abcd"));
+    }
+}
@@ -0,0 +1,281 @@
+//! Relevance reranking for hybrid search results.
+//!
+//! Reciprocal Rank Fusion only looks at ranks, never at the text. A
+//! reranker re-scores the top candidates against the query with a model
+//! that reads both, then reorders them.
+//!
+//! # Rerankers
+//!
+//! - `MockReranker`: Query-term overlap, deterministic (always available)
+//! - `CrossEncoderReranker`: Local cross-encoder via fastembed
+//!   (requires `rerank-cross-encoder` feature)
+//! - `HttpReranker`: Cohere-compatible or TEI `/rerank` API
+//!   (requires `vector-remote` feature)
+//! - `LlmReranker`: LLM-as-judge via `ecl_core::LlmProvider`
+//!   (requires `rerank-llm` feature)
+
+use async_trait::async_trait;
+use fabryk_core::{Error, Result};
+use std::collections::HashSet;
+
+use crate::hybrid::HybridSearchResult;
+
+#[cfg(feature = "rerank-cross-encoder")]
+mod cross_encoder;
+#[cfg(feature = "vector-remote")]
+mod http;
+#[cfg(feature = "rerank-llm")]
+mod llm;
+
+#[cfg(feature = "rerank-cross-encoder")]
+pub use cross_encoder::CrossEncoderReranker;
+#[cfg(feature = "vector-remote")]
+pub use http::{HttpReranker, RerankApi};
+#[cfg(feature = "rerank-llm")]
+pub use llm::LlmReranker;
+
+/// A candidate document to be scored by a reranker.
+#[derive(Debug, Clone)]
+pub struct RerankCandidate {
+    /// Document identifier.
+    pub id: String,
+    /// Text the reranker compares against the query.
+    pub text: String,
+}
+
+impl RerankCandidate {
+    /// Create a new candidate.
+    pub fn new(id: impl Into<String>, text: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            text: text.into(),
+        }
+    }
+}
+
+/// Trait for scoring query–document relevance.
+///
+/// Implementations return one score per candidate, in candidate order.
+/// Higher is more relevant; the scale is implementation-defined but must
+/// be consistent within a single call.
+#[async_trait]
+pub trait Reranker: Send + Sync {
+    /// Score each candidate's relevance to the query.
+    async fn score(&self, query: &str, candidates: &[RerankCandidate]) -> Result<Vec<f32>>;
+
+    /// The reranker name for diagnostics.
+    fn name(&self) -> &str;
+}
+
+/// Rerank the first `top_k` hybrid results.
+///
+/// The top `top_k` results are scored by the reranker, which sets their
+/// `rerank_score`, and reordered by it. Results beyond `top_k` keep their
+/// fused order and follow the reranked block. `text_of` supplies the text
+/// for each result.
+pub async fn rerank_results<F>(
+    reranker: &dyn Reranker,
+    query: &str,
+    mut results: Vec<HybridSearchResult>,
+    top_k: usize,
+    text_of: F,
+) -> Result<Vec<HybridSearchResult>>
+where
+    F: Fn(&HybridSearchResult) -> String,
+{
+    let top_k = top_k.min(results.len());
+    if top_k == 0 {
+        return Ok(results);
+    }
+
+    let tail = results.split_off(top_k);
+    let candidates: Vec<RerankCandidate> = results
+        .iter()
+        .map(|r| RerankCandidate::new(&r.id, text_of(r)))
+        .collect();
+
+    let scores = reranker.score(query, &candidates).await?;
+    if scores.len() != candidates.len() {
+        return Err(Error::operation(format!(
+            "Reranker '{}' returned {} scores for {} candidates",
+            reranker.name(),
+            scores.len(),
+            candidates.len()
+        )));
+    }
+
+    for (result, score) in results.iter_mut().zip(scores) {
+        result.rerank_score = Some(score);
+    }
+    results.sort_by(|a, b| {
+        b.rerank_score
+            .partial_cmp(&a.rerank_score)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+
+    results.extend(tail);
+    Ok(results)
+}
+
+// ============================================================================
+// MockReranker
+// ============================================================================
+
+/// A deterministic reranker for testing.
+///
+/// Scores each candidate by the fraction of distinct query terms that
+/// appear in its text (case-insensitive).
+#[derive(Debug, Default, Clone)]
+pub struct MockReranker;
+
+impl MockReranker {
+    /// Create a new mock reranker.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+fn terms(text: &str) -> HashSet<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|t| !t.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+#[async_trait]
+impl Reranker for MockReranker {
+    async fn score(&self, query: &str, candidates: &[RerankCandidate]) -> Result<Vec<f32>> {
+        let query_terms = terms(query);
+        if query_terms.is_empty() {
+            return Ok(vec![0.0; candidates.len()]);
+        }
+
+        Ok(candidates
+            .iter()
+            .map(|c| {
+                let doc_terms = terms(&c.text);
+                let overlap = query_terms.intersection(&doc_terms).count();
+                overlap as f32 / query_terms.len() as f32
+            })
+            .collect())
+    }
+
+    fn name(&self) -> &str {
+        "mock"
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn hybrid(id: &str, score: f32) -> HybridSearchResult {
+        HybridSearchResult {
+            id: id.to_string(),
+            score,
+            source: "hybrid".to_string(),
+            vector_score: None,
+            keyword_score: None,
+            rerank_score: None,
+            metadata: HashMap::new(),
+        }
+    }
+
+    fn texts() -> HashMap<&'static str, &'static str> {
+        HashMap::from([
+            ("a", "cadence resolution"),
+            ("b", "voice leading in chorales"),
+            ("c", "voice leading and cadence"),
+            ("d", "counterpoint"),
+        ])
+    }
+
+    #[tokio::test]
+    async fn test_mock_reranker_term_overlap() {
+        let reranker = MockReranker::new();
+        let candidates = vec![
+            RerankCandidate::new("1", "Voice leading rules"),
+            RerankCandidate::new("2", "Cadences"),
+        ];
+        let scores = reranker.score("voice leading", &candidates).await.unwrap();
+        assert_eq!(scores, vec![1.0, 0.0]);
+    }
+
+    #[tokio::test]
+    async fn test_mock_reranker_empty_query() {
+        let reranker = MockReranker::new();
+        let candidates = vec![RerankCandidate::new("1", "anything")];
+        let scores = reranker.score("  ", &candidates).await.unwrap();
+        assert_eq!(scores, vec![0.0]);
+    }
+
+    #[tokio::test]
+    async fn test_rerank_results_reorders_top_k_only() {
+        let texts = texts();
+        let results = vec![
+            hybrid("a", 0.04),
+            hybrid("b", 0.03),
+            hybrid("c", 0.02),
+            hybrid("d", 0.01),
+        ];
+
+        let reranked = rerank_results(&MockReranker, "voice leading", results, 3, |r| {
+            texts[r.id.as_str()].to_string()
+        })
+        .await
+        .unwrap();
+
+        let ids: Vec<&str> = reranked.iter().map(|r| r.id.as_str()).collect();
+        assert_eq!(ids, vec!["b", "c", "a", "d"]);
+        assert_eq!(reranked[0].rerank_score, Some(1.0));
+        assert_eq!(reranked[2].rerank_score, Some(0.0));
+        // Beyond top_k: untouched
+        assert!(reranked[3].rerank_score.is_none());
+        // Fused score preserved
+        assert_eq!(reranked[0].score, 0.03);
+    }
+
+    #[tokio::test]
+    async fn test_rerank_results_zero_top_k_is_noop() {
+        let results = vec![hybrid("a", 0.5)];
+        let reranked = rerank_results(&MockReranker, "q", results, 0, |_| String::new())
+            .await
+            .unwrap();
+        assert!(reranked[0].rerank_score.is_none());
+    }
+
+    struct BrokenReranker;
+
+    #[async_trait]
+    impl Reranker for BrokenReranker {
+        async fn score(&self, _query: &str, _candidates: &[RerankCandidate]) -> Result<Vec<f32>> {
+            Ok(vec![1.0])
+        }
+
+        fn name(&self) -> &str {
+            "broken"
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rerank_results_score_count_mismatch() {
+        let results = vec![hybrid("a", 0.5), hybrid("b", 0.4)];
+        let err = rerank_results(&BrokenReranker, "q", results, 2, |_| String::new())
+            .await
+            .unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("returned 1 scores for 2 candidates")
+        );
+    }
+
+    #[test]
+    fn test_trait_object_safety() {
+        fn _assert_object_safe(_: &dyn Reranker) {}
+    }
+}
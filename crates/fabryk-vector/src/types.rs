@@ -20,7 +20,7 @@ pub struct VectorConfig {
     #[serde(default = "default_backend")]
     pub backend: String,
 
-    /// Embedding provider: "fastembed" or "mock".
+    /// Embedding provider: "fastembed", "openai", "ollama", "vertex" or "mock".
     #[serde(default = "default_provider")]
     pub provider: String,
 
@@ -56,6 +56,47 @@ pub struct VectorConfig {
     /// Batch size for embedding operations.
     #[serde(default = "default_batch_size")]
     pub batch_size: usize,
+
+    /// Base URL for remote embedding providers.
+    pub base_url: Option<String>,
+
+    /// Environment variable holding the remote provider's API key.
+    pub api_key_env: Option<String>,
+
+    /// Client-side rate limit for remote providers (requests per second).
+    pub requests_per_second: Option<f64>,
+
+    /// Maximum retries for failed remote embedding requests.
+    #[serde(default = "default_max_retries")]
+    pub max_retries: u32,
+
+    /// Path to a persistent embedding cache file.
+    ///
+    /// When set, embeddings are cached by `(model, blake3(text))` so
+    /// unchanged documents are not re-embedded on rebuild. Pass it to
+    /// `VectorIndexBuilder::with_embedding_cache`, which saves new
+    /// embeddings at the end of each build.
+    pub embedding_cache_path: Option<String>,
+
+    /// Index type for the simple backend: "flat" (brute force) or "hnsw".
+    #[serde(default = "default_index_type")]
+    pub index_type: String,
+
+    /// HNSW links per node. Higher improves recall at the cost of memory.
+    #[serde(default = "default_hnsw_m")]
+    pub hnsw_m: usize,
+
+    /// HNSW candidate list size while inserting. Higher builds a better graph, slower.
+    #[serde(default = "default_hnsw_ef_construction")]
+    pub hnsw_ef_construction: usize,
+
+    /// HNSW candidate list size while searching. Higher improves recall, slower.
+    #[serde(default = "default_hnsw_ef_search")]
+    pub hnsw_ef_search: usize,
+
+    /// Search exactly when at most this many documents are (or match the filters).
+    #[serde(default = "default_hnsw_exact_search_threshold")]
+    pub hnsw_exact_search_threshold: usize,
 }
 
 fn default_backend() -> String {
@@ -86,6 +127,30 @@ fn default_batch_size() -> usize {
     64
 }
 
+fn default_max_retries() -> u32 {
+    3
+}
+
+fn default_index_type() -> String {
+    "flat".to_string()
+}
+
+fn default_hnsw_m() -> usize {
+    16
+}
+
+fn default_hnsw_ef_construction() -> usize {
+    200
+}
+
+fn default_hnsw_ef_search() -> usize {
+    64
+}
+
+fn default_hnsw_exact_search_threshold() -> usize {
+    1000
+}
+
 impl Default for VectorConfig {
     fn default() -> Self {
         Self {
@@ -100,6 +165,16 @@ impl Default for VectorConfig {
             default_limit: default_limit(),
             similarity_threshold: default_threshold(),
             batch_size: default_batch_size(),
+            base_url: None,
+            api_key_env: None,
+            requests_per_second: None,
+            max_retries: default_max_retries(),
+            embedding_cache_path: None,
+            index_type: default_index_type(),
+            hnsw_m: default_hnsw_m(),
+            hnsw_ef_construction: default_hnsw_ef_construction(),
+            hnsw_ef_search: default_hnsw_ef_search(),
+            hnsw_exact_search_threshold: default_hnsw_exact_search_threshold(),
         }
     }
 }
@@ -203,6 +278,12 @@ pub struct VectorSearchParams {
     /// Metadata filters as key-value pairs.
     #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub metadata_filters: HashMap<String, String>,
+
+    /// HNSW search candidate list size, overriding the index default.
+    ///
+    /// Ignored by backends without an HNSW index.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ef_search: Option<usize>,
 }
 
 impl VectorSearchParams {
@@ -237,6 +318,12 @@ impl VectorSearchParams {
         self.metadata_filters.insert(key.into(), value.into());
         self
     }
+
+    /// Set the HNSW search candidate list size for this query.
+    pub fn with_ef_search(mut self, ef: usize) -> Self {
+        self.ef_search = Some(ef);
+        self
+    }
 }
 
 /// A single vector search result.
@@ -312,6 +399,10 @@ pub struct VectorIndexStats {
     /// Whether the result was loaded from cache.
     #[serde(default)]
     pub from_cache: bool,
+
+    /// Number of embeddings served from the embedding cache.
+    #[serde(default)]
+    pub embeddings_cached: usize,
 }
 
 /// An error that occurred during vector index building.
@@ -349,6 +440,12 @@ mod tests {
         assert_eq!(config.default_limit, 10);
         assert_eq!(config.similarity_threshold, 0.0);
         assert_eq!(config.batch_size, 64);
+        assert!(config.base_url.is_none());
+        assert_eq!(config.max_retries, 3);
+        assert!(config.embedding_cache_path.is_none());
+        assert_eq!(config.index_type, "flat");
+        assert_eq!(config.hnsw_m, 16);
+        assert_eq!(config.hnsw_ef_search, 64);
     }
 
     #[test]
@@ -459,6 +556,7 @@ mod tests {
         assert!(params.similarity_threshold.is_none());
         assert!(params.category.is_none());
         assert!(params.metadata_filters.is_empty());
+        assert!(params.ef_search.is_none());
     }
 
     #[test]
@@ -467,13 +565,15 @@ mod tests {
             .with_limit(5)
             .with_threshold(0.5)
             .with_category("harmony")
-            .with_filter("tier", "advanced");
+            .with_filter("tier", "advanced")
+            .with_ef_search(128);
 
         assert_eq!(params.query, "semantic query");
         assert_eq!(params.limit, Some(5));
         assert_eq!(params.similarity_threshold, Some(0.5));
         assert_eq!(params.category, Some("harmony".to_string()));
         assert_eq!(params.metadata_filters.get("tier").unwrap(), "advanced");
+        assert_eq!(params.ef_search, Some(128));
     }
 
     #[test]
@@ -549,6 +649,7 @@ mod tests {
             build_duration_ms: 1500,
             errors: vec![],
             from_cache: false,
+            embeddings_cached: 0,
         };
 
         let json = serde_json::to_string(&stats).unwrap();
@@ -574,6 +675,7 @@ mod tests {
                 message: "parse error".to_string(),
             }],
             from_cache: false,
+            embeddings_cached: 0,
         };
 
         let json = serde_json::to_string(&stats).unwrap();